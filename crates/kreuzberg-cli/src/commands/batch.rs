@@ -0,0 +1,631 @@
+//! Directory ingestion for the batch command.
+//!
+//! Expands directories into the files below them (filtered by globs and MIME type) and,
+//! with `--output-dir`, writes one result file per input into a tree mirroring the
+//! sources. A manifest in the output directory records the content hash and outcome of
+//! every input, so re-runs skip unchanged files and interrupted runs resume where they
+//! stopped. Failures are collected per file instead of aborting the run.
+
+use anyhow::{Context, Result};
+use glob::{MatchOptions, Pattern};
+use kreuzberg::{ExtractionConfig, ExtractionResult, OutputFormat as ContentOutputFormat, batch_extract_file_sync};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+use walkdir::WalkDir;
+
+use crate::OutputFormat;
+
+/// Name of the manifest file written into the output directory.
+pub const MANIFEST_FILE: &str = ".kreuzberg-manifest.json";
+
+/// Files extracted per `batch_extract_file_sync` call; the manifest is saved after each.
+const CHUNK_SIZE: usize = 32;
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: false,
+    require_literal_leading_dot: false,
+};
+
+/// How batch inputs are discovered and where results go.
+#[derive(Debug, Default)]
+pub struct BatchOptions {
+    /// Recurse into directories given as inputs
+    pub recursive: bool,
+    /// Globs a file's path (relative to its input directory) must match, if any
+    pub include: Vec<String>,
+    /// Globs excluding files and directories
+    pub exclude: Vec<String>,
+    /// Accepted MIME types; `type/*` matches a whole top-level type
+    pub mime_types: Vec<String>,
+    /// Directory receiving one result file per input
+    pub output_dir: Option<PathBuf>,
+    /// Re-extract files even if the manifest says they are unchanged
+    pub force: bool,
+}
+
+/// A file to extract together with its output path relative to the output directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchInput {
+    pub path: PathBuf,
+    pub relative: PathBuf,
+}
+
+/// Outcome of one input, as stored in the manifest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntryStatus {
+    Ok,
+    Failed,
+}
+
+/// Manifest record for one input, keyed by its relative path.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub source: PathBuf,
+    pub sha256: String,
+    pub status: EntryStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Record of a previous run, used to skip unchanged inputs.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Manifest {
+    /// Hash of the extraction config and output format; a change invalidates all entries
+    pub settings_hash: String,
+    pub entries: BTreeMap<String, ManifestEntry>,
+}
+
+impl Manifest {
+    /// Hash identifying the extraction config and output extension a manifest was written with.
+    pub fn settings_hash(config: &ExtractionConfig, extension: &str) -> Result<String> {
+        let settings = serde_json::to_vec(&(config, extension)).context("Failed to serialize extraction config")?;
+        Ok(format!("{:x}", Sha256::digest(&settings)))
+    }
+
+    /// Load the manifest at `path`, starting empty if it is missing or was written with other settings.
+    pub fn load(path: &Path, settings_hash: &str) -> Result<Self> {
+        let manifest = match fs::read(path) {
+            Ok(bytes) => serde_json::from_slice::<Manifest>(&bytes)
+                .with_context(|| format!("Failed to parse manifest '{}'", path.display()))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Manifest::default(),
+            Err(e) => return Err(e).with_context(|| format!("Failed to read manifest '{}'", path.display())),
+        };
+
+        if manifest.settings_hash == settings_hash {
+            Ok(manifest)
+        } else {
+            Ok(Manifest {
+                settings_hash: settings_hash.to_string(),
+                entries: BTreeMap::new(),
+            })
+        }
+    }
+
+    /// Write the manifest via a temporary file so an interrupted run never leaves it truncated.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let tmp = path.with_extension("json.tmp");
+        let bytes = serde_json::to_vec_pretty(self).context("Failed to serialize manifest")?;
+        fs::write(&tmp, bytes).with_context(|| format!("Failed to write manifest '{}'", tmp.display()))?;
+        fs::rename(&tmp, path).with_context(|| format!("Failed to replace manifest '{}'", path.display()))
+    }
+
+    /// Whether `key` was extracted successfully from the same content and its output still exists.
+    pub fn is_unchanged(&self, key: &str, sha256: &str, output_dir: &Path) -> bool {
+        self.entries.get(key).is_some_and(|entry| {
+            entry.status == EntryStatus::Ok
+                && entry.sha256 == sha256
+                && entry
+                    .output
+                    .as_ref()
+                    .is_some_and(|output| output_dir.join(output).is_file())
+        })
+    }
+}
+
+/// Expand `paths` into the files to extract.
+///
+/// Plain files are always included. Directories require `recursive`; the files below them
+/// are filtered by the include/exclude globs (matched against the path relative to the
+/// directory) and by MIME type, and files whose type cannot be detected are skipped. An
+/// output directory below an input directory is skipped too, so earlier results and the
+/// manifest are not extracted again.
+pub fn collect_inputs(paths: &[PathBuf], options: &BatchOptions) -> Result<Vec<BatchInput>> {
+    let filter = InputFilter::new(&options.include, &options.exclude, &options.mime_types)?;
+    let output_dir = options.output_dir.as_deref().and_then(|dir| dir.canonicalize().ok());
+    let directory_count = paths.iter().filter(|path| path.is_dir()).count();
+
+    let mut inputs = Vec::new();
+    for path in paths {
+        if !path.is_dir() {
+            let name = path.file_name().map(PathBuf::from).unwrap_or_else(|| path.clone());
+            inputs.push(BatchInput {
+                path: path.clone(),
+                relative: name,
+            });
+            continue;
+        }
+
+        if !options.recursive {
+            anyhow::bail!(
+                "'{}' is a directory. Use --recursive to extract the files below it.",
+                path.display()
+            );
+        }
+
+        // With several input directories, keep them apart in the output tree.
+        let prefix = if directory_count > 1 {
+            path.file_name().map(PathBuf::from).unwrap_or_default()
+        } else {
+            PathBuf::new()
+        };
+
+        let root = path
+            .canonicalize()
+            .with_context(|| format!("Failed to resolve directory '{}'", path.display()))?;
+        if output_dir.as_ref() == Some(&root) {
+            anyhow::bail!(
+                "The output directory '{}' is also an input directory. Choose a separate --output-dir.",
+                path.display()
+            );
+        }
+        let in_output_dir = |entry: &walkdir::DirEntry| {
+            output_dir.as_deref().is_some_and(|dir| {
+                root.join(entry.path().strip_prefix(path).unwrap_or(entry.path()))
+                    .starts_with(dir)
+            })
+        };
+
+        let walker = WalkDir::new(path).follow_links(false).sort_by_file_name();
+        let entries = walker.into_iter().filter_entry(|entry| {
+            entry.depth() == 0 || !(filter.is_excluded(&relative_str(entry.path(), path)) || in_output_dir(entry))
+        });
+        for entry in entries {
+            let entry = entry.with_context(|| format!("Failed to read directory '{}'", path.display()))?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+
+            if !filter.accepts_file(&relative_str(entry.path(), path), entry.path()) {
+                continue;
+            }
+
+            inputs.push(BatchInput {
+                path: entry.path().to_path_buf(),
+                relative: prefix.join(entry.path().strip_prefix(path).unwrap_or(entry.path())),
+            });
+        }
+    }
+
+    let mut seen: HashMap<&Path, &Path> = HashMap::new();
+    for input in &inputs {
+        if let Some(previous) = seen.insert(&input.relative, &input.path) {
+            anyhow::bail!(
+                "'{}' and '{}' would both be written to '{}'. Pass them in separate runs.",
+                previous.display(),
+                input.path.display(),
+                input.relative.display()
+            );
+        }
+    }
+
+    Ok(inputs)
+}
+
+/// Include/exclude globs and MIME types deciding which files below a directory are extracted.
+#[derive(Debug, Default)]
+pub struct InputFilter {
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+    mime_types: Vec<String>,
+}
+
+impl InputFilter {
+    pub fn new(include: &[String], exclude: &[String], mime_types: &[String]) -> Result<Self> {
+        Ok(Self {
+            include: compile_globs(include, "--include")?,
+            exclude: compile_globs(exclude, "--exclude")?,
+            mime_types: mime_types.to_vec(),
+        })
+    }
+
+    /// Whether a file or directory at `relative` matches an exclude glob.
+    pub fn is_excluded(&self, relative: &str) -> bool {
+        matches_any(&self.exclude, relative)
+    }
+
+    /// Whether the file at `relative` passes the include globs and MIME filter. Excluded
+    /// ancestor directories are not checked; walkers prune those.
+    pub fn accepts_file(&self, relative: &str, path: &Path) -> bool {
+        !self.is_excluded(relative)
+            && (self.include.is_empty() || matches_any(&self.include, relative))
+            && mime_allowed(path, &self.mime_types)
+    }
+
+    /// Like [`accepts_file`](Self::accepts_file), but also rejects files below an excluded
+    /// directory, for paths that did not come from a pruned walk.
+    pub fn accepts_path(&self, relative: &str, path: &Path) -> bool {
+        let mut ancestors = relative.match_indices('/').map(|(end, _)| &relative[..end]);
+        !ancestors.any(|ancestor| self.is_excluded(ancestor)) && self.accepts_file(relative, path)
+    }
+}
+
+fn compile_globs(globs: &[String], flag: &str) -> Result<Vec<Pattern>> {
+    globs
+        .iter()
+        .map(|glob| Pattern::new(glob).with_context(|| format!("Invalid {} pattern '{}'", flag, glob)))
+        .collect()
+}
+
+fn matches_any(patterns: &[Pattern], relative: &str) -> bool {
+    patterns
+        .iter()
+        .any(|pattern| pattern.matches_with(relative, MATCH_OPTIONS))
+}
+
+/// `path` relative to `root`, with `/` separators on every platform.
+pub fn relative_str(path: &Path, root: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Whether the detected type of `path` is in `allowed`; an empty list accepts any detectable type.
+fn mime_allowed(path: &Path, allowed: &[String]) -> bool {
+    let Ok(mime_type) = kreuzberg::detect_mime_type(path, false) else {
+        return false;
+    };
+    allowed.is_empty()
+        || allowed.iter().any(|pattern| match pattern.strip_suffix("/*") {
+            Some(top_level) => mime_type.split('/').next() == Some(top_level),
+            None => pattern.eq_ignore_ascii_case(&mime_type),
+        })
+}
+
+/// Extension of the per-input output file.
+pub fn output_extension(format: OutputFormat, content_format: ContentOutputFormat) -> &'static str {
+    match (format, content_format) {
+        (OutputFormat::Json, _) => "json",
+        (OutputFormat::Text, ContentOutputFormat::Markdown) => "md",
+        (OutputFormat::Text, ContentOutputFormat::Html) => "html",
+        (OutputFormat::Text, ContentOutputFormat::Djot) => "djot",
+        (OutputFormat::Text, _) => "txt",
+    }
+}
+
+/// Output path for `relative`: the source path with the output extension appended, so
+/// `report.pdf` and `report.docx` in the same directory do not collide.
+pub fn output_path(relative: &Path, extension: &str) -> PathBuf {
+    let mut name = relative.as_os_str().to_os_string();
+    name.push(".");
+    name.push(extension);
+    PathBuf::from(name)
+}
+
+pub fn sha256_file(path: &Path) -> Result<String> {
+    let mut file = fs::File::open(path).with_context(|| format!("Failed to open '{}'", path.display()))?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher).with_context(|| format!("Failed to read '{}'", path.display()))?;
+    Ok(hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+/// Per-run counts and failures, printed when the run ends.
+#[derive(Debug, Default, Serialize)]
+pub struct BatchReport {
+    pub total: usize,
+    pub extracted: usize,
+    pub unchanged: usize,
+    pub failed: usize,
+    pub errors: Vec<FileError>,
+    pub manifest: PathBuf,
+}
+
+/// One failed input.
+#[derive(Debug, Serialize)]
+pub struct FileError {
+    pub path: PathBuf,
+    pub error: String,
+}
+
+/// Extract `inputs` into `output_dir`, skipping inputs unchanged since the last run.
+pub fn ingest_to_directory(
+    inputs: &[BatchInput],
+    output_dir: &Path,
+    config: &ExtractionConfig,
+    format: OutputFormat,
+    force: bool,
+) -> Result<BatchReport> {
+    fs::create_dir_all(output_dir)
+        .with_context(|| format!("Failed to create output directory '{}'", output_dir.display()))?;
+
+    let extension = output_extension(format, config.output_format);
+    let settings_hash = Manifest::settings_hash(config, extension)?;
+
+    let manifest_path = output_dir.join(MANIFEST_FILE);
+    let mut manifest = Manifest::load(&manifest_path, &settings_hash)?;
+    let mut report = BatchReport {
+        total: inputs.len(),
+        manifest: manifest_path.clone(),
+        ..Default::default()
+    };
+
+    let mut pending = Vec::new();
+    for input in inputs {
+        let key = relative_str(&input.relative, Path::new(""));
+        match sha256_file(&input.path) {
+            Ok(sha256) if !force && manifest.is_unchanged(&key, &sha256, output_dir) => report.unchanged += 1,
+            Ok(sha256) => pending.push((input, key, sha256)),
+            Err(e) => record_failure(
+                &mut manifest,
+                &mut report,
+                input,
+                key,
+                String::new(),
+                format!("{:#}", e),
+            ),
+        }
+    }
+
+    let pending_count = pending.len();
+    for (chunk_index, chunk) in pending.chunks(CHUNK_SIZE).enumerate() {
+        let paths: Vec<String> = chunk
+            .iter()
+            .map(|(input, _, _)| input.path.to_string_lossy().into_owned())
+            .collect();
+        let results = batch_extract_file_sync(paths, config).context("Batch extraction failed")?;
+
+        for ((input, key, sha256), result) in chunk.iter().zip(results) {
+            if let Some(error) = &result.metadata.error {
+                record_failure(
+                    &mut manifest,
+                    &mut report,
+                    input,
+                    key.clone(),
+                    sha256.clone(),
+                    error.message.clone(),
+                );
+                continue;
+            }
+
+            let output = output_path(&input.relative, extension);
+            match write_output(&output_dir.join(&output), &result, format) {
+                Ok(()) => {
+                    report.extracted += 1;
+                    manifest.entries.insert(
+                        key.clone(),
+                        ManifestEntry {
+                            source: input.path.clone(),
+                            sha256: sha256.clone(),
+                            status: EntryStatus::Ok,
+                            output: Some(output),
+                            mime_type: Some(result.mime_type.to_string()),
+                            error: None,
+                        },
+                    );
+                }
+                Err(e) => record_failure(
+                    &mut manifest,
+                    &mut report,
+                    input,
+                    key.clone(),
+                    sha256.clone(),
+                    format!("{:#}", e),
+                ),
+            }
+        }
+
+        manifest.save(&manifest_path)?;
+        let done = ((chunk_index + 1) * CHUNK_SIZE).min(pending_count);
+        eprintln!("[{}/{}] extracted", done, pending_count);
+    }
+
+    manifest.save(&manifest_path)?;
+    Ok(report)
+}
+
+fn record_failure(
+    manifest: &mut Manifest,
+    report: &mut BatchReport,
+    input: &BatchInput,
+    key: String,
+    sha256: String,
+    error: String,
+) {
+    report.failed += 1;
+    report.errors.push(FileError {
+        path: input.path.clone(),
+        error: error.clone(),
+    });
+    manifest.entries.insert(
+        key,
+        ManifestEntry {
+            source: input.path.clone(),
+            sha256,
+            status: EntryStatus::Failed,
+            output: None,
+            mime_type: None,
+            error: Some(error),
+        },
+    );
+}
+
+/// Write `result` to `path` as JSON or as its extracted content, creating parent directories.
+pub fn write_output(path: &Path, result: &ExtractionResult, format: OutputFormat) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).with_context(|| format!("Failed to create directory '{}'", parent.display()))?;
+    }
+    let mut file = fs::File::create(path).with_context(|| format!("Failed to create '{}'", path.display()))?;
+    match format {
+        OutputFormat::Json => serde_json::to_writer_pretty(&mut file, result)
+            .with_context(|| format!("Failed to write '{}'", path.display()))?,
+        OutputFormat::Text => file
+            .write_all(result.content.as_bytes())
+            .with_context(|| format!("Failed to write '{}'", path.display()))?,
+    }
+    Ok(())
+}
+
+/// Print the run summary and per-file errors.
+pub fn print_report(report: &BatchReport, format: OutputFormat) -> Result<()> {
+    match format {
+        OutputFormat::Text => {
+            println!(
+                "{} files: {} extracted, {} unchanged, {} failed",
+                report.total, report.extracted, report.unchanged, report.failed
+            );
+            for error in &report.errors {
+                println!("FAILED {}: {}", error.path.display(), error.error);
+            }
+            println!("Manifest: {}", report.manifest.display());
+        }
+        OutputFormat::Json => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(report).context("Failed to serialize batch report to JSON")?
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn write(root: &Path, relative: &str, content: &str) {
+        let path = root.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn relatives(inputs: &[BatchInput]) -> Vec<String> {
+        inputs
+            .iter()
+            .map(|input| relative_str(&input.relative, Path::new("")))
+            .collect()
+    }
+
+    #[test]
+    fn test_collect_inputs_walks_and_filters() {
+        let dir = tempdir().unwrap();
+        write(dir.path(), "a.txt", "a");
+        write(dir.path(), "nested/b.txt", "b");
+        write(dir.path(), "nested/c.html", "<p>c</p>");
+        write(dir.path(), "skip/d.txt", "d");
+        write(dir.path(), "unknown.zzz-unknown", "?");
+
+        let options = BatchOptions {
+            recursive: true,
+            exclude: vec!["skip".to_string()],
+            ..Default::default()
+        };
+        let inputs = collect_inputs(&[dir.path().to_path_buf()], &options).unwrap();
+        assert_eq!(relatives(&inputs), vec!["a.txt", "nested/b.txt", "nested/c.html"]);
+
+        let options = BatchOptions {
+            recursive: true,
+            include: vec!["nested/*".to_string()],
+            mime_types: vec!["text/plain".to_string()],
+            ..Default::default()
+        };
+        let inputs = collect_inputs(&[dir.path().to_path_buf()], &options).unwrap();
+        assert_eq!(relatives(&inputs), vec!["nested/b.txt"]);
+    }
+
+    #[test]
+    fn test_collect_inputs_skips_output_dir_below_input() {
+        let dir = tempdir().unwrap();
+        write(dir.path(), "a.txt", "a");
+        write(dir.path(), "out/a.txt.txt", "a");
+        write(dir.path(), &format!("out/{}", MANIFEST_FILE), "{}");
+
+        let options = BatchOptions {
+            recursive: true,
+            output_dir: Some(dir.path().join("out")),
+            ..Default::default()
+        };
+        let inputs = collect_inputs(&[dir.path().to_path_buf()], &options).unwrap();
+        assert_eq!(relatives(&inputs), vec!["a.txt"]);
+
+        let options = BatchOptions {
+            recursive: true,
+            output_dir: Some(dir.path().to_path_buf()),
+            ..Default::default()
+        };
+        assert!(collect_inputs(&[dir.path().to_path_buf()], &options).is_err());
+    }
+
+    #[test]
+    fn test_collect_inputs_requires_recursive_for_directories() {
+        let dir = tempdir().unwrap();
+        assert!(collect_inputs(&[dir.path().to_path_buf()], &BatchOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_output_path_appends_extension() {
+        assert_eq!(
+            output_path(Path::new("docs/report.pdf"), "md"),
+            PathBuf::from("docs/report.pdf.md")
+        );
+        assert_eq!(
+            output_extension(OutputFormat::Json, ContentOutputFormat::Markdown),
+            "json"
+        );
+        assert_eq!(output_extension(OutputFormat::Text, ContentOutputFormat::Plain), "txt");
+    }
+
+    #[test]
+    fn test_ingest_skips_unchanged_files() {
+        let source = tempdir().unwrap();
+        let output = tempdir().unwrap();
+        write(source.path(), "one.txt", "first document");
+        write(source.path(), "sub/two.txt", "second document");
+
+        let options = BatchOptions {
+            recursive: true,
+            ..Default::default()
+        };
+        let config = ExtractionConfig {
+            use_cache: false,
+            ..Default::default()
+        };
+        let inputs = collect_inputs(&[source.path().to_path_buf()], &options).unwrap();
+
+        let report = ingest_to_directory(&inputs, output.path(), &config, OutputFormat::Text, false).unwrap();
+        assert_eq!((report.extracted, report.unchanged, report.failed), (2, 0, 0));
+        assert_eq!(
+            fs::read_to_string(output.path().join("sub/two.txt.txt"))
+                .unwrap()
+                .trim(),
+            "second document"
+        );
+
+        write(source.path(), "one.txt", "changed document");
+        let report = ingest_to_directory(&inputs, output.path(), &config, OutputFormat::Text, false).unwrap();
+        assert_eq!((report.extracted, report.unchanged), (1, 1));
+
+        let report = ingest_to_directory(&inputs, output.path(), &config, OutputFormat::Json, false).unwrap();
+        assert_eq!(
+            report.extracted, 2,
+            "changing the output format invalidates the manifest"
+        );
+
+        let manifest: Manifest = serde_json::from_slice(&fs::read(output.path().join(MANIFEST_FILE)).unwrap()).unwrap();
+        assert_eq!(manifest.entries["sub/two.txt"].status, EntryStatus::Ok);
+    }
+}
@@ -14,6 +14,9 @@ use std::path::PathBuf;
 /// 2. Auto-discovered config (searches `kreuzberg.{toml,yaml,json}` in current and parent directories)
 /// 3. Default configuration (if no config file found)
 ///
+/// A `cache_backend` set in the loaded configuration becomes the process-wide default,
+/// so the OCR cache uses it as well.
+///
 /// # Configuration File Formats
 ///
 /// Supports three formats, determined by file extension:
@@ -28,6 +31,14 @@ use std::path::PathBuf;
 /// - Config file cannot be read or parsed
 /// - Config file contains invalid extraction settings
 pub fn load_config(config_path: Option<PathBuf>) -> Result<ExtractionConfig> {
+    let config = read_config(config_path)?;
+    if let Some(backend) = config.cache_backend {
+        kreuzberg::cache::set_default_backend(backend);
+    }
+    Ok(config)
+}
+
+fn read_config(config_path: Option<PathBuf>) -> Result<ExtractionConfig> {
     if let Some(path) = config_path {
         let path_str = path.to_string_lossy();
         let path_lower = path_str.to_lowercase();
@@ -0,0 +1,48 @@
+//! Diff command - Compare two versions of a document
+//!
+//! Extracts both documents, aligns their structure by section and reports inserted,
+//! deleted and modified paragraphs and table cells. The versions may be in different
+//! formats (e.g. a DOCX draft against the final PDF).
+
+use anyhow::{Context, Result};
+use kreuzberg::{ExtractionConfig, diff::diff_files_sync};
+use std::path::PathBuf;
+
+use crate::{DiffStyle, OutputFormat};
+
+/// Execute diff command
+///
+/// Returns whether the documents differ, so callers can map it to an exit code.
+pub fn diff_command(
+    old: PathBuf,
+    new: PathBuf,
+    config: ExtractionConfig,
+    format: OutputFormat,
+    style: DiffStyle,
+) -> Result<bool> {
+    let diff = diff_files_sync(&old, &new, &config).with_context(|| {
+        format!(
+            "Failed to compare '{}' and '{}'. Ensure both files are readable and the formats are supported.",
+            old.display(),
+            new.display()
+        )
+    })?;
+
+    match format {
+        OutputFormat::Text => match style {
+            DiffStyle::Unified => print!(
+                "{}",
+                diff.to_unified(&old.display().to_string(), &new.display().to_string())
+            ),
+            DiffStyle::Markdown => print!("{}", diff.to_markdown()),
+        },
+        OutputFormat::Json => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&diff).context("Failed to serialize document diff to JSON")?
+            );
+        }
+    }
+
+    Ok(!diff.is_empty())
+}
@@ -9,6 +9,7 @@ use kreuzberg::{
 };
 use std::path::PathBuf;
 
+use super::batch::{BatchOptions, collect_inputs, ingest_to_directory, print_report};
 use crate::{ContentOutputFormatArg, OutputFormat};
 
 /// Execute single document extraction command
@@ -44,7 +45,31 @@ pub fn extract_command(
 }
 
 /// Execute batch extraction command
-pub fn batch_command(paths: Vec<PathBuf>, config: ExtractionConfig, format: OutputFormat) -> Result<()> {
+///
+/// Directories are expanded according to `options`. With an output directory, results are
+/// written to files and only a summary with per-file errors is printed; the command fails
+/// if any file failed.
+pub fn batch_command(
+    paths: Vec<PathBuf>,
+    config: ExtractionConfig,
+    format: OutputFormat,
+    options: BatchOptions,
+) -> Result<()> {
+    let inputs = collect_inputs(&paths, &options)?;
+    if inputs.is_empty() {
+        anyhow::bail!("No files matched the given paths and filters.");
+    }
+
+    if let Some(output_dir) = &options.output_dir {
+        let report = ingest_to_directory(&inputs, output_dir, &config, format, options.force)?;
+        print_report(&report, format)?;
+        if report.failed > 0 {
+            anyhow::bail!("{} of {} files failed to extract", report.failed, report.total);
+        }
+        return Ok(());
+    }
+
+    let paths: Vec<PathBuf> = inputs.into_iter().map(|input| input.path).collect();
     let path_strs: Vec<String> = paths.iter().map(|p| p.to_string_lossy().to_string()).collect();
 
     let results = batch_extract_file_sync(path_strs, &config).with_context(|| {
@@ -150,6 +175,7 @@ pub fn apply_extraction_overrides(
                 overlap,
                 trim: true,
                 chunker_type: kreuzberg::chunking::ChunkerType::Text,
+                sizing: kreuzberg::chunking::ChunkSizing::Characters,
                 embedding: None,
                 preset: None,
             });
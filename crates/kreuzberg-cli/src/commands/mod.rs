@@ -2,20 +2,29 @@
 //!
 //! This module organizes the CLI commands into focused submodules:
 //! - `extract` - Document extraction commands
+//! - `batch` - Directory ingestion, output mirroring and the resumable batch manifest
 //! - `cache` - Cache management operations
 //! - `server` - API and MCP server commands
 //! - `config` - Configuration loading and discovery
+//! - `diff` - Section-aware comparison of two document versions
+//! - `watch` - Continuous extraction of a watched directory
 
+pub mod batch;
 pub mod cache;
 pub mod config;
+pub mod diff;
 pub mod extract;
 pub mod server;
+pub mod watch;
 
 // Re-export command functions for convenience
+pub use batch::BatchOptions;
 pub use cache::{clear_command, stats_command};
 pub use config::load_config;
+pub use diff::diff_command;
 pub use extract::{apply_extraction_overrides, batch_command, extract_command};
 #[cfg(feature = "mcp")]
 pub use server::mcp_command;
 #[cfg(feature = "api")]
 pub use server::serve_command;
+pub use watch::{WatchOptions, watch_command};
@@ -0,0 +1,666 @@
+//! Watch command - Continuously extract documents written into a directory
+//!
+//! Changes are picked up from native filesystem notifications (inotify on Linux) when the
+//! `native-watch` feature is enabled, with a polling scanner as the fallback. A changed file
+//! is extracted only once it has been quiet for the debounce period and its size and
+//! modification time stopped changing, so files still being copied or scanned are not read
+//! half-written. Extraction failures are retried with backoff before being reported.
+
+use anyhow::{Context, Result};
+use kreuzberg::{ExtractionConfig, ExtractionResult, extract_file_sync};
+use serde_json::json;
+use std::{
+    collections::HashMap,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, RecvTimeoutError, Sender},
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+use walkdir::WalkDir;
+
+use super::batch::{
+    BatchOptions, EntryStatus, InputFilter, MANIFEST_FILE, Manifest, ManifestEntry, collect_inputs, output_extension,
+    output_path, relative_str, sha256_file, write_output,
+};
+use crate::OutputFormat;
+
+/// Upper bound on the delay between retries of a failing file.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+/// How the watched directory is filtered, debounced and where results go.
+#[derive(Debug)]
+pub struct WatchOptions {
+    /// Globs a file's path (relative to the watched directory) must match, if any
+    pub include: Vec<String>,
+    /// Globs excluding files and directories
+    pub exclude: Vec<String>,
+    /// Accepted MIME types; `type/*` matches a whole top-level type
+    pub mime_types: Vec<String>,
+    /// Directory receiving one result file per input; NDJSON on stdout when unset
+    pub output_dir: Option<PathBuf>,
+    /// Quiet period after the last change before a file is extracted
+    pub debounce: Duration,
+    /// Use the polling scanner even when native notifications are available
+    pub poll: bool,
+    /// Interval between scans of the polling scanner
+    pub poll_interval: Duration,
+    /// Extraction attempts after the first before a file is reported as failed
+    pub retries: u32,
+    /// Only extract files changed after the watch started
+    pub skip_existing: bool,
+}
+
+/// Size and modification time of a file, used to tell when it stopped changing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileStamp {
+    len: u64,
+    modified: Option<SystemTime>,
+}
+
+impl FileStamp {
+    fn of(path: &Path) -> Option<Self> {
+        let metadata = fs::metadata(path).ok().filter(|metadata| metadata.is_file())?;
+        Some(Self {
+            len: metadata.len(),
+            modified: metadata.modified().ok(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PendingFile {
+    due: Instant,
+    /// Stamp seen at the last check; the file is ready once two checks agree
+    stamp: Option<FileStamp>,
+    /// Failed extraction attempts so far
+    attempts: u32,
+}
+
+/// Files waiting for their debounce period to pass.
+#[derive(Debug)]
+struct Debouncer {
+    debounce: Duration,
+    pending: HashMap<PathBuf, PendingFile>,
+}
+
+impl Debouncer {
+    fn new(debounce: Duration) -> Self {
+        Self {
+            debounce,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Record a change to `path`, restarting its quiet period.
+    fn touch(&mut self, path: PathBuf, now: Instant) {
+        let due = now + self.debounce;
+        self.pending
+            .entry(path)
+            .and_modify(|pending| pending.due = pending.due.max(due))
+            .or_insert(PendingFile {
+                due,
+                stamp: None,
+                attempts: 0,
+            });
+    }
+
+    /// Put `path` back with `pending` state, due after another quiet period.
+    fn defer(&mut self, path: PathBuf, pending: PendingFile, now: Instant) {
+        self.pending.insert(
+            path,
+            PendingFile {
+                due: now + self.debounce,
+                ..pending
+            },
+        );
+    }
+
+    /// Schedule another extraction attempt, backing off exponentially.
+    fn retry(&mut self, path: PathBuf, attempts: u32, now: Instant) {
+        let delay = self
+            .debounce
+            .saturating_mul(2u32.saturating_pow(attempts))
+            .min(MAX_RETRY_DELAY);
+        self.pending.insert(
+            path,
+            PendingFile {
+                due: now + delay,
+                stamp: None,
+                attempts,
+            },
+        );
+    }
+
+    /// Remove and return the files whose quiet period has passed.
+    fn take_due(&mut self, now: Instant) -> Vec<(PathBuf, PendingFile)> {
+        let due: Vec<PathBuf> = self
+            .pending
+            .iter()
+            .filter(|(_, pending)| pending.due <= now)
+            .map(|(path, _)| path.clone())
+            .collect();
+        let mut taken: Vec<(PathBuf, PendingFile)> = due
+            .into_iter()
+            .filter_map(|path| self.pending.remove_entry(&path))
+            .collect();
+        taken.sort_by(|a, b| a.0.cmp(&b.0));
+        taken
+    }
+
+    fn next_due(&self) -> Option<Instant> {
+        self.pending.values().map(|pending| pending.due).min()
+    }
+}
+
+/// Polling fallback: reports files that appeared or changed since the previous scan.
+#[derive(Debug)]
+struct PollScanner {
+    root: PathBuf,
+    snapshot: HashMap<PathBuf, FileStamp>,
+}
+
+impl PollScanner {
+    /// Start from the current contents of `root`, so only later changes are reported.
+    fn new(root: PathBuf) -> Self {
+        let mut scanner = Self {
+            root,
+            snapshot: HashMap::new(),
+        };
+        scanner.scan();
+        scanner
+    }
+
+    fn scan(&mut self) -> Vec<PathBuf> {
+        let mut snapshot = HashMap::with_capacity(self.snapshot.len());
+        let mut changed = Vec::new();
+        for entry in WalkDir::new(&self.root).into_iter().filter_map(|entry| entry.ok()) {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let Some(stamp) = FileStamp::of(entry.path()) else {
+                continue;
+            };
+            if self.snapshot.get(entry.path()) != Some(&stamp) {
+                changed.push(entry.path().to_path_buf());
+            }
+            snapshot.insert(entry.path().to_path_buf(), stamp);
+        }
+        self.snapshot = snapshot;
+        changed.sort();
+        changed
+    }
+}
+
+fn start_polling(root: PathBuf, interval: Duration, tx: Sender<PathBuf>) {
+    let mut scanner = PollScanner::new(root);
+    thread::spawn(move || {
+        loop {
+            thread::sleep(interval);
+            for path in scanner.scan() {
+                if tx.send(path).is_err() {
+                    return;
+                }
+            }
+        }
+    });
+}
+
+/// Keeps the native watcher alive for the duration of the watch.
+enum ChangeSource {
+    #[cfg(feature = "native-watch")]
+    Native {
+        _watcher: notify::RecommendedWatcher,
+    },
+    Poll,
+}
+
+#[cfg(feature = "native-watch")]
+fn start_native(root: &Path, tx: Sender<PathBuf>) -> notify::Result<notify::RecommendedWatcher> {
+    use notify::{Event, EventKind, RecursiveMode, Watcher};
+
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| match event {
+        Ok(event) if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) => {
+            for path in event.paths {
+                let _ = tx.send(path);
+            }
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!("File watcher error: {}", e),
+    })?;
+    watcher.watch(root, RecursiveMode::Recursive)?;
+    Ok(watcher)
+}
+
+fn start_change_source(root: &Path, options: &WatchOptions, tx: Sender<PathBuf>) -> ChangeSource {
+    if !options.poll {
+        #[cfg(feature = "native-watch")]
+        match start_native(root, tx.clone()) {
+            Ok(watcher) => return ChangeSource::Native { _watcher: watcher },
+            Err(e) => eprintln!("Native file notifications unavailable ({}), falling back to polling", e),
+        }
+        #[cfg(not(feature = "native-watch"))]
+        tracing::debug!("Built without the native-watch feature, polling for changes");
+    }
+
+    start_polling(root.to_path_buf(), options.poll_interval, tx);
+    ChangeSource::Poll
+}
+
+/// Where extraction results are written.
+enum Sink {
+    /// One result file per input plus a manifest, as in `batch --output-dir`
+    Directory {
+        dir: PathBuf,
+        format: OutputFormat,
+        extension: &'static str,
+        manifest: Manifest,
+        manifest_path: PathBuf,
+    },
+    /// One JSON object per line on stdout
+    Stdout,
+}
+
+impl Sink {
+    fn new(output_dir: Option<&Path>, config: &ExtractionConfig, format: OutputFormat) -> Result<Self> {
+        let Some(dir) = output_dir else {
+            return Ok(Sink::Stdout);
+        };
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create output directory '{}'", dir.display()))?;
+        let dir = dir
+            .canonicalize()
+            .with_context(|| format!("Failed to resolve output directory '{}'", dir.display()))?;
+
+        let extension = output_extension(format, config.output_format);
+        let manifest_path = dir.join(MANIFEST_FILE);
+        let manifest = Manifest::load(&manifest_path, &Manifest::settings_hash(config, extension)?)?;
+        Ok(Sink::Directory {
+            dir,
+            format,
+            extension,
+            manifest,
+            manifest_path,
+        })
+    }
+
+    fn output_dir(&self) -> Option<&Path> {
+        match self {
+            Sink::Directory { dir, .. } => Some(dir),
+            Sink::Stdout => None,
+        }
+    }
+
+    /// Whether the manifest shows `path` was already extracted with its current content.
+    fn is_unchanged(&self, key: &str, sha256: &str) -> bool {
+        match self {
+            Sink::Directory { dir, manifest, .. } => manifest.is_unchanged(key, sha256, dir),
+            Sink::Stdout => false,
+        }
+    }
+
+    fn write_result(
+        &mut self,
+        path: &Path,
+        key: &str,
+        relative: &Path,
+        sha256: String,
+        result: &ExtractionResult,
+    ) -> Result<()> {
+        match self {
+            Sink::Directory {
+                dir,
+                format,
+                extension,
+                manifest,
+                manifest_path,
+            } => {
+                let output = output_path(relative, extension);
+                write_output(&dir.join(&output), result, *format)?;
+                eprintln!("Extracted {} -> {}", path.display(), dir.join(&output).display());
+                manifest.entries.insert(
+                    key.to_string(),
+                    ManifestEntry {
+                        source: path.to_path_buf(),
+                        sha256,
+                        status: EntryStatus::Ok,
+                        output: Some(output),
+                        mime_type: Some(result.mime_type.to_string()),
+                        error: None,
+                    },
+                );
+                manifest.save(manifest_path)
+            }
+            Sink::Stdout => emit_line(&json!({ "path": path, "status": "ok", "result": result })),
+        }
+    }
+
+    fn write_failure(&mut self, path: &Path, key: &str, sha256: String, error: &str) -> Result<()> {
+        match self {
+            Sink::Directory {
+                manifest,
+                manifest_path,
+                ..
+            } => {
+                eprintln!("FAILED {}: {}", path.display(), error);
+                manifest.entries.insert(
+                    key.to_string(),
+                    ManifestEntry {
+                        source: path.to_path_buf(),
+                        sha256,
+                        status: EntryStatus::Failed,
+                        output: None,
+                        mime_type: None,
+                        error: Some(error.to_string()),
+                    },
+                );
+                manifest.save(manifest_path)
+            }
+            Sink::Stdout => emit_line(&json!({ "path": path, "status": "failed", "error": error })),
+        }
+    }
+}
+
+fn emit_line(value: &serde_json::Value) -> Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    serde_json::to_writer(&mut stdout, value).context("Failed to serialize watch result to JSON")?;
+    writeln!(stdout).context("Failed to write to stdout")?;
+    stdout.flush().context("Failed to write to stdout")
+}
+
+/// What happened to a file whose quiet period passed.
+#[derive(Debug, PartialEq, Eq)]
+enum Outcome {
+    /// Extracted, or already extracted with the same content
+    Done,
+    /// Still changing (or failed and will be retried)
+    Deferred,
+    /// Gone, or failed for the last time
+    Dropped,
+}
+
+struct Watcher {
+    root: PathBuf,
+    config: ExtractionConfig,
+    filter: InputFilter,
+    retries: u32,
+    sink: Sink,
+    debouncer: Debouncer,
+}
+
+impl Watcher {
+    /// Queue `path` if it is a file below the root that passes the filters.
+    fn on_change(&mut self, path: PathBuf, now: Instant) {
+        if self.sink.output_dir().is_some_and(|dir| path.starts_with(dir)) {
+            return;
+        }
+        let Ok(relative) = path.strip_prefix(&self.root) else {
+            return;
+        };
+        if !path.is_file() || !self.filter.accepts_path(&relative_str(relative, Path::new("")), &path) {
+            return;
+        }
+        self.debouncer.touch(path, now);
+    }
+
+    fn process_due(&mut self, now: Instant) -> Result<()> {
+        for (path, pending) in self.debouncer.take_due(now) {
+            self.process(path, pending, now)?;
+        }
+        Ok(())
+    }
+
+    fn process(&mut self, path: PathBuf, pending: PendingFile, now: Instant) -> Result<Outcome> {
+        let Some(stamp) = FileStamp::of(&path) else {
+            return Ok(Outcome::Dropped);
+        };
+        if pending.stamp != Some(stamp) {
+            self.debouncer.defer(
+                path,
+                PendingFile {
+                    stamp: Some(stamp),
+                    ..pending
+                },
+                now,
+            );
+            return Ok(Outcome::Deferred);
+        }
+
+        let relative = path.strip_prefix(&self.root).unwrap_or(&path).to_path_buf();
+        let key = relative_str(&relative, Path::new(""));
+        let sha256 = match sha256_file(&path) {
+            Ok(sha256) => sha256,
+            Err(e) => return self.fail(path, &key, String::new(), pending.attempts, &format!("{:#}", e), now),
+        };
+        if self.sink.is_unchanged(&key, &sha256) {
+            return Ok(Outcome::Done);
+        }
+
+        match extract_file_sync(&path, None, &self.config) {
+            Ok(result) => match self.sink.write_result(&path, &key, &relative, sha256.clone(), &result) {
+                Ok(()) => Ok(Outcome::Done),
+                Err(e) => self.fail(path, &key, sha256, pending.attempts, &format!("{:#}", e), now),
+            },
+            Err(e) => self.fail(path, &key, sha256, pending.attempts, &e.to_string(), now),
+        }
+    }
+
+    fn fail(
+        &mut self,
+        path: PathBuf,
+        key: &str,
+        sha256: String,
+        attempts: u32,
+        error: &str,
+        now: Instant,
+    ) -> Result<Outcome> {
+        if attempts < self.retries {
+            tracing::debug!("Extraction of '{}' failed, retrying: {}", path.display(), error);
+            self.debouncer.retry(path, attempts + 1, now);
+            return Ok(Outcome::Deferred);
+        }
+        self.sink.write_failure(&path, key, sha256, error)?;
+        Ok(Outcome::Dropped)
+    }
+}
+
+/// Execute watch command
+///
+/// Runs until interrupted. Results go to `options.output_dir` (in `format`) or, without an
+/// output directory, to stdout as NDJSON: one `{"path", "status", "result" | "error"}`
+/// object per extracted file.
+pub fn watch_command(
+    dir: PathBuf,
+    config: ExtractionConfig,
+    format: OutputFormat,
+    options: WatchOptions,
+) -> Result<()> {
+    let root = dir
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve watch directory '{}'", dir.display()))?;
+    if !root.is_dir() {
+        anyhow::bail!("'{}' is not a directory", dir.display());
+    }
+
+    let sink = Sink::new(options.output_dir.as_deref(), &config, format)?;
+    let mut watcher = Watcher {
+        filter: InputFilter::new(&options.include, &options.exclude, &options.mime_types)?,
+        root: root.clone(),
+        config,
+        retries: options.retries,
+        sink,
+        debouncer: Debouncer::new(options.debounce),
+    };
+
+    let (tx, rx) = mpsc::channel();
+    let source = start_change_source(&root, &options, tx);
+    match source {
+        #[cfg(feature = "native-watch")]
+        ChangeSource::Native { .. } => eprintln!("Watching {} for changes", root.display()),
+        ChangeSource::Poll => eprintln!(
+            "Watching {} for changes (polling every {:?})",
+            root.display(),
+            options.poll_interval
+        ),
+    }
+
+    if !options.skip_existing {
+        let existing = BatchOptions {
+            recursive: true,
+            include: options.include.clone(),
+            exclude: options.exclude.clone(),
+            mime_types: options.mime_types.clone(),
+            ..Default::default()
+        };
+        let now = Instant::now();
+        for input in collect_inputs(std::slice::from_ref(&root), &existing)? {
+            watcher.on_change(input.path, now);
+        }
+    }
+
+    run(&mut watcher, &rx)
+}
+
+fn run(watcher: &mut Watcher, rx: &Receiver<PathBuf>) -> Result<()> {
+    loop {
+        let timeout = watcher
+            .debouncer
+            .next_due()
+            .map(|due| due.saturating_duration_since(Instant::now()))
+            .unwrap_or(Duration::from_secs(3600));
+
+        match rx.recv_timeout(timeout) {
+            Ok(path) => {
+                let now = Instant::now();
+                watcher.on_change(path, now);
+                for path in rx.try_iter() {
+                    watcher.on_change(path, now);
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => anyhow::bail!("File watcher stopped unexpectedly"),
+        }
+
+        watcher.process_due(Instant::now())?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn test_watcher(root: &Path, output_dir: Option<&Path>, retries: u32) -> Watcher {
+        let config = ExtractionConfig {
+            use_cache: false,
+            ..Default::default()
+        };
+        Watcher {
+            root: root.to_path_buf(),
+            filter: InputFilter::new(&[], &["ignored".to_string()], &[]).unwrap(),
+            sink: Sink::new(output_dir, &config, OutputFormat::Text).unwrap(),
+            config,
+            retries,
+            debouncer: Debouncer::new(Duration::from_millis(100)),
+        }
+    }
+
+    #[test]
+    fn test_debouncer_waits_for_quiet_period() {
+        let start = Instant::now();
+        let mut debouncer = Debouncer::new(Duration::from_millis(100));
+        debouncer.touch(PathBuf::from("a"), start);
+        debouncer.touch(PathBuf::from("a"), start + Duration::from_millis(50));
+
+        assert!(debouncer.take_due(start + Duration::from_millis(100)).is_empty());
+        let due = debouncer.take_due(start + Duration::from_millis(150));
+        assert_eq!(due.len(), 1);
+        assert!(debouncer.next_due().is_none());
+
+        debouncer.retry(PathBuf::from("a"), 2, start);
+        assert_eq!(debouncer.next_due(), Some(start + Duration::from_millis(400)));
+    }
+
+    #[test]
+    fn test_poll_scanner_reports_new_and_modified_files() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("existing.txt"), "old").unwrap();
+        let mut scanner = PollScanner::new(dir.path().to_path_buf());
+        assert!(scanner.scan().is_empty());
+
+        fs::create_dir(dir.path().join("sub")).unwrap();
+        fs::write(dir.path().join("sub/new.txt"), "new").unwrap();
+        fs::write(dir.path().join("existing.txt"), "changed").unwrap();
+        assert_eq!(
+            scanner.scan(),
+            vec![dir.path().join("existing.txt"), dir.path().join("sub/new.txt")]
+        );
+        assert!(scanner.scan().is_empty());
+    }
+
+    #[test]
+    fn test_watcher_extracts_once_file_is_stable() {
+        let source = tempdir().unwrap();
+        let output = tempdir().unwrap();
+        let root = source.path().canonicalize().unwrap();
+        let mut watcher = test_watcher(&root, Some(output.path()), 0);
+
+        let path = root.join("drop/scan.txt");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "scanned page").unwrap();
+        fs::create_dir(root.join("ignored")).unwrap();
+        fs::write(root.join("ignored/skip.txt"), "skip").unwrap();
+
+        let now = Instant::now();
+        watcher.on_change(path.clone(), now);
+        watcher.on_change(root.join("ignored/skip.txt"), now);
+        assert_eq!(watcher.debouncer.pending.len(), 1);
+
+        let (path, pending) = watcher.debouncer.take_due(now + Duration::from_secs(1)).remove(0);
+        assert_eq!(watcher.process(path, pending, now).unwrap(), Outcome::Deferred);
+        let (path, pending) = watcher.debouncer.take_due(now + Duration::from_secs(1)).remove(0);
+        assert_eq!(watcher.process(path, pending, now).unwrap(), Outcome::Done);
+
+        let written = output.path().canonicalize().unwrap().join("drop/scan.txt.txt");
+        assert_eq!(fs::read_to_string(written).unwrap().trim(), "scanned page");
+    }
+
+    #[test]
+    fn test_watcher_keeps_running_when_output_cannot_be_written() {
+        let source = tempdir().unwrap();
+        let output = tempdir().unwrap();
+        let root = source.path().canonicalize().unwrap();
+        let mut watcher = test_watcher(&root, Some(output.path()), 0);
+
+        let path = root.join("blocked.txt");
+        fs::write(&path, "content").unwrap();
+        // A directory where the result file should go makes the write fail.
+        fs::create_dir(output.path().join("blocked.txt.txt")).unwrap();
+
+        let pending = PendingFile {
+            due: Instant::now(),
+            stamp: FileStamp::of(&path),
+            attempts: 0,
+        };
+        let outcome = watcher.process(path, pending, Instant::now()).unwrap();
+        assert_eq!(outcome, Outcome::Dropped);
+
+        let manifest: Manifest = serde_json::from_slice(&fs::read(output.path().join(MANIFEST_FILE)).unwrap()).unwrap();
+        assert_eq!(manifest.entries["blocked.txt"].status, EntryStatus::Failed);
+    }
+
+    #[test]
+    fn test_watcher_drops_deleted_files() {
+        let source = tempdir().unwrap();
+        let root = source.path().canonicalize().unwrap();
+        let mut watcher = test_watcher(&root, None, 3);
+
+        let pending = PendingFile {
+            due: Instant::now(),
+            stamp: None,
+            attempts: 0,
+        };
+        let outcome = watcher.process(root.join("gone.txt"), pending, Instant::now()).unwrap();
+        assert_eq!(outcome, Outcome::Dropped);
+    }
+}
@@ -5,9 +5,11 @@
 //!
 //! # Architecture
 //!
-//! The CLI is built using `clap` for argument parsing and provides five main commands:
+//! The CLI is built using `clap` for argument parsing and provides these main commands:
 //! - `extract`: Extract text/data from a single document
 //! - `batch`: Process multiple documents in parallel
+//! - `watch`: Extract documents as they are added to a directory
+//! - `diff`: Compare two versions of a document section by section
 //! - `detect`: Identify MIME type of a file
 //! - `cache`: Manage cache (clear, stats)
 //! - `serve`: Start API server (requires `api` feature)
@@ -47,6 +49,15 @@
 //! # Batch processing
 //! kreuzberg batch *.pdf --output-format json
 //!
+//! # Ingest a directory tree, writing one markdown file per document
+//! kreuzberg batch docs/ --recursive --include '**/*.pdf' --output-dir out/ --format text --output-format markdown
+//!
+//! # Extract scans dropped into a folder, as NDJSON on stdout
+//! kreuzberg watch /srv/scanner-inbox
+//!
+//! # Compare two revisions of a contract
+//! kreuzberg diff contract_v1.docx contract_v2.pdf --style markdown
+//!
 //! # Detect MIME type
 //! kreuzberg detect unknown-file.bin
 //! ```
@@ -62,10 +73,14 @@ use clap::{Parser, Subcommand};
 use commands::mcp_command;
 #[cfg(feature = "api")]
 use commands::serve_command;
-use commands::{apply_extraction_overrides, batch_command, clear_command, extract_command, load_config, stats_command};
+use commands::{
+    BatchOptions, WatchOptions, apply_extraction_overrides, batch_command, clear_command, diff_command,
+    extract_command, load_config, stats_command, watch_command,
+};
 use kreuzberg::{OutputFormat as ContentOutputFormat, detect_mime_type};
 use serde_json::json;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 use tracing_subscriber::EnvFilter;
 
 /// Kreuzberg document intelligence CLI
@@ -166,9 +181,41 @@ enum Commands {
 
     /// Batch extract from multiple documents
     Batch {
-        /// Paths to documents
+        /// Paths to documents (or directories with --recursive)
         paths: Vec<PathBuf>,
 
+        /// Recurse into directories and extract every supported file below them
+        #[arg(short, long)]
+        recursive: bool,
+
+        /// Only extract files whose path relative to the input directory matches this glob (repeatable)
+        ///
+        /// Example: --include '**/*.pdf' --include 'reports/*'
+        #[arg(long, value_name = "GLOB")]
+        include: Vec<String>,
+
+        /// Skip files and directories whose relative path matches this glob (repeatable)
+        #[arg(long, value_name = "GLOB")]
+        exclude: Vec<String>,
+
+        /// Only extract files of this MIME type; `type/*` matches a whole top-level type (repeatable)
+        ///
+        /// Example: --mime application/pdf --mime 'image/*'
+        #[arg(long = "mime", value_name = "TYPE")]
+        mime_types: Vec<String>,
+
+        /// Write one result file per input into this directory, mirroring the source tree.
+        ///
+        /// Results are JSON with --format json, otherwise the extracted content with an extension
+        /// matching --output-format. A manifest in the directory records content hashes so
+        /// unchanged files are skipped on the next run and interrupted runs resume.
+        #[arg(short, long)]
+        output_dir: Option<PathBuf>,
+
+        /// Re-extract all files even if the manifest shows them unchanged (requires --output-dir)
+        #[arg(long, requires = "output_dir")]
+        force: bool,
+
         /// Path to config file (TOML, YAML, or JSON). If not specified, searches for kreuzberg.toml/yaml/json in current and parent directories.
         #[arg(short, long)]
         config: Option<PathBuf>,
@@ -229,6 +276,147 @@ enum Commands {
         content_format: Option<ContentOutputFormatArg>,
     },
 
+    /// Watch a directory and extract documents as they are added or modified
+    ///
+    /// Runs until interrupted. Uses native filesystem notifications where available and
+    /// falls back to polling. A file is extracted once it has stopped changing for the
+    /// debounce period; failed extractions are retried with backoff.
+    Watch {
+        /// Directory to watch (recursively)
+        dir: PathBuf,
+
+        /// Write one result file per input into this directory, mirroring the watched tree.
+        ///
+        /// Without it, results are printed to stdout as NDJSON, one object per extracted file.
+        #[arg(short, long)]
+        output_dir: Option<PathBuf>,
+
+        /// Format of result files in --output-dir (text or json)
+        #[arg(short, long, default_value = "json")]
+        format: OutputFormat,
+
+        /// Only extract files whose path relative to the watched directory matches this glob (repeatable)
+        #[arg(long, value_name = "GLOB")]
+        include: Vec<String>,
+
+        /// Ignore files and directories whose relative path matches this glob (repeatable)
+        #[arg(long, value_name = "GLOB")]
+        exclude: Vec<String>,
+
+        /// Only extract files of this MIME type; `type/*` matches a whole top-level type (repeatable)
+        #[arg(long = "mime", value_name = "TYPE")]
+        mime_types: Vec<String>,
+
+        /// Milliseconds a file must stay unchanged before it is extracted
+        #[arg(long, default_value_t = 1000)]
+        debounce_ms: u64,
+
+        /// Poll the directory instead of using native filesystem notifications
+        #[arg(long)]
+        poll: bool,
+
+        /// Milliseconds between scans when polling
+        #[arg(long, default_value_t = 2000)]
+        poll_interval_ms: u64,
+
+        /// Extraction retries before a file is reported as failed
+        #[arg(long, default_value_t = 3)]
+        retries: u32,
+
+        /// Only extract files changed after the watch starts, not those already present
+        #[arg(long)]
+        skip_existing: bool,
+
+        /// Path to config file (TOML, YAML, or JSON). If not specified, searches for kreuzberg.toml/yaml/json in current and parent directories.
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Inline JSON configuration. Applied after config file but before individual flags.
+        #[arg(long)]
+        config_json: Option<String>,
+
+        /// Base64-encoded JSON configuration. Useful for shell environments where quotes are problematic.
+        #[arg(long)]
+        config_json_base64: Option<String>,
+
+        /// Enable OCR (overrides config file)
+        #[arg(long)]
+        ocr: Option<bool>,
+
+        /// OCR backend to use when --ocr is enabled (tesseract, paddle-ocr, easyocr)
+        #[arg(long)]
+        ocr_backend: Option<String>,
+
+        /// OCR language code. Tesseract: ISO 639-3 (eng, fra, deu). PaddleOCR: flexible (en, ch, french, korean).
+        #[arg(long)]
+        ocr_language: Option<String>,
+
+        /// Force OCR even if text extraction succeeds (overrides config file)
+        #[arg(long)]
+        force_ocr: Option<bool>,
+
+        /// Disable caching (overrides config file)
+        #[arg(long)]
+        no_cache: Option<bool>,
+
+        /// Enable quality processing (overrides config file)
+        #[arg(long)]
+        quality: Option<bool>,
+
+        /// Content output format (plain, markdown, djot, html)
+        #[arg(long, value_enum)]
+        output_format: Option<ContentOutputFormatArg>,
+    },
+
+    /// Compare two versions of a document
+    ///
+    /// Both documents are extracted and aligned section by section using their heading
+    /// structure; inserted, deleted and modified paragraphs and table cells are reported.
+    /// The versions may be in different formats (e.g. DOCX and PDF).
+    Diff {
+        /// Path to the old version
+        old: PathBuf,
+
+        /// Path to the new version
+        new: PathBuf,
+
+        /// Output format (text or json). JSON reports every change with node ids and pages.
+        #[arg(short, long, default_value = "text")]
+        format: OutputFormat,
+
+        /// Style of text output: a unified diff or a Markdown change report
+        #[arg(long, value_enum, default_value = "unified")]
+        style: DiffStyle,
+
+        /// Exit with status 1 when the documents differ, like diff(1)
+        #[arg(long)]
+        exit_code: bool,
+
+        /// Path to config file (TOML, YAML, or JSON). If not specified, searches for kreuzberg.toml/yaml/json in current and parent directories.
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Inline JSON configuration. Applied after config file but before individual flags.
+        #[arg(long)]
+        config_json: Option<String>,
+
+        /// Base64-encoded JSON configuration. Useful for shell environments where quotes are problematic.
+        #[arg(long)]
+        config_json_base64: Option<String>,
+
+        /// Enable OCR (overrides config file)
+        #[arg(long)]
+        ocr: Option<bool>,
+
+        /// Force OCR even if text extraction succeeds (overrides config file)
+        #[arg(long)]
+        force_ocr: Option<bool>,
+
+        /// Disable caching (overrides config file)
+        #[arg(long)]
+        no_cache: Option<bool>,
+    },
+
     /// Detect MIME type of a file
     Detect {
         /// Path to the file
@@ -347,6 +535,15 @@ impl std::str::FromStr for OutputFormat {
     }
 }
 
+/// Text rendering of `kreuzberg diff` results.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+enum DiffStyle {
+    /// Unified diff with one hunk per changed section
+    Unified,
+    /// Markdown report with word-level changes
+    Markdown,
+}
+
 /// Content output format for extraction results.
 ///
 /// Controls the format of the extracted content (not the CLI output format).
@@ -443,25 +640,51 @@ fn validate_chunk_params(chunk_size: Option<usize>, chunk_overlap: Option<usize>
 ///
 /// Ensures that at least one file path is provided and that all paths point to valid,
 /// accessible files. This prevents processing empty batches or failing mid-batch due
-/// to invalid paths.
+/// to invalid paths. With `recursive`, directories are accepted as well.
 ///
 /// # Errors
 ///
 /// Returns an error if:
 /// - The paths array is empty (at least one file is required)
-/// - Any path does not exist or is not a regular file
-fn validate_batch_paths(paths: &[PathBuf]) -> Result<()> {
+/// - Any path does not exist or is not a regular file (or directory, when recursive)
+fn validate_batch_paths(paths: &[PathBuf], recursive: bool) -> Result<()> {
     if paths.is_empty() {
         anyhow::bail!("No files provided for batch extraction. Please provide at least one file path.");
     }
 
     for (i, path) in paths.iter().enumerate() {
+        if recursive && path.is_dir() {
+            continue;
+        }
         validate_file_exists(path).with_context(|| format!("Invalid file at position {}", i + 1))?;
     }
 
     Ok(())
 }
 
+/// Merges `--config-json` or `--config-json-base64` (if given) into `config`.
+fn apply_inline_config(
+    config: kreuzberg::ExtractionConfig,
+    config_json: Option<String>,
+    config_json_base64: Option<String>,
+) -> Result<kreuzberg::ExtractionConfig> {
+    if let Some(json_str) = config_json {
+        let json_value: serde_json::Value =
+            serde_json::from_str(&json_str).context("Failed to parse --config-json as JSON")?;
+        merge_json_into_config(&config, json_value).context("Failed to merge --config-json with file config")
+    } else if let Some(base64_str) = config_json_base64 {
+        let json_bytes = STANDARD
+            .decode(&base64_str)
+            .context("Failed to decode base64 in --config-json-base64")?;
+        let json_str = String::from_utf8(json_bytes).context("Base64-decoded content is not valid UTF-8")?;
+        let json_value: serde_json::Value =
+            serde_json::from_str(&json_str).context("Failed to parse decoded --config-json-base64 as JSON")?;
+        merge_json_into_config(&config, json_value).context("Failed to merge --config-json-base64 with file config")
+    } else {
+        Ok(config)
+    }
+}
+
 /// Merges a JSON value into an existing extraction config.
 ///
 /// This function performs a field-by-field merge where JSON fields override
@@ -499,6 +722,9 @@ fn merge_json_into_config(
 }
 
 fn main() -> Result<()> {
+    // Serve isolated extractions when started as a worker by an engine with isolation enabled.
+    kreuzberg::isolation::run_worker_if_requested();
+
     let _ = tracing_subscriber::fmt()
         .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")))
         .with_writer(std::io::stderr)
@@ -533,23 +759,7 @@ fn main() -> Result<()> {
             let mut config = load_config(config_path)?;
 
             // Apply inline JSON config if provided (merge with file config)
-            if let Some(json_str) = config_json {
-                let json_value: serde_json::Value =
-                    serde_json::from_str(&json_str).context("Failed to parse --config-json as JSON")?;
-                // Merge inline JSON with file config
-                config = merge_json_into_config(&config, json_value)
-                    .context("Failed to merge --config-json with file config")?;
-            } else if let Some(base64_str) = config_json_base64 {
-                let json_bytes = STANDARD
-                    .decode(&base64_str)
-                    .context("Failed to decode base64 in --config-json-base64")?;
-                let json_str = String::from_utf8(json_bytes).context("Base64-decoded content is not valid UTF-8")?;
-                let json_value: serde_json::Value =
-                    serde_json::from_str(&json_str).context("Failed to parse decoded --config-json-base64 as JSON")?;
-                // Merge inline JSON with file config
-                config = merge_json_into_config(&config, json_value)
-                    .context("Failed to merge --config-json-base64 with file config")?;
-            }
+            config = apply_inline_config(config, config_json, config_json_base64)?;
 
             apply_extraction_overrides(
                 &mut config,
@@ -572,6 +782,12 @@ fn main() -> Result<()> {
 
         Commands::Batch {
             paths,
+            recursive,
+            include,
+            exclude,
+            mime_types,
+            output_dir,
+            force,
             config: config_path,
             config_json,
             config_json_base64,
@@ -585,28 +801,12 @@ fn main() -> Result<()> {
             output_format,
             content_format,
         } => {
-            validate_batch_paths(&paths)?;
+            validate_batch_paths(&paths, recursive)?;
 
             let mut config = load_config(config_path)?;
 
             // Apply inline JSON config if provided (merge with file config)
-            if let Some(json_str) = config_json {
-                let json_value: serde_json::Value =
-                    serde_json::from_str(&json_str).context("Failed to parse --config-json as JSON")?;
-                // Merge inline JSON with file config
-                config = merge_json_into_config(&config, json_value)
-                    .context("Failed to merge --config-json with file config")?;
-            } else if let Some(base64_str) = config_json_base64 {
-                let json_bytes = STANDARD
-                    .decode(&base64_str)
-                    .context("Failed to decode base64 in --config-json-base64")?;
-                let json_str = String::from_utf8(json_bytes).context("Base64-decoded content is not valid UTF-8")?;
-                let json_value: serde_json::Value =
-                    serde_json::from_str(&json_str).context("Failed to parse decoded --config-json-base64 as JSON")?;
-                // Merge inline JSON with file config
-                config = merge_json_into_config(&config, json_value)
-                    .context("Failed to merge --config-json-base64 with file config")?;
-            }
+            config = apply_inline_config(config, config_json, config_json_base64)?;
 
             apply_extraction_overrides(
                 &mut config,
@@ -624,7 +824,116 @@ fn main() -> Result<()> {
                 content_format,
             );
 
-            batch_command(paths, config, format)?;
+            let options = BatchOptions {
+                recursive,
+                include,
+                exclude,
+                mime_types,
+                output_dir,
+                force,
+            };
+            batch_command(paths, config, format, options)?;
+        }
+
+        Commands::Watch {
+            dir,
+            output_dir,
+            format,
+            include,
+            exclude,
+            mime_types,
+            debounce_ms,
+            poll,
+            poll_interval_ms,
+            retries,
+            skip_existing,
+            config: config_path,
+            config_json,
+            config_json_base64,
+            ocr,
+            ocr_backend,
+            ocr_language,
+            force_ocr,
+            no_cache,
+            quality,
+            output_format,
+        } => {
+            let mut config = load_config(config_path)?;
+
+            // Apply inline JSON config if provided (merge with file config)
+            config = apply_inline_config(config, config_json, config_json_base64)?;
+
+            apply_extraction_overrides(
+                &mut config,
+                ocr,
+                ocr_backend.as_deref(),
+                ocr_language.as_deref(),
+                force_ocr,
+                no_cache,
+                None,
+                None,
+                None,
+                quality,
+                None,
+                output_format,
+                None,
+            );
+
+            let options = WatchOptions {
+                include,
+                exclude,
+                mime_types,
+                output_dir,
+                debounce: Duration::from_millis(debounce_ms),
+                poll,
+                poll_interval: Duration::from_millis(poll_interval_ms),
+                retries,
+                skip_existing,
+            };
+            watch_command(dir, config, format, options)?;
+        }
+
+        Commands::Diff {
+            old,
+            new,
+            format,
+            style,
+            exit_code,
+            config: config_path,
+            config_json,
+            config_json_base64,
+            ocr,
+            force_ocr,
+            no_cache,
+        } => {
+            validate_file_exists(&old)?;
+            validate_file_exists(&new)?;
+
+            let mut config = load_config(config_path)?;
+
+            // Apply inline JSON config if provided (merge with file config)
+            config = apply_inline_config(config, config_json, config_json_base64)?;
+
+            apply_extraction_overrides(
+                &mut config,
+                ocr,
+                None,
+                None,
+                force_ocr,
+                no_cache,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            );
+
+            let differs = diff_command(old, new, config, format, style)?;
+            if exit_code && differs {
+                std::process::exit(1);
+            }
         }
 
         Commands::Detect { path, format } => {
@@ -371,6 +371,126 @@ fn test_batch_multiple_files() {
     assert_eq!(json.as_array().unwrap().len(), 2, "Should have 2 results");
 }
 
+#[test]
+fn test_batch_recursive_output_dir() {
+    build_binary();
+
+    let source = tempdir().expect("Failed to create temp dir");
+    let output_dir = tempdir().expect("Failed to create temp dir");
+    std::fs::create_dir_all(source.path().join("nested")).unwrap();
+    std::fs::write(source.path().join("top.txt"), "top level").unwrap();
+    std::fs::write(source.path().join("nested/inner.txt"), "nested file").unwrap();
+    std::fs::write(source.path().join("nested/skip.log"), "excluded").unwrap();
+
+    let run = || {
+        Command::new(get_binary_path())
+            .args([
+                "batch",
+                source.path().to_str().unwrap(),
+                "--recursive",
+                "--exclude",
+                "*.log",
+                "--output-dir",
+                output_dir.path().to_str().unwrap(),
+                "--format",
+                "json",
+            ])
+            .output()
+            .expect("Failed to execute batch command")
+    };
+
+    let output = run();
+    assert!(
+        output.status.success(),
+        "Batch command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("Report should be valid JSON");
+    assert_eq!(report["extracted"], 2);
+    assert!(output_dir.path().join("nested/inner.txt.json").exists());
+    assert!(!output_dir.path().join("nested/skip.log.json").exists());
+    assert!(output_dir.path().join(".kreuzberg-manifest.json").exists());
+
+    let output = run();
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("Report should be valid JSON");
+    assert_eq!(report["extracted"], 0);
+    assert_eq!(report["unchanged"], 2);
+}
+
+#[test]
+fn test_watch_extracts_new_files() {
+    build_binary();
+
+    let watched = tempdir().expect("Failed to create temp dir");
+    let output_dir = tempdir().expect("Failed to create temp dir");
+
+    let mut child = Command::new(get_binary_path())
+        .args([
+            "watch",
+            watched.path().to_str().unwrap(),
+            "--output-dir",
+            output_dir.path().to_str().unwrap(),
+            "--format",
+            "text",
+            "--poll",
+            "--poll-interval-ms",
+            "100",
+            "--debounce-ms",
+            "100",
+        ])
+        .spawn()
+        .expect("Failed to start watch command");
+
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    std::fs::write(watched.path().join("dropped.txt"), "dropped into the folder").unwrap();
+
+    let expected = output_dir.path().join("dropped.txt.txt");
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(20);
+    while !expected.exists() && std::time::Instant::now() < deadline {
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+
+    let _ = child.kill();
+    let _ = child.wait();
+
+    let content = std::fs::read_to_string(&expected).expect("Watch should write the extracted file");
+    assert!(content.contains("dropped into the folder"));
+}
+
+#[test]
+fn test_diff_reports_changed_paragraphs() {
+    build_binary();
+
+    let dir = tempdir().expect("Failed to create temp dir");
+    let old = dir.path().join("v1.adoc");
+    let new = dir.path().join("v2.adoc");
+    std::fs::write(&old, "= Terms\n\n== Payment\n\nInvoices are due within 30 days.\n").unwrap();
+    std::fs::write(&new, "= Terms\n\n== Payment\n\nInvoices are due within 45 days.\n").unwrap();
+
+    let output = Command::new(get_binary_path())
+        .args([
+            "diff",
+            old.to_str().unwrap(),
+            new.to_str().unwrap(),
+            "--format",
+            "json",
+            "--exit-code",
+        ])
+        .output()
+        .expect("Failed to execute diff command");
+
+    assert_eq!(output.status.code(), Some(1), "--exit-code should report differences");
+    let diff: serde_json::Value = serde_json::from_slice(&output.stdout).expect("Output should be valid JSON");
+    assert_eq!(diff["summary"]["blocks_modified"], 1);
+    assert_eq!(diff["sections"][0]["path"][0], "Payment");
+
+    let output = Command::new(get_binary_path())
+        .args(["diff", old.to_str().unwrap(), old.to_str().unwrap(), "--exit-code"])
+        .output()
+        .expect("Failed to execute diff command");
+    assert!(output.status.success(), "Identical documents should not differ");
+}
+
 #[test]
 fn test_batch_with_missing_file() {
     build_binary();
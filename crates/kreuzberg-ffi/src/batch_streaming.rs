@@ -44,8 +44,6 @@ use std::os::raw::{c_char, c_int, c_void};
 use std::path::Path;
 #[cfg(feature = "rayon")]
 use std::sync::Arc;
-#[cfg(feature = "rayon")]
-use std::sync::atomic::{AtomicBool, Ordering};
 
 /// Callback function invoked for each successfully extracted result.
 ///
@@ -247,7 +245,8 @@ pub unsafe extern "C" fn kreuzberg_extract_batch_streaming(
 ///
 /// - Both callbacks may be invoked concurrently from multiple threads
 /// - `user_data` must be thread-safe (e.g., synchronized with mutex)
-/// - Callback can set atomic flag to signal cancellation
+/// - A non-zero return from `result_callback` also stops extractions already in flight;
+///   no further callbacks are made
 ///
 /// # Safety
 ///
@@ -340,7 +339,10 @@ pub unsafe extern "C" fn kreuzberg_extract_batch_parallel(
     {
         use rayon::prelude::*;
 
-        let cancelled = Arc::new(AtomicBool::new(false));
+        // Cancelling the token also stops extractions already in flight.
+        let cancellation = kreuzberg::CancellationToken::new();
+        let mut config = config;
+        config.cancellation = Some(cancellation.clone());
         let config = Arc::new(config);
 
         let pool = if max_parallel > 0 {
@@ -361,11 +363,16 @@ pub unsafe extern "C" fn kreuzberg_extract_batch_parallel(
 
         pool.install(|| {
             file_paths.par_iter().for_each(|(index, path)| {
-                if cancelled.load(Ordering::Relaxed) {
+                if cancellation.is_cancelled() {
+                    return;
+                }
+
+                let outcome = extract_file_internal(path, &config);
+                if cancellation.is_cancelled() {
                     return;
                 }
 
-                match extract_file_internal(path, &config) {
+                match outcome {
                     Ok(result) => {
                         let view = create_result_view(&result);
 
@@ -373,7 +380,7 @@ pub unsafe extern "C" fn kreuzberg_extract_batch_parallel(
                             unsafe { result_callback(&view as *const _, *index, user_data_ptr as *mut c_void) };
 
                         if should_cancel != 0 {
-                            cancelled.store(true, Ordering::Relaxed);
+                            cancellation.cancel();
                         }
                     }
                     Err(e) => {
@@ -611,6 +611,8 @@ mod tests {
                 total_chunks: 1,
                 first_page: Some(1),
                 last_page: Some(1),
+                heading_path: None,
+                node_ids: None,
             },
         };
 
@@ -406,6 +406,8 @@ mod tests {
                         total_chunks: 2,
                         first_page: None,
                         last_page: None,
+                        heading_path: None,
+                        node_ids: None,
                     },
                 },
                 kreuzberg::types::Chunk {
@@ -419,6 +421,8 @@ mod tests {
                         total_chunks: 2,
                         first_page: None,
                         last_page: None,
+                        heading_path: None,
+                        node_ids: None,
                     },
                 },
             ]),
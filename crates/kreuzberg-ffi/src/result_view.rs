@@ -436,6 +436,8 @@ mod tests {
                         total_chunks: 2,
                         first_page: None,
                         last_page: None,
+                        heading_path: None,
+                        node_ids: None,
                     },
                 },
                 kreuzberg::types::Chunk {
@@ -449,6 +451,8 @@ mod tests {
                         total_chunks: 2,
                         first_page: None,
                         last_page: None,
+                        heading_path: None,
+                        node_ids: None,
                     },
                 },
             ]),
@@ -11,7 +11,7 @@ use kreuzberg::keywords::{
 };
 use kreuzberg::pdf::HierarchyConfig as RustHierarchyConfig;
 use kreuzberg::{
-    ChunkerType, ChunkingConfig as RustChunkingConfig, EmbeddingConfig as RustEmbeddingConfig,
+    ChunkSizing, ChunkerType, ChunkingConfig as RustChunkingConfig, EmbeddingConfig as RustEmbeddingConfig,
     EmbeddingModelType as RustEmbeddingModelType, ExtractionConfig, ImageExtractionConfig as RustImageExtractionConfig,
     LanguageDetectionConfig as RustLanguageDetectionConfig, OcrConfig as RustOcrConfig, PdfConfig as RustPdfConfig,
     PostProcessorConfig as RustPostProcessorConfig, TesseractConfig as RustTesseractConfig,
@@ -250,6 +250,12 @@ pub struct JsChunkingConfig {
     pub embedding: Option<JsEmbeddingConfig>,
     /// Optional preset name for chunking parameters
     pub preset: Option<String>,
+    /// Chunker type: "text" (default), "markdown" or "structure"
+    pub chunker_type: Option<String>,
+    /// Unit of max_chars and max_overlap: "characters" (default) or "tokens"
+    pub sizing: Option<String>,
+    /// tokenizer.json for token sizing (defaults to the tokenizer of the embedding model)
+    pub tokenizer_path: Option<String>,
 }
 
 impl From<JsChunkingConfig> for RustChunkingConfig {
@@ -258,7 +264,17 @@ impl From<JsChunkingConfig> for RustChunkingConfig {
             max_characters: val.max_chars.unwrap_or(1000) as usize,
             overlap: val.max_overlap.unwrap_or(200) as usize,
             trim: true,
-            chunker_type: ChunkerType::Text,
+            chunker_type: match val.chunker_type.as_deref() {
+                Some("markdown") => ChunkerType::Markdown,
+                Some("structure") => ChunkerType::Structure,
+                _ => ChunkerType::Text,
+            },
+            sizing: match val.sizing.as_deref() {
+                Some("tokens") => ChunkSizing::Tokens {
+                    tokenizer_path: val.tokenizer_path.map(Into::into),
+                },
+                _ => ChunkSizing::Characters,
+            },
             embedding: val.embedding.map(Into::into),
             preset: val.preset,
         }
@@ -956,6 +972,14 @@ pub struct JsExtractionConfig {
     pub result_format: Option<String>,
     /// Include document structure in extraction result
     pub include_document_structure: Option<bool>,
+    /// Wall-clock limit per document in seconds
+    pub timeout_secs: Option<u32>,
+    /// Return partial content instead of failing when the timeout expires
+    pub partial_results_on_cancel: Option<bool>,
+    /// Skip units that fail to extract instead of failing the document
+    pub best_effort: Option<bool>,
+    /// Storage backend of the result and embedding caches: "filesystem" | "sqlite" | "sharded"
+    pub cache_backend: Option<String>,
 }
 
 impl TryFrom<JsPageConfig> for kreuzberg::core::config::PageConfig {
@@ -1033,6 +1057,15 @@ impl TryFrom<JsExtractionConfig> for ExtractionConfig {
                 .transpose()?
                 .unwrap_or_default(),
             include_document_structure: val.include_document_structure.unwrap_or(false),
+            timeout_secs: val.timeout_secs.map(u64::from),
+            partial_results_on_cancel: val.partial_results_on_cancel.unwrap_or(false),
+            best_effort: val.best_effort.unwrap_or(false),
+            cache_backend: val
+                .cache_backend
+                .map(|s| s.parse())
+                .transpose()
+                .map_err(|e: kreuzberg::KreuzbergError| Error::new(Status::InvalidArg, e.to_string()))?,
+            cancellation: None,
             security_limits: None,
         })
     }
@@ -1112,6 +1145,25 @@ impl TryFrom<ExtractionConfig> for JsExtractionConfig {
                     cache_dir: emb.cache_dir.and_then(|p| p.to_str().map(String::from)),
                 }),
                 preset: chunk.preset,
+                chunker_type: Some(
+                    match chunk.chunker_type {
+                        ChunkerType::Text => "text",
+                        ChunkerType::Markdown => "markdown",
+                        ChunkerType::Structure => "structure",
+                    }
+                    .to_string(),
+                ),
+                sizing: Some(
+                    match chunk.sizing {
+                        ChunkSizing::Characters => "characters",
+                        ChunkSizing::Tokens { .. } => "tokens",
+                    }
+                    .to_string(),
+                ),
+                tokenizer_path: match chunk.sizing {
+                    ChunkSizing::Tokens { tokenizer_path } => tokenizer_path.and_then(|p| p.to_str().map(String::from)),
+                    ChunkSizing::Characters => None,
+                },
             }),
             images: val.images.map(|img| JsImageExtractionConfig {
                 extract_images: Some(img.extract_images),
@@ -1159,6 +1211,10 @@ impl TryFrom<ExtractionConfig> for JsExtractionConfig {
                 kreuzberg::types::OutputFormat::ElementBased => "element_based".to_string(),
             }),
             include_document_structure: Some(val.include_document_structure),
+            timeout_secs: val.timeout_secs.map(|v| v.min(u64::from(u32::MAX)) as u32),
+            partial_results_on_cancel: Some(val.partial_results_on_cancel),
+            best_effort: Some(val.best_effort),
+            cache_backend: val.cache_backend.map(|backend| backend.to_string()),
         })
     }
 }
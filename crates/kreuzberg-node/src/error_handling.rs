@@ -21,6 +21,8 @@ use super::kreuzberg_error_code_name;
 /// - `Plugin` → GenericFailure (plugin-specific errors)
 /// - `LockPoisoned` → GenericFailure (lock poisoning, should not happen)
 /// - `UnsupportedFormat` → InvalidArg (unsupported MIME types)
+/// - `Isolation` → GenericFailure (worker timed out, ran out of memory or crashed)
+/// - `Cancelled` → Cancelled (extraction cancelled or timed out)
 /// - `Other` → GenericFailure (catch-all)
 ///
 /// # Usage
@@ -73,6 +75,13 @@ pub(crate) fn convert_error(err: kreuzberg::KreuzbergError) -> napi::Error {
             Error::new(Status::InvalidArg, format!("Unsupported format: {}", format))
         }
 
+        KreuzbergError::Isolation { kind, message } => Error::new(
+            Status::GenericFailure,
+            format!("Isolated extraction failed ({}): {}", kind, message),
+        ),
+
+        KreuzbergError::Cancelled(reason) => Error::new(Status::Cancelled, format!("Extraction {}", reason)),
+
         KreuzbergError::Other(msg) => Error::new(Status::GenericFailure, msg),
     }
 }
@@ -73,6 +73,8 @@ pub struct JsChunkMetadata {
     pub total_chunks: u32,
     pub first_page: Option<u32>,
     pub last_page: Option<u32>,
+    pub heading_path: Option<Vec<String>>,
+    pub node_ids: Option<Vec<String>>,
 }
 
 #[napi(object)]
@@ -478,6 +480,8 @@ impl TryFrom<RustExtractionResult> for JsExtractionResult {
                         total_chunks: usize_to_u32(chunk.metadata.total_chunks, "chunks[].metadata.total_chunks")?,
                         first_page: chunk.metadata.first_page.map(|p| p as u32),
                         last_page: chunk.metadata.last_page.map(|p| p as u32),
+                        heading_path: chunk.metadata.heading_path,
+                        node_ids: chunk.metadata.node_ids,
                     };
 
                     let embedding = chunk
@@ -662,6 +666,8 @@ impl TryFrom<JsExtractionResult> for RustExtractionResult {
                         total_chunks: chunk.metadata.total_chunks as usize,
                         first_page: chunk.metadata.first_page.map(|v| v as usize),
                         last_page: chunk.metadata.last_page.map(|v| v as usize),
+                        heading_path: chunk.metadata.heading_path,
+                        node_ids: chunk.metadata.node_ids,
                     },
                 });
             }
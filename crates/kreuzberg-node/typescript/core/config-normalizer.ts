@@ -110,6 +110,9 @@ function normalizeChunkingConfig(chunking?: ChunkingConfig): NativeExtractionCon
 	setIfDefined(normalized, "maxOverlap", chunking.maxOverlap);
 	setIfDefined(normalized, "preset", chunking.preset);
 	setIfDefined(normalized, "embedding", chunking.embedding);
+	setIfDefined(normalized, "chunkerType", chunking.chunkerType);
+	setIfDefined(normalized, "sizing", chunking.sizing);
+	setIfDefined(normalized, "tokenizerPath", chunking.tokenizerPath);
 	setIfDefined(normalized, "enabled", chunking.enabled);
 	return normalized;
 }
@@ -350,6 +353,10 @@ function normalizeExtractionConfig(config: ExtractionConfig | null): NativeExtra
 	setIfDefined(normalized, "forceOcr", config.forceOcr);
 	setIfDefined(normalized, "includeDocumentStructure", config.includeDocumentStructure);
 	setIfDefined(normalized, "maxConcurrentExtractions", config.maxConcurrentExtractions);
+	setIfDefined(normalized, "timeoutSecs", config.timeoutSecs);
+	setIfDefined(normalized, "partialResultsOnCancel", config.partialResultsOnCancel);
+	setIfDefined(normalized, "bestEffort", config.bestEffort);
+	setIfDefined(normalized, "cacheBackend", config.cacheBackend);
 
 	const ocr = normalizeOcrConfig(config.ocr);
 	setIfDefined(normalized, "ocr", ocr);
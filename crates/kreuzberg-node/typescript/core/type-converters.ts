@@ -104,6 +104,10 @@ function convertChunk(rawChunk: unknown): Chunk {
 			firstPage: ((metadata["first_page"] ?? metadata["firstPage"]) as number | null) ?? null,
 			// biome-ignore lint/complexity/useLiteralKeys: required for strict TypeScript noPropertyAccessFromIndexSignature
 			lastPage: ((metadata["last_page"] ?? metadata["lastPage"]) as number | null) ?? null,
+			// biome-ignore lint/complexity/useLiteralKeys: required for strict TypeScript noPropertyAccessFromIndexSignature
+			headingPath: ((metadata["heading_path"] ?? metadata["headingPath"]) as string[] | null) ?? null,
+			// biome-ignore lint/complexity/useLiteralKeys: required for strict TypeScript noPropertyAccessFromIndexSignature
+			nodeIds: ((metadata["node_ids"] ?? metadata["nodeIds"]) as string[] | null) ?? null,
 		},
 	};
 }
@@ -248,6 +248,21 @@ export interface ChunkingConfig {
 	/** Embedding configuration for generating vector embeddings for each chunk. */
 	embedding?: Record<string, unknown>;
 
+	/**
+	 * Chunker type: "text" (default), "markdown", or "structure" to pack the sections, paragraphs,
+	 * list items and tables of the document structure, with headingPath and nodeIds on each chunk.
+	 */
+	chunkerType?: "text" | "markdown" | "structure";
+
+	/**
+	 * Unit of maxChars and maxOverlap: "characters" (default), or "tokens" of a HuggingFace
+	 * tokenizer. Token-sized chunks always carry tokenCount in their metadata.
+	 */
+	sizing?: "characters" | "tokens";
+
+	/** Path to a tokenizer.json for token sizing. Default: the tokenizer of the embedding model. */
+	tokenizerPath?: string;
+
 	/** Enable or disable chunking. Default: true when chunking config is provided. */
 	enabled?: boolean;
 }
@@ -644,6 +659,18 @@ export interface ExtractionConfig {
 	/** Maximum number of concurrent extractions in batch operations. Default: 4. */
 	maxConcurrentExtractions?: number;
 
+	/** Wall-clock limit per document in seconds. Expired documents fail unless partialResultsOnCancel is set. */
+	timeoutSecs?: number;
+
+	/** Return the content extracted before a timeout, with a "cancellation" processing warning. Default: false. */
+	partialResultsOnCancel?: boolean;
+
+	/** Skip pages, slides, sheets and archive entries that fail to extract, reporting each as an "extraction" processing warning. Default: false. */
+	bestEffort?: boolean;
+
+	/** Storage backend of the extraction result and embedding caches. Default: process default (KREUZBERG_CACHE_BACKEND or "filesystem"). */
+	cacheBackend?: "filesystem" | "sqlite" | "sharded";
+
 	/**
 	 * Output text format for extracted content. Default: "plain".
 	 *
@@ -912,6 +939,10 @@ export interface ChunkMetadata {
 	firstPage?: number | null;
 	/** Last page number this chunk spans (1-indexed, only when page tracking enabled) */
 	lastPage?: number | null;
+	/** Headings of the sections enclosing this chunk, outermost first (structure chunker only) */
+	headingPath?: string[] | null;
+	/** IDs of the document structure nodes in this chunk (structure chunker only) */
+	nodeIds?: string[] | null;
 }
 
 /**
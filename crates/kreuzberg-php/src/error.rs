@@ -25,6 +25,8 @@ pub fn to_php_exception(error: kreuzberg::KreuzbergError) -> PhpException {
         KreuzbergError::ImageProcessing { .. } => PhpException::default(format!("[ImageProcessing] {}", message)),
         KreuzbergError::Serialization { .. } => PhpException::default(format!("[Serialization] {}", message)),
         KreuzbergError::MissingDependency(_) => PhpException::default(format!("[MissingDependency] {}", message)),
+        KreuzbergError::Isolation { .. } => PhpException::default(format!("[Isolation] {}", message)),
+        KreuzbergError::Cancelled(_) => PhpException::default(format!("[Cancelled] {}", message)),
         KreuzbergError::Other(_) => PhpException::default(format!("[Other] {}", message)),
     }
 }
@@ -83,6 +85,8 @@ fn format_error_message(error: &kreuzberg::KreuzbergError) -> String {
             }
         }
         KreuzbergError::MissingDependency(msg) => msg.clone(),
+        KreuzbergError::Isolation { kind, message } => format!("{}: {}", kind, message),
+        KreuzbergError::Cancelled(reason) => format!("Extraction {}", reason),
         KreuzbergError::Other(msg) => msg.clone(),
     }
 }
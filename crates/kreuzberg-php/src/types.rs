@@ -736,6 +736,8 @@ impl ExtractedImage {
 /// - `total_chunks` (int): Total number of chunks
 /// - `first_page` (int|null): First page number in chunk
 /// - `last_page` (int|null): Last page number in chunk
+/// - `heading_path` (array|null): Enclosing section headings (structure chunker only)
+/// - `node_ids` (array|null): Document structure node IDs in chunk (structure chunker only)
 #[php_class]
 #[php(name = "Kreuzberg\\Types\\ChunkMetadata")]
 #[derive(Clone)]
@@ -754,6 +756,10 @@ pub struct ChunkMetadata {
     pub first_page: Option<usize>,
     #[php(prop)]
     pub last_page: Option<usize>,
+    #[php(prop)]
+    pub heading_path: Option<Vec<String>>,
+    #[php(prop)]
+    pub node_ids: Option<Vec<String>>,
 }
 
 #[php_impl]
@@ -769,6 +775,8 @@ impl ChunkMetadata {
             total_chunks: metadata.total_chunks,
             first_page: metadata.first_page,
             last_page: metadata.last_page,
+            heading_path: metadata.heading_path,
+            node_ids: metadata.node_ids,
         })
     }
 }
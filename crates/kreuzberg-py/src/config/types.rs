@@ -57,7 +57,11 @@ impl ExtractionConfig {
         pages=None,
         result_format=None,
         output_format=None,
-        include_document_structure=None
+        include_document_structure=None,
+        timeout_secs=None,
+        partial_results_on_cancel=None,
+        best_effort=None,
+        cache_backend=None
     ))]
     #[allow(clippy::too_many_arguments)]
     fn new(
@@ -78,8 +82,16 @@ impl ExtractionConfig {
         result_format: Option<String>,
         output_format: Option<String>,
         include_document_structure: Option<bool>,
+        timeout_secs: Option<u64>,
+        partial_results_on_cancel: Option<bool>,
+        best_effort: Option<bool>,
+        cache_backend: Option<String>,
     ) -> PyResult<Self> {
         let (html_options_inner, html_options_dict) = parse_html_options_dict(html_options)?;
+        let cache_backend = cache_backend
+            .map(|backend| backend.parse())
+            .transpose()
+            .map_err(|e: kreuzberg::KreuzbergError| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
         Ok(Self {
             inner: kreuzberg::ExtractionConfig {
                 use_cache: use_cache.unwrap_or(true),
@@ -97,6 +109,11 @@ impl ExtractionConfig {
                 max_concurrent_extractions,
                 pages: pages.map(Into::into),
                 include_document_structure: include_document_structure.unwrap_or(false),
+                timeout_secs,
+                partial_results_on_cancel: partial_results_on_cancel.unwrap_or(false),
+                best_effort: best_effort.unwrap_or(false),
+                cache_backend,
+                cancellation: None,
                 result_format: if let Some(rf) = result_format {
                     match rf.to_lowercase().as_str() {
                         "unified" => kreuzberg::types::OutputFormat::Unified,
@@ -264,6 +281,50 @@ impl ExtractionConfig {
         self.inner.max_concurrent_extractions = value;
     }
 
+    #[getter]
+    fn timeout_secs(&self) -> Option<u64> {
+        self.inner.timeout_secs
+    }
+
+    #[setter]
+    fn set_timeout_secs(&mut self, value: Option<u64>) {
+        self.inner.timeout_secs = value;
+    }
+
+    #[getter]
+    fn partial_results_on_cancel(&self) -> bool {
+        self.inner.partial_results_on_cancel
+    }
+
+    #[setter]
+    fn set_partial_results_on_cancel(&mut self, value: bool) {
+        self.inner.partial_results_on_cancel = value;
+    }
+
+    #[getter]
+    fn best_effort(&self) -> bool {
+        self.inner.best_effort
+    }
+
+    #[setter]
+    fn set_best_effort(&mut self, value: bool) {
+        self.inner.best_effort = value;
+    }
+
+    #[getter]
+    fn cache_backend(&self) -> Option<String> {
+        self.inner.cache_backend.map(|backend| backend.to_string())
+    }
+
+    #[setter]
+    fn set_cache_backend(&mut self, value: Option<String>) -> PyResult<()> {
+        self.inner.cache_backend = value
+            .map(|backend| backend.parse())
+            .transpose()
+            .map_err(|e: kreuzberg::KreuzbergError| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+        Ok(())
+    }
+
     #[getter]
     fn html_options<'py>(&self, py: Python<'py>) -> Option<Bound<'py, PyDict>> {
         self.html_options_dict.as_ref().map(|dict| dict.bind(py).clone())
@@ -643,10 +704,13 @@ impl EmbeddingConfig {
 /// Controls how text is split into chunks with optional embedding generation.
 ///
 /// Attributes:
-///     max_chars (int): Maximum characters per chunk (default: 1000)
-///     max_overlap (int): Overlap between chunks in characters (default: 200, must be < max_chars)
+///     max_chars (int): Maximum characters (or tokens) per chunk (default: 1000)
+///     max_overlap (int): Overlap between chunks in characters (or tokens) (default: 200, must be < max_chars)
 ///     embedding (EmbeddingConfig | None): Embedding configuration (default: None)
 ///     preset (str | None): Chunking preset to use (default: None)
+///     sizing (str | None): "characters" or "tokens" (default: "characters")
+///     tokenizer_path (str | None): tokenizer.json for token sizing; defaults to the
+///         tokenizer of the embedding model
 ///
 /// Important:
 ///     The max_overlap must be less than max_chars, otherwise a validation error will be raised.
@@ -675,24 +739,42 @@ pub struct ChunkingConfig {
 #[pymethods]
 impl ChunkingConfig {
     #[new]
-    #[pyo3(signature = (max_chars=None, max_overlap=None, embedding=None, preset=None, chunker_type=None))]
+    #[pyo3(signature = (
+        max_chars=None,
+        max_overlap=None,
+        embedding=None,
+        preset=None,
+        chunker_type=None,
+        sizing=None,
+        tokenizer_path=None
+    ))]
     fn new(
         max_chars: Option<usize>,
         max_overlap: Option<usize>,
         embedding: Option<EmbeddingConfig>,
         preset: Option<String>,
         chunker_type: Option<String>,
+        sizing: Option<String>,
+        tokenizer_path: Option<String>,
     ) -> Self {
         let ct = match chunker_type.as_deref() {
             Some("markdown") => kreuzberg::ChunkerType::Markdown,
+            Some("structure") => kreuzberg::ChunkerType::Structure,
             _ => kreuzberg::ChunkerType::Text,
         };
+        let sizing = match sizing.as_deref() {
+            Some("tokens") => kreuzberg::ChunkSizing::Tokens {
+                tokenizer_path: tokenizer_path.map(Into::into),
+            },
+            _ => kreuzberg::ChunkSizing::Characters,
+        };
         Self {
             inner: kreuzberg::ChunkingConfig {
                 max_characters: max_chars.unwrap_or(1000),
                 overlap: max_overlap.unwrap_or(200),
                 trim: true,
                 chunker_type: ct,
+                sizing,
                 embedding: embedding.map(Into::into),
                 preset,
             },
@@ -95,6 +95,8 @@ fn exception_from_module(name: &str, message: String) -> PyErr {
 /// - `ImageProcessing` → `ImageProcessingError` (custom exception)
 /// - `Serialization` → `ParsingError` (document processing failure)
 /// - `MissingDependency` → `MissingDependencyError` (custom exception)
+/// - `Isolation` → `RuntimeError` (worker timed out, ran out of memory or crashed)
+/// - `Cancelled` → `TimeoutError` when `timeout_secs` expired, otherwise `RuntimeError`
 /// - `Other` → `RuntimeError` (runtime error - must bubble up!)
 ///
 /// All errors preserve their source chain for better debugging.
@@ -134,6 +136,14 @@ pub fn to_py_err(error: kreuzberg::KreuzbergError) -> PyErr {
             exception_from_module("ParsingError", format_error_with_source(message, source))
         }
         KreuzbergError::MissingDependency(msg) => exception_from_module("MissingDependencyError", msg),
+        // RuntimeError must bubble up - a killed or crashed worker is not a document error ~keep
+        KreuzbergError::Isolation { kind, message } => {
+            PyRuntimeError::new_err(format!("Isolated extraction failed ({}): {}", kind, message))
+        }
+        KreuzbergError::Cancelled(reason @ kreuzberg::CancellationReason::TimedOut) => {
+            PyTimeoutError::new_err(format!("Extraction {}", reason))
+        }
+        KreuzbergError::Cancelled(reason) => PyRuntimeError::new_err(format!("Extraction {}", reason)),
         // RuntimeError must bubble up - unexpected errors need user reports ~keep
         KreuzbergError::Other(msg) => PyRuntimeError::new_err(msg),
     }
@@ -439,6 +439,12 @@ impl ExtractionResult {
                 if let Some(last_page) = chunk.metadata.last_page {
                     chunk_metadata_dict.set_item("last_page", last_page)?;
                 }
+                if let Some(heading_path) = chunk.metadata.heading_path {
+                    chunk_metadata_dict.set_item("heading_path", heading_path)?;
+                }
+                if let Some(node_ids) = chunk.metadata.node_ids {
+                    chunk_metadata_dict.set_item("node_ids", node_ids)?;
+                }
 
                 let py_chunk = PyChunk {
                     content: chunk.content,
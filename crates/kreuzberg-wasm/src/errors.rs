@@ -22,6 +22,8 @@ use wasm_bindgen::prelude::*;
 /// - `Plugin` → Plugin-specific error
 /// - `LockPoisoned` → Lock poisoning (internal error)
 /// - `UnsupportedFormat` → Unsupported MIME type
+/// - `Isolation` → Isolated worker timed out, ran out of memory or crashed
+/// - `Cancelled` → Extraction cancelled or timed out
 /// - `Other` → Generic error
 pub fn convert_error(err: KreuzbergError) -> JsValue {
     use kreuzberg::KreuzbergError;
@@ -57,6 +59,13 @@ pub fn convert_error(err: KreuzbergError) -> JsValue {
             ("UnsupportedFormatError", format!("Unsupported format: {}", format))
         }
 
+        KreuzbergError::Isolation { kind, message } => (
+            "IsolationError",
+            format!("Isolated extraction failed ({}): {}", kind, message),
+        ),
+
+        KreuzbergError::Cancelled(reason) => ("CancelledError", format!("Extraction {}", reason)),
+
         KreuzbergError::Other(msg) => ("Error", msg),
     };
 
@@ -0,0 +1,380 @@
+//! API key authentication, rate limiting and per-key concurrency caps.
+//!
+//! Enabled when `ServerConfig::api_keys` is non-empty. Clients send their key as
+//! `Authorization: Bearer <key>` or `X-API-Key: <key>`. Each matched route requires a
+//! scope (see [`required_scope`]); `/health`, `/info` and `/openapi.json` stay public so
+//! load balancers and schema tooling keep working.
+//!
+//! Failures are reported as [`ErrorResponse`](super::types::ErrorResponse)s:
+//!
+//! - **401** `AuthenticationError` – missing or unknown key
+//! - **403** `PermissionDeniedError` – key lacks the required scope
+//! - **429** `RateLimitError` – token bucket exhausted (with `Retry-After`)
+//! - **429** `ConcurrencyLimitError` – too many requests in flight for the key
+//!
+//! Keys are matched by comparing SHA-256 digests in constant time. A concurrency permit
+//! is held until the response body has been sent, and queued jobs keep it until they
+//! finish (see [`Caller`]).
+
+use std::{
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use axum::{
+    body::{Body, Bytes},
+    extract::{MatchedPath, Request, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use http_body::{Frame, SizeHint};
+use parking_lot::Mutex;
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::core::{ApiKeyConfig, ApiScope, ServerConfig};
+
+use super::error::ApiError;
+
+/// Header accepted as an alternative to `Authorization: Bearer`.
+const API_KEY_HEADER: &str = "x-api-key";
+
+/// Token bucket refilled continuously at `rate_per_minute / 60` tokens per second.
+#[derive(Debug)]
+struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate_per_minute: u32, burst: Option<u32>) -> Self {
+        let capacity = f64::from(burst.unwrap_or(rate_per_minute).max(1));
+        Self {
+            capacity,
+            tokens: capacity,
+            refill_per_sec: f64::from(rate_per_minute) / 60.0,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Take one token, or return how long until one becomes available.
+    fn try_acquire(&mut self, now: Instant) -> Result<(), Duration> {
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - self.tokens) / self.refill_per_sec))
+        }
+    }
+}
+
+/// Runtime state for one configured key.
+#[derive(Debug)]
+struct Client {
+    name: String,
+    digest: [u8; 32],
+    config: ApiKeyConfig,
+    bucket: Option<Mutex<TokenBucket>>,
+    concurrency: Option<Arc<Semaphore>>,
+}
+
+/// The key that authenticated a request, stored in the request extensions.
+///
+/// Handlers use `owner` to tie jobs to the key that created them and move `permit`
+/// into work that outlives the request, so it keeps counting against the key's
+/// concurrency cap.
+#[derive(Debug, Clone)]
+pub(crate) struct Caller {
+    /// Hex SHA-256 digest of the key; stable across restarts and safe to persist
+    pub(crate) owner: String,
+    /// Concurrency permit of the request, if the key has a cap
+    pub(crate) permit: Option<Arc<OwnedSemaphorePermit>>,
+}
+
+/// Shared authentication state for the router middleware.
+#[derive(Debug, Default)]
+pub(crate) struct AuthState {
+    clients: Vec<Arc<Client>>,
+}
+
+impl AuthState {
+    pub(crate) fn new(server_config: &ServerConfig) -> Self {
+        let clients = server_config
+            .api_keys
+            .iter()
+            .filter(|key| !key.key.is_empty())
+            .map(|key| {
+                let rate = key.rate_limit_per_minute.or(server_config.rate_limit_per_minute);
+                let max_concurrent = key
+                    .max_concurrent_requests
+                    .or(server_config.max_concurrent_requests_per_key);
+                Arc::new(Client {
+                    name: key.display_name(),
+                    digest: key_digest(&key.key),
+                    config: key.clone(),
+                    bucket: rate
+                        .filter(|rate| *rate > 0)
+                        .map(|rate| Mutex::new(TokenBucket::new(rate, key.burst))),
+                    concurrency: max_concurrent.map(|max| Arc::new(Semaphore::new(max.max(1)))),
+                })
+            })
+            .collect();
+        Self { clients }
+    }
+
+    pub(crate) fn enabled(&self) -> bool {
+        !self.clients.is_empty()
+    }
+
+    /// Find the client for `key`.
+    ///
+    /// Every configured key is compared, and digests have a fixed length, so the time
+    /// taken does not reveal how much of a key matched.
+    fn find(&self, key: &str) -> Option<&Arc<Client>> {
+        let digest = key_digest(key);
+        self.clients.iter().fold(None, |found, client| {
+            if bool::from(client.digest.ct_eq(&digest)) {
+                Some(client)
+            } else {
+                found
+            }
+        })
+    }
+}
+
+fn key_digest(key: &str) -> [u8; 32] {
+    Sha256::digest(key.as_bytes()).into()
+}
+
+/// Response body that keeps a concurrency permit alive until it has been sent.
+struct PermitBody {
+    inner: Body,
+    _permit: Arc<OwnedSemaphorePermit>,
+}
+
+impl http_body::Body for PermitBody {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        Pin::new(&mut self.inner).poll_frame(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+/// Scope required to call `route`, or `None` for public routes.
+///
+/// Unknown routes require `extract` so new endpoints are protected by default.
+pub(crate) fn required_scope(route: &str) -> Option<ApiScope> {
+    match route {
+        "/health" | "/info" | "/openapi.json" => None,
+        "/metrics" => Some(ApiScope::Admin),
+        route if route.starts_with("/cache") => Some(ApiScope::Admin),
+        _ => Some(ApiScope::Extract),
+    }
+}
+
+fn presented_key(headers: &HeaderMap) -> Option<&str> {
+    let bearer = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| {
+            let (scheme, token) = value.trim().split_once(' ')?;
+            scheme.eq_ignore_ascii_case("bearer").then(|| token.trim())
+        });
+    bearer
+        .or_else(|| headers.get(API_KEY_HEADER).and_then(|value| value.to_str().ok()))
+        .filter(|key| !key.is_empty())
+}
+
+/// Router middleware enforcing authentication, scopes and per-key limits.
+pub(crate) async fn authenticate(State(auth): State<Arc<AuthState>>, mut request: Request, next: Next) -> Response {
+    if !auth.enabled() {
+        return next.run(request).await;
+    }
+
+    let required = request
+        .extensions()
+        .get::<MatchedPath>()
+        .and_then(|path| required_scope(path.as_str()));
+    let Some(required) = required else {
+        return next.run(request).await;
+    };
+
+    let Some(client) = presented_key(request.headers()).and_then(|key| auth.find(key)) else {
+        let mut response = ApiError::custom(
+            StatusCode::UNAUTHORIZED,
+            "AuthenticationError",
+            "Missing or invalid API key. Send it as 'Authorization: Bearer <key>' or 'X-API-Key: <key>'",
+        )
+        .into_response();
+        response
+            .headers_mut()
+            .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+        return response;
+    };
+
+    if !client.config.allows(required) {
+        return ApiError::custom(
+            StatusCode::FORBIDDEN,
+            "PermissionDeniedError",
+            format!(
+                "API key '{}' lacks the '{}' scope required for this endpoint",
+                client.name,
+                required.as_str()
+            ),
+        )
+        .into_response();
+    }
+
+    if let Some(bucket) = &client.bucket
+        && let Err(wait) = bucket.lock().try_acquire(Instant::now())
+    {
+        let retry_after = wait.as_secs_f64().ceil().max(1.0) as u64;
+        tracing::debug!("Rate limit exceeded for API key '{}'", client.name);
+        let mut response = ApiError::custom(
+            StatusCode::TOO_MANY_REQUESTS,
+            "RateLimitError",
+            format!(
+                "Rate limit exceeded for API key '{}'. Retry after {} second(s)",
+                client.name, retry_after
+            ),
+        )
+        .into_response();
+        response.headers_mut().insert(header::RETRY_AFTER, retry_after.into());
+        return response;
+    }
+
+    let permit = match &client.concurrency {
+        Some(semaphore) => match Arc::clone(semaphore).try_acquire_owned() {
+            Ok(permit) => Some(Arc::new(permit)),
+            Err(_) => {
+                return ApiError::custom(
+                    StatusCode::TOO_MANY_REQUESTS,
+                    "ConcurrencyLimitError",
+                    format!("Too many concurrent requests for API key '{}'", client.name),
+                )
+                .into_response();
+            }
+        },
+        None => None,
+    };
+
+    request.extensions_mut().insert(Caller {
+        owner: hex::encode(client.digest),
+        permit: permit.clone(),
+    });
+    let response = next.run(request).await;
+    match permit {
+        Some(permit) => response.map(|inner| Body::new(PermitBody { inner, _permit: permit })),
+        None => response,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_bucket_refills_over_time() {
+        let mut bucket = TokenBucket::new(60, Some(2));
+        let start = bucket.last_refill;
+
+        assert!(bucket.try_acquire(start).is_ok());
+        assert!(bucket.try_acquire(start).is_ok());
+        let wait = bucket.try_acquire(start).unwrap_err();
+        assert!(wait <= Duration::from_secs(1));
+
+        assert!(bucket.try_acquire(start + Duration::from_secs(1)).is_ok());
+        assert!(bucket.try_acquire(start + Duration::from_secs(1)).is_err());
+    }
+
+    #[test]
+    fn test_required_scope() {
+        assert_eq!(required_scope("/health"), None);
+        assert_eq!(required_scope("/openapi.json"), None);
+        assert_eq!(required_scope("/extract"), Some(ApiScope::Extract));
+        assert_eq!(required_scope("/jobs/{id}"), Some(ApiScope::Extract));
+        assert_eq!(required_scope("/cache/clear"), Some(ApiScope::Admin));
+        assert_eq!(required_scope("/metrics"), Some(ApiScope::Admin));
+    }
+
+    #[test]
+    fn test_presented_key() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(presented_key(&headers), None);
+
+        headers.insert(API_KEY_HEADER, HeaderValue::from_static("from-header"));
+        assert_eq!(presented_key(&headers), Some("from-header"));
+
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_static("bearer from-bearer"));
+        assert_eq!(presented_key(&headers), Some("from-bearer"));
+
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_static("Basic abc"));
+        assert_eq!(presented_key(&headers), Some("from-header"));
+    }
+
+    #[test]
+    fn test_auth_state_applies_server_defaults() {
+        let mut limited = ApiKeyConfig::new("limited");
+        limited.rate_limit_per_minute = Some(10);
+        let config = ServerConfig {
+            api_keys: vec![ApiKeyConfig::new("default"), limited, ApiKeyConfig::new("")],
+            max_concurrent_requests_per_key: Some(3),
+            ..Default::default()
+        };
+
+        let state = AuthState::new(&config);
+        assert!(state.enabled());
+        assert_eq!(state.clients.len(), 2);
+        assert!(state.find("default").unwrap().bucket.is_none());
+        assert!(state.find("limited").unwrap().bucket.is_some());
+        assert!(state.find("limite").is_none());
+        assert!(state.find("").is_none());
+        assert_eq!(
+            state
+                .find("default")
+                .unwrap()
+                .concurrency
+                .as_ref()
+                .map(|s| s.available_permits()),
+            Some(3)
+        );
+        assert!(!AuthState::new(&ServerConfig::default()).enabled());
+    }
+
+    #[tokio::test]
+    async fn test_permit_held_until_body_is_consumed() {
+        let semaphore = Arc::new(Semaphore::new(1));
+        let permit = Arc::new(Arc::clone(&semaphore).try_acquire_owned().unwrap());
+        let body = Body::new(PermitBody {
+            inner: Body::from("streamed"),
+            _permit: permit,
+        });
+        assert_eq!(semaphore.available_permits(), 0);
+
+        let bytes = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+        assert_eq!(&bytes[..], b"streamed");
+        assert_eq!(semaphore.available_permits(), 1);
+    }
+}
@@ -115,6 +115,8 @@ impl ApiError {
             KreuzbergError::Plugin { .. } => "PluginError",
             KreuzbergError::LockPoisoned(_) => "LockPoisonedError",
             KreuzbergError::UnsupportedFormat(_) => "UnsupportedFormatError",
+            KreuzbergError::Isolation { .. } => "IsolationError",
+            KreuzbergError::Cancelled(_) => "CancelledError",
             KreuzbergError::Other(_) => "Error",
         };
 
@@ -129,6 +131,22 @@ impl ApiError {
         }
     }
 
+    /// Create an error with an explicit error type name.
+    ///
+    /// Used for protocol-level failures that do not originate from a `KreuzbergError`
+    /// (e.g. unknown resources or exhausted queues).
+    pub fn custom(status: StatusCode, error_type: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: ErrorResponse {
+                error_type: error_type.into(),
+                message: message.into(),
+                traceback: None,
+                status_code: status.as_u16(),
+            },
+        }
+    }
+
     /// Create a validation error (400).
     pub fn validation(error: KreuzbergError) -> Self {
         Self::new(StatusCode::BAD_REQUEST, error)
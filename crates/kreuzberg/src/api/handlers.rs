@@ -1,14 +1,22 @@
 //! API request handlers.
 
-use axum::{Json, extract::State};
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+};
 
 use crate::{batch_extract_bytes, cache, extract_bytes};
 
 use super::{
+    auth::Caller,
     error::{ApiError, JsonApi, MultipartApi},
+    overrides::apply_config_overrides,
+    streaming::stream_extraction,
     types::{
         ApiState, CacheClearResponse, CacheStatsResponse, ChunkRequest, ChunkResponse, EmbedRequest, EmbedResponse,
-        ExtractResponse, HealthResponse, InfoResponse,
+        ExtractResponse, ExtractStreamItem, HealthResponse, InfoResponse, JobResponse, StreamFormat,
     },
 };
 
@@ -59,16 +67,46 @@ pub async fn info_handler() -> Json<InfoResponse> {
     })
 }
 
+/// Prometheus metrics endpoint handler.
+///
+/// GET /metrics
+///
+/// Returns request, extraction, OCR and cache metrics in the Prometheus text
+/// exposition format. See [`crate::metrics`] for the recorded series.
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    tag = "health",
+    responses(
+        (status = 200, description = "Metrics in Prometheus text format", body = String, content_type = "text/plain"),
+    )
+)]
+#[cfg_attr(feature = "otel", tracing::instrument(name = "api.metrics"))]
+pub async fn metrics_handler() -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4; charset=utf-8")],
+        crate::metrics::global().render(),
+    )
+}
+
 /// Extract endpoint handler.
 ///
 /// POST /extract
 ///
 /// Accepts multipart form data with:
 /// - `files`: One or more files to extract
-/// - `config` (optional): JSON extraction configuration (overrides server defaults)
+/// - `config` (optional): Partial JSON extraction configuration, merged over the server defaults
+/// - `output_format` (optional): Output format for extracted text
+/// - `stream` (optional): `ndjson` or `sse` to stream results as files complete
 ///
 /// Returns a list of extraction results, one per file.
 ///
+/// # Streaming
+///
+/// When `stream` is set, or the `Accept` header asks for `application/x-ndjson` or
+/// `text/event-stream`, each file's result or error is emitted as soon as it finishes
+/// instead of waiting for the whole batch. See [`super::streaming`] for the wire format.
+///
 /// # Size Limits
 ///
 /// Request body size limits are enforced at the router layer via `DefaultBodyLimit` and `RequestBodyLimitLayer`.
@@ -80,15 +118,21 @@ pub async fn info_handler() -> Json<InfoResponse> {
 /// If a request exceeds the size limit, it will be rejected with HTTP 413 (Payload Too Large).
 ///
 /// The server's default config (loaded from kreuzberg.toml/yaml/json via discovery)
-/// is used as the base, and any per-request config overrides those defaults.
+/// is used as the base, and per-request config fields are merged over it. The server's
+/// `config_overrides` policy limits which fields may change (403) and to which values (400).
 #[utoipa::path(
     post,
     path = "/extract",
     tag = "extraction",
     request_body(content_type = "multipart/form-data"),
     responses(
-        (status = 200, description = "Extraction successful", body = ExtractResponse),
+        (status = 200, description = "Extraction successful; streamed per file when stream=ndjson or stream=sse", content(
+            (ExtractResponse = "application/json"),
+            (ExtractStreamItem = "application/x-ndjson"),
+            (ExtractStreamItem = "text/event-stream"),
+        )),
         (status = 400, description = "Bad request", body = crate::api::types::ErrorResponse),
+        (status = 403, description = "Config override not permitted", body = crate::api::types::ErrorResponse),
         (status = 413, description = "Payload too large", body = crate::api::types::ErrorResponse),
         (status = 500, description = "Internal server error", body = crate::api::types::ErrorResponse),
     )
@@ -97,16 +141,65 @@ pub async fn info_handler() -> Json<InfoResponse> {
     feature = "otel",
     tracing::instrument(
         name = "api.extract",
-        skip(state, multipart),
+        skip(state, headers, multipart),
         fields(files_count = tracing::field::Empty)
     )
 )]
 pub async fn extract_handler(
     State(state): State<ApiState>,
-    MultipartApi(mut multipart): MultipartApi,
-) -> Result<Json<ExtractResponse>, ApiError> {
+    headers: HeaderMap,
+    MultipartApi(multipart): MultipartApi,
+) -> Result<Response, ApiError> {
+    let form = parse_extraction_form(&state, multipart).await?;
+    let files = form.files;
+
+    #[cfg(feature = "otel")]
+    tracing::Span::current().record("files_count", files.len());
+
+    if let Some(format) = form.stream.or_else(|| StreamFormat::from_accept(&headers)) {
+        let config = form.config.unwrap_or_else(|| (*state.default_config).clone());
+        return Ok(stream_extraction(files, config, format));
+    }
+
+    // Use provided config or fall back to default from state
+    let final_config = form.config.as_ref().unwrap_or(&state.default_config);
+
+    if files.len() == 1 {
+        let (data, mime_type, _file_name) = files
+            .into_iter()
+            .next()
+            .expect("files.len() == 1 guarantees one element exists");
+        let result = extract_bytes(&data, mime_type.as_str(), final_config).await?;
+        return Ok(Json(vec![result]).into_response());
+    }
+
+    let files_data: Vec<(Vec<u8>, String)> = files.into_iter().map(|(data, mime, _name)| (data, mime)).collect();
+
+    let results = batch_extract_bytes(files_data, final_config).await?;
+    Ok(Json(results).into_response())
+}
+
+/// Uploaded file: raw bytes, MIME type and optional original file name.
+type UploadedFile = (Vec<u8>, String, Option<String>);
+
+/// Multipart form shared by `POST /extract` and `POST /jobs`.
+struct ExtractionForm {
+    files: Vec<UploadedFile>,
+    config: Option<crate::core::config::ExtractionConfig>,
+    stream: Option<StreamFormat>,
+}
+
+/// Parse the multipart form shared by `POST /extract` and `POST /jobs`.
+///
+/// Fails with a validation error when no files were provided.
+async fn parse_extraction_form(
+    state: &ApiState,
+    mut multipart: axum::extract::Multipart,
+) -> Result<ExtractionForm, ApiError> {
     let mut files = Vec::new();
-    let mut config: Option<crate::core::config::ExtractionConfig> = None;
+    let mut overrides: Option<serde_json::Value> = None;
+    let mut output_format = None;
+    let mut stream = None;
 
     while let Some(field) = multipart
         .next_field()
@@ -142,7 +235,7 @@ pub async fn extract_handler(
                     .await
                     .map_err(|e| ApiError::validation(crate::error::KreuzbergError::validation(e.to_string())))?;
 
-                config = Some(serde_json::from_str(&config_str).map_err(|e| {
+                overrides = Some(serde_json::from_str(&config_str).map_err(|e| {
                     ApiError::validation(crate::error::KreuzbergError::validation(format!(
                         "Invalid extraction configuration: {}",
                         e
@@ -155,13 +248,9 @@ pub async fn extract_handler(
                     .await
                     .map_err(|e| ApiError::validation(crate::error::KreuzbergError::validation(e.to_string())))?;
 
-                // Ensure config exists before modifying output_format
-                let cfg = config.get_or_insert_with(|| (*state.default_config).clone());
-                cfg.output_format = match format_str.to_lowercase().as_str() {
-                    "plain" => crate::core::config::OutputFormat::Plain,
-                    "markdown" => crate::core::config::OutputFormat::Markdown,
-                    "djot" => crate::core::config::OutputFormat::Djot,
-                    "html" => crate::core::config::OutputFormat::Html,
+                // Applied on top of the `config` field, wherever it appears in the form
+                output_format = match format_str.to_lowercase().as_str() {
+                    format @ ("plain" | "markdown" | "djot" | "html") => Some(format.to_string()),
                     _ => {
                         return Err(ApiError::validation(crate::error::KreuzbergError::validation(format!(
                             "Invalid output_format: '{}'. Valid values: 'plain', 'markdown', 'djot', 'html'",
@@ -170,6 +259,13 @@ pub async fn extract_handler(
                     }
                 };
             }
+            "stream" => {
+                let stream_str = field
+                    .text()
+                    .await
+                    .map_err(|e| ApiError::validation(crate::error::KreuzbergError::validation(e.to_string())))?;
+                stream = Some(StreamFormat::parse(&stream_str)?);
+            }
             _ => {}
         }
     }
@@ -180,25 +276,145 @@ pub async fn extract_handler(
         )));
     }
 
+    if let Some(format) = output_format {
+        let overrides = overrides.get_or_insert_with(|| serde_json::json!({}));
+        if let Some(object) = overrides.as_object_mut() {
+            object.insert("output_format".to_string(), serde_json::Value::String(format));
+        }
+    }
+
+    let config = overrides
+        .map(|overrides| apply_config_overrides(&state.config_overrides, &state.default_config, overrides))
+        .transpose()?;
+
+    Ok(ExtractionForm { files, config, stream })
+}
+
+/// Job submission endpoint handler.
+///
+/// POST /jobs
+///
+/// Accepts the same multipart form as `POST /extract` and queues the extraction
+/// in the background. Returns `202 Accepted` with the job status; poll
+/// `GET /jobs/{id}` until the job finishes and fetch results from `GET /jobs/{id}/result`.
+///
+/// Returns 503 when the job queue is full.
+#[utoipa::path(
+    post,
+    path = "/jobs",
+    tag = "jobs",
+    request_body(content_type = "multipart/form-data"),
+    responses(
+        (status = 202, description = "Job accepted", body = JobResponse),
+        (status = 400, description = "Bad request", body = crate::api::types::ErrorResponse),
+        (status = 403, description = "Config override not permitted", body = crate::api::types::ErrorResponse),
+        (status = 413, description = "Payload too large", body = crate::api::types::ErrorResponse),
+        (status = 503, description = "Job queue is full", body = crate::api::types::ErrorResponse),
+    )
+)]
+#[cfg_attr(
+    feature = "otel",
+    tracing::instrument(
+        name = "api.submit_job",
+        skip(state, caller, multipart),
+        fields(files_count = tracing::field::Empty)
+    )
+)]
+pub async fn submit_job_handler(
+    State(state): State<ApiState>,
+    caller: Option<Extension<Caller>>,
+    MultipartApi(multipart): MultipartApi,
+) -> Result<(StatusCode, Json<JobResponse>), ApiError> {
+    let form = parse_extraction_form(&state, multipart).await?;
+
     #[cfg(feature = "otel")]
-    tracing::Span::current().record("files_count", files.len());
+    tracing::Span::current().record("files_count", form.files.len());
 
-    // Use provided config or fall back to default from state
-    let final_config = config.as_ref().unwrap_or(&state.default_config);
+    let config = form.config.unwrap_or_else(|| (*state.default_config).clone());
+    let files = form.files.into_iter().map(|(data, mime, _name)| (data, mime)).collect();
 
-    if files.len() == 1 {
-        let (data, mime_type, _file_name) = files
-            .into_iter()
-            .next()
-            .expect("files.len() == 1 guarantees one element exists");
-        let result = extract_bytes(&data, mime_type.as_str(), final_config).await?;
-        return Ok(Json(vec![result]));
-    }
+    let job = state
+        .jobs
+        .submit(files, config, caller.map(|Extension(caller)| caller))?;
+    Ok((StatusCode::ACCEPTED, Json(job)))
+}
 
-    let files_data: Vec<(Vec<u8>, String)> = files.into_iter().map(|(data, mime, _name)| (data, mime)).collect();
+/// Job status endpoint handler.
+///
+/// GET /jobs/{id}
+#[utoipa::path(
+    get,
+    path = "/jobs/{id}",
+    tag = "jobs",
+    params(("id" = String, Path, description = "Job identifier")),
+    responses(
+        (status = 200, description = "Job status", body = JobResponse),
+        (status = 404, description = "Job not found", body = crate::api::types::ErrorResponse),
+    )
+)]
+#[cfg_attr(feature = "otel", tracing::instrument(name = "api.job_status", skip(state, caller)))]
+pub async fn job_status_handler(
+    State(state): State<ApiState>,
+    caller: Option<Extension<Caller>>,
+    Path(id): Path<String>,
+) -> Result<Json<JobResponse>, ApiError> {
+    Ok(Json(state.jobs.status(&id, owner(&caller))?))
+}
 
-    let results = batch_extract_bytes(files_data, final_config).await?;
-    Ok(Json(results))
+/// Job result endpoint handler.
+///
+/// GET /jobs/{id}/result
+///
+/// Returns the extraction results of a completed job. Failed jobs return the
+/// error that stopped them; queued, running and cancelled jobs return 409.
+#[utoipa::path(
+    get,
+    path = "/jobs/{id}/result",
+    tag = "jobs",
+    params(("id" = String, Path, description = "Job identifier")),
+    responses(
+        (status = 200, description = "Extraction results", body = ExtractResponse),
+        (status = 404, description = "Job not found", body = crate::api::types::ErrorResponse),
+        (status = 409, description = "Job has not completed", body = crate::api::types::ErrorResponse),
+    )
+)]
+#[cfg_attr(feature = "otel", tracing::instrument(name = "api.job_result", skip(state, caller)))]
+pub async fn job_result_handler(
+    State(state): State<ApiState>,
+    caller: Option<Extension<Caller>>,
+    Path(id): Path<String>,
+) -> Result<Json<ExtractResponse>, ApiError> {
+    Ok(Json(state.jobs.result(&id, owner(&caller))?))
+}
+
+/// Job cancellation endpoint handler.
+///
+/// DELETE /jobs/{id}
+///
+/// Cancels a queued or running job. Deleting a finished job removes it and its
+/// stored results.
+#[utoipa::path(
+    delete,
+    path = "/jobs/{id}",
+    tag = "jobs",
+    params(("id" = String, Path, description = "Job identifier")),
+    responses(
+        (status = 200, description = "Job cancelled or deleted", body = JobResponse),
+        (status = 404, description = "Job not found", body = crate::api::types::ErrorResponse),
+    )
+)]
+#[cfg_attr(feature = "otel", tracing::instrument(name = "api.cancel_job", skip(state, caller)))]
+pub async fn cancel_job_handler(
+    State(state): State<ApiState>,
+    caller: Option<Extension<Caller>>,
+    Path(id): Path<String>,
+) -> Result<Json<JobResponse>, ApiError> {
+    Ok(Json(state.jobs.cancel_or_delete(&id, owner(&caller))?))
+}
+
+/// Owner digest of the authenticated caller, if authentication is enabled.
+fn owner(caller: &Option<Extension<Caller>>) -> Option<&str> {
+    caller.as_ref().map(|Extension(caller)| caller.owner.as_str())
 }
 
 /// Formats endpoint handler.
@@ -390,6 +606,8 @@ pub async fn embed_handler(JsonApi(request): JsonApi<EmbedRequest>) -> Result<Js
                 total_chunks: request.texts.len(),
                 first_page: None,
                 last_page: None,
+                heading_path: None,
+                node_ids: None,
             },
         })
         .collect();
@@ -480,7 +698,7 @@ pub async fn embed_handler(JsonApi(_request): JsonApi<EmbedRequest>) -> Result<J
 )]
 pub async fn chunk_handler(JsonApi(request): JsonApi<ChunkRequest>) -> Result<Json<ChunkResponse>, ApiError> {
     use super::types::{ChunkItem, ChunkingConfigResponse};
-    use crate::chunking::{ChunkerType, ChunkingConfig, chunk_text};
+    use crate::chunking::{ChunkSizing, ChunkerType, ChunkingConfig, chunk_text};
 
     // Validate input
     if request.text.is_empty() {
@@ -519,6 +737,7 @@ pub async fn chunk_handler(JsonApi(request): JsonApi<ChunkRequest>) -> Result<Js
         overlap,
         trim: cfg.trim.unwrap_or(true),
         chunker_type,
+        sizing: ChunkSizing::Characters,
         embedding: None,
         preset: None,
     };
@@ -0,0 +1,685 @@
+//! Asynchronous extraction jobs.
+//!
+//! `POST /jobs` accepts the same multipart payload as `POST /extract` but returns
+//! immediately with a job id. Jobs wait in a bounded in-process queue and are picked
+//! up by a fixed number of workers (`ServerConfig::job_concurrency`). Clients poll
+//! `GET /jobs/{id}` for status and fetch results from `GET /jobs/{id}/result`.
+//!
+//! When `ServerConfig::job_state_dir` is set, every state transition is written to
+//! `<dir>/<id>.json` so finished results survive restarts. Uploaded files are not
+//! persisted, so jobs that were queued or running when the server stopped are marked
+//! as failed on the next start.
+//!
+//! With authentication enabled every job belongs to the API key that submitted it;
+//! other keys get 404 for it. A job also holds the submitting request's concurrency
+//! permit until it finishes, so queued work counts against the key's cap.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+    },
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use axum::http::StatusCode;
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use tokio::{
+    sync::{OwnedSemaphorePermit, Semaphore},
+    task::AbortHandle,
+};
+
+use crate::{ExtractionConfig, core::ServerConfig, extract_bytes, types::ExtractionResult};
+
+use super::{
+    auth::Caller,
+    error::ApiError,
+    types::{ErrorResponse, JobProgress, JobResponse, JobStatus},
+};
+
+/// A file submitted with a job: raw bytes and MIME type.
+pub(crate) type JobFile = (Vec<u8>, String);
+
+/// Persisted representation of a job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct JobRecord {
+    info: JobResponse,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    results: Option<Vec<ExtractionResult>>,
+    /// Digest of the API key that submitted the job (see [`Caller::owner`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    owner: Option<String>,
+}
+
+#[derive(Debug)]
+struct JobEntry {
+    record: JobRecord,
+    abort: Option<AbortHandle>,
+}
+
+/// In-process queue and registry of asynchronous extraction jobs.
+///
+/// Created once per router from the [`ServerConfig`] job settings and shared
+/// through [`ApiState`](super::ApiState).
+#[derive(Debug)]
+pub struct JobManager {
+    jobs: DashMap<String, JobEntry>,
+    workers: Arc<Semaphore>,
+    queued: AtomicUsize,
+    queue_capacity: usize,
+    retention_secs: u64,
+    state_dir: Option<PathBuf>,
+    persist_lock: parking_lot::Mutex<()>,
+    id_hasher: ahash::RandomState,
+    id_counter: AtomicU64,
+}
+
+impl JobManager {
+    /// Create a job manager from the server configuration.
+    ///
+    /// If a state directory is configured, previously persisted jobs are loaded from it.
+    /// An unusable state directory is logged and the manager falls back to in-memory state.
+    pub fn new(server_config: &ServerConfig) -> Self {
+        let state_dir = server_config
+            .job_state_dir
+            .clone()
+            .and_then(|dir| match std::fs::create_dir_all(&dir) {
+                Ok(()) => Some(dir),
+                Err(e) => {
+                    tracing::warn!(
+                        "Job state directory {} is not usable, keeping job state in memory only: {}",
+                        dir.display(),
+                        e
+                    );
+                    None
+                }
+            });
+
+        let manager = Self {
+            jobs: DashMap::new(),
+            workers: Arc::new(Semaphore::new(server_config.job_concurrency.max(1))),
+            queued: AtomicUsize::new(0),
+            queue_capacity: server_config.job_queue_capacity.max(1),
+            retention_secs: server_config.job_retention_secs,
+            state_dir,
+            persist_lock: parking_lot::Mutex::new(()),
+            id_hasher: ahash::RandomState::new(),
+            id_counter: AtomicU64::new(0),
+        };
+
+        if let Some(dir) = manager.state_dir.clone() {
+            manager.load_persisted(&dir);
+        }
+
+        manager
+    }
+
+    /// Queue a new job and start processing it as soon as a worker is free.
+    ///
+    /// The job is owned by `caller` and holds its concurrency permit until it finishes.
+    /// Must be called from within a Tokio runtime.
+    pub(crate) fn submit(
+        self: &Arc<Self>,
+        files: Vec<JobFile>,
+        config: ExtractionConfig,
+        caller: Option<Caller>,
+    ) -> Result<JobResponse, ApiError> {
+        self.prune_expired();
+
+        self.queued
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |queued| {
+                (queued < self.queue_capacity).then_some(queued + 1)
+            })
+            .map_err(|_| {
+                ApiError::custom(
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    "QueueFullError",
+                    format!("Job queue is full ({} queued jobs). Retry later.", self.queue_capacity),
+                )
+            })?;
+
+        let id = self.next_id();
+        let info = JobResponse {
+            id: id.clone(),
+            status: JobStatus::Queued,
+            progress: JobProgress {
+                processed_files: 0,
+                total_files: files.len(),
+            },
+            created_at: unix_now(),
+            started_at: None,
+            finished_at: None,
+            error: None,
+        };
+
+        self.jobs.insert(
+            id.clone(),
+            JobEntry {
+                record: JobRecord {
+                    info: info.clone(),
+                    results: None,
+                    owner: caller.as_ref().map(|caller| caller.owner.clone()),
+                },
+                abort: None,
+            },
+        );
+        self.persist(&id);
+
+        let manager = Arc::clone(self);
+        let job_id = id.clone();
+        let permit = caller.and_then(|caller| caller.permit);
+        let handle = tokio::spawn(async move { manager.run(job_id, files, config, permit).await });
+
+        if let Some(mut entry) = self.jobs.get_mut(&id) {
+            entry.abort = Some(handle.abort_handle());
+        }
+
+        Ok(info)
+    }
+
+    /// Current status of a job owned by `owner`.
+    pub(crate) fn status(&self, id: &str, owner: Option<&str>) -> Result<JobResponse, ApiError> {
+        self.prune_expired();
+        self.jobs
+            .get(id)
+            .filter(|entry| entry.record.owner.as_deref() == owner)
+            .map(|entry| entry.record.info.clone())
+            .ok_or_else(|| not_found(id))
+    }
+
+    /// Results of a completed job owned by `owner`.
+    ///
+    /// Failed jobs return their original error; unfinished or cancelled jobs return 409.
+    pub(crate) fn result(&self, id: &str, owner: Option<&str>) -> Result<Vec<ExtractionResult>, ApiError> {
+        self.prune_expired();
+        let entry = self
+            .jobs
+            .get(id)
+            .filter(|entry| entry.record.owner.as_deref() == owner)
+            .ok_or_else(|| not_found(id))?;
+        let info = &entry.record.info;
+
+        match info.status {
+            JobStatus::Completed => Ok(entry.record.results.clone().unwrap_or_default()),
+            JobStatus::Failed => {
+                let body = info.error.clone().unwrap_or_else(|| ErrorResponse {
+                    error_type: "Error".to_string(),
+                    message: "Job failed".to_string(),
+                    traceback: None,
+                    status_code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+                });
+                let status = StatusCode::from_u16(body.status_code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+                Err(ApiError { status, body })
+            }
+            JobStatus::Cancelled => Err(ApiError::custom(
+                StatusCode::CONFLICT,
+                "JobCancelledError",
+                format!("Job {} was cancelled", id),
+            )),
+            JobStatus::Queued | JobStatus::Running => Err(ApiError::custom(
+                StatusCode::CONFLICT,
+                "JobNotFinishedError",
+                format!("Job {} has not finished yet", id),
+            )),
+        }
+    }
+
+    /// Cancel a queued or running job owned by `owner`, or delete a finished one.
+    ///
+    /// Cancelled jobs stay visible until their retention expires so clients can observe
+    /// the final state; finished jobs are removed together with their persisted state.
+    pub(crate) fn cancel_or_delete(&self, id: &str, owner: Option<&str>) -> Result<JobResponse, ApiError> {
+        let info = {
+            let mut entry = self
+                .jobs
+                .get_mut(id)
+                .filter(|entry| entry.record.owner.as_deref() == owner)
+                .ok_or_else(|| not_found(id))?;
+
+            if entry.record.info.status.is_finished() {
+                None
+            } else {
+                if entry.record.info.status == JobStatus::Queued {
+                    self.queued.fetch_sub(1, Ordering::SeqCst);
+                }
+                if let Some(abort) = entry.abort.take() {
+                    abort.abort();
+                }
+                let info = &mut entry.record.info;
+                info.status = JobStatus::Cancelled;
+                info.finished_at = Some(unix_now());
+                Some(info.clone())
+            }
+        };
+
+        match info {
+            Some(info) => {
+                self.persist(id);
+                Ok(info)
+            }
+            None => {
+                let (_, entry) = self.jobs.remove(id).ok_or_else(|| not_found(id))?;
+                self.remove_persisted(id);
+                Ok(entry.record.info)
+            }
+        }
+    }
+
+    async fn run(
+        self: Arc<Self>,
+        id: String,
+        files: Vec<JobFile>,
+        config: ExtractionConfig,
+        _caller_permit: Option<Arc<OwnedSemaphorePermit>>,
+    ) {
+        let Ok(_permit) = Arc::clone(&self.workers).acquire_owned().await else {
+            return;
+        };
+
+        if !self.mark_running(&id) {
+            return;
+        }
+
+        let mut results = Vec::with_capacity(files.len());
+        for (data, mime_type) in files {
+            match extract_bytes(&data, &mime_type, &config).await {
+                Ok(result) => {
+                    results.push(result);
+                    if let Some(mut entry) = self.jobs.get_mut(&id) {
+                        entry.record.info.progress.processed_files = results.len();
+                    }
+                }
+                Err(e) => {
+                    self.finish(&id, Err(ApiError::from(e).body));
+                    return;
+                }
+            }
+        }
+
+        self.finish(&id, Ok(results));
+    }
+
+    /// Transition a queued job to running. Returns `false` if it was cancelled meanwhile.
+    fn mark_running(&self, id: &str) -> bool {
+        {
+            let Some(mut entry) = self.jobs.get_mut(id) else {
+                return false;
+            };
+            if entry.record.info.status != JobStatus::Queued {
+                return false;
+            }
+            self.queued.fetch_sub(1, Ordering::SeqCst);
+            entry.record.info.status = JobStatus::Running;
+            entry.record.info.started_at = Some(unix_now());
+        }
+        self.persist(id);
+        true
+    }
+
+    fn finish(&self, id: &str, outcome: Result<Vec<ExtractionResult>, ErrorResponse>) {
+        {
+            let Some(mut entry) = self.jobs.get_mut(id) else {
+                return;
+            };
+            if entry.record.info.status != JobStatus::Running {
+                return;
+            }
+            entry.abort = None;
+            entry.record.info.finished_at = Some(unix_now());
+            match outcome {
+                Ok(results) => {
+                    entry.record.info.status = JobStatus::Completed;
+                    entry.record.results = Some(results);
+                }
+                Err(error) => {
+                    entry.record.info.status = JobStatus::Failed;
+                    entry.record.info.error = Some(error);
+                }
+            }
+        }
+        self.persist(id);
+    }
+
+    fn next_id(&self) -> String {
+        let counter = self.id_counter.fetch_add(1, Ordering::Relaxed);
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or_default();
+        format!("{:016x}{:016x}", nanos, self.id_hasher.hash_one(counter))
+    }
+
+    /// Drop finished jobs whose retention period has elapsed.
+    fn prune_expired(&self) {
+        let now = unix_now();
+        let expired: Vec<String> = self
+            .jobs
+            .iter()
+            .filter(|entry| self.is_expired(&entry.record.info, now))
+            .map(|entry| entry.key().clone())
+            .collect();
+
+        for id in expired {
+            self.jobs.remove(&id);
+            self.remove_persisted(&id);
+        }
+    }
+
+    fn is_expired(&self, info: &JobResponse, now: u64) -> bool {
+        info.status.is_finished()
+            && info
+                .finished_at
+                .is_some_and(|finished| now.saturating_sub(finished) > self.retention_secs)
+    }
+
+    fn job_path(dir: &Path, id: &str) -> PathBuf {
+        dir.join(format!("{}.json", id))
+    }
+
+    /// Write the current state of a job to the state directory, if configured.
+    fn persist(&self, id: &str) {
+        let Some(dir) = &self.state_dir else {
+            return;
+        };
+
+        let _guard = self.persist_lock.lock();
+        let Some(record) = self.jobs.get(id).map(|entry| entry.record.clone()) else {
+            return;
+        };
+
+        let path = Self::job_path(dir, id);
+        let tmp_path = path.with_extension("json.tmp");
+        let written = serde_json::to_vec(&record)
+            .map_err(std::io::Error::other)
+            .and_then(|bytes| std::fs::write(&tmp_path, bytes))
+            .and_then(|()| std::fs::rename(&tmp_path, &path));
+
+        if let Err(e) = written {
+            tracing::warn!("Failed to persist job {} to {}: {}", id, path.display(), e);
+        }
+    }
+
+    fn remove_persisted(&self, id: &str) {
+        if let Some(dir) = &self.state_dir {
+            let _guard = self.persist_lock.lock();
+            let _ = std::fs::remove_file(Self::job_path(dir, id));
+        }
+    }
+
+    fn load_persisted(&self, dir: &Path) {
+        let entries = match std::fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                tracing::warn!("Failed to read job state directory {}: {}", dir.display(), e);
+                return;
+            }
+        };
+
+        let now = unix_now();
+        for path in entries.flatten().map(|entry| entry.path()) {
+            let Some(id) = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_suffix(".json"))
+                .filter(|id| is_valid_job_id(id))
+                .map(str::to_string)
+            else {
+                continue;
+            };
+
+            // Parse through `Value` first: serialized metadata can repeat keys that are also
+            // mirrored into the flattened `additional` map (e.g. `output_format`).
+            let record = std::fs::read(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|bytes| serde_json::from_slice::<serde_json::Value>(&bytes).map_err(|e| e.to_string()))
+                .and_then(|value| serde_json::from_value::<JobRecord>(value).map_err(|e| e.to_string()));
+            let mut record = match record {
+                Ok(record) if record.info.id == id => record,
+                Ok(_) => {
+                    tracing::warn!("Ignoring job state file {} with mismatching id", path.display());
+                    continue;
+                }
+                Err(e) => {
+                    tracing::warn!("Ignoring unreadable job state file {}: {}", path.display(), e);
+                    continue;
+                }
+            };
+
+            let interrupted = !record.info.status.is_finished();
+            if interrupted {
+                record.info.status = JobStatus::Failed;
+                record.info.finished_at = Some(now);
+                record.info.error = Some(ErrorResponse {
+                    error_type: "JobInterruptedError".to_string(),
+                    message: "Job was interrupted by a server restart".to_string(),
+                    traceback: None,
+                    status_code: StatusCode::SERVICE_UNAVAILABLE.as_u16(),
+                });
+            }
+
+            if self.is_expired(&record.info, now) {
+                let _ = std::fs::remove_file(&path);
+                continue;
+            }
+
+            self.jobs.insert(id.clone(), JobEntry { record, abort: None });
+            if interrupted {
+                self.persist(&id);
+            }
+        }
+    }
+}
+
+/// Job ids are 32 lowercase hexadecimal characters.
+fn is_valid_job_id(id: &str) -> bool {
+    id.len() == 32 && id.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+fn not_found(id: &str) -> ApiError {
+    ApiError::custom(
+        StatusCode::NOT_FOUND,
+        "JobNotFoundError",
+        format!("Job {} not found", id),
+    )
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manager(config: ServerConfig) -> Arc<JobManager> {
+        Arc::new(JobManager::new(&config))
+    }
+
+    fn text_file(text: &str) -> JobFile {
+        (text.as_bytes().to_vec(), "text/plain".to_string())
+    }
+
+    async fn wait_until_finished(manager: &JobManager, id: &str) -> JobResponse {
+        wait_until_finished_for(manager, id, None).await
+    }
+
+    async fn wait_until_finished_for(manager: &JobManager, id: &str, owner: Option<&str>) -> JobResponse {
+        for _ in 0..500 {
+            let info = manager.status(id, owner).expect("job exists");
+            if info.status.is_finished() {
+                return info;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        panic!("job {} did not finish in time", id);
+    }
+
+    #[test]
+    fn test_job_ids_are_unique_and_valid() {
+        let manager = JobManager::new(&ServerConfig::default());
+        let first = manager.next_id();
+        let second = manager.next_id();
+        assert_ne!(first, second);
+        assert!(is_valid_job_id(&first));
+        assert!(!is_valid_job_id("../../etc/passwd"));
+    }
+
+    #[tokio::test]
+    async fn test_job_completes_with_results() {
+        let manager = manager(ServerConfig::default());
+        let info = manager
+            .submit(
+                vec![text_file("first"), text_file("second")],
+                ExtractionConfig::default(),
+                None,
+            )
+            .expect("job accepted");
+        assert_eq!(info.progress.total_files, 2);
+
+        let finished = wait_until_finished(&manager, &info.id).await;
+        assert_eq!(finished.status, JobStatus::Completed);
+        assert_eq!(finished.progress.processed_files, 2);
+
+        let results = manager.result(&info.id, None).expect("results available");
+        assert_eq!(results.len(), 2);
+        assert!(results[0].content.contains("first"));
+    }
+
+    #[tokio::test]
+    async fn test_failed_job_reports_error() {
+        let manager = manager(ServerConfig::default());
+        let info = manager
+            .submit(
+                vec![(b"data".to_vec(), "application/x-unknown".to_string())],
+                ExtractionConfig::default(),
+                None,
+            )
+            .expect("job accepted");
+
+        let finished = wait_until_finished(&manager, &info.id).await;
+        assert_eq!(finished.status, JobStatus::Failed);
+        assert!(finished.error.is_some());
+        assert!(manager.result(&info.id, None).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_queue_capacity_and_cancellation() {
+        let manager = manager(ServerConfig {
+            job_concurrency: 1,
+            job_queue_capacity: 1,
+            ..Default::default()
+        });
+
+        // Hold the only worker so submitted jobs stay queued.
+        let permit = Arc::clone(&manager.workers).acquire_owned().await.unwrap();
+
+        let queued = manager
+            .submit(vec![text_file("queued")], ExtractionConfig::default(), None)
+            .expect("job accepted");
+        let rejected = manager
+            .submit(vec![text_file("overflow")], ExtractionConfig::default(), None)
+            .expect_err("queue is full");
+        assert_eq!(rejected.status, StatusCode::SERVICE_UNAVAILABLE);
+
+        let cancelled = manager.cancel_or_delete(&queued.id, None).expect("job cancelled");
+        assert_eq!(cancelled.status, JobStatus::Cancelled);
+        assert_eq!(
+            manager.result(&queued.id, None).unwrap_err().status,
+            StatusCode::CONFLICT
+        );
+
+        // Cancelling frees the queue slot.
+        let accepted = manager
+            .submit(vec![text_file("next")], ExtractionConfig::default(), None)
+            .expect("job accepted after cancellation");
+        drop(permit);
+        assert_eq!(
+            wait_until_finished(&manager, &accepted.id).await.status,
+            JobStatus::Completed
+        );
+
+        // Deleting a finished job removes it.
+        manager.cancel_or_delete(&accepted.id, None).expect("job deleted");
+        assert_eq!(
+            manager.status(&accepted.id, None).unwrap_err().status,
+            StatusCode::NOT_FOUND
+        );
+    }
+
+    #[tokio::test]
+    async fn test_jobs_are_scoped_to_their_owner() {
+        let manager = manager(ServerConfig::default());
+        let semaphore = Arc::new(Semaphore::new(1));
+        let caller = Caller {
+            owner: "owner".to_string(),
+            permit: Some(Arc::new(Arc::clone(&semaphore).try_acquire_owned().unwrap())),
+        };
+        let info = manager
+            .submit(vec![text_file("owned")], ExtractionConfig::default(), Some(caller))
+            .expect("job accepted");
+
+        wait_until_finished_for(&manager, &info.id, Some("owner")).await;
+        assert_eq!(
+            semaphore.available_permits(),
+            1,
+            "permit released when the job finishes"
+        );
+
+        for other in [None, Some("other")] {
+            assert_eq!(
+                manager.status(&info.id, other).unwrap_err().status,
+                StatusCode::NOT_FOUND
+            );
+            assert_eq!(
+                manager.result(&info.id, other).unwrap_err().status,
+                StatusCode::NOT_FOUND
+            );
+            assert_eq!(
+                manager.cancel_or_delete(&info.id, other).unwrap_err().status,
+                StatusCode::NOT_FOUND
+            );
+        }
+        assert!(manager.result(&info.id, Some("owner")).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_jobs_persist_across_restarts() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ServerConfig {
+            job_state_dir: Some(dir.path().to_path_buf()),
+            ..Default::default()
+        };
+
+        let first = manager(config.clone());
+        let info = first
+            .submit(vec![text_file("persisted")], ExtractionConfig::default(), None)
+            .expect("job accepted");
+        wait_until_finished(&first, &info.id).await;
+
+        // Simulate a job that was in flight when the server stopped.
+        let interrupted_id = "0123456789abcdef0123456789abcdef";
+        let mut interrupted = first.jobs.get(&info.id).unwrap().record.clone();
+        interrupted.info.id = interrupted_id.to_string();
+        interrupted.info.status = JobStatus::Running;
+        interrupted.results = None;
+        std::fs::write(
+            JobManager::job_path(dir.path(), interrupted_id),
+            serde_json::to_vec(&interrupted).unwrap(),
+        )
+        .unwrap();
+        drop(first);
+
+        let restarted = manager(config);
+        let results = restarted.result(&info.id, None).expect("results survive restart");
+        assert!(results[0].content.contains("persisted"));
+
+        let failed = restarted.status(interrupted_id, None).unwrap();
+        assert_eq!(failed.status, JobStatus::Failed);
+        assert_eq!(failed.error.unwrap().error_type, "JobInterruptedError");
+    }
+}
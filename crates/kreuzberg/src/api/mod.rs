@@ -6,13 +6,26 @@
 //! # Endpoints
 //!
 //! - `POST /extract` - Extract text from uploaded files (multipart form data)
+//! - `POST /jobs` - Queue an asynchronous extraction job (same form data as `/extract`)
+//! - `GET /jobs/{id}` - Job status and progress
+//! - `GET /jobs/{id}/result` - Results of a completed job
+//! - `DELETE /jobs/{id}` - Cancel a running job or delete a finished one
 //! - `POST /embed` - Generate embeddings for text (JSON body with texts array)
 //! - `POST /chunk` - Chunk text into smaller pieces (JSON body with text and config)
 //! - `GET /health` - Health check endpoint
 //! - `GET /info` - Server information
+//! - `GET /metrics` - Prometheus metrics
 //! - `GET /cache/stats` - Get cache statistics
 //! - `DELETE /cache/clear` - Clear all cached files
 //!
+//! # Authentication
+//!
+//! When `ServerConfig::api_keys` (or `KREUZBERG_API_KEYS`) is set, requests must carry a
+//! key as `Authorization: Bearer <key>` or `X-API-Key: <key>`. Keys with the `extract`
+//! scope may call extraction, job, embedding and chunking endpoints; `admin` keys may
+//! also manage the cache and read metrics. Per-key rate limits and concurrency caps
+//! answer with HTTP 429. `/health`, `/info` and `/openapi.json` are always public.
+//!
 //! # Examples
 //!
 //! ## Starting the server
@@ -61,6 +74,15 @@
 //!      -F 'config={"ocr":{"language":"eng"}}' \
 //!      http://localhost:8000/extract
 //!
+//! # Stream results as each file completes (NDJSON or Server-Sent Events)
+//! curl -N -F "files=@doc1.pdf" -F "files=@doc2.docx" -F "stream=ndjson" \
+//!      http://localhost:8000/extract
+//!
+//! # Asynchronous job: submit, poll, fetch results
+//! curl -F "files=@scan.pdf" http://localhost:8000/jobs
+//! curl http://localhost:8000/jobs/<id>
+//! curl http://localhost:8000/jobs/<id>/result
+//!
 //! # Health check
 //! curl http://localhost:8000/health
 //!
@@ -84,20 +106,26 @@
 //!      -d '{"text":"Long text to chunk...","chunker_type":"text"}'
 //! ```
 
+mod auth;
 mod config;
 mod error;
 mod handlers;
+mod jobs;
 #[cfg(feature = "api")]
 pub mod openapi;
+mod overrides;
 mod router;
 mod startup;
+mod streaming;
 mod types;
 
 pub use config::load_server_config;
 pub use error::ApiError;
+pub use jobs::JobManager;
 pub use router::{create_router, create_router_with_limits, create_router_with_limits_and_server_config};
 pub use startup::{serve, serve_default, serve_with_config, serve_with_config_and_limits, serve_with_server_config};
 pub use types::{
     ApiSizeLimits, ApiState, CacheClearResponse, CacheStatsResponse, ChunkRequest, ChunkResponse, EmbedRequest,
-    EmbedResponse, ErrorResponse, ExtractResponse, HealthResponse, InfoResponse,
+    EmbedResponse, ErrorResponse, ExtractResponse, ExtractStreamItem, ExtractStreamSummary, HealthResponse,
+    InfoResponse, JobProgress, JobResponse, JobStatus, StreamFormat,
 };
@@ -32,7 +32,12 @@ use utoipa::OpenApi;
     paths(
         crate::api::handlers::health_handler,
         crate::api::handlers::info_handler,
+        crate::api::handlers::metrics_handler,
         crate::api::handlers::extract_handler,
+        crate::api::handlers::submit_job_handler,
+        crate::api::handlers::job_status_handler,
+        crate::api::handlers::job_result_handler,
+        crate::api::handlers::cancel_job_handler,
         crate::api::handlers::formats_handler,
         crate::api::handlers::cache_stats_handler,
         crate::api::handlers::cache_clear_handler,
@@ -45,6 +50,11 @@ use utoipa::OpenApi;
             crate::api::types::PluginStatus,
             crate::api::types::InfoResponse,
             crate::api::types::ErrorResponse,
+            crate::api::types::ExtractStreamItem,
+            crate::api::types::ExtractStreamSummary,
+            crate::api::types::JobStatus,
+            crate::api::types::JobProgress,
+            crate::api::types::JobResponse,
             crate::api::types::CacheStatsResponse,
             crate::api::types::CacheClearResponse,
             crate::api::types::EmbedRequest,
@@ -73,6 +83,7 @@ use utoipa::OpenApi;
     tags(
         (name = "health", description = "Health and status endpoints"),
         (name = "extraction", description = "Document extraction endpoints"),
+        (name = "jobs", description = "Asynchronous extraction jobs"),
         (name = "cache", description = "Cache management endpoints"),
         (name = "embeddings", description = "Text embedding generation"),
         (name = "chunking", description = "Text chunking operations")
@@ -133,8 +144,11 @@ mod tests {
         // Health endpoints
         assert!(schema.contains("/health"));
         assert!(schema.contains("/info"));
+        assert!(schema.contains("/metrics"));
         // Extraction
         assert!(schema.contains("/extract"));
+        // Jobs
+        assert!(schema.contains("/jobs/{id}/result"));
         // Cache
         assert!(schema.contains("/cache/stats"));
         assert!(schema.contains("/cache/clear"));
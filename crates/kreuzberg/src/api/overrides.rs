@@ -0,0 +1,318 @@
+//! Per-request extraction config overrides.
+//!
+//! The `config` form field of `/extract` and `/jobs` holds a partial `ExtractionConfig`
+//! that is deep-merged over the server default: nested objects are merged key by key, any
+//! other value replaces the default.
+//!
+//! The server's [`ConfigOverridePolicy`] is enforced on the fields that actually change.
+//! Changes are found by comparing the canonical serialization of the merged config with
+//! that of the default (after materializing any sections the request creates), so serde
+//! aliases such as `chunking.max_characters` cannot be used to slip past the policy.
+//! Sections that are unset in the default and created by the request (even as `{}`)
+//! are checked as well, since creating one switches its feature on.
+
+use std::collections::BTreeSet;
+
+use axum::http::StatusCode;
+use serde_json::Value;
+
+use crate::{ExtractionConfig, core::ConfigOverridePolicy, error::KreuzbergError};
+
+use super::error::ApiError;
+
+/// Merge `overrides` over `base` and check the result against `policy`.
+///
+/// Returns 400 for malformed configs or out-of-bounds values and 403 when a field the
+/// policy does not allow was changed.
+pub(crate) fn apply_config_overrides(
+    policy: &ConfigOverridePolicy,
+    base: &ExtractionConfig,
+    overrides: Value,
+) -> Result<ExtractionConfig, ApiError> {
+    if !overrides.is_object() {
+        return Err(ApiError::validation(KreuzbergError::validation(
+            "Invalid extraction configuration: expected a JSON object",
+        )));
+    }
+
+    let base_json = to_json(base)?;
+    let mut merged_json = base_json.clone();
+    deep_merge(&mut merged_json, &overrides);
+    let merged: ExtractionConfig = serde_json::from_value(merged_json).map_err(|e| {
+        ApiError::validation(KreuzbergError::validation(format!(
+            "Invalid extraction configuration: {}",
+            e
+        )))
+    })?;
+
+    if policy.is_unrestricted() {
+        return Ok(merged);
+    }
+
+    // Sections created by the request start from their own defaults; those defaults are
+    // not changes made by the client.
+    let mut skeleton_json = base_json.clone();
+    deep_merge(&mut skeleton_json, &structure_of(&overrides));
+    let before = serde_json::from_value::<ExtractionConfig>(skeleton_json)
+        .map_or_else(|_| to_json(base), |skeleton| to_json(&skeleton))?;
+    let after = to_json(&merged)?;
+
+    let mut created = Vec::new();
+    collect_created_sections(&base_json, &after, "", &mut created);
+    created.retain(|path| !is_default_subsection(&base_json, path));
+    let mut changes = Vec::new();
+    collect_changes(&before, &after, "", &mut changes);
+
+    let denied = created
+        .iter()
+        .find(|path| !policy.allows_section(path))
+        .or_else(|| changes.iter().find(|path| !policy.allows(path)));
+    if let Some(path) = denied {
+        return Err(ApiError::custom(
+            StatusCode::FORBIDDEN,
+            "ConfigOverrideDeniedError",
+            format!("Overriding '{}' is not permitted by the server configuration", path),
+        ));
+    }
+
+    for (path, bounds) in &policy.bounds {
+        let value = lookup(&after, path);
+        if value == lookup(&before, path) {
+            continue;
+        }
+        if let Some(number) = value.and_then(Value::as_f64)
+            && !bounds.contains(number)
+        {
+            return Err(ApiError::validation(KreuzbergError::validation(format!(
+                "Value {} for '{}' is outside the allowed range [{}, {}]",
+                number,
+                path,
+                bounds.min.map_or_else(|| "-inf".to_string(), |min| min.to_string()),
+                bounds.max.map_or_else(|| "inf".to_string(), |max| max.to_string()),
+            ))));
+        }
+    }
+
+    Ok(merged)
+}
+
+fn to_json(config: &ExtractionConfig) -> Result<Value, ApiError> {
+    serde_json::to_value(config).map_err(|e| {
+        ApiError::internal(KreuzbergError::Other(format!(
+            "Failed to serialize extraction configuration: {}",
+            e
+        )))
+    })
+}
+
+/// Merge `overlay` into `target`, recursing into objects present on both sides.
+fn deep_merge(target: &mut Value, overlay: &Value) {
+    match (target, overlay) {
+        (Value::Object(target), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match target.get_mut(key) {
+                    Some(existing) if existing.is_object() && value.is_object() => deep_merge(existing, value),
+                    _ => {
+                        target.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (target, overlay) => *target = overlay.clone(),
+    }
+}
+
+/// The object structure of `value` with every non-object leaf removed.
+fn structure_of(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .filter(|(_, value)| value.is_object())
+                .map(|(key, value)| (key.clone(), structure_of(value)))
+                .collect(),
+        ),
+        _ => Value::Null,
+    }
+}
+
+/// Record the dotted paths of objects in `after` that are absent or `null` in `before`.
+fn collect_created_sections(before: &Value, after: &Value, path: &str, created: &mut Vec<String>) {
+    let Value::Object(new) = after else {
+        return;
+    };
+    for (key, value) in new.iter().filter(|(_, value)| value.is_object()) {
+        let child = if path.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", path, key)
+        };
+        let old = before.get(key).unwrap_or(&Value::Null);
+        if old.is_null() {
+            created.push(child.clone());
+        }
+        collect_created_sections(old, value, &child, created);
+    }
+}
+
+/// Whether the section at `path` is part of its parent's defaults, i.e. creating the
+/// parent as `{}` already creates it.
+fn is_default_subsection(base: &Value, path: &str) -> bool {
+    let Some((parent, _)) = path.rsplit_once('.') else {
+        return false;
+    };
+    let empty_parent = parent
+        .rsplit('.')
+        .fold(Value::Object(Default::default()), |value, key| {
+            Value::Object([(key.to_string(), value)].into_iter().collect())
+        });
+    let mut skeleton = base.clone();
+    deep_merge(&mut skeleton, &empty_parent);
+    serde_json::from_value::<ExtractionConfig>(skeleton)
+        .ok()
+        .and_then(|config| serde_json::to_value(&config).ok())
+        .is_some_and(|value| lookup(&value, path).is_some_and(Value::is_object))
+}
+
+/// Record the dotted paths at which `before` and `after` differ.
+///
+/// Objects are compared key by key; a missing key counts as `null`.
+fn collect_changes(before: &Value, after: &Value, path: &str, changes: &mut Vec<String>) {
+    match (before, after) {
+        (Value::Object(old), Value::Object(new)) => {
+            let keys: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+            for key in keys {
+                let child = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                collect_changes(
+                    old.get(key).unwrap_or(&Value::Null),
+                    new.get(key).unwrap_or(&Value::Null),
+                    &child,
+                    changes,
+                );
+            }
+        }
+        _ if before != after => changes.push(path.to_string()),
+        _ => {}
+    }
+}
+
+fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(value, |value, key| value.get(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde_json::json;
+
+    use crate::core::ValueBounds;
+
+    use super::*;
+
+    fn policy(allowed: &[&str], denied: &[&str]) -> ConfigOverridePolicy {
+        ConfigOverridePolicy {
+            allowed_fields: allowed.iter().map(|s| s.to_string()).collect(),
+            denied_fields: denied.iter().map(|s| s.to_string()).collect(),
+            bounds: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_overrides_merge_over_server_default() {
+        let base = ExtractionConfig {
+            use_cache: false,
+            ..Default::default()
+        };
+        let config = apply_config_overrides(
+            &ConfigOverridePolicy::default(),
+            &base,
+            json!({"ocr": {"language": "deu"}}),
+        )
+        .unwrap();
+
+        assert!(!config.use_cache);
+        assert_eq!(config.ocr.unwrap().language, "deu");
+    }
+
+    #[test]
+    fn test_allowlist_ignores_defaults_of_new_sections() {
+        let policy = policy(&["ocr.language", "output_format"], &[]);
+        let base = ExtractionConfig::default();
+
+        assert!(apply_config_overrides(&policy, &base, json!({"ocr": {"language": "fra"}})).is_ok());
+        assert!(apply_config_overrides(&policy, &base, json!({"output_format": "markdown"})).is_ok());
+
+        let err = apply_config_overrides(&policy, &base, json!({"force_ocr": true})).unwrap_err();
+        assert_eq!(err.status, StatusCode::FORBIDDEN);
+        assert!(err.body.message.contains("force_ocr"));
+    }
+
+    #[test]
+    fn test_denylist_covers_aliases_and_parents() {
+        let policy = policy(&[], &["chunking.max_chars", "force_ocr"]);
+        let base = ExtractionConfig::default();
+
+        let err = apply_config_overrides(&policy, &base, json!({"chunking": {"max_characters": 10}})).unwrap_err();
+        assert_eq!(err.status, StatusCode::FORBIDDEN);
+        assert!(apply_config_overrides(&policy, &base, json!({"chunking": {"max_overlap": 10}})).is_ok());
+        // Re-sending the current value is not a change.
+        assert!(apply_config_overrides(&policy, &base, json!({"force_ocr": false})).is_ok());
+    }
+
+    #[test]
+    fn test_denied_sections_cannot_be_created_empty() {
+        let policy = policy(&[], &["images", "chunking.embedding"]);
+        let base = ExtractionConfig::default();
+
+        let err = apply_config_overrides(&policy, &base, json!({"images": {}})).unwrap_err();
+        assert_eq!(err.status, StatusCode::FORBIDDEN);
+        assert!(err.body.message.contains("'images'"));
+
+        let err = apply_config_overrides(&policy, &base, json!({"chunking": {"embedding": {}}})).unwrap_err();
+        assert_eq!(err.status, StatusCode::FORBIDDEN);
+        assert!(err.body.message.contains("chunking.embedding"));
+
+        // Creating the parent section does not switch on the denied child.
+        assert!(apply_config_overrides(&policy, &base, json!({"chunking": {}})).is_ok());
+    }
+
+    #[test]
+    fn test_allowlist_rejects_sections_without_allowed_fields() {
+        let policy = policy(&["ocr.language"], &[]);
+        let base = ExtractionConfig::default();
+
+        let err = apply_config_overrides(&policy, &base, json!({"images": {}})).unwrap_err();
+        assert_eq!(err.status, StatusCode::FORBIDDEN);
+        assert!(apply_config_overrides(&policy, &base, json!({"ocr": {}})).is_ok());
+    }
+
+    #[test]
+    fn test_bounds_reject_out_of_range_values() {
+        let mut policy = ConfigOverridePolicy::default();
+        policy.bounds.insert(
+            "chunking.max_chars".to_string(),
+            ValueBounds {
+                min: Some(100.0),
+                max: Some(4000.0),
+            },
+        );
+        let base = ExtractionConfig::default();
+
+        assert!(apply_config_overrides(&policy, &base, json!({"chunking": {"max_chars": 2000}})).is_ok());
+        let err = apply_config_overrides(&policy, &base, json!({"chunking": {"max_chars": 50000}})).unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+        assert!(err.body.message.contains("chunking.max_chars"));
+    }
+
+    #[test]
+    fn test_invalid_overrides_are_rejected() {
+        let policy = ConfigOverridePolicy::default();
+        let base = ExtractionConfig::default();
+        assert!(apply_config_overrides(&policy, &base, json!([1, 2])).is_err());
+        assert!(apply_config_overrides(&policy, &base, json!({"use_cache": "yes"})).is_err());
+    }
+}
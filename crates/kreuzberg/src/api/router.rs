@@ -1,10 +1,12 @@
 //! API router setup and configuration.
 
-use std::sync::Arc;
+use std::{sync::Arc, time::Instant};
 
 use axum::{
     Router,
-    extract::DefaultBodyLimit,
+    extract::{DefaultBodyLimit, MatchedPath, Request},
+    middleware::{self, Next},
+    response::Response,
     routing::{delete, get, post},
 };
 use tower_http::{
@@ -16,10 +18,13 @@ use tower_http::{
 use crate::{ExtractionConfig, core::ServerConfig};
 
 use super::{
+    auth::{AuthState, authenticate},
     handlers::{
-        cache_clear_handler, cache_stats_handler, chunk_handler, embed_handler, extract_handler, formats_handler,
-        health_handler, info_handler,
+        cache_clear_handler, cache_stats_handler, cancel_job_handler, chunk_handler, embed_handler, extract_handler,
+        formats_handler, health_handler, info_handler, job_result_handler, job_status_handler, metrics_handler,
+        submit_job_handler,
     },
+    jobs::JobManager,
     types::{ApiSizeLimits, ApiState},
 };
 
@@ -119,8 +124,23 @@ pub fn create_router_with_limits_and_server_config(
     limits: ApiSizeLimits,
     server_config: ServerConfig,
 ) -> Router {
+    let auth = Arc::new(AuthState::new(&server_config));
+    if auth.enabled() {
+        tracing::info!(
+            "API key authentication enabled for {} key(s)",
+            server_config.api_keys.len()
+        );
+    } else if server_config.host != "127.0.0.1" && server_config.host != "localhost" {
+        tracing::warn!(
+            "API key authentication is disabled. Any client that can reach the server may submit work \
+             and clear the cache. Set KREUZBERG_API_KEYS or `api_keys` in the server config."
+        );
+    }
+
     let state = ApiState {
         default_config: Arc::new(config),
+        config_overrides: Arc::new(server_config.config_overrides.clone()),
+        jobs: Arc::new(JobManager::new(&server_config)),
     };
 
     // CORS configuration based on ServerConfig
@@ -155,11 +175,15 @@ pub fn create_router_with_limits_and_server_config(
 
     let mut router = Router::new()
         .route("/extract", post(extract_handler))
+        .route("/jobs", post(submit_job_handler))
+        .route("/jobs/{id}", get(job_status_handler).delete(cancel_job_handler))
+        .route("/jobs/{id}/result", get(job_result_handler))
         .route("/embed", post(embed_handler))
         .route("/chunk", post(chunk_handler))
         .route("/formats", get(formats_handler))
         .route("/health", get(health_handler))
         .route("/info", get(info_handler))
+        .route("/metrics", get(metrics_handler))
         .route("/cache/stats", get(cache_stats_handler))
         .route("/cache/clear", delete(cache_clear_handler));
 
@@ -169,7 +193,10 @@ pub fn create_router_with_limits_and_server_config(
         router = router.route("/openapi.json", get(openapi_schema_handler));
     }
 
+    // Route layers run inside CORS so preflight requests never need credentials.
     router
+        .route_layer(middleware::from_fn_with_state(auth, authenticate))
+        .route_layer(middleware::from_fn(record_request_metrics))
         .layer(DefaultBodyLimit::max(limits.max_request_body_bytes))
         .layer(RequestBodyLimitLayer::new(limits.max_request_body_bytes))
         .layer(cors_layer)
@@ -177,6 +204,31 @@ pub fn create_router_with_limits_and_server_config(
         .with_state(state)
 }
 
+/// Record request count and latency per matched route.
+///
+/// Uses the route template (e.g. `/jobs/{id}`) rather than the raw path so label
+/// cardinality stays bounded. Requests that match no route are not recorded.
+async fn record_request_metrics(request: Request, next: Next) -> Response {
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string());
+    let method = request.method().clone();
+    let start = Instant::now();
+
+    let response = next.run(request).await;
+
+    if let Some(route) = route {
+        crate::metrics::global().record_http_request(
+            method.as_str(),
+            &route,
+            response.status().as_u16(),
+            start.elapsed(),
+        );
+    }
+    response
+}
+
 /// OpenAPI schema handler.
 ///
 /// Returns the OpenAPI 3.1 JSON schema for all documented endpoints.
@@ -226,6 +226,10 @@ pub async fn serve_with_server_config(extraction_config: ExtractionConfig, serve
         server_config.max_multipart_field_bytes,
     );
 
+    if let Some(backend) = server_config.cache_backend.or(extraction_config.cache_backend) {
+        crate::cache::set_default_backend(backend);
+    }
+
     let addr = SocketAddr::new(ip, server_config.port);
     let app = create_router_with_limits_and_server_config(extraction_config, limits, server_config.clone());
 
@@ -0,0 +1,263 @@
+//! Streaming responses for multi-file extraction.
+//!
+//! By default `POST /extract` answers with a single JSON array once every file has been
+//! processed. Clients can opt into a streamed response instead, in which each file's
+//! result (or error) is emitted as soon as it completes, in completion order:
+//!
+//! - **NDJSON** (`application/x-ndjson`): one [`ExtractStreamItem`] per line.
+//! - **Server-Sent Events** (`text/event-stream`): `result` and `error` events carrying an
+//!   [`ExtractStreamItem`], followed by a final `done` event with an [`ExtractStreamSummary`].
+//!
+//! Streaming is requested with the `stream` form field (`ndjson` or `sse`) or through the
+//! `Accept` header. As with the FFI `kreuzberg_extract_batch_streaming`, a failing file
+//! does not stop the remaining files. When the client disconnects, pending extractions
+//! are aborted.
+
+use std::{collections::HashMap, convert::Infallible, sync::Arc, time::Instant};
+
+use axum::{
+    body::{Body, Bytes},
+    http::{HeaderMap, HeaderValue, header},
+    response::{
+        IntoResponse, Response,
+        sse::{Event, KeepAlive, Sse},
+    },
+};
+use tokio::{
+    sync::{Semaphore, mpsc},
+    task::JoinSet,
+};
+use tokio_stream::{StreamExt, wrappers::ReceiverStream};
+
+use crate::{ExtractionConfig, error::KreuzbergError, extract_bytes};
+
+use super::{
+    error::ApiError,
+    types::{ExtractStreamItem, ExtractStreamSummary, StreamFormat},
+};
+
+/// Number of finished items buffered before extraction waits for the client to catch up.
+const STREAM_BUFFER: usize = 16;
+
+/// Messages produced by the extraction task.
+enum StreamMessage {
+    Item(Box<ExtractStreamItem>),
+    Done(ExtractStreamSummary),
+}
+
+impl StreamFormat {
+    /// Parse the value of the `stream` form field.
+    pub(crate) fn parse(value: &str) -> Result<Self, ApiError> {
+        match value.trim().to_lowercase().as_str() {
+            "ndjson" | "jsonl" => Ok(Self::Ndjson),
+            "sse" => Ok(Self::Sse),
+            other => Err(ApiError::validation(KreuzbergError::validation(format!(
+                "Invalid stream: '{}'. Valid values: 'ndjson', 'sse'",
+                other
+            )))),
+        }
+    }
+
+    /// Detect a streaming format requested through the `Accept` header.
+    pub(crate) fn from_accept(headers: &HeaderMap) -> Option<Self> {
+        let accept = headers.get(header::ACCEPT)?.to_str().ok()?;
+        accept
+            .split(',')
+            .map(|media| media.split(';').next().unwrap_or("").trim().to_lowercase())
+            .find_map(|media| match media.as_str() {
+                "application/x-ndjson" | "application/jsonl" => Some(Self::Ndjson),
+                "text/event-stream" => Some(Self::Sse),
+                _ => None,
+            })
+    }
+}
+
+/// Extract `files` concurrently and stream each outcome in the requested format.
+///
+/// Files are `(bytes, mime_type, file_name)` tuples. Concurrency follows
+/// `ExtractionConfig::max_concurrent_extractions`, as in `batch_extract_bytes`.
+pub(crate) fn stream_extraction(
+    files: Vec<(Vec<u8>, String, Option<String>)>,
+    config: ExtractionConfig,
+    format: StreamFormat,
+) -> Response {
+    let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+    tokio::spawn(run_extractions(files, Arc::new(config), tx));
+
+    let messages = ReceiverStream::new(rx);
+    match format {
+        StreamFormat::Ndjson => {
+            let lines = messages.filter_map(|message| match message {
+                StreamMessage::Item(item) => Some(Ok::<_, Infallible>(encode_ndjson(&item))),
+                StreamMessage::Done(_) => None,
+            });
+            let mut response = Body::from_stream(lines).into_response();
+            response
+                .headers_mut()
+                .insert(header::CONTENT_TYPE, HeaderValue::from_static("application/x-ndjson"));
+            response
+        }
+        StreamFormat::Sse => {
+            let events = messages.map(|message| match message {
+                StreamMessage::Item(item) => {
+                    let name = if item.error.is_some() { "error" } else { "result" };
+                    Event::default().event(name).id(item.index.to_string()).json_data(&item)
+                }
+                StreamMessage::Done(summary) => Event::default().event("done").json_data(summary),
+            });
+            Sse::new(events).keep_alive(KeepAlive::default()).into_response()
+        }
+    }
+}
+
+async fn run_extractions(
+    files: Vec<(Vec<u8>, String, Option<String>)>,
+    config: Arc<ExtractionConfig>,
+    tx: mpsc::Sender<StreamMessage>,
+) {
+    let total = files.len();
+    let max_concurrent = config
+        .max_concurrent_extractions
+        .unwrap_or_else(|| (num_cpus::get() as f64 * 1.5).ceil() as usize);
+    let semaphore = Arc::new(Semaphore::new(max_concurrent.max(1)));
+
+    let mut tasks = JoinSet::new();
+    let mut task_files = HashMap::with_capacity(total);
+
+    for (index, (data, mime_type, file_name)) in files.into_iter().enumerate() {
+        let config = Arc::clone(&config);
+        let semaphore = Arc::clone(&semaphore);
+
+        let handle = tasks.spawn(async move {
+            let _permit = semaphore.acquire().await;
+            let start = Instant::now();
+            let mut result =
+                crate::core::batch_mode::with_batch_mode(async { extract_bytes(&data, &mime_type, &config).await })
+                    .await;
+            if let Ok(ref mut r) = result {
+                r.metadata.extraction_duration_ms = Some(start.elapsed().as_millis() as u64);
+            }
+            result
+        });
+        task_files.insert(handle.id(), (index, file_name));
+    }
+
+    let mut summary = ExtractStreamSummary {
+        total,
+        succeeded: 0,
+        failed: 0,
+    };
+
+    while let Some(joined) = tasks.join_next_with_id().await {
+        let (id, outcome) = match joined {
+            Ok((id, outcome)) => (id, outcome),
+            Err(join_err) => (
+                join_err.id(),
+                Err(KreuzbergError::Other(format!("Task panicked: {}", join_err))),
+            ),
+        };
+        let (index, file_name) = task_files.remove(&id).unwrap_or_default();
+
+        let item = match outcome {
+            Ok(result) => {
+                summary.succeeded += 1;
+                ExtractStreamItem {
+                    index,
+                    file_name,
+                    result: Some(result),
+                    error: None,
+                }
+            }
+            Err(e) => {
+                summary.failed += 1;
+                ExtractStreamItem {
+                    index,
+                    file_name,
+                    result: None,
+                    error: Some(ApiError::from(e).body),
+                }
+            }
+        };
+
+        // The client went away: dropping the JoinSet aborts the remaining extractions.
+        if tx.send(StreamMessage::Item(Box::new(item))).await.is_err() {
+            return;
+        }
+    }
+
+    let _ = tx.send(StreamMessage::Done(summary)).await;
+}
+
+fn encode_ndjson(item: &ExtractStreamItem) -> Bytes {
+    let mut line = serde_json::to_vec(item).unwrap_or_else(|e| {
+        let fallback = ExtractStreamItem {
+            index: item.index,
+            file_name: item.file_name.clone(),
+            result: None,
+            error: Some(ApiError::internal(KreuzbergError::Other(format!("Failed to serialize result: {}", e))).body),
+        };
+        serde_json::to_vec(&fallback).unwrap_or_default()
+    });
+    line.push(b'\n');
+    Bytes::from(line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(accept: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT, HeaderValue::from_str(accept).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_stream_format_from_accept() {
+        assert_eq!(
+            StreamFormat::from_accept(&headers("application/x-ndjson")),
+            Some(StreamFormat::Ndjson)
+        );
+        assert_eq!(
+            StreamFormat::from_accept(&headers("application/json, text/event-stream;q=0.9")),
+            Some(StreamFormat::Sse)
+        );
+        assert_eq!(StreamFormat::from_accept(&headers("application/json")), None);
+        assert_eq!(StreamFormat::from_accept(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn test_stream_format_parse() {
+        assert_eq!(StreamFormat::parse("NDJSON").unwrap(), StreamFormat::Ndjson);
+        assert_eq!(StreamFormat::parse("sse").unwrap(), StreamFormat::Sse);
+        assert!(StreamFormat::parse("xml").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_run_extractions_reports_each_file() {
+        let (tx, mut rx) = mpsc::channel(STREAM_BUFFER);
+        let files = vec![
+            (b"first".to_vec(), "text/plain".to_string(), Some("a.txt".to_string())),
+            (b"data".to_vec(), "application/x-unknown".to_string(), None),
+        ];
+        run_extractions(files, Arc::new(ExtractionConfig::default()), tx).await;
+
+        let mut items = Vec::new();
+        let mut summary = None;
+        while let Some(message) = rx.recv().await {
+            match message {
+                StreamMessage::Item(item) => items.push(*item),
+                StreamMessage::Done(done) => summary = Some(done),
+            }
+        }
+
+        items.sort_by_key(|item| item.index);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].file_name.as_deref(), Some("a.txt"));
+        assert!(items[0].result.is_some());
+        assert!(items[1].error.is_some());
+
+        let summary = summary.expect("summary is sent last");
+        assert_eq!((summary.total, summary.succeeded, summary.failed), (2, 1, 1));
+    }
+}
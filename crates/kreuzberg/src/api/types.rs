@@ -5,6 +5,8 @@ use std::sync::Arc;
 
 use crate::{ExtractionConfig, types::ExtractionResult};
 
+use super::jobs::JobManager;
+
 /// API server size limit configuration.
 ///
 /// Controls maximum sizes for request bodies and multipart uploads.
@@ -152,6 +154,46 @@ pub struct InfoResponse {
 /// Extraction response (list of results).
 pub type ExtractResponse = Vec<ExtractionResult>;
 
+/// Wire format for streamed extraction responses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamFormat {
+    /// Newline-delimited JSON (`application/x-ndjson`), one item per line
+    Ndjson,
+    /// Server-Sent Events (`text/event-stream`)
+    Sse,
+}
+
+/// A single file outcome emitted by a streamed `POST /extract`.
+///
+/// Exactly one of `result` or `error` is set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub struct ExtractStreamItem {
+    /// Zero-based position of the file in the upload
+    pub index: usize,
+    /// Original file name, if provided by the client
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_name: Option<String>,
+    /// Extraction result for successfully processed files
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<ExtractionResult>,
+    /// Error details for files that failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorResponse>,
+}
+
+/// Final summary event of a Server-Sent Events extraction stream.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub struct ExtractStreamSummary {
+    /// Number of files in the request
+    pub total: usize,
+    /// Number of files extracted successfully
+    pub succeeded: usize,
+    /// Number of files that failed
+    pub failed: usize,
+}
+
 /// Error response.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
@@ -173,11 +215,74 @@ pub struct ErrorResponse {
 /// API server state.
 ///
 /// Holds the default extraction configuration loaded from config file
-/// (via discovery or explicit path). Per-request configs override these defaults.
+/// (via discovery or explicit path). Per-request configs override these defaults
+/// within the limits of `config_overrides`.
 #[derive(Debug, Clone)]
 pub struct ApiState {
     /// Default extraction configuration
     pub default_config: Arc<ExtractionConfig>,
+    /// Fields and values clients may override per request
+    pub config_overrides: Arc<crate::core::ConfigOverridePolicy>,
+    /// Background job queue used by the `/jobs` endpoints
+    pub jobs: Arc<JobManager>,
+}
+
+/// Lifecycle state of an asynchronous extraction job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    /// Waiting for a free worker
+    Queued,
+    /// Currently being extracted
+    Running,
+    /// Finished successfully; results are available
+    Completed,
+    /// Finished with an error
+    Failed,
+    /// Cancelled before it finished
+    Cancelled,
+}
+
+impl JobStatus {
+    /// Whether the job has reached a terminal state.
+    pub fn is_finished(self) -> bool {
+        matches!(self, Self::Completed | Self::Failed | Self::Cancelled)
+    }
+}
+
+/// Progress of an asynchronous extraction job.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub struct JobProgress {
+    /// Number of files already extracted
+    pub processed_files: usize,
+    /// Total number of files submitted with the job
+    pub total_files: usize,
+}
+
+/// Status of an asynchronous extraction job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub struct JobResponse {
+    /// Job identifier
+    #[cfg_attr(feature = "api", schema(example = "0000018f2b1c3a4d9e8f7a6b5c4d3e2f"))]
+    pub id: String,
+    /// Current job status
+    pub status: JobStatus,
+    /// Extraction progress
+    pub progress: JobProgress,
+    /// Submission time (seconds since the Unix epoch)
+    pub created_at: u64,
+    /// Time the job started running (seconds since the Unix epoch)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<u64>,
+    /// Time the job finished (seconds since the Unix epoch)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<u64>,
+    /// Error details for failed jobs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorResponse>,
 }
 
 /// Cache statistics response.
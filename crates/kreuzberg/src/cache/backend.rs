@@ -0,0 +1,230 @@
+//! Storage backends for [`GenericCache`](super::GenericCache).
+//!
+//! A backend stores opaque byte blobs under string keys and is responsible for expiring
+//! and evicting them according to its [`CacheLimits`]. Three implementations ship with
+//! the crate:
+//!
+//! - [`FilesystemBackend`](super::FilesystemBackend): one `.msgpack` file (plus an optional
+//!   `.meta` file) per key in a flat directory. The historical layout and the default.
+//! - [`ShardedBackend`](super::ShardedBackend): entries addressed by the SHA-256 of their
+//!   key in a two-level sharded tree, written atomically so several processes (or hosts on
+//!   a shared volume) can use the same directory.
+//! - `SqliteBackend` (feature `cache-sqlite`): a single SQLite database with indexed
+//!   age and LRU eviction, for caches with hundreds of thousands of entries.
+//!
+//! The backend used by the built-in caches (OCR, embeddings, extraction results) is chosen
+//! with [`set_default_backend`], `ServerConfig::cache_backend` or the
+//! `KREUZBERG_CACHE_BACKEND` environment variable. `ExtractionConfig::cache_backend`
+//! overrides it for the embedding and extraction result caches of an extraction.
+
+use crate::error::{KreuzbergError, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::RwLock;
+use std::time::UNIX_EPOCH;
+
+use super::core::CacheStats;
+
+/// Storage backend of a cache.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheBackendType {
+    /// One file per entry in a flat directory.
+    #[default]
+    Filesystem,
+    /// Single-file SQLite database (requires the `cache-sqlite` feature).
+    Sqlite,
+    /// Content-addressed sharded directory, safe for concurrent multi-process access.
+    Sharded,
+}
+
+impl CacheBackendType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Filesystem => "filesystem",
+            Self::Sqlite => "sqlite",
+            Self::Sharded => "sharded",
+        }
+    }
+}
+
+impl fmt::Display for CacheBackendType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for CacheBackendType {
+    type Err = KreuzbergError;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "filesystem" | "fs" => Ok(Self::Filesystem),
+            "sqlite" => Ok(Self::Sqlite),
+            "sharded" => Ok(Self::Sharded),
+            other => Err(KreuzbergError::validation(format!(
+                "Invalid cache backend '{}'. Must be 'filesystem', 'sqlite' or 'sharded'.",
+                other
+            ))),
+        }
+    }
+}
+
+/// Expiry and eviction limits of a cache.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CacheLimits {
+    /// Entries older than this are treated as missing and removed on cleanup.
+    pub max_age_days: f64,
+    /// Cleanup evicts entries until the cache is below this size: the oldest written first
+    /// for the filesystem and sharded backends, the least recently used first for SQLite.
+    pub max_size_mb: f64,
+    /// Cleanup evicts more aggressively when less disk space than this is available.
+    pub min_free_space_mb: f64,
+}
+
+impl CacheLimits {
+    pub fn new(max_age_days: f64, max_size_mb: f64, min_free_space_mb: f64) -> Self {
+        Self {
+            max_age_days,
+            max_size_mb,
+            min_free_space_mb,
+        }
+    }
+
+    pub(super) fn max_age_secs(&self) -> f64 {
+        self.max_age_days * 24.0 * 3600.0
+    }
+}
+
+/// Storage for a [`GenericCache`](super::GenericCache).
+///
+/// Implementations must be safe to share between threads. Read failures should be
+/// reported as a miss rather than an error where possible, since callers treat the cache
+/// as an optional optimization.
+pub trait CacheBackend: Send + Sync {
+    /// The kind of this backend.
+    fn backend_type(&self) -> CacheBackendType;
+
+    /// Directory holding the backend's data.
+    fn location(&self) -> &Path;
+
+    /// Look up `cache_key`.
+    ///
+    /// When `source_file` is given and the entry was stored with a source file, the entry
+    /// is only returned if that file's size and modification time are unchanged.
+    fn get(&self, cache_key: &str, source_file: Option<&str>) -> Result<Option<Vec<u8>>>;
+
+    /// Store `data` under `cache_key`, remembering the state of `source_file` if given.
+    fn set(&self, cache_key: &str, data: &[u8], source_file: Option<&str>) -> Result<()>;
+
+    /// Remove `cache_key`, returning whether an entry existed.
+    fn remove(&self, cache_key: &str) -> Result<bool>;
+
+    /// Remove every entry, returning the number removed and the space freed in MB.
+    fn clear(&self) -> Result<(usize, f64)>;
+
+    /// Entry count, size and age statistics.
+    fn stats(&self) -> Result<CacheStats>;
+
+    /// Remove expired entries and evict old ones when the cache exceeds its limits,
+    /// returning the number removed and the space freed in MB.
+    fn cleanup(&self) -> Result<(usize, f64)>;
+}
+
+/// Size and modification time of the file a cache entry was derived from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct SourceStamp {
+    pub(super) size: u64,
+    pub(super) mtime: u64,
+}
+
+impl SourceStamp {
+    pub(super) const LEN: usize = 16;
+
+    /// Current stamp of `path`, or `None` if it cannot be read.
+    pub(super) fn of(path: &str) -> Option<Self> {
+        let metadata = std::fs::metadata(path).ok()?;
+        let mtime = metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs())?;
+        Some(Self {
+            size: metadata.len(),
+            mtime,
+        })
+    }
+
+    pub(super) fn to_bytes(self) -> [u8; Self::LEN] {
+        let mut bytes = [0u8; Self::LEN];
+        bytes[..8].copy_from_slice(&self.size.to_le_bytes());
+        bytes[8..].copy_from_slice(&self.mtime.to_le_bytes());
+        bytes
+    }
+
+    pub(super) fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != Self::LEN {
+            return None;
+        }
+        let size = u64::from_le_bytes(bytes[..8].try_into().ok()?);
+        let mtime = u64::from_le_bytes(bytes[8..].try_into().ok()?);
+        Some(Self { size, mtime })
+    }
+
+    /// Whether an entry stored with stamp `stored` is still valid for `source_file`.
+    pub(super) fn matches(stored: Option<Self>, source_file: Option<&str>) -> bool {
+        match (stored, source_file) {
+            (Some(stored), Some(path)) => Self::of(path) == Some(stored),
+            _ => true,
+        }
+    }
+}
+
+/// Open a backend of kind `backend_type` storing its data in `dir`.
+pub fn open_backend(
+    backend_type: CacheBackendType,
+    dir: PathBuf,
+    limits: CacheLimits,
+) -> Result<Box<dyn CacheBackend>> {
+    match backend_type {
+        CacheBackendType::Filesystem => Ok(Box::new(super::FilesystemBackend::new(dir, limits)?)),
+        CacheBackendType::Sharded => Ok(Box::new(super::ShardedBackend::new(dir, limits)?)),
+        #[cfg(feature = "cache-sqlite")]
+        CacheBackendType::Sqlite => Ok(Box::new(super::SqliteBackend::new(dir, limits)?)),
+        #[cfg(not(feature = "cache-sqlite"))]
+        CacheBackendType::Sqlite => Err(KreuzbergError::cache(
+            "The SQLite cache backend requires the `cache-sqlite` feature".to_string(),
+        )),
+    }
+}
+
+static DEFAULT_BACKEND: RwLock<Option<CacheBackendType>> = RwLock::new(None);
+
+/// Backend used by the built-in caches.
+///
+/// Returns the backend set with [`set_default_backend`], otherwise the value of
+/// `KREUZBERG_CACHE_BACKEND`, otherwise [`CacheBackendType::Filesystem`].
+pub fn default_backend() -> CacheBackendType {
+    if let Some(backend) = DEFAULT_BACKEND.read().ok().and_then(|backend| *backend) {
+        return backend;
+    }
+    match std::env::var("KREUZBERG_CACHE_BACKEND") {
+        Ok(value) => value.parse().unwrap_or_else(|e| {
+            tracing::warn!("Ignoring KREUZBERG_CACHE_BACKEND: {}", e);
+            CacheBackendType::Filesystem
+        }),
+        Err(_) => CacheBackendType::Filesystem,
+    }
+}
+
+/// Select the backend used by built-in caches opened from now on.
+///
+/// Caches that are already open keep their backend.
+pub fn set_default_backend(backend: CacheBackendType) {
+    match DEFAULT_BACKEND.write() {
+        Ok(mut default) => *default = Some(backend),
+        Err(poisoned) => *poisoned.into_inner() = Some(backend),
+    }
+}
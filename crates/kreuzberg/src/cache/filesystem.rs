@@ -0,0 +1,270 @@
+//! Flat-directory cache backend: one `.msgpack` file per key.
+
+use crate::error::{KreuzbergError, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use super::backend::{CacheBackend, CacheBackendType, CacheLimits, SourceStamp};
+use super::cleanup::{get_cache_metadata, smart_cleanup_cache};
+use super::core::CacheStats;
+
+/// Stores each entry as `<key>.msgpack` in a single directory, with the source file's
+/// size and modification time in a `<key>.meta` sidecar.
+///
+/// Simple and inspectable, but directory scans make cleanup slow once a cache holds
+/// hundreds of thousands of entries.
+pub struct FilesystemBackend {
+    cache_dir: PathBuf,
+    limits: CacheLimits,
+    /// Tracks cache files being deleted to prevent read-during-delete race conditions
+    deleting_files: Mutex<HashSet<PathBuf>>,
+}
+
+impl FilesystemBackend {
+    pub fn new(cache_dir: PathBuf, limits: CacheLimits) -> Result<Self> {
+        fs::create_dir_all(&cache_dir)
+            .map_err(|e| KreuzbergError::cache(format!("Failed to create cache directory: {}", e)))?;
+        Ok(Self {
+            cache_dir,
+            limits,
+            deleting_files: Mutex::new(HashSet::new()),
+        })
+    }
+
+    fn get_cache_path(&self, cache_key: &str) -> PathBuf {
+        self.cache_dir.join(format!("{}.msgpack", cache_key))
+    }
+
+    fn get_metadata_path(&self, cache_key: &str) -> PathBuf {
+        self.cache_dir.join(format!("{}.meta", cache_key))
+    }
+
+    fn is_valid(&self, cache_key: &str, cache_path: &Path, source_file: Option<&str>) -> bool {
+        if !cache_path.exists() {
+            return false;
+        }
+
+        if let Ok(metadata) = fs::metadata(cache_path)
+            && let Ok(modified) = metadata.modified()
+            && let Ok(elapsed) = SystemTime::now().duration_since(modified)
+        {
+            let age_days = elapsed.as_secs() as f64 / (24.0 * 3600.0);
+            if age_days > self.limits.max_age_days {
+                return false;
+            }
+        }
+
+        if source_file.is_some() {
+            let meta_path = self.get_metadata_path(cache_key);
+            if meta_path.exists() {
+                let stored = fs::read(&meta_path)
+                    .ok()
+                    .and_then(|bytes| SourceStamp::from_bytes(&bytes));
+                return stored.is_some() && SourceStamp::matches(stored, source_file);
+            }
+        }
+
+        true
+    }
+
+    /// Mark a file path as being deleted to prevent concurrent reads.
+    ///
+    /// # TOCTOU Race Condition
+    ///
+    /// There is a Time-Of-Check-To-Time-Of-Use (TOCTOU) race condition between:
+    /// 1. Iterating directory entries in `clear()` (getting path/metadata)
+    /// 2. Marking the file for deletion here
+    /// 3. Actually deleting the file
+    ///
+    /// **Race scenario:**
+    /// - Thread A: Begins iterating in `clear()`, gets path
+    /// - Thread B: Calls `get()`, checks `deleting_files` (not marked yet), proceeds
+    /// - Thread A: Calls `mark_for_deletion()` here
+    /// - Thread A: Deletes file with `fs::remove_file()`
+    /// - Thread B: Tries to read file, but it's already deleted
+    ///
+    /// **Why this is acceptable:**
+    /// - Cache operations are best-effort optimizations, not critical
+    /// - `get()` already handles file read failures gracefully (treats as cache miss)
+    /// - The worst case is a failed read → cache miss → recomputation
+    /// - No data corruption or invariant violations occur
+    /// - Alternative (atomic operation) would require complex locking impacting performance
+    fn mark_for_deletion(&self, path: &Path) -> Result<()> {
+        let mut deleting = self
+            .deleting_files
+            .lock()
+            .map_err(|e| KreuzbergError::LockPoisoned(format!("Deleting files mutex poisoned: {}", e)))?;
+        deleting.insert(path.to_path_buf());
+        Ok(())
+    }
+
+    /// Remove a file path from the deletion set
+    fn unmark_deletion(&self, path: &Path) -> Result<()> {
+        let mut deleting = self
+            .deleting_files
+            .lock()
+            .map_err(|e| KreuzbergError::LockPoisoned(format!("Deleting files mutex poisoned: {}", e)))?;
+        deleting.remove(&path.to_path_buf());
+        Ok(())
+    }
+
+    fn cache_dir_str(&self) -> Result<&str> {
+        self.cache_dir
+            .to_str()
+            .ok_or_else(|| KreuzbergError::validation("Cache directory path contains invalid UTF-8".to_string()))
+    }
+}
+
+impl CacheBackend for FilesystemBackend {
+    fn backend_type(&self) -> CacheBackendType {
+        CacheBackendType::Filesystem
+    }
+
+    fn location(&self) -> &Path {
+        &self.cache_dir
+    }
+
+    fn get(&self, cache_key: &str, source_file: Option<&str>) -> Result<Option<Vec<u8>>> {
+        let cache_path = self.get_cache_path(cache_key);
+
+        {
+            let deleting = self
+                .deleting_files
+                .lock()
+                .map_err(|e| KreuzbergError::LockPoisoned(format!("Deleting files mutex poisoned: {}", e)))?;
+            if deleting.contains(&cache_path) {
+                return Ok(None);
+            }
+        }
+
+        if !self.is_valid(cache_key, &cache_path, source_file) {
+            return Ok(None);
+        }
+
+        match fs::read(&cache_path) {
+            Ok(content) => Ok(Some(content)),
+            Err(_) => {
+                // Best-effort cleanup of corrupted cache files ~keep
+                if let Err(e) = fs::remove_file(&cache_path) {
+                    tracing::debug!("Failed to remove corrupted cache file: {}", e);
+                }
+                if let Err(e) = fs::remove_file(self.get_metadata_path(cache_key)) {
+                    tracing::debug!("Failed to remove corrupted metadata file: {}", e);
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    fn set(&self, cache_key: &str, data: &[u8], source_file: Option<&str>) -> Result<()> {
+        let cache_path = self.get_cache_path(cache_key);
+
+        // Write to a temporary file and rename it into place so concurrent readers never
+        // observe a partially written entry.
+        let timestamp = SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let temp_path = self
+            .cache_dir
+            .join(format!(".{}.{}.{}.tmp", cache_key, std::process::id(), timestamp));
+        fs::write(&temp_path, data).map_err(|e| KreuzbergError::cache(format!("Failed to write cache file: {}", e)))?;
+        if let Err(e) = fs::rename(&temp_path, &cache_path) {
+            let _ = fs::remove_file(&temp_path);
+            return Err(KreuzbergError::cache(format!("Failed to write cache file: {}", e)));
+        }
+
+        if let Some(stamp) = source_file.and_then(SourceStamp::of) {
+            // Cache metadata write failure - safe to ignore, cache is optional fallback ~keep
+            let _ = fs::write(self.get_metadata_path(cache_key), stamp.to_bytes());
+        }
+
+        Ok(())
+    }
+
+    fn remove(&self, cache_key: &str) -> Result<bool> {
+        let _ = fs::remove_file(self.get_metadata_path(cache_key));
+        match fs::remove_file(self.get_cache_path(cache_key)) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(KreuzbergError::cache(format!("Failed to remove cache file: {}", e))),
+        }
+    }
+
+    fn clear(&self) -> Result<(usize, f64)> {
+        let dir_path = &self.cache_dir;
+
+        if !dir_path.exists() {
+            return Ok((0, 0.0));
+        }
+
+        let mut removed_count = 0;
+        let mut removed_size = 0.0;
+
+        let read_dir = fs::read_dir(dir_path)
+            .map_err(|e| KreuzbergError::cache(format!("Failed to read cache directory: {}", e)))?;
+
+        for entry in read_dir {
+            let entry = match entry {
+                Ok(e) => e,
+                Err(e) => {
+                    tracing::debug!("Error reading entry: {}", e);
+                    continue;
+                }
+            };
+
+            let metadata = match entry.metadata() {
+                Ok(m) if m.is_file() => m,
+                _ => continue,
+            };
+
+            let path = entry.path();
+            if path.extension().and_then(|s| s.to_str()) != Some("msgpack") {
+                continue;
+            }
+
+            let size_mb = metadata.len() as f64 / (1024.0 * 1024.0);
+
+            // Mark file for deletion to prevent concurrent access ~keep
+            if let Err(e) = self.mark_for_deletion(&path) {
+                tracing::debug!("Failed to mark file for deletion: {} (continuing anyway)", e);
+            }
+
+            match fs::remove_file(&path) {
+                Ok(_) => {
+                    removed_count += 1;
+                    removed_size += size_mb;
+                    // Unmark after successful deletion ~keep
+                    if let Err(e) = self.unmark_deletion(&path) {
+                        tracing::debug!("Failed to unmark deleted file: {} (non-critical)", e);
+                    }
+                }
+                Err(e) => {
+                    tracing::debug!("Failed to remove {:?}: {}", path, e);
+                    // Unmark after failed deletion to allow retries ~keep
+                    if let Err(e) = self.unmark_deletion(&path) {
+                        tracing::debug!("Failed to unmark file after deletion error: {} (non-critical)", e);
+                    }
+                }
+            }
+        }
+
+        Ok((removed_count, removed_size))
+    }
+
+    fn stats(&self) -> Result<CacheStats> {
+        get_cache_metadata(self.cache_dir_str()?)
+    }
+
+    fn cleanup(&self) -> Result<(usize, f64)> {
+        smart_cleanup_cache(
+            self.cache_dir_str()?,
+            self.limits.max_age_days,
+            self.limits.max_size_mb,
+            self.limits.min_free_space_mb,
+        )
+    }
+}
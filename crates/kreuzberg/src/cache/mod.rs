@@ -1,20 +1,36 @@
 //! Generic cache implementation with lock poisoning recovery.
 //!
 //! This module provides a thread-safe caching system with automatic cleanup,
-//! processing locks, and validation capabilities.
+//! processing locks, and validation capabilities. Entries are stored by a pluggable
+//! [`CacheBackend`] (flat directory, sharded directory or SQLite).
 
+mod backend;
 mod cleanup;
 mod core;
+mod filesystem;
+mod sharded;
+#[cfg(feature = "cache-sqlite")]
+mod sqlite;
 mod utilities;
 
 // Re-export all public types and functions for backward compatibility
+pub use backend::{CacheBackend, CacheBackendType, CacheLimits, default_backend, open_backend, set_default_backend};
 pub use cleanup::{
     batch_cleanup_caches, cleanup_cache, clear_cache_directory, get_cache_metadata, is_cache_valid, smart_cleanup_cache,
 };
+#[cfg(feature = "ocr")]
+pub(crate) use core::shared_cache;
+#[cfg(any(feature = "ocr", feature = "embeddings"))]
+pub(crate) use core::shared_cache_with_backend;
+pub(crate) use core::shared_user_cache;
 pub use core::{CacheStats, GenericCache};
+pub use filesystem::FilesystemBackend;
+pub use sharded::ShardedBackend;
+#[cfg(feature = "cache-sqlite")]
+pub use sqlite::SqliteBackend;
 pub use utilities::{
     fast_hash, filter_old_cache_entries, generate_cache_key, get_available_disk_space, sort_cache_by_access_time,
-    validate_cache_key,
+    user_cache_dir, validate_cache_key,
 };
 
 #[cfg(test)]
@@ -46,6 +62,14 @@ mod tests {
         assert!(!validate_cache_key("0123456789abcdef0123456789abcdef0"));
     }
 
+    #[test]
+    fn test_user_cache_dir_is_absolute() {
+        if let Some(dir) = user_cache_dir() {
+            assert!(dir.is_absolute());
+            assert!(dir.ends_with("kreuzberg"));
+        }
+    }
+
     #[test]
     fn test_fast_hash() {
         let data1 = b"test data";
@@ -342,4 +366,65 @@ mod tests {
         assert_eq!(cache.cache_type(), "test");
         assert!(cache.cache_dir().to_string_lossy().contains("test"));
     }
+
+    #[test]
+    fn test_generic_cache_with_backend_types() {
+        let backends = [
+            CacheBackendType::Filesystem,
+            CacheBackendType::Sharded,
+            #[cfg(feature = "cache-sqlite")]
+            CacheBackendType::Sqlite,
+        ];
+
+        for backend_type in backends {
+            let temp_dir = tempdir().unwrap();
+            let cache = GenericCache::with_backend_type(
+                "test".to_string(),
+                Some(temp_dir.path().to_str().unwrap().to_string()),
+                backend_type,
+                CacheLimits::new(30.0, 500.0, 1000.0),
+            )
+            .unwrap();
+
+            assert_eq!(cache.backend_type(), backend_type);
+            assert_eq!(cache.cache_dir(), temp_dir.path().join("test"));
+
+            cache.set("key1", b"data1".to_vec(), None).unwrap();
+            cache.set("key2", b"data2".to_vec(), None).unwrap();
+            assert_eq!(cache.get("key1", None).unwrap(), Some(b"data1".to_vec()));
+            assert_eq!(cache.get_stats().unwrap().total_files, 2, "{}", backend_type);
+
+            assert!(cache.remove("key1").unwrap());
+            assert!(!cache.remove("key1").unwrap());
+            assert_eq!(cache.clear().unwrap().0, 1, "{}", backend_type);
+            assert_eq!(cache.get("key2", None).unwrap(), None);
+        }
+    }
+
+    #[test]
+    fn test_cache_backend_type_parsing() {
+        assert_eq!("sqlite".parse::<CacheBackendType>().unwrap(), CacheBackendType::Sqlite);
+        assert_eq!(
+            " Sharded ".parse::<CacheBackendType>().unwrap(),
+            CacheBackendType::Sharded
+        );
+        assert_eq!("fs".parse::<CacheBackendType>().unwrap(), CacheBackendType::Filesystem);
+        assert!("redis".parse::<CacheBackendType>().is_err());
+        assert_eq!(
+            serde_json::to_string(&CacheBackendType::Sharded).unwrap(),
+            "\"sharded\""
+        );
+    }
+
+    #[cfg(not(feature = "cache-sqlite"))]
+    #[test]
+    fn test_sqlite_backend_requires_feature() {
+        let temp_dir = tempdir().unwrap();
+        let result = open_backend(
+            CacheBackendType::Sqlite,
+            temp_dir.path().to_path_buf(),
+            CacheLimits::new(30.0, 500.0, 1000.0),
+        );
+        assert!(result.is_err());
+    }
 }
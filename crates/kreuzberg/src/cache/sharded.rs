@@ -0,0 +1,431 @@
+//! Content-addressed, sharded directory cache backend.
+//!
+//! Entries live at `<dir>/<aa>/<bb>/<sha256(key)>.entry`, where `aa` and `bb` are the first
+//! two byte pairs of the hex digest. Keys are hashed with SHA-256 rather than `ahash`
+//! so every process and host maps a key to the same path, and sharding keeps directories
+//! small enough for fast lookups and scans with millions of entries.
+//!
+//! Each entry file is self-describing: a header holds the full key (checked on read, so a
+//! digest collision is a miss rather than wrong data) and the source file stamp, followed
+//! by the payload. Entries are written to `<dir>/tmp/` and renamed into place, so readers
+//! in other processes only ever see complete files, and every removal tolerates the file
+//! already being gone. No locks are held across processes.
+
+use crate::error::{KreuzbergError, Result};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::backend::{CacheBackend, CacheBackendType, CacheLimits, SourceStamp};
+use super::core::CacheStats;
+use super::utilities::get_available_disk_space;
+
+const ENTRY_MAGIC: &[u8; 4] = b"KZC1";
+const ENTRY_EXTENSION: &str = "entry";
+const TMP_DIR: &str = "tmp";
+
+/// Temporary files older than this are left over from crashed writers.
+const STALE_TMP_AGE: Duration = Duration::from_secs(3600);
+
+/// Sharded directory backend, safe for concurrent use by multiple processes.
+pub struct ShardedBackend {
+    cache_dir: PathBuf,
+    limits: CacheLimits,
+}
+
+struct EntryFile {
+    path: PathBuf,
+    size: u64,
+    modified: SystemTime,
+}
+
+impl ShardedBackend {
+    pub fn new(cache_dir: PathBuf, limits: CacheLimits) -> Result<Self> {
+        fs::create_dir_all(cache_dir.join(TMP_DIR))
+            .map_err(|e| KreuzbergError::cache(format!("Failed to create cache directory: {}", e)))?;
+        Ok(Self { cache_dir, limits })
+    }
+
+    fn entry_path(&self, cache_key: &str) -> PathBuf {
+        let digest = format!("{:x}", Sha256::digest(cache_key.as_bytes()));
+        self.cache_dir
+            .join(&digest[0..2])
+            .join(&digest[2..4])
+            .join(format!("{}.{}", digest, ENTRY_EXTENSION))
+    }
+
+    fn is_expired(&self, modified: SystemTime) -> bool {
+        SystemTime::now()
+            .duration_since(modified)
+            .is_ok_and(|age| age.as_secs_f64() > self.limits.max_age_secs())
+    }
+
+    /// All entry files, skipping anything that disappears while scanning.
+    fn scan(&self) -> Vec<EntryFile> {
+        let mut entries = Vec::new();
+        for shard in read_dirs(&self.cache_dir) {
+            if shard.file_name().is_some_and(|name| name == TMP_DIR) {
+                continue;
+            }
+            for sub_shard in read_dirs(&shard) {
+                let Ok(read_dir) = fs::read_dir(&sub_shard) else {
+                    continue;
+                };
+                for entry in read_dir.flatten() {
+                    let path = entry.path();
+                    if path.extension().and_then(|s| s.to_str()) != Some(ENTRY_EXTENSION) {
+                        continue;
+                    }
+                    let Ok(metadata) = entry.metadata() else {
+                        continue;
+                    };
+                    let Ok(modified) = metadata.modified() else {
+                        continue;
+                    };
+                    entries.push(EntryFile {
+                        path,
+                        size: metadata.len(),
+                        modified,
+                    });
+                }
+            }
+        }
+        entries
+    }
+
+    fn remove_stale_tmp_files(&self) {
+        let Ok(read_dir) = fs::read_dir(self.cache_dir.join(TMP_DIR)) else {
+            return;
+        };
+        for entry in read_dir.flatten() {
+            let stale = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+                .is_some_and(|age| age > STALE_TMP_AGE);
+            if stale {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+    }
+}
+
+/// Subdirectories of `dir`, or nothing if it cannot be read.
+fn read_dirs(dir: &Path) -> Vec<PathBuf> {
+    let Ok(read_dir) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    read_dir
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+        .map(|entry| entry.path())
+        .collect()
+}
+
+/// Remove `path`, returning whether this call removed it.
+fn remove_entry(path: &Path) -> bool {
+    match fs::remove_file(path) {
+        Ok(()) => true,
+        Err(e) if e.kind() == ErrorKind::NotFound => false,
+        Err(e) => {
+            tracing::debug!("Failed to remove {:?}: {}", path, e);
+            false
+        }
+    }
+}
+
+fn encode_entry(cache_key: &str, data: &[u8], source: Option<SourceStamp>) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(ENTRY_MAGIC.len() + 4 + cache_key.len() + 1 + SourceStamp::LEN + data.len());
+    bytes.extend_from_slice(ENTRY_MAGIC);
+    bytes.extend_from_slice(&(cache_key.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(cache_key.as_bytes());
+    match source {
+        Some(stamp) => {
+            bytes.push(1);
+            bytes.extend_from_slice(&stamp.to_bytes());
+        }
+        None => bytes.push(0),
+    }
+    bytes.extend_from_slice(data);
+    bytes
+}
+
+/// Split an entry file into its source stamp and payload, or `None` if it is malformed
+/// or belongs to a different key.
+fn decode_entry<'a>(cache_key: &str, bytes: &'a [u8]) -> Option<(Option<SourceStamp>, &'a [u8])> {
+    let rest = bytes.strip_prefix(ENTRY_MAGIC)?;
+    let (key_len, rest) = rest.split_at_checked(4)?;
+    let key_len = u32::from_le_bytes(key_len.try_into().ok()?) as usize;
+    let (key, rest) = rest.split_at_checked(key_len)?;
+    if key != cache_key.as_bytes() {
+        return None;
+    }
+    let (flag, rest) = rest.split_first()?;
+    match flag {
+        0 => Some((None, rest)),
+        1 => {
+            let (stamp, data) = rest.split_at_checked(SourceStamp::LEN)?;
+            Some((Some(SourceStamp::from_bytes(stamp)?), data))
+        }
+        _ => None,
+    }
+}
+
+impl CacheBackend for ShardedBackend {
+    fn backend_type(&self) -> CacheBackendType {
+        CacheBackendType::Sharded
+    }
+
+    fn location(&self) -> &Path {
+        &self.cache_dir
+    }
+
+    fn get(&self, cache_key: &str, source_file: Option<&str>) -> Result<Option<Vec<u8>>> {
+        let path = self.entry_path(cache_key);
+        let modified = match fs::metadata(&path).and_then(|m| m.modified()) {
+            Ok(modified) => modified,
+            Err(_) => return Ok(None),
+        };
+        if self.is_expired(modified) {
+            remove_entry(&path);
+            return Ok(None);
+        }
+
+        let bytes = match fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(_) => return Ok(None),
+        };
+        match decode_entry(cache_key, &bytes) {
+            Some((stored, data)) => {
+                if SourceStamp::matches(stored, source_file) {
+                    Ok(Some(data.to_vec()))
+                } else {
+                    Ok(None)
+                }
+            }
+            None => {
+                tracing::debug!("Ignoring malformed or colliding cache entry {:?}", path);
+                Ok(None)
+            }
+        }
+    }
+
+    fn set(&self, cache_key: &str, data: &[u8], source_file: Option<&str>) -> Result<()> {
+        let path = self.entry_path(cache_key);
+        let Some(shard_dir) = path.parent() else {
+            return Err(KreuzbergError::cache("Invalid cache entry path".to_string()));
+        };
+        fs::create_dir_all(shard_dir)
+            .map_err(|e| KreuzbergError::cache(format!("Failed to create cache shard directory: {}", e)))?;
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or(ENTRY_EXTENSION);
+        let temp_path = self.cache_dir.join(TMP_DIR).join(format!(
+            "{}.{}.{:?}.{}",
+            file_name,
+            std::process::id(),
+            std::thread::current().id(),
+            timestamp
+        ));
+
+        let source = source_file.and_then(SourceStamp::of);
+        fs::write(&temp_path, encode_entry(cache_key, data, source))
+            .map_err(|e| KreuzbergError::cache(format!("Failed to write cache file: {}", e)))?;
+        fs::rename(&temp_path, &path).map_err(|e| {
+            let _ = fs::remove_file(&temp_path);
+            KreuzbergError::cache(format!("Failed to move cache file into place: {}", e))
+        })
+    }
+
+    fn remove(&self, cache_key: &str) -> Result<bool> {
+        Ok(remove_entry(&self.entry_path(cache_key)))
+    }
+
+    fn clear(&self) -> Result<(usize, f64)> {
+        let mut removed_count = 0;
+        let mut removed_size = 0.0;
+        for entry in self.scan() {
+            if remove_entry(&entry.path) {
+                removed_count += 1;
+                removed_size += entry.size as f64 / (1024.0 * 1024.0);
+            }
+        }
+        Ok((removed_count, removed_size))
+    }
+
+    fn stats(&self) -> Result<CacheStats> {
+        let entries = self.scan();
+        let now = SystemTime::now();
+        let ages: Vec<f64> = entries
+            .iter()
+            .map(|e| now.duration_since(e.modified).unwrap_or_default().as_secs_f64() / (24.0 * 3600.0))
+            .collect();
+        let total_size: u64 = entries.iter().map(|e| e.size).sum();
+
+        Ok(CacheStats {
+            total_files: entries.len(),
+            total_size_mb: total_size as f64 / (1024.0 * 1024.0),
+            available_space_mb: get_available_disk_space(&self.cache_dir.to_string_lossy())?,
+            oldest_file_age_days: ages.iter().copied().fold(0.0, f64::max),
+            newest_file_age_days: ages.iter().copied().reduce(f64::min).unwrap_or(0.0),
+        })
+    }
+
+    fn cleanup(&self) -> Result<(usize, f64)> {
+        self.remove_stale_tmp_files();
+
+        let mut removed_count = 0;
+        let mut removed_size = 0.0;
+        let mut remaining = Vec::new();
+        for entry in self.scan() {
+            if self.is_expired(entry.modified) {
+                if remove_entry(&entry.path) {
+                    removed_count += 1;
+                    removed_size += entry.size as f64 / (1024.0 * 1024.0);
+                }
+            } else {
+                remaining.push(entry);
+            }
+        }
+
+        let available_mb = get_available_disk_space(&self.cache_dir.to_string_lossy())?;
+        let mut total_mb = remaining.iter().map(|e| e.size).sum::<u64>() as f64 / (1024.0 * 1024.0);
+        let low_disk = available_mb < self.limits.min_free_space_mb;
+        if total_mb <= self.limits.max_size_mb && !low_disk {
+            return Ok((removed_count, removed_size));
+        }
+
+        let target_mb = self.limits.max_size_mb.min(total_mb) * if low_disk { 0.5 } else { 0.8 };
+        remaining.sort_by_key(|e| e.modified);
+        for entry in remaining {
+            if total_mb <= target_mb {
+                break;
+            }
+            let size_mb = entry.size as f64 / (1024.0 * 1024.0);
+            if remove_entry(&entry.path) {
+                removed_count += 1;
+                removed_size += size_mb;
+            }
+            // Count entries another process evicted first as freed, too.
+            total_mb -= size_mb;
+        }
+
+        Ok((removed_count, removed_size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn backend(dir: &Path, limits: CacheLimits) -> ShardedBackend {
+        ShardedBackend::new(dir.to_path_buf(), limits).unwrap()
+    }
+
+    #[test]
+    fn test_entries_are_sharded_by_key_digest() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = backend(dir.path(), CacheLimits::new(30.0, 500.0, 0.0));
+
+        cache.set("some key", b"payload", None).unwrap();
+        let path = cache.entry_path("some key");
+        assert!(path.is_file());
+        assert_eq!(path.parent().unwrap().parent().unwrap().parent().unwrap(), dir.path());
+        assert_eq!(cache.get("some key", None).unwrap(), Some(b"payload".to_vec()));
+        assert_eq!(cache.get("other key", None).unwrap(), None);
+
+        assert!(cache.remove("some key").unwrap());
+        assert!(!cache.remove("some key").unwrap());
+        assert_eq!(cache.get("some key", None).unwrap(), None);
+    }
+
+    #[test]
+    fn test_entry_for_different_key_is_a_miss() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = backend(dir.path(), CacheLimits::new(30.0, 500.0, 0.0));
+
+        // Simulate a digest collision by planting another key's entry at this key's path.
+        let path = cache.entry_path("wanted");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, encode_entry("other", b"wrong data", None)).unwrap();
+
+        assert_eq!(cache.get("wanted", None).unwrap(), None);
+    }
+
+    #[test]
+    fn test_source_file_change_invalidates_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = backend(&dir.path().join("cache"), CacheLimits::new(30.0, 500.0, 0.0));
+        let source = dir.path().join("source.txt");
+        fs::write(&source, b"original").unwrap();
+        let source = source.to_str().unwrap();
+
+        cache.set("key", b"derived", Some(source)).unwrap();
+        assert_eq!(cache.get("key", Some(source)).unwrap(), Some(b"derived".to_vec()));
+
+        fs::write(source, b"modified with a different size").unwrap();
+        assert_eq!(cache.get("key", Some(source)).unwrap(), None);
+    }
+
+    #[test]
+    fn test_cleanup_evicts_expired_and_oldest_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = backend(dir.path(), CacheLimits::new(1.0, 0.0015, 0.0));
+        let data = vec![0u8; 1024];
+        for key in ["expired", "old", "new"] {
+            cache.set(key, &data, None).unwrap();
+        }
+        let set_age = |key: &str, secs: u64| {
+            let time = SystemTime::now() - Duration::from_secs(secs);
+            filetime::set_file_mtime(cache.entry_path(key), filetime::FileTime::from_system_time(time)).unwrap();
+        };
+        set_age("expired", 2 * 24 * 3600);
+        set_age("old", 60);
+
+        let (removed, _) = cache.cleanup().unwrap();
+        assert_eq!(removed, 2);
+        assert_eq!(cache.get("expired", None).unwrap(), None);
+        assert_eq!(cache.get("old", None).unwrap(), None);
+        assert!(cache.get("new", None).unwrap().is_some());
+
+        let stats = cache.stats().unwrap();
+        assert_eq!(stats.total_files, 1);
+        assert_eq!(cache.clear().unwrap().0, 1);
+    }
+
+    #[test]
+    fn test_concurrent_writers_share_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().to_path_buf();
+        let handles: Vec<_> = (0..4)
+            .map(|worker| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    // Separate instances stand in for separate processes.
+                    let cache = ShardedBackend::new(path, CacheLimits::new(30.0, 500.0, 0.0)).unwrap();
+                    for i in 0..25 {
+                        let value = format!("value-{}", i);
+                        cache.set(&format!("key-{}", i), value.as_bytes(), None).unwrap();
+                        let read = cache.get(&format!("key-{}", (i + worker) % 25), None).unwrap();
+                        if let Some(read) = read {
+                            assert!(read.starts_with(b"value-"));
+                        }
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let cache = backend(dir.path(), CacheLimits::new(30.0, 500.0, 0.0));
+        assert_eq!(cache.stats().unwrap().total_files, 25);
+        assert_eq!(fs::read_dir(dir.path().join(TMP_DIR)).unwrap().count(), 0);
+    }
+}
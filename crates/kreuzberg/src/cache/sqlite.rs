@@ -0,0 +1,361 @@
+//! Single-file SQLite cache backend.
+//!
+//! All entries of a cache live in `<dir>/cache.sqlite3`. Expiry and eviction are
+//! index-backed `DELETE`s instead of directory scans, which keeps cleanup fast with
+//! hundreds of thousands of entries. The database runs in WAL mode with a busy timeout,
+//! so several processes on the same host can share it.
+
+use crate::error::{KreuzbergError, Result};
+use rusqlite::{Connection, OptionalExtension, params};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::backend::{CacheBackend, CacheBackendType, CacheLimits, SourceStamp};
+use super::core::CacheStats;
+use super::utilities::get_available_disk_space;
+
+const DATABASE_FILE: &str = "cache.sqlite3";
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS entries (
+        key TEXT PRIMARY KEY NOT NULL,
+        data BLOB NOT NULL,
+        size INTEGER NOT NULL,
+        created_at INTEGER NOT NULL,
+        accessed_at INTEGER NOT NULL,
+        source_size INTEGER,
+        source_mtime INTEGER
+    );
+    CREATE INDEX IF NOT EXISTS entries_created_at ON entries (created_at);
+    CREATE INDEX IF NOT EXISTS entries_accessed_at ON entries (accessed_at);
+";
+
+/// SQLite-backed cache storage with indexed age and least-recently-used eviction.
+pub struct SqliteBackend {
+    cache_dir: PathBuf,
+    limits: CacheLimits,
+    connection: Mutex<Connection>,
+}
+
+fn sqlite_error(action: &str, e: rusqlite::Error) -> KreuzbergError {
+    KreuzbergError::cache(format!("Failed to {}: {}", action, e))
+}
+
+fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+fn to_mb(bytes: i64) -> f64 {
+    bytes as f64 / (1024.0 * 1024.0)
+}
+
+impl SqliteBackend {
+    pub fn new(cache_dir: PathBuf, limits: CacheLimits) -> Result<Self> {
+        std::fs::create_dir_all(&cache_dir)
+            .map_err(|e| KreuzbergError::cache(format!("Failed to create cache directory: {}", e)))?;
+
+        let connection =
+            Connection::open(cache_dir.join(DATABASE_FILE)).map_err(|e| sqlite_error("open cache database", e))?;
+        connection
+            .busy_timeout(BUSY_TIMEOUT)
+            .map_err(|e| sqlite_error("configure cache database", e))?;
+        connection
+            .query_row("PRAGMA journal_mode = WAL", [], |_| Ok(()))
+            .map_err(|e| sqlite_error("configure cache database", e))?;
+        connection
+            .execute_batch("PRAGMA synchronous = NORMAL;")
+            .map_err(|e| sqlite_error("configure cache database", e))?;
+        connection
+            .execute_batch(SCHEMA)
+            .map_err(|e| sqlite_error("create cache schema", e))?;
+
+        Ok(Self {
+            cache_dir,
+            limits,
+            connection: Mutex::new(connection),
+        })
+    }
+
+    fn with_connection<T>(&self, f: impl FnOnce(&mut Connection) -> Result<T>) -> Result<T> {
+        let mut connection = self
+            .connection
+            .lock()
+            .map_err(|e| KreuzbergError::LockPoisoned(format!("Cache database mutex poisoned: {}", e)))?;
+        f(&mut connection)
+    }
+
+    fn expiry_cutoff(&self) -> i64 {
+        now_millis() - (self.limits.max_age_secs() * 1000.0) as i64
+    }
+}
+
+impl CacheBackend for SqliteBackend {
+    fn backend_type(&self) -> CacheBackendType {
+        CacheBackendType::Sqlite
+    }
+
+    fn location(&self) -> &Path {
+        &self.cache_dir
+    }
+
+    fn get(&self, cache_key: &str, source_file: Option<&str>) -> Result<Option<Vec<u8>>> {
+        let cutoff = self.expiry_cutoff();
+        self.with_connection(|conn| {
+            let row = conn
+                .query_row(
+                    "SELECT data, created_at, source_size, source_mtime FROM entries WHERE key = ?1",
+                    params![cache_key],
+                    |row| {
+                        Ok((
+                            row.get::<_, Vec<u8>>(0)?,
+                            row.get::<_, i64>(1)?,
+                            row.get::<_, Option<i64>>(2)?,
+                            row.get::<_, Option<i64>>(3)?,
+                        ))
+                    },
+                )
+                .optional()
+                .map_err(|e| sqlite_error("read cache entry", e))?;
+
+            let Some((data, created_at, source_size, source_mtime)) = row else {
+                return Ok(None);
+            };
+            if created_at < cutoff {
+                conn.execute("DELETE FROM entries WHERE key = ?1", params![cache_key])
+                    .map_err(|e| sqlite_error("remove expired cache entry", e))?;
+                return Ok(None);
+            }
+
+            let stored = source_size.zip(source_mtime).map(|(size, mtime)| SourceStamp {
+                size: size as u64,
+                mtime: mtime as u64,
+            });
+            if !SourceStamp::matches(stored, source_file) {
+                return Ok(None);
+            }
+
+            conn.execute(
+                "UPDATE entries SET accessed_at = ?2 WHERE key = ?1",
+                params![cache_key, now_millis()],
+            )
+            .map_err(|e| sqlite_error("update cache entry", e))?;
+            Ok(Some(data))
+        })
+    }
+
+    fn set(&self, cache_key: &str, data: &[u8], source_file: Option<&str>) -> Result<()> {
+        let source = source_file.and_then(SourceStamp::of);
+        let now = now_millis();
+        self.with_connection(|conn| {
+            conn.execute(
+                "INSERT OR REPLACE INTO entries (key, data, size, created_at, accessed_at, source_size, source_mtime)
+                 VALUES (?1, ?2, ?3, ?4, ?4, ?5, ?6)",
+                params![
+                    cache_key,
+                    data,
+                    data.len() as i64,
+                    now,
+                    source.map(|s| s.size as i64),
+                    source.map(|s| s.mtime as i64),
+                ],
+            )
+            .map_err(|e| sqlite_error("write cache entry", e))?;
+            Ok(())
+        })
+    }
+
+    fn remove(&self, cache_key: &str) -> Result<bool> {
+        self.with_connection(|conn| {
+            let removed = conn
+                .execute("DELETE FROM entries WHERE key = ?1", params![cache_key])
+                .map_err(|e| sqlite_error("remove cache entry", e))?;
+            Ok(removed > 0)
+        })
+    }
+
+    fn clear(&self) -> Result<(usize, f64)> {
+        self.with_connection(|conn| {
+            let tx = conn.transaction().map_err(|e| sqlite_error("clear cache", e))?;
+            let (count, size): (i64, i64) = tx
+                .query_row("SELECT COUNT(*), COALESCE(SUM(size), 0) FROM entries", [], |row| {
+                    Ok((row.get(0)?, row.get(1)?))
+                })
+                .map_err(|e| sqlite_error("clear cache", e))?;
+            tx.execute("DELETE FROM entries", [])
+                .map_err(|e| sqlite_error("clear cache", e))?;
+            tx.commit().map_err(|e| sqlite_error("clear cache", e))?;
+            Ok((count as usize, to_mb(size)))
+        })
+    }
+
+    fn stats(&self) -> Result<CacheStats> {
+        let (count, size, oldest, newest) = self.with_connection(|conn| {
+            conn.query_row(
+                "SELECT COUNT(*), COALESCE(SUM(size), 0), MIN(created_at), MAX(created_at) FROM entries",
+                [],
+                |row| {
+                    Ok((
+                        row.get::<_, i64>(0)?,
+                        row.get::<_, i64>(1)?,
+                        row.get::<_, Option<i64>>(2)?,
+                        row.get::<_, Option<i64>>(3)?,
+                    ))
+                },
+            )
+            .map_err(|e| sqlite_error("read cache statistics", e))
+        })?;
+
+        let now = now_millis();
+        let age_days =
+            |created: Option<i64>| created.map_or(0.0, |c| (now - c).max(0) as f64 / (1000.0 * 24.0 * 3600.0));
+        Ok(CacheStats {
+            total_files: count as usize,
+            total_size_mb: to_mb(size),
+            available_space_mb: get_available_disk_space(&self.cache_dir.to_string_lossy())?,
+            oldest_file_age_days: age_days(oldest),
+            newest_file_age_days: age_days(newest),
+        })
+    }
+
+    fn cleanup(&self) -> Result<(usize, f64)> {
+        let cutoff = self.expiry_cutoff();
+        let available_mb = get_available_disk_space(&self.cache_dir.to_string_lossy())?;
+        let low_disk = available_mb < self.limits.min_free_space_mb;
+        let max_size_mb = self.limits.max_size_mb;
+
+        self.with_connection(|conn| {
+            let tx = conn.transaction().map_err(|e| sqlite_error("clean up cache", e))?;
+            let (mut removed_count, mut removed_size): (i64, i64) = tx
+                .query_row(
+                    "SELECT COUNT(*), COALESCE(SUM(size), 0) FROM entries WHERE created_at < ?1",
+                    params![cutoff],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .map_err(|e| sqlite_error("clean up cache", e))?;
+            tx.execute("DELETE FROM entries WHERE created_at < ?1", params![cutoff])
+                .map_err(|e| sqlite_error("clean up cache", e))?;
+
+            let total: i64 = tx
+                .query_row("SELECT COALESCE(SUM(size), 0) FROM entries", [], |row| row.get(0))
+                .map_err(|e| sqlite_error("clean up cache", e))?;
+            let mut total_mb = to_mb(total);
+
+            if total_mb > max_size_mb || low_disk {
+                let target_mb = max_size_mb.min(total_mb) * if low_disk { 0.5 } else { 0.8 };
+                let mut victims = Vec::new();
+                {
+                    let mut oldest = tx
+                        .prepare("SELECT key, size FROM entries ORDER BY accessed_at")
+                        .map_err(|e| sqlite_error("evict cache entries", e))?;
+                    let mut rows = oldest.query([]).map_err(|e| sqlite_error("evict cache entries", e))?;
+                    while total_mb > target_mb {
+                        let Some(row) = rows.next().map_err(|e| sqlite_error("evict cache entries", e))? else {
+                            break;
+                        };
+                        let key: String = row.get(0).map_err(|e| sqlite_error("evict cache entries", e))?;
+                        let size: i64 = row.get(1).map_err(|e| sqlite_error("evict cache entries", e))?;
+                        total_mb -= to_mb(size);
+                        removed_size += size;
+                        victims.push(key);
+                    }
+                }
+
+                let mut delete = tx
+                    .prepare("DELETE FROM entries WHERE key = ?1")
+                    .map_err(|e| sqlite_error("evict cache entries", e))?;
+                for key in &victims {
+                    delete
+                        .execute(params![key])
+                        .map_err(|e| sqlite_error("evict cache entries", e))?;
+                }
+                removed_count += victims.len() as i64;
+            }
+
+            tx.commit().map_err(|e| sqlite_error("clean up cache", e))?;
+            Ok((removed_count as usize, to_mb(removed_size)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip_and_source_validation() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = SqliteBackend::new(dir.path().join("cache"), CacheLimits::new(30.0, 500.0, 0.0)).unwrap();
+        let source = dir.path().join("source.txt");
+        std::fs::write(&source, b"original").unwrap();
+        let source = source.to_str().unwrap();
+
+        cache.set("plain", b"one", None).unwrap();
+        cache.set("derived", b"two", Some(source)).unwrap();
+        assert_eq!(cache.get("plain", None).unwrap(), Some(b"one".to_vec()));
+        assert_eq!(cache.get("derived", Some(source)).unwrap(), Some(b"two".to_vec()));
+        assert_eq!(cache.get("missing", None).unwrap(), None);
+        assert!(dir.path().join("cache").join(DATABASE_FILE).is_file());
+
+        std::fs::write(source, b"modified with a different size").unwrap();
+        assert_eq!(cache.get("derived", Some(source)).unwrap(), None);
+
+        assert!(cache.remove("plain").unwrap());
+        assert_eq!(cache.get("plain", None).unwrap(), None);
+        assert_eq!(cache.stats().unwrap().total_files, 1);
+        assert_eq!(cache.clear().unwrap().0, 1);
+        assert_eq!(cache.stats().unwrap().total_files, 0);
+    }
+
+    #[test]
+    fn test_cleanup_evicts_expired_then_least_recently_used() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = SqliteBackend::new(dir.path().to_path_buf(), CacheLimits::new(1.0, 0.002, 0.0)).unwrap();
+        let data = vec![0u8; 1024];
+        for key in ["expired", "a", "b", "c"] {
+            cache.set(key, &data, None).unwrap();
+        }
+        let now = now_millis();
+        cache
+            .with_connection(|conn| {
+                let backdate = |key: &str, created: i64, accessed: i64| {
+                    conn.execute(
+                        "UPDATE entries SET created_at = ?2, accessed_at = ?3 WHERE key = ?1",
+                        params![key, created, accessed],
+                    )
+                    .unwrap();
+                };
+                backdate("expired", now - 2 * 24 * 3600 * 1000, now);
+                backdate("a", now - 3000, now - 1000);
+                backdate("b", now - 3000, now - 3000);
+                backdate("c", now - 3000, now - 2000);
+                Ok(())
+            })
+            .unwrap();
+
+        // 3 KB remain after expiry; the two least recently used go to get under 80% of 2 KB.
+        let (removed, _) = cache.cleanup().unwrap();
+        assert_eq!(removed, 3);
+        assert_eq!(cache.get("expired", None).unwrap(), None);
+        assert_eq!(cache.get("b", None).unwrap(), None);
+        assert_eq!(cache.get("c", None).unwrap(), None);
+        assert!(cache.get("a", None).unwrap().is_some());
+
+        assert_eq!(cache.cleanup().unwrap().0, 0);
+        assert_eq!(cache.stats().unwrap().total_files, 1);
+    }
+
+    #[test]
+    fn test_database_is_shared_between_instances() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = SqliteBackend::new(dir.path().to_path_buf(), CacheLimits::new(30.0, 500.0, 0.0)).unwrap();
+        let second = SqliteBackend::new(dir.path().to_path_buf(), CacheLimits::new(30.0, 500.0, 0.0)).unwrap();
+
+        first.set("key", b"value", None).unwrap();
+        assert_eq!(second.get("key", None).unwrap(), Some(b"value".to_vec()));
+    }
+}
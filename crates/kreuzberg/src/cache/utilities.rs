@@ -3,12 +3,39 @@
 use crate::error::Result;
 use ahash::AHasher;
 use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
 
 #[cfg(unix)]
 use crate::error::KreuzbergError;
 #[cfg(unix)]
 use std::path::Path;
 
+/// Per-user cache directory for kreuzberg, e.g. `~/.cache/kreuzberg` on Linux.
+///
+/// Uses `XDG_CACHE_HOME` when set, otherwise the platform convention:
+/// `~/Library/Caches` on macOS, `%LOCALAPPDATA%` on Windows and `~/.cache` elsewhere.
+/// Returns `None` if the home directory cannot be determined.
+pub fn user_cache_dir() -> Option<PathBuf> {
+    let non_empty = |name: &str| {
+        std::env::var_os(name)
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+    };
+
+    let base = non_empty("XDG_CACHE_HOME")
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| {
+            if cfg!(windows) {
+                non_empty("LOCALAPPDATA")
+            } else if cfg!(target_os = "macos") {
+                non_empty("HOME").map(|home| home.join("Library").join("Caches"))
+            } else {
+                non_empty("HOME").map(|home| home.join(".cache"))
+            }
+        })?;
+    Some(base.join("kreuzberg"))
+}
+
 /// Cache key hash format width (32 hex digits for u64 hash)
 const CACHE_KEY_HASH_WIDTH: usize = 32;
 
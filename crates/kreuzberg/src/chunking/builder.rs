@@ -80,6 +80,32 @@ where
     Ok(chunks)
 }
 
+/// Build chunks from text segments and the byte offsets they start at in the text.
+///
+/// Used when the overlap is not measured in characters, so that offsets cannot be
+/// derived from it.
+///
+/// # Errors
+///
+/// Returns an error if page boundary calculation fails.
+#[cfg(feature = "chunking-tokenizers")]
+pub fn build_chunks_at<'a, I>(text_chunks: I, page_boundaries: Option<&[PageBoundary]>) -> Result<Vec<Chunk>>
+where
+    I: IntoIterator<Item = (usize, &'a str)>,
+{
+    let chunks_vec: Vec<(usize, &str)> = text_chunks.into_iter().collect();
+    let total_chunks = chunks_vec.len();
+    let mut chunks = Vec::with_capacity(total_chunks);
+
+    for (index, (byte_start, chunk_text)) in chunks_vec.into_iter().enumerate() {
+        let mut byte_offset = byte_start;
+        let chunk = build_single_chunk(chunk_text, index, total_chunks, &mut byte_offset, 0, page_boundaries)?;
+        chunks.push(chunk);
+    }
+
+    Ok(chunks)
+}
+
 /// Build a single chunk with metadata.
 ///
 /// # Arguments
@@ -138,6 +164,8 @@ fn build_single_chunk(
             total_chunks,
             first_page,
             last_page,
+            heading_path: None,
+            node_ids: None,
         },
     })
 }
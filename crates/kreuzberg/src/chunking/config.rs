@@ -2,8 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 
-// Re-export ChunkingConfig and ChunkerType from core config (canonical location)
-pub use crate::core::config::processing::{ChunkerType, ChunkingConfig};
+// Re-export ChunkingConfig, ChunkerType and ChunkSizing from core config (canonical location)
+pub use crate::core::config::processing::{ChunkSizing, ChunkerType, ChunkingConfig};
 
 /// Result of a text chunking operation.
 ///
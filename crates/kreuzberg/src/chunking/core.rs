@@ -7,8 +7,11 @@ use crate::error::Result;
 use crate::types::PageBoundary;
 use text_splitter::{MarkdownSplitter, TextSplitter};
 
+#[cfg(feature = "chunking-tokenizers")]
+use super::builder::build_chunks_at;
 use super::builder::{build_chunk_config, build_chunks};
-use super::config::{ChunkerType, ChunkingConfig, ChunkingResult};
+use super::config::{ChunkSizing, ChunkerType, ChunkingConfig, ChunkingResult};
+use super::sizing::Sizer;
 use super::validation::validate_utf8_boundaries;
 
 /// Split text into chunks with optional page boundary tracking.
@@ -29,7 +32,7 @@ use super::validation::validate_utf8_boundaries;
 /// # Examples
 ///
 /// ```rust
-/// use kreuzberg::chunking::{chunk_text, ChunkingConfig, ChunkerType};
+/// use kreuzberg::chunking::{chunk_text, ChunkSizing, ChunkingConfig, ChunkerType};
 ///
 /// # fn example() -> kreuzberg::Result<()> {
 /// let config = ChunkingConfig {
@@ -37,6 +40,7 @@ use super::validation::validate_utf8_boundaries;
 ///     overlap: 50,
 ///     trim: true,
 ///     chunker_type: ChunkerType::Text,
+///     sizing: ChunkSizing::Characters,
 ///     embedding: None,
 ///     preset: None,
 /// };
@@ -61,20 +65,34 @@ pub fn chunk_text(
         validate_utf8_boundaries(text, boundaries)?;
     }
 
-    let chunk_config = build_chunk_config(config.max_characters, config.overlap, config.trim)?;
+    let sizer = Sizer::new(config)?;
+    let chunk_config = build_chunk_config(config.max_characters, config.overlap, config.trim)?.with_sizer(&sizer);
 
-    let text_chunks: Vec<&str> = match config.chunker_type {
+    let text_chunks: Vec<(usize, &str)> = match config.chunker_type {
         ChunkerType::Text => {
             let splitter = TextSplitter::new(chunk_config);
-            splitter.chunks(text).collect()
+            splitter.chunk_indices(text).collect()
         }
-        ChunkerType::Markdown => {
+        // Without a document structure, structure chunking splits along markdown instead.
+        ChunkerType::Markdown | ChunkerType::Structure => {
             let splitter = MarkdownSplitter::new(chunk_config);
-            splitter.chunks(text).collect()
+            splitter.chunk_indices(text).collect()
         }
     };
 
-    let chunks = build_chunks(text_chunks.into_iter(), config.overlap, page_boundaries)?;
+    let mut chunks = match sizer {
+        Sizer::Characters { .. } => build_chunks(
+            text_chunks.into_iter().map(|(_, chunk)| chunk),
+            config.overlap,
+            page_boundaries,
+        )?,
+        // A token overlap says nothing about byte offsets, so take them from the splitter.
+        #[cfg(feature = "chunking-tokenizers")]
+        Sizer::Tokens(_) => build_chunks_at(text_chunks, page_boundaries)?,
+    };
+    for chunk in &mut chunks {
+        chunk.metadata.token_count = Some(sizer.token_count(&chunk.content));
+    }
     let chunk_count = chunks.len();
 
     Ok(ChunkingResult { chunks, chunk_count })
@@ -120,6 +138,7 @@ pub fn chunk_text_with_type(
         overlap,
         trim,
         chunker_type,
+        sizing: ChunkSizing::Characters,
         embedding: None,
         preset: None,
     };
@@ -181,6 +200,7 @@ mod tests {
             overlap: 10,
             trim: true,
             chunker_type: ChunkerType::Text,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -189,6 +209,8 @@ mod tests {
         assert_eq!(result.chunks.len(), 1);
         assert_eq!(result.chunk_count, 1);
         assert_eq!(result.chunks[0].content, text);
+        // No tokenizer configured: one estimated token per started four characters of a word.
+        assert_eq!(result.chunks[0].metadata.token_count, Some(1 + 1 + 1 + 2 + 2));
     }
 
     #[test]
@@ -198,6 +220,7 @@ mod tests {
             overlap: 5,
             trim: true,
             chunker_type: ChunkerType::Text,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -215,6 +238,7 @@ mod tests {
             overlap: 5,
             trim: true,
             chunker_type: ChunkerType::Text,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -240,6 +264,7 @@ mod tests {
             overlap: 10,
             trim: true,
             chunker_type: ChunkerType::Markdown,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -256,6 +281,7 @@ mod tests {
             overlap: 10,
             trim: true,
             chunker_type: ChunkerType::Markdown,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -272,6 +298,7 @@ mod tests {
             overlap: 10,
             trim: true,
             chunker_type: ChunkerType::Markdown,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -288,6 +315,7 @@ mod tests {
             overlap: 5,
             trim: true,
             chunker_type: ChunkerType::Text,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -304,6 +332,7 @@ mod tests {
             overlap: 5,
             trim: false,
             chunker_type: ChunkerType::Text,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -320,6 +349,7 @@ mod tests {
             overlap: 20,
             trim: true,
             chunker_type: ChunkerType::Text,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -359,6 +389,7 @@ mod tests {
             overlap: 5,
             trim: true,
             chunker_type: ChunkerType::Text,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -375,6 +406,7 @@ mod tests {
             overlap: 5,
             trim: true,
             chunker_type: ChunkerType::Text,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -397,6 +429,7 @@ mod tests {
             overlap: 20,
             trim: true,
             chunker_type: ChunkerType::Text,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -421,6 +454,7 @@ mod tests {
             overlap: 20,
             trim: true,
             chunker_type: ChunkerType::Text,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -437,6 +471,7 @@ mod tests {
             overlap: 5,
             trim: true,
             chunker_type: ChunkerType::Text,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -452,6 +487,7 @@ mod tests {
             overlap: 10,
             trim: true,
             chunker_type: ChunkerType::Markdown,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -468,6 +504,7 @@ mod tests {
             overlap: 10,
             trim: true,
             chunker_type: ChunkerType::Markdown,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -484,6 +521,7 @@ mod tests {
             overlap: 5,
             trim: true,
             chunker_type: ChunkerType::Text,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -500,6 +538,7 @@ mod tests {
             overlap: 5,
             trim: true,
             chunker_type: ChunkerType::Text,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -517,6 +556,7 @@ mod tests {
             overlap: 5,
             trim: true,
             chunker_type: ChunkerType::Text,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -532,6 +572,7 @@ mod tests {
             overlap: 5,
             trim: true,
             chunker_type: ChunkerType::Text,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -547,6 +588,7 @@ mod tests {
             overlap: 5,
             trim: false,
             chunker_type: ChunkerType::Text,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -601,6 +643,7 @@ mod tests {
             overlap: 0,
             trim: false,
             chunker_type: ChunkerType::Text,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -629,6 +672,7 @@ mod tests {
             overlap: 3,
             trim: false,
             chunker_type: ChunkerType::Text,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -665,6 +709,7 @@ mod tests {
                 overlap,
                 trim: false,
                 chunker_type: ChunkerType::Text,
+                sizing: ChunkSizing::Characters,
                 embedding: None,
                 preset: None,
             };
@@ -699,6 +744,7 @@ mod tests {
             overlap: 5,
             trim: false,
             chunker_type: ChunkerType::Text,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -728,6 +774,7 @@ mod tests {
             overlap: 5,
             trim: true,
             chunker_type: ChunkerType::Text,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -762,6 +809,7 @@ mod tests {
             overlap: 5,
             trim: true,
             chunker_type: ChunkerType::Text,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -783,6 +831,7 @@ mod tests {
             overlap: 5,
             trim: true,
             chunker_type: ChunkerType::Text,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -803,6 +852,7 @@ mod tests {
             overlap: 5,
             trim: false,
             chunker_type: ChunkerType::Text,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -841,6 +891,7 @@ mod tests {
             overlap: 5,
             trim: true,
             chunker_type: ChunkerType::Text,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -866,6 +917,7 @@ mod tests {
             overlap: 5,
             trim: true,
             chunker_type: ChunkerType::Text,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -898,6 +950,7 @@ mod tests {
             overlap: 5,
             trim: true,
             chunker_type: ChunkerType::Text,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -930,6 +983,7 @@ mod tests {
             overlap: 5,
             trim: true,
             chunker_type: ChunkerType::Text,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -967,6 +1021,7 @@ mod tests {
             overlap: 10,
             trim: true,
             chunker_type: ChunkerType::Text,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -991,6 +1046,7 @@ mod tests {
             overlap: 0,
             trim: false,
             chunker_type: ChunkerType::Text,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -1026,6 +1082,7 @@ mod tests {
             overlap: 5,
             trim: true,
             chunker_type: ChunkerType::Text,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -1058,6 +1115,7 @@ mod tests {
             overlap: 2,
             trim: false,
             chunker_type: ChunkerType::Text,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -15,11 +15,13 @@
 //!
 //! - **Text**: Generic text splitter, splits on whitespace and punctuation
 //! - **Markdown**: Markdown-aware splitter, preserves formatting and structure
+//! - **Structure**: Packs the sections, paragraphs, list items and tables of the
+//!   document structure, never splitting a table row (see [`structure`])
 //!
 //! # Example
 //!
 //! ```rust
-//! use kreuzberg::chunking::{chunk_text, ChunkingConfig, ChunkerType};
+//! use kreuzberg::chunking::{chunk_text, ChunkSizing, ChunkingConfig, ChunkerType};
 //!
 //! # fn example() -> kreuzberg::Result<()> {
 //! let config = ChunkingConfig {
@@ -27,6 +29,7 @@
 //!     overlap: 50,
 //!     trim: true,
 //!     chunker_type: ChunkerType::Text,
+//!     sizing: ChunkSizing::Characters,
 //!     embedding: None,
 //!     preset: None,
 //! };
@@ -58,13 +61,16 @@ mod builder;
 pub mod config;
 pub mod core;
 pub mod processor;
+mod sizing;
+pub mod structure;
 pub mod validation;
 
 // Re-export submodule types and functions
 pub use boundaries::{calculate_page_range, validate_page_boundaries};
-pub use config::{ChunkerType, ChunkingConfig, ChunkingResult}; // ChunkingConfig re-exported from core::config::processing
+pub use config::{ChunkSizing, ChunkerType, ChunkingConfig, ChunkingResult}; // ChunkingConfig re-exported from core::config::processing
 pub use core::{chunk_text, chunk_text_with_type, chunk_texts_batch};
 pub use processor::ChunkingProcessor;
+pub use structure::{chunk_document, chunk_structure};
 pub use validation::{ADAPTIVE_VALIDATION_THRESHOLD, precompute_utf8_boundaries, validate_utf8_boundaries};
 
 use crate::error::Result;
@@ -54,8 +54,11 @@ impl PostProcessor for ChunkingProcessor {
             None => return Ok(()),
         };
 
-        let chunking_result = crate::chunking::chunk_text(&result.content, chunking_config, None)
-            .map_err(|e| KreuzbergError::Other(format!("Chunking failed: {}", e)))?;
+        let chunking_result = match chunking_config.chunker_type {
+            crate::chunking::ChunkerType::Structure => crate::chunking::chunk_document(result, chunking_config),
+            _ => crate::chunking::chunk_text(&result.content, chunking_config, None),
+        }
+        .map_err(|e| KreuzbergError::Other(format!("Chunking failed: {}", e)))?;
         result.chunks = Some(chunking_result.chunks);
 
         Ok(())
@@ -91,6 +94,7 @@ mod tests {
                 overlap: 10,
                 trim: true,
                 chunker_type: crate::chunking::ChunkerType::Text,
+                sizing: crate::chunking::ChunkSizing::Characters,
                 embedding: None,
                 preset: None,
             }),
@@ -199,6 +203,7 @@ mod tests {
                 overlap: 10,
                 trim: true,
                 chunker_type: crate::chunking::ChunkerType::Text,
+                sizing: crate::chunking::ChunkSizing::Characters,
                 embedding: None,
                 preset: None,
             }),
@@ -0,0 +1,170 @@
+//! Measuring chunk sizes in characters or tokens.
+//!
+//! With [`ChunkSizing::Tokens`], sizes are counted with a HuggingFace tokenizer loaded
+//! from a `tokenizer.json` file: the configured `tokenizer_path`, or the tokenizer of
+//! the embedding model of the chunking configuration. Loaded tokenizers are cached per
+//! path for the lifetime of the process.
+//!
+//! Every chunk gets a token count. With [`ChunkSizing::Characters`] it is counted with the
+//! tokenizer of the embedding model when the chunking configuration has one (resolved once
+//! per model). Without a tokenizer, or without the `chunking-tokenizers` feature, it is an
+//! estimate: every whitespace-separated word counts as one token per started four
+//! characters, the usual average for subword tokenizers on English text.
+
+use text_splitter::{Characters, ChunkSizer};
+
+use crate::error::{KreuzbergError, Result};
+
+use super::config::{ChunkSizing, ChunkingConfig};
+
+#[cfg(feature = "chunking-tokenizers")]
+use once_cell::sync::Lazy;
+#[cfg(feature = "chunking-tokenizers")]
+use std::collections::HashMap;
+#[cfg(feature = "chunking-tokenizers")]
+use std::path::{Path, PathBuf};
+#[cfg(feature = "chunking-tokenizers")]
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "chunking-tokenizers")]
+use tokenizers::Tokenizer;
+
+/// Characters per token assumed by [`estimate_tokens`].
+const CHARS_PER_TOKEN: usize = 4;
+
+#[cfg(feature = "chunking-tokenizers")]
+static TOKENIZERS: Lazy<Mutex<HashMap<PathBuf, Arc<Tokenizer>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Resolved `tokenizer.json` paths by embedding model and model cache directory.
+#[cfg(all(feature = "chunking-tokenizers", feature = "embeddings"))]
+static TOKENIZER_PATHS: Lazy<Mutex<HashMap<String, PathBuf>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// The measure `max_characters` and `overlap` of a chunking configuration are given in.
+pub(crate) enum Sizer {
+    Characters {
+        /// Tokenizer of the embedding model, only used for token counts
+        #[cfg(feature = "chunking-tokenizers")]
+        counter: Option<Arc<Tokenizer>>,
+    },
+    #[cfg(feature = "chunking-tokenizers")]
+    Tokens(Arc<Tokenizer>),
+}
+
+impl Sizer {
+    /// Resolve the measure of `config`, loading its tokenizer if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::Validation` if token sizing is requested without the
+    /// `chunking-tokenizers` feature or without a tokenizer source, or if the tokenizer
+    /// cannot be loaded.
+    pub(crate) fn new(config: &ChunkingConfig) -> Result<Self> {
+        match &config.sizing {
+            ChunkSizing::Characters => Ok(Self::Characters {
+                #[cfg(feature = "chunking-tokenizers")]
+                counter: counting_tokenizer(config),
+            }),
+            #[cfg(feature = "chunking-tokenizers")]
+            ChunkSizing::Tokens { tokenizer_path } => {
+                let path = match tokenizer_path {
+                    Some(path) => path.clone(),
+                    None => embedding_tokenizer(config)?,
+                };
+                load_tokenizer(&path).map(Self::Tokens)
+            }
+            #[cfg(not(feature = "chunking-tokenizers"))]
+            ChunkSizing::Tokens { .. } => Err(KreuzbergError::validation(
+                "Token-based chunk sizing requires the `chunking-tokenizers` feature".to_string(),
+            )),
+        }
+    }
+
+    /// Token count of `text`, estimated when no tokenizer is known.
+    pub(crate) fn token_count(&self, text: &str) -> usize {
+        match self.token_counter() {
+            Some(counter) => counter.size(text),
+            None => estimate_tokens(text),
+        }
+    }
+
+    fn token_counter(&self) -> Option<&dyn ChunkSizer> {
+        match self {
+            #[cfg(feature = "chunking-tokenizers")]
+            Self::Characters { counter } => counter.as_deref().map(|tokenizer| tokenizer as &dyn ChunkSizer),
+            #[cfg(not(feature = "chunking-tokenizers"))]
+            Self::Characters { .. } => None,
+            #[cfg(feature = "chunking-tokenizers")]
+            Self::Tokens(tokenizer) => Some(tokenizer.as_ref()),
+        }
+    }
+}
+
+impl ChunkSizer for Sizer {
+    fn size(&self, chunk: &str) -> usize {
+        match self {
+            Self::Characters { .. } => Characters.size(chunk),
+            #[cfg(feature = "chunking-tokenizers")]
+            Self::Tokens(tokenizer) => tokenizer.as_ref().size(chunk),
+        }
+    }
+}
+
+/// Estimated token count of `text`: one token per started [`CHARS_PER_TOKEN`] characters
+/// of every whitespace-separated word.
+fn estimate_tokens(text: &str) -> usize {
+    text.split_whitespace()
+        .map(|word| word.chars().count().div_ceil(CHARS_PER_TOKEN))
+        .sum()
+}
+
+/// Path of the tokenizer of the chunking embedding model.
+#[cfg(feature = "chunking-tokenizers")]
+fn embedding_tokenizer(config: &ChunkingConfig) -> Result<PathBuf> {
+    match &config.embedding {
+        #[cfg(feature = "embeddings")]
+        Some(embedding) => {
+            // Resolving builds a HuggingFace Hub client and checks its cache; do it once.
+            let key = serde_json::to_string(&(&embedding.model, &embedding.cache_dir))
+                .map_err(|e| KreuzbergError::validation(format!("Invalid embedding model: {}", e)))?;
+            let mut paths = TOKENIZER_PATHS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            if let Some(path) = paths.get(&key) {
+                return Ok(path.clone());
+            }
+            let path = crate::embeddings::tokenizer_file(embedding)?;
+            paths.insert(key, path.clone());
+            Ok(path)
+        }
+        #[cfg(not(feature = "embeddings"))]
+        Some(_) => Err(KreuzbergError::validation(
+            "Using the tokenizer of the embedding model requires the `embeddings` feature; set `tokenizer_path` instead"
+                .to_string(),
+        )),
+        None => Err(KreuzbergError::validation(
+            "Token-based chunk sizing needs a `tokenizer_path` or an embedding model".to_string(),
+        )),
+    }
+}
+
+/// Tokenizer of the embedding model for token counts under character sizing, if the
+/// configuration has one and it can be loaded.
+#[cfg(feature = "chunking-tokenizers")]
+fn counting_tokenizer(config: &ChunkingConfig) -> Option<Arc<Tokenizer>> {
+    config.embedding.as_ref()?;
+    embedding_tokenizer(config)
+        .and_then(|path| load_tokenizer(&path))
+        .map_err(|e| tracing::debug!("Chunk token counts unavailable: {}", e))
+        .ok()
+}
+
+#[cfg(feature = "chunking-tokenizers")]
+fn load_tokenizer(path: &Path) -> Result<Arc<Tokenizer>> {
+    let mut cache = TOKENIZERS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(tokenizer) = cache.get(path) {
+        return Ok(Arc::clone(tokenizer));
+    }
+
+    let tokenizer = Tokenizer::from_file(path)
+        .map_err(|e| KreuzbergError::validation(format!("Failed to load tokenizer from {}: {}", path.display(), e)))?;
+    let tokenizer = Arc::new(tokenizer);
+    cache.insert(path.to_path_buf(), Arc::clone(&tokenizer));
+    Ok(tokenizer)
+}
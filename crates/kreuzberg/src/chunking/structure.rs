@@ -0,0 +1,877 @@
+//! Structure-aware chunking driven by a [`DocumentStructure`].
+//!
+//! Instead of splitting the flat content, the document tree is flattened into units in
+//! reading order: headings, paragraphs, list items, code blocks and tables. Units are
+//! packed into chunks of at most `max_characters`, measured in the unit of `sizing`:
+//!
+//! - A heading starts a new chunk unless its whole section fits into the current one,
+//!   and a chunk never ends with a heading.
+//! - Units too large for a chunk of their own are split with the text splitter.
+//! - Tables are split only between rows, and every part repeats the header rows. A
+//!   single row longer than `max_characters` becomes an oversized chunk.
+//!
+//! With token sizing, the sizes of the units and separators of a chunk are added up, so
+//! the token count of the joined chunk can differ from that sum by a few tokens.
+//!
+//! Each chunk records the heading path of the section it belongs to and the IDs of the
+//! nodes it contains. `overlap` is not applied between structure chunks.
+
+use std::collections::HashMap;
+
+use text_splitter::{ChunkSizer, TextSplitter};
+
+use crate::error::Result;
+use crate::types::{
+    Chunk, ChunkMetadata, ContentLayer, DocumentNode, DocumentStructure, ExtractionResult, NodeContent, NodeIndex,
+    PageBoundary, TableGrid,
+};
+
+use super::boundaries::calculate_page_range;
+use super::builder::build_chunk_config;
+use super::config::{ChunkingConfig, ChunkingResult};
+use super::sizing::Sizer;
+
+/// Separator placed between units of one chunk.
+const SEPARATOR: &str = "\n\n";
+
+/// How far ahead in the content a unit's text is searched for when computing offsets.
+const SEARCH_WINDOW: usize = 64 * 1024;
+
+/// Longest prefix or suffix of a unit used to find it in the content.
+const PROBE_BYTES: usize = 64;
+
+/// A long text only starts in the chunk of its carried headings if at least this fraction
+/// (one over the divisor) of the chunk size is left for it.
+const MIN_PIECE_DIVISOR: usize = 4;
+
+/// Chunk an extraction result along its document structure.
+///
+/// Uses `result.document` when the extractor produced one and otherwise derives a
+/// structure from the pages, tables and content of the result.
+pub fn chunk_document(result: &ExtractionResult, config: &ChunkingConfig) -> Result<ChunkingResult> {
+    let page_boundaries = result.metadata.pages.as_ref().and_then(|ps| ps.boundaries.as_deref());
+    match &result.document {
+        Some(structure) => chunk_structure(structure, &result.content, config, page_boundaries),
+        None => {
+            let structure = crate::extraction::transform::transform_to_document_structure(result);
+            chunk_structure(&structure, &result.content, config, page_boundaries)
+        }
+    }
+}
+
+/// Split the body of `structure` into chunks.
+///
+/// `content` is the text the byte offsets of the chunks refer to, normally the content
+/// of the extraction result the structure belongs to. A chunk spans from the first to
+/// the last of its units that could be found in `content`. Page numbers come from the
+/// nodes, or from `page_boundaries` when the nodes carry none.
+pub fn chunk_structure(
+    structure: &DocumentStructure,
+    content: &str,
+    config: &ChunkingConfig,
+    page_boundaries: Option<&[PageBoundary]>,
+) -> Result<ChunkingResult> {
+    // Validates max_characters the same way the text chunkers do.
+    build_chunk_config(config.max_characters, 0, config.trim)?;
+    let sizer = Sizer::new(config)?;
+
+    let units = flatten(structure);
+    let mut packer = Packer {
+        max: config.max_characters,
+        sizer: &sizer,
+        separator: sizer.size(SEPARATOR),
+        newline: sizer.size("\n"),
+        section_lengths: section_lengths(&units, &sizer),
+        current: Vec::new(),
+        current_size: 0,
+        chunks: Vec::new(),
+    };
+    for (index, unit) in units.iter().enumerate() {
+        packer.add(index, unit);
+    }
+    packer.flush(true);
+
+    let mut locator = Locator { content, cursor: 0 };
+    let total_chunks = packer.chunks.len();
+    let mut chunks = Vec::with_capacity(total_chunks);
+    for (chunk_index, parts) in packer.chunks.into_iter().enumerate() {
+        let mut chunk = locator.build(parts, chunk_index, total_chunks, page_boundaries)?;
+        chunk.metadata.token_count = Some(sizer.token_count(&chunk.content));
+        chunks.push(chunk);
+    }
+
+    Ok(ChunkingResult {
+        chunk_count: chunks.len(),
+        chunks,
+    })
+}
+
+/// A leaf of the document tree in reading order.
+#[derive(Debug)]
+struct Unit<'a> {
+    node: &'a DocumentNode,
+    /// Heading texts of the enclosing sections, including this unit's own for headings
+    path: Vec<String>,
+    kind: UnitKind,
+}
+
+#[derive(Debug)]
+enum UnitKind {
+    Heading {
+        level: u8,
+        text: String,
+    },
+    Text(String),
+    Table {
+        header: Vec<Vec<String>>,
+        rows: Vec<Vec<String>>,
+    },
+}
+
+impl Unit<'_> {
+    fn is_heading(&self) -> bool {
+        matches!(self.kind, UnitKind::Heading { .. })
+    }
+
+    /// Size of the unit when kept whole.
+    fn size(&self, sizer: &Sizer) -> usize {
+        match &self.kind {
+            UnitKind::Heading { text, .. } | UnitKind::Text(text) => sizer.size(text),
+            UnitKind::Table { header, rows } => sizer.size(&render_table(header, rows)),
+        }
+    }
+}
+
+/// A unit, or a part of one, placed in a chunk.
+#[derive(Debug)]
+struct Part<'a> {
+    unit: &'a Unit<'a>,
+    text: String,
+    /// Text expected at the start and end of the part in the content
+    probes: (String, String),
+}
+
+fn flatten(structure: &DocumentStructure) -> Vec<Unit<'_>> {
+    let mut flattener = Flattener {
+        structure,
+        stack: Vec::new(),
+        units: Vec::new(),
+    };
+    for (index, _) in structure.body_roots() {
+        flattener.visit(index, None);
+    }
+    flattener.units
+}
+
+struct Flattener<'a> {
+    structure: &'a DocumentStructure,
+    /// Open headings as (level, text)
+    stack: Vec<(u8, String)>,
+    units: Vec<Unit<'a>>,
+}
+
+impl<'a> Flattener<'a> {
+    /// Visit `index`; `marker` is the list marker when the node is a list item.
+    fn visit(&mut self, index: NodeIndex, marker: Option<String>) {
+        let Some(node) = self.structure.get(index) else {
+            return;
+        };
+        if node.content_layer != ContentLayer::Body {
+            return;
+        }
+
+        match &node.content {
+            NodeContent::Group {
+                heading_level,
+                heading_text: Some(text),
+                ..
+            } => self.open_section(node, heading_level.unwrap_or(1), text),
+            NodeContent::Heading { level, text } => {
+                if !self.is_group_heading(node, text) {
+                    self.open_section(node, *level, text);
+                }
+            }
+            NodeContent::List { ordered } => {
+                for (i, child) in node.children.iter().enumerate() {
+                    let marker = if *ordered {
+                        format!("{}.", i + 1)
+                    } else {
+                        "-".to_string()
+                    };
+                    self.visit(*child, Some(marker));
+                }
+                return;
+            }
+            NodeContent::ListItem { text } => {
+                let text = match marker {
+                    Some(marker) => format!("{} {}", marker, text.trim()),
+                    None => text.trim().to_string(),
+                };
+                self.push(node, UnitKind::Text(text));
+            }
+            NodeContent::Table { grid } => {
+                let (header, rows) = table_rows(grid);
+                if !header.is_empty() || !rows.is_empty() {
+                    self.push(node, UnitKind::Table { header, rows });
+                }
+            }
+            NodeContent::Image { description, .. } => {
+                if let Some(description) = description {
+                    self.push(node, UnitKind::Text(description.trim().to_string()));
+                }
+            }
+            content => {
+                if let Some(text) = content.text() {
+                    self.push(node, UnitKind::Text(text.trim().to_string()));
+                }
+            }
+        }
+
+        for child in &node.children {
+            self.visit(*child, None);
+        }
+    }
+
+    /// Whether `node` is the heading of its parent group, which already opened the section.
+    fn is_group_heading(&self, node: &DocumentNode, text: &str) -> bool {
+        node.parent
+            .and_then(|parent| self.structure.get(parent))
+            .is_some_and(|parent| {
+                matches!(&parent.content, NodeContent::Group { heading_text: Some(heading), .. } if heading == text)
+            })
+    }
+
+    fn open_section(&mut self, node: &'a DocumentNode, level: u8, text: &str) {
+        let text = text.trim();
+        while self.stack.last().is_some_and(|(open, _)| *open >= level) {
+            self.stack.pop();
+        }
+        self.stack.push((level, text.to_string()));
+        self.push(
+            node,
+            UnitKind::Heading {
+                level,
+                text: text.to_string(),
+            },
+        );
+    }
+
+    fn push(&mut self, node: &'a DocumentNode, kind: UnitKind) {
+        if let UnitKind::Heading { text, .. } | UnitKind::Text(text) = &kind
+            && text.is_empty()
+        {
+            return;
+        }
+        self.units.push(Unit {
+            node,
+            path: self.stack.iter().map(|(_, text)| text.clone()).collect(),
+            kind,
+        });
+    }
+}
+
+/// Split a grid into header rows and body rows of cell texts.
+///
+/// Header rows are the leading rows containing a header cell.
+fn table_rows(grid: &TableGrid) -> (Vec<Vec<String>>, Vec<Vec<String>>) {
+    let mut rows: Vec<(bool, Vec<String>)> = vec![(false, vec![String::new(); grid.cols as usize]); grid.rows as usize];
+    for cell in &grid.cells {
+        let (Some((is_header, row)), col) = (rows.get_mut(cell.row as usize), cell.col as usize) else {
+            continue;
+        };
+        if col >= row.len() {
+            row.resize(col + 1, String::new());
+        }
+        row[col] = cell.content.trim().to_string();
+        *is_header |= cell.is_header;
+    }
+    rows.retain(|(_, row)| row.iter().any(|cell| !cell.is_empty()));
+
+    let header_rows = rows.iter().take_while(|(is_header, _)| *is_header).count();
+    let body = rows.split_off(header_rows);
+    (
+        rows.into_iter().map(|(_, row)| row).collect(),
+        body.into_iter().map(|(_, row)| row).collect(),
+    )
+}
+
+/// Render header and body rows as a markdown table.
+fn render_table(header: &[Vec<String>], rows: &[Vec<String>]) -> String {
+    let cols = header.iter().chain(rows).map(Vec::len).max().unwrap_or(0);
+    let line = |row: &[String]| {
+        let mut line = String::from("|");
+        for col in 0..cols {
+            line.push(' ');
+            line.push_str(&row.get(col).map(|c| c.replace('|', "\\|")).unwrap_or_default());
+            line.push_str(" |");
+        }
+        line
+    };
+
+    let mut lines: Vec<String> = header.iter().map(|row| line(row)).collect();
+    if !header.is_empty() {
+        lines.push(format!("|{}", " --- |".repeat(cols)));
+    }
+    lines.extend(rows.iter().map(|row| line(row)));
+    lines.join("\n")
+}
+
+/// Size of each section, keyed by the index of its heading unit. A section runs up to
+/// the next heading of the same or a higher level.
+fn section_lengths(units: &[Unit<'_>], sizer: &Sizer) -> HashMap<usize, usize> {
+    let separator = sizer.size(SEPARATOR);
+    let mut lengths = HashMap::new();
+    let mut open: Vec<(u8, usize, usize)> = Vec::new();
+    for (index, unit) in units.iter().enumerate() {
+        if let UnitKind::Heading { level, .. } = unit.kind {
+            while let Some(&(open_level, start, length)) = open.last()
+                && open_level >= level
+            {
+                open.pop();
+                lengths.insert(start, length);
+            }
+            open.push((level, index, 0));
+        }
+        let length = unit.size(sizer);
+        for (_, _, section) in open.iter_mut() {
+            *section += if *section == 0 { length } else { length + separator };
+        }
+    }
+    for (_, start, length) in open {
+        lengths.insert(start, length);
+    }
+    lengths
+}
+
+struct Packer<'a> {
+    max: usize,
+    sizer: &'a Sizer,
+    /// Sizes of [`SEPARATOR`] and of the newline between table rows
+    separator: usize,
+    newline: usize,
+    section_lengths: HashMap<usize, usize>,
+    current: Vec<Part<'a>>,
+    /// Sum of the sizes of the parts in `current`, each measured once when pushed
+    current_size: usize,
+    chunks: Vec<Vec<Part<'a>>>,
+}
+
+impl<'a> Packer<'a> {
+    fn add(&mut self, index: usize, unit: &'a Unit<'a>) {
+        if unit.is_heading() && !self.current.is_empty() {
+            let section = self
+                .section_lengths
+                .get(&index)
+                .copied()
+                .unwrap_or_else(|| unit.size(self.sizer));
+            if !self.fits(section) {
+                self.flush(false);
+            }
+        }
+
+        match &unit.kind {
+            UnitKind::Heading { text, .. } => {
+                let size = self.sizer.size(text);
+                self.push(unit, text.clone(), probes(text), size);
+            }
+            UnitKind::Text(text) => self.add_text(unit, text),
+            UnitKind::Table { header, rows } => self.add_table(unit, header, rows),
+        }
+    }
+
+    fn add_text(&mut self, unit: &'a Unit<'a>, text: &str) {
+        let length = self.sizer.size(text);
+        if self.fits(length) {
+            self.push(unit, text.to_string(), probes(text), length);
+            return;
+        }
+        self.flush(false);
+        if self.fits(length) {
+            self.push(unit, text.to_string(), probes(text), length);
+            return;
+        }
+
+        // Too large for one chunk: fill what is left after any carried headings, unless
+        // that is too little for a useful piece, then continue in chunks of their own.
+        let mut rest = text;
+        if !self.current.is_empty() {
+            if self.remaining() >= self.min_piece()
+                && let Some((piece, end)) = split_first(rest, self.remaining(), self.sizer)
+            {
+                let size = self.sizer.size(piece);
+                self.push(unit, piece.to_string(), probes(piece), size);
+                rest = &rest[end..];
+            }
+            self.flush(true);
+        }
+
+        let Ok(config) = build_chunk_config(self.max, 0, true) else {
+            return;
+        };
+        let splitter = TextSplitter::new(config.with_sizer(self.sizer));
+        for (_, piece) in splitter.chunk_indices(rest) {
+            if !self.current.is_empty() {
+                self.flush(true);
+            }
+            let size = self.sizer.size(piece);
+            self.push(unit, piece.to_string(), probes(piece), size);
+        }
+    }
+
+    fn add_table(&mut self, unit: &'a Unit<'a>, header: &[Vec<String>], rows: &[Vec<String>]) {
+        let whole = render_table(header, rows);
+        let length = self.sizer.size(&whole);
+        if !self.fits(length) {
+            self.flush(false);
+        }
+        if self.fits(length) || rows.is_empty() {
+            self.push(unit, whole, table_probes(header, rows), length);
+            return;
+        }
+
+        let mut start = 0;
+        while start < rows.len() {
+            let mut end = start + 1;
+            let mut length = self.sizer.size(&render_table(header, &rows[start..end]));
+            while end < rows.len() {
+                let next = length + self.newline + self.sizer.size(&render_table(&[], &rows[end..=end]));
+                if !self.fits(next) {
+                    break;
+                }
+                length = next;
+                end += 1;
+            }
+            let part = &rows[start..end];
+            let text = render_table(header, part);
+            let size = self.sizer.size(&text);
+            self.push(unit, text, table_probes(&[], part), size);
+            start = end;
+            if start < rows.len() {
+                self.flush(true);
+            }
+        }
+    }
+
+    fn push(&mut self, unit: &'a Unit<'a>, text: String, probes: (String, String), size: usize) {
+        self.current_size += size;
+        self.current.push(Part { unit, text, probes });
+    }
+
+    fn length(&self) -> usize {
+        self.current_size + self.separator * self.current.len().saturating_sub(1)
+    }
+
+    /// Room left for a new unit, after its separator.
+    fn remaining(&self) -> usize {
+        if self.current.is_empty() {
+            self.max
+        } else {
+            self.max.saturating_sub(self.length() + self.separator)
+        }
+    }
+
+    /// Smallest room worth filling with the start of a text that continues in later chunks.
+    fn min_piece(&self) -> usize {
+        (self.max / MIN_PIECE_DIVISOR).max(1)
+    }
+
+    fn fits(&self, length: usize) -> bool {
+        length <= self.remaining()
+    }
+
+    /// Close the current chunk. Unless `force` is set, a chunk holding only headings is
+    /// kept open so the headings lead the next chunk.
+    fn flush(&mut self, force: bool) {
+        if self.current.is_empty() || (!force && self.current.iter().all(|part| part.unit.is_heading())) {
+            return;
+        }
+        self.chunks.push(std::mem::take(&mut self.current));
+        self.current_size = 0;
+    }
+}
+
+/// The first piece the text splitter produces from `text` with the given capacity, and
+/// the byte offset where it ends.
+fn split_first<'t>(text: &'t str, capacity: usize, sizer: &Sizer) -> Option<(&'t str, usize)> {
+    let config = build_chunk_config(capacity, 0, true).ok()?.with_sizer(sizer);
+    TextSplitter::new(config)
+        .chunk_indices(text)
+        .next()
+        .map(|(offset, piece)| (piece, offset + piece.len()))
+}
+
+/// Trimmed first and last lines of `text`, cut to [`PROBE_BYTES`].
+fn probes(text: &str) -> (String, String) {
+    let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+    let first = lines.next().unwrap_or_default();
+    let last = lines.next_back().unwrap_or(first);
+    (prefix(first).to_string(), suffix(last).to_string())
+}
+
+fn table_probes(header: &[Vec<String>], rows: &[Vec<String>]) -> (String, String) {
+    let mut cells = header.iter().chain(rows).flatten().filter(|cell| !cell.is_empty());
+    let first = cells.next().map(String::as_str).unwrap_or_default();
+    let last = cells.next_back().map(String::as_str).unwrap_or(first);
+    (prefix(first).to_string(), suffix(last).to_string())
+}
+
+fn prefix(text: &str) -> &str {
+    let mut end = text.len().min(PROBE_BYTES);
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+fn suffix(text: &str) -> &str {
+    let mut start = text.len().saturating_sub(PROBE_BYTES);
+    while !text.is_char_boundary(start) {
+        start += 1;
+    }
+    &text[start..]
+}
+
+/// Maps parts to byte ranges of the content, moving forward through it.
+struct Locator<'a> {
+    content: &'a str,
+    cursor: usize,
+}
+
+impl Locator<'_> {
+    fn build(
+        &mut self,
+        parts: Vec<Part<'_>>,
+        chunk_index: usize,
+        total_chunks: usize,
+        page_boundaries: Option<&[PageBoundary]>,
+    ) -> Result<Chunk> {
+        let mut range: Option<(usize, usize)> = None;
+        for part in &parts {
+            if let Some((start, end)) = self.locate(&part.probes) {
+                range = Some(range.map_or((start, end), |(first, _)| (first, end)));
+            }
+        }
+        let (byte_start, byte_end) = range.unwrap_or((self.cursor, self.cursor));
+
+        let pages = parts.iter().filter_map(|part| {
+            let node = part.unit.node;
+            node.page.map(|page| (page, node.page_end.unwrap_or(page).max(page)))
+        });
+        let (first_page, last_page) = match pages.reduce(|(a, b), (c, d)| (a.min(c), b.max(d))) {
+            Some((first, last)) => (Some(first as usize), Some(last as usize)),
+            None => match page_boundaries {
+                Some(boundaries) => calculate_page_range(byte_start, byte_end, boundaries)?,
+                None => (None, None),
+            },
+        };
+
+        let mut node_ids: Vec<String> = Vec::new();
+        for part in &parts {
+            let id = part.unit.node.id.to_string();
+            if node_ids.last() != Some(&id) {
+                node_ids.push(id);
+            }
+        }
+
+        // The section the chunk's body belongs to; headings carried over from an
+        // enclosing section do not shorten the path.
+        let mut paths: Vec<&Vec<String>> = parts
+            .iter()
+            .filter(|part| !part.unit.is_heading())
+            .map(|part| &part.unit.path)
+            .collect();
+        if paths.is_empty() {
+            paths = parts.iter().map(|part| &part.unit.path).collect();
+        }
+        let mut heading_path = paths.first().map(|path| path.to_vec()).unwrap_or_default();
+        for path in &paths[1..] {
+            let common = heading_path.iter().zip(path.iter()).take_while(|(a, b)| a == b).count();
+            heading_path.truncate(common);
+        }
+
+        let content = parts
+            .iter()
+            .map(|part| part.text.as_str())
+            .collect::<Vec<_>>()
+            .join(SEPARATOR);
+
+        Ok(Chunk {
+            content,
+            embedding: None,
+            metadata: ChunkMetadata {
+                byte_start,
+                byte_end,
+                token_count: None,
+                chunk_index,
+                total_chunks,
+                first_page,
+                last_page,
+                heading_path: Some(heading_path),
+                node_ids: Some(node_ids),
+            },
+        })
+    }
+
+    /// Find a part's probes at or after the cursor and advance past it.
+    fn locate(&mut self, (first, last): &(String, String)) -> Option<(usize, usize)> {
+        if first.is_empty() {
+            return None;
+        }
+        let mut window_end = (self.cursor + SEARCH_WINDOW).min(self.content.len());
+        while !self.content.is_char_boundary(window_end) {
+            window_end += 1;
+        }
+        let window = &self.content[self.cursor..window_end];
+
+        let start = self.cursor + window.find(first.as_str())?;
+        let end = self.content[start..window_end]
+            .find(last.as_str())
+            .map(|offset| start + offset + last.len())
+            .filter(|end| *end >= start + first.len())
+            .unwrap_or(start + first.len());
+        self.cursor = end;
+        Some((start, end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunking::{ChunkSizing, ChunkerType};
+    use crate::types::{GridCell, NodeId};
+
+    fn node(id: usize, content: NodeContent, parent: Option<u32>) -> DocumentNode {
+        DocumentNode {
+            id: NodeId::new(format!("node-{}", id)),
+            content,
+            parent: parent.map(NodeIndex),
+            children: Vec::new(),
+            content_layer: ContentLayer::Body,
+            page: Some(1 + id as u32 / 4),
+            page_end: None,
+            bbox: None,
+            annotations: Vec::new(),
+        }
+    }
+
+    fn structure(contents: Vec<(NodeContent, Option<u32>)>) -> DocumentStructure {
+        let mut structure = DocumentStructure::new();
+        for (i, (content, parent)) in contents.into_iter().enumerate() {
+            structure.nodes.push(node(i, content, parent));
+            if let Some(parent) = parent {
+                structure.nodes[parent as usize].children.push(NodeIndex(i as u32));
+            }
+        }
+        structure
+    }
+
+    fn group(level: u8, text: &str) -> NodeContent {
+        NodeContent::Group {
+            label: None,
+            heading_level: Some(level),
+            heading_text: Some(text.to_string()),
+        }
+    }
+
+    fn paragraph(text: &str) -> NodeContent {
+        NodeContent::Paragraph { text: text.to_string() }
+    }
+
+    fn table(rows: &[Vec<&str>]) -> NodeContent {
+        let mut cells = Vec::new();
+        for (row, values) in rows.iter().enumerate() {
+            for (col, value) in values.iter().enumerate() {
+                cells.push(GridCell {
+                    content: value.to_string(),
+                    row: row as u32,
+                    col: col as u32,
+                    row_span: 1,
+                    col_span: 1,
+                    is_header: row == 0,
+                    bbox: None,
+                });
+            }
+        }
+        NodeContent::Table {
+            grid: TableGrid {
+                rows: rows.len() as u32,
+                cols: rows.first().map_or(0, |r| r.len() as u32),
+                cells,
+            },
+        }
+    }
+
+    fn config(max_characters: usize) -> ChunkingConfig {
+        ChunkingConfig {
+            max_characters,
+            overlap: 0,
+            trim: true,
+            chunker_type: ChunkerType::Structure,
+            sizing: ChunkSizing::Characters,
+            embedding: None,
+            preset: None,
+        }
+    }
+
+    #[test]
+    fn test_small_sections_share_a_chunk() {
+        let doc = structure(vec![
+            (group(1, "Manual"), None),
+            (group(2, "Install"), Some(0)),
+            (paragraph("Run the installer."), Some(1)),
+            (group(2, "Usage"), Some(0)),
+            (paragraph("Start the tool."), Some(3)),
+        ]);
+        let content = "Manual\n\nInstall\n\nRun the installer.\n\nUsage\n\nStart the tool.";
+
+        let result = chunk_structure(&doc, content, &config(1000), None).unwrap();
+
+        assert_eq!(result.chunk_count, 1);
+        let chunk = &result.chunks[0];
+        assert_eq!(chunk.content, content);
+        assert_eq!(chunk.metadata.heading_path, Some(vec!["Manual".to_string()]));
+        assert_eq!(chunk.metadata.node_ids.as_ref().unwrap().len(), 5);
+        assert_eq!((chunk.metadata.byte_start, chunk.metadata.byte_end), (0, content.len()));
+        assert_eq!(
+            (chunk.metadata.first_page, chunk.metadata.last_page),
+            (Some(1), Some(2))
+        );
+    }
+
+    #[test]
+    fn test_sections_that_do_not_fit_start_new_chunks() {
+        let body = "word ".repeat(12);
+        let doc = structure(vec![
+            (group(1, "Manual"), None),
+            (group(2, "Install"), Some(0)),
+            (paragraph(&body), Some(1)),
+            (group(2, "Usage"), Some(0)),
+            (paragraph(&body), Some(3)),
+        ]);
+
+        let result = chunk_structure(&doc, "", &config(90), None).unwrap();
+
+        let paths: Vec<_> = result
+            .chunks
+            .iter()
+            .map(|c| c.metadata.heading_path.clone().unwrap())
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                vec!["Manual".to_string(), "Install".to_string()],
+                vec!["Manual".to_string(), "Usage".to_string()],
+            ]
+        );
+        assert!(result.chunks[1].content.starts_with("Usage\n\nword"));
+        assert!(result.chunks.iter().all(|c| c.content.chars().count() <= 90));
+    }
+
+    #[test]
+    fn test_oversized_paragraph_is_split_after_its_heading() {
+        let body = "Lorem ipsum dolor sit amet. ".repeat(10);
+        let doc = structure(vec![(group(1, "Intro"), None), (paragraph(&body), Some(0))]);
+
+        let result = chunk_structure(&doc, &format!("Intro\n\n{}", body), &config(100), None).unwrap();
+
+        assert!(result.chunk_count > 2);
+        assert!(result.chunks[0].content.starts_with("Intro\n\nLorem"));
+        for chunk in &result.chunks {
+            assert!(chunk.content.chars().count() <= 100, "{:?}", chunk.content);
+            assert_eq!(chunk.metadata.heading_path, Some(vec!["Intro".to_string()]));
+        }
+        let offsets: Vec<_> = result.chunks.iter().map(|c| c.metadata.byte_start).collect();
+        assert!(offsets.windows(2).all(|w| w[0] < w[1]), "{:?}", offsets);
+    }
+
+    #[test]
+    fn test_long_heading_does_not_get_a_sliver_of_text() {
+        let heading = "A heading long enough to leave almost no room in its chunk for the text after it";
+        let body = "Lorem ipsum dolor sit amet. ".repeat(10);
+        let doc = structure(vec![(group(1, heading), None), (paragraph(&body), Some(0))]);
+
+        let result = chunk_structure(&doc, &format!("{}\n\n{}", heading, body), &config(100), None).unwrap();
+
+        assert_eq!(result.chunks[0].content, heading);
+        assert!(result.chunks[1].content.starts_with("Lorem ipsum"));
+        assert!(result.chunks.iter().all(|c| c.content.chars().count() <= 100));
+    }
+
+    #[test]
+    fn test_tables_split_between_rows_with_repeated_header() {
+        let rows: Vec<(String, String)> = (0..10)
+            .map(|i| (format!("part-{}", i), format!("{} mm", i * 5)))
+            .collect();
+        let mut grid = vec![vec!["Part", "Size"]];
+        grid.extend(rows.iter().map(|(part, size)| vec![part.as_str(), size.as_str()]));
+        let doc = structure(vec![(group(1, "Parts"), None), (table(&grid), Some(0))]);
+
+        let result = chunk_structure(&doc, "", &config(100), None).unwrap();
+
+        assert!(result.chunk_count > 1);
+        assert!(result.chunks[0].content.starts_with("Parts\n\n| Part | Size |"));
+        let mut seen = Vec::new();
+        for chunk in &result.chunks {
+            assert!(
+                chunk.content.contains("| Part | Size |\n| --- | --- |"),
+                "{}",
+                chunk.content
+            );
+            assert_eq!(chunk.metadata.node_ids.as_ref().unwrap().last().unwrap(), "node-1");
+            seen.extend(
+                chunk
+                    .content
+                    .lines()
+                    .filter(|l| l.starts_with("| part-"))
+                    .map(String::from),
+            );
+        }
+        let expected: Vec<_> = rows
+            .iter()
+            .map(|(part, size)| format!("| {} | {} |", part, size))
+            .collect();
+        assert_eq!(seen, expected);
+    }
+
+    #[test]
+    fn test_oversized_row_is_kept_whole() {
+        let long = "x".repeat(150);
+        let doc = structure(vec![(
+            table(&[vec!["Key", "Value"], vec!["a", &long], vec!["b", "short"]]),
+            None,
+        )]);
+
+        let result = chunk_structure(&doc, "", &config(100), None).unwrap();
+
+        assert_eq!(result.chunk_count, 2);
+        assert!(result.chunks[0].content.ends_with(&format!("| a | {} |", long)));
+        assert!(result.chunks[1].content.ends_with("| b | short |"));
+    }
+
+    #[test]
+    fn test_lists_and_furniture() {
+        let mut doc = structure(vec![
+            (NodeContent::List { ordered: true }, None),
+            (
+                NodeContent::ListItem {
+                    text: "First".to_string(),
+                },
+                Some(0),
+            ),
+            (
+                NodeContent::ListItem {
+                    text: "Second".to_string(),
+                },
+                Some(0),
+            ),
+            (paragraph("Page 1 of 3"), None),
+        ]);
+        doc.nodes[3].content_layer = ContentLayer::Footer;
+
+        let result = chunk_structure(&doc, "", &config(1000), None).unwrap();
+
+        assert_eq!(result.chunk_count, 1);
+        assert_eq!(result.chunks[0].content, "1. First\n\n2. Second");
+        assert_eq!(result.chunks[0].metadata.heading_path, Some(Vec::new()));
+    }
+}
@@ -0,0 +1,461 @@
+//! Cooperative cancellation and per-document timeouts.
+//!
+//! A [`CancellationToken`] set as [`ExtractionConfig::cancellation`] cancels every
+//! extraction using that configuration, including all documents of a batch.
+//! [`ExtractionConfig::timeout_secs`] bounds each document separately.
+//!
+//! Extractors check for cancellation between units of work (pages, sheets, slides, OCR'd
+//! images) with [`ExtractionConfig::checkpoint`]. By default a cancelled extraction fails
+//! with [`KreuzbergError::Cancelled`] as soon as it is noticed. With
+//! [`ExtractionConfig::partial_results_on_cancel`] the extractor stops at the next
+//! checkpoint instead, and the content extracted so far is returned with a
+//! [`ProcessingWarning`] saying where extraction stopped.
+//!
+//! The same per-document token collects the units that extractors skip in
+//! [`best_effort`](ExtractionConfig::best_effort) mode with [`ExtractionConfig::recover`],
+//! so that one damaged page, slide, sheet or archive entry costs only that unit.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use kreuzberg::core::cancellation::CancellationToken;
+//! use kreuzberg::ExtractionConfig;
+//!
+//! # async fn example() -> kreuzberg::Result<()> {
+//! let token = CancellationToken::new();
+//! let config = ExtractionConfig {
+//!     cancellation: Some(token.clone()),
+//!     timeout_secs: Some(30),
+//!     ..Default::default()
+//! };
+//!
+//! // e.g. from a "stop" button handler
+//! let stop = token.clone();
+//! tokio::spawn(async move { stop.cancel() });
+//!
+//! let results = kreuzberg::batch_extract_file(vec!["a.pdf", "b.pdf"], &config).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::core::config::ExtractionConfig;
+use crate::error::CancellationReason;
+use crate::types::{ExtractionResult, ProcessingWarning};
+use crate::{KreuzbergError, Result};
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+
+/// Handle to cancel one or more extractions.
+///
+/// Cloning yields a handle to the same token. Cancelling a token also cancels the tokens
+/// derived from it with [`child`](Self::child) and [`child_with_timeout`](Self::child_with_timeout).
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    cancelled: AtomicBool,
+    deadline: Option<Instant>,
+    children: Mutex<Vec<Weak<Inner>>>,
+    /// Where an extractor stopped early to return partial results.
+    stopped_at: Mutex<Option<String>>,
+    /// Units skipped in best-effort mode, as warning messages.
+    skipped: Mutex<Vec<String>>,
+    #[cfg(feature = "tokio-runtime")]
+    notify: tokio::sync::Notify,
+}
+
+impl CancellationToken {
+    /// Create a token that is only cancelled by [`cancel`](Self::cancel).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a token that is cancelled after `timeout`, or earlier by [`cancel`](Self::cancel).
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Self::from_inner(Inner {
+            deadline: Instant::now().checked_add(timeout),
+            ..Default::default()
+        })
+    }
+
+    /// Create a token that is cancelled together with this one.
+    pub fn child(&self) -> Self {
+        self.derive(self.inner.deadline)
+    }
+
+    /// Create a token that is cancelled together with this one, or after `timeout`.
+    pub fn child_with_timeout(&self, timeout: Duration) -> Self {
+        let deadline = Instant::now().checked_add(timeout);
+        self.derive(match (self.inner.deadline, deadline) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        })
+    }
+
+    /// Cancel this token and all tokens derived from it.
+    pub fn cancel(&self) {
+        Self::cancel_inner(&self.inner);
+    }
+
+    /// Whether the token has been cancelled or its deadline has passed.
+    pub fn is_cancelled(&self) -> bool {
+        self.reason().is_some()
+    }
+
+    /// Why the token is cancelled, or `None` if it is not.
+    pub fn reason(&self) -> Option<CancellationReason> {
+        if self.inner.cancelled.load(Ordering::Acquire) {
+            Some(CancellationReason::Cancelled)
+        } else if self.inner.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            Some(CancellationReason::TimedOut)
+        } else {
+            None
+        }
+    }
+
+    /// Return [`KreuzbergError::Cancelled`] if the token is cancelled.
+    pub fn check(&self) -> Result<()> {
+        match self.reason() {
+            Some(reason) => Err(KreuzbergError::Cancelled(reason)),
+            None => Ok(()),
+        }
+    }
+
+    /// Wait until the token is cancelled or its deadline passes.
+    #[cfg(feature = "tokio-runtime")]
+    pub async fn cancelled(&self) {
+        loop {
+            let notified = self.inner.notify.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            if self.is_cancelled() {
+                return;
+            }
+            match self.inner.deadline {
+                Some(deadline) => {
+                    tokio::select! {
+                        _ = notified => {}
+                        _ = tokio::time::sleep_until(deadline.into()) => {}
+                    }
+                }
+                None => notified.await,
+            }
+        }
+    }
+
+    fn from_inner(inner: Inner) -> Self {
+        Self { inner: Arc::new(inner) }
+    }
+
+    fn derive(&self, deadline: Option<Instant>) -> Self {
+        let child = Self::from_inner(Inner {
+            deadline,
+            ..Default::default()
+        });
+        let mut children = self.inner.children.lock().unwrap_or_else(|e| e.into_inner());
+        children.retain(|weak| weak.strong_count() > 0);
+        children.push(Arc::downgrade(&child.inner));
+        if self.inner.cancelled.load(Ordering::Acquire) {
+            child.inner.cancelled.store(true, Ordering::Release);
+        }
+        child
+    }
+
+    fn cancel_inner(inner: &Inner) {
+        if inner.cancelled.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        #[cfg(feature = "tokio-runtime")]
+        inner.notify.notify_waiters();
+        let children = std::mem::take(&mut *inner.children.lock().unwrap_or_else(|e| e.into_inner()));
+        for child in children.iter().filter_map(Weak::upgrade) {
+            Self::cancel_inner(&child);
+        }
+    }
+
+    fn record_stop(&self, location: String) {
+        self.inner
+            .stopped_at
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get_or_insert(location);
+    }
+
+    fn stopped_at(&self) -> Option<String> {
+        self.inner.stopped_at.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    fn record_skip(&self, message: String) {
+        self.inner
+            .skipped
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(message);
+    }
+
+    fn take_skipped(&self) -> Vec<String> {
+        std::mem::take(&mut *self.inner.skipped.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+impl std::fmt::Debug for CancellationToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CancellationToken")
+            .field("reason", &self.reason())
+            .field("deadline", &self.inner.deadline)
+            .finish()
+    }
+}
+
+impl ExtractionConfig {
+    /// Check for cancellation at a unit boundary such as a page, sheet, slide or OCR'd image.
+    ///
+    /// Returns `Ok(false)` to continue. When the extraction has been cancelled or timed out,
+    /// returns [`KreuzbergError::Cancelled`], or `Ok(true)` with
+    /// [`partial_results_on_cancel`](Self::partial_results_on_cancel), in which case the
+    /// caller should stop and return what it has extracted so far. `location` names the
+    /// unit that would have been processed next, e.g. `"page 12"`.
+    pub fn checkpoint(&self, location: impl FnOnce() -> String) -> Result<bool> {
+        let Some(token) = &self.cancellation else {
+            return Ok(false);
+        };
+        match token.reason() {
+            None => Ok(false),
+            Some(_) if self.partial_results_on_cancel => {
+                token.record_stop(location());
+                Ok(true)
+            }
+            Some(reason) => Err(KreuzbergError::Cancelled(reason)),
+        }
+    }
+
+    /// Recover from the failure of one unit such as a page, sheet, slide or archive entry.
+    ///
+    /// Returns `Ok(Some(value))` on success. When `result` is an error and
+    /// [`best_effort`](Self::best_effort) is set, the unit is recorded as skipped and `Ok(None)`
+    /// is returned so that the caller can go on with the next one; the extraction result then
+    /// carries a [`ProcessingWarning`] from the `"extraction"` source naming `location`.
+    /// Otherwise, or once the extraction has been cancelled, the error is returned unchanged.
+    ///
+    /// Skipped units are only reported as warnings by the extraction functions, which give
+    /// every document its own token; extractors called directly just log them.
+    pub fn recover<T, E: std::fmt::Display>(
+        &self,
+        location: impl FnOnce() -> String,
+        result: std::result::Result<T, E>,
+    ) -> std::result::Result<Option<T>, E> {
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(e) if !self.best_effort || self.cancellation.as_ref().is_some_and(|t| t.is_cancelled()) => Err(e),
+            Err(e) => {
+                let message = format!("Skipped {}: {}", location(), e);
+                tracing::warn!("{}", message);
+                if let Some(token) = &self.cancellation {
+                    token.record_skip(message);
+                }
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// Token for one document: a child of the configured token, limited by `timeout_secs`.
+///
+/// `None` when the configuration asks for neither cancellation, a timeout nor best-effort
+/// extraction.
+pub(crate) fn document_token(config: &ExtractionConfig) -> Option<CancellationToken> {
+    let timeout = config.timeout_secs.map(Duration::from_secs);
+    let token = match (&config.cancellation, timeout) {
+        (None, None) if config.best_effort => CancellationToken::new(),
+        (None, None) => return None,
+        (Some(token), None) => token.child(),
+        (Some(token), Some(timeout)) => token.child_with_timeout(timeout),
+        (None, Some(timeout)) => CancellationToken::with_timeout(timeout),
+    };
+    Some(token)
+}
+
+/// `config` with its token replaced by `token`.
+pub(crate) fn with_token<'a>(
+    config: &'a ExtractionConfig,
+    token: Option<&CancellationToken>,
+) -> Cow<'a, ExtractionConfig> {
+    match token {
+        Some(token) => Cow::Owned(ExtractionConfig {
+            cancellation: Some(token.clone()),
+            ..config.clone()
+        }),
+        None => Cow::Borrowed(config),
+    }
+}
+
+/// Run one document's extraction under `token`.
+///
+/// Unless partial results are requested, the extraction is abandoned as soon as the token is
+/// cancelled, even if the extractor never reaches a checkpoint.
+pub(crate) async fn run<F>(token: Option<&CancellationToken>, partial: bool, extraction: F) -> Result<ExtractionResult>
+where
+    F: std::future::Future<Output = Result<ExtractionResult>>,
+{
+    let Some(token) = token else {
+        return extraction.await;
+    };
+    token.check()?;
+
+    #[cfg(feature = "tokio-runtime")]
+    let result = if partial {
+        extraction.await
+    } else {
+        tokio::select! {
+            result = extraction => result,
+            _ = token.cancelled() => Err(KreuzbergError::Cancelled(
+                token.reason().unwrap_or(CancellationReason::Cancelled),
+            )),
+        }
+    };
+    #[cfg(not(feature = "tokio-runtime"))]
+    let result = extraction.await;
+
+    finish(token, partial, result)
+}
+
+/// Flag a result cut short or with units skipped by an extractor under `token`, or fail a
+/// late one.
+pub(crate) fn finish(
+    token: &CancellationToken,
+    partial: bool,
+    result: Result<ExtractionResult>,
+) -> Result<ExtractionResult> {
+    let mut result = match (result, token.reason()) {
+        (Ok(result), _) => result,
+        // Extractors may report cancellation through their own error types.
+        (Err(_), Some(reason)) => return Err(KreuzbergError::Cancelled(reason)),
+        (Err(e), None) => return Err(e),
+    };
+    for message in token.take_skipped() {
+        result.processing_warnings.push(ProcessingWarning {
+            source: "extraction".to_string(),
+            message,
+        });
+    }
+    if let Some(location) = token.stopped_at() {
+        let reason = token.reason().unwrap_or(CancellationReason::Cancelled);
+        result.processing_warnings.push(ProcessingWarning {
+            source: "cancellation".to_string(),
+            message: format!("Extraction {} before {}; the result is incomplete", reason, location),
+        });
+    } else if !partial {
+        token.check()?;
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel_propagates_to_children() {
+        let parent = CancellationToken::new();
+        let child = parent.child();
+        let grandchild = child.child_with_timeout(Duration::from_secs(60));
+        assert!(!grandchild.is_cancelled());
+
+        parent.cancel();
+        assert_eq!(child.reason(), Some(CancellationReason::Cancelled));
+        assert_eq!(grandchild.reason(), Some(CancellationReason::Cancelled));
+        assert!(parent.child().is_cancelled());
+
+        // Cancelling a child leaves its parent alone.
+        let parent = CancellationToken::new();
+        parent.child().cancel();
+        assert!(parent.check().is_ok());
+    }
+
+    #[test]
+    fn test_deadline_is_inherited() {
+        let parent = CancellationToken::with_timeout(Duration::ZERO);
+        let child = parent.child_with_timeout(Duration::from_secs(60));
+        assert_eq!(child.reason(), Some(CancellationReason::TimedOut));
+        assert!(matches!(
+            child.check(),
+            Err(KreuzbergError::Cancelled(CancellationReason::TimedOut))
+        ));
+    }
+
+    #[test]
+    fn test_checkpoint() {
+        let token = CancellationToken::new();
+        let mut config = ExtractionConfig {
+            cancellation: Some(token.clone()),
+            ..Default::default()
+        };
+        assert!(!config.checkpoint(|| "page 1".to_string()).unwrap());
+
+        token.cancel();
+        assert!(matches!(
+            config.checkpoint(|| "page 2".to_string()),
+            Err(KreuzbergError::Cancelled(CancellationReason::Cancelled))
+        ));
+
+        config.partial_results_on_cancel = true;
+        assert!(config.checkpoint(|| "page 3".to_string()).unwrap());
+        assert!(config.checkpoint(|| "page 4".to_string()).unwrap());
+        assert_eq!(token.stopped_at().as_deref(), Some("page 3"));
+    }
+
+    #[test]
+    fn test_recover() {
+        let token = CancellationToken::new();
+        let mut config = ExtractionConfig {
+            cancellation: Some(token.clone()),
+            ..Default::default()
+        };
+        let failed = || Err::<u32, _>(KreuzbergError::parsing("bad xref"));
+
+        assert_eq!(
+            config
+                .recover(|| "page 1".to_string(), Ok::<_, KreuzbergError>(7))
+                .unwrap(),
+            Some(7)
+        );
+        assert!(config.recover(|| "page 2".to_string(), failed()).is_err());
+
+        config.best_effort = true;
+        assert_eq!(config.recover(|| "page 3".to_string(), failed()).unwrap(), None);
+        let result = finish(&token, false, Ok(ExtractionResult::default())).unwrap();
+        assert_eq!(result.processing_warnings.len(), 1);
+        assert_eq!(result.processing_warnings[0].source, "extraction");
+        assert!(result.processing_warnings[0].message.starts_with("Skipped page 3: "));
+
+        token.cancel();
+        assert!(config.recover(|| "page 4".to_string(), failed()).is_err());
+    }
+
+    #[cfg(feature = "tokio-runtime")]
+    #[tokio::test]
+    async fn test_cancelled_wakes_on_cancel_and_deadline() {
+        let parent = CancellationToken::new();
+        let child = parent.child();
+        let waiter = tokio::spawn({
+            let child = child.clone();
+            async move { child.cancelled().await }
+        });
+        tokio::task::yield_now().await;
+        parent.cancel();
+        tokio::time::timeout(Duration::from_secs(5), waiter)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let token = CancellationToken::with_timeout(Duration::from_millis(20));
+        tokio::time::timeout(Duration::from_secs(5), token.cancelled())
+            .await
+            .unwrap();
+    }
+}
@@ -8,7 +8,7 @@ use serde::{Deserialize, Serialize};
 use super::super::formats::OutputFormat;
 use super::super::ocr::OcrConfig;
 use super::super::page::PageConfig;
-use super::super::processing::{ChunkingConfig, PostProcessorConfig};
+use super::super::processing::{ChunkerType, ChunkingConfig, PostProcessorConfig};
 use super::types::{ImageExtractionConfig, LanguageDetectionConfig, TokenReductionConfig};
 
 /// Main extraction configuration.
@@ -134,6 +134,44 @@ pub struct ExtractionConfig {
     /// Independent of `result_format` — can be combined with Unified or ElementBased.
     #[serde(default)]
     pub include_document_structure: bool,
+
+    /// Seconds a single document may take before its extraction is cancelled
+    /// (None = no limit).
+    ///
+    /// Applies to each document of a batch separately. See
+    /// [`cancellation`](crate::core::cancellation).
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+
+    /// Return the content extracted so far instead of failing when an extraction is
+    /// cancelled or times out.
+    ///
+    /// Extractors stop at the next page, sheet, slide or OCR boundary and the result
+    /// carries a `ProcessingWarning` from the `"cancellation"` source.
+    #[serde(default)]
+    pub partial_results_on_cancel: bool,
+
+    /// Skip pages, slides, sheets and archive entries that fail to extract instead of
+    /// failing the whole document.
+    ///
+    /// Each skipped unit is reported as a `ProcessingWarning` from the `"extraction"`
+    /// source. See [`ExtractionConfig::recover`].
+    #[serde(default)]
+    pub best_effort: bool,
+
+    /// Storage backend of the extraction result and embedding caches (None = process
+    /// default, see [`default_backend`](crate::cache::default_backend)).
+    ///
+    /// The OCR cache is opened once per OCR backend and uses the process default; the
+    /// CLI and API server make a configured backend the process default at startup.
+    #[serde(default)]
+    pub cache_backend: Option<crate::cache::CacheBackendType>,
+
+    /// Token to cancel extractions using this configuration (None = not cancellable).
+    ///
+    /// Not serialized.
+    #[serde(skip)]
+    pub cancellation: Option<crate::core::cancellation::CancellationToken>,
 }
 
 impl Default for ExtractionConfig {
@@ -161,6 +199,11 @@ impl Default for ExtractionConfig {
             result_format: crate::types::OutputFormat::Unified,
             output_format: OutputFormat::Plain,
             include_document_structure: false,
+            timeout_secs: None,
+            partial_results_on_cancel: false,
+            best_effort: false,
+            cache_backend: None,
+            cancellation: None,
         }
     }
 }
@@ -184,6 +227,19 @@ impl ExtractionConfig {
 
         ocr_enabled || image_extraction_enabled
     }
+
+    /// Check if extractors should build a `DocumentStructure`.
+    ///
+    /// Returns `true` when `include_document_structure` is set or when chunking uses
+    /// [`ChunkerType::Structure`](crate::ChunkerType::Structure), which packs chunks
+    /// along the structure.
+    pub fn needs_document_structure(&self) -> bool {
+        self.include_document_structure
+            || self
+                .chunking
+                .as_ref()
+                .is_some_and(|chunking| chunking.chunker_type == ChunkerType::Structure)
+    }
 }
 
 fn default_true() -> bool {
@@ -98,6 +98,7 @@ impl ExtractionConfig {
                     overlap: 200,
                     trim: true,
                     chunker_type: super::super::processing::ChunkerType::Text,
+                    sizing: super::super::processing::ChunkSizing::Characters,
                     embedding: None,
                     preset: None,
                 });
@@ -126,6 +127,7 @@ impl ExtractionConfig {
                     overlap: 200,
                     trim: true,
                     chunker_type: super::super::processing::ChunkerType::Text,
+                    sizing: super::super::processing::ChunkSizing::Characters,
                     embedding: None,
                     preset: None,
                 });
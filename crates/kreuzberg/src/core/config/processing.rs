@@ -13,12 +13,36 @@ use std::path::PathBuf;
 ///
 /// * `Text` - Generic text splitter, splits on whitespace and punctuation
 /// * `Markdown` - Markdown-aware splitter, preserves formatting and structure
+/// * `Structure` - Packs sections, paragraphs, list items and tables of the document
+///   structure, recording heading paths and node IDs on each chunk
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum ChunkerType {
     #[default]
     Text,
     Markdown,
+    Structure,
+}
+
+/// Unit in which chunk size and overlap are measured.
+///
+/// # Variants
+///
+/// * `Characters` - Unicode characters
+/// * `Tokens` - Tokens of a HuggingFace tokenizer (requires the `chunking-tokenizers`
+///   feature). The tokenizer is loaded from `tokenizer_path`, or otherwise from the model
+///   of the chunking embedding configuration (requires the `embeddings` feature).
+///   Every chunk then carries its token count.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChunkSizing {
+    #[default]
+    Characters,
+    Tokens {
+        /// Path to a `tokenizer.json` file
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tokenizer_path: Option<PathBuf>,
+    },
 }
 
 /// Post-processor configuration.
@@ -78,13 +102,13 @@ impl Default for PostProcessorConfig {
 /// overlap, trimming behavior, and optional embeddings.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChunkingConfig {
-    /// Maximum characters per chunk
+    /// Maximum chunk size, in characters or in tokens depending on `sizing`
     ///
     /// Default: 1000
     #[serde(default = "default_chunk_size", rename = "max_chars", alias = "max_characters")]
     pub max_characters: usize,
 
-    /// Overlap between chunks in characters
+    /// Overlap between chunks, in the unit of `sizing`
     ///
     /// Default: 200
     #[serde(default = "default_chunk_overlap", rename = "max_overlap", alias = "overlap")]
@@ -96,12 +120,18 @@ pub struct ChunkingConfig {
     #[serde(default = "default_trim")]
     pub trim: bool,
 
-    /// Type of chunker to use (Text or Markdown)
+    /// Type of chunker to use (Text, Markdown or Structure)
     ///
     /// Default: Text
     #[serde(default = "default_chunker_type")]
     pub chunker_type: ChunkerType,
 
+    /// Whether sizes are measured in characters or tokens
+    ///
+    /// Default: characters
+    #[serde(default)]
+    pub sizing: ChunkSizing,
+
     /// Optional embedding configuration for chunk embeddings
     #[serde(skip_serializing_if = "Option::is_none")]
     pub embedding: Option<EmbeddingConfig>,
@@ -118,6 +148,7 @@ impl Default for ChunkingConfig {
             overlap: 200,
             trim: true,
             chunker_type: ChunkerType::Text,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         }
@@ -254,6 +285,7 @@ mod tests {
             overlap: 200,
             trim: true,
             chunker_type: ChunkerType::Text,
+            sizing: ChunkSizing::Characters,
             embedding: None,
             preset: None,
         };
@@ -374,4 +406,27 @@ mod tests {
             _ => panic!("Expected Custom variant"),
         }
     }
+
+    /// Tests the serialized form of chunk sizing and its default.
+    #[test]
+    fn test_chunk_sizing_serialization() {
+        let config: ChunkingConfig = serde_json::from_str(
+            r#"{"max_chars": 256, "sizing": {"type": "tokens", "tokenizer_path": "tokenizer.json"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.sizing,
+            ChunkSizing::Tokens {
+                tokenizer_path: Some(PathBuf::from("tokenizer.json"))
+            }
+        );
+
+        let config: ChunkingConfig = serde_json::from_str(r#"{"sizing": {"type": "tokens"}}"#).unwrap();
+        assert_eq!(config.sizing, ChunkSizing::Tokens { tokenizer_path: None });
+
+        let config: ChunkingConfig = serde_json::from_str("{}").unwrap();
+        assert_eq!(config.sizing, ChunkSizing::Characters);
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(json.contains(r#""sizing":{"type":"characters"}"#), "{}", json);
+    }
 }
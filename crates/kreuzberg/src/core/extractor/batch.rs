@@ -74,19 +74,24 @@ pub async fn batch_extract_file(
         .unwrap_or_else(|| (num_cpus::get() as f64 * 1.5).ceil() as usize);
     let semaphore = Arc::new(Semaphore::new(max_concurrent));
 
+    // Spawned tasks do not inherit the caller's engine scope; carry it over explicitly.
+    let engine = crate::engine::current();
     let mut tasks = JoinSet::new();
 
     for (index, path) in paths.into_iter().enumerate() {
         let path_buf = path.as_ref().to_path_buf();
         let config_clone = Arc::clone(&config_arc);
         let semaphore_clone = Arc::clone(&semaphore);
+        let engine = engine.clone();
 
         tasks.spawn(async move {
             let _permit = semaphore_clone.acquire().await.unwrap();
             let start = Instant::now();
-            let mut result =
-                crate::core::batch_mode::with_batch_mode(async { extract_file(&path_buf, None, &config_clone).await })
-                    .await;
+            let mut result = engine
+                .scope(crate::core::batch_mode::with_batch_mode(async {
+                    extract_file(&path_buf, None, &config_clone).await
+                }))
+                .await;
             let elapsed_ms = start.elapsed().as_millis() as u64;
 
             // Add extraction timing to result metadata for benchmarking
@@ -206,19 +211,23 @@ pub async fn batch_extract_bytes(
         .unwrap_or_else(|| (num_cpus::get() as f64 * 1.5).ceil() as usize);
     let semaphore = Arc::new(Semaphore::new(max_concurrent));
 
+    // Spawned tasks do not inherit the caller's engine scope; carry it over explicitly.
+    let engine = crate::engine::current();
     let mut tasks = JoinSet::new();
 
     for (index, (bytes, mime_type)) in contents.into_iter().enumerate() {
         let config_clone = Arc::clone(&config_arc);
         let semaphore_clone = Arc::clone(&semaphore);
+        let engine = engine.clone();
 
         tasks.spawn(async move {
             let _permit = semaphore_clone.acquire().await.unwrap();
             let start = Instant::now();
-            let mut result = crate::core::batch_mode::with_batch_mode(async {
-                extract_bytes(&bytes, &mime_type, &config_clone).await
-            })
-            .await;
+            let mut result = engine
+                .scope(crate::core::batch_mode::with_batch_mode(async {
+                    extract_bytes(&bytes, &mime_type, &config_clone).await
+                }))
+                .await;
             let elapsed_ms = start.elapsed().as_millis() as u64;
 
             // Add extraction timing to result metadata for benchmarking
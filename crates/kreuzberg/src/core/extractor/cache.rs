@@ -0,0 +1,458 @@
+//! Content-addressed cache of whole extraction results.
+//!
+//! When `ExtractionConfig::use_cache` is enabled, the final `ExtractionResult` (after the
+//! post-processing pipeline, so including chunks and embeddings) is stored in a
+//! [`GenericCache`] under `<KREUZBERG_CACHE_DIR>/extractions`, in the backend selected by
+//! `ExtractionConfig::cache_backend` or the process-wide default. Without
+//! `KREUZBERG_CACHE_DIR` the per-user cache directory is used (see
+//! [`user_cache_dir`](crate::cache::user_cache_dir)), so library callers never get a
+//! cache written into their working directory. The key combines:
+//!
+//! - a hash of the input bytes, so renamed or copied files still hit
+//! - the MIME type the input was extracted as
+//! - a canonical hash of the effective configuration
+//! - the name and version of the extractor, the post-processors and validators registered
+//!   with the engine at the time and, when OCR is configured, the OCR backend
+//! - the engine, unless it is the global one
+//! - the kreuzberg version
+//!
+//! All hashes are SHA-256 so keys stay stable across processes and hosts; `ahash` is
+//! seeded per process and would turn every restart into a cold cache.
+//!
+//! Plugins registered by the application are also keyed by instance (see
+//! [`identity`](crate::plugins::identity)), so their results are only reused while the same
+//! instance is registered: a plugin can change behavior without changing its version.
+//!
+//! Cache failures never fail an extraction: they are logged and treated as a miss.
+
+use crate::cache::{CacheBackendType, CacheLimits, GenericCache, default_backend, shared_user_cache};
+use crate::core::config::ExtractionConfig;
+use crate::engine::Kreuzberg;
+use crate::plugins::DocumentExtractor;
+use crate::plugins::identity::identity;
+use crate::types::ExtractionResult;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+use std::sync::Arc;
+
+const CACHE_TYPE: &str = "extractions";
+const MAX_AGE_DAYS: f64 = 30.0;
+const MAX_CACHE_SIZE_MB: f64 = 1000.0;
+const MIN_FREE_SPACE_MB: f64 = 1000.0;
+
+/// Read size used when hashing input content. Bytes and files are hashed in the same
+/// blocks so an in-memory copy of a file maps to the same key as the file itself.
+const HASH_BLOCK_SIZE: usize = 64 * 1024;
+
+/// Configuration fields that do not influence the extracted result. Timeouts only cut
+/// results short, and incomplete results carry warnings and are never cached.
+const NON_RESULT_FIELDS: &[&str] = &[
+    "use_cache",
+    "cache_backend",
+    "max_concurrent_extractions",
+    "timeout_secs",
+    "partial_results_on_cancel",
+];
+
+pub(in crate::core::extractor) struct ResultCache {
+    cache: Arc<GenericCache>,
+}
+
+impl ResultCache {
+    #[cfg(test)]
+    pub(in crate::core::extractor) fn new(cache_dir: Option<String>) -> crate::Result<Self> {
+        let cache = GenericCache::new(
+            CACHE_TYPE.to_string(),
+            cache_dir,
+            MAX_AGE_DAYS,
+            MAX_CACHE_SIZE_MB,
+            MIN_FREE_SPACE_MB,
+        )?;
+        Ok(Self { cache: Arc::new(cache) })
+    }
+
+    /// Process-wide cache rooted at `KREUZBERG_CACHE_DIR` or the per-user cache directory,
+    /// stored in `backend`, or `None` if it could not be opened.
+    pub(in crate::core::extractor) fn shared(backend: CacheBackendType) -> Option<ResultCache> {
+        let limits = CacheLimits::new(MAX_AGE_DAYS, MAX_CACHE_SIZE_MB, MIN_FREE_SPACE_MB);
+        shared_user_cache(CACHE_TYPE, backend, limits).map(|cache| Self { cache })
+    }
+
+    pub(in crate::core::extractor) fn get(&self, key: &str) -> Option<ExtractionResult> {
+        let bytes = match self.cache.get(key, None) {
+            Ok(bytes) => bytes?,
+            Err(e) => {
+                tracing::debug!("Extraction cache lookup failed: {}", e);
+                return None;
+            }
+        };
+        match decode_result(&bytes) {
+            Ok(result) => Some(result),
+            Err(e) => {
+                tracing::debug!("Discarding unreadable extraction cache entry {}: {}", key, e);
+                None
+            }
+        }
+    }
+
+    /// Store `result` unless it carries processing warnings, which may stem from transient
+    /// failures (a model download, a timed-out OCR backend) that a later run would not hit.
+    pub(in crate::core::extractor) fn set(&self, key: &str, result: &mut ExtractionResult) {
+        if !result.processing_warnings.is_empty() || result.metadata.error.is_some() {
+            return;
+        }
+
+        let stored = encode_result(result).and_then(|bytes| self.cache.set(key, bytes, None));
+        if let Err(e) = stored {
+            tracing::debug!("Failed to store extraction result in cache: {}", e);
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct StoredResultRef<'a> {
+    result: &'a ExtractionResult,
+    additional: HashMap<&'a str, &'a Value>,
+}
+
+#[derive(Deserialize)]
+struct StoredResult {
+    result: ExtractionResult,
+    additional: HashMap<String, Value>,
+}
+
+/// Encode `result` as MessagePack so that [`decode_result`] restores it exactly.
+pub(crate) fn encode_result(result: &mut ExtractionResult) -> crate::Result<Vec<u8>> {
+    // `Metadata::additional` is flattened into the metadata map, where its keys can
+    // collide with typed fields (the deprecated `output_format` copy, format fields);
+    // keep it out of the flattened form so the result deserializes exactly.
+    let additional = std::mem::take(&mut result.metadata.additional);
+    let stored = StoredResultRef {
+        result,
+        additional: additional.iter().map(|(k, v)| (k.as_ref(), v)).collect(),
+    };
+    let encoded = rmp_serde::to_vec_named(&stored).map_err(crate::KreuzbergError::from);
+    result.metadata.additional = additional;
+    encoded
+}
+
+/// Decode a result encoded with [`encode_result`].
+pub(crate) fn decode_result(bytes: &[u8]) -> crate::Result<ExtractionResult> {
+    let StoredResult { mut result, additional } = rmp_serde::from_slice(bytes)?;
+    result.metadata.additional = additional.into_iter().map(|(k, v)| (Cow::Owned(k), v)).collect();
+    Ok(result)
+}
+
+/// The cache and key to use for an extraction, or `None` when `use_cache` is off, the
+/// cache is unavailable or the content could not be hashed.
+pub(in crate::core::extractor) fn prepare(
+    engine: &Kreuzberg,
+    config: &ExtractionConfig,
+    mime_type: &str,
+    extractor: &Arc<dyn DocumentExtractor>,
+    content_hash: impl FnOnce() -> std::io::Result<String>,
+) -> Option<(ResultCache, String)> {
+    if !config.use_cache {
+        return None;
+    }
+    let cache = ResultCache::shared(config.cache_backend.unwrap_or_else(default_backend))?;
+    let content_hash = match content_hash() {
+        Ok(hash) => hash,
+        Err(e) => {
+            tracing::debug!("Could not hash extraction input, skipping result cache: {}", e);
+            return None;
+        }
+    };
+    let key = cache_key(engine, &content_hash, mime_type, extractor, config)?;
+    Some((cache, key))
+}
+
+/// Hash of everything `reader` yields, as hex digest plus length.
+pub(in crate::core::extractor) fn hash_content(mut reader: impl Read) -> std::io::Result<String> {
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; HASH_BLOCK_SIZE];
+    let mut total = 0u64;
+    loop {
+        let mut filled = 0;
+        while filled < buffer.len() {
+            match reader.read(&mut buffer[filled..]) {
+                Ok(0) => break,
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        if filled == 0 {
+            break;
+        }
+        hasher.update(&buffer[..filled]);
+        total += filled as u64;
+        if filled < buffer.len() {
+            break;
+        }
+    }
+    Ok(format!("{:x}-{}", hasher.finalize(), total))
+}
+
+pub(in crate::core::extractor) fn hash_file(path: &Path) -> std::io::Result<String> {
+    hash_content(std::fs::File::open(path)?)
+}
+
+/// Cache key for extracting content with hash `content_hash` as `mime_type`, or `None` if
+/// the configuration cannot be serialized.
+fn cache_key(
+    engine: &Kreuzberg,
+    content_hash: &str,
+    mime_type: &str,
+    extractor: &Arc<dyn DocumentExtractor>,
+    config: &ExtractionConfig,
+) -> Option<String> {
+    let config_hash = config_hash(config)?;
+
+    // Built-in post-processors register with the global registry on first use; do it now
+    // so the key does not change between the first and later extractions.
+    if engine.is_global() {
+        crate::core::pipeline::initialize_features();
+    }
+    let mut plugins = vec![format!("extractor:{}", identity(extractor))];
+    if let Ok(registry) = engine.post_processor_registry().read() {
+        plugins.extend(registry.get_all().iter().map(|p| format!("processor:{}", identity(p))));
+    }
+    if let Ok(registry) = engine.validator_registry().read() {
+        plugins.extend(registry.get_all().iter().map(|v| format!("validator:{}", identity(v))));
+    }
+    if let Some(ocr) = &config.ocr {
+        let backend = engine
+            .ocr_backend_registry()
+            .read()
+            .ok()
+            .and_then(|registry| registry.get(&ocr.backend).ok());
+        match backend {
+            Some(backend) => plugins.push(format!("ocr:{}", identity(&backend))),
+            None => plugins.push(format!("ocr:{}@unavailable", ocr.backend)),
+        }
+    }
+    plugins.sort();
+    let plugins = plugins.join(",");
+
+    let mut hasher = Sha256::new();
+    for (name, value) in [
+        ("content", content_hash),
+        ("mime", mime_type),
+        ("config", &config_hash),
+        ("plugins", &plugins),
+        ("engine", engine.cache_namespace().unwrap_or("global")),
+        ("version", env!("CARGO_PKG_VERSION")),
+    ] {
+        hasher.update(name.as_bytes());
+        hasher.update(b"=");
+        hasher.update(value.as_bytes());
+        hasher.update(b"\n");
+    }
+    Some(format!("{:x}", hasher.finalize()))
+}
+
+/// Hash of the configuration serialized with sorted keys and without fields that cannot
+/// change the result.
+fn config_hash(config: &ExtractionConfig) -> Option<String> {
+    let mut value = match serde_json::to_value(config) {
+        Ok(value) => value,
+        Err(e) => {
+            tracing::debug!("Extraction config is not serializable, skipping result cache: {}", e);
+            return None;
+        }
+    };
+    if let Value::Object(fields) = &mut value {
+        for field in NON_RESULT_FIELDS {
+            fields.remove(*field);
+        }
+    }
+
+    let mut canonical = String::new();
+    write_canonical(&value, &mut canonical);
+    Some(format!("{:x}", Sha256::digest(canonical.as_bytes())))
+}
+
+/// Serialize `value` as JSON with object keys in sorted order, independent of whether
+/// `serde_json` preserves insertion order.
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(fields) => {
+            let mut entries: Vec<_> = fields.iter().collect();
+            entries.sort_by_key(|(key, _)| *key);
+            out.push('{');
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical(value, out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Chunk, ChunkMetadata, ExcelMetadata, FormatMetadata, ProcessingWarning};
+
+    fn sample_result() -> ExtractionResult {
+        let mut result = ExtractionResult {
+            content: "Hello, cache".to_string(),
+            mime_type: Cow::Borrowed("text/plain"),
+            chunks: Some(vec![Chunk {
+                content: "Hello, cache".to_string(),
+                embedding: Some(vec![0.25, -0.5, 1.0]),
+                metadata: ChunkMetadata {
+                    byte_start: 0,
+                    byte_end: 12,
+                    token_count: None,
+                    chunk_index: 0,
+                    total_chunks: 1,
+                    first_page: None,
+                    last_page: None,
+                    heading_path: None,
+                    node_ids: None,
+                },
+            }]),
+            ..Default::default()
+        };
+        result.metadata.title = Some("Title".to_string());
+        result.metadata.format = Some(FormatMetadata::Excel(ExcelMetadata {
+            sheet_count: 1,
+            sheet_names: vec!["Sheet1".to_string()],
+        }));
+        result.metadata.output_format = Some("plain".to_string());
+        result
+            .metadata
+            .additional
+            .insert(Cow::Borrowed("output_format"), serde_json::json!("plain"));
+        result
+            .metadata
+            .additional
+            .insert(Cow::Borrowed("custom"), serde_json::json!({"nested": [1, 2]}));
+        result
+    }
+
+    #[test]
+    fn test_result_roundtrip_preserves_chunks_and_metadata() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ResultCache::new(Some(dir.path().to_string_lossy().to_string())).unwrap();
+        let mut result = sample_result();
+
+        assert!(cache.get("0123456789abcdef0123456789abcdef").is_none());
+        cache.set("0123456789abcdef0123456789abcdef", &mut result);
+        assert_eq!(result.metadata.additional.len(), 2);
+        let cached = cache.get("0123456789abcdef0123456789abcdef").unwrap();
+
+        assert_eq!(cached.content, result.content);
+        assert_eq!(cached.mime_type, "text/plain");
+        assert_eq!(cached.metadata.title.as_deref(), Some("Title"));
+        assert_eq!(cached.metadata.output_format.as_deref(), Some("plain"));
+        assert!(matches!(
+            cached.metadata.format,
+            Some(FormatMetadata::Excel(ExcelMetadata { sheet_count: 1, .. }))
+        ));
+        assert_eq!(cached.metadata.additional.len(), 2);
+        assert_eq!(
+            cached.metadata.additional.get("custom"),
+            Some(&serde_json::json!({"nested": [1, 2]}))
+        );
+        let chunks = cached.chunks.unwrap();
+        assert_eq!(chunks[0].embedding, Some(vec![0.25, -0.5, 1.0]));
+        assert!(dir.path().join(CACHE_TYPE).is_dir());
+    }
+
+    #[test]
+    fn test_results_with_warnings_are_not_stored() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ResultCache::new(Some(dir.path().to_string_lossy().to_string())).unwrap();
+        let mut result = sample_result();
+        result.processing_warnings.push(ProcessingWarning {
+            source: "embedding".to_string(),
+            message: "model unavailable".to_string(),
+        });
+
+        cache.set("0123456789abcdef0123456789abcdef", &mut result);
+        assert!(cache.get("0123456789abcdef0123456789abcdef").is_none());
+    }
+
+    #[test]
+    fn test_hash_content_matches_for_files_and_bytes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("large.bin");
+        let content: Vec<u8> = (0..HASH_BLOCK_SIZE * 2 + 17).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&path, &content).unwrap();
+
+        let from_bytes = hash_content(content.as_slice()).unwrap();
+        assert_eq!(hash_file(&path).unwrap(), from_bytes);
+        assert!(from_bytes.ends_with(&format!("-{}", content.len())));
+        assert_ne!(hash_content(&content[1..]).unwrap(), from_bytes);
+    }
+
+    #[test]
+    fn test_hash_content_is_stable_across_processes() {
+        assert_eq!(
+            hash_content(b"abc".as_slice()).unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad-3"
+        );
+    }
+
+    #[test]
+    fn test_config_hash_ignores_fields_that_do_not_affect_results() {
+        let config = ExtractionConfig::default();
+        let tuned = ExtractionConfig {
+            use_cache: false,
+            max_concurrent_extractions: Some(2),
+            timeout_secs: Some(5),
+            ..Default::default()
+        };
+        let different = ExtractionConfig {
+            force_ocr: true,
+            ..Default::default()
+        };
+
+        assert_eq!(config_hash(&config), config_hash(&tuned));
+        assert_ne!(config_hash(&config), config_hash(&different));
+    }
+
+    #[test]
+    fn test_cache_keys_are_namespaced_per_engine() {
+        let config = ExtractionConfig::default();
+        let extractor: Arc<dyn DocumentExtractor> = Arc::new(crate::extractors::PlainTextExtractor::new());
+        let key = |engine: &Kreuzberg| cache_key(engine, "abc-3", "text/plain", &extractor, &config).unwrap();
+        let tenant_a = Kreuzberg::new(config.clone()).unwrap();
+        let tenant_b = Kreuzberg::new(config.clone()).unwrap();
+
+        assert_eq!(key(Kreuzberg::global()), key(Kreuzberg::global()));
+        assert_eq!(key(&tenant_a), key(&tenant_a.clone()));
+        assert_ne!(key(&tenant_a), key(&tenant_b));
+        assert_ne!(key(&tenant_a), key(Kreuzberg::global()));
+    }
+
+    #[test]
+    fn test_write_canonical_sorts_keys() {
+        let mut out = String::new();
+        write_canonical(&serde_json::json!({"b": [1, {"d": 1, "c": "x"}], "a": null}), &mut out);
+        assert_eq!(out, r#"{"a":null,"b":[1,{"c":"x","d":1}]}"#);
+    }
+}
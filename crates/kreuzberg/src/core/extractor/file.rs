@@ -9,11 +9,13 @@
 #[cfg(any(feature = "otel", not(feature = "office")))]
 use crate::KreuzbergError;
 use crate::Result;
+use crate::core::cancellation;
 use crate::core::config::ExtractionConfig;
 use crate::core::mime::{LEGACY_POWERPOINT_MIME_TYPE, LEGACY_WORD_MIME_TYPE};
 use crate::types::ExtractionResult;
 use std::path::Path;
 
+use super::cache;
 use super::helpers::get_extractor;
 
 /// Sanitize a file path to return only the filename.
@@ -180,12 +182,39 @@ pub(in crate::core::extractor) async fn extract_file_with_extractor(
     mime_type: &str,
     config: &ExtractionConfig,
 ) -> Result<ExtractionResult> {
-    crate::extractors::ensure_initialized()?;
+    let engine = crate::engine::current();
+    let extractor = get_extractor(&engine, mime_type)?;
+    let cached = cache::prepare(&engine, config, mime_type, &extractor, || cache::hash_file(path));
+    if let Some((cache, key)) = &cached
+        && let Some(result) = cache.get(key)
+    {
+        return Ok(result);
+    }
+
+    #[cfg(feature = "metrics")]
+    let (_in_flight, start) = (crate::metrics::global().start_extraction(), std::time::Instant::now());
+
+    let token = cancellation::document_token(config);
+    let scoped = cancellation::with_token(config, token.as_ref());
+    let config = &*scoped;
+    let mut result = cancellation::run(token.as_ref(), config.partial_results_on_cancel, async {
+        #[cfg(feature = "tokio-runtime")]
+        if let Some(pool) = isolation_pool(&engine, extractor.as_ref())? {
+            let result = pool.extract_file(path, mime_type, extractor.name(), config).await?;
+            return crate::core::pipeline::run_pipeline(result, config).await;
+        }
+        let result = extractor.extract_file(path, mime_type, config).await?;
+        crate::core::pipeline::run_pipeline(result, config).await
+    })
+    .await;
 
-    let extractor = get_extractor(mime_type)?;
-    let mut result = extractor.extract_file(path, mime_type, config).await?;
-    result = crate::core::pipeline::run_pipeline(result, config).await?;
-    Ok(result)
+    #[cfg(feature = "metrics")]
+    crate::metrics::global().record_extraction(mime_type, extractor.name(), start.elapsed(), result.is_ok());
+
+    if let (Some((cache, key)), Ok(result)) = (&cached, &mut result) {
+        cache.set(key, result);
+    }
+    result
 }
 
 pub(in crate::core::extractor) async fn extract_bytes_with_extractor(
@@ -193,10 +222,58 @@ pub(in crate::core::extractor) async fn extract_bytes_with_extractor(
     mime_type: &str,
     config: &ExtractionConfig,
 ) -> Result<ExtractionResult> {
-    crate::extractors::ensure_initialized()?;
+    let engine = crate::engine::current();
+    let extractor = get_extractor(&engine, mime_type)?;
+    let cached = cache::prepare(&engine, config, mime_type, &extractor, || cache::hash_content(content));
+    if let Some((cache, key)) = &cached
+        && let Some(result) = cache.get(key)
+    {
+        return Ok(result);
+    }
 
-    let extractor = get_extractor(mime_type)?;
-    let mut result = extractor.extract_bytes(content, mime_type, config).await?;
-    result = crate::core::pipeline::run_pipeline(result, config).await?;
-    Ok(result)
+    #[cfg(feature = "metrics")]
+    let (_in_flight, start) = (crate::metrics::global().start_extraction(), std::time::Instant::now());
+
+    let token = cancellation::document_token(config);
+    let scoped = cancellation::with_token(config, token.as_ref());
+    let config = &*scoped;
+    let mut result = cancellation::run(token.as_ref(), config.partial_results_on_cancel, async {
+        #[cfg(feature = "tokio-runtime")]
+        if let Some(pool) = isolation_pool(&engine, extractor.as_ref())? {
+            let result = pool.extract_bytes(content, mime_type, extractor.name(), config).await?;
+            return crate::core::pipeline::run_pipeline(result, config).await;
+        }
+        let result = extractor.extract_bytes(content, mime_type, config).await?;
+        crate::core::pipeline::run_pipeline(result, config).await
+    })
+    .await;
+
+    #[cfg(feature = "metrics")]
+    crate::metrics::global().record_extraction(mime_type, extractor.name(), start.elapsed(), result.is_ok());
+
+    if let (Some((cache, key)), Ok(result)) = (&cached, &mut result) {
+        cache.set(key, result);
+    }
+    result
+}
+
+/// The isolation pool to run `extractor` in, if the engine has isolation enabled.
+///
+/// Workers only have the built-in extractors, so an extractor registered at runtime after
+/// isolation was enabled is refused rather than silently replaced by the worker's choice.
+#[cfg(feature = "tokio-runtime")]
+fn isolation_pool(
+    engine: &crate::engine::Kreuzberg,
+    extractor: &dyn crate::plugins::DocumentExtractor,
+) -> Result<Option<std::sync::Arc<crate::isolation::IsolationPool>>> {
+    let Some(pool) = engine.isolation() else {
+        return Ok(None);
+    };
+    if !crate::plugins::identity::is_builtin(extractor) {
+        return Err(crate::KreuzbergError::validation(format!(
+            "Extractor '{}' is not built in and cannot run in an isolation worker",
+            extractor.name()
+        )));
+    }
+    Ok(Some(pool))
 }
@@ -2,12 +2,13 @@
 //!
 //! This module provides shared utilities used across extraction modules.
 
+use crate::engine::Kreuzberg;
 use crate::plugins::DocumentExtractor;
 use crate::utils::{PoolSizeHint, estimate_pool_size};
 use crate::{KreuzbergError, Result};
 use std::sync::Arc;
 
-/// Get an extractor from the registry.
+/// Get an extractor from the registry of `engine`.
 ///
 /// This function acquires the registry read lock and retrieves the appropriate
 /// extractor for the given MIME type.
@@ -16,8 +17,14 @@ use std::sync::Arc;
 ///
 /// RwLock read + HashMap lookup is ~100ns, fast enough without caching.
 /// Removed thread-local cache to avoid Tokio work-stealing scheduler issues.
-pub(in crate::core::extractor) fn get_extractor(mime_type: &str) -> Result<Arc<dyn DocumentExtractor>> {
-    let registry = crate::plugins::registry::get_document_extractor_registry();
+pub(in crate::core::extractor) fn get_extractor(
+    engine: &Kreuzberg,
+    mime_type: &str,
+) -> Result<Arc<dyn DocumentExtractor>> {
+    if engine.is_global() {
+        crate::extractors::ensure_initialized()?;
+    }
+    let registry = engine.extractor_registry();
     let registry_read = registry
         .read()
         .map_err(|e| KreuzbergError::Other(format!("Document extractor registry lock poisoned: {}", e)))?;
@@ -29,6 +29,7 @@ pub(super) fn extract_bytes_sync_impl(
     config: Option<&crate::core::config::ExtractionConfig>,
 ) -> crate::Result<crate::types::ExtractionResult> {
     use crate::KreuzbergError;
+    use crate::core::cancellation;
     use crate::core::extractor::helpers::get_extractor;
     use crate::core::mime;
 
@@ -47,9 +48,7 @@ pub(super) fn extract_bytes_sync_impl(
         });
     };
 
-    crate::extractors::ensure_initialized()?;
-
-    let extractor = get_extractor(&validated_mime)?;
+    let extractor = get_extractor(crate::engine::Kreuzberg::global(), &validated_mime)?;
 
     let sync_extractor = extractor.as_sync_extractor().ok_or_else(|| {
         KreuzbergError::UnsupportedFormat(format!(
@@ -58,9 +57,18 @@ pub(super) fn extract_bytes_sync_impl(
         ))
     })?;
 
-    let mut result = sync_extractor.extract_sync(content, &validated_mime, &cfg)?;
+    let token = cancellation::document_token(&cfg);
+    let cfg = cancellation::with_token(&cfg, token.as_ref());
+    if let Some(token) = &token {
+        token.check()?;
+    }
 
-    result = crate::core::pipeline::run_pipeline_sync(result, &cfg)?;
+    let result = sync_extractor
+        .extract_sync(content, &validated_mime, &cfg)
+        .and_then(|result| crate::core::pipeline::run_pipeline_sync(result, &cfg));
 
-    Ok(result)
+    match &token {
+        Some(token) => cancellation::finish(token, cfg.partial_results_on_cancel, result),
+        None => result,
+    }
 }
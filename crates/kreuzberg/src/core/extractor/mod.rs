@@ -12,6 +12,7 @@
 //! - [`batch_extract_bytes`] - Extract content from multiple byte arrays concurrently
 
 mod bytes;
+mod cache;
 mod file;
 mod helpers;
 mod legacy;
@@ -29,6 +30,11 @@ pub use sync::{batch_extract_bytes_sync, extract_bytes_sync};
 #[cfg(feature = "tokio-runtime")]
 pub use sync::extract_file_sync;
 
+#[cfg(feature = "tokio-runtime")]
+pub(crate) use cache::{decode_result, encode_result};
+#[cfg(feature = "tokio-runtime")]
+pub(crate) use sync::block_on;
+
 #[cfg(feature = "tokio-runtime")]
 pub use batch::{batch_extract_bytes, batch_extract_file};
 #[cfg(feature = "tokio-runtime")]
@@ -488,4 +494,43 @@ mod tests {
 
         assert_eq!(success_count, 30);
     }
+
+    #[tokio::test]
+    async fn test_extract_caches_results_by_content() {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let content = format!("cached content {} {}", std::process::id(), nanos);
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("cached.txt");
+        std::fs::write(&file_path, &content).unwrap();
+
+        let config = ExtractionConfig::default();
+        let engine = crate::engine::Kreuzberg::global();
+        let extractor = helpers::get_extractor(engine, "text/plain").unwrap();
+        let (result_cache, key) = cache::prepare(engine, &config, "text/plain", &extractor, || {
+            cache::hash_content(content.as_bytes())
+        })
+        .unwrap();
+        assert!(result_cache.get(&key).is_none());
+
+        let extracted = extract_file(&file_path, None, &config).await.unwrap();
+        let cached = result_cache.get(&key).expect("result should be cached");
+        assert_eq!(cached.content, extracted.content);
+
+        let from_bytes = extract_bytes(content.as_bytes(), "text/plain", &config).await.unwrap();
+        assert_eq!(from_bytes.content, extracted.content);
+
+        let uncached = ExtractionConfig {
+            use_cache: false,
+            ..Default::default()
+        };
+        assert!(
+            cache::prepare(engine, &uncached, "text/plain", &extractor, || {
+                cache::hash_content(content.as_bytes())
+            })
+            .is_none()
+        );
+    }
 }
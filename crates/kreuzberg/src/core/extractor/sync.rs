@@ -45,6 +45,12 @@ static GLOBAL_RUNTIME: Lazy<tokio::runtime::Runtime> = Lazy::new(|| {
         .expect("Failed to create global Tokio runtime - system may be out of resources")
 });
 
+/// Block the current thread on `future` using the global Tokio runtime.
+#[cfg(feature = "tokio-runtime")]
+pub(crate) fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    GLOBAL_RUNTIME.block_on(future)
+}
+
 /// Synchronous wrapper for `extract_file`.
 ///
 /// This is a convenience function that blocks the current thread until extraction completes.
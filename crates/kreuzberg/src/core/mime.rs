@@ -51,6 +51,10 @@ pub const EXCEL_TEMPLATE_MIME_TYPE: &str = "application/vnd.ms-excel.template.ma
 
 pub const OPENDOC_SPREADSHEET_MIME_TYPE: &str = "application/vnd.oasis.opendocument.spreadsheet";
 
+pub const PAGES_MIME_TYPE: &str = "application/vnd.apple.pages";
+pub const NUMBERS_MIME_TYPE: &str = "application/vnd.apple.numbers";
+pub const KEYNOTE_MIME_TYPE: &str = "application/vnd.apple.keynote";
+
 /// A format definition in the centralized registry.
 ///
 /// Each entry defines a document format with its file extensions, primary MIME type,
@@ -163,6 +167,11 @@ static FORMATS: &[FormatEntry] = &[
         mime_type: "application/vnd.oasis.opendocument.text",
         aliases: &[],
     },
+    FormatEntry {
+        extensions: &["pages"],
+        mime_type: "application/vnd.apple.pages",
+        aliases: &["application/x-iwork-pages-sffpages"],
+    },
     // ── Presentations ───────────────────────────────────────────────────
     FormatEntry {
         extensions: &["pptx"],
@@ -184,6 +193,11 @@ static FORMATS: &[FormatEntry] = &[
         mime_type: "application/vnd.ms-powerpoint",
         aliases: &[],
     },
+    FormatEntry {
+        extensions: &["key"],
+        mime_type: "application/vnd.apple.keynote",
+        aliases: &["application/x-iwork-keynote-sffkey"],
+    },
     // ── Spreadsheets ────────────────────────────────────────────────────
     FormatEntry {
         extensions: &["xlsx"],
@@ -220,6 +234,11 @@ static FORMATS: &[FormatEntry] = &[
         mime_type: "application/vnd.oasis.opendocument.spreadsheet",
         aliases: &[],
     },
+    FormatEntry {
+        extensions: &["numbers"],
+        mime_type: "application/vnd.apple.numbers",
+        aliases: &["application/x-iwork-numbers-sffnumbers"],
+    },
     // ── Images ──────────────────────────────────────────────────────────
     FormatEntry {
         extensions: &["bmp"],
@@ -375,6 +394,21 @@ static FORMATS: &[FormatEntry] = &[
         mime_type: "text/x-rst",
         aliases: &["text/prs.fallenstein.rst"],
     },
+    FormatEntry {
+        extensions: &["adoc", "asciidoc"],
+        mime_type: "text/x-asciidoc",
+        aliases: &["text/asciidoc"],
+    },
+    FormatEntry {
+        extensions: &["textile"],
+        mime_type: "text/x-textile",
+        aliases: &["text/textile"],
+    },
+    FormatEntry {
+        extensions: &["wiki", "mediawiki", "wikitext"],
+        mime_type: "text/x-mediawiki",
+        aliases: &["text/x-wiki"],
+    },
     FormatEntry {
         extensions: &["org"],
         mime_type: "text/x-org",
@@ -415,6 +449,26 @@ static FORMATS: &[FormatEntry] = &[
         mime_type: "application/x-fictionbook+xml",
         aliases: &["application/x-fictionbook", "text/x-fictionbook"],
     },
+    FormatEntry {
+        extensions: &["mobi", "prc"],
+        mime_type: "application/x-mobipocket-ebook",
+        aliases: &[],
+    },
+    FormatEntry {
+        extensions: &["azw"],
+        mime_type: "application/vnd.amazon.ebook",
+        aliases: &[],
+    },
+    FormatEntry {
+        extensions: &["azw3"],
+        mime_type: "application/vnd.amazon.mobi8-ebook",
+        aliases: &["application/x-mobi8-ebook"],
+    },
+    FormatEntry {
+        extensions: &["chm"],
+        mime_type: "application/vnd.ms-htmlhelp",
+        aliases: &["application/x-chm"],
+    },
     FormatEntry {
         extensions: &["opml"],
         mime_type: "application/xml+opml",
@@ -618,6 +672,17 @@ pub fn detect_mime_type_from_bytes(content: &[u8]) -> Result<String> {
         }
     }
 
+    // PalmDB and ITSF containers have no signature in `infer`
+    if content
+        .get(60..68)
+        .is_some_and(|t| t == b"BOOKMOBI" || t == b"TEXtREAd")
+    {
+        return Ok("application/x-mobipocket-ebook".to_string());
+    }
+    if content.starts_with(b"ITSF") {
+        return Ok("application/vnd.ms-htmlhelp".to_string());
+    }
+
     if let Ok(text) = std::str::from_utf8(content) {
         let trimmed = text.trim_start();
 
@@ -655,6 +720,10 @@ pub fn detect_mime_type_from_bytes(content: &[u8]) -> Result<String> {
 /// - XLSX: contains `xl/workbook.xml`
 /// - PPTX: contains `ppt/presentation.xml`
 ///
+/// Apple iWork bundles all contain `Index/Document.iwa`; Keynote bundles are
+/// recognised by their per-slide `Index/Slide` archives and Numbers bundles by
+/// their `Index/Tables/` directory, anything else is treated as Pages.
+///
 /// This function scans the ZIP's local file headers without fully parsing the archive,
 /// making it efficient for MIME type detection.
 fn detect_office_format_from_zip(content: &[u8]) -> Option<&'static str> {
@@ -662,6 +731,7 @@ fn detect_office_format_from_zip(content: &[u8]) -> Option<&'static str> {
     const DOCX_MARKER: &[u8] = b"word/document.xml";
     const XLSX_MARKER: &[u8] = b"xl/workbook.xml";
     const PPTX_MARKER: &[u8] = b"ppt/presentation.xml";
+    const IWORK_MARKER: &[u8] = b"Index/Document.iwa";
 
     // Check for each marker using a sliding window search
     if contains_subsequence(content, DOCX_MARKER) {
@@ -673,6 +743,15 @@ fn detect_office_format_from_zip(content: &[u8]) -> Option<&'static str> {
     if contains_subsequence(content, PPTX_MARKER) {
         return Some(POWER_POINT_MIME_TYPE);
     }
+    if contains_subsequence(content, IWORK_MARKER) {
+        if contains_subsequence(content, b"Index/Slide") {
+            return Some(KEYNOTE_MIME_TYPE);
+        }
+        if contains_subsequence(content, b"Index/Tables/") {
+            return Some(NUMBERS_MIME_TYPE);
+        }
+        return Some(PAGES_MIME_TYPE);
+    }
 
     None
 }
@@ -815,6 +894,9 @@ mod tests {
             ("test.ppt", LEGACY_POWERPOINT_MIME_TYPE),
             ("test.docx", DOCX_MIME_TYPE),
             ("test.doc", LEGACY_WORD_MIME_TYPE),
+            ("test.pages", PAGES_MIME_TYPE),
+            ("test.numbers", NUMBERS_MIME_TYPE),
+            ("test.key", KEYNOTE_MIME_TYPE),
         ];
 
         for (filename, expected_mime) in test_cases {
@@ -1023,6 +1105,69 @@ mod tests {
         assert_eq!(mime, "application/zip", "Plain ZIP should remain as application/zip");
     }
 
+    #[test]
+    fn test_detect_iwork_format_from_zip_bytes() {
+        use std::io::Write;
+
+        let bundle = |entries: &[&str]| {
+            let mut buffer = std::io::Cursor::new(Vec::new());
+            let mut zip = zip::ZipWriter::new(&mut buffer);
+            for name in entries {
+                zip.start_file(*name, zip::write::SimpleFileOptions::default()).unwrap();
+                zip.write_all(b"\0").unwrap();
+            }
+            zip.finish().unwrap();
+            buffer.into_inner()
+        };
+
+        let pages = bundle(&["Index/Document.iwa", "Index/DocumentStylesheet.iwa"]);
+        let numbers = bundle(&["Index/Document.iwa", "Index/Tables/Tile.iwa"]);
+        let keynote = bundle(&["Index/Document.iwa", "Index/Slide-1234.iwa"]);
+
+        assert_eq!(detect_mime_type_from_bytes(&pages).unwrap(), PAGES_MIME_TYPE);
+        assert_eq!(detect_mime_type_from_bytes(&numbers).unwrap(), NUMBERS_MIME_TYPE);
+        assert_eq!(detect_mime_type_from_bytes(&keynote).unwrap(), KEYNOTE_MIME_TYPE);
+    }
+
+    #[test]
+    fn test_detect_ebook_and_help_formats() {
+        assert_eq!(
+            detect_mime_type("book.mobi", false).unwrap(),
+            "application/x-mobipocket-ebook"
+        );
+        assert_eq!(
+            detect_mime_type("book.azw3", false).unwrap(),
+            "application/vnd.amazon.mobi8-ebook"
+        );
+        assert_eq!(
+            detect_mime_type("help.chm", false).unwrap(),
+            "application/vnd.ms-htmlhelp"
+        );
+
+        let mut mobi = vec![0u8; 78];
+        mobi[60..68].copy_from_slice(b"BOOKMOBI");
+        assert_eq!(
+            detect_mime_type_from_bytes(&mobi).unwrap(),
+            "application/x-mobipocket-ebook"
+        );
+
+        let mut chm = b"ITSF".to_vec();
+        chm.extend_from_slice(&[3, 0, 0, 0, 0x60, 0, 0, 0, 1, 0xFF]);
+        assert_eq!(
+            detect_mime_type_from_bytes(&chm).unwrap(),
+            "application/vnd.ms-htmlhelp"
+        );
+    }
+
+    #[test]
+    fn test_detect_lightweight_markup_formats() {
+        assert_eq!(detect_mime_type("guide.adoc", false).unwrap(), "text/x-asciidoc");
+        assert_eq!(detect_mime_type("guide.asciidoc", false).unwrap(), "text/x-asciidoc");
+        assert_eq!(detect_mime_type("notes.textile", false).unwrap(), "text/x-textile");
+        assert_eq!(detect_mime_type("page.wiki", false).unwrap(), "text/x-mediawiki");
+        assert_eq!(validate_mime_type("text/x-wiki").unwrap(), "text/x-wiki");
+    }
+
     #[test]
     fn test_list_supported_formats_not_empty() {
         let formats = list_supported_formats();
@@ -32,6 +32,7 @@
 pub(crate) mod batch_mode;
 #[cfg(feature = "tokio-runtime")]
 pub mod batch_optimizations;
+pub mod cancellation;
 pub mod config;
 pub mod config_validation;
 pub mod extractor;
@@ -53,7 +54,7 @@ pub use config_validation::{
     validate_token_reduction_level,
 };
 pub use formats::{KNOWN_FORMATS, is_valid_format_field};
-pub use server_config::ServerConfig;
+pub use server_config::{ApiKeyConfig, ApiScope, ConfigOverridePolicy, ServerConfig, ValueBounds};
 
 #[cfg(feature = "tokio-runtime")]
 pub use batch_optimizations::{BatchProcessor, BatchProcessorConfig};
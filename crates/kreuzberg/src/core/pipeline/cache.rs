@@ -1,38 +1,33 @@
 //! Processor caching to reduce lock contention.
 //!
 //! This module manages the caching of post-processors by processing stage,
-//! eliminating repeated registry lock acquisitions.
+//! eliminating repeated registry lock acquisitions. Each [`Kreuzberg`](crate::Kreuzberg)
+//! engine owns its own cache.
 
 use crate::Result;
+use crate::plugins::registry::PostProcessorRegistry;
 use crate::plugins::{PostProcessor, ProcessingStage};
-use once_cell::sync::Lazy;
 use std::sync::Arc;
-use std::sync::RwLock as StdRwLock;
 
 /// Cached post-processors for each stage to reduce lock contention.
 ///
 /// This cache is populated once during the first pipeline run and reused
 /// for all subsequent extractions, eliminating 3 of 4 registry lock acquisitions
 /// per extraction.
-pub(super) struct ProcessorCache {
+pub(crate) struct ProcessorCache {
     pub(super) early: Arc<Vec<Arc<dyn PostProcessor>>>,
     pub(super) middle: Arc<Vec<Arc<dyn PostProcessor>>>,
     pub(super) late: Arc<Vec<Arc<dyn PostProcessor>>>,
 }
 
 impl ProcessorCache {
-    /// Create a new processor cache by fetching from the registry.
-    pub(super) fn new() -> Result<Self> {
-        let processor_registry = crate::plugins::registry::get_post_processor_registry();
-        let registry = processor_registry
-            .read()
-            .map_err(|e| crate::KreuzbergError::Other(format!("Post-processor registry lock poisoned: {}", e)))?;
-
-        Ok(Self {
+    /// Create a new processor cache from the processors in `registry`.
+    pub(super) fn new(registry: &PostProcessorRegistry) -> Self {
+        Self {
             early: Arc::new(registry.get_for_stage(ProcessingStage::Early)),
             middle: Arc::new(registry.get_for_stage(ProcessingStage::Middle)),
             late: Arc::new(registry.get_for_stage(ProcessingStage::Late)),
-        })
+        }
     }
 
     /// Get processors for a specific stage from cache.
@@ -46,15 +41,12 @@ impl ProcessorCache {
     }
 }
 
-/// Lazy processor cache - initialized on first use, then cached.
-pub(super) static PROCESSOR_CACHE: Lazy<StdRwLock<Option<ProcessorCache>>> = Lazy::new(|| StdRwLock::new(None));
-
-/// Clear the processor cache (primarily for testing when registry changes).
+/// Clear the processor cache of the global engine (primarily for testing when registry changes).
+///
+/// Engines created with [`Kreuzberg::new`](crate::Kreuzberg::new) clear their own cache when
+/// post-processors are registered through them; see
+/// [`Kreuzberg::clear_processor_cache`](crate::Kreuzberg::clear_processor_cache).
 #[allow(dead_code)]
 pub fn clear_processor_cache() -> Result<()> {
-    let mut cache = PROCESSOR_CACHE
-        .write()
-        .map_err(|e| crate::KreuzbergError::Other(format!("Processor cache lock poisoned: {}", e)))?;
-    *cache = None;
-    Ok(())
+    crate::engine::Kreuzberg::global().clear_processor_cache()
 }
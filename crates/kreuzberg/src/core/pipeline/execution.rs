@@ -74,9 +74,13 @@ fn should_processor_run(pp_config: &Option<&crate::core::config::PostProcessorCo
     }
 }
 
-/// Execute all registered validators.
-pub(super) async fn execute_validators(result: &ExtractionResult, config: &ExtractionConfig) -> Result<()> {
-    let validator_registry = crate::plugins::registry::get_validator_registry();
+/// Execute all validators registered with `engine`.
+pub(super) async fn execute_validators(
+    engine: &crate::engine::Kreuzberg,
+    result: &ExtractionResult,
+    config: &ExtractionConfig,
+) -> Result<()> {
+    let validator_registry = engine.validator_registry();
     let validators = {
         let registry = validator_registry
             .read()
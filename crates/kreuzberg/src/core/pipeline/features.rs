@@ -13,8 +13,17 @@ pub(super) fn execute_chunking(result: &mut ExtractionResult, config: &Extractio
     #[cfg(feature = "chunking")]
     if let Some(ref chunking_config) = config.chunking {
         let page_boundaries = result.metadata.pages.as_ref().and_then(|ps| ps.boundaries.as_deref());
+        let chunked = match chunking_config.chunker_type {
+            crate::chunking::ChunkerType::Structure => crate::chunking::chunk_document(result, chunking_config),
+            _ => crate::chunking::chunk_text(&result.content, chunking_config, page_boundaries),
+        };
 
-        match crate::chunking::chunk_text(&result.content, chunking_config, page_boundaries) {
+        // The structure was only built for chunking.
+        if !config.include_document_structure {
+            result.document = None;
+        }
+
+        match chunked {
             Ok(chunking_result) => {
                 result.chunks = Some(chunking_result.chunks);
 
@@ -31,7 +40,12 @@ pub(super) fn execute_chunking(result: &mut ExtractionResult, config: &Extractio
                 if let Some(ref embedding_config) = chunking_config.embedding
                     && let Some(ref mut chunks) = result.chunks
                 {
-                    match crate::embeddings::generate_embeddings_for_chunks(chunks, embedding_config) {
+                    match crate::embeddings::embed_chunks(
+                        chunks,
+                        embedding_config,
+                        config.use_cache,
+                        config.cache_backend,
+                    ) {
                         Ok(()) => {
                             // DEPRECATED: kept for backward compatibility; will be removed in next major version.
                             // embeddings_generated is derivable from result.chunks having non-None embeddings.
@@ -4,8 +4,9 @@
 //! required for pipeline execution.
 
 use crate::Result;
+use crate::engine::Kreuzberg;
 
-use super::cache::{PROCESSOR_CACHE, ProcessorCache};
+use super::cache::ProcessorCache;
 
 /// Type alias for processor stages tuple (Early, Middle, Late).
 type ProcessorStages = (
@@ -15,7 +16,7 @@ type ProcessorStages = (
 );
 
 /// Initialize feature-specific systems that may be needed during pipeline execution.
-pub(super) fn initialize_features() {
+pub(crate) fn initialize_features() {
     #[cfg(any(feature = "keywords-yake", feature = "keywords-rake"))]
     {
         let _ = crate::keywords::ensure_initialized();
@@ -40,20 +41,58 @@ pub(super) fn initialize_features() {
     }
 }
 
-/// Initialize the processor cache if not already initialized.
-pub(super) fn initialize_processor_cache() -> Result<()> {
-    let mut cache_lock = PROCESSOR_CACHE
+/// Register the built-in post-processors enabled by crate features into `registry`.
+///
+/// This is the per-engine equivalent of [`initialize_features`], which registers the same
+/// processors with the global registry on first use.
+#[cfg_attr(
+    not(any(
+        feature = "chunking",
+        feature = "quality",
+        feature = "language-detection",
+        feature = "keywords-yake",
+        feature = "keywords-rake"
+    )),
+    allow(unused_variables)
+)]
+pub(crate) fn register_builtin_processors(
+    registry: &mut crate::plugins::registry::PostProcessorRegistry,
+) -> Result<()> {
+    #[cfg(any(feature = "keywords-yake", feature = "keywords-rake"))]
+    registry.register(std::sync::Arc::new(crate::keywords::KeywordExtractor), 50)?;
+
+    #[cfg(feature = "language-detection")]
+    registry.register(std::sync::Arc::new(crate::language_detection::LanguageDetector), 40)?;
+
+    #[cfg(feature = "chunking")]
+    registry.register(std::sync::Arc::new(crate::chunking::ChunkingProcessor), 50)?;
+
+    #[cfg(feature = "quality")]
+    registry.register(std::sync::Arc::new(crate::text::QualityProcessor), 30)?;
+
+    Ok(())
+}
+
+/// Initialize the processor cache of `engine` if not already initialized.
+pub(super) fn initialize_processor_cache(engine: &Kreuzberg) -> Result<()> {
+    let mut cache_lock = engine
+        .processor_cache()
         .write()
         .map_err(|e| crate::KreuzbergError::Other(format!("Processor cache lock poisoned: {}", e)))?;
     if cache_lock.is_none() {
-        *cache_lock = Some(ProcessorCache::new()?);
+        let processor_registry = engine.post_processor_registry();
+        let registry = processor_registry
+            .read()
+            .map_err(|e| crate::KreuzbergError::Other(format!("Post-processor registry lock poisoned: {}", e)))?;
+        *cache_lock = Some(ProcessorCache::new(&registry));
     }
     Ok(())
 }
 
-/// Get processors from the cache, organized by stage.
-pub(super) fn get_processors_from_cache() -> Result<ProcessorStages> {
-    let cache_lock = PROCESSOR_CACHE
+/// Get processors from the cache of `engine`, organized by stage.
+pub(super) fn get_processors_from_cache(engine: &Kreuzberg) -> Result<ProcessorStages> {
+    let cache_lock = engine
+        .processor_cache()
         .read()
         .map_err(|e| crate::KreuzbergError::Other(format!("Processor cache lock poisoned: {}", e)))?;
     let cache = cache_lock
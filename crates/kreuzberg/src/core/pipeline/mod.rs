@@ -12,8 +12,10 @@ mod initialization;
 #[cfg(test)]
 mod tests;
 
+pub(crate) use cache::ProcessorCache;
 pub use cache::clear_processor_cache;
 pub use format::apply_output_format;
+pub(crate) use initialization::{initialize_features, register_builtin_processors};
 
 use crate::Result;
 use crate::core::config::ExtractionConfig;
@@ -21,10 +23,13 @@ use crate::types::ExtractionResult;
 
 use execution::{execute_processors, execute_validators};
 use features::{execute_chunking, execute_language_detection};
-use initialization::{get_processors_from_cache, initialize_features, initialize_processor_cache};
+use initialization::{get_processors_from_cache, initialize_processor_cache};
 
 /// Run the post-processing pipeline on an extraction result.
 ///
+/// Post-processors and validators are taken from the engine the call runs under (see
+/// [`Kreuzberg`](crate::Kreuzberg)), or from the global registries outside of one.
+///
 /// Executes post-processing in the following order:
 /// 1. Post-Processors - Execute by stage (Early, Middle, Late) to modify/enhance the result
 /// 2. Quality Processing - Text cleaning and quality scoring
@@ -56,11 +61,15 @@ pub async fn run_pipeline(mut result: ExtractionResult, config: &ExtractionConfi
     let pp_config = config.postprocessor.as_ref();
     let postprocessing_enabled = pp_config.is_none_or(|c| c.enabled);
 
+    let engine = crate::engine::current();
+
     if postprocessing_enabled {
-        initialize_features();
-        initialize_processor_cache()?;
+        if engine.is_global() {
+            initialize_features();
+        }
+        initialize_processor_cache(&engine)?;
 
-        let (early_processors, middle_processors, late_processors) = get_processors_from_cache()?;
+        let (early_processors, middle_processors, late_processors) = get_processors_from_cache(&engine)?;
 
         execute_processors(
             &mut result,
@@ -75,7 +84,7 @@ pub async fn run_pipeline(mut result: ExtractionResult, config: &ExtractionConfi
 
     execute_chunking(&mut result, config)?;
     execute_language_detection(&mut result, config)?;
-    execute_validators(&result, config).await?;
+    execute_validators(&engine, &result, config).await?;
 
     // Transform to element-based output if requested
     if config.result_format == crate::types::OutputFormat::ElementBased {
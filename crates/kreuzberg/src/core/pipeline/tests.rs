@@ -146,6 +146,7 @@ async fn test_pipeline_with_chunking() {
             overlap: 50,
             trim: true,
             chunker_type: crate::ChunkerType::Text,
+            sizing: crate::ChunkSizing::Characters,
             embedding: None,
             preset: None,
         }),
@@ -353,6 +354,7 @@ async fn test_pipeline_with_all_features() {
             overlap: 50,
             trim: true,
             chunker_type: crate::ChunkerType::Text,
+            sizing: crate::ChunkSizing::Characters,
             embedding: None,
             preset: None,
         }),
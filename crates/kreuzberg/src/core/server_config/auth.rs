@@ -0,0 +1,157 @@
+//! API key configuration for the API server.
+//!
+//! Keys are static secrets loaded from the config file or `KREUZBERG_API_KEYS`. Each key
+//! carries a set of scopes and optional rate and concurrency limits. Enforcement lives in
+//! the API server; this module only describes the configuration.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::{KreuzbergError, Result};
+
+/// Permission granted to an API key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ApiScope {
+    /// Extraction, job, embedding and chunking endpoints.
+    Extract,
+    /// Cache management and metrics endpoints. Implies `Extract`.
+    Admin,
+}
+
+impl ApiScope {
+    /// Whether a key holding this scope may call endpoints that require `required`.
+    pub fn grants(self, required: ApiScope) -> bool {
+        self == required || self == ApiScope::Admin
+    }
+
+    /// Scope name as used in configuration files.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Extract => "extract",
+            Self::Admin => "admin",
+        }
+    }
+
+    /// Parse a scope name (`extract` or `admin`, case-insensitive).
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "extract" => Ok(Self::Extract),
+            "admin" => Ok(Self::Admin),
+            other => Err(KreuzbergError::validation(format!(
+                "Invalid API key scope: '{}'. Valid values: 'extract', 'admin'",
+                other
+            ))),
+        }
+    }
+}
+
+fn default_scopes() -> Vec<ApiScope> {
+    vec![ApiScope::Extract]
+}
+
+/// A static API key accepted by the server.
+///
+/// # Example (TOML)
+///
+/// ```toml
+/// [[api_keys]]
+/// key = "change-me"
+/// name = "ingest-worker"
+/// scopes = ["extract"]
+/// rate_limit_per_minute = 120
+/// max_concurrent_requests = 4
+/// ```
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiKeyConfig {
+    /// The secret sent by clients as `Authorization: Bearer <key>` or `X-API-Key: <key>`.
+    pub key: String,
+
+    /// Human-readable client name used in logs and error messages.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Scopes granted to this key (default: `["extract"]`).
+    #[serde(default = "default_scopes")]
+    pub scopes: Vec<ApiScope>,
+
+    /// Sustained request rate allowed for this key. Falls back to
+    /// `ServerConfig::rate_limit_per_minute`; `None` in both means unlimited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit_per_minute: Option<u32>,
+
+    /// Maximum burst size for the token bucket (default: the per-minute rate).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub burst: Option<u32>,
+
+    /// Maximum number of requests handled concurrently for this key. Falls back to
+    /// `ServerConfig::max_concurrent_requests_per_key`; `None` in both means unlimited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_requests: Option<usize>,
+}
+
+impl ApiKeyConfig {
+    /// Create a key with the default `extract` scope and no limits.
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            name: None,
+            scopes: default_scopes(),
+            rate_limit_per_minute: None,
+            burst: None,
+            max_concurrent_requests: None,
+        }
+    }
+
+    /// Name used to identify the client in logs and error messages.
+    ///
+    /// Falls back to a short prefix of the key so the full secret is never logged.
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("{}…", self.key.chars().take(4).collect::<String>()))
+    }
+
+    /// Whether this key may call endpoints that require `required`.
+    pub fn allows(&self, required: ApiScope) -> bool {
+        self.scopes.iter().any(|scope| scope.grants(required))
+    }
+
+    /// Parse one `KREUZBERG_API_KEYS` entry: `key` or `key:scope+scope`.
+    pub(super) fn parse_env_entry(entry: &str) -> Result<Self> {
+        let (key, scopes) = match entry.split_once(':') {
+            Some((key, scopes)) => (key.trim(), Some(scopes)),
+            None => (entry.trim(), None),
+        };
+        if key.is_empty() {
+            return Err(KreuzbergError::validation("KREUZBERG_API_KEYS contains an empty key"));
+        }
+
+        let mut config = Self::new(key);
+        if let Some(scopes) = scopes {
+            config.scopes = scopes
+                .split('+')
+                .filter(|s| !s.trim().is_empty())
+                .map(ApiScope::parse)
+                .collect::<Result<_>>()?;
+            if config.scopes.is_empty() {
+                config.scopes = default_scopes();
+            }
+        }
+        Ok(config)
+    }
+}
+
+// Keep secrets out of debug output (e.g. logged server configuration).
+impl fmt::Debug for ApiKeyConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ApiKeyConfig")
+            .field("key", &"<redacted>")
+            .field("name", &self.name)
+            .field("scopes", &self.scopes)
+            .field("rate_limit_per_minute", &self.rate_limit_per_minute)
+            .field("burst", &self.burst)
+            .field("max_concurrent_requests", &self.max_concurrent_requests)
+            .finish()
+    }
+}
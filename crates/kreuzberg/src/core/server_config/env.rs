@@ -88,3 +88,153 @@ pub fn apply_env_overrides(
 
     Ok(())
 }
+
+/// Apply environment variable overrides to the job queue settings of a ServerConfig.
+///
+/// Reads the following environment variables and overrides config values if set:
+///
+/// - `KREUZBERG_JOB_CONCURRENCY` - Number of concurrently processed jobs (must be > 0)
+/// - `KREUZBERG_JOB_QUEUE_CAPACITY` - Maximum number of queued jobs (must be > 0)
+/// - `KREUZBERG_JOB_RETENTION_SECS` - Retention for finished jobs in seconds
+/// - `KREUZBERG_JOB_STATE_DIR` - Directory for persisted job state (empty disables persistence)
+///
+/// # Errors
+///
+/// Returns `KreuzbergError::Validation` if a numeric value cannot be parsed or
+/// the concurrency or queue capacity is zero.
+pub fn apply_job_env_overrides(
+    job_concurrency: &mut usize,
+    job_queue_capacity: &mut usize,
+    job_retention_secs: &mut u64,
+    job_state_dir: &mut Option<std::path::PathBuf>,
+) -> Result<()> {
+    if let Ok(value) = std::env::var("KREUZBERG_JOB_CONCURRENCY") {
+        *job_concurrency = parse_positive("KREUZBERG_JOB_CONCURRENCY", &value)?;
+    }
+
+    if let Ok(value) = std::env::var("KREUZBERG_JOB_QUEUE_CAPACITY") {
+        *job_queue_capacity = parse_positive("KREUZBERG_JOB_QUEUE_CAPACITY", &value)?;
+    }
+
+    if let Ok(value) = std::env::var("KREUZBERG_JOB_RETENTION_SECS") {
+        *job_retention_secs = value.parse::<u64>().map_err(|e| {
+            KreuzbergError::validation(format!(
+                "KREUZBERG_JOB_RETENTION_SECS must be a valid u64, got '{}': {}",
+                value, e
+            ))
+        })?;
+    }
+
+    if let Ok(value) = std::env::var("KREUZBERG_JOB_STATE_DIR") {
+        let trimmed = value.trim();
+        *job_state_dir = if trimmed.is_empty() {
+            None
+        } else {
+            Some(std::path::PathBuf::from(trimmed))
+        };
+    }
+
+    Ok(())
+}
+
+/// Apply environment variable overrides to the authentication settings of a ServerConfig.
+///
+/// Reads the following environment variables and overrides config values if set:
+///
+/// - `KREUZBERG_API_KEYS` - Comma-separated API keys, each `key` or `key:scope+scope`
+///   (e.g. `k1:extract,k2:admin`); replaces keys from the config file, empty disables auth
+/// - `KREUZBERG_RATE_LIMIT_PER_MINUTE` - Default request rate per key (must be > 0)
+/// - `KREUZBERG_MAX_CONCURRENT_REQUESTS_PER_KEY` - Default concurrency cap per key (must be > 0)
+///
+/// # Errors
+///
+/// Returns `KreuzbergError::Validation` if a key entry is invalid or a limit cannot
+/// be parsed or is zero.
+pub fn apply_auth_env_overrides(
+    api_keys: &mut Vec<super::ApiKeyConfig>,
+    rate_limit_per_minute: &mut Option<u32>,
+    max_concurrent_requests_per_key: &mut Option<usize>,
+) -> Result<()> {
+    if let Ok(value) = std::env::var("KREUZBERG_API_KEYS") {
+        *api_keys = value
+            .split(',')
+            .filter(|entry| !entry.trim().is_empty())
+            .map(super::ApiKeyConfig::parse_env_entry)
+            .collect::<Result<_>>()?;
+    }
+
+    if let Ok(value) = std::env::var("KREUZBERG_RATE_LIMIT_PER_MINUTE") {
+        let rate = parse_positive("KREUZBERG_RATE_LIMIT_PER_MINUTE", &value)?;
+        *rate_limit_per_minute = Some(u32::try_from(rate).map_err(|_| {
+            KreuzbergError::validation(format!(
+                "KREUZBERG_RATE_LIMIT_PER_MINUTE must fit in a u32, got '{}'",
+                value
+            ))
+        })?);
+    }
+
+    if let Ok(value) = std::env::var("KREUZBERG_MAX_CONCURRENT_REQUESTS_PER_KEY") {
+        *max_concurrent_requests_per_key = Some(parse_positive("KREUZBERG_MAX_CONCURRENT_REQUESTS_PER_KEY", &value)?);
+    }
+
+    Ok(())
+}
+
+/// Apply environment variable overrides to the config override policy of a ServerConfig.
+///
+/// Reads the following environment variables and overrides config values if set:
+///
+/// - `KREUZBERG_CONFIG_ALLOWED_FIELDS` - Comma-separated field paths clients may override
+/// - `KREUZBERG_CONFIG_DENIED_FIELDS` - Comma-separated field paths clients may not override
+/// - `KREUZBERG_CONFIG_BOUNDS` - Comma-separated `path=min..max` entries (e.g. `images.target_dpi=72..300`)
+///
+/// # Errors
+///
+/// Returns `KreuzbergError::Validation` if `KREUZBERG_CONFIG_BOUNDS` is malformed.
+pub fn apply_override_policy_env_overrides(policy: &mut super::ConfigOverridePolicy) -> Result<()> {
+    if let Ok(value) = std::env::var("KREUZBERG_CONFIG_ALLOWED_FIELDS") {
+        policy.allowed_fields = split_list(&value);
+    }
+
+    if let Ok(value) = std::env::var("KREUZBERG_CONFIG_DENIED_FIELDS") {
+        policy.denied_fields = split_list(&value);
+    }
+
+    if let Ok(value) = std::env::var("KREUZBERG_CONFIG_BOUNDS") {
+        policy.bounds = super::ConfigOverridePolicy::parse_bounds(&value)?;
+    }
+
+    Ok(())
+}
+
+/// Apply the `KREUZBERG_CACHE_BACKEND` override to a ServerConfig.
+///
+/// # Errors
+///
+/// Returns `KreuzbergError::Validation` if the value is not a known backend.
+pub fn apply_cache_env_overrides(cache_backend: &mut Option<crate::cache::CacheBackendType>) -> Result<()> {
+    if let Ok(value) = std::env::var("KREUZBERG_CACHE_BACKEND") {
+        *cache_backend = Some(value.parse()?);
+    }
+
+    Ok(())
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+fn parse_positive(name: &str, value: &str) -> Result<usize> {
+    match value.parse::<usize>() {
+        Ok(0) => Err(KreuzbergError::validation(format!("{} must be greater than 0", name))),
+        Ok(parsed) => Ok(parsed),
+        Err(e) => Err(KreuzbergError::validation(format!(
+            "{} must be a valid usize, got '{}': {}",
+            name, value, e
+        ))),
+    }
+}
@@ -11,6 +11,8 @@
 //! - **Backward compatibility**: Supports legacy `max_upload_mb` field for smooth migrations
 //! - **Sensible defaults**: All fields have reasonable defaults matching current behavior
 //! - **Flexible CORS**: Support for all origins (default) or specific origin lists
+//! - **Authentication**: Optional API keys with scopes, rate limits and concurrency caps
+//! - **Config overrides**: Allow/deny lists and bounds for per-request extraction settings
 //!
 //! # Example
 //!
@@ -32,13 +34,19 @@
 //! ```
 
 use crate::Result;
+use crate::cache::CacheBackendType;
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+mod auth;
 mod env;
 mod loader;
+mod overrides;
 mod validation;
 
+pub use auth::{ApiKeyConfig, ApiScope};
+pub use overrides::{ConfigOverridePolicy, ValueBounds};
+
 #[cfg(test)]
 mod tests;
 
@@ -54,6 +62,15 @@ const DEFAULT_MAX_REQUEST_BODY_BYTES: usize = 104_857_600;
 /// Default maximum multipart field size: 100 MB
 const DEFAULT_MAX_MULTIPART_FIELD_BYTES: usize = 104_857_600;
 
+/// Default number of background jobs processed concurrently
+const DEFAULT_JOB_CONCURRENCY: usize = 2;
+
+/// Default maximum number of jobs waiting in the queue
+const DEFAULT_JOB_QUEUE_CAPACITY: usize = 100;
+
+/// Default retention for finished jobs: 1 hour
+const DEFAULT_JOB_RETENTION_SECS: u64 = 3600;
+
 /// API server configuration.
 ///
 /// This struct holds all configuration options for the Kreuzberg API server,
@@ -67,6 +84,15 @@ const DEFAULT_MAX_MULTIPART_FIELD_BYTES: usize = 104_857_600;
 /// - `max_request_body_bytes`: 104_857_600 (100 MB)
 /// - `max_multipart_field_bytes`: 104_857_600 (100 MB)
 /// - `max_upload_mb`: None (legacy field, not used if other fields set)
+/// - `job_concurrency`: 2
+/// - `job_queue_capacity`: 100
+/// - `job_retention_secs`: 3600 (1 hour)
+/// - `job_state_dir`: None (job state is kept in memory only)
+/// - `api_keys`: empty vector (authentication disabled)
+/// - `rate_limit_per_minute`: None (unlimited)
+/// - `max_concurrent_requests_per_key`: None (unlimited)
+/// - `config_overrides`: unrestricted (clients may override any extraction setting)
+/// - `cache_backend`: None (process default, normally the flat filesystem layout)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct ServerConfig {
@@ -101,6 +127,53 @@ pub struct ServerConfig {
     /// New configurations should use `max_multipart_field_bytes` directly.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_upload_mb: Option<usize>,
+
+    /// Number of asynchronous extraction jobs processed concurrently (default: 2)
+    #[serde(default = "default_job_concurrency")]
+    pub job_concurrency: usize,
+
+    /// Maximum number of jobs waiting for a worker (default: 100).
+    ///
+    /// Submissions beyond this limit are rejected with HTTP 503 until the queue drains.
+    #[serde(default = "default_job_queue_capacity")]
+    pub job_queue_capacity: usize,
+
+    /// How long finished jobs and their results are kept, in seconds (default: 3600)
+    #[serde(default = "default_job_retention_secs")]
+    pub job_retention_secs: u64,
+
+    /// Directory used to persist job state and results.
+    ///
+    /// When set, finished jobs survive server restarts. Jobs that were still queued
+    /// or running when the server stopped are reported as failed after a restart.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub job_state_dir: Option<PathBuf>,
+
+    /// API keys accepted by the server. Empty vector disables authentication.
+    ///
+    /// When keys are configured, every endpoint except `/health`, `/info` and
+    /// `/openapi.json` requires a key with a matching scope.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub api_keys: Vec<ApiKeyConfig>,
+
+    /// Default sustained request rate per API key, for keys without their own limit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate_limit_per_minute: Option<u32>,
+
+    /// Default cap on concurrently handled requests per API key, for keys without their own limit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_requests_per_key: Option<usize>,
+
+    /// Restrictions on the extraction config clients may send with a request.
+    #[serde(skip_serializing_if = "ConfigOverridePolicy::is_unrestricted")]
+    pub config_overrides: ConfigOverridePolicy,
+
+    /// Storage backend for the OCR, embedding and extraction result caches.
+    ///
+    /// `None` keeps the process default (`KREUZBERG_CACHE_BACKEND`, else `filesystem`).
+    /// Use `sharded` when several server instances share one cache volume.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_backend: Option<CacheBackendType>,
 }
 
 impl Default for ServerConfig {
@@ -112,6 +185,15 @@ impl Default for ServerConfig {
             max_request_body_bytes: default_max_request_body_bytes(),
             max_multipart_field_bytes: default_max_multipart_field_bytes(),
             max_upload_mb: None,
+            job_concurrency: default_job_concurrency(),
+            job_queue_capacity: default_job_queue_capacity(),
+            job_retention_secs: default_job_retention_secs(),
+            job_state_dir: None,
+            api_keys: Vec::new(),
+            rate_limit_per_minute: None,
+            max_concurrent_requests_per_key: None,
+            config_overrides: ConfigOverridePolicy::default(),
+            cache_backend: None,
         }
     }
 }
@@ -133,6 +215,18 @@ fn default_max_multipart_field_bytes() -> usize {
     DEFAULT_MAX_MULTIPART_FIELD_BYTES
 }
 
+fn default_job_concurrency() -> usize {
+    DEFAULT_JOB_CONCURRENCY
+}
+
+fn default_job_queue_capacity() -> usize {
+    DEFAULT_JOB_QUEUE_CAPACITY
+}
+
+fn default_job_retention_secs() -> u64 {
+    DEFAULT_JOB_RETENTION_SECS
+}
+
 impl ServerConfig {
     /// Create a new `ServerConfig` with default values.
     pub fn new() -> Self {
@@ -199,6 +293,23 @@ impl ServerConfig {
         self.cors_origins.is_empty() || self.cors_origins.contains(&origin.to_string())
     }
 
+    /// Check if API key authentication is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kreuzberg::core::{ApiKeyConfig, ServerConfig};
+    ///
+    /// let mut config = ServerConfig::default();
+    /// assert!(!config.auth_enabled());
+    ///
+    /// config.api_keys.push(ApiKeyConfig::new("secret"));
+    /// assert!(config.auth_enabled());
+    /// ```
+    pub fn auth_enabled(&self) -> bool {
+        !self.api_keys.is_empty()
+    }
+
     /// Get maximum request body size in megabytes (rounded up).
     ///
     /// # Example
@@ -248,6 +359,17 @@ impl ServerConfig {
     /// - `KREUZBERG_MAX_REQUEST_BODY_BYTES` - Max request body size in bytes
     /// - `KREUZBERG_MAX_MULTIPART_FIELD_BYTES` - Max multipart field size in bytes
     /// - `KREUZBERG_MAX_UPLOAD_SIZE_MB` - Max upload size in MB (legacy)
+    /// - `KREUZBERG_JOB_CONCURRENCY` - Number of concurrently processed jobs
+    /// - `KREUZBERG_JOB_QUEUE_CAPACITY` - Maximum number of queued jobs
+    /// - `KREUZBERG_JOB_RETENTION_SECS` - Retention for finished jobs in seconds
+    /// - `KREUZBERG_JOB_STATE_DIR` - Directory for persisted job state
+    /// - `KREUZBERG_API_KEYS` - Comma-separated API keys, each `key` or `key:scope+scope`
+    /// - `KREUZBERG_RATE_LIMIT_PER_MINUTE` - Default request rate per API key
+    /// - `KREUZBERG_MAX_CONCURRENT_REQUESTS_PER_KEY` - Default concurrency cap per API key
+    /// - `KREUZBERG_CONFIG_ALLOWED_FIELDS` - Comma-separated config fields clients may override
+    /// - `KREUZBERG_CONFIG_DENIED_FIELDS` - Comma-separated config fields clients may not override
+    /// - `KREUZBERG_CONFIG_BOUNDS` - Comma-separated `path=min..max` bounds for overrides
+    /// - `KREUZBERG_CACHE_BACKEND` - Cache storage backend (`filesystem`, `sqlite` or `sharded`)
     ///
     /// # Errors
     ///
@@ -256,6 +378,11 @@ impl ServerConfig {
     /// - `KREUZBERG_MAX_REQUEST_BODY_BYTES` cannot be parsed as usize
     /// - `KREUZBERG_MAX_MULTIPART_FIELD_BYTES` cannot be parsed as usize
     /// - `KREUZBERG_MAX_UPLOAD_SIZE_MB` cannot be parsed as usize
+    /// - A job setting cannot be parsed or is zero
+    /// - `KREUZBERG_API_KEYS` contains an empty key or an unknown scope
+    /// - A rate or concurrency limit cannot be parsed or is zero
+    /// - `KREUZBERG_CONFIG_BOUNDS` is malformed
+    /// - `KREUZBERG_CACHE_BACKEND` names an unknown backend
     ///
     /// # Example
     ///
@@ -285,6 +412,19 @@ impl ServerConfig {
             &mut self.max_multipart_field_bytes,
             &mut self.max_upload_mb,
         )?;
+        env::apply_job_env_overrides(
+            &mut self.job_concurrency,
+            &mut self.job_queue_capacity,
+            &mut self.job_retention_secs,
+            &mut self.job_state_dir,
+        )?;
+        env::apply_auth_env_overrides(
+            &mut self.api_keys,
+            &mut self.rate_limit_per_minute,
+            &mut self.max_concurrent_requests_per_key,
+        )?;
+        env::apply_override_policy_env_overrides(&mut self.config_overrides)?;
+        env::apply_cache_env_overrides(&mut self.cache_backend)?;
 
         // Apply legacy field normalization
         self.normalize_legacy_fields();
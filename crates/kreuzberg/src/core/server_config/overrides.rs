@@ -0,0 +1,136 @@
+//! Policy for per-request extraction config overrides.
+//!
+//! API clients may send a partial `ExtractionConfig` that is merged over the server
+//! default. This policy restricts which fields they may change and to which values.
+//! Fields are addressed by dotted paths of their serialized names, e.g. `ocr.language`
+//! or `chunking.max_chars`; a path also covers everything below it.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{KreuzbergError, Result};
+
+/// Inclusive numeric bounds for an overridable field.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ValueBounds {
+    /// Smallest accepted value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    /// Largest accepted value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+}
+
+impl ValueBounds {
+    /// Whether `value` lies within the bounds.
+    pub fn contains(&self, value: f64) -> bool {
+        self.min.is_none_or(|min| value >= min) && self.max.is_none_or(|max| value <= max)
+    }
+}
+
+/// Which extraction config fields clients may override per request.
+///
+/// The default policy allows every field, matching the behaviour of earlier releases.
+///
+/// # Example (TOML)
+///
+/// ```toml
+/// [config_overrides]
+/// allowed_fields = ["ocr.language", "chunking", "output_format", "images.target_dpi"]
+/// denied_fields = ["force_ocr", "security_limits"]
+///
+/// [config_overrides.bounds]
+/// "images.target_dpi" = { min = 72, max = 300 }
+/// "chunking.max_chars" = { max = 4000 }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConfigOverridePolicy {
+    /// Fields clients may change. Empty means every field not listed in `denied_fields`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub allowed_fields: Vec<String>,
+
+    /// Fields clients may never change. Takes precedence over `allowed_fields`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub denied_fields: Vec<String>,
+
+    /// Numeric bounds for overridden values, keyed by field path.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub bounds: BTreeMap<String, ValueBounds>,
+}
+
+impl ConfigOverridePolicy {
+    /// Whether the policy places no restriction on overrides.
+    pub fn is_unrestricted(&self) -> bool {
+        self.allowed_fields.is_empty() && self.denied_fields.is_empty() && self.bounds.is_empty()
+    }
+
+    /// Whether clients may change the field at `path`.
+    ///
+    /// Changing a field is denied when it lies below a denied path or contains one
+    /// (replacing `ocr` would also replace a denied `ocr.language`).
+    pub fn allows(&self, path: &str) -> bool {
+        let denied = self
+            .denied_fields
+            .iter()
+            .any(|denied| is_within(path, denied) || is_within(denied, path));
+        let allowed =
+            self.allowed_fields.is_empty() || self.allowed_fields.iter().any(|allowed| is_within(path, allowed));
+        allowed && !denied
+    }
+
+    /// Whether clients may create the section at `path` that is unset in the server default.
+    ///
+    /// Creating a section switches on the feature it configures, so a section below a
+    /// denied path is refused even when it is sent empty. With an allowlist, a section may
+    /// be created when it is allowed itself or holds an allowed field.
+    pub fn allows_section(&self, path: &str) -> bool {
+        let denied = self.denied_fields.iter().any(|denied| is_within(path, denied));
+        let allowed = self.allowed_fields.is_empty()
+            || self
+                .allowed_fields
+                .iter()
+                .any(|allowed| is_within(path, allowed) || is_within(allowed, path));
+        allowed && !denied
+    }
+
+    /// Parse `KREUZBERG_CONFIG_BOUNDS` entries: `path=min..max`, either side optional.
+    pub(super) fn parse_bounds(value: &str) -> Result<BTreeMap<String, ValueBounds>> {
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                let invalid = || {
+                    KreuzbergError::validation(format!(
+                        "KREUZBERG_CONFIG_BOUNDS entries must look like 'path=min..max', got '{}'",
+                        entry
+                    ))
+                };
+                let (path, range) = entry.split_once('=').ok_or_else(invalid)?;
+                let (min, max) = range.split_once("..").ok_or_else(invalid)?;
+                let parse = |bound: &str| -> Result<Option<f64>> {
+                    let bound = bound.trim();
+                    if bound.is_empty() {
+                        Ok(None)
+                    } else {
+                        bound.parse::<f64>().map(Some).map_err(|_| invalid())
+                    }
+                };
+                Ok((
+                    path.trim().to_string(),
+                    ValueBounds {
+                        min: parse(min)?,
+                        max: parse(max)?,
+                    },
+                ))
+            })
+            .collect()
+    }
+}
+
+/// Whether `path` equals `prefix` or lies below it.
+fn is_within(path: &str, prefix: &str) -> bool {
+    path == prefix || (path.starts_with(prefix) && path.as_bytes().get(prefix.len()) == Some(&b'.'))
+}
@@ -11,6 +11,14 @@ fn test_default_config() {
     assert_eq!(config.max_request_body_bytes, 104_857_600);
     assert_eq!(config.max_multipart_field_bytes, 104_857_600);
     assert!(config.max_upload_mb.is_none());
+    assert_eq!(config.job_concurrency, 2);
+    assert_eq!(config.job_queue_capacity, 100);
+    assert_eq!(config.job_retention_secs, 3600);
+    assert!(config.job_state_dir.is_none());
+    assert!(config.api_keys.is_empty());
+    assert!(!config.auth_enabled());
+    assert!(config.rate_limit_per_minute.is_none());
+    assert!(config.max_concurrent_requests_per_key.is_none());
 }
 
 #[test]
@@ -122,3 +130,18 @@ fn test_serde_with_max_upload_mb_serialization() {
     // Should serialize with max_upload_mb when Some
     assert!(json.contains("max_upload_mb"));
 }
+
+#[test]
+fn test_api_key_scopes_and_debug_redaction() {
+    use crate::core::{ApiKeyConfig, ApiScope};
+
+    let mut key = ApiKeyConfig::new("super-secret");
+    assert!(key.allows(ApiScope::Extract));
+    assert!(!key.allows(ApiScope::Admin));
+
+    key.scopes = vec![ApiScope::Admin];
+    assert!(key.allows(ApiScope::Extract));
+    assert!(key.allows(ApiScope::Admin));
+
+    assert!(!format!("{:?}", key).contains("super-secret"));
+}
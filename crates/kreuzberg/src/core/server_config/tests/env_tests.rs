@@ -214,3 +214,180 @@ fn test_apply_env_multiple_overrides() {
         }
     }
 }
+
+#[serial_test::serial]
+#[test]
+fn test_apply_env_job_overrides() {
+    let keys = [
+        "KREUZBERG_JOB_CONCURRENCY",
+        "KREUZBERG_JOB_QUEUE_CAPACITY",
+        "KREUZBERG_JOB_RETENTION_SECS",
+        "KREUZBERG_JOB_STATE_DIR",
+    ];
+    let originals: Vec<_> = keys.iter().map(|key| std::env::var(key).ok()).collect();
+    unsafe {
+        std::env::set_var("KREUZBERG_JOB_CONCURRENCY", "8");
+        std::env::set_var("KREUZBERG_JOB_QUEUE_CAPACITY", "500");
+        std::env::set_var("KREUZBERG_JOB_RETENTION_SECS", "60");
+        std::env::set_var("KREUZBERG_JOB_STATE_DIR", "/var/lib/kreuzberg/jobs");
+    }
+
+    let mut config = ServerConfig::default();
+    config.apply_env_overrides().unwrap();
+
+    assert_eq!(config.job_concurrency, 8);
+    assert_eq!(config.job_queue_capacity, 500);
+    assert_eq!(config.job_retention_secs, 60);
+    assert_eq!(
+        config.job_state_dir.as_deref(),
+        Some(std::path::Path::new("/var/lib/kreuzberg/jobs"))
+    );
+
+    unsafe {
+        std::env::set_var("KREUZBERG_JOB_CONCURRENCY", "0");
+    }
+    let mut config = ServerConfig::default();
+    assert!(config.apply_env_overrides().is_err());
+
+    // Cleanup
+    unsafe {
+        for (key, original) in keys.iter().zip(originals) {
+            if let Some(orig) = original {
+                std::env::set_var(key, orig);
+            } else {
+                std::env::remove_var(key);
+            }
+        }
+    }
+}
+
+#[serial_test::serial]
+#[test]
+fn test_apply_env_auth_overrides() {
+    use crate::core::ApiScope;
+
+    let keys = [
+        "KREUZBERG_API_KEYS",
+        "KREUZBERG_RATE_LIMIT_PER_MINUTE",
+        "KREUZBERG_MAX_CONCURRENT_REQUESTS_PER_KEY",
+    ];
+    let originals: Vec<_> = keys.iter().map(|key| std::env::var(key).ok()).collect();
+    unsafe {
+        std::env::set_var("KREUZBERG_API_KEYS", "k1, k2:admin, k3:extract+admin");
+        std::env::set_var("KREUZBERG_RATE_LIMIT_PER_MINUTE", "120");
+        std::env::set_var("KREUZBERG_MAX_CONCURRENT_REQUESTS_PER_KEY", "4");
+    }
+
+    let mut config = ServerConfig::default();
+    config.apply_env_overrides().unwrap();
+
+    assert_eq!(config.api_keys.len(), 3);
+    assert_eq!(config.api_keys[0].key, "k1");
+    assert_eq!(config.api_keys[0].scopes, vec![ApiScope::Extract]);
+    assert_eq!(config.api_keys[1].scopes, vec![ApiScope::Admin]);
+    assert_eq!(config.api_keys[2].scopes, vec![ApiScope::Extract, ApiScope::Admin]);
+    assert_eq!(config.rate_limit_per_minute, Some(120));
+    assert_eq!(config.max_concurrent_requests_per_key, Some(4));
+
+    unsafe {
+        std::env::set_var("KREUZBERG_API_KEYS", "k1:superuser");
+    }
+    let mut config = ServerConfig::default();
+    assert!(config.apply_env_overrides().is_err());
+
+    unsafe {
+        std::env::set_var("KREUZBERG_API_KEYS", "k1");
+        std::env::set_var("KREUZBERG_RATE_LIMIT_PER_MINUTE", "0");
+    }
+    let mut config = ServerConfig::default();
+    assert!(config.apply_env_overrides().is_err());
+
+    // Cleanup
+    unsafe {
+        for (key, original) in keys.iter().zip(originals) {
+            if let Some(orig) = original {
+                std::env::set_var(key, orig);
+            } else {
+                std::env::remove_var(key);
+            }
+        }
+    }
+}
+
+#[serial_test::serial]
+#[test]
+fn test_apply_env_config_override_policy() {
+    let keys = [
+        "KREUZBERG_CONFIG_ALLOWED_FIELDS",
+        "KREUZBERG_CONFIG_DENIED_FIELDS",
+        "KREUZBERG_CONFIG_BOUNDS",
+    ];
+    let originals: Vec<_> = keys.iter().map(|key| std::env::var(key).ok()).collect();
+    unsafe {
+        std::env::set_var("KREUZBERG_CONFIG_ALLOWED_FIELDS", "ocr.language, chunking");
+        std::env::set_var("KREUZBERG_CONFIG_DENIED_FIELDS", "force_ocr");
+        std::env::set_var(
+            "KREUZBERG_CONFIG_BOUNDS",
+            "images.target_dpi=72..300, chunking.max_chars=..4000",
+        );
+    }
+
+    let mut config = ServerConfig::default();
+    config.apply_env_overrides().unwrap();
+
+    let policy = &config.config_overrides;
+    assert_eq!(policy.allowed_fields, vec!["ocr.language", "chunking"]);
+    assert_eq!(policy.denied_fields, vec!["force_ocr"]);
+    assert_eq!(policy.bounds["images.target_dpi"].min, Some(72.0));
+    assert_eq!(policy.bounds["images.target_dpi"].max, Some(300.0));
+    assert_eq!(policy.bounds["chunking.max_chars"].min, None);
+    assert!(policy.allows("chunking.max_chars"));
+    assert!(!policy.allows("ocr"));
+    assert!(!policy.allows("force_ocr"));
+
+    unsafe {
+        std::env::set_var("KREUZBERG_CONFIG_BOUNDS", "images.target_dpi=high");
+    }
+    let mut config = ServerConfig::default();
+    assert!(config.apply_env_overrides().is_err());
+
+    // Cleanup
+    unsafe {
+        for (key, original) in keys.iter().zip(originals) {
+            if let Some(orig) = original {
+                std::env::set_var(key, orig);
+            } else {
+                std::env::remove_var(key);
+            }
+        }
+    }
+}
+
+#[serial_test::serial]
+#[test]
+fn test_apply_env_cache_backend_override() {
+    let original = std::env::var("KREUZBERG_CACHE_BACKEND").ok();
+    unsafe {
+        std::env::set_var("KREUZBERG_CACHE_BACKEND", "sharded");
+    }
+
+    let mut config = ServerConfig::default();
+    assert_eq!(config.cache_backend, None);
+    config.apply_env_overrides().unwrap();
+    assert_eq!(config.cache_backend, Some(crate::cache::CacheBackendType::Sharded));
+
+    unsafe {
+        std::env::set_var("KREUZBERG_CACHE_BACKEND", "redis");
+    }
+    let mut config = ServerConfig::default();
+    assert!(config.apply_env_overrides().is_err());
+
+    // Cleanup
+    unsafe {
+        if let Some(orig) = original {
+            std::env::set_var("KREUZBERG_CACHE_BACKEND", orig);
+        } else {
+            std::env::remove_var("KREUZBERG_CACHE_BACKEND");
+        }
+    }
+}
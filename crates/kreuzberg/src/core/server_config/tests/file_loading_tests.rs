@@ -213,6 +213,7 @@ port = 9000
 cors_origins = ["https://app1.com", "https://app2.com", "https://app3.com"]
 max_request_body_bytes = 200000000
 max_multipart_field_bytes = 150000000
+cache_backend = "sqlite"
         "#,
     )
     .unwrap();
@@ -229,6 +230,7 @@ max_multipart_field_bytes = 150000000
     assert_eq!(config.max_multipart_field_bytes, 150_000_000);
     assert_eq!(config.max_request_body_mb(), 191);
     assert_eq!(config.max_multipart_field_mb(), 144);
+    assert_eq!(config.cache_backend, Some(crate::cache::CacheBackendType::Sqlite));
 }
 
 #[test]
@@ -339,3 +341,37 @@ port = 6000
     assert_eq!(config.host, "192.168.1.1");
     assert_eq!(config.port, 6000);
 }
+
+#[test]
+fn test_from_toml_file_with_api_keys() {
+    use crate::core::ApiScope;
+
+    let dir = tempdir().unwrap();
+    let config_path = dir.path().join("server.toml");
+
+    fs::write(
+        &config_path,
+        r#"
+rate_limit_per_minute = 60
+
+[[api_keys]]
+key = "ingest-key"
+name = "ingest"
+
+[[api_keys]]
+key = "ops-key"
+scopes = ["admin"]
+max_concurrent_requests = 2
+        "#,
+    )
+    .unwrap();
+
+    let config = ServerConfig::from_toml_file(&config_path).unwrap();
+    assert!(config.auth_enabled());
+    assert_eq!(config.rate_limit_per_minute, Some(60));
+    assert_eq!(config.api_keys.len(), 2);
+    assert_eq!(config.api_keys[0].name.as_deref(), Some("ingest"));
+    assert_eq!(config.api_keys[0].scopes, vec![ApiScope::Extract]);
+    assert_eq!(config.api_keys[1].scopes, vec![ApiScope::Admin]);
+    assert_eq!(config.api_keys[1].max_concurrent_requests, Some(2));
+}
@@ -0,0 +1,641 @@
+//! Section-aware comparison of two document versions.
+//!
+//! Both documents are reduced to their `DocumentStructure` and split into sections by
+//! heading. Sections are aligned by heading path (ignoring clause numbering, so renumbered
+//! sections still match), then the paragraphs, list items and tables inside each pair of
+//! sections are aligned. Changed blocks are reported as inserted, deleted or modified, with
+//! a word-level diff for modified text and a cell-level diff for modified tables.
+//!
+//! Because the comparison works on extracted structure, the two versions may be in
+//! different formats, e.g. a DOCX draft against the signed PDF.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use kreuzberg::{ExtractionConfig, diff::diff_files_sync};
+//!
+//! # fn main() -> kreuzberg::Result<()> {
+//! let diff = diff_files_sync("contract_v1.docx", "contract_v2.pdf", &ExtractionConfig::default())?;
+//! println!("{}", diff.to_markdown());
+//! # Ok(())
+//! # }
+//! ```
+
+mod render;
+mod sections;
+mod sequence;
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::core::config::ExtractionConfig;
+use crate::types::{DocumentStructure, ExtractionResult, TableGrid};
+use crate::{KreuzbergError, Result};
+use sections::{Block, Section};
+use sequence::{Aligned, Hunk, Words, align, pair_similar};
+
+/// Kind of change to a section, block or table cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    /// Present only in the new document.
+    Inserted,
+    /// Present only in the old document.
+    Deleted,
+    /// Present in both documents with different content.
+    Modified,
+}
+
+/// Differences between two documents, grouped by section.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DocumentDiff {
+    /// Counts of changes.
+    pub summary: DiffSummary,
+    /// Changed sections in reading order; unchanged sections are omitted.
+    pub sections: Vec<SectionDiff>,
+}
+
+impl DocumentDiff {
+    /// Whether the documents have the same content.
+    pub fn is_empty(&self) -> bool {
+        self.sections.is_empty()
+    }
+}
+
+/// Counts of changes in a [`DocumentDiff`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiffSummary {
+    pub sections_inserted: usize,
+    pub sections_deleted: usize,
+    pub sections_modified: usize,
+    pub blocks_inserted: usize,
+    pub blocks_deleted: usize,
+    pub blocks_modified: usize,
+    pub cells_changed: usize,
+}
+
+/// Changes within one section.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SectionDiff {
+    /// Whether the whole section was inserted or deleted, or only its content changed.
+    pub change: ChangeKind,
+    /// Heading path of the section (in the new document, unless deleted); empty for the
+    /// content before the first heading.
+    pub path: Vec<String>,
+    /// Heading in the old document, if the section existed there.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub old_heading: Option<String>,
+    /// Heading in the new document, if the section exists there.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub new_heading: Option<String>,
+    /// Changed blocks in reading order.
+    pub changes: Vec<BlockChange>,
+}
+
+impl SectionDiff {
+    /// Whether the section heading text changed (e.g. renumbered or renamed).
+    pub fn heading_changed(&self) -> bool {
+        self.change == ChangeKind::Modified && self.old_heading != self.new_heading
+    }
+}
+
+/// A changed paragraph, list item, table or other content block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockChange {
+    pub change: ChangeKind,
+    /// Node type, e.g. `paragraph`, `list_item` or `table`.
+    pub node_type: String,
+    /// The block in the old document, unless inserted.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub old: Option<BlockVersion>,
+    /// The block in the new document, unless deleted.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub new: Option<BlockVersion>,
+    /// Word-level diff of modified text blocks.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub segments: Vec<TextSegment>,
+    /// Changed cells of modified tables.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub cells: Vec<CellChange>,
+}
+
+/// One side of a [`BlockChange`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockVersion {
+    /// Node id in that document's `DocumentStructure`.
+    pub node_id: String,
+    /// Block text; tables are rendered row by row with ` | ` between cells.
+    pub text: String,
+    /// Page number, when the format has pages.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub page: Option<u32>,
+}
+
+/// Run of words in a word-level diff.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextSegment {
+    pub op: SegmentOp,
+    pub text: String,
+}
+
+/// Whether a [`TextSegment`] is shared, removed or added.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SegmentOp {
+    Equal,
+    Deleted,
+    Inserted,
+}
+
+/// A changed table cell, addressed by zero-indexed row and column.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CellChange {
+    pub row: u32,
+    pub col: u32,
+    pub change: ChangeKind,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub old: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub new: Option<String>,
+}
+
+/// Compare two document structures.
+pub fn diff_structures(old: &DocumentStructure, new: &DocumentStructure) -> DocumentDiff {
+    let old_sections = sections::sections(old);
+    let new_sections = sections::sections(new);
+    let old_keys: Vec<Vec<String>> = old_sections.iter().map(Section::key).collect();
+    let new_keys: Vec<Vec<String>> = new_sections.iter().map(Section::key).collect();
+
+    let mut diff = DocumentDiff::default();
+    for aligned in align(&old_keys, &new_keys) {
+        match aligned {
+            Aligned::Equal(i, j) => diff.push(diff_section(&old_sections[i], &new_sections[j])),
+            Aligned::Changed(hunk) => {
+                let pairs = pair_similar(
+                    &hunk,
+                    |i| SectionWords::new(&old_sections[i]),
+                    |j| SectionWords::new(&new_sections[j]),
+                    |old, new| Some(old.similarity(new)),
+                );
+                merge_hunk(
+                    &hunk,
+                    &pairs,
+                    |i| Some(whole_section(&old_sections[i], ChangeKind::Deleted)),
+                    |j| Some(whole_section(&new_sections[j], ChangeKind::Inserted)),
+                    |i, j| diff_section(&old_sections[i], &new_sections[j]),
+                )
+                .into_iter()
+                .for_each(|section| diff.push(Some(section)));
+            }
+        }
+    }
+    diff
+}
+
+/// Compare the document structures of two extraction results.
+///
+/// Both results must have been extracted with `include_document_structure` enabled.
+pub fn diff_results(old: &ExtractionResult, new: &ExtractionResult) -> Result<DocumentDiff> {
+    Ok(diff_structures(document_of(old, "old")?, document_of(new, "new")?))
+}
+
+fn document_of<'a>(result: &'a ExtractionResult, which: &str) -> Result<&'a DocumentStructure> {
+    result.document.as_ref().ok_or_else(|| KreuzbergError::Validation {
+        message: format!(
+            "The {} extraction result has no document structure; extract with include_document_structure enabled",
+            which
+        ),
+        source: None,
+    })
+}
+
+/// Extract two documents and compare them.
+///
+/// The documents may be in different formats. `config` is used for both, with
+/// `include_document_structure` enabled.
+pub async fn diff_files(
+    old: impl AsRef<Path>,
+    new: impl AsRef<Path>,
+    config: &ExtractionConfig,
+) -> Result<DocumentDiff> {
+    let config = structure_config(config);
+    let old = crate::extract_file(old, None, &config).await?;
+    let new = crate::extract_file(new, None, &config).await?;
+    diff_results(&old, &new)
+}
+
+/// Synchronous wrapper for [`diff_files`].
+#[cfg(feature = "tokio-runtime")]
+pub fn diff_files_sync(
+    old: impl AsRef<Path>,
+    new: impl AsRef<Path>,
+    config: &ExtractionConfig,
+) -> Result<DocumentDiff> {
+    let config = structure_config(config);
+    let old = crate::extract_file_sync(old, None, &config)?;
+    let new = crate::extract_file_sync(new, None, &config)?;
+    diff_results(&old, &new)
+}
+
+fn structure_config(config: &ExtractionConfig) -> ExtractionConfig {
+    ExtractionConfig {
+        include_document_structure: true,
+        ..config.clone()
+    }
+}
+
+impl DocumentDiff {
+    fn push(&mut self, section: Option<SectionDiff>) {
+        let Some(section) = section else {
+            return;
+        };
+        match section.change {
+            ChangeKind::Inserted => self.summary.sections_inserted += 1,
+            ChangeKind::Deleted => self.summary.sections_deleted += 1,
+            ChangeKind::Modified => self.summary.sections_modified += 1,
+        }
+        for change in &section.changes {
+            match change.change {
+                ChangeKind::Inserted => self.summary.blocks_inserted += 1,
+                ChangeKind::Deleted => self.summary.blocks_deleted += 1,
+                ChangeKind::Modified => self.summary.blocks_modified += 1,
+            }
+            self.summary.cells_changed += change.cells.len();
+        }
+        self.sections.push(section);
+    }
+}
+
+/// Resolve a hunk into results: unpaired deletions first, then the new side in order,
+/// with paired items compared.
+fn merge_hunk<T>(
+    hunk: &Hunk,
+    pairs: &[(usize, usize)],
+    mut deleted: impl FnMut(usize) -> Option<T>,
+    mut inserted: impl FnMut(usize) -> Option<T>,
+    mut modified: impl FnMut(usize, usize) -> Option<T>,
+) -> Vec<T> {
+    let paired_old: BTreeMap<usize, usize> = pairs.iter().copied().collect();
+    let paired_new: BTreeMap<usize, usize> = pairs.iter().map(|&(i, j)| (j, i)).collect();
+
+    let mut results: Vec<T> = hunk
+        .deleted
+        .iter()
+        .filter(|i| !paired_old.contains_key(i))
+        .filter_map(|&i| deleted(i))
+        .collect();
+    for &j in &hunk.inserted {
+        let result = match paired_new.get(&j) {
+            Some(&i) => modified(i, j),
+            None => inserted(j),
+        };
+        results.extend(result);
+    }
+    results
+}
+
+/// Tokenized heading and text of a section, for pairing renamed or moved sections.
+struct SectionWords {
+    heading: Words,
+    text: Words,
+    has_blocks: bool,
+}
+
+impl SectionWords {
+    fn new(section: &Section<'_>) -> Self {
+        Self {
+            heading: Words::new(section.heading().unwrap_or("")),
+            text: Words::new(&section.text()),
+            has_blocks: !section.blocks.is_empty(),
+        }
+    }
+
+    fn similarity(&self, other: &SectionWords) -> f64 {
+        let headings = self.heading.similarity(&other.heading);
+        if !self.has_blocks && !other.has_blocks {
+            headings
+        } else {
+            headings.max(self.text.similarity(&other.text))
+        }
+    }
+}
+
+fn whole_section(section: &Section<'_>, change: ChangeKind) -> SectionDiff {
+    let heading = section.heading().map(str::to_string);
+    let (old_heading, new_heading) = match change {
+        ChangeKind::Deleted => (heading, None),
+        _ => (None, heading),
+    };
+    SectionDiff {
+        change,
+        path: section.path.clone(),
+        old_heading,
+        new_heading,
+        changes: section.blocks.iter().map(|block| whole_block(block, change)).collect(),
+    }
+}
+
+fn diff_section(old: &Section<'_>, new: &Section<'_>) -> Option<SectionDiff> {
+    let old_keys: Vec<_> = old.blocks.iter().map(Block::key).collect();
+    let new_keys: Vec<_> = new.blocks.iter().map(Block::key).collect();
+
+    let mut changes = Vec::new();
+    for aligned in align(&old_keys, &new_keys) {
+        let Aligned::Changed(hunk) = aligned else {
+            continue;
+        };
+        let pairs = pair_similar(
+            &hunk,
+            |i| (old.blocks[i].kind(), Words::new(&old.blocks[i].text)),
+            |j| (new.blocks[j].kind(), Words::new(&new.blocks[j].text)),
+            |(old_kind, old_words), (new_kind, new_words)| {
+                (old_kind == new_kind).then(|| old_words.similarity(new_words))
+            },
+        );
+        changes.extend(merge_hunk(
+            &hunk,
+            &pairs,
+            |i| Some(whole_block(&old.blocks[i], ChangeKind::Deleted)),
+            |j| Some(whole_block(&new.blocks[j], ChangeKind::Inserted)),
+            |i, j| Some(modified_block(&old.blocks[i], &new.blocks[j])),
+        ));
+    }
+
+    let section = SectionDiff {
+        change: ChangeKind::Modified,
+        path: new.path.clone(),
+        old_heading: old.heading().map(str::to_string),
+        new_heading: new.heading().map(str::to_string),
+        changes,
+    };
+    (!section.changes.is_empty() || section.heading_changed()).then_some(section)
+}
+
+fn version(block: &Block<'_>) -> BlockVersion {
+    BlockVersion {
+        node_id: block.node.id.as_ref().to_string(),
+        text: block.text.clone(),
+        page: block.node.page,
+    }
+}
+
+fn whole_block(block: &Block<'_>, change: ChangeKind) -> BlockChange {
+    let (old, new) = match change {
+        ChangeKind::Deleted => (Some(version(block)), None),
+        _ => (None, Some(version(block))),
+    };
+    BlockChange {
+        change,
+        node_type: block.kind().to_string(),
+        old,
+        new,
+        segments: Vec::new(),
+        cells: Vec::new(),
+    }
+}
+
+fn modified_block(old: &Block<'_>, new: &Block<'_>) -> BlockChange {
+    let (segments, cells) = match (old.table, new.table) {
+        (Some(old_grid), Some(new_grid)) => (Vec::new(), diff_cells(old_grid, new_grid)),
+        _ => (word_segments(&old.text, &new.text), Vec::new()),
+    };
+    BlockChange {
+        change: ChangeKind::Modified,
+        node_type: new.kind().to_string(),
+        old: Some(version(old)),
+        new: Some(version(new)),
+        segments,
+        cells,
+    }
+}
+
+fn word_segments(old: &str, new: &str) -> Vec<TextSegment> {
+    let old_words: Vec<&str> = old.split_whitespace().collect();
+    let new_words: Vec<&str> = new.split_whitespace().collect();
+
+    let mut segments: Vec<TextSegment> = Vec::new();
+    for edit in sequence::diff_sequences(&old_words, &new_words) {
+        let (op, word) = match edit {
+            sequence::Edit::Equal(i, _) => (SegmentOp::Equal, old_words[i]),
+            sequence::Edit::Delete(i) => (SegmentOp::Deleted, old_words[i]),
+            sequence::Edit::Insert(j) => (SegmentOp::Inserted, new_words[j]),
+        };
+        match segments.last_mut() {
+            Some(last) if last.op == op => {
+                last.text.push(' ');
+                last.text.push_str(word);
+            }
+            _ => segments.push(TextSegment {
+                op,
+                text: word.to_string(),
+            }),
+        }
+    }
+    segments
+}
+
+fn diff_cells(old: &TableGrid, new: &TableGrid) -> Vec<CellChange> {
+    let cells = |grid: &TableGrid| -> BTreeMap<(u32, u32), String> {
+        grid.cells
+            .iter()
+            .map(|cell| ((cell.row, cell.col), sections::normalize_whitespace(&cell.content)))
+            .collect()
+    };
+    let (old_cells, new_cells) = (cells(old), cells(new));
+
+    let mut positions: Vec<(u32, u32)> = old_cells.keys().chain(new_cells.keys()).copied().collect();
+    positions.sort_unstable();
+    positions.dedup();
+
+    positions
+        .into_iter()
+        .filter_map(|(row, col)| {
+            let old = old_cells.get(&(row, col)).filter(|text| !text.is_empty());
+            let new = new_cells.get(&(row, col)).filter(|text| !text.is_empty());
+            let change = match (old, new) {
+                (Some(old), Some(new)) if old == new => return None,
+                (Some(_), Some(_)) => ChangeKind::Modified,
+                (Some(_), None) => ChangeKind::Deleted,
+                (None, Some(_)) => ChangeKind::Inserted,
+                (None, None) => return None,
+            };
+            Some(CellChange {
+                row,
+                col,
+                change,
+                old: old.cloned(),
+                new: new.cloned(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ContentLayer, DocumentNode, GridCell, NodeContent, NodeId};
+
+    fn document(contents: Vec<NodeContent>) -> DocumentStructure {
+        let mut structure = DocumentStructure::new();
+        for (i, content) in contents.into_iter().enumerate() {
+            structure.nodes.push(DocumentNode {
+                id: NodeId::new(format!("node-{}", i)),
+                content,
+                parent: None,
+                children: Vec::new(),
+                content_layer: ContentLayer::Body,
+                page: Some(1),
+                page_end: None,
+                bbox: None,
+                annotations: Vec::new(),
+            });
+        }
+        structure
+    }
+
+    fn heading(text: &str) -> NodeContent {
+        NodeContent::Heading {
+            level: 1,
+            text: text.to_string(),
+        }
+    }
+
+    fn paragraph(text: &str) -> NodeContent {
+        NodeContent::Paragraph { text: text.to_string() }
+    }
+
+    fn table(rows: &[&[&str]]) -> NodeContent {
+        let cells = rows
+            .iter()
+            .enumerate()
+            .flat_map(|(row, cells)| {
+                cells.iter().enumerate().map(move |(col, content)| GridCell {
+                    content: content.to_string(),
+                    row: row as u32,
+                    col: col as u32,
+                    row_span: 1,
+                    col_span: 1,
+                    is_header: row == 0,
+                    bbox: None,
+                })
+            })
+            .collect();
+        NodeContent::Table {
+            grid: TableGrid {
+                rows: rows.len() as u32,
+                cols: rows.first().map_or(0, |row| row.len() as u32),
+                cells,
+            },
+        }
+    }
+
+    #[test]
+    fn test_identical_documents_have_no_diff() {
+        let doc = document(vec![heading("Scope"), paragraph("Applies to all services.")]);
+        assert!(diff_structures(&doc, &doc).is_empty());
+    }
+
+    #[test]
+    fn test_diff_reports_block_changes_per_section() {
+        let old = document(vec![
+            heading("1. Payment"),
+            paragraph("Invoices are due within 30 days of receipt."),
+            paragraph("Late payments accrue interest."),
+            heading("2. Termination"),
+            paragraph("Either party may terminate with notice."),
+        ]);
+        let new = document(vec![
+            heading("1. Definitions"),
+            paragraph("Services means the work described in the order."),
+            heading("2. Payment"),
+            paragraph("Invoices are due within 45 days of receipt."),
+            paragraph("Late payments accrue interest."),
+            paragraph("Disputed amounts may be withheld."),
+            heading("3. Termination"),
+            paragraph("Either party may terminate with notice."),
+        ]);
+        let diff = diff_structures(&old, &new);
+
+        assert_eq!(diff.summary.sections_inserted, 1);
+        assert_eq!(
+            diff.summary.sections_modified, 2,
+            "renumbered sections report a heading change"
+        );
+        assert_eq!(diff.summary.blocks_modified, 1);
+        assert_eq!(diff.summary.blocks_inserted, 2);
+
+        let definitions = &diff.sections[0];
+        assert_eq!(definitions.change, ChangeKind::Inserted);
+        assert_eq!(definitions.new_heading.as_deref(), Some("1. Definitions"));
+
+        let payment = &diff.sections[1];
+        assert!(payment.heading_changed());
+        assert_eq!(payment.changes.len(), 2);
+        let modified = &payment.changes[0];
+        assert_eq!(modified.change, ChangeKind::Modified);
+        assert!(modified.segments.contains(&TextSegment {
+            op: SegmentOp::Deleted,
+            text: "30".to_string()
+        }));
+        assert!(modified.segments.contains(&TextSegment {
+            op: SegmentOp::Inserted,
+            text: "45".to_string()
+        }));
+        assert_eq!(payment.changes[1].change, ChangeKind::Inserted);
+
+        let termination = &diff.sections[2];
+        assert!(termination.changes.is_empty());
+        assert!(termination.heading_changed());
+    }
+
+    #[test]
+    fn test_diff_reports_table_cells() {
+        let old = document(vec![
+            heading("Fees"),
+            table(&[&["Item", "Price"], &["Support", "100"], &["Hosting", "50"]]),
+        ]);
+        let new = document(vec![
+            heading("Fees"),
+            table(&[&["Item", "Price"], &["Support", "120"], &["Hosting", "50"]]),
+        ]);
+        let diff = diff_structures(&old, &new);
+
+        let change = &diff.sections[0].changes[0];
+        assert_eq!(change.node_type, "table");
+        assert_eq!(
+            change.cells,
+            vec![CellChange {
+                row: 1,
+                col: 1,
+                change: ChangeKind::Modified,
+                old: Some("100".to_string()),
+                new: Some("120".to_string()),
+            }]
+        );
+        assert_eq!(diff.summary.cells_changed, 1);
+    }
+
+    #[test]
+    fn test_diff_reports_deleted_section() {
+        let old = document(vec![
+            heading("Scope"),
+            paragraph("All services."),
+            heading("Warranty"),
+            paragraph("None."),
+        ]);
+        let new = document(vec![heading("Scope"), paragraph("All services.")]);
+        let diff = diff_structures(&old, &new);
+
+        assert_eq!(diff.sections.len(), 1);
+        assert_eq!(diff.sections[0].change, ChangeKind::Deleted);
+        assert_eq!(diff.sections[0].old_heading.as_deref(), Some("Warranty"));
+        assert_eq!(diff.summary.blocks_deleted, 1);
+    }
+
+    #[test]
+    fn test_diff_results_requires_structure() {
+        let result = ExtractionResult::default();
+        assert!(diff_results(&result, &result).is_err());
+    }
+}
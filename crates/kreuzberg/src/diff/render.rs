@@ -0,0 +1,274 @@
+//! Human-readable renderings of a [`DocumentDiff`].
+
+use std::fmt::Write;
+
+use super::{BlockChange, ChangeKind, DocumentDiff, SectionDiff, SegmentOp};
+
+impl DocumentDiff {
+    /// Render as a unified diff: one `@@` hunk per changed section with `-`/`+` lines
+    /// per block. Modified tables list only their changed cells.
+    pub fn to_unified(&self, old_label: &str, new_label: &str) -> String {
+        let mut out = format!("--- {}\n+++ {}\n", old_label, new_label);
+        for section in &self.sections {
+            let _ = writeln!(out, "@@ {} @@", section_title(section));
+            if section.heading_changed() || section.change != ChangeKind::Modified {
+                if let Some(heading) = &section.old_heading {
+                    let _ = writeln!(out, "-# {}", heading);
+                }
+                if let Some(heading) = &section.new_heading {
+                    let _ = writeln!(out, "+# {}", heading);
+                }
+            }
+            for change in &section.changes {
+                unified_block(&mut out, change);
+            }
+        }
+        out
+    }
+
+    /// Render as a Markdown report: a summary line, then one heading per changed section
+    /// with a bullet per changed block. Word changes are shown as ~~deleted~~ **inserted**.
+    pub fn to_markdown(&self) -> String {
+        let summary = &self.summary;
+        let mut out = String::from("# Document changes\n\n");
+        if self.is_empty() {
+            out.push_str("No changes.\n");
+            return out;
+        }
+        let _ = writeln!(
+            out,
+            "{} sections inserted, {} deleted, {} modified; {} blocks inserted, {} deleted, {} modified; {} table cells changed.",
+            summary.sections_inserted,
+            summary.sections_deleted,
+            summary.sections_modified,
+            summary.blocks_inserted,
+            summary.blocks_deleted,
+            summary.blocks_modified,
+            summary.cells_changed
+        );
+
+        for section in &self.sections {
+            let _ = writeln!(out, "\n## {}\n", section_title(section));
+            match section.change {
+                ChangeKind::Inserted => out.push_str("*Section inserted.*\n\n"),
+                ChangeKind::Deleted => out.push_str("*Section deleted.*\n\n"),
+                ChangeKind::Modified if section.heading_changed() => {
+                    let _ = writeln!(
+                        out,
+                        "*Heading changed from \"{}\" to \"{}\".*\n",
+                        section.old_heading.as_deref().unwrap_or(""),
+                        section.new_heading.as_deref().unwrap_or("")
+                    );
+                }
+                ChangeKind::Modified => {}
+            }
+            for change in &section.changes {
+                markdown_block(&mut out, change);
+            }
+        }
+        out
+    }
+}
+
+fn section_title(section: &SectionDiff) -> String {
+    if section.path.is_empty() {
+        "(before first heading)".to_string()
+    } else {
+        section.path.join(" › ")
+    }
+}
+
+fn unified_block(out: &mut String, change: &BlockChange) {
+    if !change.cells.is_empty() {
+        for cell in &change.cells {
+            if let Some(old) = &cell.old {
+                let _ = writeln!(
+                    out,
+                    "-[{} r{}c{}] {}",
+                    change.node_type,
+                    cell.row + 1,
+                    cell.col + 1,
+                    old
+                );
+            }
+            if let Some(new) = &cell.new {
+                let _ = writeln!(
+                    out,
+                    "+[{} r{}c{}] {}",
+                    change.node_type,
+                    cell.row + 1,
+                    cell.col + 1,
+                    new
+                );
+            }
+        }
+        return;
+    }
+    if let Some(old) = &change.old {
+        for line in old.text.lines() {
+            let _ = writeln!(out, "-{}", line);
+        }
+    }
+    if let Some(new) = &change.new {
+        for line in new.text.lines() {
+            let _ = writeln!(out, "+{}", line);
+        }
+    }
+}
+
+fn markdown_block(out: &mut String, change: &BlockChange) {
+    let label = match change.change {
+        ChangeKind::Inserted => "Inserted",
+        ChangeKind::Deleted => "Deleted",
+        ChangeKind::Modified => "Modified",
+    };
+    let page = change
+        .new
+        .as_ref()
+        .or(change.old.as_ref())
+        .and_then(|version| version.page)
+        .map(|page| format!(" (page {})", page))
+        .unwrap_or_default();
+    let node_type = change.node_type.replace('_', " ");
+    let _ = write!(out, "- **{}** {}{}: ", label, node_type, page);
+
+    if !change.cells.is_empty() {
+        out.push('\n');
+        for cell in &change.cells {
+            let _ = writeln!(
+                out,
+                "  - row {}, column {}: {}",
+                cell.row + 1,
+                cell.col + 1,
+                inline_change(cell.old.as_deref(), cell.new.as_deref())
+            );
+        }
+    } else if !change.segments.is_empty() {
+        let rendered: Vec<String> = change
+            .segments
+            .iter()
+            .map(|segment| match segment.op {
+                SegmentOp::Equal => escape(&segment.text),
+                SegmentOp::Deleted => format!("~~{}~~", escape(&segment.text)),
+                SegmentOp::Inserted => format!("**{}**", escape(&segment.text)),
+            })
+            .collect();
+        let _ = writeln!(out, "{}", rendered.join(" "));
+    } else {
+        let old = change.old.as_ref().map(|version| one_line(&version.text));
+        let new = change.new.as_ref().map(|version| one_line(&version.text));
+        let _ = writeln!(out, "{}", inline_change(old.as_deref(), new.as_deref()));
+    }
+}
+
+fn inline_change(old: Option<&str>, new: Option<&str>) -> String {
+    match (old, new) {
+        (Some(old), Some(new)) => format!("~~{}~~ → **{}**", escape(old), escape(new)),
+        (Some(old), None) => format!("~~{}~~", escape(old)),
+        (None, Some(new)) => escape(new),
+        (None, None) => String::new(),
+    }
+}
+
+fn one_line(text: &str) -> String {
+    text.lines().map(str::trim).collect::<Vec<_>>().join(" / ")
+}
+
+/// Escape characters that would turn block text into Markdown emphasis.
+fn escape(text: &str) -> String {
+    text.replace('*', "\\*").replace('~', "\\~")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{BlockVersion, CellChange, DiffSummary, TextSegment};
+    use super::*;
+
+    fn sample() -> DocumentDiff {
+        DocumentDiff {
+            summary: DiffSummary {
+                sections_modified: 1,
+                blocks_modified: 2,
+                cells_changed: 1,
+                ..Default::default()
+            },
+            sections: vec![SectionDiff {
+                change: ChangeKind::Modified,
+                path: vec!["Payment".to_string()],
+                old_heading: Some("4. Payment".to_string()),
+                new_heading: Some("5. Payment".to_string()),
+                changes: vec![
+                    BlockChange {
+                        change: ChangeKind::Modified,
+                        node_type: "paragraph".to_string(),
+                        old: Some(BlockVersion {
+                            node_id: "a".to_string(),
+                            text: "Due in 30 days".to_string(),
+                            page: Some(2),
+                        }),
+                        new: Some(BlockVersion {
+                            node_id: "b".to_string(),
+                            text: "Due in 45 days".to_string(),
+                            page: Some(3),
+                        }),
+                        segments: vec![
+                            TextSegment {
+                                op: SegmentOp::Equal,
+                                text: "Due in".to_string(),
+                            },
+                            TextSegment {
+                                op: SegmentOp::Deleted,
+                                text: "30".to_string(),
+                            },
+                            TextSegment {
+                                op: SegmentOp::Inserted,
+                                text: "45".to_string(),
+                            },
+                            TextSegment {
+                                op: SegmentOp::Equal,
+                                text: "days".to_string(),
+                            },
+                        ],
+                        cells: Vec::new(),
+                    },
+                    BlockChange {
+                        change: ChangeKind::Modified,
+                        node_type: "table".to_string(),
+                        old: None,
+                        new: None,
+                        segments: Vec::new(),
+                        cells: vec![CellChange {
+                            row: 1,
+                            col: 1,
+                            change: ChangeKind::Modified,
+                            old: Some("100".to_string()),
+                            new: Some("120".to_string()),
+                        }],
+                    },
+                ],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_to_unified() {
+        let unified = sample().to_unified("v1.docx", "v2.pdf");
+        assert_eq!(
+            unified,
+            "--- v1.docx\n+++ v2.pdf\n@@ Payment @@\n-# 4. Payment\n+# 5. Payment\n-Due in 30 days\n+Due in 45 days\n-[table r2c2] 100\n+[table r2c2] 120\n"
+        );
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let markdown = sample().to_markdown();
+        assert!(markdown.contains("## Payment\n"));
+        assert!(markdown.contains("*Heading changed from \"4. Payment\" to \"5. Payment\".*"));
+        assert!(markdown.contains("- **Modified** paragraph (page 3): Due in ~~30~~ **45** days\n"));
+        assert!(markdown.contains("  - row 2, column 2: ~~100~~ → **120**\n"));
+        assert_eq!(
+            DocumentDiff::default().to_markdown(),
+            "# Document changes\n\nNo changes.\n"
+        );
+    }
+}
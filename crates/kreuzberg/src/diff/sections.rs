@@ -0,0 +1,308 @@
+//! Flattening of a `DocumentStructure` into heading-delimited sections of content blocks.
+
+use crate::types::{DocumentNode, DocumentStructure, NodeContent, NodeIndex, TableGrid};
+
+/// A leaf content node: paragraph, list item, table, code block and so on.
+#[derive(Debug)]
+pub(super) struct Block<'a> {
+    pub node: &'a DocumentNode,
+    /// Display text; tables are rendered row by row with ` | ` between cells
+    pub text: String,
+    pub table: Option<&'a TableGrid>,
+}
+
+impl Block<'_> {
+    pub fn kind(&self) -> &'static str {
+        self.node.content.node_type_str()
+    }
+
+    /// Comparison key: node type plus whitespace-normalized text.
+    pub fn key(&self) -> (&'static str, String) {
+        (self.kind(), normalize_whitespace(&self.text))
+    }
+}
+
+/// The blocks between one heading and the next.
+#[derive(Debug)]
+pub(super) struct Section<'a> {
+    /// Heading texts from the outermost enclosing section down to this one; empty for
+    /// content before the first heading.
+    pub path: Vec<String>,
+    pub blocks: Vec<Block<'a>>,
+}
+
+impl Section<'_> {
+    pub fn heading(&self) -> Option<&str> {
+        self.path.last().map(String::as_str)
+    }
+
+    /// Alignment key: the heading path with numbering and case differences removed, so
+    /// renumbered clauses ("4. Payment" vs "5. Payment") still match.
+    pub fn key(&self) -> Vec<String> {
+        self.path.iter().map(|heading| normalize_heading(heading)).collect()
+    }
+
+    pub fn text(&self) -> String {
+        self.blocks
+            .iter()
+            .map(|block| block.text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Split the body of `structure` into sections in reading order.
+///
+/// Headings and section groups open a section nested below all open sections of a lower
+/// level. Headers, footers and other furniture are ignored.
+pub(super) fn sections(structure: &DocumentStructure) -> Vec<Section<'_>> {
+    let mut builder = Builder {
+        structure,
+        stack: Vec::new(),
+        sections: vec![Section {
+            path: Vec::new(),
+            blocks: Vec::new(),
+        }],
+    };
+    for (index, _) in structure.body_roots() {
+        builder.visit(index);
+    }
+
+    let mut sections = builder.sections;
+    if sections.len() > 1 && sections[0].blocks.is_empty() {
+        sections.remove(0);
+    }
+    sections
+}
+
+struct Builder<'a> {
+    structure: &'a DocumentStructure,
+    /// Open headings as (level, text)
+    stack: Vec<(u8, String)>,
+    sections: Vec<Section<'a>>,
+}
+
+impl<'a> Builder<'a> {
+    fn visit(&mut self, index: NodeIndex) {
+        let Some(node) = self.structure.get(index) else {
+            return;
+        };
+
+        match &node.content {
+            NodeContent::Group {
+                heading_level,
+                heading_text: Some(text),
+                ..
+            } => self.open_section(heading_level.unwrap_or(1), text),
+            NodeContent::Heading { level, text } => {
+                if !self.is_group_heading(node, text) {
+                    self.open_section(*level, text);
+                }
+            }
+            NodeContent::Table { grid } => self.push_block(node, table_text(grid), Some(grid)),
+            NodeContent::Image { description, .. } => {
+                if let Some(description) = description {
+                    self.push_block(node, description.clone(), None);
+                }
+            }
+            content => {
+                if let Some(text) = content.text() {
+                    self.push_block(node, text.to_string(), None);
+                }
+            }
+        }
+
+        for child in &node.children {
+            self.visit(*child);
+        }
+    }
+
+    /// Whether `node` is the heading of its parent group, which already opened the section.
+    fn is_group_heading(&self, node: &DocumentNode, text: &str) -> bool {
+        node.parent
+            .and_then(|parent| self.structure.get(parent))
+            .is_some_and(|parent| {
+                matches!(&parent.content, NodeContent::Group { heading_text: Some(heading), .. } if heading == text)
+            })
+    }
+
+    fn open_section(&mut self, level: u8, text: &str) {
+        while self.stack.last().is_some_and(|(open, _)| *open >= level) {
+            self.stack.pop();
+        }
+        self.stack.push((level, text.trim().to_string()));
+        self.sections.push(Section {
+            path: self.stack.iter().map(|(_, text)| text.clone()).collect(),
+            blocks: Vec::new(),
+        });
+    }
+
+    fn push_block(&mut self, node: &'a DocumentNode, text: String, table: Option<&'a TableGrid>) {
+        if text.trim().is_empty() && table.is_none() {
+            return;
+        }
+        if let Some(section) = self.sections.last_mut() {
+            section.blocks.push(Block { node, text, table });
+        }
+    }
+}
+
+fn table_text(grid: &TableGrid) -> String {
+    let mut cells: Vec<_> = grid.cells.iter().collect();
+    cells.sort_by_key(|cell| (cell.row, cell.col));
+    let mut rows: Vec<Vec<&str>> = Vec::new();
+    for cell in cells {
+        let row = cell.row as usize;
+        if rows.len() <= row {
+            rows.resize_with(row + 1, Vec::new);
+        }
+        rows[row].push(cell.content.trim());
+    }
+    rows.iter().map(|row| row.join(" | ")).collect::<Vec<_>>().join("\n")
+}
+
+pub(super) fn normalize_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Lowercase `heading` and drop leading clause numbering such as "4.", "3.1.2", "§ 5" or "(b)".
+fn normalize_heading(heading: &str) -> String {
+    let normalized = normalize_whitespace(heading).to_lowercase();
+    let mut rest = normalized.as_str();
+    while let Some((token, tail)) = rest.split_once(' ') {
+        let numeric = token.chars().all(|c| c.is_ascii_digit() || ".()§".contains(c));
+        let lettered = token.len() <= 6
+            && token.starts_with('(')
+            && token.ends_with(')')
+            && token[1..token.len() - 1].chars().all(|c| c.is_ascii_alphanumeric());
+        if !numeric && !lettered {
+            break;
+        }
+        rest = tail;
+    }
+    rest.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ContentLayer, GridCell, NodeId};
+
+    fn structure(contents: Vec<(NodeContent, Option<u32>)>) -> DocumentStructure {
+        let mut structure = DocumentStructure::new();
+        for (i, (content, parent)) in contents.into_iter().enumerate() {
+            structure.nodes.push(DocumentNode {
+                id: NodeId::new(format!("node-{}", i)),
+                content,
+                parent: parent.map(NodeIndex),
+                children: Vec::new(),
+                content_layer: ContentLayer::Body,
+                page: None,
+                page_end: None,
+                bbox: None,
+                annotations: Vec::new(),
+            });
+            if let Some(parent) = parent {
+                structure.nodes[parent as usize].children.push(NodeIndex(i as u32));
+            }
+        }
+        structure
+    }
+
+    fn paragraph(text: &str) -> NodeContent {
+        NodeContent::Paragraph { text: text.to_string() }
+    }
+
+    fn heading(level: u8, text: &str) -> NodeContent {
+        NodeContent::Heading {
+            level,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_sections_follow_heading_levels() {
+        let structure = structure(vec![
+            (paragraph("Preamble"), None),
+            (heading(1, "1. Terms"), None),
+            (paragraph("Terms body"), None),
+            (heading(2, "1.1 Payment"), None),
+            (paragraph("Pay in 30 days"), None),
+            (heading(1, "2. Termination"), None),
+            (paragraph("Either party may terminate"), None),
+        ]);
+        let sections = sections(&structure);
+
+        let paths: Vec<Vec<String>> = sections.iter().map(|section| section.path.clone()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                vec![],
+                vec!["1. Terms".to_string()],
+                vec!["1. Terms".to_string(), "1.1 Payment".to_string()],
+                vec!["2. Termination".to_string()],
+            ]
+        );
+        assert_eq!(sections[2].key(), vec!["terms".to_string(), "payment".to_string()]);
+        assert_eq!(sections[2].blocks[0].text, "Pay in 30 days");
+    }
+
+    #[test]
+    fn test_sections_use_group_headings_once() {
+        let structure = structure(vec![
+            (
+                NodeContent::Group {
+                    label: None,
+                    heading_level: Some(1),
+                    heading_text: Some("Scope".to_string()),
+                },
+                None,
+            ),
+            (heading(1, "Scope"), Some(0)),
+            (
+                NodeContent::Table {
+                    grid: TableGrid {
+                        rows: 1,
+                        cols: 2,
+                        cells: vec![
+                            GridCell {
+                                content: "b".to_string(),
+                                row: 0,
+                                col: 1,
+                                row_span: 1,
+                                col_span: 1,
+                                is_header: false,
+                                bbox: None,
+                            },
+                            GridCell {
+                                content: "a".to_string(),
+                                row: 0,
+                                col: 0,
+                                row_span: 1,
+                                col_span: 1,
+                                is_header: false,
+                                bbox: None,
+                            },
+                        ],
+                    },
+                },
+                Some(0),
+            ),
+        ]);
+        let sections = sections(&structure);
+
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].heading(), Some("Scope"));
+        assert_eq!(sections[0].blocks[0].text, "a | b");
+    }
+
+    #[test]
+    fn test_normalize_heading_strips_numbering() {
+        assert_eq!(normalize_heading("4.  Payment Terms"), "payment terms");
+        assert_eq!(normalize_heading("3.1.2 Fees"), "fees");
+        assert_eq!(normalize_heading("(b) Notices"), "notices");
+        assert_eq!(normalize_heading("§ 5 Liability"), "liability");
+        assert_eq!(normalize_heading("Introduction"), "introduction");
+        assert_eq!(normalize_heading("42"), "42");
+    }
+}
@@ -0,0 +1,365 @@
+//! Sequence alignment and text similarity used by the document diff.
+
+use std::collections::HashMap;
+
+/// Edit distance beyond which alignment gives up and treats the inputs as entirely
+/// replaced. The backtrack keeps one snapshot of O(D) diagonals per round, O(D²) in
+/// total, so this bounds memory to about 8 MiB and time to O((N + M) · D) for unrelated
+/// inputs; similarity pairing still matches up modified items afterwards.
+const MAX_EDIT_DISTANCE: usize = 1024;
+
+/// Similarity comparisons made per hunk when pairing modified items. Beyond it, each
+/// deleted item is only compared with the inserted items closest to its position.
+const MAX_PAIR_COMPARISONS: usize = 100_000;
+
+/// One step of an edit script between sequences `a` and `b`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Edit {
+    /// `a[i] == b[j]`
+    Equal(usize, usize),
+    /// `a[i]` was removed
+    Delete(usize),
+    /// `b[j]` was added
+    Insert(usize),
+}
+
+/// Shortest edit script turning `a` into `b` (Myers' O(ND) algorithm).
+pub(super) fn diff_sequences<T: PartialEq>(a: &[T], b: &[T]) -> Vec<Edit> {
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+
+    let mut edits: Vec<Edit> = (0..prefix).map(|i| Edit::Equal(i, i)).collect();
+    let middle_a = &a[prefix..a.len() - suffix];
+    let middle_b = &b[prefix..b.len() - suffix];
+    edits.extend(myers(middle_a, middle_b).into_iter().map(|edit| match edit {
+        Edit::Equal(i, j) => Edit::Equal(i + prefix, j + prefix),
+        Edit::Delete(i) => Edit::Delete(i + prefix),
+        Edit::Insert(j) => Edit::Insert(j + prefix),
+    }));
+    edits.extend((0..suffix).map(|offset| Edit::Equal(a.len() - suffix + offset, b.len() - suffix + offset)));
+    edits
+}
+
+fn myers<T: PartialEq>(a: &[T], b: &[T]) -> Vec<Edit> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let max = (n + m) as usize;
+    if max == 0 {
+        return Vec::new();
+    }
+    let limit = max.min(MAX_EDIT_DISTANCE) as isize;
+
+    // v[k + offset] is the furthest x reached on diagonal k; one snapshot per round is
+    // kept (only the diagonals that round can read) for the backtrack.
+    let offset = limit + 1;
+    let mut v = vec![0isize; 2 * offset as usize + 1];
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+
+    let mut found = false;
+    'rounds: for d in 0..=limit {
+        trace.push(v[(offset - d - 1) as usize..=(offset + d + 1) as usize].to_vec());
+        for k in (-d..=d).step_by(2) {
+            let index = (k + offset) as usize;
+            let mut x = if k == -d || (k != d && v[index - 1] < v[index + 1]) {
+                v[index + 1]
+            } else {
+                v[index - 1] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[index] = x;
+            if x >= n && y >= m {
+                found = true;
+                break 'rounds;
+            }
+        }
+    }
+
+    if !found {
+        return (0..a.len())
+            .map(Edit::Delete)
+            .chain((0..b.len()).map(Edit::Insert))
+            .collect();
+    }
+
+    let mut edits = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for (d, snapshot) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let at = |k: isize| snapshot[(k + d + 1) as usize];
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && at(k - 1) < at(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = at(prev_k);
+        let prev_y = prev_x - prev_k;
+
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            edits.push(Edit::Equal(x as usize, y as usize));
+        }
+        if d > 0 {
+            if x == prev_x {
+                y -= 1;
+                edits.push(Edit::Insert(y as usize));
+            } else {
+                x -= 1;
+                edits.push(Edit::Delete(x as usize));
+            }
+        }
+    }
+    edits.reverse();
+    edits
+}
+
+/// A maximal run of edits without equal items: the `a` indices removed and the `b`
+/// indices added between two aligned items.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(super) struct Hunk {
+    pub deleted: Vec<usize>,
+    pub inserted: Vec<usize>,
+}
+
+/// Alignment of two sequences as equal pairs interleaved with hunks, in order.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum Aligned {
+    Equal(usize, usize),
+    Changed(Hunk),
+}
+
+pub(super) fn align<T: PartialEq>(a: &[T], b: &[T]) -> Vec<Aligned> {
+    let mut aligned = Vec::new();
+    let mut hunk = Hunk::default();
+    for edit in diff_sequences(a, b) {
+        match edit {
+            Edit::Equal(i, j) => {
+                if !hunk.deleted.is_empty() || !hunk.inserted.is_empty() {
+                    aligned.push(Aligned::Changed(std::mem::take(&mut hunk)));
+                }
+                aligned.push(Aligned::Equal(i, j));
+            }
+            Edit::Delete(i) => hunk.deleted.push(i),
+            Edit::Insert(j) => hunk.inserted.push(j),
+        }
+    }
+    if !hunk.deleted.is_empty() || !hunk.inserted.is_empty() {
+        aligned.push(Aligned::Changed(hunk));
+    }
+    aligned
+}
+
+/// Pair deleted and inserted items of a hunk that are similar enough to count as one
+/// modified item. Pairs keep their relative order.
+///
+/// `old` and `new` prepare an item for comparison (e.g. tokenize it) and run once per
+/// item; `similarity` returns `None` for items that can never be paired. At most
+/// [`MAX_PAIR_COMPARISONS`] comparisons are made.
+pub(super) fn pair_similar<A, B>(
+    hunk: &Hunk,
+    old: impl Fn(usize) -> A,
+    new: impl Fn(usize) -> B,
+    similarity: impl Fn(&A, &B) -> Option<f64>,
+) -> Vec<(usize, usize)> {
+    const MIN_SIMILARITY: f64 = 0.5;
+
+    if hunk.deleted.is_empty() || hunk.inserted.is_empty() {
+        return Vec::new();
+    }
+    let old: Vec<A> = hunk.deleted.iter().map(|&i| old(i)).collect();
+    let new: Vec<B> = hunk.inserted.iter().map(|&j| new(j)).collect();
+    let window = (MAX_PAIR_COMPARISONS / old.len()).max(1);
+
+    let mut pairs = Vec::new();
+    let mut next_insert = 0;
+    for (i, old_item) in hunk.deleted.iter().zip(&old) {
+        let candidates = next_insert..(next_insert + window).min(new.len());
+        let best = candidates
+            .filter_map(|position| similarity(old_item, &new[position]).map(|score| (position, score)))
+            .filter(|(_, score)| *score >= MIN_SIMILARITY)
+            .max_by(|a, b| a.1.total_cmp(&b.1).then(b.0.cmp(&a.0)));
+        if let Some((position, _)) = best {
+            pairs.push((*i, hunk.inserted[position]));
+            next_insert = position + 1;
+        }
+    }
+    pairs
+}
+
+/// Lowercased word counts of a text, tokenized once and compared many times.
+#[derive(Debug, Default)]
+pub(super) struct Words {
+    counts: HashMap<String, usize>,
+    len: usize,
+}
+
+impl Words {
+    pub(super) fn new(text: &str) -> Self {
+        let mut words = Self::default();
+        for word in text.split_whitespace() {
+            let word = word.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase();
+            if !word.is_empty() {
+                *words.counts.entry(word).or_default() += 1;
+                words.len += 1;
+            }
+        }
+        words
+    }
+
+    /// Dice coefficient of the two word multisets, from 0.0 (disjoint) to 1.0.
+    pub(super) fn similarity(&self, other: &Words) -> f64 {
+        if self.len == 0 && other.len == 0 {
+            return 1.0;
+        }
+        let (small, large) = if self.counts.len() <= other.counts.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let common: usize = small
+            .counts
+            .iter()
+            .filter_map(|(word, &count)| large.counts.get(word).map(|&other| count.min(other)))
+            .sum();
+        2.0 * common as f64 / (self.len + other.len) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(a: &[char], b: &[char], edits: &[Edit]) -> Vec<char> {
+        let mut out = Vec::new();
+        for edit in edits {
+            match *edit {
+                Edit::Equal(i, j) => {
+                    assert_eq!(a[i], b[j]);
+                    out.push(a[i]);
+                }
+                Edit::Insert(j) => out.push(b[j]),
+                Edit::Delete(_) => {}
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_diff_sequences_produces_minimal_script() {
+        let a: Vec<char> = "ABCABBA".chars().collect();
+        let b: Vec<char> = "CBABAC".chars().collect();
+        let edits = diff_sequences(&a, &b);
+
+        assert_eq!(apply(&a, &b, &edits), b);
+        let changes = edits.iter().filter(|edit| !matches!(edit, Edit::Equal(..))).count();
+        assert_eq!(changes, 5);
+
+        assert!(diff_sequences::<char>(&[], &[]).is_empty());
+        assert_eq!(diff_sequences(&['a'], &[]), vec![Edit::Delete(0)]);
+    }
+
+    #[test]
+    fn test_diff_sequences_reconstructs_generated_inputs() {
+        let mut state = 0x2545_f491_u32;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state
+        };
+        for _ in 0..200 {
+            let a: Vec<char> = (0..next() % 20).map(|_| (b'a' + (next() % 4) as u8) as char).collect();
+            let b: Vec<char> = (0..next() % 20).map(|_| (b'a' + (next() % 4) as u8) as char).collect();
+            let edits = diff_sequences(&a, &b);
+
+            assert_eq!(apply(&a, &b, &edits), b);
+            let kept: Vec<char> = edits
+                .iter()
+                .filter_map(|edit| match *edit {
+                    Edit::Equal(i, _) | Edit::Delete(i) => Some(a[i]),
+                    Edit::Insert(_) => None,
+                })
+                .collect();
+            assert_eq!(kept, a);
+        }
+    }
+
+    #[test]
+    fn test_align_groups_hunks_and_pairs_similar_items() {
+        let a = ["intro", "old clause", "end"];
+        let b = ["intro", "new clause", "extra", "end"];
+        let aligned = align(&a, &b);
+
+        assert_eq!(aligned.len(), 3);
+        let Aligned::Changed(hunk) = &aligned[1] else {
+            panic!("expected a hunk, got {:?}", aligned[1]);
+        };
+        assert_eq!(hunk.deleted, vec![1]);
+        assert_eq!(hunk.inserted, vec![1, 2]);
+
+        let pairs = pair_similar(
+            hunk,
+            |i| Words::new(a[i]),
+            |j| Words::new(b[j]),
+            |a, b| Some(a.similarity(b)),
+        );
+        assert_eq!(pairs, vec![(1, 1)]);
+    }
+
+    #[test]
+    fn test_pair_similar_tokenizes_once_and_caps_comparisons() {
+        let count = 1_000;
+        let hunk = Hunk {
+            deleted: (0..count).collect(),
+            inserted: (0..count).collect(),
+        };
+        let tokenized = std::cell::Cell::new(0);
+        let compared = std::cell::Cell::new(0);
+        let pairs = pair_similar(
+            &hunk,
+            |i| {
+                tokenized.set(tokenized.get() + 1);
+                Words::new(&format!("clause {}", i))
+            },
+            |j| {
+                tokenized.set(tokenized.get() + 1);
+                Words::new(&format!("clause {}", j))
+            },
+            |a, b| {
+                compared.set(compared.get() + 1);
+                Some(a.similarity(b))
+            },
+        );
+
+        assert_eq!(tokenized.get(), 2 * count);
+        assert!(compared.get() <= MAX_PAIR_COMPARISONS);
+        assert_eq!(pairs.len(), count);
+        assert!(pairs.iter().all(|&(i, j)| i == j));
+    }
+
+    #[test]
+    fn test_myers_gives_up_beyond_max_edit_distance() {
+        let a: Vec<usize> = (0..MAX_EDIT_DISTANCE).collect();
+        let b: Vec<usize> = (MAX_EDIT_DISTANCE..2 * MAX_EDIT_DISTANCE + 1).collect();
+        let edits = diff_sequences(&a, &b);
+        assert_eq!(edits.len(), a.len() + b.len());
+        assert!(edits.iter().all(|edit| !matches!(edit, Edit::Equal(..))));
+    }
+
+    #[test]
+    fn test_similarity() {
+        let similarity = |a: &str, b: &str| Words::new(a).similarity(&Words::new(b));
+        assert_eq!(similarity("The fee is 5%.", "the FEE is 5%"), 1.0);
+        assert_eq!(similarity("alpha beta", "gamma delta"), 0.0);
+        assert!((similarity("a b c d", "a b c e") - 0.75).abs() < 1e-9);
+    }
+}
@@ -170,6 +170,17 @@ fn onnx_runtime_install_message() -> String {
     }
 }
 
+/// Directory model files are downloaded to, defaulting to `./.kreuzberg/embeddings`.
+#[cfg(feature = "embeddings")]
+fn model_cache_dir(cache_dir: Option<std::path::PathBuf>) -> std::path::PathBuf {
+    cache_dir.unwrap_or_else(|| {
+        let mut path = std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
+        path.push(".kreuzberg");
+        path.push("embeddings");
+        path
+    })
+}
+
 /// Get or initialize a text embedding model from cache.
 ///
 /// This function ensures models are initialized only once and reused across
@@ -180,12 +191,7 @@ pub fn get_or_init_model(
     model: EmbeddingModel,
     cache_dir: Option<std::path::PathBuf>,
 ) -> crate::Result<CachedEmbedding> {
-    let cache_directory = cache_dir.unwrap_or_else(|| {
-        let mut path = std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
-        path.push(".kreuzberg");
-        path.push("embeddings");
-        path
-    });
+    let cache_directory = model_cache_dir(cache_dir);
 
     let model_key = format!("{:?}_{}", model, cache_directory.display());
 
@@ -394,42 +400,124 @@ pub fn generate_embeddings_for_chunks(
     chunks: &mut [crate::types::Chunk],
     config: &crate::core::config::EmbeddingConfig,
 ) -> crate::Result<()> {
-    if chunks.is_empty() {
-        return Ok(());
-    }
+    embed_chunks(chunks, config, true, None)
+}
 
-    let fastembed_model = match &config.model {
+/// Resolve the fastembed model of an embedding model type.
+#[cfg(feature = "embeddings")]
+fn resolve_model(model: &crate::core::config::EmbeddingModelType) -> crate::Result<EmbeddingModel> {
+    match model {
         crate::core::config::EmbeddingModelType::Preset { name } => {
             let preset = get_preset(name).ok_or_else(|| crate::KreuzbergError::Plugin {
                 message: format!("Unknown embedding preset: {}", name),
                 plugin_name: "embeddings".to_string(),
             })?;
-            preset.model.clone()
+            Ok(preset.model.clone())
         }
         #[cfg(feature = "embeddings")]
         crate::core::config::EmbeddingModelType::FastEmbed { model, .. } => match model.as_str() {
-            "AllMiniLML6V2Q" => fastembed::EmbeddingModel::AllMiniLML6V2Q,
-            "BGEBaseENV15" => fastembed::EmbeddingModel::BGEBaseENV15,
-            "BGELargeENV15" => fastembed::EmbeddingModel::BGELargeENV15,
-            "MultilingualE5Base" => fastembed::EmbeddingModel::MultilingualE5Base,
-            _ => {
-                return Err(crate::KreuzbergError::Plugin {
-                    message: format!("Unknown fastembed model: {}", model),
-                    plugin_name: "embeddings".to_string(),
-                });
-            }
-        },
-        crate::core::config::EmbeddingModelType::Custom { .. } => {
-            return Err(crate::KreuzbergError::Plugin {
-                message: "Custom ONNX models are not yet supported for embedding generation".to_string(),
+            "AllMiniLML6V2Q" => Ok(fastembed::EmbeddingModel::AllMiniLML6V2Q),
+            "BGEBaseENV15" => Ok(fastembed::EmbeddingModel::BGEBaseENV15),
+            "BGELargeENV15" => Ok(fastembed::EmbeddingModel::BGELargeENV15),
+            "MultilingualE5Base" => Ok(fastembed::EmbeddingModel::MultilingualE5Base),
+            _ => Err(crate::KreuzbergError::Plugin {
+                message: format!("Unknown fastembed model: {}", model),
                 plugin_name: "embeddings".to_string(),
-            });
-        }
+            }),
+        },
+        crate::core::config::EmbeddingModelType::Custom { .. } => Err(crate::KreuzbergError::Plugin {
+            message: "Custom ONNX models are not yet supported for embedding generation".to_string(),
+            plugin_name: "embeddings".to_string(),
+        }),
+    }
+}
+
+/// Path of the `tokenizer.json` of the configured embedding model.
+///
+/// Only the tokenizer is fetched from the HuggingFace Hub, into the same cache directory
+/// as the model files, so token-based chunking does not load the ONNX model.
+#[cfg(feature = "embeddings")]
+pub(crate) fn tokenizer_file(config: &crate::core::config::EmbeddingConfig) -> crate::Result<std::path::PathBuf> {
+    let model = resolve_model(&config.model)?;
+    let info = TextEmbedding::get_model_info(&model).map_err(|e| crate::KreuzbergError::Plugin {
+        message: format!("Unknown embedding model {:?}: {}", model, e),
+        plugin_name: "embeddings".to_string(),
+    })?;
+
+    let api = hf_hub::api::sync::ApiBuilder::new()
+        .with_cache_dir(model_cache_dir(config.cache_dir.clone()))
+        .with_progress(config.show_download_progress)
+        .build()
+        .map_err(|e| crate::KreuzbergError::Plugin {
+            message: format!("Failed to initialize HuggingFace Hub API: {}", e),
+            plugin_name: "embeddings".to_string(),
+        })?;
+    api.model(info.model_code.clone())
+        .get("tokenizer.json")
+        .map_err(|e| crate::KreuzbergError::Plugin {
+            message: format!("Failed to download the tokenizer of {}: {}", info.model_code, e),
+            plugin_name: "embeddings".to_string(),
+        })
+}
+
+/// Cache type of the per-chunk embedding cache.
+#[cfg(feature = "embeddings")]
+const EMBEDDING_CACHE_TYPE: &str = "embeddings";
+
+/// Like [`generate_embeddings_for_chunks`], optionally bypassing the embedding cache.
+///
+/// With `use_cache`, embeddings are looked up in the shared `embeddings` cache by model,
+/// normalization and chunk text, and only the misses are sent to the model. Identical
+/// chunks across documents (headers, boilerplate) and re-extractions are then free.
+/// The cache is stored in `cache_backend`, or the default backend when `None`.
+#[cfg(feature = "embeddings")]
+pub(crate) fn embed_chunks(
+    chunks: &mut [crate::types::Chunk],
+    config: &crate::core::config::EmbeddingConfig,
+    use_cache: bool,
+    cache_backend: Option<crate::cache::CacheBackendType>,
+) -> crate::Result<()> {
+    if chunks.is_empty() {
+        return Ok(());
+    }
+
+    let fastembed_model = resolve_model(&config.model)?;
+
+    let cache = if use_cache {
+        crate::cache::shared_cache_with_backend(
+            EMBEDDING_CACHE_TYPE,
+            cache_backend.unwrap_or_else(crate::cache::default_backend),
+            crate::cache::CacheLimits::new(30.0, 1000.0, 1000.0),
+        )
+    } else {
+        None
+    };
+    let model_id = format!("{:?}", fastembed_model);
+    let cache_keys: Vec<String> = match cache {
+        Some(_) => chunks
+            .iter()
+            .map(|chunk| embedding_cache_key(&model_id, config.normalize, &chunk.content))
+            .collect(),
+        None => Vec::new(),
     };
 
+    let mut missing = Vec::new();
+    for (index, chunk) in chunks.iter_mut().enumerate() {
+        let cached = cache
+            .as_deref()
+            .and_then(|cache| cached_embedding(cache, &cache_keys[index]));
+        match cached {
+            Some(embedding) => chunk.embedding = Some(embedding),
+            None => missing.push(index),
+        }
+    }
+    if missing.is_empty() {
+        return Ok(());
+    }
+
     let model = get_or_init_model(fastembed_model, config.cache_dir.clone())?;
 
-    let texts: Vec<String> = chunks.iter().map(|chunk| chunk.content.clone()).collect();
+    let texts: Vec<String> = missing.iter().map(|&index| chunks[index].content.clone()).collect();
 
     let embeddings_result = {
         let locked_model = model.lock().map_err(|e| crate::KreuzbergError::Plugin {
@@ -448,7 +536,7 @@ pub fn generate_embeddings_for_chunks(
             })?
     };
 
-    for (chunk, mut embedding) in chunks.iter_mut().zip(embeddings_result.into_iter()) {
+    for (&index, mut embedding) in missing.iter().zip(embeddings_result.into_iter()) {
         if config.normalize {
             let magnitude: f32 = embedding.iter().map(|x| x * x).sum::<f32>().sqrt();
             if magnitude > 0.0 {
@@ -456,12 +544,43 @@ pub fn generate_embeddings_for_chunks(
             }
         }
 
-        chunk.embedding = Some(embedding);
+        if let Some(cache) = &cache {
+            let stored = rmp_serde::to_vec(&embedding)
+                .map_err(crate::KreuzbergError::from)
+                .and_then(|bytes| cache.set(&cache_keys[index], bytes, None));
+            if let Err(e) = stored {
+                tracing::debug!("Failed to store embedding in cache: {}", e);
+            }
+        }
+
+        chunks[index].embedding = Some(embedding);
     }
 
     Ok(())
 }
 
+/// SHA-256 of the model, normalization flag and text, so keys are stable across processes.
+#[cfg(feature = "embeddings")]
+fn embedding_cache_key(model_id: &str, normalize: bool, text: &str) -> String {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    hasher.update(format!("model={}\nnormalize={}\ntext=", model_id, normalize).as_bytes());
+    hasher.update(text.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(feature = "embeddings")]
+fn cached_embedding(cache: &crate::cache::GenericCache, key: &str) -> Option<Vec<f32>> {
+    match cache.get(key, None) {
+        Ok(bytes) => rmp_serde::from_slice(&bytes?).ok(),
+        Err(e) => {
+            tracing::debug!("Embedding cache lookup failed: {}", e);
+            None
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -511,4 +630,37 @@ mod tests {
     #[cfg(feature = "embeddings")]
     #[test]
     fn test_lock_poisoning_recovery_semantics() {}
+
+    #[cfg(feature = "embeddings")]
+    #[test]
+    fn test_embedding_cache_key_covers_model_normalization_and_text() {
+        let key = embedding_cache_key("BGESmallENV15", true, "hello");
+
+        assert_eq!(key, embedding_cache_key("BGESmallENV15", true, "hello"));
+        assert_eq!(key.len(), 64);
+        assert_ne!(key, embedding_cache_key("BGEBaseENV15", true, "hello"));
+        assert_ne!(key, embedding_cache_key("BGESmallENV15", false, "hello"));
+        assert_ne!(key, embedding_cache_key("BGESmallENV15", true, "hello!"));
+    }
+
+    #[cfg(feature = "embeddings")]
+    #[test]
+    fn test_cached_embedding_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = crate::cache::GenericCache::new(
+            EMBEDDING_CACHE_TYPE.to_string(),
+            Some(dir.path().to_string_lossy().to_string()),
+            30.0,
+            100.0,
+            0.0,
+        )
+        .unwrap();
+        let key = embedding_cache_key("BGESmallENV15", true, "hello");
+
+        assert_eq!(cached_embedding(&cache, &key), None);
+        cache
+            .set(&key, rmp_serde::to_vec(&vec![0.5f32, -0.25]).unwrap(), None)
+            .unwrap();
+        assert_eq!(cached_embedding(&cache, &key), Some(vec![0.5, -0.25]));
+    }
 }
@@ -0,0 +1,696 @@
+//! Instance-scoped extraction engine.
+//!
+//! A [`Kreuzberg`] engine owns its own plugin registries (document extractors, OCR
+//! backends, post-processors and validators), its post-processor cache and a default
+//! [`ExtractionConfig`]. Several engines can live in one process with different plugin
+//! sets, e.g. one per tenant, without seeing each other's registrations.
+//!
+//! The free functions ([`extract_file`](crate::extract_file),
+//! [`register_extractor`](crate::plugins::register_extractor),
+//! [`get_document_extractor_registry`](crate::get_document_extractor_registry), ...) keep
+//! working unchanged: they operate on the [global engine](Kreuzberg::global), whose
+//! registries are the process-wide ones.
+//!
+//! Extraction results cached with `use_cache` are kept apart per engine: every engine
+//! other than the global one adds an id of its own to its cache keys, so tenants that
+//! register different plugins under the same name never see each other's results.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use kreuzberg::{ExtractionConfig, Kreuzberg};
+//!
+//! # async fn example() -> kreuzberg::Result<()> {
+//! let tenant_a = Kreuzberg::new(ExtractionConfig::default())?;
+//! let tenant_b = Kreuzberg::new(ExtractionConfig::default())?;
+//! tenant_a.unregister_post_processor("quality-processing")?;
+//!
+//! let a = tenant_a.extract_file("document.pdf", None, None).await?;
+//! let b = tenant_b.extract_file("document.pdf", None, None).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::core::config::ExtractionConfig;
+use crate::core::pipeline::ProcessorCache;
+use crate::plugins::registry::{
+    DocumentExtractorRegistry, OcrBackendRegistry, PostProcessorRegistry, ValidatorRegistry,
+    get_document_extractor_registry, get_ocr_backend_registry, get_post_processor_registry, get_validator_registry,
+};
+use crate::plugins::{DocumentExtractor, OcrBackend, PostProcessor, Validator};
+use crate::{KreuzbergError, Result};
+use once_cell::sync::Lazy;
+use std::sync::{Arc, RwLock};
+
+#[cfg(feature = "tokio-runtime")]
+use crate::types::ExtractionResult;
+#[cfg(feature = "tokio-runtime")]
+use std::path::Path;
+
+#[cfg(feature = "tokio-runtime")]
+tokio::task_local! {
+    /// Engine the current extraction runs under; unset for the free functions.
+    static CURRENT_ENGINE: Kreuzberg;
+}
+
+static GLOBAL_ENGINE: Lazy<Kreuzberg> = Lazy::new(|| Kreuzberg {
+    inner: Arc::new(EngineInner {
+        registries: Registries::Global,
+        cache_namespace: None,
+        processor_cache: RwLock::new(None),
+        config: ExtractionConfig::default(),
+        #[cfg(feature = "tokio-runtime")]
+        isolation: RwLock::new(None),
+    }),
+});
+
+/// An extraction engine with its own plugin registries, caches and default configuration.
+///
+/// Cloning is cheap and yields a handle to the same engine.
+#[derive(Clone)]
+pub struct Kreuzberg {
+    inner: Arc<EngineInner>,
+}
+
+struct EngineInner {
+    registries: Registries,
+    /// Added to result cache keys; `None` for the global engine.
+    cache_namespace: Option<String>,
+    processor_cache: RwLock<Option<ProcessorCache>>,
+    config: ExtractionConfig,
+    #[cfg(feature = "tokio-runtime")]
+    isolation: RwLock<Option<Arc<crate::isolation::IsolationPool>>>,
+}
+
+enum Registries {
+    /// The process-wide registries. Looked up on access so that, as before, built-in
+    /// plugins are only initialized when first needed.
+    Global,
+    Owned {
+        extractors: Arc<RwLock<DocumentExtractorRegistry>>,
+        ocr_backends: Arc<RwLock<OcrBackendRegistry>>,
+        post_processors: Arc<RwLock<PostProcessorRegistry>>,
+        validators: Arc<RwLock<ValidatorRegistry>>,
+    },
+}
+
+impl Kreuzberg {
+    /// Create an engine with the built-in extractors, OCR backends and post-processors
+    /// enabled by crate features, using `config` when extraction calls pass no configuration.
+    pub fn new(config: ExtractionConfig) -> Result<Self> {
+        let mut extractors = DocumentExtractorRegistry::new();
+        crate::extractors::register_default_extractors_into(&mut extractors)?;
+        let mut post_processors = PostProcessorRegistry::new();
+        crate::core::pipeline::register_builtin_processors(&mut post_processors)?;
+
+        Ok(Self::with_registries(
+            extractors,
+            OcrBackendRegistry::new(),
+            post_processors,
+            config,
+        ))
+    }
+
+    /// Create an engine without any plugins registered.
+    pub fn empty(config: ExtractionConfig) -> Self {
+        Self::with_registries(
+            DocumentExtractorRegistry::new(),
+            OcrBackendRegistry::new_empty(),
+            PostProcessorRegistry::new(),
+            config,
+        )
+    }
+
+    fn with_registries(
+        extractors: DocumentExtractorRegistry,
+        ocr_backends: OcrBackendRegistry,
+        post_processors: PostProcessorRegistry,
+        config: ExtractionConfig,
+    ) -> Self {
+        Self {
+            inner: Arc::new(EngineInner {
+                registries: Registries::Owned {
+                    extractors: Arc::new(RwLock::new(extractors)),
+                    ocr_backends: Arc::new(RwLock::new(ocr_backends)),
+                    post_processors: Arc::new(RwLock::new(post_processors)),
+                    validators: Arc::new(RwLock::new(ValidatorRegistry::new())),
+                },
+                cache_namespace: Some(crate::plugins::identity::unique_id()),
+                processor_cache: RwLock::new(None),
+                config,
+                #[cfg(feature = "tokio-runtime")]
+                isolation: RwLock::new(None),
+            }),
+        }
+    }
+
+    /// The process-wide engine used by the free extraction and registration functions.
+    ///
+    /// Its registries are the ones returned by [`get_document_extractor_registry`] and
+    /// friends, and its default configuration is [`ExtractionConfig::default`].
+    pub fn global() -> &'static Kreuzberg {
+        &GLOBAL_ENGINE
+    }
+
+    /// Whether this is the [global engine](Self::global).
+    pub fn is_global(&self) -> bool {
+        matches!(self.inner.registries, Registries::Global)
+    }
+
+    /// Namespace of this engine's entries in the result cache, `None` for the global engine.
+    pub(crate) fn cache_namespace(&self) -> Option<&str> {
+        self.inner.cache_namespace.as_deref()
+    }
+
+    /// Configuration used when an extraction call passes none.
+    pub fn config(&self) -> &ExtractionConfig {
+        &self.inner.config
+    }
+
+    /// Document extractor registry of this engine.
+    pub fn extractor_registry(&self) -> Arc<RwLock<DocumentExtractorRegistry>> {
+        match &self.inner.registries {
+            Registries::Global => get_document_extractor_registry(),
+            Registries::Owned { extractors, .. } => Arc::clone(extractors),
+        }
+    }
+
+    /// OCR backend registry of this engine.
+    pub fn ocr_backend_registry(&self) -> Arc<RwLock<OcrBackendRegistry>> {
+        match &self.inner.registries {
+            Registries::Global => get_ocr_backend_registry(),
+            Registries::Owned { ocr_backends, .. } => Arc::clone(ocr_backends),
+        }
+    }
+
+    /// Post-processor registry of this engine.
+    ///
+    /// Call [`clear_processor_cache`](Self::clear_processor_cache) after changing it
+    /// directly; the `register_post_processor` and `unregister_post_processor` methods do so
+    /// automatically.
+    pub fn post_processor_registry(&self) -> Arc<RwLock<PostProcessorRegistry>> {
+        match &self.inner.registries {
+            Registries::Global => get_post_processor_registry(),
+            Registries::Owned { post_processors, .. } => Arc::clone(post_processors),
+        }
+    }
+
+    /// Validator registry of this engine.
+    pub fn validator_registry(&self) -> Arc<RwLock<ValidatorRegistry>> {
+        match &self.inner.registries {
+            Registries::Global => get_validator_registry(),
+            Registries::Owned { validators, .. } => Arc::clone(validators),
+        }
+    }
+
+    pub(crate) fn processor_cache(&self) -> &RwLock<Option<ProcessorCache>> {
+        &self.inner.processor_cache
+    }
+
+    /// Drop the cached post-processor lists so the next extraction re-reads the registry.
+    pub fn clear_processor_cache(&self) -> Result<()> {
+        let mut cache = self
+            .inner
+            .processor_cache
+            .write()
+            .map_err(|e| KreuzbergError::Other(format!("Processor cache lock poisoned: {}", e)))?;
+        *cache = None;
+        Ok(())
+    }
+
+    /// Register a document extractor with this engine.
+    pub fn register_extractor(&self, extractor: Arc<dyn DocumentExtractor>) -> Result<()> {
+        write(&self.extractor_registry(), "Document extractor")?.register(extractor)
+    }
+
+    /// Unregister a document extractor by name, calling its `shutdown()` method.
+    pub fn unregister_extractor(&self, name: &str) -> Result<()> {
+        write(&self.extractor_registry(), "Document extractor")?.remove(name)
+    }
+
+    /// Names of the document extractors registered with this engine.
+    pub fn list_extractors(&self) -> Result<Vec<String>> {
+        Ok(read(&self.extractor_registry(), "Document extractor")?.list())
+    }
+
+    /// Register an OCR backend with this engine.
+    pub fn register_ocr_backend(&self, backend: Arc<dyn OcrBackend>) -> Result<()> {
+        write(&self.ocr_backend_registry(), "OCR backend")?.register(backend)
+    }
+
+    /// Unregister an OCR backend by name, calling its `shutdown()` method.
+    pub fn unregister_ocr_backend(&self, name: &str) -> Result<()> {
+        write(&self.ocr_backend_registry(), "OCR backend")?.remove(name)
+    }
+
+    /// Names of the OCR backends registered with this engine.
+    pub fn list_ocr_backends(&self) -> Result<Vec<String>> {
+        Ok(read(&self.ocr_backend_registry(), "OCR backend")?.list())
+    }
+
+    /// Register a post-processor with this engine.
+    ///
+    /// `priority` orders processors within a stage; higher runs first.
+    pub fn register_post_processor(&self, processor: Arc<dyn PostProcessor>, priority: i32) -> Result<()> {
+        write(&self.post_processor_registry(), "Post-processor")?.register(processor, priority)?;
+        self.clear_processor_cache()
+    }
+
+    /// Unregister a post-processor by name, calling its `shutdown()` method.
+    pub fn unregister_post_processor(&self, name: &str) -> Result<()> {
+        write(&self.post_processor_registry(), "Post-processor")?.remove(name)?;
+        self.clear_processor_cache()
+    }
+
+    /// Names of the post-processors registered with this engine.
+    pub fn list_post_processors(&self) -> Result<Vec<String>> {
+        Ok(read(&self.post_processor_registry(), "Post-processor")?.list())
+    }
+
+    /// Register a validator with this engine.
+    pub fn register_validator(&self, validator: Arc<dyn Validator>) -> Result<()> {
+        write(&self.validator_registry(), "Validator")?.register(validator)
+    }
+
+    /// Unregister a validator by name, calling its `shutdown()` method.
+    pub fn unregister_validator(&self, name: &str) -> Result<()> {
+        write(&self.validator_registry(), "Validator")?.remove(name)
+    }
+
+    /// Names of the validators registered with this engine.
+    pub fn list_validators(&self) -> Result<Vec<String>> {
+        Ok(read(&self.validator_registry(), "Validator")?.list())
+    }
+
+    /// Start an out-of-process plugin and register it with this engine.
+    ///
+    /// An adapter is registered for every capability the plugin reports during the
+    /// handshake. See [`plugins::external`](crate::plugins::external) for the protocol.
+    #[cfg(feature = "tokio-runtime")]
+    pub async fn register_external_plugin(
+        &self,
+        config: crate::plugins::external::ExternalPluginConfig,
+    ) -> Result<Arc<crate::plugins::external::ExternalPlugin>> {
+        let plugin = crate::plugins::external::ExternalPlugin::start(config).await?;
+        plugin.register_with(self)?;
+        Ok(plugin)
+    }
+
+    /// Load a sandboxed WebAssembly plugin and register it with this engine.
+    ///
+    /// See [`WasiPluginConfig`](crate::plugins::external::WasiPluginConfig) for the limits
+    /// applied to the module.
+    #[cfg(feature = "wasi-plugins")]
+    pub async fn register_wasi_plugin(
+        &self,
+        config: crate::plugins::external::WasiPluginConfig,
+    ) -> Result<Arc<crate::plugins::external::ExternalPlugin>> {
+        let plugin = crate::plugins::external::ExternalPlugin::load_wasi(config).await?;
+        plugin.register_with(self)?;
+        Ok(plugin)
+    }
+
+    /// Extract documents in isolated worker processes, or in this process again with `None`.
+    ///
+    /// Blocks until the workers have started; replacing a configuration stops the previous
+    /// workers once their current extractions finish. See [`isolation`](crate::isolation).
+    ///
+    /// Fails if an extractor that is not built in is registered: workers only run the
+    /// built-in extractors.
+    #[cfg(feature = "tokio-runtime")]
+    pub fn set_isolation(&self, config: Option<crate::isolation::IsolationConfig>) -> Result<()> {
+        if config.is_some() {
+            let registry = self.extractor_registry();
+            let custom: Vec<String> = read(&registry, "Document extractor")?
+                .get_all()
+                .iter()
+                .filter(|extractor| !crate::plugins::identity::is_builtin(extractor.as_ref()))
+                .map(|extractor| extractor.name().to_string())
+                .collect();
+            if !custom.is_empty() {
+                return Err(KreuzbergError::validation(format!(
+                    "Isolation cannot run extractors registered at runtime: {}",
+                    custom.join(", ")
+                )));
+            }
+        }
+        let pool = config
+            .map(crate::isolation::IsolationPool::start)
+            .transpose()?
+            .map(Arc::new);
+        *self
+            .inner
+            .isolation
+            .write()
+            .map_err(|e| KreuzbergError::LockPoisoned(format!("Isolation lock poisoned: {}", e)))? = pool;
+        Ok(())
+    }
+
+    /// Whether extractions of this engine run in isolated worker processes.
+    #[cfg(feature = "tokio-runtime")]
+    pub fn is_isolated(&self) -> bool {
+        self.isolation().is_some()
+    }
+
+    #[cfg(feature = "tokio-runtime")]
+    pub(crate) fn isolation(&self) -> Option<Arc<crate::isolation::IsolationPool>> {
+        self.inner
+            .isolation
+            .read()
+            .map(|pool| pool.clone())
+            .unwrap_or_else(|e| e.into_inner().clone())
+    }
+
+    /// Extract content from a file using this engine's plugins.
+    ///
+    /// Uses [`config`](Self::config) when `config` is `None`. See
+    /// [`extract_file`](crate::extract_file) for the extraction steps and errors.
+    #[cfg(feature = "tokio-runtime")]
+    pub async fn extract_file(
+        &self,
+        path: impl AsRef<Path>,
+        mime_type: Option<&str>,
+        config: Option<&ExtractionConfig>,
+    ) -> Result<ExtractionResult> {
+        let config = config.unwrap_or(&self.inner.config);
+        self.scope(crate::core::extractor::extract_file(path, mime_type, config))
+            .await
+    }
+
+    /// Extract content from a byte array using this engine's plugins.
+    ///
+    /// Uses [`config`](Self::config) when `config` is `None`.
+    #[cfg(feature = "tokio-runtime")]
+    pub async fn extract_bytes(
+        &self,
+        content: &[u8],
+        mime_type: &str,
+        config: Option<&ExtractionConfig>,
+    ) -> Result<ExtractionResult> {
+        let config = config.unwrap_or(&self.inner.config);
+        self.scope(crate::core::extractor::extract_bytes(content, mime_type, config))
+            .await
+    }
+
+    /// Extract content from multiple files concurrently using this engine's plugins.
+    ///
+    /// Uses [`config`](Self::config) when `config` is `None`.
+    #[cfg(feature = "tokio-runtime")]
+    pub async fn batch_extract_file(
+        &self,
+        paths: Vec<impl AsRef<Path>>,
+        config: Option<&ExtractionConfig>,
+    ) -> Result<Vec<ExtractionResult>> {
+        let config = config.unwrap_or(&self.inner.config);
+        self.scope(crate::core::extractor::batch_extract_file(paths, config))
+            .await
+    }
+
+    /// Extract content from multiple byte arrays concurrently using this engine's plugins.
+    ///
+    /// Uses [`config`](Self::config) when `config` is `None`.
+    #[cfg(feature = "tokio-runtime")]
+    pub async fn batch_extract_bytes(
+        &self,
+        contents: Vec<(Vec<u8>, String)>,
+        config: Option<&ExtractionConfig>,
+    ) -> Result<Vec<ExtractionResult>> {
+        let config = config.unwrap_or(&self.inner.config);
+        self.scope(crate::core::extractor::batch_extract_bytes(contents, config))
+            .await
+    }
+
+    /// Synchronous wrapper for [`extract_file`](Self::extract_file).
+    #[cfg(feature = "tokio-runtime")]
+    pub fn extract_file_sync(
+        &self,
+        path: impl AsRef<Path>,
+        mime_type: Option<&str>,
+        config: Option<&ExtractionConfig>,
+    ) -> Result<ExtractionResult> {
+        crate::core::extractor::block_on(self.extract_file(path, mime_type, config))
+    }
+
+    /// Synchronous wrapper for [`extract_bytes`](Self::extract_bytes).
+    #[cfg(feature = "tokio-runtime")]
+    pub fn extract_bytes_sync(
+        &self,
+        content: &[u8],
+        mime_type: &str,
+        config: Option<&ExtractionConfig>,
+    ) -> Result<ExtractionResult> {
+        crate::core::extractor::block_on(self.extract_bytes(content, mime_type, config))
+    }
+
+    /// Run `future` with this engine as the [current](current) one.
+    #[cfg(feature = "tokio-runtime")]
+    pub(crate) async fn scope<F: std::future::Future>(&self, future: F) -> F::Output {
+        CURRENT_ENGINE.scope(self.clone(), future).await
+    }
+}
+
+impl std::fmt::Debug for Kreuzberg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Kreuzberg")
+            .field("global", &self.is_global())
+            .field("extractors", &self.list_extractors().unwrap_or_default())
+            .field("post_processors", &self.list_post_processors().unwrap_or_default())
+            .field("validators", &self.list_validators().unwrap_or_default())
+            .finish_non_exhaustive()
+    }
+}
+
+/// The engine the current extraction runs under, or the global engine outside of one.
+pub(crate) fn current() -> Kreuzberg {
+    #[cfg(feature = "tokio-runtime")]
+    if let Ok(engine) = CURRENT_ENGINE.try_with(Kreuzberg::clone) {
+        return engine;
+    }
+    GLOBAL_ENGINE.clone()
+}
+
+fn read<'a, T>(registry: &'a RwLock<T>, kind: &str) -> Result<std::sync::RwLockReadGuard<'a, T>> {
+    registry
+        .read()
+        .map_err(|e| KreuzbergError::Other(format!("{} registry lock poisoned: {}", kind, e)))
+}
+
+fn write<'a, T>(registry: &'a RwLock<T>, kind: &str) -> Result<std::sync::RwLockWriteGuard<'a, T>> {
+    registry
+        .write()
+        .map_err(|e| KreuzbergError::Other(format!("{} registry lock poisoned: {}", kind, e)))
+}
+
+#[cfg(all(test, feature = "tokio-runtime"))]
+mod tests {
+    use super::*;
+    use crate::plugins::{Plugin, ProcessingStage};
+    use async_trait::async_trait;
+    use std::borrow::Cow;
+
+    struct TenantExtractor;
+
+    impl Plugin for TenantExtractor {
+        fn name(&self) -> &str {
+            "tenant-extractor"
+        }
+        fn version(&self) -> String {
+            "1.0.0".to_string()
+        }
+        fn initialize(&self) -> Result<()> {
+            Ok(())
+        }
+        fn shutdown(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl DocumentExtractor for TenantExtractor {
+        async fn extract_bytes(&self, _: &[u8], mime_type: &str, _: &ExtractionConfig) -> Result<ExtractionResult> {
+            Ok(ExtractionResult {
+                content: "from tenant extractor".to_string(),
+                mime_type: Cow::Owned(mime_type.to_string()),
+                ..Default::default()
+            })
+        }
+
+        fn supported_mime_types(&self) -> &[&str] {
+            &["text/plain"]
+        }
+
+        fn priority(&self) -> i32 {
+            1000
+        }
+    }
+
+    struct Shout;
+
+    impl Plugin for Shout {
+        fn name(&self) -> &str {
+            "shout"
+        }
+        fn version(&self) -> String {
+            "1.0.0".to_string()
+        }
+        fn initialize(&self) -> Result<()> {
+            Ok(())
+        }
+        fn shutdown(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl PostProcessor for Shout {
+        async fn process(&self, result: &mut ExtractionResult, _: &ExtractionConfig) -> Result<()> {
+            result.content = result.content.to_uppercase();
+            Ok(())
+        }
+
+        fn processing_stage(&self) -> ProcessingStage {
+            ProcessingStage::Late
+        }
+    }
+
+    /// Replaces the whole content, as a tenant-specific redaction step would.
+    struct Redactor(&'static str);
+
+    impl Plugin for Redactor {
+        fn name(&self) -> &str {
+            "redactor"
+        }
+        fn version(&self) -> String {
+            "1.0.0".to_string()
+        }
+        fn initialize(&self) -> Result<()> {
+            Ok(())
+        }
+        fn shutdown(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl PostProcessor for Redactor {
+        async fn process(&self, result: &mut ExtractionResult, _: &ExtractionConfig) -> Result<()> {
+            result.content = self.0.to_string();
+            Ok(())
+        }
+
+        fn processing_stage(&self) -> ProcessingStage {
+            ProcessingStage::Late
+        }
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_engines_have_isolated_extractors() {
+        let tenant = Kreuzberg::new(ExtractionConfig::default()).unwrap();
+        let other = Kreuzberg::new(ExtractionConfig::default()).unwrap();
+        tenant.register_extractor(Arc::new(TenantExtractor)).unwrap();
+
+        let result = tenant.extract_bytes(b"hello", "text/plain", None).await.unwrap();
+        assert_eq!(result.content, "from tenant extractor");
+
+        let result = other.extract_bytes(b"hello", "text/plain", None).await.unwrap();
+        assert_eq!(result.content.trim_end(), "hello");
+
+        let result = crate::extract_bytes(b"hello", "text/plain", &ExtractionConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(result.content.trim_end(), "hello");
+        assert!(
+            !crate::plugins::list_extractors()
+                .unwrap()
+                .contains(&"tenant-extractor".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_engine_post_processors_apply_only_to_that_engine() {
+        let tenant = Kreuzberg::new(ExtractionConfig::default()).unwrap();
+        let other = Kreuzberg::new(ExtractionConfig::default()).unwrap();
+
+        let before = tenant.extract_bytes(b"quiet", "text/plain", None).await.unwrap();
+        assert_eq!(before.content.trim_end(), "quiet");
+
+        // Registering must invalidate the processor cache filled by the first extraction.
+        tenant.register_post_processor(Arc::new(Shout), 0).unwrap();
+        let shouted = tenant.extract_bytes(b"quiet", "text/plain", None).await.unwrap();
+        assert_eq!(shouted.content.trim_end(), "QUIET");
+
+        let result = other.extract_bytes(b"quiet", "text/plain", None).await.unwrap();
+        assert_eq!(result.content.trim_end(), "quiet");
+
+        tenant.unregister_post_processor("shout").unwrap();
+        let after = tenant.extract_bytes(b"quiet", "text/plain", None).await.unwrap();
+        assert_eq!(after.content.trim_end(), "quiet");
+    }
+
+    #[tokio::test]
+    async fn test_cached_results_are_not_shared_between_engines() {
+        let config = ExtractionConfig {
+            use_cache: true,
+            ..Default::default()
+        };
+        let tenant_a = Kreuzberg::new(config.clone()).unwrap();
+        let tenant_b = Kreuzberg::new(config).unwrap();
+        tenant_a
+            .register_post_processor(Arc::new(Redactor("redacted by a")), 0)
+            .unwrap();
+        tenant_b
+            .register_post_processor(Arc::new(Redactor("redacted by b")), 0)
+            .unwrap();
+
+        let content = b"cross-tenant cache check";
+        let a = tenant_a.extract_bytes(content, "text/plain", None).await.unwrap();
+        let b = tenant_b.extract_bytes(content, "text/plain", None).await.unwrap();
+        assert_eq!(a.content, "redacted by a");
+        assert_eq!(b.content, "redacted by b");
+
+        let again = tenant_a.extract_bytes(content, "text/plain", None).await.unwrap();
+        assert_eq!(again.content, "redacted by a");
+    }
+
+    #[tokio::test]
+    async fn test_batch_extraction_uses_engine_plugins() {
+        let tenant = Kreuzberg::new(ExtractionConfig::default()).unwrap();
+        tenant.register_extractor(Arc::new(TenantExtractor)).unwrap();
+
+        let contents = vec![
+            (b"one".to_vec(), "text/plain".to_string()),
+            (b"two".to_vec(), "text/plain".to_string()),
+        ];
+        let results = tenant.batch_extract_bytes(contents, None).await.unwrap();
+        assert!(results.iter().all(|r| r.content == "from tenant extractor"));
+    }
+
+    #[tokio::test]
+    async fn test_empty_engine_has_no_extractors() {
+        let engine = Kreuzberg::empty(ExtractionConfig::default());
+        assert!(engine.list_extractors().unwrap().is_empty());
+        assert!(engine.list_post_processors().unwrap().is_empty());
+
+        let result = engine.extract_bytes(b"hello", "text/plain", None).await;
+        assert!(matches!(result, Err(KreuzbergError::UnsupportedFormat(_))));
+    }
+
+    #[tokio::test]
+    async fn test_engine_default_config_is_used() {
+        let config = ExtractionConfig {
+            output_format: crate::core::config::OutputFormat::Markdown,
+            ..Default::default()
+        };
+        let engine = Kreuzberg::new(config).unwrap();
+        assert!(!engine.is_global());
+        assert!(Kreuzberg::global().is_global());
+        assert_eq!(
+            engine.config().output_format,
+            crate::core::config::OutputFormat::Markdown
+        );
+
+        let result = engine.extract_bytes(b"# Title", "text/plain", None).await.unwrap();
+        assert_eq!(result.metadata.output_format.as_deref(), Some("markdown"));
+    }
+}
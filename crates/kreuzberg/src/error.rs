@@ -64,6 +64,8 @@ pub type Result<T> = std::result::Result<T, KreuzbergError>;
 /// - `Plugin` - Plugin-specific errors
 /// - `LockPoisoned` - Mutex/RwLock poisoning (should not happen in normal operation)
 /// - `UnsupportedFormat` - Unsupported MIME type or file format
+/// - `Isolation` - An isolated extraction worker timed out, ran out of memory or crashed
+/// - `Cancelled` - Extraction was cancelled or exceeded `ExtractionConfig::timeout_secs`
 /// - `Other` - Catch-all for uncommon errors
 #[derive(Debug, Error)]
 pub enum KreuzbergError {
@@ -124,10 +126,59 @@ pub enum KreuzbergError {
     #[error("Unsupported format: {0}")]
     UnsupportedFormat(String),
 
+    #[error("Isolated extraction failed ({kind}): {message}")]
+    Isolation { kind: IsolationFailure, message: String },
+
+    #[error("Extraction {0}")]
+    Cancelled(CancellationReason),
+
     #[error("{0}")]
     Other(String),
 }
 
+/// Why an extraction running in an isolated worker process failed.
+///
+/// See [`isolation`](crate::isolation).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IsolationFailure {
+    /// The extraction exceeded its wall-clock limit and the worker was killed.
+    Timeout,
+    /// The worker exceeded its resident memory limit and was killed.
+    MemoryLimit,
+    /// The worker exited or broke the protocol, e.g. after a segfault in a native library.
+    Crash,
+}
+
+impl std::fmt::Display for IsolationFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Timeout => "timeout",
+            Self::MemoryLimit => "memory limit",
+            Self::Crash => "crash",
+        })
+    }
+}
+
+/// Why an extraction was stopped early.
+///
+/// See [`CancellationToken`](crate::core::cancellation::CancellationToken).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CancellationReason {
+    /// The extraction's cancellation token was cancelled.
+    Cancelled,
+    /// The extraction ran past its deadline.
+    TimedOut,
+}
+
+impl std::fmt::Display for CancellationReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Cancelled => "cancelled",
+            Self::TimedOut => "timed out",
+        })
+    }
+}
+
 #[cfg(any(feature = "excel", feature = "excel-wasm"))]
 impl From<calamine::Error> for KreuzbergError {
     fn from(err: calamine::Error) -> Self {
@@ -326,6 +377,26 @@ mod tests {
         assert_eq!(err.to_string(), "Unsupported format: application/unknown");
     }
 
+    #[test]
+    fn test_isolation_error() {
+        let err = KreuzbergError::Isolation {
+            kind: IsolationFailure::MemoryLimit,
+            message: "worker used 3 GiB".to_string(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "Isolated extraction failed (memory limit): worker used 3 GiB"
+        );
+    }
+
+    #[test]
+    fn test_cancelled_error() {
+        let err = KreuzbergError::Cancelled(CancellationReason::TimedOut);
+        assert_eq!(err.to_string(), "Extraction timed out");
+        let err = KreuzbergError::Cancelled(CancellationReason::Cancelled);
+        assert_eq!(err.to_string(), "Extraction cancelled");
+    }
+
     #[test]
     fn test_other_error() {
         let err = KreuzbergError::Other("unexpected error".to_string());
@@ -16,10 +16,16 @@ mod zip;
 
 // Re-export all public functions for backward compatibility
 pub use gzip::{decompress_gzip, extract_gzip, extract_gzip_metadata, extract_gzip_text_content};
+pub(crate) use sevenz::extract_7z_text_content_with_config;
 pub use sevenz::{extract_7z_metadata, extract_7z_text_content};
+pub(crate) use tar::extract_tar_text_content_with_config;
 pub use tar::{extract_tar_metadata, extract_tar_text_content};
+pub(crate) use zip::extract_zip_text_content_with_config;
 pub use zip::{extract_zip_metadata, extract_zip_text_content};
 
+use crate::core::config::ExtractionConfig;
+use crate::error::{KreuzbergError, Result};
+
 /// Archive metadata extracted from an archive file.
 #[derive(Debug, Clone)]
 pub struct ArchiveMetadata {
@@ -49,6 +55,16 @@ pub(crate) const TEXT_EXTENSIONS: &[&str] = &[
     ".txt", ".md", ".json", ".xml", ".html", ".csv", ".log", ".yaml", ".toml",
 ];
 
+/// Leave out an entry that could not be read, reporting it in best-effort mode.
+fn skip_entry(config: Option<&ExtractionConfig>, path: &str, error: impl std::fmt::Display) -> Result<()> {
+    if let Some(config) = config.filter(|config| config.best_effort) {
+        config
+            .recover(|| format!("archive entry '{}'", path), Err::<(), _>(error.to_string()))
+            .map_err(KreuzbergError::parsing)?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,7 +2,8 @@
 //!
 //! Provides functions for extracting metadata and text content from 7Z archives.
 
-use super::{ArchiveEntry, ArchiveMetadata, TEXT_EXTENSIONS};
+use super::{ArchiveEntry, ArchiveMetadata, TEXT_EXTENSIONS, skip_entry};
+use crate::core::config::ExtractionConfig;
 use crate::error::{KreuzbergError, Result};
 use crate::extractors::security::SecurityLimits;
 use sevenz_rust2::{ArchiveReader, Password};
@@ -91,6 +92,16 @@ pub fn extract_7z_metadata(bytes: &[u8], limits: &SecurityLimits) -> Result<Arch
 ///
 /// Returns an error if the 7z archive cannot be read or parsed.
 pub fn extract_7z_text_content(bytes: &[u8], limits: &SecurityLimits) -> Result<HashMap<String, String>> {
+    extract_7z_text_content_with_config(bytes, limits, None)
+}
+
+/// Like [`extract_7z_text_content`], reporting unreadable entries when `config` asks for
+/// best-effort extraction.
+pub(crate) fn extract_7z_text_content_with_config(
+    bytes: &[u8],
+    limits: &SecurityLimits,
+    config: Option<&ExtractionConfig>,
+) -> Result<HashMap<String, String>> {
     let cursor = Cursor::new(bytes);
     let mut archive = ArchiveReader::new(cursor, Password::empty())
         .map_err(|e| KreuzbergError::parsing(format!("Failed to read 7z archive: {}", e)))?;
@@ -106,6 +117,7 @@ pub fn extract_7z_text_content(bytes: &[u8], limits: &SecurityLimits) -> Result<
     let mut contents = HashMap::new();
     let max_content_size = limits.max_content_size;
     let mut total_content_size = 0usize;
+    let mut unreadable = Vec::new();
 
     archive
         .for_each_entries(|entry, reader| {
@@ -113,20 +125,29 @@ pub fn extract_7z_text_content(bytes: &[u8], limits: &SecurityLimits) -> Result<
 
             if !entry.is_directory() && TEXT_EXTENSIONS.iter().any(|ext| path.to_lowercase().ends_with(ext)) {
                 let mut content = Vec::new();
-                if let Ok(_) = reader.read_to_end(&mut content)
-                    && let Ok(text) = String::from_utf8(content)
-                {
-                    total_content_size = total_content_size.saturating_add(text.len());
-                    if total_content_size > max_content_size {
-                        return Ok(false);
+                let text = reader
+                    .read_to_end(&mut content)
+                    .map_err(|e| e.to_string())
+                    .and_then(|_| String::from_utf8(content).map_err(|e| e.to_string()));
+                match text {
+                    Ok(text) => {
+                        total_content_size = total_content_size.saturating_add(text.len());
+                        if total_content_size > max_content_size {
+                            return Ok(false);
+                        }
+                        contents.insert(path, text);
                     }
-                    contents.insert(path, text);
+                    Err(e) => unreadable.push((path, e)),
                 }
             }
             Ok(true)
         })
         .map_err(|e| KreuzbergError::parsing(format!("Failed to read 7z entries: {}", e)))?;
 
+    for (path, error) in unreadable {
+        skip_entry(config, &path, error)?;
+    }
+
     if total_content_size > max_content_size {
         return Err(KreuzbergError::validation(format!(
             "7z archive text content exceeds limit: {} bytes (max: {} bytes)",
@@ -3,7 +3,8 @@
 //! Provides functions for extracting metadata and text content from TAR archives.
 //! Supports plain TAR as well as compressed variants (TAR.GZ, TAR.BZ2).
 
-use super::{ArchiveEntry, ArchiveMetadata, TEXT_EXTENSIONS};
+use super::{ArchiveEntry, ArchiveMetadata, TEXT_EXTENSIONS, skip_entry};
+use crate::core::config::ExtractionConfig;
 use crate::error::{KreuzbergError, Result};
 use crate::extractors::security::SecurityLimits;
 use std::collections::HashMap;
@@ -102,6 +103,16 @@ pub fn extract_tar_metadata(bytes: &[u8], limits: &SecurityLimits) -> Result<Arc
 ///
 /// Returns an error if the TAR archive cannot be read or parsed.
 pub fn extract_tar_text_content(bytes: &[u8], limits: &SecurityLimits) -> Result<HashMap<String, String>> {
+    extract_tar_text_content_with_config(bytes, limits, None)
+}
+
+/// Like [`extract_tar_text_content`], skipping and reporting unreadable entries when `config`
+/// asks for best-effort extraction.
+pub(crate) fn extract_tar_text_content_with_config(
+    bytes: &[u8],
+    limits: &SecurityLimits,
+    config: Option<&ExtractionConfig>,
+) -> Result<HashMap<String, String>> {
     let cursor = Cursor::new(bytes);
     let mut archive = TarArchive::new(cursor);
 
@@ -136,15 +147,18 @@ pub fn extract_tar_text_content(bytes: &[u8], limits: &SecurityLimits) -> Result
         {
             let estimated_size = (entry.size().min(10 * 1024 * 1024)) as usize;
             let mut content = String::with_capacity(estimated_size);
-            if entry.read_to_string(&mut content).is_ok() {
-                total_content_size = total_content_size.saturating_add(content.len());
-                if total_content_size > limits.max_content_size {
-                    return Err(KreuzbergError::validation(format!(
-                        "TAR archive text content exceeds limit: {} bytes (max: {} bytes)",
-                        total_content_size, limits.max_content_size
-                    )));
+            match entry.read_to_string(&mut content) {
+                Ok(_) => {
+                    total_content_size = total_content_size.saturating_add(content.len());
+                    if total_content_size > limits.max_content_size {
+                        return Err(KreuzbergError::validation(format!(
+                            "TAR archive text content exceeds limit: {} bytes (max: {} bytes)",
+                            total_content_size, limits.max_content_size
+                        )));
+                    }
+                    contents.insert(path, content);
                 }
-                contents.insert(path, content);
+                Err(e) => skip_entry(config, &path, e)?,
             }
         }
     }
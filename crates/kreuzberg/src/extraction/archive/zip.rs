@@ -2,7 +2,8 @@
 //!
 //! Provides functions for extracting metadata and text content from ZIP archives.
 
-use super::{ArchiveEntry, ArchiveMetadata, TEXT_EXTENSIONS};
+use super::{ArchiveEntry, ArchiveMetadata, TEXT_EXTENSIONS, skip_entry};
+use crate::core::config::ExtractionConfig;
 use crate::error::{KreuzbergError, Result};
 use crate::extractors::security::SecurityLimits;
 use std::collections::HashMap;
@@ -92,6 +93,16 @@ pub fn extract_zip_metadata(bytes: &[u8], limits: &SecurityLimits) -> Result<Arc
 ///
 /// Returns an error if the ZIP archive cannot be read or parsed.
 pub fn extract_zip_text_content(bytes: &[u8], limits: &SecurityLimits) -> Result<HashMap<String, String>> {
+    extract_zip_text_content_with_config(bytes, limits, None)
+}
+
+/// Like [`extract_zip_text_content`], skipping and reporting unreadable entries when `config`
+/// asks for best-effort extraction.
+pub(crate) fn extract_zip_text_content_with_config(
+    bytes: &[u8],
+    limits: &SecurityLimits,
+    config: Option<&ExtractionConfig>,
+) -> Result<HashMap<String, String>> {
     let cursor = Cursor::new(bytes);
     let mut archive =
         ZipArchive::new(cursor).map_err(|e| KreuzbergError::parsing(format!("Failed to read ZIP archive: {}", e)))?;
@@ -118,15 +129,18 @@ pub fn extract_zip_text_content(bytes: &[u8], limits: &SecurityLimits) -> Result
         if !file.is_dir() && TEXT_EXTENSIONS.iter().any(|ext| path.to_lowercase().ends_with(ext)) {
             let estimated_size = (file.size() as usize).min(10 * 1024 * 1024);
             let mut content = String::with_capacity(estimated_size);
-            if file.read_to_string(&mut content).is_ok() {
-                total_content_size = total_content_size.saturating_add(content.len());
-                if total_content_size > limits.max_content_size {
-                    return Err(KreuzbergError::validation(format!(
-                        "ZIP archive text content exceeds limit: {} bytes (max: {} bytes)",
-                        total_content_size, limits.max_content_size
-                    )));
+            match file.read_to_string(&mut content) {
+                Ok(_) => {
+                    total_content_size = total_content_size.saturating_add(content.len());
+                    if total_content_size > limits.max_content_size {
+                        return Err(KreuzbergError::validation(format!(
+                            "ZIP archive text content exceeds limit: {} bytes (max: {} bytes)",
+                            total_content_size, limits.max_content_size
+                        )));
+                    }
+                    contents.insert(path, content);
                 }
-                contents.insert(path, content);
+                Err(e) => skip_entry(config, &path, e)?,
             }
         }
     }
@@ -0,0 +1,715 @@
+//! Microsoft Compiled HTML Help (CHM) parsing.
+//!
+//! A CHM file is an ITSF container: a PMGL directory lists every stored file
+//! with its section, offset and length. Section 0 is stored uncompressed;
+//! section 1 ("MSCompressed") is an LZX stream split into 32 KiB frames whose
+//! compressed offsets are recorded in a reset table. Topics are read in the
+//! order of the table of contents (`.hhc` sitemap) and returned as HTML for
+//! conversion with [`crate::extraction::html`].
+
+use std::collections::{HashMap, HashSet};
+
+use crate::error::{KreuzbergError, Result};
+use crate::extraction::mobi::decode_windows_1252;
+use lzxd::{Lzxd, WindowSize};
+
+/// MIME type for Compiled HTML Help files (`.chm`).
+pub const CHM_MIME_TYPE: &str = "application/vnd.ms-htmlhelp";
+
+const LZX_FRAME_SIZE: usize = 0x8000;
+
+/// Upper bound on the decompressed section size, guarding against corrupt reset tables.
+const MAX_UNCOMPRESSED_SIZE: u64 = 512 * 1024 * 1024;
+
+const CONTENT_PATH: &str = "::DataSpace/Storage/MSCompressed/Content";
+const CONTROL_DATA_PATH: &str = "::DataSpace/Storage/MSCompressed/ControlData";
+const RESET_TABLE_PATH: &str =
+    "::DataSpace/Storage/MSCompressed/Transform/{7FC28940-9D31-11D0-9B27-00A0C91E9C7C}/InstanceData/ResetTable";
+const SYSTEM_PATH: &str = "/#SYSTEM";
+
+/// `#SYSTEM` record codes.
+const SYSTEM_CONTENTS_FILE: u16 = 0;
+const SYSTEM_TITLE: u16 = 3;
+
+/// A help topic in table of contents order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChmTopic {
+    /// Path of the topic inside the archive (e.g. `/html/intro.htm`).
+    pub path: String,
+    /// Title from the table of contents, if listed there.
+    pub title: Option<String>,
+    /// Topic HTML.
+    pub html: String,
+}
+
+/// A parsed help file.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChmDocument {
+    /// Help file title from the `#SYSTEM` file.
+    pub title: Option<String>,
+    pub topics: Vec<ChmTopic>,
+}
+
+#[derive(Debug, Clone)]
+struct ChmEntry {
+    name: String,
+    section: u64,
+    offset: u64,
+    length: u64,
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    data.get(offset..offset + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Option<u64> {
+    data.get(offset..offset + 8)
+        .and_then(|b| b.try_into().ok())
+        .map(u64::from_le_bytes)
+}
+
+/// Read a big-endian base-128 integer (`ENCINT`).
+fn read_encint(data: &[u8], pos: &mut usize) -> Option<u64> {
+    let mut value = 0u64;
+    for _ in 0..10 {
+        let byte = *data.get(*pos)?;
+        *pos += 1;
+        value = (value << 7) | (byte & 0x7F) as u64;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+fn truncated(what: &str) -> KreuzbergError {
+    KreuzbergError::parsing(format!("Invalid CHM file: truncated {}", what))
+}
+
+/// Decode topic or metadata bytes: UTF-8 when valid, Windows-1252 otherwise.
+fn decode_bytes(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => decode_windows_1252(bytes),
+    }
+}
+
+/// ITSF container with its file directory.
+struct ChmArchive<'a> {
+    data: &'a [u8],
+    content_offset: u64,
+    entries: Vec<ChmEntry>,
+    index: HashMap<String, usize>,
+    section1: Option<Vec<u8>>,
+}
+
+impl<'a> ChmArchive<'a> {
+    fn parse(data: &'a [u8]) -> Result<Self> {
+        if !data.starts_with(b"ITSF") {
+            return Err(KreuzbergError::parsing("Not a CHM file: missing ITSF signature"));
+        }
+        let version = read_u32(data, 4).ok_or_else(|| truncated("header"))?;
+        let dir_offset = read_u64(data, 0x48).ok_or_else(|| truncated("header"))?;
+        let dir_length = read_u64(data, 0x50).ok_or_else(|| truncated("header"))?;
+        let content_offset = if version >= 3 {
+            read_u64(data, 0x58).ok_or_else(|| truncated("header"))?
+        } else {
+            dir_offset.saturating_add(dir_length)
+        };
+
+        let dir = usize::try_from(dir_offset)
+            .ok()
+            .and_then(|start| data.get(start..))
+            .filter(|dir| dir.starts_with(b"ITSP"))
+            .ok_or_else(|| KreuzbergError::parsing("Invalid CHM file: missing ITSP directory"))?;
+        let header_len = read_u32(dir, 8).ok_or_else(|| truncated("directory header"))? as usize;
+        let chunk_size = read_u32(dir, 0x10).ok_or_else(|| truncated("directory header"))? as usize;
+        let chunk_count = read_u32(dir, 0x2C).ok_or_else(|| truncated("directory header"))? as usize;
+        if chunk_size < 0x14 {
+            return Err(KreuzbergError::parsing("Invalid CHM file: bad directory chunk size"));
+        }
+
+        let mut entries = Vec::new();
+        for chunk_index in 0..chunk_count {
+            let start = header_len + chunk_index * chunk_size;
+            let Some(chunk) = dir.get(start..start + chunk_size) else {
+                break;
+            };
+            // PMGI chunks are only an index over the PMGL listing
+            if !chunk.starts_with(b"PMGL") {
+                continue;
+            }
+            let free_space = read_u32(chunk, 4).unwrap_or(0) as usize;
+            let end = chunk_size.saturating_sub(free_space);
+            let mut pos = 0x14;
+            while pos < end {
+                let Some(name_len) = read_encint(chunk, &mut pos) else {
+                    break;
+                };
+                let Some(name) = chunk.get(pos..pos + name_len as usize) else {
+                    break;
+                };
+                pos += name_len as usize;
+                let (Some(section), Some(offset), Some(length)) = (
+                    read_encint(chunk, &mut pos),
+                    read_encint(chunk, &mut pos),
+                    read_encint(chunk, &mut pos),
+                ) else {
+                    break;
+                };
+                entries.push(ChmEntry {
+                    name: String::from_utf8_lossy(name).into_owned(),
+                    section,
+                    offset,
+                    length,
+                });
+            }
+        }
+
+        let index = entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| (entry.name.to_lowercase(), i))
+            .collect();
+
+        Ok(Self {
+            data,
+            content_offset,
+            entries,
+            index,
+            section1: None,
+        })
+    }
+
+    fn entry(&self, name: &str) -> Option<&ChmEntry> {
+        self.index.get(&name.to_lowercase()).map(|&i| &self.entries[i])
+    }
+
+    fn read_section0(&self, entry: &ChmEntry) -> Option<Vec<u8>> {
+        let start = usize::try_from(self.content_offset.checked_add(entry.offset)?).ok()?;
+        let end = start.checked_add(usize::try_from(entry.length).ok()?)?;
+        self.data.get(start..end).map(<[u8]>::to_vec)
+    }
+
+    /// Read a file by its archive path (case-insensitive).
+    fn read(&mut self, name: &str) -> Option<Vec<u8>> {
+        let entry = self.entry(name)?.clone();
+        match entry.section {
+            0 => self.read_section0(&entry),
+            1 => {
+                if self.section1.is_none() {
+                    self.section1 = Some(self.decompress_section1().unwrap_or_default());
+                }
+                let section = self.section1.as_deref()?;
+                let start = usize::try_from(entry.offset).ok()?;
+                let end = start.checked_add(usize::try_from(entry.length).ok()?)?;
+                section.get(start..end).map(<[u8]>::to_vec)
+            }
+            _ => None,
+        }
+    }
+
+    /// Decompress the LZX section, frame by frame.
+    ///
+    /// Decoding stops at the first corrupt frame; everything before it is kept.
+    fn decompress_section1(&self) -> Result<Vec<u8>> {
+        let read_file = |path: &str| {
+            self.entry(path)
+                .and_then(|entry| self.read_section0(entry))
+                .ok_or_else(|| KreuzbergError::parsing(format!("Invalid CHM file: missing {}", path)))
+        };
+        let content = read_file(CONTENT_PATH)?;
+        let control = read_file(CONTROL_DATA_PATH)?;
+        let reset_table = read_file(RESET_TABLE_PATH)?;
+
+        if control.get(4..8) != Some(b"LZXC".as_slice()) {
+            return Err(KreuzbergError::parsing("Invalid CHM file: unknown compression"));
+        }
+        let control_version = read_u32(&control, 8).ok_or_else(|| truncated("LZX control data"))?;
+        let mut reset_interval = read_u32(&control, 12).ok_or_else(|| truncated("LZX control data"))? as usize;
+        let mut window_size = read_u32(&control, 16).ok_or_else(|| truncated("LZX control data"))? as usize;
+        if control_version == 2 {
+            reset_interval = reset_interval.saturating_mul(LZX_FRAME_SIZE);
+            window_size = window_size.saturating_mul(LZX_FRAME_SIZE);
+        }
+        let window = match window_size {
+            0x8000 => WindowSize::KB32,
+            0x10000 => WindowSize::KB64,
+            0x20000 => WindowSize::KB128,
+            0x40000 => WindowSize::KB256,
+            0x80000 => WindowSize::KB512,
+            0x100000 => WindowSize::MB1,
+            0x200000 => WindowSize::MB2,
+            other => {
+                return Err(KreuzbergError::parsing(format!(
+                    "Invalid CHM file: unsupported LZX window size {}",
+                    other
+                )));
+            }
+        };
+        let frames_per_reset = (reset_interval / LZX_FRAME_SIZE).max(1);
+
+        let entry_count = read_u32(&reset_table, 4).ok_or_else(|| truncated("reset table"))? as usize;
+        let entry_size = read_u32(&reset_table, 8).ok_or_else(|| truncated("reset table"))? as usize;
+        let table_offset = read_u32(&reset_table, 12).ok_or_else(|| truncated("reset table"))? as usize;
+        let uncompressed_len = read_u64(&reset_table, 16).ok_or_else(|| truncated("reset table"))?;
+        let compressed_len = read_u64(&reset_table, 24).ok_or_else(|| truncated("reset table"))?;
+        if entry_size != 8 {
+            return Err(KreuzbergError::parsing(
+                "Invalid CHM file: unsupported reset table layout",
+            ));
+        }
+        let uncompressed_len = uncompressed_len.min(MAX_UNCOMPRESSED_SIZE) as usize;
+        let compressed_len = (compressed_len as usize).min(content.len());
+        let frame_offsets: Vec<usize> = (0..entry_count)
+            .map_while(|i| read_u64(&reset_table, table_offset + i * 8))
+            .map(|offset| offset as usize)
+            .collect();
+
+        let mut decoder = Lzxd::new(window);
+        let mut output = Vec::with_capacity(uncompressed_len);
+        let frame_count = uncompressed_len.div_ceil(LZX_FRAME_SIZE);
+        for frame in 0..frame_count {
+            let Some(&start) = frame_offsets.get(frame) else {
+                break;
+            };
+            let end = frame_offsets.get(frame + 1).copied().unwrap_or(compressed_len);
+            let Some(chunk) = content.get(start..end.max(start)) else {
+                break;
+            };
+            if frame > 0 && frame % frames_per_reset == 0 {
+                decoder.reset();
+            }
+            let frame_len = LZX_FRAME_SIZE.min(uncompressed_len - output.len());
+            match decoder.decompress_next(chunk, frame_len) {
+                Ok(decoded) => output.extend_from_slice(decoded),
+                Err(_) => break,
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Parse `#SYSTEM` into `(contents file, title)`.
+    fn system_info(&mut self) -> (Option<String>, Option<String>) {
+        let Some(system) = self.read(SYSTEM_PATH) else {
+            return (None, None);
+        };
+        let mut contents = None;
+        let mut title = None;
+        let mut pos = 4;
+        while pos + 4 <= system.len() {
+            let code = u16::from_le_bytes([system[pos], system[pos + 1]]);
+            let len = u16::from_le_bytes([system[pos + 2], system[pos + 3]]) as usize;
+            let Some(value) = system.get(pos + 4..pos + 4 + len) else {
+                break;
+            };
+            let value = decode_bytes(value.split(|&b| b == 0).next().unwrap_or_default());
+            let value = value.trim().to_string();
+            if !value.is_empty() {
+                match code {
+                    SYSTEM_CONTENTS_FILE => contents = Some(value),
+                    SYSTEM_TITLE => title = Some(value),
+                    _ => {}
+                }
+            }
+            pos += 4 + len;
+        }
+        (contents, title)
+    }
+}
+
+/// Find an attribute value inside a single tag.
+fn attribute_value(tag: &str, attr: &str) -> Option<String> {
+    let lower = tag.to_ascii_lowercase();
+    let mut search = 0;
+    while let Some(found) = lower[search..].find(attr) {
+        let start = search + found;
+        search = start + attr.len();
+        let preceded_ok = lower[..start].ends_with(|c: char| c.is_whitespace());
+        let rest = lower[search..].trim_start();
+        if !preceded_ok || !rest.starts_with('=') {
+            continue;
+        }
+        let value_start = tag.len() - rest.len() + 1;
+        let value = tag[value_start..].trim_start();
+        return Some(match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => value[1..].split(quote).next().unwrap_or_default().to_string(),
+            _ => value
+                .split(|c: char| c.is_whitespace() || c == '>')
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+        });
+    }
+    None
+}
+
+/// Parse a sitemap (`.hhc`) into `(name, local path)` pairs in document order.
+fn parse_sitemap(html: &str) -> Vec<(Option<String>, String)> {
+    let lower = html.to_ascii_lowercase();
+    let mut topics = Vec::new();
+    let mut search = 0;
+
+    while let Some(found) = lower[search..].find("<object") {
+        let start = search + found;
+        let end = lower[start..].find("</object").map_or(lower.len(), |e| start + e);
+        search = end;
+
+        let mut name = None;
+        let mut local = None;
+        let mut param_search = start;
+        while let Some(found) = lower[param_search..end].find("<param") {
+            let tag_start = param_search + found;
+            let tag_end = lower[tag_start..end].find('>').map_or(end, |e| tag_start + e + 1);
+            param_search = tag_end;
+
+            let tag = &html[tag_start..tag_end];
+            let Some(param) = attribute_value(tag, "name") else {
+                continue;
+            };
+            let value = attribute_value(tag, "value").filter(|v| !v.trim().is_empty());
+            match param.to_ascii_lowercase().as_str() {
+                "name" if name.is_none() => name = value,
+                "local" if local.is_none() => local = value,
+                _ => {}
+            }
+        }
+
+        if let Some(local) = local {
+            topics.push((name, local));
+        }
+    }
+
+    topics
+}
+
+/// Normalize a sitemap link to an archive path, relative to `base_dir`.
+fn resolve_local(base_dir: &str, local: &str) -> Option<String> {
+    // `ms-its:other.chm::/page.htm` style links point inside the (same) archive
+    let local = local.rsplit("::").next().unwrap_or(local);
+    let local = local.split(['#', '?']).next().unwrap_or_default().replace('\\', "/");
+    if local.is_empty() || local.contains("://") {
+        return None;
+    }
+
+    let joined = if local.starts_with('/') {
+        local
+    } else {
+        format!("{}/{}", base_dir.trim_end_matches('/'), local)
+    };
+
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in joined.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            _ => segments.push(segment),
+        }
+    }
+    Some(format!("/{}", segments.join("/")))
+}
+
+fn is_html_topic(name: &str) -> bool {
+    let lower = name.to_ascii_lowercase();
+    name.starts_with('/') && !name.starts_with("/#") && !name.starts_with("/$") && {
+        lower.ends_with(".htm") || lower.ends_with(".html")
+    }
+}
+
+/// Parse a CHM file into its topics.
+///
+/// Topics follow the table of contents; files missing from it are not
+/// returned. Without a table of contents every HTML file is returned in
+/// directory order.
+pub fn parse_chm(data: &[u8]) -> Result<ChmDocument> {
+    let mut archive = ChmArchive::parse(data)?;
+    let (contents_file, title) = archive.system_info();
+
+    let contents_path = contents_file
+        .and_then(|file| resolve_local("/", &file))
+        .filter(|path| archive.entry(path).is_some())
+        .or_else(|| {
+            archive
+                .entries
+                .iter()
+                .find(|entry| entry.name.to_ascii_lowercase().ends_with(".hhc"))
+                .map(|entry| entry.name.clone())
+        });
+
+    let mut listed: Vec<(Option<String>, String)> = Vec::new();
+    if let Some(path) = contents_path
+        && let Some(sitemap) = archive.read(&path)
+    {
+        let base_dir = path.rfind('/').map_or("/", |i| &path[..=i]).to_string();
+        let mut seen = HashSet::new();
+        for (name, local) in parse_sitemap(&decode_bytes(&sitemap)) {
+            if let Some(resolved) = resolve_local(&base_dir, &local)
+                && archive.entry(&resolved).is_some()
+                && seen.insert(resolved.to_lowercase())
+            {
+                listed.push((name, resolved));
+            }
+        }
+    }
+    if listed.is_empty() {
+        listed = archive
+            .entries
+            .iter()
+            .filter(|entry| is_html_topic(&entry.name))
+            .map(|entry| (None, entry.name.clone()))
+            .collect();
+    }
+
+    let mut topics = Vec::with_capacity(listed.len());
+    for (name, path) in listed {
+        if let Some(bytes) = archive.read(&path) {
+            topics.push(ChmTopic {
+                path,
+                title: name,
+                html: decode_bytes(&bytes),
+            });
+        }
+    }
+
+    if topics.is_empty() && archive.entries.is_empty() {
+        return Err(KreuzbergError::parsing("Invalid CHM file: empty directory"));
+    }
+
+    Ok(ChmDocument { title, topics })
+}
+
+#[cfg(test)]
+pub(crate) mod test_support {
+    use super::*;
+
+    pub(crate) fn encint(mut value: u64) -> Vec<u8> {
+        let mut bytes = vec![(value & 0x7F) as u8];
+        value >>= 7;
+        while value > 0 {
+            bytes.push((value & 0x7F) as u8 | 0x80);
+            value >>= 7;
+        }
+        bytes.reverse();
+        bytes
+    }
+
+    /// Encode data as LZX frames, each a single uncompressed block.
+    pub(crate) fn lzx_uncompressed_frames(data: &[u8]) -> (Vec<u8>, Vec<u64>) {
+        let mut stream = Vec::new();
+        let mut offsets = Vec::new();
+        for frame in data.chunks(LZX_FRAME_SIZE) {
+            offsets.push(stream.len() as u64);
+            // E8 flag (0), block type 3, 24-bit block size, 4 bits padding to align
+            let header = (3u32 << 28) | ((frame.len() as u32) << 4);
+            stream.extend_from_slice(&((header >> 16) as u16).to_le_bytes());
+            stream.extend_from_slice(&(header as u16).to_le_bytes());
+            for _ in 0..3 {
+                stream.extend_from_slice(&1u32.to_le_bytes());
+            }
+            stream.extend_from_slice(frame);
+            if frame.len() % 2 == 1 {
+                stream.push(0);
+            }
+        }
+        (stream, offsets)
+    }
+
+    /// Build a CHM with `plain` files in section 0 and `compressed` files in section 1.
+    pub(crate) fn build_chm(plain: &[(&str, Vec<u8>)], compressed: &[(&str, Vec<u8>)]) -> Vec<u8> {
+        let mut section0 = Vec::new();
+        let mut entries: Vec<(String, u64, u64, u64)> = Vec::new();
+        let mut add = |section0: &mut Vec<u8>, name: &str, bytes: &[u8]| {
+            entries.push((name.to_string(), 0, section0.len() as u64, bytes.len() as u64));
+            section0.extend_from_slice(bytes);
+        };
+        for (name, bytes) in plain {
+            add(&mut section0, name, bytes);
+        }
+
+        let mut section1 = Vec::new();
+        let mut compressed_entries = Vec::new();
+        for (name, bytes) in compressed {
+            compressed_entries.push((name.to_string(), 1, section1.len() as u64, bytes.len() as u64));
+            section1.extend_from_slice(bytes);
+        }
+        if !compressed.is_empty() {
+            let (stream, offsets) = lzx_uncompressed_frames(&section1);
+
+            let mut control = 6u32.to_le_bytes().to_vec();
+            control.extend_from_slice(b"LZXC");
+            for value in [2u32, 1, 2, 1, 0] {
+                control.extend_from_slice(&value.to_le_bytes());
+            }
+
+            let mut reset = Vec::new();
+            for value in [2u32, offsets.len() as u32, 8, 0x28] {
+                reset.extend_from_slice(&value.to_le_bytes());
+            }
+            for value in [section1.len() as u64, stream.len() as u64, LZX_FRAME_SIZE as u64] {
+                reset.extend_from_slice(&value.to_le_bytes());
+            }
+            for offset in offsets {
+                reset.extend_from_slice(&offset.to_le_bytes());
+            }
+
+            add(&mut section0, CONTENT_PATH, &stream);
+            add(&mut section0, CONTROL_DATA_PATH, &control);
+            add(&mut section0, RESET_TABLE_PATH, &reset);
+        }
+        entries.extend(compressed_entries);
+
+        const CHUNK_SIZE: usize = 0x1000;
+        let mut listing = Vec::new();
+        for (name, section, offset, length) in &entries {
+            listing.extend(encint(name.len() as u64));
+            listing.extend_from_slice(name.as_bytes());
+            listing.extend(encint(*section));
+            listing.extend(encint(*offset));
+            listing.extend(encint(*length));
+        }
+        let mut chunk = b"PMGL".to_vec();
+        chunk.extend_from_slice(&((CHUNK_SIZE - 0x14 - listing.len()) as u32).to_le_bytes());
+        chunk.extend_from_slice(&0u32.to_le_bytes());
+        chunk.extend_from_slice(&(-1i32).to_le_bytes());
+        chunk.extend_from_slice(&(-1i32).to_le_bytes());
+        chunk.extend_from_slice(&listing);
+        chunk.resize(CHUNK_SIZE, 0);
+
+        let mut itsp = vec![0u8; 0x54];
+        itsp[..4].copy_from_slice(b"ITSP");
+        itsp[4..8].copy_from_slice(&1u32.to_le_bytes());
+        itsp[8..12].copy_from_slice(&0x54u32.to_le_bytes());
+        itsp[0x10..0x14].copy_from_slice(&(CHUNK_SIZE as u32).to_le_bytes());
+        itsp[0x1C..0x20].copy_from_slice(&(-1i32).to_le_bytes());
+        itsp[0x2C..0x30].copy_from_slice(&1u32.to_le_bytes());
+        itsp.extend_from_slice(&chunk);
+
+        let dir_offset = 0x60u64;
+        let content_offset = dir_offset + itsp.len() as u64;
+        let mut data = vec![0u8; 0x60];
+        data[..4].copy_from_slice(b"ITSF");
+        data[4..8].copy_from_slice(&3u32.to_le_bytes());
+        data[8..12].copy_from_slice(&0x60u32.to_le_bytes());
+        data[0x48..0x50].copy_from_slice(&dir_offset.to_le_bytes());
+        data[0x50..0x58].copy_from_slice(&(itsp.len() as u64).to_le_bytes());
+        data[0x58..0x60].copy_from_slice(&content_offset.to_le_bytes());
+        data.extend_from_slice(&itsp);
+        data.extend_from_slice(&section0);
+        data
+    }
+
+    pub(crate) fn system_file(contents: &str, title: &str) -> Vec<u8> {
+        let mut system = 3u32.to_le_bytes().to_vec();
+        for (code, value) in [(SYSTEM_CONTENTS_FILE, contents), (SYSTEM_TITLE, title)] {
+            let mut bytes = value.as_bytes().to_vec();
+            bytes.push(0);
+            system.extend_from_slice(&code.to_le_bytes());
+            system.extend_from_slice(&(bytes.len() as u16).to_le_bytes());
+            system.extend_from_slice(&bytes);
+        }
+        system
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_support::*;
+    use super::*;
+
+    const SITEMAP: &str = r#"<HTML><BODY><UL>
+<LI><OBJECT type="text/sitemap">
+  <param name="Name" value="Getting Started">
+  <param name="Local" value="html/start.htm">
+</OBJECT>
+<UL><LI><OBJECT type="text/sitemap">
+  <param name="Name" value="Installation">
+  <param name="Local" value="html/install.htm#top">
+</OBJECT></UL>
+</UL></BODY></HTML>"#;
+
+    #[test]
+    fn test_parse_chm_uncompressed_topics_in_toc_order() {
+        let data = build_chm(
+            &[
+                ("/#SYSTEM", system_file("toc.hhc", "Product Help")),
+                ("/html/install.htm", b"<h1>Install</h1>".to_vec()),
+                ("/html/start.htm", b"<h1>Start</h1>".to_vec()),
+                ("/toc.hhc", SITEMAP.as_bytes().to_vec()),
+            ],
+            &[],
+        );
+
+        let document = parse_chm(&data).unwrap();
+        assert_eq!(document.title.as_deref(), Some("Product Help"));
+        let topics: Vec<_> = document
+            .topics
+            .iter()
+            .map(|t| (t.path.as_str(), t.title.as_deref(), t.html.as_str()))
+            .collect();
+        assert_eq!(
+            topics,
+            vec![
+                ("/html/start.htm", Some("Getting Started"), "<h1>Start</h1>"),
+                ("/html/install.htm", Some("Installation"), "<h1>Install</h1>"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_chm_lzx_section() {
+        let large = format!("<p>{}</p>", "lorem ipsum ".repeat(4000));
+        let data = build_chm(
+            &[("/#SYSTEM", system_file("", "Compressed"))],
+            &[
+                ("/a.htm", b"<h1>Alpha</h1>".to_vec()),
+                ("/b.html", large.clone().into_bytes()),
+                ("/c.css", b"body {}".to_vec()),
+            ],
+        );
+
+        let document = parse_chm(&data).unwrap();
+        assert_eq!(document.topics.len(), 2);
+        assert_eq!(document.topics[0].html, "<h1>Alpha</h1>");
+        assert_eq!(document.topics[1].html, large);
+    }
+
+    #[test]
+    fn test_parse_chm_windows_1252_topic() {
+        let data = build_chm(&[("/page.htm", b"<p>caf\xe9</p>".to_vec())], &[]);
+        assert_eq!(parse_chm(&data).unwrap().topics[0].html, "<p>café</p>");
+    }
+
+    #[test]
+    fn test_parse_chm_invalid() {
+        assert!(parse_chm(b"not a chm file").is_err());
+        assert!(parse_chm(b"ITSF").is_err());
+    }
+
+    #[test]
+    fn test_resolve_local() {
+        assert_eq!(resolve_local("/", "a/b.htm#x").as_deref(), Some("/a/b.htm"));
+        assert_eq!(resolve_local("/docs/", "../x.htm").as_deref(), Some("/x.htm"));
+        assert_eq!(
+            resolve_local("/", "ms-its:help.chm::/topic.htm").as_deref(),
+            Some("/topic.htm")
+        );
+        assert_eq!(resolve_local("/", "http://example.com/"), None);
+    }
+
+    #[test]
+    fn test_attribute_value() {
+        assert_eq!(
+            attribute_value(r#"<param name="Local" value='a b.htm'>"#, "value").as_deref(),
+            Some("a b.htm")
+        );
+        assert_eq!(
+            attribute_value("<param name=Name value=x>", "name").as_deref(),
+            Some("Name")
+        );
+        assert_eq!(attribute_value("<param value=x>", "name"), None);
+    }
+}
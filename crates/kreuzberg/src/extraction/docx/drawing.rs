@@ -2,7 +2,7 @@
 //!
 //! This module handles extraction and parsing of drawing objects (`<w:drawing>`)
 //! from DOCX documents. Drawing objects can be inline or anchored and may contain
-//! images or shapes.
+//! images, shapes, charts or SmartArt diagrams.
 
 use quick_xml::Reader;
 use quick_xml::events::{BytesStart, Event};
@@ -14,7 +14,9 @@ pub struct Drawing {
     pub drawing_type: DrawingType,
     pub extent: Option<Extent>,
     pub doc_properties: Option<DocProperties>,
-    pub image_ref: Option<String>, // r:embed rId value
+    pub image_ref: Option<String>,   // r:embed rId value
+    pub chart_ref: Option<String>,   // c:chart r:id value
+    pub diagram_ref: Option<String>, // dgm:relIds r:dm value (SmartArt data part)
 }
 
 /// Whether the drawing is inline or anchored.
@@ -83,13 +85,16 @@ pub enum WrapType {
 /// Parse a drawing object starting after the `<w:drawing>` Start event.
 ///
 /// This function reads events until it encounters the closing `</w:drawing>` tag,
-/// parsing the drawing type (inline or anchored), extent, properties, and image references.
+/// parsing the drawing type (inline or anchored), extent, properties, and image, chart
+/// and SmartArt references.
 pub fn parse_drawing(reader: &mut Reader<&[u8]>) -> Drawing {
     let mut drawing = Drawing {
         drawing_type: DrawingType::Inline,
         extent: None,
         doc_properties: None,
         image_ref: None,
+        chart_ref: None,
+        diagram_ref: None,
     };
 
     let mut depth = 1; // We've already consumed the <w:drawing> start
@@ -175,6 +180,12 @@ pub fn parse_drawing(reader: &mut Reader<&[u8]>) -> Drawing {
                     b"blip" => {
                         drawing.image_ref = get_attr(e, b"embed");
                     }
+                    b"chart" => {
+                        drawing.chart_ref = get_attr(e, b"id");
+                    }
+                    b"relIds" => {
+                        drawing.diagram_ref = get_attr(e, b"dm");
+                    }
                     b"wrapNone" => {
                         if let DrawingType::Anchored(ref mut anchor) = drawing.drawing_type {
                             anchor.wrap_type = WrapType::None;
@@ -301,6 +312,8 @@ mod tests {
                         extent: None,
                         doc_properties: None,
                         image_ref: None,
+                        chart_ref: None,
+                        diagram_ref: None,
                     };
                 }
                 Err(_) => {
@@ -309,6 +322,8 @@ mod tests {
                         extent: None,
                         doc_properties: None,
                         image_ref: None,
+                        chart_ref: None,
+                        diagram_ref: None,
                     };
                 }
                 _ => {}
@@ -546,6 +561,50 @@ mod tests {
         assert_eq!(drawing.image_ref, Some("rId9".to_string()));
     }
 
+    #[test]
+    fn test_parse_drawing_chart_and_smartart_refs() {
+        let xml = br#"<w:drawing xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"
+                        xmlns:wp="http://schemas.openxmlformats.org/drawingml/2006/wordprocessingDrawing"
+                        xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main"
+                        xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart"
+                        xmlns:dgm="http://schemas.openxmlformats.org/drawingml/2006/diagram"
+                        xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+          <wp:inline>
+            <wp:extent cx="5486400" cy="3200400"/>
+            <wp:docPr id="7" name="Chart 7"/>
+            <a:graphic>
+              <a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/chart">
+                <c:chart r:id="rId10"/>
+              </a:graphicData>
+            </a:graphic>
+          </wp:inline>
+        </w:drawing>"#;
+
+        let drawing = parse_drawing_from_xml(xml);
+        assert_eq!(drawing.chart_ref, Some("rId10".to_string()));
+        assert_eq!(drawing.diagram_ref, None);
+        assert_eq!(drawing.image_ref, None);
+
+        let xml = br#"<w:drawing xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"
+                        xmlns:wp="http://schemas.openxmlformats.org/drawingml/2006/wordprocessingDrawing"
+                        xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main"
+                        xmlns:dgm="http://schemas.openxmlformats.org/drawingml/2006/diagram"
+                        xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+          <wp:inline>
+            <wp:docPr id="8" name="Diagram 8"/>
+            <a:graphic>
+              <a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/diagram">
+                <dgm:relIds r:dm="rId11" r:lo="rId12" r:qs="rId13" r:cs="rId14"/>
+              </a:graphicData>
+            </a:graphic>
+          </wp:inline>
+        </w:drawing>"#;
+
+        let drawing = parse_drawing_from_xml(xml);
+        assert_eq!(drawing.diagram_ref, Some("rId11".to_string()));
+        assert_eq!(drawing.chart_ref, None);
+    }
+
     #[test]
     fn test_drawing_serialization() {
         let drawing = Drawing {
@@ -557,6 +616,8 @@ mod tests {
                 description: Some("Test description".to_string()),
             }),
             image_ref: Some("rId5".to_string()),
+            chart_ref: None,
+            diagram_ref: None,
         };
 
         // Serialize to JSON
@@ -46,6 +46,10 @@ pub struct Document {
     pub drawings: Vec<super::drawing::Drawing>,
     /// Image relationships (rId → target path) for image extraction.
     pub image_relationships: HashMap<String, String>,
+    /// Charts referenced by drawings, keyed by relationship ID.
+    pub charts: HashMap<String, crate::extraction::drawingml::Chart>,
+    /// SmartArt diagrams referenced by drawings, keyed by data-part relationship ID.
+    pub smartarts: HashMap<String, crate::extraction::drawingml::SmartArt>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -310,6 +314,13 @@ impl Document {
                         let Some(drawing) = self.drawings.get(*idx) else {
                             continue;
                         };
+                        if let Some(graphic) = self.drawing_graphic_markdown(drawing) {
+                            Self::ensure_blank_line(&mut output);
+                            output.push_str(&graphic);
+                            output.push('\n');
+                            prev_was_list = false;
+                            continue;
+                        }
                         let alt = drawing
                             .doc_properties
                             .as_ref()
@@ -380,6 +391,19 @@ impl Document {
         output
     }
 
+    /// Render a drawing that holds a chart or SmartArt diagram, if its part was resolved.
+    fn drawing_graphic_markdown(&self, drawing: &super::drawing::Drawing) -> Option<String> {
+        if let Some(chart) = drawing.chart_ref.as_ref().and_then(|rid| self.charts.get(rid)) {
+            return Some(chart.to_markdown());
+        }
+        drawing
+            .diagram_ref
+            .as_ref()
+            .and_then(|rid| self.smartarts.get(rid))
+            .filter(|smartart| !smartart.is_empty())
+            .map(|smartart| smartart.to_markdown())
+    }
+
     /// Helper: append a paragraph's markdown to output, managing list transitions.
     fn append_paragraph_markdown(
         &self,
@@ -839,13 +863,18 @@ impl<R: Read + Seek> DocxParser<R> {
         let mut document = Document::new();
 
         // Parse relationships first for hyperlink URL resolution
-        if let Ok(rels_xml) = self.read_file("word/_rels/document.xml.rels") {
-            self.relationships = Self::parse_relationships_xml(&rels_xml);
+        let rels_xml = self.read_file("word/_rels/document.xml.rels").ok();
+        if let Some(ref rels_xml) = rels_xml {
+            self.relationships = Self::parse_relationships_xml(rels_xml);
         }
 
         let document_xml = self.read_file("word/document.xml")?;
         self.parse_document_xml(&document_xml, &mut document)?;
 
+        if let Some(ref rels_xml) = rels_xml {
+            self.load_drawing_graphics(rels_xml, &mut document);
+        }
+
         if let Ok(numbering_xml) = self.read_file("word/numbering.xml") {
             let numbering_defs = self.parse_numbering(&numbering_xml)?;
             document.numbering_defs = numbering_defs;
@@ -877,6 +906,48 @@ impl<R: Read + Seek> DocxParser<R> {
         Ok(document)
     }
 
+    /// Resolve and parse chart and SmartArt parts referenced by drawings (best-effort).
+    fn load_drawing_graphics(&mut self, rels_xml: &str, document: &mut Document) {
+        use crate::extraction::drawingml;
+
+        let part_targets = drawingml::parse_relationship_targets(rels_xml);
+        let resolve = |rid: &str| {
+            part_targets
+                .get(rid)
+                .and_then(|target| drawingml::resolve_part_path("word/document.xml", target))
+        };
+
+        let chart_refs: Vec<String> = document.drawings.iter().filter_map(|d| d.chart_ref.clone()).collect();
+        for rid in chart_refs {
+            if document.charts.contains_key(&rid) {
+                continue;
+            }
+            let Some(path) = resolve(&rid) else { continue };
+            match self.read_file(&path).map(|xml| drawingml::parse_chart_xml(&xml)) {
+                Ok(Ok(chart)) => {
+                    document.charts.insert(rid, chart);
+                }
+                Ok(Err(e)) => tracing::debug!("Skipping unparseable DOCX chart '{}': {}", path, e),
+                Err(_) => tracing::debug!("DOCX chart part '{}' not found", path),
+            }
+        }
+
+        let diagram_refs: Vec<String> = document.drawings.iter().filter_map(|d| d.diagram_ref.clone()).collect();
+        for rid in diagram_refs {
+            if document.smartarts.contains_key(&rid) {
+                continue;
+            }
+            let Some(path) = resolve(&rid) else { continue };
+            match self.read_file(&path).map(|xml| drawingml::parse_smartart_xml(&xml)) {
+                Ok(Ok(smartart)) => {
+                    document.smartarts.insert(rid, smartart);
+                }
+                Ok(Err(e)) => tracing::debug!("Skipping unparseable DOCX SmartArt '{}': {}", path, e),
+                Err(_) => tracing::debug!("DOCX SmartArt part '{}' not found", path),
+            }
+        }
+    }
+
     /// Parse relationship file to get rId → target mappings for hyperlinks and images.
     fn parse_relationships_xml(xml: &str) -> HashMap<String, String> {
         let mut rels = HashMap::new();
@@ -0,0 +1,407 @@
+//! DrawingML chart part parsing (`c:chartSpace`).
+//!
+//! Charts store a cached copy of their source data next to each series reference
+//! (`c:strCache` / `c:numCache`), so series names, categories and values can be
+//! recovered without evaluating the spreadsheet formulas they point to.
+
+use roxmltree::{Document, Node};
+use serde::{Deserialize, Serialize};
+
+use super::{CHART_NAMESPACE, drawingml_paragraph_text};
+use crate::error::{KreuzbergError, Result};
+use crate::extraction::cells_to_markdown;
+
+/// Maximum number of data points read from a single series cache.
+const MAX_POINTS_PER_SERIES: usize = 100_000;
+
+/// A chart parsed from a `c:chartSpace` part.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Chart {
+    /// Plot type of the first plot group, e.g. `bar`, `line`, `pie`, `scatter`.
+    pub chart_type: Option<String>,
+    /// Chart title text.
+    pub title: Option<String>,
+    /// Title of the category (or X) axis.
+    pub category_axis_title: Option<String>,
+    /// Title of the value (or Y) axis.
+    pub value_axis_title: Option<String>,
+    /// Data series across all plot groups, in document order.
+    pub series: Vec<ChartSeries>,
+}
+
+/// A single chart data series.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ChartSeries {
+    /// Series name (legend entry).
+    pub name: Option<String>,
+    /// Category labels (or X values for scatter/bubble charts).
+    pub categories: Vec<String>,
+    /// Data values; `None` marks a missing point.
+    pub values: Vec<Option<f64>>,
+}
+
+impl Chart {
+    /// Whether the chart carries no title and no data.
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none()
+            && self
+                .series
+                .iter()
+                .all(|s| s.values.is_empty() && s.categories.is_empty())
+    }
+
+    /// Lay the chart data out as table rows: one row per category, one column per series.
+    ///
+    /// The first row is the header (category axis title followed by series names).
+    /// Returns an empty vector when the chart has no series.
+    pub fn to_cells(&self) -> Vec<Vec<String>> {
+        if self.series.is_empty() {
+            return Vec::new();
+        }
+
+        let categories = self
+            .series
+            .iter()
+            .map(|s| &s.categories)
+            .find(|c| !c.is_empty())
+            .cloned()
+            .unwrap_or_default();
+        let row_count = self
+            .series
+            .iter()
+            .map(|s| s.values.len())
+            .max()
+            .unwrap_or(0)
+            .max(categories.len());
+
+        let mut header = Vec::with_capacity(self.series.len() + 1);
+        header.push(
+            self.category_axis_title
+                .clone()
+                .unwrap_or_else(|| "Category".to_string()),
+        );
+        for (idx, series) in self.series.iter().enumerate() {
+            header.push(series.name.clone().unwrap_or_else(|| format!("Series {}", idx + 1)));
+        }
+
+        let mut rows = Vec::with_capacity(row_count + 1);
+        rows.push(header);
+        for row_idx in 0..row_count {
+            let mut row = Vec::with_capacity(self.series.len() + 1);
+            row.push(
+                categories
+                    .get(row_idx)
+                    .cloned()
+                    .unwrap_or_else(|| (row_idx + 1).to_string()),
+            );
+            for series in &self.series {
+                row.push(
+                    series
+                        .values
+                        .get(row_idx)
+                        .copied()
+                        .flatten()
+                        .map(format_value)
+                        .unwrap_or_default(),
+                );
+            }
+            rows.push(row);
+        }
+        rows
+    }
+
+    /// Render the chart as a caption line followed by a GFM table.
+    pub fn to_markdown(&self) -> String {
+        let mut output = String::new();
+        output.push_str("**Chart");
+        if let Some(ref title) = self.title {
+            output.push_str(": ");
+            output.push_str(title);
+        }
+        output.push_str("**");
+        if let Some(ref value_title) = self.value_axis_title {
+            output.push_str(" (");
+            output.push_str(value_title);
+            output.push(')');
+        }
+
+        let cells = self.to_cells();
+        if !cells.is_empty() {
+            output.push_str("\n\n");
+            output.push_str(cells_to_markdown(&cells).trim_end());
+        }
+        output
+    }
+
+    /// Render the chart as plain text: the title line followed by one space-separated line per row.
+    pub fn to_text(&self) -> String {
+        let mut lines = Vec::new();
+        if let Some(ref title) = self.title {
+            lines.push(title.clone());
+        }
+        for row in self.to_cells() {
+            let line = row
+                .iter()
+                .map(|cell| cell.trim())
+                .filter(|cell| !cell.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ");
+            lines.push(line);
+        }
+        lines.join("\n")
+    }
+}
+
+/// Format a data value without a trailing `.0` for whole numbers.
+fn format_value(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{}", value as i64)
+    } else {
+        value.to_string()
+    }
+}
+
+/// Parse a chart part (`word/charts/chart1.xml`, `ppt/charts/chart1.xml`, `xl/charts/chart1.xml`).
+pub fn parse_chart_xml(xml: &str) -> Result<Chart> {
+    let doc = Document::parse(xml).map_err(|e| KreuzbergError::parsing(format!("Failed to parse chart XML: {}", e)))?;
+
+    let chart_node = doc
+        .descendants()
+        .find(|n| is_chart_element(n, "chart"))
+        .ok_or_else(|| KreuzbergError::parsing("No <c:chart> element found".to_string()))?;
+
+    let mut chart = Chart {
+        title: child(&chart_node, "title").and_then(|t| title_text(&t)),
+        ..Default::default()
+    };
+
+    let Some(plot_area) = child(&chart_node, "plotArea") else {
+        return Ok(chart);
+    };
+
+    for plot in plot_area
+        .children()
+        .filter(|n| n.is_element() && n.tag_name().name().ends_with("Chart"))
+    {
+        if chart.chart_type.is_none() {
+            let name = plot.tag_name().name();
+            chart.chart_type = Some(name.trim_end_matches("Chart").to_string());
+        }
+        for ser in plot.children().filter(|n| is_chart_element(n, "ser")) {
+            chart.series.push(parse_series(&ser));
+        }
+    }
+
+    for axis in plot_area.children().filter(|n| n.is_element()) {
+        let title = child(&axis, "title").and_then(|t| title_text(&t));
+        match axis.tag_name().name() {
+            "catAx" | "dateAx" => chart.category_axis_title = chart.category_axis_title.take().or(title),
+            "valAx" => {
+                // Scatter charts use two value axes; the bottom one is the X axis
+                let is_x_axis = child(&axis, "axPos").and_then(|p| p.attribute("val")) == Some("b");
+                if is_x_axis && chart.chart_type.as_deref() == Some("scatter") {
+                    chart.category_axis_title = chart.category_axis_title.take().or(title);
+                } else {
+                    chart.value_axis_title = chart.value_axis_title.take().or(title);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(chart)
+}
+
+fn parse_series(ser: &Node) -> ChartSeries {
+    let name = child(ser, "tx").and_then(|tx| {
+        child(&tx, "v")
+            .and_then(|v| v.text().map(str::to_string))
+            .or_else(|| cached_strings(&tx).into_iter().next())
+    });
+
+    let categories = child(ser, "cat")
+        .or_else(|| child(ser, "xVal"))
+        .map(|cat| cached_strings(&cat))
+        .unwrap_or_default();
+
+    let values = child(ser, "val")
+        .or_else(|| child(ser, "yVal"))
+        .map(|val| {
+            cached_strings(&val)
+                .into_iter()
+                .map(|v| v.trim().parse::<f64>().ok())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    ChartSeries {
+        name: name.filter(|n| !n.trim().is_empty()),
+        categories,
+        values,
+    }
+}
+
+/// Read the cached points (`c:pt idx=".."><c:v>`) beneath a data reference, in index order.
+///
+/// Multi-level category caches are flattened by joining the levels of each point with `" / "`.
+fn cached_strings(node: &Node) -> Vec<String> {
+    let caches: Vec<Node> = node
+        .descendants()
+        .filter(|n| is_chart_element(n, "strCache") || is_chart_element(n, "numCache") || is_chart_element(n, "lvl"))
+        .collect();
+
+    let levels: Vec<Vec<String>> = caches
+        .iter()
+        .filter(|cache| {
+            // Skip `strCache`/`multiLvlStrCache` wrappers whose points live in nested `lvl` elements
+            cache.children().any(|n| is_chart_element(&n, "pt"))
+        })
+        .map(|cache| {
+            let declared = child(cache, "ptCount")
+                .and_then(|c| c.attribute("val"))
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(0);
+            let mut points = vec![String::new(); declared.min(MAX_POINTS_PER_SERIES)];
+            for pt in cache.children().filter(|n| is_chart_element(n, "pt")) {
+                let Some(idx) = pt.attribute("idx").and_then(|i| i.parse::<usize>().ok()) else {
+                    continue;
+                };
+                if idx >= MAX_POINTS_PER_SERIES {
+                    continue;
+                }
+                if idx >= points.len() {
+                    points.resize(idx + 1, String::new());
+                }
+                points[idx] = child(&pt, "v").and_then(|v| v.text()).unwrap_or_default().to_string();
+            }
+            points
+        })
+        .collect();
+
+    match levels.len() {
+        0 => Vec::new(),
+        1 => levels.into_iter().next().unwrap_or_default(),
+        _ => {
+            // Multi-level: the innermost level comes first in the file; render outer levels first
+            let len = levels.iter().map(Vec::len).max().unwrap_or(0);
+            (0..len)
+                .map(|i| {
+                    levels
+                        .iter()
+                        .rev()
+                        .filter_map(|level| level.get(i).filter(|s| !s.is_empty()))
+                        .cloned()
+                        .collect::<Vec<_>>()
+                        .join(" / ")
+                })
+                .collect()
+        }
+    }
+}
+
+/// Extract title text from either rich text (`c:tx/c:rich`) or a cached string reference.
+fn title_text(title: &Node) -> Option<String> {
+    let tx = child(title, "tx")?;
+    let text = if let Some(rich) = child(&tx, "rich") {
+        drawingml_paragraph_text(rich).replace('\n', " ")
+    } else {
+        cached_strings(&tx).join(" ")
+    };
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+fn child<'a, 'input>(node: &Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|n| is_chart_element(n, name))
+}
+
+fn is_chart_element(node: &Node, name: &str) -> bool {
+    node.is_element() && node.tag_name().name() == name && node.tag_name().namespace() == Some(CHART_NAMESPACE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BAR_CHART: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<c:chartSpace xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart"
+              xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main">
+  <c:chart>
+    <c:title><c:tx><c:rich><a:bodyPr/><a:p><a:r><a:t>Revenue</a:t></a:r><a:r><a:t> by year</a:t></a:r></a:p></c:rich></c:tx></c:title>
+    <c:plotArea>
+      <c:barChart>
+        <c:barDir val="col"/>
+        <c:ser>
+          <c:idx val="0"/>
+          <c:tx><c:strRef><c:f>Sheet1!$B$1</c:f><c:strCache><c:ptCount val="1"/><c:pt idx="0"><c:v>North</c:v></c:pt></c:strCache></c:strRef></c:tx>
+          <c:cat><c:strRef><c:f>Sheet1!$A$2:$A$4</c:f><c:strCache><c:ptCount val="3"/>
+            <c:pt idx="0"><c:v>2021</c:v></c:pt><c:pt idx="1"><c:v>2022</c:v></c:pt><c:pt idx="2"><c:v>2023</c:v></c:pt>
+          </c:strCache></c:strRef></c:cat>
+          <c:val><c:numRef><c:f>Sheet1!$B$2:$B$4</c:f><c:numCache><c:formatCode>General</c:formatCode><c:ptCount val="3"/>
+            <c:pt idx="0"><c:v>10</c:v></c:pt><c:pt idx="2"><c:v>12.5</c:v></c:pt>
+          </c:numCache></c:numRef></c:val>
+        </c:ser>
+        <c:ser>
+          <c:idx val="1"/>
+          <c:tx><c:v>South</c:v></c:tx>
+          <c:val><c:numRef><c:numCache><c:ptCount val="3"/>
+            <c:pt idx="0"><c:v>7</c:v></c:pt><c:pt idx="1"><c:v>8</c:v></c:pt><c:pt idx="2"><c:v>9</c:v></c:pt>
+          </c:numCache></c:numRef></c:val>
+        </c:ser>
+      </c:barChart>
+      <c:catAx><c:axId val="1"/><c:title><c:tx><c:rich><a:p><a:r><a:t>Year</a:t></a:r></a:p></c:rich></c:tx></c:title></c:catAx>
+      <c:valAx><c:axId val="2"/><c:title><c:tx><c:rich><a:p><a:r><a:t>EUR m</a:t></a:r></a:p></c:rich></c:tx></c:title></c:valAx>
+    </c:plotArea>
+  </c:chart>
+</c:chartSpace>"#;
+
+    #[test]
+    fn test_parse_bar_chart() {
+        let chart = parse_chart_xml(BAR_CHART).unwrap();
+
+        assert_eq!(chart.chart_type.as_deref(), Some("bar"));
+        assert_eq!(chart.title.as_deref(), Some("Revenue by year"));
+        assert_eq!(chart.category_axis_title.as_deref(), Some("Year"));
+        assert_eq!(chart.value_axis_title.as_deref(), Some("EUR m"));
+        assert_eq!(chart.series.len(), 2);
+        assert_eq!(chart.series[0].name.as_deref(), Some("North"));
+        assert_eq!(chart.series[0].categories, vec!["2021", "2022", "2023"]);
+        assert_eq!(chart.series[0].values, vec![Some(10.0), None, Some(12.5)]);
+        assert_eq!(chart.series[1].name.as_deref(), Some("South"));
+    }
+
+    #[test]
+    fn test_chart_to_markdown_table() {
+        let chart = parse_chart_xml(BAR_CHART).unwrap();
+        let markdown = chart.to_markdown();
+
+        assert!(markdown.starts_with("**Chart: Revenue by year** (EUR m)"));
+        assert!(markdown.contains("| Year | North | South |"));
+        assert!(markdown.contains("| 2022 |  | 8 |"));
+        assert!(markdown.contains("| 2023 | 12.5 | 9 |"));
+    }
+
+    #[test]
+    fn test_parse_multi_level_categories() {
+        let xml = r#"<c:chartSpace xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart">
+  <c:chart><c:plotArea><c:lineChart><c:ser>
+    <c:cat><c:multiLvlStrRef><c:multiLvlStrCache><c:ptCount val="2"/>
+      <c:lvl><c:pt idx="0"><c:v>Q1</c:v></c:pt><c:pt idx="1"><c:v>Q2</c:v></c:pt></c:lvl>
+      <c:lvl><c:pt idx="0"><c:v>2024</c:v></c:pt></c:lvl>
+    </c:multiLvlStrCache></c:multiLvlStrRef></c:cat>
+    <c:val><c:numRef><c:numCache><c:ptCount val="2"/><c:pt idx="0"><c:v>1</c:v></c:pt><c:pt idx="1"><c:v>2</c:v></c:pt></c:numCache></c:numRef></c:val>
+  </c:ser></c:lineChart></c:plotArea></c:chart>
+</c:chartSpace>"#;
+        let chart = parse_chart_xml(xml).unwrap();
+
+        assert_eq!(chart.chart_type.as_deref(), Some("line"));
+        assert_eq!(chart.series[0].categories, vec!["2024 / Q1", "Q2"]);
+        assert_eq!(chart.to_cells()[0], vec!["Category", "Series 1"]);
+    }
+
+    #[test]
+    fn test_parse_chart_invalid_xml() {
+        assert!(parse_chart_xml("<c:chartSpace").is_err());
+        assert!(parse_chart_xml("<root/>").is_err());
+    }
+}
@@ -0,0 +1,173 @@
+//! Shared DrawingML graphic parsing for OOXML documents.
+//!
+//! DOCX, PPTX and XLSX embed charts (`c:chartSpace` parts) and SmartArt diagrams
+//! (`dgm:dataModel` parts) as separate package parts referenced from a
+//! `<a:graphicData>` element by relationship ID. This module parses those parts
+//! into small typed structures so every OOXML extractor can render them the same way.
+
+pub mod chart;
+pub mod smartart;
+
+use std::collections::HashMap;
+
+use roxmltree::Document;
+
+pub use chart::{Chart, ChartSeries, parse_chart_xml};
+pub use smartart::{SmartArt, SmartArtNode, parse_smartart_xml};
+
+/// `graphicData` URI identifying an embedded chart.
+pub const CHART_URI: &str = "http://schemas.openxmlformats.org/drawingml/2006/chart";
+
+/// `graphicData` URI identifying an embedded SmartArt diagram.
+pub const DIAGRAM_URI: &str = "http://schemas.openxmlformats.org/drawingml/2006/diagram";
+
+/// DrawingML chart namespace (`c:` prefix).
+pub const CHART_NAMESPACE: &str = "http://schemas.openxmlformats.org/drawingml/2006/chart";
+
+/// DrawingML diagram namespace (`dgm:` prefix).
+pub const DIAGRAM_NAMESPACE: &str = "http://schemas.openxmlformats.org/drawingml/2006/diagram";
+
+/// Parse a `.rels` part into an `Id → Target` map covering all relationship types.
+///
+/// Malformed XML yields an empty map; relationships are best-effort lookups.
+pub fn parse_relationship_targets(xml: &str) -> HashMap<String, String> {
+    let Ok(doc) = Document::parse(xml) else {
+        return HashMap::new();
+    };
+
+    doc.descendants()
+        .filter(|n| n.tag_name().name() == "Relationship")
+        .filter(|n| n.attribute("TargetMode") != Some("External"))
+        .filter_map(|n| Some((n.attribute("Id")?.to_string(), n.attribute("Target")?.to_string())))
+        .collect()
+}
+
+/// Collect the targets of all relationships whose `Type` ends with `/{type_suffix}`, in file order.
+///
+/// ```
+/// # use kreuzberg::extraction::drawingml::relationship_targets_of_type;
+/// let xml = r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+///   <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/drawing" Target="../drawings/drawing1.xml"/>
+/// </Relationships>"#;
+/// assert_eq!(relationship_targets_of_type(xml, "drawing"), vec!["../drawings/drawing1.xml"]);
+/// ```
+pub fn relationship_targets_of_type(xml: &str, type_suffix: &str) -> Vec<String> {
+    let Ok(doc) = Document::parse(xml) else {
+        return Vec::new();
+    };
+
+    doc.descendants()
+        .filter(|n| n.tag_name().name() == "Relationship")
+        .filter(|n| {
+            n.attribute("Type")
+                .and_then(|t| t.rsplit_once('/'))
+                .is_some_and(|(_, suffix)| suffix == type_suffix)
+        })
+        .filter_map(|n| n.attribute("Target").map(str::to_string))
+        .collect()
+}
+
+/// Resolve a relationship target against the part that owns the relationship.
+///
+/// Absolute targets (`/ppt/charts/chart1.xml`) are made archive-relative, relative
+/// targets are joined with the source part's directory and `..` segments collapsed.
+/// Returns `None` when the target escapes the package root.
+///
+/// # Examples
+///
+/// ```
+/// # use kreuzberg::extraction::drawingml::resolve_part_path;
+/// assert_eq!(
+///     resolve_part_path("ppt/slides/slide1.xml", "../charts/chart1.xml").as_deref(),
+///     Some("ppt/charts/chart1.xml")
+/// );
+/// assert_eq!(
+///     resolve_part_path("word/document.xml", "charts/chart1.xml").as_deref(),
+///     Some("word/charts/chart1.xml")
+/// );
+/// ```
+pub fn resolve_part_path(source_part: &str, target: &str) -> Option<String> {
+    if let Some(absolute) = target.strip_prefix('/') {
+        return normalize_segments(Vec::new(), absolute);
+    }
+    let mut segments: Vec<&str> = source_part.split('/').collect();
+    // Drop the source part's file name to get its directory
+    segments.pop();
+    normalize_segments(segments, target)
+}
+
+/// Path of the `.rels` part holding the relationships of `part` (`xl/worksheets/_rels/sheet1.xml.rels`).
+pub fn part_rels_path(part: &str) -> String {
+    match part.rsplit_once('/') {
+        Some((dir, file)) => format!("{}/_rels/{}.rels", dir, file),
+        None => format!("_rels/{}.rels", part),
+    }
+}
+
+fn normalize_segments<'a>(mut segments: Vec<&'a str>, target: &'a str) -> Option<String> {
+    for segment in target.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop()?;
+            }
+            other => segments.push(other),
+        }
+    }
+    Some(segments.join("/"))
+}
+
+/// Collect the text of all `<a:t>` runs beneath a node, one line per `<a:p>` paragraph.
+pub(crate) fn drawingml_paragraph_text(node: roxmltree::Node) -> String {
+    let paragraphs: Vec<String> = node
+        .descendants()
+        .filter(|n| n.is_element() && n.tag_name().name() == "p")
+        .map(|p| {
+            p.descendants()
+                .filter(|n| n.is_element() && n.tag_name().name() == "t")
+                .filter_map(|t| t.text())
+                .collect::<String>()
+        })
+        .filter(|text| !text.trim().is_empty())
+        .collect();
+    paragraphs.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_part_path_parent_segments() {
+        assert_eq!(
+            resolve_part_path("xl/drawings/drawing1.xml", "../charts/chart2.xml").as_deref(),
+            Some("xl/charts/chart2.xml")
+        );
+        assert_eq!(
+            resolve_part_path("ppt/slides/slide1.xml", "/ppt/diagrams/data1.xml").as_deref(),
+            Some("ppt/diagrams/data1.xml")
+        );
+        assert_eq!(resolve_part_path("word/document.xml", "../../evil.xml"), None);
+    }
+
+    #[test]
+    fn test_part_rels_path() {
+        assert_eq!(
+            part_rels_path("xl/worksheets/sheet1.xml"),
+            "xl/worksheets/_rels/sheet1.xml.rels"
+        );
+        assert_eq!(part_rels_path("workbook.xml"), "_rels/workbook.xml.rels");
+    }
+
+    #[test]
+    fn test_parse_relationship_targets_skips_external() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/chart" Target="charts/chart1.xml"/>
+  <Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink" Target="https://example.com" TargetMode="External"/>
+</Relationships>"#;
+        let rels = parse_relationship_targets(xml);
+        assert_eq!(rels.get("rId1").map(String::as_str), Some("charts/chart1.xml"));
+        assert!(!rels.contains_key("rId2"));
+    }
+}
@@ -0,0 +1,255 @@
+//! SmartArt diagram data parsing (`dgm:dataModel`).
+//!
+//! The data part of a SmartArt diagram (`diagrams/data1.xml`) holds a flat list of
+//! points (`dgm:pt`) and parent/child connections (`dgm:cxn`). The visual layout
+//! lives in separate parts and is ignored; only the node text hierarchy is kept.
+
+use std::collections::{HashMap, HashSet};
+
+use roxmltree::{Document, Node};
+use serde::{Deserialize, Serialize};
+
+use super::{DIAGRAM_NAMESPACE, drawingml_paragraph_text};
+use crate::error::{KreuzbergError, Result};
+
+/// Maximum nesting depth followed when building the SmartArt tree.
+const MAX_SMARTART_DEPTH: usize = 32;
+
+/// A SmartArt diagram reduced to its node text hierarchy.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SmartArt {
+    /// Top-level nodes in diagram order.
+    pub nodes: Vec<SmartArtNode>,
+}
+
+/// A single SmartArt node with its nested children.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SmartArtNode {
+    pub text: String,
+    pub children: Vec<SmartArtNode>,
+}
+
+impl SmartArt {
+    /// Whether the diagram contains no text.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Flatten the tree into `(depth, text)` pairs in reading order.
+    pub fn flatten(&self) -> Vec<(usize, &str)> {
+        fn walk<'a>(nodes: &'a [SmartArtNode], depth: usize, out: &mut Vec<(usize, &'a str)>) {
+            for node in nodes {
+                out.push((depth, node.text.as_str()));
+                walk(&node.children, depth + 1, out);
+            }
+        }
+
+        let mut out = Vec::new();
+        walk(&self.nodes, 0, &mut out);
+        out
+    }
+
+    /// Render the diagram as a nested markdown bullet list.
+    pub fn to_markdown(&self) -> String {
+        self.flatten()
+            .into_iter()
+            .map(|(depth, text)| format!("{}- {}", "  ".repeat(depth), text.replace('\n', " ")))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Render the diagram as plain text, one node per line indented by depth.
+    pub fn to_text(&self) -> String {
+        self.flatten()
+            .into_iter()
+            .map(|(depth, text)| format!("{}{}", "  ".repeat(depth), text.replace('\n', " ")))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Parse a SmartArt data model part (`word/diagrams/data1.xml`, `ppt/diagrams/data1.xml`, ...).
+pub fn parse_smartart_xml(xml: &str) -> Result<SmartArt> {
+    let doc =
+        Document::parse(xml).map_err(|e| KreuzbergError::parsing(format!("Failed to parse SmartArt XML: {}", e)))?;
+
+    let pt_lst = doc
+        .descendants()
+        .find(|n| is_diagram_element(n, "ptLst"))
+        .ok_or_else(|| KreuzbergError::parsing("No <dgm:ptLst> element found".to_string()))?;
+
+    // modelId → text for content points; the document root is tracked separately
+    let mut texts: HashMap<&str, String> = HashMap::new();
+    let mut root_id = None;
+    for pt in pt_lst.children().filter(|n| is_diagram_element(n, "pt")) {
+        let Some(model_id) = pt.attribute("modelId") else {
+            continue;
+        };
+        match pt.attribute("type").unwrap_or("node") {
+            "doc" => root_id = Some(model_id),
+            "node" | "asst" => {
+                let text = pt
+                    .children()
+                    .find(|n| is_diagram_element(n, "t"))
+                    .map(drawingml_paragraph_text)
+                    .unwrap_or_default();
+                texts.insert(model_id, text);
+            }
+            _ => {}
+        }
+    }
+
+    // Parent-of connections, ordered by source order
+    let mut children: HashMap<&str, Vec<(u32, &str)>> = HashMap::new();
+    let mut has_parent: HashSet<&str> = HashSet::new();
+    if let Some(cxn_lst) = doc.descendants().find(|n| is_diagram_element(n, "cxnLst")) {
+        for cxn in cxn_lst.children().filter(|n| is_diagram_element(n, "cxn")) {
+            if cxn.attribute("type").unwrap_or("parOf") != "parOf" {
+                continue;
+            }
+            let (Some(src), Some(dest)) = (cxn.attribute("srcId"), cxn.attribute("destId")) else {
+                continue;
+            };
+            let order = cxn.attribute("srcOrd").and_then(|o| o.parse().ok()).unwrap_or(0);
+            children.entry(src).or_default().push((order, dest));
+            has_parent.insert(dest);
+        }
+    }
+    for list in children.values_mut() {
+        list.sort_by_key(|(order, _)| *order);
+    }
+
+    let mut visited = HashSet::new();
+    let nodes = match root_id {
+        Some(root) => build_nodes(root, &children, &texts, &mut visited, 0),
+        None => {
+            // No document point: treat every parentless node as top-level, in list order
+            let top_level: Vec<&str> = pt_lst
+                .children()
+                .filter(|n| is_diagram_element(n, "pt"))
+                .filter_map(|n| n.attribute("modelId"))
+                .filter(|id| texts.contains_key(id) && !has_parent.contains(id))
+                .collect();
+            top_level
+                .into_iter()
+                .flat_map(|id| build_node(id, &children, &texts, &mut visited, 0))
+                .collect()
+        }
+    };
+
+    Ok(SmartArt { nodes })
+}
+
+fn build_nodes<'a>(
+    parent: &'a str,
+    children: &HashMap<&'a str, Vec<(u32, &'a str)>>,
+    texts: &HashMap<&'a str, String>,
+    visited: &mut HashSet<&'a str>,
+    depth: usize,
+) -> Vec<SmartArtNode> {
+    if depth > MAX_SMARTART_DEPTH {
+        return Vec::new();
+    }
+    children
+        .get(parent)
+        .map(|list| {
+            list.iter()
+                .flat_map(|(_, id)| build_node(id, children, texts, visited, depth))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Build the subtree for `id`, splicing children of text-less nodes into the parent level.
+fn build_node<'a>(
+    id: &'a str,
+    children: &HashMap<&'a str, Vec<(u32, &'a str)>>,
+    texts: &HashMap<&'a str, String>,
+    visited: &mut HashSet<&'a str>,
+    depth: usize,
+) -> Vec<SmartArtNode> {
+    if !visited.insert(id) {
+        return Vec::new();
+    }
+    let Some(text) = texts.get(id) else {
+        return Vec::new();
+    };
+    if text.trim().is_empty() {
+        return build_nodes(id, children, texts, visited, depth + 1);
+    }
+    vec![SmartArtNode {
+        text: text.trim().to_string(),
+        children: build_nodes(id, children, texts, visited, depth + 1),
+    }]
+}
+
+fn is_diagram_element(node: &Node, name: &str) -> bool {
+    node.is_element() && node.tag_name().name() == name && node.tag_name().namespace() == Some(DIAGRAM_NAMESPACE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HIERARCHY: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<dgm:dataModel xmlns:dgm="http://schemas.openxmlformats.org/drawingml/2006/diagram"
+               xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main">
+  <dgm:ptLst>
+    <dgm:pt modelId="0" type="doc"><dgm:t><a:p><a:endParaRPr/></a:p></dgm:t></dgm:pt>
+    <dgm:pt modelId="1"><dgm:t><a:p><a:r><a:t>Strategy</a:t></a:r></a:p></dgm:t></dgm:pt>
+    <dgm:pt modelId="2"><dgm:t><a:p><a:r><a:t>Grow </a:t></a:r><a:r><a:t>revenue</a:t></a:r></a:p></dgm:t></dgm:pt>
+    <dgm:pt modelId="3"><dgm:t><a:p><a:r><a:t>Cut costs</a:t></a:r></a:p></dgm:t></dgm:pt>
+    <dgm:pt modelId="4"><dgm:t><a:p><a:r><a:t>Execution</a:t></a:r></a:p></dgm:t></dgm:pt>
+    <dgm:pt modelId="9" type="parTrans"/>
+    <dgm:pt modelId="10" type="pres"><dgm:t/></dgm:pt>
+  </dgm:ptLst>
+  <dgm:cxnLst>
+    <dgm:cxn modelId="20" srcId="0" destId="4" srcOrd="1"/>
+    <dgm:cxn modelId="21" srcId="0" destId="1" srcOrd="0"/>
+    <dgm:cxn modelId="22" srcId="1" destId="3" srcOrd="1"/>
+    <dgm:cxn modelId="23" srcId="1" destId="2" srcOrd="0"/>
+    <dgm:cxn modelId="24" type="presOf" srcId="1" destId="10"/>
+  </dgm:cxnLst>
+</dgm:dataModel>"#;
+
+    #[test]
+    fn test_parse_smartart_hierarchy() {
+        let smartart = parse_smartart_xml(HIERARCHY).unwrap();
+
+        assert_eq!(smartart.nodes.len(), 2);
+        assert_eq!(smartart.nodes[0].text, "Strategy");
+        assert_eq!(smartart.nodes[0].children.len(), 2);
+        assert_eq!(smartart.nodes[0].children[0].text, "Grow revenue");
+        assert_eq!(smartart.nodes[0].children[1].text, "Cut costs");
+        assert_eq!(smartart.nodes[1].text, "Execution");
+    }
+
+    #[test]
+    fn test_smartart_to_markdown() {
+        let smartart = parse_smartart_xml(HIERARCHY).unwrap();
+
+        assert_eq!(
+            smartart.to_markdown(),
+            "- Strategy\n  - Grow revenue\n  - Cut costs\n- Execution"
+        );
+    }
+
+    #[test]
+    fn test_parse_smartart_cyclic_connections() {
+        let xml = r#"<dgm:dataModel xmlns:dgm="http://schemas.openxmlformats.org/drawingml/2006/diagram"
+               xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main">
+  <dgm:ptLst>
+    <dgm:pt modelId="1"><dgm:t><a:p><a:r><a:t>A</a:t></a:r></a:p></dgm:t></dgm:pt>
+    <dgm:pt modelId="2"><dgm:t><a:p><a:r><a:t>B</a:t></a:r></a:p></dgm:t></dgm:pt>
+  </dgm:ptLst>
+  <dgm:cxnLst>
+    <dgm:cxn modelId="3" srcId="1" destId="2"/>
+    <dgm:cxn modelId="4" srcId="2" destId="1"/>
+  </dgm:cxnLst>
+</dgm:dataModel>"#;
+        let smartart = parse_smartart_xml(xml).unwrap();
+
+        // Every node has a parent and there is no document point, so nothing is reachable
+        assert!(smartart.is_empty());
+    }
+}
@@ -33,6 +33,7 @@ use std::fmt::Write as FmtWrite;
 use std::io::{Cursor, Read, Seek};
 use std::path::Path;
 
+use crate::core::config::ExtractionConfig;
 use crate::error::{KreuzbergError, Result};
 use crate::extraction::capacity;
 use crate::types::{ExcelSheet, ExcelWorkbook};
@@ -52,6 +53,15 @@ use crate::extraction::office_metadata::{
 use serde_json::Value;
 
 pub fn read_excel_file(file_path: &str) -> Result<ExcelWorkbook> {
+    read_excel_file_impl(file_path, None)
+}
+
+/// Like [`read_excel_file`], checking `config` for cancellation before each sheet.
+pub(crate) fn read_excel_file_with_config(file_path: &str, config: &ExtractionConfig) -> Result<ExcelWorkbook> {
+    read_excel_file_impl(file_path, Some(config))
+}
+
+fn read_excel_file_impl(file_path: &str, extraction_config: Option<&ExtractionConfig>) -> Result<ExcelWorkbook> {
     let lower_path = file_path.to_lowercase();
 
     #[cfg(feature = "office")]
@@ -73,7 +83,15 @@ pub fn read_excel_file(file_path: &str) -> Result<ExcelWorkbook> {
         let file = std::fs::File::open(file_path)?;
         let workbook = calamine::Xlsx::new(std::io::BufReader::new(file))
             .map_err(|e| KreuzbergError::parsing(format!("Failed to parse XLSX: {}", e)))?;
-        return process_xlsx_workbook(workbook, office_metadata);
+        #[cfg_attr(not(feature = "office"), allow(unused_mut))]
+        let mut workbook = process_xlsx_workbook(workbook, office_metadata, extraction_config)?;
+        #[cfg(feature = "office")]
+        if let Ok(file) = std::fs::File::open(file_path)
+            && let Ok(mut archive) = zip::ZipArchive::new(file)
+        {
+            attach_xlsx_drawings(&mut workbook, &mut archive);
+        }
+        return Ok(workbook);
     }
 
     // For .xlam (Excel add-in), try XLSX parsing but gracefully return empty workbook on failure
@@ -81,7 +99,7 @@ pub fn read_excel_file(file_path: &str) -> Result<ExcelWorkbook> {
         let file = std::fs::File::open(file_path)?;
         match calamine::Xlsx::new(std::io::BufReader::new(file)) {
             Ok(workbook) => {
-                return process_xlsx_workbook(workbook, office_metadata);
+                return process_xlsx_workbook(workbook, office_metadata, extraction_config);
             }
             Err(_) => {
                 // .xlam files may not contain proper workbook data - return empty workbook
@@ -98,7 +116,7 @@ pub fn read_excel_file(file_path: &str) -> Result<ExcelWorkbook> {
         let file = std::fs::File::open(file_path)?;
         match calamine::Xls::new(std::io::BufReader::new(file)) {
             Ok(workbook) => {
-                return process_workbook(workbook, office_metadata);
+                return process_workbook(workbook, office_metadata, extraction_config);
             }
             Err(_) => {
                 return Ok(ExcelWorkbook {
@@ -114,7 +132,7 @@ pub fn read_excel_file(file_path: &str) -> Result<ExcelWorkbook> {
         let file = std::fs::File::open(file_path)?;
         let workbook = calamine::Xlsb::new(std::io::BufReader::new(file))
             .map_err(|e| KreuzbergError::parsing(format!("Failed to parse XLSB: {}", e)))?;
-        return process_workbook(workbook, office_metadata);
+        return process_workbook(workbook, office_metadata, extraction_config);
     }
 
     // For other formats, use open_workbook_auto
@@ -133,10 +151,27 @@ pub fn read_excel_file(file_path: &str) -> Result<ExcelWorkbook> {
         Err(e) => return Err(KreuzbergError::parsing(format!("Failed to parse Excel file: {}", e))),
     };
 
-    process_workbook(workbook, office_metadata)
+    process_workbook(workbook, office_metadata, extraction_config)
 }
 
 pub fn read_excel_bytes(data: &[u8], file_extension: &str) -> Result<ExcelWorkbook> {
+    read_excel_bytes_impl(data, file_extension, None)
+}
+
+/// Like [`read_excel_bytes`], checking `config` for cancellation before each sheet.
+pub(crate) fn read_excel_bytes_with_config(
+    data: &[u8],
+    file_extension: &str,
+    config: &ExtractionConfig,
+) -> Result<ExcelWorkbook> {
+    read_excel_bytes_impl(data, file_extension, Some(config))
+}
+
+fn read_excel_bytes_impl(
+    data: &[u8],
+    file_extension: &str,
+    extraction_config: Option<&ExtractionConfig>,
+) -> Result<ExcelWorkbook> {
     #[cfg(feature = "office")]
     let office_metadata = match file_extension.to_lowercase().as_str() {
         ".xlsx" | ".xlsm" | ".xlam" | ".xltm" => extract_xlsx_office_metadata_from_bytes(data).ok(),
@@ -152,13 +187,19 @@ pub fn read_excel_bytes(data: &[u8], file_extension: &str) -> Result<ExcelWorkbo
             let cursor = Cursor::new(data);
             let workbook = calamine::Xlsx::new(cursor)
                 .map_err(|e| KreuzbergError::parsing(format!("Failed to parse XLSX: {}", e)))?;
-            process_xlsx_workbook(workbook, office_metadata)
+            #[cfg_attr(not(feature = "office"), allow(unused_mut))]
+            let mut workbook = process_xlsx_workbook(workbook, office_metadata, extraction_config)?;
+            #[cfg(feature = "office")]
+            if let Ok(mut archive) = zip::ZipArchive::new(Cursor::new(data)) {
+                attach_xlsx_drawings(&mut workbook, &mut archive);
+            }
+            Ok(workbook)
         }
         // Exotic format: .xlam (Excel add-in) - may not contain proper workbook data
         ".xlam" => {
             let cursor = Cursor::new(data);
             match calamine::Xlsx::new(cursor) {
-                Ok(workbook) => process_xlsx_workbook(workbook, office_metadata),
+                Ok(workbook) => process_xlsx_workbook(workbook, office_metadata, extraction_config),
                 Err(_) => {
                     // .xlam files may not contain proper workbook data - return empty workbook
                     Ok(ExcelWorkbook {
@@ -173,13 +214,13 @@ pub fn read_excel_bytes(data: &[u8], file_extension: &str) -> Result<ExcelWorkbo
             let cursor = Cursor::new(data);
             let workbook = calamine::Xls::new(cursor)
                 .map_err(|e| KreuzbergError::parsing(format!("Failed to parse XLS: {}", e)))?;
-            process_workbook(workbook, office_metadata)
+            process_workbook(workbook, office_metadata, extraction_config)
         }
         // Exotic format: .xla (legacy add-in) - may not contain proper workbook data
         ".xla" => {
             let cursor = Cursor::new(data);
             match calamine::Xls::new(cursor) {
-                Ok(workbook) => process_workbook(workbook, office_metadata),
+                Ok(workbook) => process_workbook(workbook, office_metadata, extraction_config),
                 Err(_) => {
                     // .xla files may not contain proper workbook data - return empty workbook
                     Ok(ExcelWorkbook {
@@ -194,14 +235,14 @@ pub fn read_excel_bytes(data: &[u8], file_extension: &str) -> Result<ExcelWorkbo
             let cursor = Cursor::new(data);
             let workbook = calamine::Xlsb::new(cursor)
                 .map_err(|e| KreuzbergError::parsing(format!("Failed to parse XLSB: {}", e)))?;
-            process_workbook(workbook, office_metadata)
+            process_workbook(workbook, office_metadata, extraction_config)
         }
         // Standard OpenDocument format
         ".ods" => {
             let cursor = Cursor::new(data);
             let workbook = calamine::Ods::new(cursor)
                 .map_err(|e| KreuzbergError::parsing(format!("Failed to parse ODS: {}", e)))?;
-            process_workbook(workbook, office_metadata)
+            process_workbook(workbook, office_metadata, extraction_config)
         }
         _ => Err(KreuzbergError::parsing(format!(
             "Unsupported file extension: {}",
@@ -219,18 +260,20 @@ pub fn read_excel_bytes(data: &[u8], file_extension: &str) -> Result<ExcelWorkbo
 fn process_xlsx_workbook<RS: Read + Seek>(
     mut workbook: calamine::Xlsx<RS>,
     office_metadata: Option<HashMap<String, String>>,
+    extraction_config: Option<&ExtractionConfig>,
 ) -> Result<ExcelWorkbook> {
     let sheet_names = workbook.sheet_names();
     let mut sheets = Vec::with_capacity(sheet_names.len());
 
     for name in &sheet_names {
+        if stop_before_sheet(extraction_config, name)? {
+            break;
+        }
         // Use worksheet_cells_reader to stream cells and detect pathological bounding boxes
         match process_xlsx_sheet_safe(&mut workbook, name) {
             Ok(sheet) => sheets.push(sheet),
-            Err(e) => {
-                // Log but don't fail - continue with other sheets
-                tracing::warn!("Failed to process sheet '{}': {}", name, e);
-            }
+            // Don't fail - continue with other sheets
+            Err(e) => skip_sheet(extraction_config, name, e)?,
         }
     }
 
@@ -290,6 +333,7 @@ fn process_xlsx_sheet_safe<RS: Read + Seek>(workbook: &mut calamine::Xlsx<RS>, s
             col_count: 0,
             cell_count: 0,
             table_cells: None,
+            drawing_text: None,
         });
     }
 
@@ -383,6 +427,7 @@ fn process_sparse_sheet_from_cells(
         col_count: bb_cols,
         cell_count,
         table_cells: None, // No structured table for sparse sheets
+        drawing_text: None,
     })
 }
 
@@ -398,18 +443,27 @@ fn col_to_excel_letter(col: u32) -> String {
     result
 }
 
-fn process_workbook<RS, R>(mut workbook: R, office_metadata: Option<HashMap<String, String>>) -> Result<ExcelWorkbook>
+fn process_workbook<RS, R>(
+    mut workbook: R,
+    office_metadata: Option<HashMap<String, String>>,
+    extraction_config: Option<&ExtractionConfig>,
+) -> Result<ExcelWorkbook>
 where
     RS: std::io::Read + std::io::Seek,
     R: Reader<RS>,
+    R::Error: std::fmt::Display,
 {
     let sheet_names = workbook.sheet_names();
 
     let mut sheets = Vec::with_capacity(sheet_names.len());
 
     for name in &sheet_names {
-        if let Ok(range) = workbook.worksheet_range(name) {
-            sheets.push(process_sheet(name, &range));
+        if stop_before_sheet(extraction_config, name)? {
+            break;
+        }
+        match workbook.worksheet_range(name) {
+            Ok(range) => sheets.push(process_sheet(name, &range)),
+            Err(e) => skip_sheet(extraction_config, name, e)?,
         }
     }
 
@@ -418,6 +472,27 @@ where
     Ok(ExcelWorkbook { sheets, metadata })
 }
 
+/// Cancellation checkpoint before the sheet called `name`.
+fn stop_before_sheet(extraction_config: Option<&ExtractionConfig>, name: &str) -> Result<bool> {
+    match extraction_config {
+        Some(config) => config.checkpoint(|| format!("sheet '{}'", name)),
+        None => Ok(false),
+    }
+}
+
+/// Leave out a sheet that could not be read, reporting it in best-effort mode.
+fn skip_sheet(extraction_config: Option<&ExtractionConfig>, name: &str, error: impl std::fmt::Display) -> Result<()> {
+    match extraction_config {
+        Some(config) if config.best_effort => {
+            config
+                .recover(|| format!("sheet '{}'", name), Err::<(), _>(error.to_string()))
+                .map_err(KreuzbergError::parsing)?;
+        }
+        _ => tracing::warn!("Failed to process sheet '{}': {}", name, error),
+    }
+    Ok(())
+}
+
 #[inline]
 fn process_sheet(name: &str, range: &Range<Data>) -> ExcelSheet {
     let (rows, cols) = range.get_size();
@@ -437,6 +512,7 @@ fn process_sheet(name: &str, range: &Range<Data>) -> ExcelSheet {
             col_count: cols,
             cell_count,
             table_cells: None,
+            drawing_text: None,
         }
     } else {
         let (markdown, table_cells) = generate_markdown_and_cells(name, range, estimated_capacity);
@@ -447,6 +523,7 @@ fn process_sheet(name: &str, range: &Range<Data>) -> ExcelSheet {
             col_count: cols,
             cell_count,
             table_cells: Some(table_cells),
+            drawing_text: None,
         }
     }
 }
@@ -681,6 +758,13 @@ pub fn excel_to_text(workbook: &ExcelWorkbook) -> String {
                 result.push_str(&line);
             }
         }
+
+        if let Some(drawing_text) = &sheet.drawing_text {
+            if sheet.table_cells.is_some() {
+                result.push_str("\n\n");
+            }
+            result.push_str(drawing_text);
+        }
     }
 
     result
@@ -702,6 +786,137 @@ pub fn excel_to_markdown(workbook: &ExcelWorkbook) -> String {
     result
 }
 
+/// Parse the charts and SmartArt diagrams anchored on each sheet and append their renderings.
+///
+/// Graphics are resolved through `workbook.xml` → sheet part → drawing part → chart/diagram part.
+/// Chart sheets have no cell data and therefore no [`ExcelSheet`] yet; they are appended as new
+/// sheets. All lookups are best-effort: a missing or malformed part is skipped.
+#[cfg(feature = "office")]
+fn attach_xlsx_drawings<R: Read + Seek>(workbook: &mut ExcelWorkbook, archive: &mut zip::ZipArchive<R>) {
+    use crate::extraction::drawingml::{self, CHART_URI, DIAGRAM_URI};
+    use crate::extraction::ooxml_constants::{DRAWINGML_NAMESPACE, RELATIONSHIPS_NAMESPACE};
+
+    const MAX_PART_SIZE: u64 = 100 * 1024 * 1024;
+
+    let mut read_part = |path: &str| -> Option<String> {
+        let file = archive.by_name(path).ok()?;
+        let mut xml = String::new();
+        file.take(MAX_PART_SIZE).read_to_string(&mut xml).ok()?;
+        Some(xml)
+    };
+
+    let Some(workbook_xml) = read_part("xl/workbook.xml") else {
+        return;
+    };
+    let workbook_rels = read_part("xl/_rels/workbook.xml.rels")
+        .map(|xml| drawingml::parse_relationship_targets(&xml))
+        .unwrap_or_default();
+    let Ok(workbook_doc) = roxmltree::Document::parse(&workbook_xml) else {
+        return;
+    };
+
+    for sheet_node in workbook_doc
+        .descendants()
+        .filter(|n| n.is_element() && n.tag_name().name() == "sheet")
+    {
+        let (Some(name), Some(rid)) = (
+            sheet_node.attribute("name"),
+            sheet_node.attribute((RELATIONSHIPS_NAMESPACE, "id")),
+        ) else {
+            continue;
+        };
+        let Some(sheet_part) = workbook_rels
+            .get(rid)
+            .and_then(|target| drawingml::resolve_part_path("xl/workbook.xml", target))
+        else {
+            continue;
+        };
+        let Some(sheet_rels) = read_part(&drawingml::part_rels_path(&sheet_part)) else {
+            continue;
+        };
+
+        let mut markdown_parts = Vec::new();
+        let mut text_parts = Vec::new();
+        for drawing_target in drawingml::relationship_targets_of_type(&sheet_rels, "drawing") {
+            let Some(drawing_part) = drawingml::resolve_part_path(&sheet_part, &drawing_target) else {
+                continue;
+            };
+            let (Some(drawing_xml), Some(drawing_rels)) = (
+                read_part(&drawing_part),
+                read_part(&drawingml::part_rels_path(&drawing_part)),
+            ) else {
+                continue;
+            };
+            let drawing_rels = drawingml::parse_relationship_targets(&drawing_rels);
+            let Ok(drawing_doc) = roxmltree::Document::parse(&drawing_xml) else {
+                continue;
+            };
+
+            for graphic_data in drawing_doc.descendants().filter(|n| {
+                n.is_element()
+                    && n.tag_name().name() == "graphicData"
+                    && n.tag_name().namespace() == Some(DRAWINGML_NAMESPACE)
+            }) {
+                let uri = graphic_data.attribute("uri");
+                let reference = graphic_data.children().find_map(|n| match (uri, n.tag_name().name()) {
+                    (Some(CHART_URI), "chart") => n.attribute((RELATIONSHIPS_NAMESPACE, "id")),
+                    (Some(DIAGRAM_URI), "relIds") => n.attribute((RELATIONSHIPS_NAMESPACE, "dm")),
+                    _ => None,
+                });
+                let Some(part_xml) = reference
+                    .and_then(|rid| drawing_rels.get(rid))
+                    .and_then(|target| drawingml::resolve_part_path(&drawing_part, target))
+                    .and_then(|path| read_part(&path))
+                else {
+                    continue;
+                };
+
+                if uri == Some(CHART_URI) {
+                    match drawingml::parse_chart_xml(&part_xml) {
+                        Ok(chart) if !chart.is_empty() => {
+                            markdown_parts.push(chart.to_markdown());
+                            text_parts.push(chart.to_text());
+                        }
+                        Ok(_) => {}
+                        Err(e) => tracing::debug!("Skipping unparseable chart on sheet '{}': {}", name, e),
+                    }
+                } else {
+                    match drawingml::parse_smartart_xml(&part_xml) {
+                        Ok(smartart) if !smartart.is_empty() => {
+                            markdown_parts.push(smartart.to_markdown());
+                            text_parts.push(smartart.to_text());
+                        }
+                        Ok(_) => {}
+                        Err(e) => tracing::debug!("Skipping unparseable SmartArt on sheet '{}': {}", name, e),
+                    }
+                }
+            }
+        }
+
+        if markdown_parts.is_empty() {
+            continue;
+        }
+        let markdown = markdown_parts.join("\n\n");
+        let text = text_parts.join("\n\n");
+
+        if let Some(sheet) = workbook.sheets.iter_mut().find(|s| s.name == name) {
+            sheet.markdown.push_str("\n\n");
+            sheet.markdown.push_str(&markdown);
+            sheet.drawing_text = Some(text);
+        } else {
+            workbook.sheets.push(ExcelSheet {
+                name: name.to_owned(),
+                markdown: format!("## {}\n\n{}", name, markdown),
+                row_count: 0,
+                col_count: 0,
+                cell_count: 0,
+                table_cells: None,
+                drawing_text: Some(text),
+            });
+        }
+    }
+}
+
 #[cfg(feature = "office")]
 fn extract_xlsx_office_metadata_from_file(file_path: &str) -> Result<HashMap<String, String>> {
     use std::fs::File;
@@ -1034,4 +1249,26 @@ mod tests {
         assert_eq!(sheet.col_count, 5);
         assert_eq!(sheet.cell_count, 50);
     }
+
+    #[test]
+    fn test_cancelled_workbook_stops_before_first_sheet() {
+        let path =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../test_documents/xlsx/excel_multi_sheet.xlsx");
+        let Ok(data) = std::fs::read(&path) else {
+            return;
+        };
+        let token = crate::CancellationToken::new();
+        token.cancel();
+        let mut config = ExtractionConfig {
+            cancellation: Some(token),
+            ..Default::default()
+        };
+
+        let err = read_excel_bytes_with_config(&data, ".xlsx", &config).unwrap_err();
+        assert!(matches!(err, KreuzbergError::Cancelled(_)));
+
+        config.partial_results_on_cancel = true;
+        let workbook = read_excel_bytes_with_config(&data, ".xlsx", &config).unwrap();
+        assert!(workbook.sheets.is_empty());
+    }
 }
@@ -11,7 +11,8 @@ use crate::types::{ExtractedImage, ExtractionResult, Metadata};
 ///
 /// For each image, spawns a blocking OCR task and stores the result
 /// in `image.ocr_result`. If OCR is not configured or fails for an
-/// individual image, that image's `ocr_result` remains `None`.
+/// individual image, that image's `ocr_result` remains `None`. Cancellation is checked
+/// before each image.
 ///
 /// This function is the single shared implementation used by all
 /// document extractors (DOCX, PPTX, Jupyter, Markdown, etc.).
@@ -29,15 +30,18 @@ pub async fn process_images_with_ocr(
     let output_format = config.output_format;
 
     for image in &mut images {
+        // Images left without OCR keep `ocr_result` as `None`, like failed ones.
+        if config.checkpoint(|| format!("OCR of image {}", image.image_index + 1))? {
+            break;
+        }
+
         let image_data = image.data.clone();
         let tess_config_clone = tess_config.clone();
         let span = tracing::Span::current();
 
         let ocr_result = tokio::task::spawn_blocking(move || {
             let _guard = span.entered();
-            let cache_dir = std::env::var("KREUZBERG_CACHE_DIR").ok().map(std::path::PathBuf::from);
-
-            let proc = OcrProcessor::new(cache_dir)?;
+            let proc = OcrProcessor::new(None)?;
             let ocr_tess_config: crate::ocr::types::TesseractConfig = (&tess_config_clone).into();
             proc.process_image_with_format(&image_data, &ocr_tess_config, output_format)
         })
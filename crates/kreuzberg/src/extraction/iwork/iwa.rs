@@ -0,0 +1,364 @@
+//! IWA archive decoding.
+//!
+//! An `.iwa` file is a sequence of chunks, each a 4-byte header (a zero byte
+//! followed by a 24-bit little-endian length) and a raw Snappy block. Unlike the
+//! standard Snappy framing format there are no stream identifiers or checksums.
+//!
+//! The decompressed stream is a list of archives: a varint-prefixed
+//! `TSP.ArchiveInfo` message followed by the payload messages it describes.
+//! Messages are decoded with a minimal schema-less protobuf reader because the
+//! iWork schemas are private and change between releases.
+
+use crate::error::{KreuzbergError, Result};
+
+/// Upper bound on the decompressed size of a single IWA file.
+const MAX_DECOMPRESSED_SIZE: usize = 512 * 1024 * 1024;
+
+/// A single object stored in an IWA file.
+#[derive(Debug, Clone)]
+pub struct IwaObject {
+    /// Object identifier, unique within the document.
+    pub identifier: u64,
+    /// Message type ID of the primary payload (`TSWP.StorageArchive` = 2001, ...).
+    pub type_id: u32,
+    /// Raw protobuf bytes of the primary payload.
+    pub data: Vec<u8>,
+    /// Identifiers of objects referenced from the payload, as recorded by the writer.
+    pub object_references: Vec<u64>,
+}
+
+/// Decompress an IWA file into its raw protobuf stream.
+pub fn decompress_iwa(data: &[u8]) -> Result<Vec<u8>> {
+    let mut decoder = snap::raw::Decoder::new();
+    let mut output = Vec::new();
+    let mut offset = 0;
+
+    while offset < data.len() {
+        if data.len() - offset < 4 {
+            return Err(KreuzbergError::parsing("Truncated IWA chunk header".to_string()));
+        }
+        if data[offset] != 0 {
+            return Err(KreuzbergError::parsing(format!(
+                "Unsupported IWA chunk type 0x{:02x}",
+                data[offset]
+            )));
+        }
+        let length = u32::from_le_bytes([data[offset + 1], data[offset + 2], data[offset + 3], 0]) as usize;
+        offset += 4;
+
+        let chunk = data
+            .get(offset..offset + length)
+            .ok_or_else(|| KreuzbergError::parsing("Truncated IWA chunk".to_string()))?;
+        let decompressed_len = snap::raw::decompress_len(chunk)
+            .map_err(|e| KreuzbergError::parsing(format!("Invalid IWA Snappy block: {}", e)))?;
+        if output.len() + decompressed_len > MAX_DECOMPRESSED_SIZE {
+            return Err(KreuzbergError::parsing(
+                "IWA archive exceeds maximum decompressed size".to_string(),
+            ));
+        }
+        output.extend(
+            decoder
+                .decompress_vec(chunk)
+                .map_err(|e| KreuzbergError::parsing(format!("Failed to decompress IWA chunk: {}", e)))?,
+        );
+        offset += length;
+    }
+
+    Ok(output)
+}
+
+/// Decode all objects from a decompressed IWA stream.
+///
+/// Only the first payload of each archive is kept; additional payloads carry
+/// version-specific diffs that are not needed for text extraction.
+pub fn parse_iwa_objects(stream: &[u8]) -> Result<Vec<IwaObject>> {
+    let mut objects = Vec::new();
+    let mut offset = 0;
+
+    while offset < stream.len() {
+        let (info_len, consumed) = read_varint(&stream[offset..])?;
+        offset += consumed;
+        let info_bytes = take(stream, offset, info_len as usize)?;
+        offset += info_len as usize;
+
+        let info = ProtoMessage::parse(info_bytes)?;
+        let identifier = info.varint(1).unwrap_or(0);
+
+        let mut primary = None;
+        for message_info in info.messages(2) {
+            let message_info = message_info?;
+            let type_id = message_info.varint(1).unwrap_or(0) as u32;
+            let length = message_info.varint(3).unwrap_or(0) as usize;
+            let payload = take(stream, offset, length)?;
+            offset += length;
+
+            if primary.is_none() {
+                primary = Some(IwaObject {
+                    identifier,
+                    type_id,
+                    data: payload.to_vec(),
+                    object_references: message_info.packed_varints(5),
+                });
+            }
+        }
+
+        if let Some(object) = primary {
+            objects.push(object);
+        }
+    }
+
+    Ok(objects)
+}
+
+fn take(data: &[u8], offset: usize, len: usize) -> Result<&[u8]> {
+    offset
+        .checked_add(len)
+        .and_then(|end| data.get(offset..end))
+        .ok_or_else(|| KreuzbergError::parsing("Truncated IWA message".to_string()))
+}
+
+/// Read a base-128 varint, returning the value and the number of bytes consumed.
+pub fn read_varint(data: &[u8]) -> Result<(u64, usize)> {
+    let mut value = 0u64;
+    for (i, byte) in data.iter().enumerate().take(10) {
+        value |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok((value, i + 1));
+        }
+    }
+    Err(KreuzbergError::parsing("Invalid protobuf varint".to_string()))
+}
+
+/// A decoded protobuf field value.
+#[derive(Debug, Clone, Copy)]
+pub enum ProtoValue<'a> {
+    Varint(u64),
+    Fixed64(u64),
+    Bytes(&'a [u8]),
+    Fixed32(u32),
+}
+
+/// A protobuf message decoded into `(field number, value)` pairs without a schema.
+#[derive(Debug, Clone, Default)]
+pub struct ProtoMessage<'a> {
+    fields: Vec<(u32, ProtoValue<'a>)>,
+}
+
+impl<'a> ProtoMessage<'a> {
+    /// Decode the top-level fields of a message.
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
+        let mut fields = Vec::new();
+        let mut offset = 0;
+
+        while offset < data.len() {
+            let (key, consumed) = read_varint(&data[offset..])?;
+            offset += consumed;
+            let field = (key >> 3) as u32;
+            let value = match key & 0x7 {
+                0 => {
+                    let (value, consumed) = read_varint(&data[offset..])?;
+                    offset += consumed;
+                    ProtoValue::Varint(value)
+                }
+                1 => {
+                    let bytes = take(data, offset, 8)?;
+                    offset += 8;
+                    ProtoValue::Fixed64(u64::from_le_bytes(bytes.try_into().unwrap_or_default()))
+                }
+                2 => {
+                    let (len, consumed) = read_varint(&data[offset..])?;
+                    offset += consumed;
+                    let bytes = take(data, offset, len as usize)?;
+                    offset += len as usize;
+                    ProtoValue::Bytes(bytes)
+                }
+                5 => {
+                    let bytes = take(data, offset, 4)?;
+                    offset += 4;
+                    ProtoValue::Fixed32(u32::from_le_bytes(bytes.try_into().unwrap_or_default()))
+                }
+                wire_type => {
+                    return Err(KreuzbergError::parsing(format!(
+                        "Unsupported protobuf wire type {}",
+                        wire_type
+                    )));
+                }
+            };
+            fields.push((field, value));
+        }
+
+        Ok(Self { fields })
+    }
+
+    fn values(&self, field: u32) -> impl Iterator<Item = &ProtoValue<'a>> {
+        self.fields.iter().filter(move |(f, _)| *f == field).map(|(_, v)| v)
+    }
+
+    /// First varint value of `field`.
+    pub fn varint(&self, field: u32) -> Option<u64> {
+        self.values(field).find_map(|v| match v {
+            ProtoValue::Varint(value) => Some(*value),
+            _ => None,
+        })
+    }
+
+    /// First length-delimited value of `field`.
+    pub fn bytes(&self, field: u32) -> Option<&'a [u8]> {
+        self.values(field).find_map(|v| match v {
+            ProtoValue::Bytes(bytes) => Some(*bytes),
+            _ => None,
+        })
+    }
+
+    /// All length-delimited values of a repeated `field`.
+    pub fn all_bytes(&self, field: u32) -> impl Iterator<Item = &'a [u8]> + '_ {
+        self.values(field).filter_map(|v| match v {
+            ProtoValue::Bytes(bytes) => Some(*bytes),
+            _ => None,
+        })
+    }
+
+    /// First value of a string `field`; invalid UTF-8 is replaced.
+    pub fn string(&self, field: u32) -> Option<String> {
+        self.bytes(field).map(|b| String::from_utf8_lossy(b).into_owned())
+    }
+
+    /// All values of a repeated string `field`.
+    pub fn strings(&self, field: u32) -> Vec<String> {
+        self.all_bytes(field)
+            .map(|b| String::from_utf8_lossy(b).into_owned())
+            .collect()
+    }
+
+    /// First value of an embedded message `field`.
+    pub fn message(&self, field: u32) -> Option<ProtoMessage<'a>> {
+        self.bytes(field).and_then(|b| ProtoMessage::parse(b).ok())
+    }
+
+    /// All values of a repeated embedded message `field`.
+    pub fn messages(&self, field: u32) -> impl Iterator<Item = Result<ProtoMessage<'a>>> + '_ {
+        self.all_bytes(field).map(ProtoMessage::parse)
+    }
+
+    /// Identifier of a `TSP.Reference` stored in `field`.
+    pub fn reference(&self, field: u32) -> Option<u64> {
+        self.message(field).and_then(|m| m.varint(1))
+    }
+
+    /// Values of a repeated varint `field`, accepting both packed and unpacked encodings.
+    pub fn packed_varints(&self, field: u32) -> Vec<u64> {
+        let mut out = Vec::new();
+        for value in self.values(field) {
+            match value {
+                ProtoValue::Varint(v) => out.push(*v),
+                ProtoValue::Bytes(bytes) => {
+                    let mut bytes = *bytes;
+                    while let Ok((v, consumed)) = read_varint(bytes) {
+                        out.push(v);
+                        bytes = &bytes[consumed..];
+                        if bytes.is_empty() {
+                            break;
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_support {
+    //! Helpers for building synthetic IWA files in tests.
+
+    pub fn varint(mut value: u64, out: &mut Vec<u8>) {
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                out.push(byte);
+                return;
+            }
+            out.push(byte | 0x80);
+        }
+    }
+
+    pub fn field_varint(field: u32, value: u64, out: &mut Vec<u8>) {
+        varint(u64::from(field) << 3, out);
+        varint(value, out);
+    }
+
+    pub fn field_bytes(field: u32, bytes: &[u8], out: &mut Vec<u8>) {
+        varint((u64::from(field) << 3) | 2, out);
+        varint(bytes.len() as u64, out);
+        out.extend_from_slice(bytes);
+    }
+
+    pub fn reference(field: u32, identifier: u64, out: &mut Vec<u8>) {
+        let mut msg = Vec::new();
+        field_varint(1, identifier, &mut msg);
+        field_bytes(field, &msg, out);
+    }
+
+    /// Encode `(identifier, type, payload, references)` objects as a compressed IWA file.
+    pub fn build_iwa(objects: &[(u64, u32, Vec<u8>, Vec<u64>)]) -> Vec<u8> {
+        let mut stream = Vec::new();
+        for (identifier, type_id, payload, references) in objects {
+            let mut message_info = Vec::new();
+            field_varint(1, u64::from(*type_id), &mut message_info);
+            field_varint(3, payload.len() as u64, &mut message_info);
+            if !references.is_empty() {
+                let mut packed = Vec::new();
+                for r in references {
+                    varint(*r, &mut packed);
+                }
+                field_bytes(5, &packed, &mut message_info);
+            }
+
+            let mut info = Vec::new();
+            field_varint(1, *identifier, &mut info);
+            field_bytes(2, &message_info, &mut info);
+
+            varint(info.len() as u64, &mut stream);
+            stream.extend_from_slice(&info);
+            stream.extend_from_slice(payload);
+        }
+
+        let compressed = snap::raw::Encoder::new().compress_vec(&stream).unwrap();
+        let mut out = vec![0];
+        out.extend_from_slice(&(compressed.len() as u32).to_le_bytes()[..3]);
+        out.extend_from_slice(&compressed);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_support::*;
+    use super::*;
+
+    #[test]
+    fn test_parse_iwa_round_trip() {
+        let mut payload = Vec::new();
+        field_bytes(3, b"Hello", &mut payload);
+        let iwa = build_iwa(&[(7, 2001, payload.clone(), vec![9, 10])]);
+
+        let stream = decompress_iwa(&iwa).unwrap();
+        let objects = parse_iwa_objects(&stream).unwrap();
+
+        assert_eq!(objects.len(), 1);
+        assert_eq!(objects[0].identifier, 7);
+        assert_eq!(objects[0].type_id, 2001);
+        assert_eq!(objects[0].data, payload);
+        assert_eq!(objects[0].object_references, vec![9, 10]);
+        let message = ProtoMessage::parse(&objects[0].data).unwrap();
+        assert_eq!(message.strings(3), vec!["Hello".to_string()]);
+    }
+
+    #[test]
+    fn test_decompress_iwa_rejects_truncated_chunk() {
+        assert!(decompress_iwa(&[0, 10, 0, 0, 1, 2]).is_err());
+        assert!(decompress_iwa(&[1, 0, 0, 0]).is_err());
+    }
+}
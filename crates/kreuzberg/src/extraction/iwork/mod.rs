@@ -0,0 +1,695 @@
+//! Apple iWork (Pages, Numbers, Keynote) extraction.
+//!
+//! iWork 2013+ documents are ZIP bundles whose `Index/` directory holds IWA
+//! archives: Snappy-compressed streams of protobuf objects (see [`iwa`]). The
+//! protobuf schemas are private, so only a small, long-stable subset is decoded:
+//!
+//! - `TSWP.StorageArchive` text storages (body text, text boxes, notes)
+//! - `TST.TableModelArchive` tables with their tiles and string tables
+//! - Keynote's per-slide archives (`Index/Slide-*.iwa`) for slide boundaries
+//!
+//! When the bundle has no IWA content (iWork '09 XML bundles) or none of it can
+//! be decoded, callers fall back to the preview PDF embedded in the bundle.
+
+pub mod iwa;
+pub mod table;
+
+use std::collections::{HashMap, HashSet};
+use std::io::{Cursor, Read};
+
+use zip::ZipArchive;
+
+use crate::error::{KreuzbergError, Result};
+use crate::extraction::cells_to_markdown;
+use crate::types::{PageBoundary, PageContent, PageInfo, PageStructure, PageUnitType, Table};
+
+use iwa::{IwaObject, ProtoMessage, decompress_iwa, parse_iwa_objects};
+pub use table::IworkTable;
+
+/// MIME type of Apple Pages documents.
+pub const PAGES_MIME_TYPE: &str = "application/vnd.apple.pages";
+/// MIME type of Apple Numbers spreadsheets.
+pub const NUMBERS_MIME_TYPE: &str = "application/vnd.apple.numbers";
+/// MIME type of Apple Keynote presentations.
+pub const KEYNOTE_MIME_TYPE: &str = "application/vnd.apple.keynote";
+
+/// `TSWP.StorageArchive` message types.
+const STORAGE_TYPES: &[u32] = &[2001, 2005];
+
+/// `TSWP.StorageArchive.KIND` values for table cell text, rendered through tables instead.
+const CELL_STORAGE_KINDS: &[u64] = &[5, 7];
+
+/// `TSWP.StorageArchive.KIND` values that are not part of the main reading flow.
+const HEADER_STORAGE_KIND: u64 = 1;
+const FOOTNOTE_STORAGE_KIND: u64 = 2;
+const NOTE_STORAGE_KIND: u64 = 4;
+
+/// Maximum uncompressed size of a single IWA entry read from the bundle.
+const MAX_IWA_ENTRY_SIZE: u64 = 256 * 1024 * 1024;
+
+/// Maximum size of an embedded preview PDF.
+const MAX_PREVIEW_SIZE: u64 = 512 * 1024 * 1024;
+
+/// The iWork application a document belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IworkKind {
+    Pages,
+    Numbers,
+    Keynote,
+}
+
+impl IworkKind {
+    /// Map a MIME type (including the legacy `application/x-iwork-*` aliases) to an iWork kind.
+    pub fn from_mime_type(mime_type: &str) -> Option<Self> {
+        match mime_type {
+            PAGES_MIME_TYPE | "application/x-iwork-pages-sffpages" => Some(Self::Pages),
+            NUMBERS_MIME_TYPE | "application/x-iwork-numbers-sffnumbers" => Some(Self::Numbers),
+            KEYNOTE_MIME_TYPE | "application/x-iwork-keynote-sffkey" => Some(Self::Keynote),
+            _ => None,
+        }
+    }
+}
+
+/// Natively extracted iWork content.
+#[derive(Debug, Clone)]
+pub struct IworkExtractionResult {
+    /// Markdown content.
+    pub content: String,
+    /// Tables found in the document.
+    pub tables: Vec<Table>,
+    /// Number of slides (Keynote only).
+    pub slide_count: usize,
+    /// Page structure with slide boundaries (Keynote only, when page tracking is enabled).
+    pub page_structure: Option<PageStructure>,
+    /// Per-slide content (Keynote only, when page extraction is enabled).
+    pub page_contents: Option<Vec<PageContent>>,
+}
+
+/// Outcome of reading an iWork bundle.
+#[derive(Debug, Clone)]
+pub enum IworkExtraction {
+    /// Content decoded from the IWA archives.
+    Native(IworkExtractionResult),
+    /// The IWA content was missing or unreadable; these are the bytes of the bundle's preview PDF.
+    PreviewPdf(Vec<u8>),
+}
+
+/// IWA objects loaded from a bundle, indexed by identifier.
+pub(crate) struct IworkBundle {
+    objects: HashMap<u64, IwaObject>,
+    /// `(file name relative to Index/, object ids in file order)`, in archive order.
+    files: Vec<(String, Vec<u64>)>,
+}
+
+impl IworkBundle {
+    pub(crate) fn get(&self, id: u64) -> Option<&IwaObject> {
+        self.objects.get(&id)
+    }
+
+    /// Plain text of a `TSWP.StorageArchive`, or `None` for other objects.
+    pub(crate) fn storage_text(&self, object: &IwaObject) -> Option<String> {
+        storage(object).map(|(_, text)| text)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    /// Slide files in presentation order.
+    ///
+    /// The order follows a depth-first walk of the object reference graph from
+    /// the document root, which visits the slide tree in show order. Slides not
+    /// reachable from the root are appended in archive order.
+    fn slide_files(&self) -> Vec<usize> {
+        let slide_files: Vec<usize> = self
+            .files
+            .iter()
+            .enumerate()
+            .filter(|(_, (name, _))| is_slide_file(name))
+            .map(|(i, _)| i)
+            .collect();
+        if slide_files.is_empty() {
+            return slide_files;
+        }
+
+        let object_file: HashMap<u64, usize> = slide_files
+            .iter()
+            .flat_map(|&i| self.files[i].1.iter().map(move |&id| (id, i)))
+            .collect();
+
+        let roots: Vec<u64> = self
+            .files
+            .iter()
+            .find(|(name, _)| name == "Document.iwa")
+            .map(|(_, ids)| ids.clone())
+            .unwrap_or_default();
+
+        let mut ordered = Vec::new();
+        let mut seen_files = HashSet::new();
+        let mut visited = HashSet::new();
+        let mut stack: Vec<u64> = roots.into_iter().rev().collect();
+        while let Some(id) = stack.pop() {
+            if !visited.insert(id) {
+                continue;
+            }
+            if let Some(&file) = object_file.get(&id)
+                && seen_files.insert(file)
+            {
+                ordered.push(file);
+            }
+            if let Some(object) = self.objects.get(&id) {
+                stack.extend(object.object_references.iter().rev().copied());
+            }
+        }
+
+        for file in slide_files {
+            if seen_files.insert(file) {
+                ordered.push(file);
+            }
+        }
+        ordered
+    }
+
+    /// Decode every table model stored in, or referenced from, the given objects.
+    fn tables_for(&self, ids: &[u64], seen: &mut HashSet<u64>) -> Vec<IworkTable> {
+        let mut tables = Vec::new();
+        for &id in ids {
+            let Some(object) = self.objects.get(&id) else {
+                continue;
+            };
+            let candidates = std::iter::once(id).chain(object.object_references.iter().copied());
+            for candidate in candidates {
+                let Some(model) = self.objects.get(&candidate) else {
+                    continue;
+                };
+                if model.type_id == table::TABLE_MODEL_TYPE
+                    && seen.insert(candidate)
+                    && let Some(table) = table::decode_table(self, &model.data)
+                {
+                    tables.push(table);
+                }
+            }
+        }
+        tables
+    }
+}
+
+fn is_slide_file(name: &str) -> bool {
+    let file = name.rsplit('/').next().unwrap_or(name);
+    file.starts_with("Slide") && file.ends_with(".iwa")
+}
+
+/// Decode a `TSWP.StorageArchive` into `(kind, text)`.
+fn storage(object: &IwaObject) -> Option<(u64, String)> {
+    if !STORAGE_TYPES.contains(&object.type_id) {
+        return None;
+    }
+    let message = ProtoMessage::parse(&object.data).ok()?;
+    let kind = message.varint(1).unwrap_or(0);
+    Some((kind, normalize_storage_text(&message.strings(3).concat())))
+}
+
+/// Map iWork control characters to plain text line structure.
+fn normalize_storage_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            // Paragraph / line / page / column / section breaks
+            '\u{2029}' | '\u{2028}' | '\u{000C}' | '\u{000E}' | '\u{0004}' | '\u{0005}' => out.push('\n'),
+            // Attachment and footnote anchors
+            '\u{FFFC}' | '\u{FFFD}' => {}
+            c if c.is_control() && c != '\n' && c != '\t' => {}
+            c => out.push(c),
+        }
+    }
+
+    out.lines()
+        .map(str::trim_end)
+        .filter(|line| !line.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Read the IWA objects of a bundle and return the bytes of its preview PDF, if any.
+fn load_bundle(data: &[u8]) -> Result<(IworkBundle, Option<Vec<u8>>)> {
+    let mut archive = ZipArchive::new(Cursor::new(data))
+        .map_err(|e| KreuzbergError::parsing(format!("Failed to open iWork bundle: {}", e)))?;
+
+    let mut bundle = IworkBundle {
+        objects: HashMap::new(),
+        files: Vec::new(),
+    };
+    let mut preview = None;
+
+    for i in 0..archive.len() {
+        let Ok(mut entry) = archive.by_index(i) else {
+            continue;
+        };
+        let name = entry.name().to_string();
+        let lower = name.to_ascii_lowercase();
+
+        if lower.ends_with("preview.pdf") && preview.is_none() && entry.size() <= MAX_PREVIEW_SIZE {
+            let mut bytes = Vec::with_capacity(entry.size() as usize);
+            if entry.read_to_end(&mut bytes).is_ok() {
+                preview = Some(bytes);
+            }
+        } else if lower.ends_with("index.zip") && entry.size() <= MAX_IWA_ENTRY_SIZE {
+            // Older 2013-era bundles keep the IWA files in a nested Index.zip
+            let mut bytes = Vec::with_capacity(entry.size() as usize);
+            if entry.read_to_end(&mut bytes).is_ok()
+                && let Ok(mut nested) = ZipArchive::new(Cursor::new(bytes))
+            {
+                for j in 0..nested.len() {
+                    if let Ok(mut nested_entry) = nested.by_index(j) {
+                        let nested_name = format!("Index/{}", nested_entry.name());
+                        load_iwa_entry(&mut bundle, &nested_name, &mut nested_entry);
+                    }
+                }
+            }
+        } else {
+            load_iwa_entry(&mut bundle, &name, &mut entry);
+        }
+    }
+
+    Ok((bundle, preview))
+}
+
+fn load_iwa_entry<R: Read>(bundle: &mut IworkBundle, name: &str, entry: &mut zip::read::ZipFile<'_, R>) {
+    let Some((_, relative)) = name.split_once("Index/") else {
+        return;
+    };
+    if !relative.ends_with(".iwa") || entry.size() > MAX_IWA_ENTRY_SIZE {
+        return;
+    }
+
+    let mut bytes = Vec::with_capacity(entry.size() as usize);
+    if entry.read_to_end(&mut bytes).is_err() {
+        return;
+    }
+
+    match decompress_iwa(&bytes).and_then(|stream| parse_iwa_objects(&stream)) {
+        Ok(objects) => {
+            let ids = objects.iter().map(|o| o.identifier).collect();
+            for object in objects {
+                bundle.objects.insert(object.identifier, object);
+            }
+            bundle.files.push((relative.to_string(), ids));
+        }
+        Err(e) => {
+            tracing::debug!("Skipping unreadable IWA file {}: {}", name, e);
+        }
+    }
+}
+
+/// Extract an iWork document from its bundle bytes.
+///
+/// `kind` selects the layout for Pages and Numbers documents; bundles containing
+/// Keynote slide archives are always laid out slide by slide.
+pub fn extract_iwork_from_bytes(
+    data: &[u8],
+    kind: IworkKind,
+    page_config: Option<&crate::core::config::PageConfig>,
+) -> Result<IworkExtraction> {
+    let (bundle, preview) = load_bundle(data)?;
+
+    let result = if bundle.is_empty() {
+        None
+    } else if !bundle.slide_files().is_empty() {
+        Some(extract_slides(&bundle, page_config))
+    } else {
+        Some(extract_flow(&bundle, kind))
+    };
+
+    match (result, preview) {
+        (Some(result), _) if !result.content.trim().is_empty() => Ok(IworkExtraction::Native(result)),
+        (_, Some(preview)) => Ok(IworkExtraction::PreviewPdf(preview)),
+        (Some(result), None) => Ok(IworkExtraction::Native(result)),
+        (None, None) => Err(KreuzbergError::parsing(
+            "iWork bundle contains no IWA archives and no preview PDF".to_string(),
+        )),
+    }
+}
+
+fn to_table(table: &IworkTable, page_number: usize) -> Table {
+    Table {
+        cells: table.cells.clone(),
+        markdown: cells_to_markdown(&table.cells),
+        page_number,
+        bounding_box: None,
+    }
+}
+
+/// Lay out a Pages or Numbers document as a single flow.
+fn extract_flow(bundle: &IworkBundle, kind: IworkKind) -> IworkExtractionResult {
+    let mut body = Vec::new();
+    let mut other = Vec::new();
+    let mut footnotes = Vec::new();
+    let mut all_ids = Vec::new();
+
+    for (name, ids) in &bundle.files {
+        if name.contains("Stylesheet") {
+            continue;
+        }
+        all_ids.extend_from_slice(ids);
+        for id in ids {
+            let Some((kind, text)) = bundle.get(*id).and_then(storage) else {
+                continue;
+            };
+            if text.is_empty() || CELL_STORAGE_KINDS.contains(&kind) || kind == HEADER_STORAGE_KIND {
+                continue;
+            }
+            match kind {
+                0 => body.push(text),
+                FOOTNOTE_STORAGE_KIND => footnotes.push(text),
+                _ => other.push(text),
+            }
+        }
+    }
+
+    let iwork_tables = bundle.tables_for(&all_ids, &mut HashSet::new());
+    let tables: Vec<Table> = iwork_tables.iter().map(|t| to_table(t, 1)).collect();
+
+    let mut sections: Vec<String> = Vec::new();
+    let rendered_tables = iwork_tables
+        .iter()
+        .zip(&tables)
+        .filter(|(_, t)| !t.cells.is_empty())
+        .map(|(iwork_table, table)| {
+            if iwork_table.name.trim().is_empty() {
+                table.markdown.clone()
+            } else {
+                format!("## {}\n\n{}", iwork_table.name.trim(), table.markdown)
+            }
+        });
+
+    if kind == IworkKind::Numbers {
+        sections.extend(rendered_tables);
+        sections.extend(body);
+        sections.extend(other);
+    } else {
+        sections.extend(body);
+        sections.extend(other);
+        sections.extend(rendered_tables);
+    }
+    sections.extend(footnotes);
+
+    IworkExtractionResult {
+        content: sections.join("\n\n"),
+        tables,
+        slide_count: 0,
+        page_structure: None,
+        page_contents: None,
+    }
+}
+
+/// Lay out a Keynote presentation slide by slide.
+fn extract_slides(
+    bundle: &IworkBundle,
+    page_config: Option<&crate::core::config::PageConfig>,
+) -> IworkExtractionResult {
+    let slide_files = bundle.slide_files();
+    let mut content = String::new();
+    let mut tables = Vec::new();
+    let mut boundaries = Vec::new();
+    let mut page_contents = Vec::new();
+    let mut seen_tables = HashSet::new();
+
+    for (index, &file) in slide_files.iter().enumerate() {
+        let slide_number = index + 1;
+        let ids = &bundle.files[file].1;
+
+        let mut parts = Vec::new();
+        let mut notes = Vec::new();
+        for id in ids {
+            let Some((kind, text)) = bundle.get(*id).and_then(storage) else {
+                continue;
+            };
+            if text.is_empty() || CELL_STORAGE_KINDS.contains(&kind) {
+                continue;
+            }
+            if kind == NOTE_STORAGE_KIND {
+                notes.push(text);
+            } else {
+                parts.push(text);
+            }
+        }
+
+        let mut slide_tables = Vec::new();
+        for iwork_table in bundle.tables_for(ids, &mut seen_tables) {
+            let table = to_table(&iwork_table, slide_number);
+            if !table.cells.is_empty() {
+                parts.push(table.markdown.clone());
+            }
+            slide_tables.push(table);
+        }
+
+        let mut slide_content = parts.join("\n\n");
+        if !notes.is_empty() {
+            slide_content.push_str("\n\n### Notes:\n");
+            slide_content.push_str(&notes.join("\n\n"));
+        }
+
+        let byte_start = content.len();
+        if let Some(cfg) = page_config
+            && cfg.insert_page_markers
+        {
+            content.push_str(&cfg.marker_format.replace("{page_num}", &slide_number.to_string()));
+        }
+        content.push_str(&format!("<!-- Slide number: {} -->\n", slide_number));
+        content.push_str(&slide_content);
+        content.push_str("\n\n");
+
+        if page_config.is_some() {
+            boundaries.push(PageBoundary {
+                byte_start,
+                byte_end: content.len(),
+                page_number: slide_number,
+            });
+            page_contents.push(PageContent {
+                page_number: slide_number,
+                is_blank: Some(
+                    slide_tables.is_empty() && crate::extraction::blank_detection::is_page_text_blank(&slide_content),
+                ),
+                content: slide_content,
+                tables: slide_tables.iter().cloned().map(std::sync::Arc::new).collect(),
+                images: Vec::new(),
+                hierarchy: None,
+            });
+        }
+        tables.extend(slide_tables);
+    }
+
+    let content = content.trim_end().to_string();
+    if let Some(last) = boundaries.last_mut() {
+        last.byte_end = last.byte_end.min(content.len());
+    }
+
+    let slide_count = slide_files.len();
+    let page_structure = page_config.map(|_| PageStructure {
+        total_count: slide_count,
+        unit_type: PageUnitType::Slide,
+        pages: Some(
+            page_contents
+                .iter()
+                .map(|pc| PageInfo {
+                    number: pc.page_number,
+                    title: None,
+                    dimensions: None,
+                    image_count: None,
+                    table_count: Some(pc.tables.len()),
+                    hidden: None,
+                    is_blank: pc.is_blank,
+                })
+                .collect(),
+        ),
+        boundaries: Some(boundaries),
+    });
+
+    IworkExtractionResult {
+        content,
+        tables,
+        slide_count,
+        page_structure,
+        page_contents: page_config.map(|_| page_contents),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::iwa::test_support::*;
+    use super::*;
+    use std::io::Write;
+
+    fn storage_payload(kind: u64, text: &str) -> Vec<u8> {
+        let mut payload = Vec::new();
+        field_varint(1, kind, &mut payload);
+        field_bytes(3, text.as_bytes(), &mut payload);
+        payload
+    }
+
+    fn build_bundle(files: &[(&str, Vec<u8>)]) -> Vec<u8> {
+        let mut buffer = Cursor::new(Vec::new());
+        {
+            let mut zip = zip::ZipWriter::new(&mut buffer);
+            let options = zip::write::SimpleFileOptions::default();
+            for (name, bytes) in files {
+                zip.start_file(*name, options).unwrap();
+                zip.write_all(bytes).unwrap();
+            }
+            zip.finish().unwrap();
+        }
+        buffer.into_inner()
+    }
+
+    fn native(extraction: IworkExtraction) -> IworkExtractionResult {
+        match extraction {
+            IworkExtraction::Native(result) => result,
+            IworkExtraction::PreviewPdf(_) => panic!("expected native extraction"),
+        }
+    }
+
+    #[test]
+    fn test_extract_pages_body_text() {
+        let document = build_iwa(&[
+            (1, 10000, Vec::new(), vec![2]),
+            (
+                2,
+                2001,
+                storage_payload(0, "Title\u{2029}First paragraph\u{FFFC}."),
+                vec![],
+            ),
+            (3, 2001, storage_payload(1, "Running header"), vec![]),
+        ]);
+        let bundle = build_bundle(&[("Index/Document.iwa", document)]);
+
+        let result = native(extract_iwork_from_bytes(&bundle, IworkKind::Pages, None).unwrap());
+        assert_eq!(result.content, "Title\n\nFirst paragraph.");
+        assert_eq!(result.slide_count, 0);
+    }
+
+    #[test]
+    fn test_extract_keynote_slides_in_show_order() {
+        // The document root references slide 20 before slide 10
+        let document = build_iwa(&[(1, 1, Vec::new(), vec![20, 10])]);
+        let slide_a = build_iwa(&[
+            (10, 5, Vec::new(), vec![11]),
+            (11, 2001, storage_payload(3, "Second"), vec![]),
+        ]);
+        let slide_b = build_iwa(&[
+            (20, 5, Vec::new(), vec![21]),
+            (21, 2001, storage_payload(3, "First"), vec![]),
+            (22, 2001, storage_payload(4, "Speaker note"), vec![]),
+        ]);
+        let bundle = build_bundle(&[
+            ("Index/Document.iwa", document),
+            ("Index/Slide-10.iwa", slide_a),
+            ("Index/Slide-20.iwa", slide_b),
+        ]);
+
+        let page_config = crate::core::config::PageConfig {
+            extract_pages: true,
+            ..Default::default()
+        };
+        let result = native(extract_iwork_from_bytes(&bundle, IworkKind::Keynote, Some(&page_config)).unwrap());
+
+        assert_eq!(result.slide_count, 2);
+        assert!(result.content.starts_with("<!-- Slide number: 1 -->\nFirst"));
+        assert!(result.content.contains("### Notes:\nSpeaker note"));
+        let pages = result.page_contents.unwrap();
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[1].content, "Second");
+        let boundaries = result.page_structure.unwrap().boundaries.unwrap();
+        assert_eq!(
+            &result.content[boundaries[1].byte_start..boundaries[1].byte_end],
+            "<!-- Slide number: 2 -->\nSecond"
+        );
+    }
+
+    #[test]
+    fn test_extract_numbers_table() {
+        let mut strings = Vec::new();
+        for (key, text) in [(1u64, "Name"), (2, "Score")] {
+            let mut entry = Vec::new();
+            field_varint(1, key, &mut entry);
+            field_bytes(3, text.as_bytes(), &mut entry);
+            field_bytes(3, &entry, &mut strings);
+        }
+
+        let text_cell = |id: u32| {
+            let mut cell = vec![5, 3, 0, 0, 0, 0, 0, 0];
+            cell.extend_from_slice(&0x8u32.to_le_bytes());
+            cell.extend_from_slice(&id.to_le_bytes());
+            cell
+        };
+        let mut number_cell = vec![5, 2, 0, 0, 0, 0, 0, 0];
+        number_cell.extend_from_slice(&0x2u32.to_le_bytes());
+        number_cell.extend_from_slice(&97.5f64.to_le_bytes());
+
+        let row = |cells: &[Vec<u8>], index: u64| {
+            let mut buffer = Vec::new();
+            let mut offsets = Vec::new();
+            for cell in cells {
+                offsets.extend_from_slice(&(buffer.len() as u16).to_le_bytes());
+                buffer.extend_from_slice(cell);
+            }
+            let mut info = Vec::new();
+            field_varint(1, index, &mut info);
+            field_bytes(6, &buffer, &mut info);
+            field_bytes(7, &offsets, &mut info);
+            info
+        };
+        let mut tile = Vec::new();
+        field_bytes(5, &row(&[text_cell(1), text_cell(2)], 0), &mut tile);
+        field_bytes(5, &row(&[text_cell(1), number_cell], 1), &mut tile);
+
+        let mut tile_entry = Vec::new();
+        field_varint(1, 0, &mut tile_entry);
+        reference(2, 40, &mut tile_entry);
+        let mut tile_storage = Vec::new();
+        field_bytes(1, &tile_entry, &mut tile_storage);
+        let mut data_store = Vec::new();
+        field_bytes(3, &tile_storage, &mut data_store);
+        reference(4, 41, &mut data_store);
+
+        let mut model = Vec::new();
+        field_bytes(1, b"table-1", &mut model);
+        field_bytes(4, &data_store, &mut model);
+        field_varint(6, 3, &mut model);
+        field_varint(7, 2, &mut model);
+        field_bytes(8, b"Results", &mut model);
+
+        let document = build_iwa(&[(1, 1, Vec::new(), vec![30]), (30, 6001, model, vec![40, 41])]);
+        let tables = build_iwa(&[(40, 6002, tile, vec![]), (41, 6005, strings, vec![])]);
+        let bundle = build_bundle(&[("Index/Document.iwa", document), ("Index/Tables/Tile.iwa", tables)]);
+
+        let result = native(extract_iwork_from_bytes(&bundle, IworkKind::Numbers, None).unwrap());
+        assert_eq!(result.tables.len(), 1);
+        assert_eq!(
+            result.tables[0].cells,
+            vec![
+                vec!["Name".to_string(), "Score".to_string()],
+                vec!["Name".to_string(), "97.5".to_string()],
+            ]
+        );
+        assert!(result.content.starts_with("## Results\n\n| Name | Score |"));
+    }
+
+    #[test]
+    fn test_falls_back_to_preview_pdf() {
+        let bundle = build_bundle(&[
+            ("index.xml.gz", b"not an iwa".to_vec()),
+            ("QuickLook/Preview.pdf", b"%PDF-1.4".to_vec()),
+        ]);
+
+        match extract_iwork_from_bytes(&bundle, IworkKind::Pages, None).unwrap() {
+            IworkExtraction::PreviewPdf(bytes) => assert_eq!(bytes, b"%PDF-1.4"),
+            IworkExtraction::Native(_) => panic!("expected preview fallback"),
+        }
+    }
+
+    #[test]
+    fn test_rejects_bundle_without_content() {
+        let bundle = build_bundle(&[("index.xml.gz", b"legacy".to_vec())]);
+        assert!(extract_iwork_from_bytes(&bundle, IworkKind::Pages, None).is_err());
+    }
+}
@@ -0,0 +1,318 @@
+//! Table decoding for `TST.TableModelArchive` objects.
+//!
+//! Cell values live in tiles of up to 256 rows. Each tile row stores a packed
+//! cell buffer plus a per-column offset table; strings and rich text are
+//! interned in separate data lists referenced from the table's data store.
+
+use std::collections::HashMap;
+
+use super::IworkBundle;
+use super::iwa::ProtoMessage;
+
+/// `TST.TableModelArchive` message type.
+pub(super) const TABLE_MODEL_TYPE: u32 = 6001;
+
+/// Rows per tile in the tile storage.
+const TILE_ROWS: usize = 256;
+
+/// Upper bounds guarding against corrupt dimensions.
+const MAX_ROWS: usize = 1_000_000;
+const MAX_COLUMNS: usize = 1_000;
+
+/// Supported cell storage layout version.
+const CELL_STORAGE_VERSION: u8 = 5;
+
+const NUMBER_CELL: u8 = 2;
+const TEXT_CELL: u8 = 3;
+const DATE_CELL: u8 = 5;
+const BOOL_CELL: u8 = 6;
+const DURATION_CELL: u8 = 7;
+const ERROR_CELL: u8 = 8;
+const RICH_TEXT_CELL: u8 = 9;
+const CURRENCY_CELL: u8 = 10;
+
+/// Seconds between the Unix epoch and the Core Data epoch (2001-01-01T00:00:00Z).
+const CORE_DATA_EPOCH_OFFSET: i64 = 978_307_200;
+
+/// A decoded table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IworkTable {
+    /// Table name as shown in the document (may be empty).
+    pub name: String,
+    /// Cell text in row-major order, trimmed to the last non-empty row.
+    pub cells: Vec<Vec<String>>,
+}
+
+/// Decode a `TST.TableModelArchive` payload.
+pub(super) fn decode_table(bundle: &IworkBundle, model_data: &[u8]) -> Option<IworkTable> {
+    let model = ProtoMessage::parse(model_data).ok()?;
+    let name = model.string(8).unwrap_or_default();
+    let rows = (model.varint(6).unwrap_or(0) as usize).min(MAX_ROWS);
+    let columns = (model.varint(7).unwrap_or(0) as usize).min(MAX_COLUMNS);
+    if rows == 0 || columns == 0 {
+        return None;
+    }
+
+    let data_store = model.message(4)?;
+    let strings = data_store
+        .reference(4)
+        .map(|id| string_table(bundle, id))
+        .unwrap_or_default();
+    let rich_texts = data_store
+        .reference(17)
+        .map(|id| rich_text_table(bundle, id))
+        .unwrap_or_default();
+
+    let mut cells = vec![vec![String::new(); columns]; rows];
+    let tiles = data_store.message(3)?;
+    for tile_ref in tiles.messages(1).filter_map(|t| t.ok()) {
+        let tile_start = tile_ref.varint(1).unwrap_or(0) as usize * TILE_ROWS;
+        let Some(tile) = tile_ref.reference(2).and_then(|id| bundle.get(id)) else {
+            continue;
+        };
+        let Ok(tile) = ProtoMessage::parse(&tile.data) else {
+            continue;
+        };
+
+        for row_info in tile.messages(5).filter_map(|r| r.ok()) {
+            let row = tile_start + row_info.varint(1).unwrap_or(0) as usize;
+            if row >= rows {
+                continue;
+            }
+            // Only the post-BNC storage (fields 6/7) uses the v5 cell layout
+            let (Some(buffer), Some(offsets)) = (row_info.bytes(6), row_info.bytes(7)) else {
+                continue;
+            };
+            let wide_offsets = row_info.varint(8).unwrap_or(0) != 0;
+
+            for (column, offset) in offsets.chunks_exact(2).take(columns).enumerate() {
+                let offset = u16::from_le_bytes([offset[0], offset[1]]);
+                if offset == u16::MAX {
+                    continue;
+                }
+                let offset = if wide_offsets {
+                    offset as usize * 4
+                } else {
+                    offset as usize
+                };
+                if let Some(cell) = buffer.get(offset..)
+                    && let Some(text) = decode_cell(cell, &strings, &rich_texts)
+                {
+                    cells[row][column] = text;
+                }
+            }
+        }
+    }
+
+    while cells.last().is_some_and(|row| row.iter().all(String::is_empty)) {
+        cells.pop();
+    }
+
+    Some(IworkTable { name, cells })
+}
+
+/// Decode a single v5 cell record into display text.
+fn decode_cell(buffer: &[u8], strings: &HashMap<u32, String>, rich_texts: &HashMap<u32, String>) -> Option<String> {
+    if buffer.len() < 12 || buffer[0] != CELL_STORAGE_VERSION {
+        return None;
+    }
+    let cell_type = buffer[1];
+    let flags = u32::from_le_bytes([buffer[8], buffer[9], buffer[10], buffer[11]]);
+    let mut offset = 12;
+
+    let mut decimal = None;
+    let mut double = None;
+    let mut seconds = None;
+    let mut string_id = None;
+    let mut rich_id = None;
+
+    if flags & 0x1 != 0 {
+        decimal = buffer.get(offset..offset + 16).and_then(decode_decimal128);
+        offset += 16;
+    }
+    if flags & 0x2 != 0 {
+        double = read_f64(buffer, offset);
+        offset += 8;
+    }
+    if flags & 0x4 != 0 {
+        seconds = read_f64(buffer, offset);
+        offset += 8;
+    }
+    if flags & 0x8 != 0 {
+        string_id = read_u32(buffer, offset);
+        offset += 4;
+    }
+    if flags & 0x10 != 0 {
+        rich_id = read_u32(buffer, offset);
+    }
+
+    let number = decimal.or(double);
+    match cell_type {
+        NUMBER_CELL | CURRENCY_CELL | DURATION_CELL => number.map(format_number),
+        TEXT_CELL => string_id.and_then(|id| strings.get(&id).cloned()),
+        RICH_TEXT_CELL => rich_id.and_then(|id| rich_texts.get(&id).cloned()),
+        DATE_CELL => seconds.and_then(format_core_data_date),
+        BOOL_CELL => number.map(|v| if v != 0.0 { "TRUE" } else { "FALSE" }.to_string()),
+        ERROR_CELL => Some("#ERROR".to_string()),
+        _ => None,
+    }
+}
+
+fn read_f64(buffer: &[u8], offset: usize) -> Option<f64> {
+    buffer
+        .get(offset..offset + 8)
+        .and_then(|b| b.try_into().ok())
+        .map(f64::from_le_bytes)
+}
+
+fn read_u32(buffer: &[u8], offset: usize) -> Option<u32> {
+    buffer
+        .get(offset..offset + 4)
+        .and_then(|b| b.try_into().ok())
+        .map(u32::from_le_bytes)
+}
+
+/// Decode an IEEE 754-2008 decimal128 value in binary integer decimal encoding.
+fn decode_decimal128(bytes: &[u8]) -> Option<f64> {
+    if bytes.len() != 16 {
+        return None;
+    }
+    let exponent = ((((bytes[15] & 0x7f) as i32) << 7) | (bytes[14] >> 1) as i32) - 0x1820;
+    let mut mantissa = (bytes[14] & 1) as f64;
+    for byte in bytes[..14].iter().rev() {
+        mantissa = mantissa * 256.0 + *byte as f64;
+    }
+    let value = mantissa * 10f64.powi(exponent);
+    Some(if bytes[15] & 0x80 != 0 { -value } else { value })
+}
+
+fn format_number(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{}", value as i64)
+    } else {
+        // Round away binary noise from the decimal conversion
+        let rounded = format!("{:.10}", value);
+        rounded.trim_end_matches('0').trim_end_matches('.').to_string()
+    }
+}
+
+/// Format seconds since 2001-01-01 UTC as an ISO 8601 date or date-time.
+fn format_core_data_date(seconds: f64) -> Option<String> {
+    if !seconds.is_finite() {
+        return None;
+    }
+    let unix = seconds.round() as i64 + CORE_DATA_EPOCH_OFFSET;
+    let days = unix.div_euclid(86_400);
+    let secs_of_day = unix.rem_euclid(86_400);
+    let (year, month, day) = civil_from_days(days);
+
+    if secs_of_day == 0 {
+        Some(format!("{:04}-{:02}-{:02}", year, month, day))
+    } else {
+        Some(format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            year,
+            month,
+            day,
+            secs_of_day / 3600,
+            (secs_of_day % 3600) / 60,
+            secs_of_day % 60
+        ))
+    }
+}
+
+/// Convert days since the Unix epoch to a proleptic Gregorian `(year, month, day)`.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Read a `TST.TableDataList` of plain strings into `key → string`.
+fn string_table(bundle: &IworkBundle, id: u64) -> HashMap<u32, String> {
+    data_list_entries(bundle, id)
+        .into_iter()
+        .filter_map(|entry| Some((entry.varint(1)? as u32, entry.string(3)?)))
+        .collect()
+}
+
+/// Read a rich text `TST.TableDataList` into `key → plain text`.
+///
+/// Each entry references a payload object which in turn references the
+/// `TSWP.StorageArchive` holding the cell text.
+fn rich_text_table(bundle: &IworkBundle, id: u64) -> HashMap<u32, String> {
+    data_list_entries(bundle, id)
+        .into_iter()
+        .filter_map(|entry| {
+            let key = entry.varint(1)? as u32;
+            let payload = bundle.get(entry.reference(4)?)?;
+            let text = bundle.storage_text(payload).or_else(|| {
+                let storage_id = ProtoMessage::parse(&payload.data).ok()?.reference(1)?;
+                bundle.storage_text(bundle.get(storage_id)?)
+            })?;
+            Some((key, text))
+        })
+        .collect()
+}
+
+fn data_list_entries(bundle: &IworkBundle, id: u64) -> Vec<ProtoMessage<'_>> {
+    bundle
+        .get(id)
+        .and_then(|list| ProtoMessage::parse(&list.data).ok())
+        .map(|list| list.messages(3).filter_map(|e| e.ok()).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_decimal128() {
+        // 12345 * 10^-2
+        let mut bytes = [0u8; 16];
+        bytes[..2].copy_from_slice(&12345u16.to_le_bytes());
+        let exponent = (0x1820 - 2) as u16;
+        bytes[14] = ((exponent & 0x7f) << 1) as u8;
+        bytes[15] = (exponent >> 7) as u8;
+
+        assert_eq!(decode_decimal128(&bytes).map(format_number).as_deref(), Some("123.45"));
+    }
+
+    #[test]
+    fn test_format_core_data_date() {
+        assert_eq!(format_core_data_date(0.0).as_deref(), Some("2001-01-01"));
+        assert_eq!(
+            format_core_data_date(86_400.0 * 365.0 + 3661.0).as_deref(),
+            Some("2002-01-01T01:01:01")
+        );
+    }
+
+    #[test]
+    fn test_decode_cell_types() {
+        let mut strings = HashMap::new();
+        strings.insert(4, "Revenue".to_string());
+
+        let mut text = vec![CELL_STORAGE_VERSION, TEXT_CELL, 0, 0, 0, 0, 0, 0];
+        text.extend_from_slice(&0x8u32.to_le_bytes());
+        text.extend_from_slice(&4u32.to_le_bytes());
+        assert_eq!(
+            decode_cell(&text, &strings, &HashMap::new()).as_deref(),
+            Some("Revenue")
+        );
+
+        let mut number = vec![CELL_STORAGE_VERSION, NUMBER_CELL, 0, 0, 0, 0, 0, 0];
+        number.extend_from_slice(&0x2u32.to_le_bytes());
+        number.extend_from_slice(&42.0f64.to_le_bytes());
+        assert_eq!(decode_cell(&number, &strings, &HashMap::new()).as_deref(), Some("42"));
+
+        assert_eq!(decode_cell(&[4, TEXT_CELL], &strings, &HashMap::new()), None);
+    }
+}
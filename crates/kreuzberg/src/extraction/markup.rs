@@ -0,0 +1,401 @@
+//! Shared block model for lightweight markup languages.
+//!
+//! The AsciiDoc, Textile and MediaWiki extractors parse their source into a
+//! flat list of [`MarkupBlock`]s. This module turns those blocks into the text
+//! content, [`Table`]s and [`DocumentStructure`] of an extraction result, so
+//! all three formats expose the same node types.
+
+use crate::types::document_structure::GridCell;
+use crate::types::{ContentLayer, DocumentNode, DocumentStructure, NodeContent, NodeId, NodeIndex, Table, TableGrid};
+
+/// A block-level element of a markup document.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MarkupBlock {
+    /// Section heading (level 1 is the top-most section).
+    Heading { level: u8, text: String },
+    /// Paragraph of inline text.
+    Paragraph(String),
+    /// Bullet or numbered list; nested items are flattened.
+    List { ordered: bool, items: Vec<String> },
+    /// Literal or source code block.
+    Code { language: Option<String>, text: String },
+    /// Table rows; the first row is treated as the header.
+    Table(Vec<Vec<String>>),
+    /// Block quote or admonition (`label` holds e.g. `NOTE`).
+    Quote {
+        label: Option<String>,
+        paragraphs: Vec<String>,
+    },
+}
+
+/// Render blocks as markdown-style text.
+pub fn render_text(blocks: &[MarkupBlock]) -> String {
+    let mut sections: Vec<String> = Vec::with_capacity(blocks.len());
+
+    for block in blocks {
+        let rendered = match block {
+            MarkupBlock::Heading { level, text } => format!("{} {}", "#".repeat(*level as usize), text),
+            MarkupBlock::Paragraph(text) => text.clone(),
+            MarkupBlock::List { ordered, items } => items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| {
+                    if *ordered {
+                        format!("{}. {}", i + 1, item)
+                    } else {
+                        format!("- {}", item)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            MarkupBlock::Code { language, text } => {
+                format!("```{}\n{}\n```", language.as_deref().unwrap_or(""), text)
+            }
+            MarkupBlock::Table(rows) => cells_to_markdown(rows).trim_end().to_string(),
+            MarkupBlock::Quote { label, paragraphs } => quote_paragraphs(label.as_deref(), paragraphs)
+                .iter()
+                .map(|p| format!("> {}", p))
+                .collect::<Vec<_>>()
+                .join("\n>\n"),
+        };
+        if !rendered.trim().is_empty() {
+            sections.push(rendered);
+        }
+    }
+
+    sections.join("\n\n")
+}
+
+/// Collect the table blocks as [`Table`]s.
+pub fn extract_tables(blocks: &[MarkupBlock]) -> Vec<Table> {
+    blocks
+        .iter()
+        .filter_map(|block| match block {
+            MarkupBlock::Table(rows) if !rows.is_empty() => Some(Table {
+                cells: rows.clone(),
+                markdown: cells_to_markdown(rows),
+                page_number: 1,
+                bounding_box: None,
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Build a [`DocumentStructure`] with heading-driven section nesting.
+pub fn build_document_structure(blocks: &[MarkupBlock]) -> DocumentStructure {
+    let mut builder = StructureBuilder {
+        doc: DocumentStructure::with_capacity(blocks.len() * 2),
+        section_stack: Vec::new(),
+    };
+
+    for block in blocks {
+        match block {
+            MarkupBlock::Heading { level, text } => {
+                let level = (*level).clamp(1, 6);
+                while builder.section_stack.last().is_some_and(|(l, _)| *l >= level) {
+                    builder.section_stack.pop();
+                }
+                let group = builder.push(
+                    NodeContent::Group {
+                        label: None,
+                        heading_level: Some(level),
+                        heading_text: Some(text.clone()),
+                    },
+                    builder.current_section(),
+                );
+                builder.push(
+                    NodeContent::Heading {
+                        level,
+                        text: text.clone(),
+                    },
+                    Some(group),
+                );
+                builder.section_stack.push((level, group));
+            }
+            MarkupBlock::Paragraph(text) => {
+                builder.push(NodeContent::Paragraph { text: text.clone() }, builder.current_section());
+            }
+            MarkupBlock::List { ordered, items } => {
+                let list = builder.push(NodeContent::List { ordered: *ordered }, builder.current_section());
+                for item in items {
+                    builder.push(NodeContent::ListItem { text: item.clone() }, Some(list));
+                }
+            }
+            MarkupBlock::Code { language, text } => {
+                builder.push(
+                    NodeContent::Code {
+                        text: text.clone(),
+                        language: language.clone(),
+                    },
+                    builder.current_section(),
+                );
+            }
+            MarkupBlock::Table(rows) => {
+                builder.push(
+                    NodeContent::Table {
+                        grid: cells_to_grid(rows),
+                    },
+                    builder.current_section(),
+                );
+            }
+            MarkupBlock::Quote { label, paragraphs } => {
+                let quote = builder.push(NodeContent::Quote, builder.current_section());
+                for text in quote_paragraphs(label.as_deref(), paragraphs) {
+                    builder.push(NodeContent::Paragraph { text }, Some(quote));
+                }
+            }
+        }
+    }
+
+    builder.doc
+}
+
+struct StructureBuilder {
+    doc: DocumentStructure,
+    section_stack: Vec<(u8, NodeIndex)>,
+}
+
+impl StructureBuilder {
+    fn current_section(&self) -> Option<NodeIndex> {
+        self.section_stack.last().map(|(_, idx)| *idx)
+    }
+
+    fn push(&mut self, content: NodeContent, parent: Option<NodeIndex>) -> NodeIndex {
+        let index = self.doc.len() as u32;
+        let node = DocumentNode {
+            id: NodeId::generate(content.node_type_str(), content.text().unwrap_or(""), None, index),
+            content,
+            parent: None,
+            children: Vec::new(),
+            content_layer: ContentLayer::Body,
+            page: None,
+            page_end: None,
+            bbox: None,
+            annotations: Vec::new(),
+        };
+        let idx = self.doc.push_node(node);
+        if let Some(parent) = parent {
+            self.doc.add_child(parent, idx);
+        }
+        idx
+    }
+}
+
+/// Prefix the admonition label to the first quoted paragraph.
+fn quote_paragraphs(label: Option<&str>, paragraphs: &[String]) -> Vec<String> {
+    let mut out: Vec<String> = paragraphs.iter().filter(|p| !p.trim().is_empty()).cloned().collect();
+    if let Some(label) = label {
+        match out.first_mut() {
+            Some(first) => *first = format!("{}: {}", label, first),
+            None => out.push(label.to_string()),
+        }
+    }
+    out
+}
+
+fn cells_to_grid(rows: &[Vec<String>]) -> TableGrid {
+    let cols = rows.iter().map(Vec::len).max().unwrap_or(0) as u32;
+    let cells = rows
+        .iter()
+        .enumerate()
+        .flat_map(|(row_idx, row)| {
+            row.iter().enumerate().map(move |(col_idx, content)| GridCell {
+                content: content.clone(),
+                row: row_idx as u32,
+                col: col_idx as u32,
+                row_span: 1,
+                col_span: 1,
+                is_header: row_idx == 0,
+                bbox: None,
+            })
+        })
+        .collect();
+
+    TableGrid {
+        rows: rows.len() as u32,
+        cols,
+        cells,
+    }
+}
+
+/// Remove paired inline delimiters such as `*bold*` or `@code@`, keeping the text.
+///
+/// An opening delimiter must follow the start of the text or a non-alphanumeric
+/// character and precede a non-space; a closing delimiter must follow a
+/// non-space and not be followed by an alphanumeric character. This leaves
+/// `snake_case` or `2*3*4` untouched.
+pub fn unwrap_delimited(text: &str, delimiter: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut pos = 0;
+
+    while let Some(rel) = text[pos..].find(delimiter) {
+        let start = pos + rel;
+        let inner_start = start + delimiter.len();
+        let opens = text[..start].chars().next_back().is_none_or(|c| !c.is_alphanumeric())
+            && text[inner_start..].chars().next().is_some_and(|c| !c.is_whitespace());
+
+        let close = opens
+            .then(|| {
+                let mut search = inner_start;
+                while let Some(rel) = text[search..].find(delimiter) {
+                    let end = search + rel;
+                    let before = text[..end].chars().next_back();
+                    let after = text[end + delimiter.len()..].chars().next();
+                    if end > inner_start
+                        && before.is_some_and(|c| !c.is_whitespace())
+                        && after.is_none_or(|c| !c.is_alphanumeric())
+                    {
+                        return Some(end);
+                    }
+                    search = end + delimiter.len();
+                }
+                None
+            })
+            .flatten();
+
+        match close {
+            Some(end) => {
+                out.push_str(&text[pos..start]);
+                out.push_str(&text[inner_start..end]);
+                pos = end + delimiter.len();
+            }
+            None => {
+                out.push_str(&text[pos..inner_start]);
+                pos = inner_start;
+            }
+        }
+    }
+
+    out.push_str(&text[pos..]);
+    out
+}
+
+/// Convert table rows to a markdown table.
+pub fn cells_to_markdown(rows: &[Vec<String>]) -> String {
+    let Some(header) = rows.first() else {
+        return String::new();
+    };
+    let cols = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let row_line = |row: &[String]| {
+        let mut line = String::from("|");
+        for col in 0..cols {
+            line.push(' ');
+            line.push_str(&row.get(col).map(|c| c.replace('|', "\\|")).unwrap_or_default());
+            line.push_str(" |");
+        }
+        line.push('\n');
+        line
+    };
+
+    let mut md = row_line(header);
+    md.push('|');
+    for _ in 0..cols {
+        md.push_str(" --- |");
+    }
+    md.push('\n');
+    for row in &rows[1..] {
+        md.push_str(&row_line(row));
+    }
+    md
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Vec<MarkupBlock> {
+        vec![
+            MarkupBlock::Heading {
+                level: 1,
+                text: "Install".to_string(),
+            },
+            MarkupBlock::Paragraph("Run the installer.".to_string()),
+            MarkupBlock::List {
+                ordered: true,
+                items: vec!["Download".to_string(), "Run".to_string()],
+            },
+            MarkupBlock::Heading {
+                level: 2,
+                text: "Config".to_string(),
+            },
+            MarkupBlock::Code {
+                language: Some("toml".to_string()),
+                text: "key = 1".to_string(),
+            },
+            MarkupBlock::Quote {
+                label: Some("NOTE".to_string()),
+                paragraphs: vec!["Restart afterwards.".to_string()],
+            },
+            MarkupBlock::Table(vec![
+                vec!["Key".to_string(), "Default".to_string()],
+                vec!["port".to_string(), "8000".to_string()],
+            ]),
+        ]
+    }
+
+    #[test]
+    fn test_render_text() {
+        let text = render_text(&sample());
+        assert!(text.starts_with("# Install\n\nRun the installer."));
+        assert!(text.contains("1. Download\n2. Run"));
+        assert!(text.contains("## Config"));
+        assert!(text.contains("```toml\nkey = 1\n```"));
+        assert!(text.contains("> NOTE: Restart afterwards."));
+        assert!(text.contains("| port | 8000 |"));
+    }
+
+    #[test]
+    fn test_unwrap_delimited() {
+        assert_eq!(unwrap_delimited("a *bold* word", "*"), "a bold word");
+        assert_eq!(unwrap_delimited("*one* and *two*", "*"), "one and two");
+        assert_eq!(unwrap_delimited("snake_case_name", "_"), "snake_case_name");
+        assert_eq!(unwrap_delimited("2 * 3 * 4", "*"), "2 * 3 * 4");
+        assert_eq!(unwrap_delimited("call @run()@ now", "@"), "call run() now");
+        assert_eq!(unwrap_delimited("(**strong**)", "**"), "(strong)");
+    }
+
+    #[test]
+    fn test_extract_tables() {
+        let tables = extract_tables(&sample());
+        assert_eq!(tables.len(), 1);
+        assert_eq!(tables[0].cells[1], vec!["port", "8000"]);
+        assert!(tables[0].markdown.contains("| --- | --- |"));
+    }
+
+    #[test]
+    fn test_build_document_structure_nests_sections() {
+        let doc = build_document_structure(&sample());
+        assert!(doc.validate().is_ok());
+
+        let install = &doc.nodes[0];
+        assert!(matches!(
+            install.content,
+            NodeContent::Group {
+                heading_level: Some(1),
+                ..
+            }
+        ));
+        assert!(install.parent.is_none());
+
+        let config_idx = doc
+            .nodes
+            .iter()
+            .position(|n| matches!(&n.content, NodeContent::Heading { text, .. } if text == "Config"))
+            .unwrap();
+        let config_group = doc.nodes[config_idx].parent.unwrap();
+        assert_eq!(doc.nodes[config_group.0 as usize].parent, Some(NodeIndex(0)));
+
+        let types: Vec<&str> = doc.nodes.iter().map(|n| n.content.node_type_str()).collect();
+        for expected in ["list", "list_item", "code", "quote", "table"] {
+            assert!(types.contains(&expected), "missing {expected} in {types:?}");
+        }
+
+        let quote_idx = types.iter().position(|t| *t == "quote").unwrap();
+        let child = doc.nodes[quote_idx].children[0];
+        assert_eq!(
+            doc.nodes[child.0 as usize].content.text(),
+            Some("NOTE: Restart afterwards.")
+        );
+    }
+}
@@ -0,0 +1,279 @@
+//! HUFF/CDIC decompression used by Mobipocket books.
+//!
+//! The HUFF record holds a canonical Huffman code table; the CDIC records hold
+//! the phrase dictionary the codes index into. Phrases can themselves be
+//! compressed and are expanded on first use.
+
+use crate::error::{KreuzbergError, Result};
+
+/// Maximum nesting of compressed phrases before the dictionary is considered corrupt.
+const MAX_PHRASE_DEPTH: usize = 32;
+
+#[derive(Debug, Clone, Copy, Default)]
+struct CodeEntry {
+    code_len: u32,
+    terminal: bool,
+    max_code: u64,
+}
+
+#[derive(Debug, Clone)]
+enum Phrase {
+    /// Fully expanded phrase bytes.
+    Literal(Vec<u8>),
+    /// Phrase still compressed with the same code table.
+    Compressed(Vec<u8>),
+    /// Phrase currently being expanded (guards against cycles).
+    Expanding,
+}
+
+/// HUFF/CDIC decoder state.
+#[derive(Debug)]
+pub(super) struct HuffCdicReader {
+    dict1: Vec<CodeEntry>,
+    min_code: [u64; 33],
+    max_code: [u64; 33],
+    phrases: Vec<Phrase>,
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    data.get(offset..offset + 2).map(|b| u16::from_be_bytes([b[0], b[1]]))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    data.get(offset..offset + 4)
+        .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+fn corrupt(message: &str) -> KreuzbergError {
+    KreuzbergError::parsing(format!("Invalid MOBI HUFF/CDIC data: {}", message))
+}
+
+impl HuffCdicReader {
+    /// Build a decoder from the HUFF record and its CDIC records.
+    pub(super) fn new(huff: &[u8], cdics: &[&[u8]]) -> Result<Self> {
+        if !huff.starts_with(b"HUFF") {
+            return Err(corrupt("missing HUFF header"));
+        }
+        let off1 = read_u32(huff, 8).ok_or_else(|| corrupt("truncated HUFF header"))? as usize;
+        let off2 = read_u32(huff, 12).ok_or_else(|| corrupt("truncated HUFF header"))? as usize;
+
+        let mut dict1 = Vec::with_capacity(256);
+        for i in 0..256 {
+            let value = read_u32(huff, off1 + i * 4).ok_or_else(|| corrupt("truncated code table"))?;
+            let code_len = value & 0x1F;
+            if code_len == 0 {
+                return Err(corrupt("zero-length code"));
+            }
+            let max_code = (((value >> 8) as u64 + 1) << (32 - code_len)) - 1;
+            dict1.push(CodeEntry {
+                code_len,
+                terminal: value & 0x80 != 0,
+                max_code,
+            });
+        }
+
+        let mut min_code = [0u64; 33];
+        let mut max_code = [0u64; 33];
+        for code_len in 1..=32usize {
+            let offset = off2 + (code_len - 1) * 8;
+            let min = read_u32(huff, offset).ok_or_else(|| corrupt("truncated code limits"))? as u64;
+            let max = read_u32(huff, offset + 4).ok_or_else(|| corrupt("truncated code limits"))? as u64;
+            min_code[code_len] = min << (32 - code_len);
+            max_code[code_len] = ((max + 1) << (32 - code_len)).saturating_sub(1);
+        }
+
+        let mut phrases = Vec::new();
+        for cdic in cdics {
+            if !cdic.starts_with(b"CDIC") {
+                return Err(corrupt("missing CDIC header"));
+            }
+            let total = read_u32(cdic, 8).ok_or_else(|| corrupt("truncated CDIC header"))? as usize;
+            let bits = read_u32(cdic, 12).ok_or_else(|| corrupt("truncated CDIC header"))?;
+            let count = (1usize << bits.min(16)).min(total.saturating_sub(phrases.len()));
+
+            for i in 0..count {
+                let offset = read_u16(cdic, 16 + i * 2).ok_or_else(|| corrupt("truncated phrase offsets"))? as usize;
+                let header = read_u16(cdic, 16 + offset).ok_or_else(|| corrupt("phrase out of range"))?;
+                let start = 18 + offset;
+                let end = (start + (header & 0x7FFF) as usize).min(cdic.len());
+                let bytes = cdic.get(start..end).unwrap_or_default().to_vec();
+                phrases.push(if header & 0x8000 != 0 {
+                    Phrase::Literal(bytes)
+                } else {
+                    Phrase::Compressed(bytes)
+                });
+            }
+        }
+
+        Ok(Self {
+            dict1,
+            min_code,
+            max_code,
+            phrases,
+        })
+    }
+
+    /// Decompress one text record.
+    pub(super) fn decompress(&mut self, data: &[u8]) -> Result<Vec<u8>> {
+        let mut out = Vec::with_capacity(data.len() * 3);
+        self.unpack(data, 0, &mut out)?;
+        Ok(out)
+    }
+
+    fn unpack(&mut self, data: &[u8], depth: usize, out: &mut Vec<u8>) -> Result<()> {
+        if depth > MAX_PHRASE_DEPTH {
+            return Err(corrupt("phrase nesting too deep"));
+        }
+
+        let read_window = |pos: usize| -> u64 {
+            let mut buf = [0u8; 8];
+            for (i, byte) in buf.iter_mut().enumerate() {
+                *byte = data.get(pos + i).copied().unwrap_or(0);
+            }
+            u64::from_be_bytes(buf)
+        };
+
+        let mut bits_left = data.len() as i64 * 8;
+        let mut pos = 0usize;
+        let mut window = read_window(pos);
+        let mut n: i64 = 32;
+
+        loop {
+            if n <= 0 {
+                pos += 4;
+                window = read_window(pos);
+                n += 32;
+            }
+            let code = (window >> n) & 0xFFFF_FFFF;
+
+            let entry = self.dict1[(code >> 24) as usize];
+            let mut code_len = entry.code_len as usize;
+            let mut max_code = entry.max_code;
+            if !entry.terminal {
+                while code_len < 32 && code < self.min_code[code_len] {
+                    code_len += 1;
+                }
+                max_code = self.max_code[code_len];
+            }
+
+            n -= code_len as i64;
+            bits_left -= code_len as i64;
+            if bits_left < 0 {
+                break;
+            }
+
+            let index = (max_code.wrapping_sub(code) >> (32 - code_len)) as usize;
+            let phrase = self
+                .phrases
+                .get_mut(index)
+                .ok_or_else(|| corrupt("phrase index out of range"))?;
+
+            match std::mem::replace(phrase, Phrase::Expanding) {
+                Phrase::Literal(bytes) => {
+                    out.extend_from_slice(&bytes);
+                    self.phrases[index] = Phrase::Literal(bytes);
+                }
+                Phrase::Compressed(bytes) => {
+                    let mut expanded = Vec::with_capacity(bytes.len() * 2);
+                    self.unpack(&bytes, depth + 1, &mut expanded)?;
+                    out.extend_from_slice(&expanded);
+                    self.phrases[index] = Phrase::Literal(expanded);
+                }
+                Phrase::Expanding => return Err(corrupt("recursive phrase")),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub(super) mod test_support {
+    /// Build a HUFF record where every byte is an 8-bit terminal code and
+    /// byte `b` selects phrase `255 - b`.
+    pub(crate) fn identity_huff() -> Vec<u8> {
+        let off1 = 24u32;
+        let off2 = off1 + 256 * 4;
+        let mut huff = b"HUFF".to_vec();
+        huff.extend_from_slice(&24u32.to_be_bytes());
+        huff.extend_from_slice(&off1.to_be_bytes());
+        huff.extend_from_slice(&off2.to_be_bytes());
+        huff.extend_from_slice(&[0u8; 8]);
+        for _ in 0..256 {
+            huff.extend_from_slice(&((255u32 << 8) | 0x80 | 8).to_be_bytes());
+        }
+        huff.extend_from_slice(&[0u8; 64 * 4]);
+        huff
+    }
+
+    /// Build a CDIC record of 256 phrases; `phrases[i]` is `(bytes, literal)`.
+    pub(crate) fn cdic(phrases: &[(Vec<u8>, bool)]) -> Vec<u8> {
+        let mut offsets = Vec::new();
+        let mut body = Vec::new();
+        let table_len = phrases.len() * 2;
+        for (bytes, literal) in phrases {
+            offsets.push((table_len + body.len()) as u16);
+            let flag = if *literal { 0x8000 } else { 0 };
+            body.extend_from_slice(&(bytes.len() as u16 | flag).to_be_bytes());
+            body.extend_from_slice(bytes);
+        }
+
+        let mut record = b"CDIC".to_vec();
+        record.extend_from_slice(&16u32.to_be_bytes());
+        record.extend_from_slice(&(phrases.len() as u32).to_be_bytes());
+        record.extend_from_slice(&8u32.to_be_bytes());
+        for offset in offsets {
+            record.extend_from_slice(&offset.to_be_bytes());
+        }
+        record.extend_from_slice(&body);
+        record
+    }
+
+    /// Phrase table where phrase `255 - b` is the single byte `b`.
+    pub(crate) fn identity_phrases() -> Vec<(Vec<u8>, bool)> {
+        (0..256u32).map(|i| (vec![(255 - i) as u8], true)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_support::*;
+    use super::*;
+
+    #[test]
+    fn test_huffcdic_literal_phrases() {
+        let huff = identity_huff();
+        let cdic = cdic(&identity_phrases());
+        let mut reader = HuffCdicReader::new(&huff, &[&cdic]).unwrap();
+
+        assert_eq!(reader.decompress(b"Hello").unwrap(), b"Hello");
+    }
+
+    #[test]
+    fn test_huffcdic_compressed_phrase_is_expanded() {
+        let huff = identity_huff();
+        let mut phrases = identity_phrases();
+        // Phrase for byte 0x01 expands to "ab" through two nested codes
+        phrases[255 - 0x01] = (b"ab".to_vec(), false);
+        let cdic = cdic(&phrases);
+        let mut reader = HuffCdicReader::new(&huff, &[&cdic]).unwrap();
+
+        assert_eq!(reader.decompress(&[b'x', 0x01, 0x01]).unwrap(), b"xabab");
+    }
+
+    #[test]
+    fn test_huffcdic_recursive_phrase_errors() {
+        let huff = identity_huff();
+        let mut phrases = identity_phrases();
+        phrases[255 - 0x01] = (vec![0x01], false);
+        let cdic = cdic(&phrases);
+        let mut reader = HuffCdicReader::new(&huff, &[&cdic]).unwrap();
+
+        assert!(reader.decompress(&[0x01]).is_err());
+    }
+
+    #[test]
+    fn test_huffcdic_invalid_header() {
+        assert!(HuffCdicReader::new(b"NOPE", &[]).is_err());
+    }
+}
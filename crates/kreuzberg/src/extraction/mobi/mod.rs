@@ -0,0 +1,486 @@
+//! Mobipocket (MOBI, AZW) and Kindle Format 8 (AZW3) parsing.
+//!
+//! Books are PalmDB databases: record 0 carries the PalmDOC and MOBI headers
+//! plus optional EXTH metadata, followed by the compressed text records.
+//! Text is stored either uncompressed, PalmDOC LZ77 compressed or HUFF/CDIC
+//! compressed. The decoded text is HTML that callers convert with
+//! [`crate::extraction::html`].
+
+mod huffcdic;
+mod palmdoc;
+
+use crate::error::{KreuzbergError, Result};
+use huffcdic::HuffCdicReader;
+
+/// MIME type for Mobipocket books (`.mobi`, `.prc`).
+pub const MOBI_MIME_TYPE: &str = "application/x-mobipocket-ebook";
+/// MIME type for Kindle books (`.azw`).
+pub const AZW_MIME_TYPE: &str = "application/vnd.amazon.ebook";
+/// MIME type for Kindle Format 8 books (`.azw3`).
+pub const AZW3_MIME_TYPE: &str = "application/vnd.amazon.mobi8-ebook";
+
+const PALMDB_HEADER_LEN: usize = 78;
+
+const COMPRESSION_NONE: u16 = 1;
+const COMPRESSION_PALMDOC: u16 = 2;
+const COMPRESSION_HUFF_CDIC: u16 = 17480;
+
+const ENCODING_UTF8: u32 = 65001;
+
+/// Upper bound on the decoded text size, guarding against corrupt headers.
+const MAX_TEXT_LENGTH: usize = 256 * 1024 * 1024;
+
+/// EXTH record types.
+const EXTH_AUTHOR: u32 = 100;
+const EXTH_PUBLISHER: u32 = 101;
+const EXTH_DESCRIPTION: u32 = 103;
+const EXTH_SUBJECT: u32 = 105;
+const EXTH_PUBLISHED: u32 = 106;
+const EXTH_UPDATED_TITLE: u32 = 503;
+const EXTH_LANGUAGE: u32 = 524;
+
+/// A decoded Mobipocket book.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MobiBook {
+    /// Book HTML (first text flow for KF8 books).
+    pub html: String,
+    pub title: Option<String>,
+    pub authors: Vec<String>,
+    pub publisher: Option<String>,
+    pub description: Option<String>,
+    pub subjects: Vec<String>,
+    pub language: Option<String>,
+    pub published: Option<String>,
+    /// Whether the text was stored in Kindle Format 8 layout.
+    pub is_kf8: bool,
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    data.get(offset..offset + 2).map(|b| u16::from_be_bytes([b[0], b[1]]))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    data.get(offset..offset + 4)
+        .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+/// PalmDB record table.
+struct PalmDb<'a> {
+    data: &'a [u8],
+    offsets: Vec<usize>,
+}
+
+impl<'a> PalmDb<'a> {
+    fn parse(data: &'a [u8]) -> Result<Self> {
+        if data.len() < PALMDB_HEADER_LEN {
+            return Err(KreuzbergError::parsing("File too small to be a MOBI book"));
+        }
+        let kind = &data[60..68];
+        if kind != b"BOOKMOBI" && kind != b"TEXtREAd" {
+            return Err(KreuzbergError::parsing(
+                "Not a MOBI book: missing BOOKMOBI/TEXtREAd signature",
+            ));
+        }
+
+        let count = read_u16(data, 76).unwrap_or(0) as usize;
+        let mut offsets = Vec::with_capacity(count);
+        for i in 0..count {
+            let offset = read_u32(data, PALMDB_HEADER_LEN + i * 8)
+                .ok_or_else(|| KreuzbergError::parsing("Truncated MOBI record table"))?
+                as usize;
+            offsets.push(offset.min(data.len()));
+        }
+        if offsets.is_empty() {
+            return Err(KreuzbergError::parsing("MOBI book has no records"));
+        }
+
+        Ok(Self { data, offsets })
+    }
+
+    fn record(&self, index: usize) -> Option<&'a [u8]> {
+        let start = *self.offsets.get(index)?;
+        let end = self.offsets.get(index + 1).copied().unwrap_or(self.data.len());
+        self.data.get(start..end.max(start))
+    }
+}
+
+/// Size of a trailing entry, stored as a variable-width integer ending the record.
+///
+/// The integer's first byte has its high bit set; only the last four bytes are considered.
+fn backward_varint(data: &[u8]) -> usize {
+    let mut value = 0usize;
+    for &byte in &data[data.len().saturating_sub(4)..] {
+        if byte & 0x80 != 0 {
+            value = 0;
+        }
+        value = (value << 7) | (byte & 0x7F) as usize;
+    }
+    value
+}
+
+/// Number of trailing bytes appended to a text record according to the extra data flags.
+fn trailing_entries_size(record: &[u8], extra_flags: u16) -> usize {
+    let mut size = 0usize;
+    let mut flags = extra_flags >> 1;
+    while flags != 0 {
+        if flags & 1 != 0 {
+            size += backward_varint(&record[..record.len().saturating_sub(size)]);
+        }
+        flags >>= 1;
+    }
+    if extra_flags & 1 != 0
+        && let Some(&byte) = record.len().checked_sub(size + 1).and_then(|i| record.get(i))
+    {
+        size += (byte & 0x3) as usize + 1;
+    }
+    size.min(record.len())
+}
+
+/// Parse EXTH records into `(type, data)` pairs.
+fn parse_exth(record0: &[u8], offset: usize) -> Vec<(u32, &[u8])> {
+    let mut records = Vec::new();
+    if record0.get(offset..offset + 4) != Some(b"EXTH".as_slice()) {
+        return records;
+    }
+    let count = read_u32(record0, offset + 8).unwrap_or(0) as usize;
+    let mut pos = offset + 12;
+    for _ in 0..count {
+        let (Some(kind), Some(len)) = (read_u32(record0, pos), read_u32(record0, pos + 4)) else {
+            break;
+        };
+        let len = len as usize;
+        if len < 8 {
+            break;
+        }
+        let Some(value) = record0.get(pos + 8..pos + len) else {
+            break;
+        };
+        records.push((kind, value));
+        pos += len;
+    }
+    records
+}
+
+/// Decode Windows-1252 bytes, mapping the 0x80-0x9F range to their Unicode equivalents.
+pub(crate) fn decode_windows_1252(bytes: &[u8]) -> String {
+    const HIGH: [char; 32] = [
+        '\u{20AC}', '\u{81}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}', '\u{02C6}',
+        '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{8D}', '\u{017D}', '\u{8F}', '\u{90}', '\u{2018}',
+        '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}', '\u{02DC}', '\u{2122}', '\u{0161}',
+        '\u{203A}', '\u{0153}', '\u{9D}', '\u{017E}', '\u{0178}',
+    ];
+    bytes
+        .iter()
+        .map(|&b| match b {
+            0x80..=0x9F => HIGH[(b - 0x80) as usize],
+            _ => b as char,
+        })
+        .collect()
+}
+
+fn decode_text(bytes: &[u8], encoding: u32) -> String {
+    if encoding == ENCODING_UTF8 {
+        String::from_utf8_lossy(bytes).into_owned()
+    } else {
+        decode_windows_1252(bytes)
+    }
+}
+
+/// Parse a MOBI, AZW or AZW3 book.
+pub fn parse_mobi(data: &[u8]) -> Result<MobiBook> {
+    let db = PalmDb::parse(data)?;
+    let record0 = db
+        .record(0)
+        .filter(|r| r.len() >= 16)
+        .ok_or_else(|| KreuzbergError::parsing("MOBI header record is truncated"))?;
+
+    let compression = read_u16(record0, 0).unwrap_or(COMPRESSION_NONE);
+    let text_length = (read_u32(record0, 4).unwrap_or(0) as usize).min(MAX_TEXT_LENGTH);
+    let text_records = read_u16(record0, 8).unwrap_or(0) as usize;
+    let encryption = read_u16(record0, 12).unwrap_or(0);
+    if encryption != 0 {
+        return Err(KreuzbergError::UnsupportedFormat(
+            "DRM-protected MOBI books are not supported".to_string(),
+        ));
+    }
+
+    let mut book = MobiBook::default();
+    let mut encoding = 1252;
+    let mut extra_flags = 0u16;
+    let mut huff_records = None;
+    let mut fdst_index = None;
+
+    if record0.get(16..20) == Some(b"MOBI".as_slice()) {
+        let header_len = read_u32(record0, 20).unwrap_or(0) as usize;
+        encoding = read_u32(record0, 28).unwrap_or(1252);
+        let version = read_u32(record0, 36).unwrap_or(0);
+        book.is_kf8 = version >= 8;
+
+        let name_offset = read_u32(record0, 84).unwrap_or(0) as usize;
+        let name_len = read_u32(record0, 88).unwrap_or(0) as usize;
+        if let Some(name) = record0.get(name_offset..name_offset.saturating_add(name_len)) {
+            let name = decode_text(name, encoding);
+            if !name.trim().is_empty() {
+                book.title = Some(name.trim().to_string());
+            }
+        }
+
+        if compression == COMPRESSION_HUFF_CDIC {
+            let first = read_u32(record0, 112).unwrap_or(0) as usize;
+            let count = read_u32(record0, 116).unwrap_or(0) as usize;
+            huff_records = Some((first, count));
+        }
+        if header_len >= 0xE4 {
+            extra_flags = read_u16(record0, 0xF2).unwrap_or(0);
+        }
+        if book.is_kf8 {
+            fdst_index = read_u32(record0, 0xC0).filter(|&i| i != u32::MAX).map(|i| i as usize);
+        }
+
+        let exth_flags = read_u32(record0, 128).unwrap_or(0);
+        if exth_flags & 0x40 != 0 {
+            for (kind, value) in parse_exth(record0, 16 + header_len) {
+                let value = decode_text(value, encoding).trim().to_string();
+                if value.is_empty() {
+                    continue;
+                }
+                match kind {
+                    EXTH_AUTHOR => book.authors.push(value),
+                    EXTH_PUBLISHER => book.publisher = Some(value),
+                    EXTH_DESCRIPTION => book.description = Some(value),
+                    EXTH_SUBJECT => book.subjects.push(value),
+                    EXTH_PUBLISHED => book.published = Some(value),
+                    EXTH_UPDATED_TITLE => book.title = Some(value),
+                    EXTH_LANGUAGE => book.language = Some(value),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    let mut huff = match huff_records {
+        Some((first, count)) => {
+            let huff = db
+                .record(first)
+                .ok_or_else(|| KreuzbergError::parsing("MOBI HUFF record is missing"))?;
+            let cdics: Vec<&[u8]> = (first + 1..first + count.max(1)).filter_map(|i| db.record(i)).collect();
+            Some(HuffCdicReader::new(huff, &cdics)?)
+        }
+        None => None,
+    };
+
+    let mut text = Vec::with_capacity(text_length);
+    for index in 1..=text_records {
+        let Some(record) = db.record(index) else {
+            break;
+        };
+        let record = &record[..record.len() - trailing_entries_size(record, extra_flags)];
+        match compression {
+            COMPRESSION_NONE => text.extend_from_slice(record),
+            COMPRESSION_PALMDOC => text.extend_from_slice(&palmdoc::decompress(record)),
+            COMPRESSION_HUFF_CDIC => {
+                let reader = huff
+                    .as_mut()
+                    .ok_or_else(|| KreuzbergError::parsing("MOBI HUFF record is missing"))?;
+                text.extend_from_slice(&reader.decompress(record)?);
+            }
+            other => {
+                return Err(KreuzbergError::UnsupportedFormat(format!(
+                    "Unsupported MOBI compression type {}",
+                    other
+                )));
+            }
+        }
+        if text.len() >= text_length {
+            break;
+        }
+    }
+    text.truncate(text_length);
+
+    // KF8 stores CSS and SVG flows after the HTML; the FDST record marks the boundaries
+    if let Some(fdst) = fdst_index.and_then(|i| db.record(i))
+        && fdst.starts_with(b"FDST")
+        && let Some(table) = read_u32(fdst, 4)
+        && let Some(end) = read_u32(fdst, table as usize + 4)
+    {
+        text.truncate(end as usize);
+    }
+
+    book.html = decode_text(&text, encoding);
+    Ok(book)
+}
+
+#[cfg(test)]
+pub(crate) mod test_support {
+    /// Build a PalmDB file from `type_creator` and raw records.
+    pub(crate) fn palmdb(type_creator: &[u8; 8], records: &[Vec<u8>]) -> Vec<u8> {
+        let mut data = vec![0u8; 78];
+        data[..4].copy_from_slice(b"test");
+        data[60..68].copy_from_slice(type_creator);
+        data[76..78].copy_from_slice(&(records.len() as u16).to_be_bytes());
+
+        let mut offset = 78 + records.len() * 8 + 2;
+        for (i, record) in records.iter().enumerate() {
+            data.extend_from_slice(&(offset as u32).to_be_bytes());
+            data.extend_from_slice(&[0, 0, 0, i as u8]);
+            offset += record.len();
+        }
+        data.extend_from_slice(&[0, 0]);
+        for record in records {
+            data.extend_from_slice(record);
+        }
+        data
+    }
+
+    /// Build a MOBI record 0 with an EXTH block.
+    pub(crate) fn mobi_record0(
+        compression: u16,
+        text_length: usize,
+        text_records: u16,
+        title: &str,
+        exth: &[(u32, &str)],
+        huff: Option<(u32, u32)>,
+    ) -> Vec<u8> {
+        const HEADER_LEN: usize = 0xE8;
+        let mut record = vec![0u8; 16 + HEADER_LEN];
+        record[0..2].copy_from_slice(&compression.to_be_bytes());
+        record[4..8].copy_from_slice(&(text_length as u32).to_be_bytes());
+        record[8..10].copy_from_slice(&text_records.to_be_bytes());
+        record[10..12].copy_from_slice(&4096u16.to_be_bytes());
+        record[16..20].copy_from_slice(b"MOBI");
+        record[20..24].copy_from_slice(&(HEADER_LEN as u32).to_be_bytes());
+        record[24..28].copy_from_slice(&2u32.to_be_bytes());
+        record[28..32].copy_from_slice(&65001u32.to_be_bytes());
+        record[36..40].copy_from_slice(&6u32.to_be_bytes());
+        record[0xC0..0xC4].copy_from_slice(&u32::MAX.to_be_bytes());
+        if let Some((first, count)) = huff {
+            record[112..116].copy_from_slice(&first.to_be_bytes());
+            record[116..120].copy_from_slice(&count.to_be_bytes());
+        }
+        record[128..132].copy_from_slice(&0x40u32.to_be_bytes());
+
+        let mut exth_records = Vec::new();
+        for (kind, value) in exth {
+            exth_records.extend_from_slice(&kind.to_be_bytes());
+            exth_records.extend_from_slice(&((value.len() + 8) as u32).to_be_bytes());
+            exth_records.extend_from_slice(value.as_bytes());
+        }
+        record.extend_from_slice(b"EXTH");
+        record.extend_from_slice(&((exth_records.len() + 12) as u32).to_be_bytes());
+        record.extend_from_slice(&(exth.len() as u32).to_be_bytes());
+        record.extend_from_slice(&exth_records);
+
+        let name_offset = record.len();
+        record.extend_from_slice(title.as_bytes());
+        record[84..88].copy_from_slice(&(name_offset as u32).to_be_bytes());
+        record[88..92].copy_from_slice(&(title.len() as u32).to_be_bytes());
+        record
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_support::*;
+    use super::*;
+
+    #[test]
+    fn test_parse_mobi_uncompressed_with_exth() {
+        let html = "<html><body><h1>Chapter 1</h1><p>It was a dark night.</p></body></html>";
+        let record0 = mobi_record0(
+            COMPRESSION_NONE,
+            html.len(),
+            1,
+            "Short Title",
+            &[
+                (EXTH_AUTHOR, "Jane Doe"),
+                (EXTH_PUBLISHER, "Acme"),
+                (EXTH_UPDATED_TITLE, "Full Book Title"),
+                (EXTH_LANGUAGE, "en"),
+            ],
+            None,
+        );
+        let data = palmdb(b"BOOKMOBI", &[record0, html.as_bytes().to_vec()]);
+
+        let book = parse_mobi(&data).unwrap();
+        assert_eq!(book.html, html);
+        assert_eq!(book.title.as_deref(), Some("Full Book Title"));
+        assert_eq!(book.authors, vec!["Jane Doe".to_string()]);
+        assert_eq!(book.publisher.as_deref(), Some("Acme"));
+        assert_eq!(book.language.as_deref(), Some("en"));
+        assert!(!book.is_kf8);
+    }
+
+    #[test]
+    fn test_parse_mobi_palmdoc_records() {
+        let record0 = mobi_record0(COMPRESSION_PALMDOC, 13, 2, "Book", &[], None);
+        // "<p>ab" + (space + 'c') ; "de</p>"
+        let first = vec![b'<', b'p', b'>', b'a', b'b', b'c' ^ 0x80];
+        let second = b"de</p>".to_vec();
+        let data = palmdb(b"BOOKMOBI", &[record0, first, second]);
+
+        let book = parse_mobi(&data).unwrap();
+        assert_eq!(book.html, "<p>ab cde</p>");
+    }
+
+    #[test]
+    fn test_parse_mobi_huff_cdic() {
+        use super::huffcdic::test_support::{cdic, identity_huff, identity_phrases};
+
+        let text = "<p>Huffman</p>";
+        let record0 = mobi_record0(COMPRESSION_HUFF_CDIC, text.len(), 1, "Book", &[], Some((2, 2)));
+        let data = palmdb(
+            b"BOOKMOBI",
+            &[
+                record0,
+                text.as_bytes().to_vec(),
+                identity_huff(),
+                cdic(&identity_phrases()),
+            ],
+        );
+
+        assert_eq!(parse_mobi(&data).unwrap().html, text);
+    }
+
+    #[test]
+    fn test_parse_palmdoc_textread() {
+        let mut record0 = vec![0u8; 16];
+        record0[0..2].copy_from_slice(&COMPRESSION_NONE.to_be_bytes());
+        record0[4..8].copy_from_slice(&5u32.to_be_bytes());
+        record0[8..10].copy_from_slice(&1u16.to_be_bytes());
+        let data = palmdb(b"TEXtREAd", &[record0, b"caf\xe9".to_vec()]);
+
+        assert_eq!(parse_mobi(&data).unwrap().html, "café");
+    }
+
+    #[test]
+    fn test_parse_mobi_rejects_drm() {
+        let mut record0 = mobi_record0(COMPRESSION_NONE, 0, 0, "Book", &[], None);
+        record0[12..14].copy_from_slice(&2u16.to_be_bytes());
+        let data = palmdb(b"BOOKMOBI", &[record0]);
+
+        assert!(matches!(parse_mobi(&data), Err(KreuzbergError::UnsupportedFormat(_))));
+    }
+
+    #[test]
+    fn test_parse_mobi_invalid_signature() {
+        assert!(parse_mobi(&[0u8; 100]).is_err());
+        assert!(parse_mobi(b"short").is_err());
+    }
+
+    #[test]
+    fn test_trailing_entries_size() {
+        // Multibyte flag: last byte low bits = 1 => strip 2 bytes
+        assert_eq!(trailing_entries_size(b"text\x00\x01", 0x1), 2);
+        // One trailing entry of 3 bytes whose size is stored backward as 0x83
+        assert_eq!(trailing_entries_size(b"text\xAA\xBB\x83", 0x2), 3);
+        assert_eq!(trailing_entries_size(b"text", 0), 0);
+    }
+
+    #[test]
+    fn test_decode_windows_1252() {
+        assert_eq!(
+            decode_windows_1252(b"\x93quoted\x94 \x80"),
+            "\u{201C}quoted\u{201D} \u{20AC}"
+        );
+    }
+}
@@ -0,0 +1,88 @@
+//! PalmDOC LZ77 decompression.
+//!
+//! Each text record is compressed independently with a byte-oriented LZ77
+//! variant: literal runs, 11-bit distance back-references and a space+char
+//! pair encoding.
+
+/// Decompress a single PalmDOC-compressed record.
+pub(super) fn decompress(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len() * 2);
+    let mut i = 0;
+
+    while i < data.len() {
+        let c = data[i];
+        i += 1;
+
+        match c {
+            // Literal run of the next `c` bytes
+            0x01..=0x08 => {
+                let end = (i + c as usize).min(data.len());
+                out.extend_from_slice(&data[i..end]);
+                i = end;
+            }
+            // Back-reference: 11-bit distance, 3-bit length
+            0x80..=0xBF => {
+                let Some(&next) = data.get(i) else {
+                    break;
+                };
+                i += 1;
+                let pair = ((c as usize) << 8) | next as usize;
+                let distance = (pair >> 3) & 0x07FF;
+                let length = (pair & 0x07) + 3;
+                if distance == 0 || distance > out.len() {
+                    continue;
+                }
+                // Byte-by-byte copy: the source may overlap the output
+                let start = out.len() - distance;
+                for k in 0..length {
+                    out.push(out[start + k]);
+                }
+            }
+            // Space followed by an ASCII character
+            0xC0..=0xFF => {
+                out.push(b' ');
+                out.push(c ^ 0x80);
+            }
+            _ => out.push(c),
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decompress_literals_and_space_pairs() {
+        // "Hi" + (space + 'a') + literal run of 2 bytes
+        let data = [b'H', b'i', b'a' ^ 0x80, 0x02, 0xC0, 0x00];
+        assert_eq!(decompress(&data), b"Hi a\xC0\x00");
+    }
+
+    #[test]
+    fn test_decompress_back_reference() {
+        // "abcd" then copy 4 bytes from distance 4
+        let distance = 4usize;
+        let length = 4usize;
+        let pair = 0x8000 | (distance << 3) | (length - 3);
+        let data = [b'a', b'b', b'c', b'd', (pair >> 8) as u8, pair as u8];
+        assert_eq!(decompress(&data), b"abcdabcd");
+    }
+
+    #[test]
+    fn test_decompress_overlapping_reference() {
+        // "ab" then copy 6 bytes from distance 2 (overlapping)
+        let pair = 0x8000 | (2 << 3) | (6 - 3);
+        let data = [b'a', b'b', (pair >> 8) as u8, pair as u8];
+        assert_eq!(decompress(&data), b"abababab");
+    }
+
+    #[test]
+    fn test_decompress_invalid_distance_is_skipped() {
+        let pair = 0x8000 | (10 << 3);
+        let data = [b'x', (pair >> 8) as u8, pair as u8];
+        assert_eq!(decompress(&data), b"x");
+    }
+}
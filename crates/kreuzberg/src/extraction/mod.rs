@@ -25,12 +25,27 @@ pub mod excel;
 #[cfg(feature = "html")]
 pub mod html;
 
+#[cfg(feature = "office")]
+pub mod chm;
+
 #[cfg(feature = "office")]
 pub mod doc;
 
 #[cfg(feature = "office")]
 pub mod docx;
 
+#[cfg(feature = "office")]
+pub mod drawingml;
+
+#[cfg(feature = "office")]
+pub mod iwork;
+
+#[cfg(feature = "office")]
+pub mod markup;
+
+#[cfg(feature = "office")]
+pub mod mobi;
+
 #[cfg(feature = "office")]
 pub mod office_metadata;
 
@@ -8,7 +8,7 @@ use std::io::{Cursor, Read, Seek};
 use std::path::Path;
 use zip::ZipArchive;
 
-use super::elements::Slide;
+use super::elements::{Slide, SlideElement};
 use super::image_handling::get_full_image_path;
 use crate::error::{KreuzbergError, Result};
 
@@ -144,6 +144,11 @@ impl<R: Read + Seek> SlideIterator<R> {
         self.total_slides
     }
 
+    /// Number of the slide [`next_slide`](Self::next_slide) reads, if any is left.
+    pub(super) fn next_slide_number(&self) -> Option<u32> {
+        (self.current_index < self.total_slides).then(|| (self.current_index + 1) as u32)
+    }
+
     pub(super) fn next_slide(&mut self) -> Result<Option<Slide>> {
         if self.current_index >= self.total_slides {
             return Ok(None);
@@ -151,19 +156,67 @@ impl<R: Read + Seek> SlideIterator<R> {
 
         let slide_path = &self.container.slide_paths()[self.current_index].clone();
         let slide_number = (self.current_index + 1) as u32;
+        // Advance first so that a slide failing to parse can be skipped.
+        self.current_index += 1;
 
         let xml_data = self.container.read_file(slide_path)?;
 
         let rels_path = self.container.get_slide_rels_path(slide_path);
         let rels_data = self.container.read_file(&rels_path).ok();
 
-        let slide = Slide::from_xml(slide_number, &xml_data, rels_data.as_deref())?;
-
-        self.current_index += 1;
+        let mut slide = Slide::from_xml(slide_number, &xml_data, rels_data.as_deref())?;
+        if let Some(ref rels) = rels_data {
+            self.load_slide_graphics(slide_path, rels, &mut slide);
+        }
 
         Ok(Some(slide))
     }
 
+    /// Parse the chart and SmartArt parts referenced by a slide (best-effort).
+    fn load_slide_graphics(&mut self, slide_path: &str, rels_data: &[u8], slide: &mut Slide) {
+        use crate::extraction::drawingml;
+
+        let references: Vec<SlideElement> = slide.graphic_references().cloned().collect();
+        if references.is_empty() {
+            return;
+        }
+        let Ok(rels_xml) = std::str::from_utf8(rels_data) else {
+            return;
+        };
+        let targets = drawingml::parse_relationship_targets(rels_xml);
+
+        for element in references {
+            let (SlideElement::Chart(ref reference, _) | SlideElement::SmartArt(ref reference, _)) = element else {
+                continue;
+            };
+            let Some(path) = targets
+                .get(&reference.id)
+                .and_then(|target| drawingml::resolve_part_path(slide_path, target))
+            else {
+                continue;
+            };
+            let Ok(data) = self.container.read_file(&path) else {
+                tracing::debug!("PPTX graphic part '{}' not found", path);
+                continue;
+            };
+            let xml = String::from_utf8_lossy(&data);
+            match element {
+                SlideElement::Chart(..) => match drawingml::parse_chart_xml(&xml) {
+                    Ok(chart) => {
+                        slide.charts.insert(reference.id.clone(), chart);
+                    }
+                    Err(e) => tracing::debug!("Skipping unparseable PPTX chart '{}': {}", path, e),
+                },
+                _ => match drawingml::parse_smartart_xml(&xml) {
+                    Ok(smartart) => {
+                        slide.smartarts.insert(reference.id.clone(), smartart);
+                    }
+                    Err(e) => tracing::debug!("Skipping unparseable PPTX SmartArt '{}': {}", path, e),
+                },
+            }
+        }
+    }
+
     pub(super) fn get_slide_images(&mut self, slide: &Slide) -> Result<HashMap<String, Vec<u8>>> {
         let mut image_data = HashMap::new();
 
@@ -117,6 +117,20 @@ impl ContentBuilder {
         self.content.push_str("</table>\n");
     }
 
+    pub(super) fn add_chart(&mut self, chart: &crate::extraction::drawingml::Chart) {
+        if chart.is_empty() {
+            return;
+        }
+
+        self.content.push_str("\n**Chart");
+        if let Some(ref title) = chart.title {
+            self.content.push_str(": ");
+            self.content.push_str(title);
+        }
+        self.content.push_str("**\n");
+        self.add_table(&chart.to_cells());
+    }
+
     pub(super) fn add_list_item(&mut self, level: u32, is_ordered: bool, text: &str) {
         let indent_count = level.saturating_sub(1) as usize;
         for _ in 0..indent_count {
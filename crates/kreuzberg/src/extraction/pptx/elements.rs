@@ -3,6 +3,10 @@
 //! This module defines the internal data structures used to represent
 //! slide elements, formatting, and text runs during XML parsing.
 
+use std::collections::HashMap;
+
+use crate::extraction::drawingml::{Chart, SmartArt};
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
 pub(super) struct ElementPosition {
     pub(super) x: i64,
@@ -83,12 +87,20 @@ pub(super) struct ImageReference {
     pub(super) target: String,
 }
 
+/// Relationship ID of a chart or SmartArt data part referenced from a `graphicFrame`.
+#[derive(Debug, Clone)]
+pub(super) struct GraphicReference {
+    pub(super) id: String,
+}
+
 #[derive(Debug, Clone)]
 pub(super) enum SlideElement {
     Text(TextElement, ElementPosition),
     Table(TableElement, ElementPosition),
     Image(ImageReference, ElementPosition),
     List(ListElement, ElementPosition),
+    Chart(GraphicReference, ElementPosition),
+    SmartArt(GraphicReference, ElementPosition),
     Unknown,
 }
 
@@ -98,7 +110,9 @@ impl SlideElement {
             SlideElement::Text(_, pos)
             | SlideElement::Table(_, pos)
             | SlideElement::Image(_, pos)
-            | SlideElement::List(_, pos) => *pos,
+            | SlideElement::List(_, pos)
+            | SlideElement::Chart(_, pos)
+            | SlideElement::SmartArt(_, pos) => *pos,
             SlideElement::Unknown => ElementPosition::default(),
         }
     }
@@ -109,6 +123,10 @@ pub(super) struct Slide {
     pub(super) slide_number: u32,
     pub(super) elements: Vec<SlideElement>,
     pub(super) images: Vec<ImageReference>,
+    /// Parsed chart parts keyed by relationship ID.
+    pub(super) charts: HashMap<String, Chart>,
+    /// Parsed SmartArt data parts keyed by relationship ID.
+    pub(super) smartarts: HashMap<String, SmartArt>,
 }
 
 #[derive(Debug, Clone)]
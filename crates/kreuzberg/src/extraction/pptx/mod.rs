@@ -45,6 +45,8 @@ mod image_handling;
 mod metadata;
 mod parser;
 
+use std::collections::HashMap;
+
 use bytes::Bytes;
 
 use crate::error::Result;
@@ -73,7 +75,7 @@ pub fn extract_pptx_from_path(
     page_config: Option<&crate::core::config::PageConfig>,
 ) -> Result<PptxExtractionResult> {
     let container = PptxContainer::open(path)?;
-    extract_pptx_from_container(container, extract_images, page_config)
+    extract_pptx_from_container(container, extract_images, page_config, None)
 }
 
 /// Extract PPTX content from a byte buffer.
@@ -93,13 +95,36 @@ pub fn extract_pptx_from_bytes(
     page_config: Option<&crate::core::config::PageConfig>,
 ) -> Result<PptxExtractionResult> {
     let container = PptxContainer::from_bytes(data)?;
-    extract_pptx_from_container(container, extract_images, page_config)
+    extract_pptx_from_container(container, extract_images, page_config, None)
+}
+
+/// Like [`extract_pptx_from_path`], taking page tracking from `config` and checking for
+/// cancellation before each slide.
+pub(crate) fn extract_pptx_from_path_with_config(
+    path: &str,
+    extract_images: bool,
+    config: &crate::core::config::ExtractionConfig,
+) -> Result<PptxExtractionResult> {
+    let container = PptxContainer::open(path)?;
+    extract_pptx_from_container(container, extract_images, config.pages.as_ref(), Some(config))
+}
+
+/// Like [`extract_pptx_from_bytes`], taking page tracking from `config` and checking for
+/// cancellation before each slide.
+pub(crate) fn extract_pptx_from_bytes_with_config(
+    data: &[u8],
+    extract_images: bool,
+    config: &crate::core::config::ExtractionConfig,
+) -> Result<PptxExtractionResult> {
+    let container = PptxContainer::from_bytes(data)?;
+    extract_pptx_from_container(container, extract_images, config.pages.as_ref(), Some(config))
 }
 
 fn extract_pptx_from_container<R: std::io::Read + std::io::Seek>(
     mut container: PptxContainer<R>,
     extract_images: bool,
     page_config: Option<&crate::core::config::PageConfig>,
+    extraction_config: Option<&crate::core::config::ExtractionConfig>,
 ) -> Result<PptxExtractionResult> {
     let config = ParserConfig {
         extract_images,
@@ -108,7 +133,12 @@ fn extract_pptx_from_container<R: std::io::Read + std::io::Seek>(
 
     let metadata = extract_metadata(&mut container.archive);
 
-    let notes = extract_all_notes(&mut container)?;
+    let notes = match extraction_config {
+        Some(config) => config
+            .recover(|| "speaker notes".to_string(), extract_all_notes(&mut container))?
+            .unwrap_or_default(),
+        None => extract_all_notes(&mut container)?,
+    };
 
     let mut iterator = SlideIterator::new(container);
     let slide_count = iterator.slide_count();
@@ -120,7 +150,22 @@ fn extract_pptx_from_container<R: std::io::Read + std::io::Seek>(
     let mut total_table_count = 0;
     let mut extracted_images = Vec::new();
 
-    while let Some(slide) = iterator.next_slide()? {
+    while let Some(slide_number) = iterator.next_slide_number() {
+        if let Some(extraction_config) = extraction_config
+            && extraction_config.checkpoint(|| format!("slide {}", slide_number))?
+        {
+            break;
+        }
+
+        let slide = match extraction_config {
+            Some(config) => config.recover(|| format!("slide {}", slide_number), iterator.next_slide())?,
+            None => Some(iterator.next_slide()?),
+        };
+        // `None` is a slide skipped in best-effort mode.
+        let Some(Some(slide)) = slide else {
+            continue;
+        };
+
         let byte_start = if page_config.is_some() {
             content_builder.start_slide(slide.slide_number)
         } else {
@@ -226,9 +271,18 @@ impl elements::Slide {
             slide_number,
             elements,
             images,
+            charts: HashMap::new(),
+            smartarts: HashMap::new(),
         })
     }
 
+    /// Chart and SmartArt elements whose parts still need to be loaded.
+    fn graphic_references(&self) -> impl Iterator<Item = &SlideElement> {
+        self.elements
+            .iter()
+            .filter(|e| matches!(e, SlideElement::Chart(_, _) | SlideElement::SmartArt(_, _)))
+    }
+
     fn to_markdown(&self, config: &ParserConfig) -> String {
         let mut builder = ContentBuilder::new();
 
@@ -278,6 +332,18 @@ impl elements::Slide {
                 SlideElement::Image(img_ref, _) => {
                     builder.add_image(&img_ref.id, self.slide_number);
                 }
+                SlideElement::Chart(chart_ref, _) => {
+                    if let Some(chart) = self.charts.get(&chart_ref.id) {
+                        builder.add_chart(chart);
+                    }
+                }
+                SlideElement::SmartArt(diagram_ref, _) => {
+                    if let Some(smartart) = self.smartarts.get(&diagram_ref.id) {
+                        for (depth, text) in smartart.flatten() {
+                            builder.add_list_item(depth as u32 + 1, false, text);
+                        }
+                    }
+                }
                 SlideElement::Unknown => {}
             }
         }
@@ -9,9 +9,10 @@ use crate::error::{KreuzbergError, Result};
 use crate::text::utf8_validation;
 
 use super::elements::{
-    ElementPosition, Formatting, ImageReference, ListElement, ListItem, ParsedContent, Run, SlideElement, TableCell,
-    TableElement, TableRow, TextElement,
+    ElementPosition, Formatting, GraphicReference, ImageReference, ListElement, ListItem, ParsedContent, Run,
+    SlideElement, TableCell, TableElement, TableRow, TextElement,
 };
+use crate::extraction::drawingml::{CHART_NAMESPACE, CHART_URI, DIAGRAM_NAMESPACE, DIAGRAM_URI};
 
 use crate::extraction::ooxml_constants::{DRAWINGML_NAMESPACE, PRESENTATIONML_NAMESPACE, RELATIONSHIPS_NAMESPACE};
 
@@ -69,6 +70,8 @@ fn parse_group(node: &Node) -> Result<Vec<SlideElement>> {
         "graphicFrame" => {
             if let Some(graphic_element) = parse_graphic_frame(node)? {
                 elements.push(SlideElement::Table(graphic_element, position));
+            } else if let Some(element) = parse_graphic_reference(node, position) {
+                elements.push(element);
             }
         }
         "pic" => {
@@ -149,6 +152,34 @@ fn parse_graphic_frame(node: &Node) -> Result<Option<TableElement>> {
     Ok(None)
 }
 
+/// Detect a chart (`c:chart r:id`) or SmartArt (`dgm:relIds r:dm`) reference in a graphic frame.
+fn parse_graphic_reference(node: &Node, position: ElementPosition) -> Option<SlideElement> {
+    let graphic_data = node.descendants().find(|n| {
+        n.is_element() && n.tag_name().name() == "graphicData" && n.tag_name().namespace() == Some(DRAWINGML_NAMESPACE)
+    })?;
+
+    match graphic_data.attribute("uri")? {
+        CHART_URI => {
+            let chart = graphic_data.children().find(|n| {
+                n.is_element() && n.tag_name().name() == "chart" && n.tag_name().namespace() == Some(CHART_NAMESPACE)
+            })?;
+            let id = chart.attribute((RELATIONSHIPS_NAMESPACE, "id"))?;
+            Some(SlideElement::Chart(GraphicReference { id: id.to_string() }, position))
+        }
+        DIAGRAM_URI => {
+            let rel_ids = graphic_data.children().find(|n| {
+                n.is_element() && n.tag_name().name() == "relIds" && n.tag_name().namespace() == Some(DIAGRAM_NAMESPACE)
+            })?;
+            let id = rel_ids.attribute((RELATIONSHIPS_NAMESPACE, "dm"))?;
+            Some(SlideElement::SmartArt(
+                GraphicReference { id: id.to_string() },
+                position,
+            ))
+        }
+        _ => None,
+    }
+}
+
 fn parse_table(tbl_node: &Node) -> Result<TableElement> {
     let mut rows = Vec::new();
 
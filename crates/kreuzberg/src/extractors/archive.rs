@@ -3,8 +3,9 @@
 use crate::Result;
 use crate::core::config::ExtractionConfig;
 use crate::extraction::archive::{
-    ArchiveMetadata as ExtractedMetadata, extract_7z_metadata, extract_7z_text_content, extract_gzip,
-    extract_tar_metadata, extract_tar_text_content, extract_zip_metadata, extract_zip_text_content,
+    ArchiveMetadata as ExtractedMetadata, extract_7z_metadata, extract_7z_text_content_with_config, extract_gzip,
+    extract_tar_metadata, extract_tar_text_content_with_config, extract_zip_metadata,
+    extract_zip_text_content_with_config,
 };
 use crate::extractors::SyncExtractor;
 use crate::extractors::security::ZipBombValidator;
@@ -167,7 +168,7 @@ impl DocumentExtractor for ZipExtractor {
             .map_err(|e| crate::error::KreuzbergError::validation(e.to_string()))?;
 
         let extraction_metadata = extract_zip_metadata(content, &limits)?;
-        let text_contents = extract_zip_text_content(content, &limits)?;
+        let text_contents = extract_zip_text_content_with_config(content, &limits, Some(config))?;
         Ok(build_archive_result(
             extraction_metadata,
             text_contents,
@@ -201,7 +202,7 @@ impl SyncExtractor for ZipExtractor {
             .map_err(|e| crate::error::KreuzbergError::validation(e.to_string()))?;
 
         let extraction_metadata = extract_zip_metadata(content, &limits)?;
-        let text_contents = extract_zip_text_content(content, &limits)?;
+        let text_contents = extract_zip_text_content_with_config(content, &limits, Some(config))?;
         Ok(build_archive_result(
             extraction_metadata,
             text_contents,
@@ -273,7 +274,7 @@ impl DocumentExtractor for TarExtractor {
     ) -> Result<ExtractionResult> {
         let limits = config.security_limits.clone().unwrap_or_default();
         let extraction_metadata = extract_tar_metadata(content, &limits)?;
-        let text_contents = extract_tar_text_content(content, &limits)?;
+        let text_contents = extract_tar_text_content_with_config(content, &limits, Some(config))?;
         Ok(build_archive_result(
             extraction_metadata,
             text_contents,
@@ -304,7 +305,7 @@ impl SyncExtractor for TarExtractor {
     fn extract_sync(&self, content: &[u8], mime_type: &str, config: &ExtractionConfig) -> Result<ExtractionResult> {
         let limits = config.security_limits.clone().unwrap_or_default();
         let extraction_metadata = extract_tar_metadata(content, &limits)?;
-        let text_contents = extract_tar_text_content(content, &limits)?;
+        let text_contents = extract_tar_text_content_with_config(content, &limits, Some(config))?;
         Ok(build_archive_result(
             extraction_metadata,
             text_contents,
@@ -376,7 +377,7 @@ impl DocumentExtractor for SevenZExtractor {
     ) -> Result<ExtractionResult> {
         let limits = config.security_limits.clone().unwrap_or_default();
         let extraction_metadata = extract_7z_metadata(content, &limits)?;
-        let text_contents = extract_7z_text_content(content, &limits)?;
+        let text_contents = extract_7z_text_content_with_config(content, &limits, Some(config))?;
         Ok(build_archive_result(
             extraction_metadata,
             text_contents,
@@ -402,7 +403,7 @@ impl SyncExtractor for SevenZExtractor {
     fn extract_sync(&self, content: &[u8], mime_type: &str, config: &ExtractionConfig) -> Result<ExtractionResult> {
         let limits = config.security_limits.clone().unwrap_or_default();
         let extraction_metadata = extract_7z_metadata(content, &limits)?;
-        let text_contents = extract_7z_text_content(content, &limits)?;
+        let text_contents = extract_7z_text_content_with_config(content, &limits, Some(config))?;
         Ok(build_archive_result(
             extraction_metadata,
             text_contents,
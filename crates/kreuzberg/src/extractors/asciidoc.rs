@@ -0,0 +1,984 @@
+//! Native Rust AsciiDoc extractor.
+//!
+//! This extractor parses AsciiDoc documents line by line.
+//! It extracts:
+//! - Document header: title, author line, revision line
+//! - Attribute entries (:author:, :revdate:, :keywords:, etc.)
+//! - Section titles (== to ======)
+//! - Paragraphs with inline markup removed
+//! - Lists (bullet, numbered, description lists)
+//! - Listing and literal blocks with source language
+//! - Tables (|=== blocks)
+//! - Admonitions (NOTE, TIP, IMPORTANT, WARNING, CAUTION) and quote blocks
+
+#[cfg(feature = "office")]
+use crate::Result;
+#[cfg(feature = "office")]
+use crate::core::config::ExtractionConfig;
+#[cfg(feature = "office")]
+use crate::extraction::markup::{self, MarkupBlock};
+#[cfg(feature = "office")]
+use crate::plugins::{DocumentExtractor, Plugin};
+#[cfg(feature = "office")]
+use crate::types::{ExtractionResult, Metadata};
+#[cfg(feature = "office")]
+use ahash::AHashMap;
+#[cfg(feature = "office")]
+use async_trait::async_trait;
+#[cfg(feature = "office")]
+use once_cell::sync::Lazy;
+#[cfg(feature = "office")]
+use regex::Regex;
+#[cfg(feature = "office")]
+use std::borrow::Cow;
+#[cfg(feature = "office")]
+use std::collections::BTreeMap;
+
+#[cfg(feature = "office")]
+static ATTRIBUTE_ENTRY: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^:(!?[\w][\w-]*!?):(?:\s+(.*))?$").expect("AsciiDoc attribute regex is valid"));
+#[cfg(feature = "office")]
+static SECTION_TITLE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(={1,6})\s+(.+?)(?:\s+=+)?$").expect("AsciiDoc section regex is valid"));
+#[cfg(feature = "office")]
+static LIST_ITEM: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*(\*{1,5}|-|\.{1,5}|\d+\.|[a-z]\.)\s+(?:\[[ xX*]\]\s+)?(.*)$")
+        .expect("AsciiDoc list item regex is valid")
+});
+#[cfg(feature = "office")]
+static DESCRIPTION_ITEM: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(\S.*?)(?::{2,4}|;;)(?:\s+(.*))?$").expect("AsciiDoc description regex is valid"));
+#[cfg(feature = "office")]
+static ADMONITION_PARAGRAPH: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(NOTE|TIP|IMPORTANT|WARNING|CAUTION):\s+(.*)$").expect("AsciiDoc admonition regex is valid")
+});
+#[cfg(feature = "office")]
+static BLOCK_MACRO: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(image|include|video|audio|toc|ifdef|ifndef|ifeval|endif)::").expect("AsciiDoc macro regex is valid")
+});
+#[cfg(feature = "office")]
+static URL_MACRO: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(?:link:([^\s\[]+)|((?:https?|ftp|file|irc|mailto):[^\s\[]+))\[([^\]]*)\]")
+        .expect("AsciiDoc URL macro regex is valid")
+});
+#[cfg(feature = "office")]
+static INLINE_MACRO: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(?:xref|image|kbd|btn|menu|footnote|footnoteref|pass|stem|latexmath|asciimath|anchor|indexterm2?):([^\s\[]*)\[([^\]]*)\]")
+        .expect("AsciiDoc inline macro regex is valid")
+});
+#[cfg(feature = "office")]
+static CROSS_REFERENCE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"<<([^,>]+)(?:,\s*([^>]+))?>>").expect("AsciiDoc xref regex is valid"));
+#[cfg(feature = "office")]
+static INLINE_ANCHOR: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\[\[[^\]]*\]\]|\[#[^\]]*\]").expect("AsciiDoc anchor regex is valid"));
+#[cfg(feature = "office")]
+static ATTRIBUTE_REFERENCE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{([\w][\w-]*)\}").expect("AsciiDoc attribute reference regex is valid"));
+
+/// Document header of an AsciiDoc file.
+#[cfg(feature = "office")]
+#[derive(Debug, Default)]
+struct AsciiDocHeader {
+    title: Option<String>,
+    authors: Vec<String>,
+    revision: Option<String>,
+    revision_date: Option<String>,
+    attributes: BTreeMap<String, String>,
+}
+
+/// Native Rust AsciiDoc extractor.
+///
+/// Parses AsciiDoc documents and extracts:
+/// - Metadata from the document header and attribute entries
+/// - Section hierarchy, lists, code blocks, tables and admonitions
+/// - A `DocumentStructure` when requested
+#[cfg(feature = "office")]
+pub struct AsciiDocExtractor;
+
+#[cfg(feature = "office")]
+impl AsciiDocExtractor {
+    /// Create a new AsciiDoc extractor.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Parse an AsciiDoc document into its header and blocks.
+    fn parse(content: &str) -> (AsciiDocHeader, Vec<MarkupBlock>) {
+        let lines: Vec<&str> = content.lines().collect();
+        let mut header = AsciiDocHeader::default();
+        let body_start = Self::parse_header(&lines, &mut header);
+        let blocks = Self::parse_blocks(&lines[body_start..], &mut header.attributes);
+        (header, blocks)
+    }
+
+    /// Parse the document header; returns the index of the first body line.
+    fn parse_header(lines: &[&str], header: &mut AsciiDocHeader) -> usize {
+        let mut i = 0;
+        while i < lines.len() && (lines[i].trim().is_empty() || Self::is_line_comment(lines[i])) {
+            i += 1;
+        }
+
+        let Some(title) = lines.get(i).and_then(|line| line.strip_prefix("= ")) else {
+            return 0;
+        };
+        header.title = Some(title.trim().to_string());
+        i += 1;
+
+        let mut header_lines = 0;
+        while i < lines.len() && !lines[i].trim().is_empty() {
+            let line = lines[i].trim_end();
+            if let Some(caps) = ATTRIBUTE_ENTRY.captures(line) {
+                Self::set_attribute(&mut header.attributes, &caps[1], caps.get(2).map_or("", |m| m.as_str()));
+            } else if !Self::is_line_comment(line) {
+                match header_lines {
+                    0 => header.authors = Self::parse_author_line(line),
+                    1 => Self::parse_revision_line(line, header),
+                    _ => {}
+                }
+                header_lines += 1;
+            }
+            i += 1;
+        }
+
+        if header.authors.is_empty()
+            && let Some(author) = header
+                .attributes
+                .get("authors")
+                .or_else(|| header.attributes.get("author"))
+        {
+            header.authors = Self::parse_author_line(author);
+        }
+        if let Some(revnumber) = header.attributes.get("revnumber") {
+            header.revision = Some(revnumber.clone());
+        }
+        if let Some(revdate) = header.attributes.get("revdate") {
+            header.revision_date = Some(revdate.clone());
+        }
+
+        i
+    }
+
+    /// Split an author line ("Jane Doe <jane@example.com>; John Roe") into names.
+    fn parse_author_line(line: &str) -> Vec<String> {
+        line.split(';')
+            .map(|author| author.split('<').next().unwrap_or("").trim().to_string())
+            .filter(|author| !author.is_empty())
+            .collect()
+    }
+
+    /// Parse a revision line ("v1.2, 2024-01-15: Draft").
+    fn parse_revision_line(line: &str, header: &mut AsciiDocHeader) {
+        let line = line.split(':').next().unwrap_or(line);
+        let mut parts = line.splitn(2, ',').map(str::trim);
+        let first = parts.next().unwrap_or("");
+        match parts.next() {
+            Some(date) => {
+                header.revision = Some(first.trim_start_matches('v').to_string());
+                header.revision_date = Some(date.to_string());
+            }
+            None if first.starts_with('v') || first.chars().next().is_some_and(|c| c.is_ascii_digit()) => {
+                header.revision = Some(first.trim_start_matches('v').to_string());
+            }
+            None => {}
+        }
+    }
+
+    fn set_attribute(attributes: &mut BTreeMap<String, String>, name: &str, value: &str) {
+        if name.starts_with('!') || name.ends_with('!') {
+            attributes.remove(name.trim_matches('!'));
+        } else {
+            attributes.insert(name.to_lowercase(), value.trim().to_string());
+        }
+    }
+
+    fn is_line_comment(line: &str) -> bool {
+        line.starts_with("//") && !line.starts_with("////")
+    }
+
+    /// Return the delimiter if `line` opens a delimited block.
+    fn block_delimiter(line: &str) -> Option<&str> {
+        let line = line.trim_end();
+        if line == "--" || line.starts_with("|===") || line.starts_with(",===") || line.starts_with(":===") {
+            return Some(line);
+        }
+        let first = line.chars().next()?;
+        (line.len() >= 4
+            && matches!(first, '-' | '.' | '=' | '_' | '*' | '+' | '/')
+            && line.chars().all(|c| c == first))
+        .then_some(line)
+    }
+
+    /// Parse the block attribute line `[style, arg, ...]`.
+    fn parse_block_attributes(line: &str) -> Option<Vec<String>> {
+        let line = line.trim();
+        if !line.starts_with('[') || !line.ends_with(']') || line.starts_with("[[") {
+            return None;
+        }
+        let mut parts = Vec::new();
+        let mut current = String::new();
+        let mut in_quotes = false;
+        for c in line[1..line.len() - 1].chars() {
+            match c {
+                '"' => in_quotes = !in_quotes,
+                ',' if !in_quotes => parts.push(std::mem::take(&mut current)),
+                _ => current.push(c),
+            }
+        }
+        parts.push(current);
+        Some(
+            parts
+                .iter()
+                .map(|part| part.trim().trim_start_matches(['.', '#', '%']).to_string())
+                .collect(),
+        )
+    }
+
+    fn admonition_label(style: Option<&str>) -> Option<String> {
+        let style = style?.to_uppercase();
+        matches!(style.as_str(), "NOTE" | "TIP" | "IMPORTANT" | "WARNING" | "CAUTION").then_some(style)
+    }
+
+    /// Parse body lines into blocks.
+    fn parse_blocks(lines: &[&str], attributes: &mut BTreeMap<String, String>) -> Vec<MarkupBlock> {
+        let mut blocks = Vec::new();
+        let mut block_attrs: Option<Vec<String>> = None;
+        let mut i = 0;
+
+        while i < lines.len() {
+            let line = lines[i].trim_end();
+
+            if line.trim().is_empty() || Self::is_line_comment(line) || matches!(line, "'''" | "<<<" | "+") {
+                i += 1;
+                continue;
+            }
+
+            if let Some(caps) = ATTRIBUTE_ENTRY.captures(line) {
+                Self::set_attribute(attributes, &caps[1], caps.get(2).map_or("", |m| m.as_str()));
+                i += 1;
+                continue;
+            }
+
+            if let Some(parsed) = Self::parse_block_attributes(line) {
+                block_attrs = Some(parsed);
+                i += 1;
+                continue;
+            }
+
+            if line.starts_with("[[") && line.ends_with("]]") {
+                i += 1;
+                continue;
+            }
+
+            // Block titles (".Title") label the following block
+            if line.starts_with('.') && line.chars().nth(1).is_some_and(|c| c != '.' && !c.is_whitespace()) {
+                i += 1;
+                continue;
+            }
+
+            if BLOCK_MACRO.is_match(line) {
+                i += 1;
+                continue;
+            }
+
+            let style = block_attrs.as_ref().and_then(|attrs| attrs.first().cloned());
+            let style = style.as_deref().filter(|s| !s.is_empty());
+
+            if let Some(caps) = SECTION_TITLE.captures(line) {
+                let level = caps[1].len().saturating_sub(1).max(1) as u8;
+                blocks.push(MarkupBlock::Heading {
+                    level,
+                    text: Self::convert_inline(&caps[2], attributes),
+                });
+                block_attrs = None;
+                i += 1;
+                continue;
+            }
+
+            if let Some(delimiter) = Self::block_delimiter(line) {
+                let delimiter = delimiter.to_string();
+                let start = i + 1;
+                let mut end = start;
+                while end < lines.len() && lines[end].trim_end() != delimiter {
+                    end += 1;
+                }
+                let inner = &lines[start..end.min(lines.len())];
+                Self::push_delimited_block(&mut blocks, &delimiter, inner, block_attrs.as_deref(), attributes);
+                block_attrs = None;
+                i = end + 1;
+                continue;
+            }
+
+            if let Some(caps) = ADMONITION_PARAGRAPH.captures(line) {
+                let label = caps[1].to_string();
+                let mut text = caps[2].to_string();
+                i += 1;
+                while i < lines.len() && !Self::ends_paragraph(lines[i]) {
+                    text.push(' ');
+                    text.push_str(lines[i].trim());
+                    i += 1;
+                }
+                blocks.push(MarkupBlock::Quote {
+                    label: Some(label),
+                    paragraphs: vec![Self::convert_inline(&text, attributes)],
+                });
+                block_attrs = None;
+                continue;
+            }
+
+            if LIST_ITEM.is_match(line) {
+                let (items, ordered, next) = Self::parse_list(lines, i, attributes);
+                blocks.push(MarkupBlock::List { ordered, items });
+                block_attrs = None;
+                i = next;
+                continue;
+            }
+
+            if let Some(caps) = DESCRIPTION_ITEM.captures(line)
+                && !line.contains("://")
+            {
+                let mut items = Vec::new();
+                let mut term = caps[1].trim().to_string();
+                let mut definition = caps.get(2).map(|m| m.as_str().to_string()).unwrap_or_default();
+                i += 1;
+                loop {
+                    while i < lines.len() && !lines[i].trim().is_empty() && !DESCRIPTION_ITEM.is_match(lines[i]) {
+                        if !definition.is_empty() {
+                            definition.push(' ');
+                        }
+                        definition.push_str(lines[i].trim().trim_start_matches(['*', '-', ' ']));
+                        i += 1;
+                    }
+                    items.push(Self::convert_inline(&format!("{}: {}", term, definition), attributes));
+                    let mut next = i;
+                    while next < lines.len() && lines[next].trim().is_empty() {
+                        next += 1;
+                    }
+                    match lines.get(next).and_then(|l| DESCRIPTION_ITEM.captures(l)) {
+                        Some(caps) if !lines[next].contains("://") => {
+                            term = caps[1].trim().to_string();
+                            definition = caps.get(2).map(|m| m.as_str().to_string()).unwrap_or_default();
+                            i = next + 1;
+                        }
+                        _ => break,
+                    }
+                }
+                blocks.push(MarkupBlock::List { ordered: false, items });
+                block_attrs = None;
+                continue;
+            }
+
+            // Indented lines form a literal paragraph
+            if line.starts_with(' ') || line.starts_with('\t') {
+                let mut code_lines = Vec::new();
+                while i < lines.len() && !lines[i].trim().is_empty() {
+                    code_lines.push(lines[i]);
+                    i += 1;
+                }
+                blocks.push(MarkupBlock::Code {
+                    language: None,
+                    text: Self::dedent(&code_lines),
+                });
+                block_attrs = None;
+                continue;
+            }
+
+            // A trailing " +" marks a hard line break
+            let mut paragraph_lines = vec![line.trim().trim_end_matches(" +")];
+            i += 1;
+            while i < lines.len() && !Self::ends_paragraph(lines[i]) {
+                paragraph_lines.push(lines[i].trim().trim_end_matches(" +"));
+                i += 1;
+            }
+
+            match style {
+                Some("source") | Some("listing") | Some("literal") => blocks.push(MarkupBlock::Code {
+                    language: Self::source_language(block_attrs.as_deref()),
+                    text: paragraph_lines.join("\n"),
+                }),
+                Some("quote") | Some("verse") => blocks.push(MarkupBlock::Quote {
+                    label: None,
+                    paragraphs: vec![Self::convert_inline(&paragraph_lines.join(" "), attributes)],
+                }),
+                _ => {
+                    let text = Self::convert_inline(&paragraph_lines.join(" "), attributes);
+                    match Self::admonition_label(style) {
+                        Some(label) => blocks.push(MarkupBlock::Quote {
+                            label: Some(label),
+                            paragraphs: vec![text],
+                        }),
+                        None => blocks.push(MarkupBlock::Paragraph(text)),
+                    }
+                }
+            }
+            block_attrs = None;
+        }
+
+        blocks
+    }
+
+    /// Parse consecutive list items starting at `start`; nested items are flattened.
+    ///
+    /// Returns the items, whether the list is ordered and the next line index.
+    fn parse_list(
+        lines: &[&str],
+        start: usize,
+        attributes: &mut BTreeMap<String, String>,
+    ) -> (Vec<String>, bool, usize) {
+        let mut items = Vec::new();
+        let mut ordered = None;
+        let mut i = start;
+
+        while let Some(caps) = lines.get(i).and_then(|line| LIST_ITEM.captures(line.trim_end())) {
+            let marker = &caps[1];
+            ordered.get_or_insert(marker.starts_with('.') || marker.ends_with('.'));
+            let mut text = caps[2].to_string();
+            i += 1;
+
+            loop {
+                while i < lines.len() && !Self::ends_paragraph(lines[i]) {
+                    text.push(' ');
+                    text.push_str(lines[i].trim());
+                    i += 1;
+                }
+                // "+" attaches the following paragraph to the item
+                if lines.get(i).is_some_and(|line| line.trim_end() == "+") {
+                    i += 1;
+                    continue;
+                }
+                break;
+            }
+            items.push(Self::convert_inline(&text, attributes));
+
+            let mut next = i;
+            while next < lines.len() && lines[next].trim().is_empty() {
+                next += 1;
+            }
+            if lines.get(next).is_some_and(|line| LIST_ITEM.is_match(line.trim_end())) {
+                i = next;
+            } else {
+                break;
+            }
+        }
+
+        (items, ordered.unwrap_or(false), i)
+    }
+
+    /// Whether `line` ends the current paragraph.
+    fn ends_paragraph(line: &str) -> bool {
+        let line = line.trim_end();
+        line.trim().is_empty()
+            || Self::block_delimiter(line).is_some()
+            || SECTION_TITLE.is_match(line)
+            || Self::parse_block_attributes(line).is_some()
+            || LIST_ITEM.is_match(line)
+            || line == "+"
+    }
+
+    fn source_language(attrs: Option<&[String]>) -> Option<String> {
+        let attrs = attrs?;
+        match attrs.first().map(String::as_str) {
+            Some("source") => attrs
+                .get(1)
+                .filter(|lang| !lang.is_empty() && !lang.contains('='))
+                .cloned(),
+            _ => None,
+        }
+    }
+
+    fn push_delimited_block(
+        blocks: &mut Vec<MarkupBlock>,
+        delimiter: &str,
+        inner: &[&str],
+        attrs: Option<&[String]>,
+        attributes: &mut BTreeMap<String, String>,
+    ) {
+        let style = attrs
+            .and_then(|a| a.first())
+            .map(String::as_str)
+            .filter(|s| !s.is_empty());
+
+        if delimiter.contains("===") && !delimiter.chars().all(|c| c == '=') {
+            let separator = delimiter.chars().next().unwrap_or('|');
+            let rows = Self::parse_table(inner, separator, attrs, attributes);
+            if !rows.is_empty() {
+                blocks.push(MarkupBlock::Table(rows));
+            }
+            return;
+        }
+
+        match delimiter.chars().next() {
+            Some('/') | Some('+') => {}
+            Some('-') if delimiter != "--" => blocks.push(MarkupBlock::Code {
+                language: Self::source_language(attrs),
+                text: inner.join("\n"),
+            }),
+            Some('.') => blocks.push(MarkupBlock::Code {
+                language: None,
+                text: inner.join("\n"),
+            }),
+            Some('_') => blocks.push(MarkupBlock::Quote {
+                label: None,
+                paragraphs: Self::block_paragraphs(inner, attributes),
+            }),
+            _ => {
+                if let Some(label) = Self::admonition_label(style) {
+                    blocks.push(MarkupBlock::Quote {
+                        label: Some(label),
+                        paragraphs: Self::block_paragraphs(inner, attributes),
+                    });
+                } else if matches!(style, Some("source") | Some("listing")) {
+                    blocks.push(MarkupBlock::Code {
+                        language: Self::source_language(attrs),
+                        text: inner.join("\n"),
+                    });
+                } else {
+                    blocks.extend(Self::parse_blocks(inner, attributes));
+                }
+            }
+        }
+    }
+
+    /// Flatten the text of a compound block into paragraphs.
+    fn block_paragraphs(inner: &[&str], attributes: &mut BTreeMap<String, String>) -> Vec<String> {
+        Self::parse_blocks(inner, attributes)
+            .into_iter()
+            .flat_map(|block| match block {
+                MarkupBlock::Heading { text, .. } | MarkupBlock::Paragraph(text) => vec![text],
+                MarkupBlock::List { items, .. } => items,
+                MarkupBlock::Code { text, .. } => vec![text],
+                MarkupBlock::Table(rows) => vec![markup::cells_to_markdown(&rows)],
+                MarkupBlock::Quote { paragraphs, .. } => paragraphs,
+            })
+            .collect()
+    }
+
+    /// Parse a `|===` table. Cells may sit on one line or one per line.
+    fn parse_table(
+        inner: &[&str],
+        separator: char,
+        attrs: Option<&[String]>,
+        attributes: &BTreeMap<String, String>,
+    ) -> Vec<Vec<String>> {
+        let declared_cols = attrs.and_then(|a| {
+            let spec = a.iter().find_map(|attr| attr.strip_prefix("cols="))?;
+            Some(
+                spec.split([',', ';'])
+                    .map(|col| match col.split_once('*') {
+                        Some((count, _)) => count.trim().parse().unwrap_or(1),
+                        None => 1,
+                    })
+                    .sum::<usize>(),
+            )
+        });
+
+        let mut cells: Vec<String> = Vec::new();
+        let mut first_line_cols = None;
+
+        for line in inner {
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+            if separator != '|' || trimmed.starts_with('|') {
+                let parts: Vec<&str> = if separator == '|' {
+                    trimmed.split('|').skip(1).collect()
+                } else {
+                    trimmed.split(separator).collect()
+                };
+                first_line_cols.get_or_insert(parts.len());
+                cells.extend(parts.iter().map(|cell| Self::convert_inline(cell, attributes)));
+            } else if let Some(last) = cells.last_mut() {
+                last.push(' ');
+                last.push_str(&Self::convert_inline(trimmed, attributes));
+            }
+        }
+
+        let cols = declared_cols.or(first_line_cols).unwrap_or(1).max(1);
+        cells.chunks(cols).map(<[String]>::to_vec).collect()
+    }
+
+    /// Remove common leading indentation.
+    fn dedent(lines: &[&str]) -> String {
+        let indent = lines
+            .iter()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| l.len() - l.trim_start().len())
+            .min()
+            .unwrap_or(0);
+        lines
+            .iter()
+            .map(|l| l.get(indent..).unwrap_or(l.trim_start()))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Replace attribute references, macros and formatting marks with plain text.
+    fn convert_inline(text: &str, attributes: &BTreeMap<String, String>) -> String {
+        let text = ATTRIBUTE_REFERENCE.replace_all(text, |caps: &regex::Captures| {
+            attributes
+                .get(&caps[1].to_lowercase())
+                .cloned()
+                .unwrap_or_else(|| caps[0].to_string())
+        });
+        let text = INLINE_ANCHOR.replace_all(&text, "");
+        let text = CROSS_REFERENCE.replace_all(&text, |caps: &regex::Captures| {
+            caps.get(2).map_or(&caps[1], |m| m.as_str()).trim().to_string()
+        });
+        let text = INLINE_MACRO.replace_all(&text, |caps: &regex::Captures| {
+            if caps[2].is_empty() {
+                caps[1].to_string()
+            } else {
+                caps[2].to_string()
+            }
+        });
+        let text = URL_MACRO.replace_all(&text, |caps: &regex::Captures| {
+            let label = caps[3].split(',').next().unwrap_or("").trim();
+            if label.is_empty() {
+                caps.get(1)
+                    .or_else(|| caps.get(2))
+                    .map_or("", |m| m.as_str())
+                    .to_string()
+            } else {
+                label.to_string()
+            }
+        });
+
+        let mut text = text.into_owned();
+        for delimiter in ["**", "__", "``", "##", "*", "_", "`", "#", "^", "~"] {
+            text = markup::unwrap_delimited(&text, delimiter);
+        }
+        text.trim().to_string()
+    }
+
+    /// Build result metadata from the document header.
+    fn build_metadata(header: &AsciiDocHeader) -> Metadata {
+        let mut additional: AHashMap<Cow<'static, str>, serde_json::Value> = AHashMap::new();
+        if let Some(revision) = &header.revision {
+            additional.insert(Cow::Borrowed("revision"), serde_json::json!(revision));
+        }
+        if !header.attributes.is_empty() {
+            additional.insert(Cow::Borrowed("attributes"), serde_json::json!(header.attributes));
+        }
+
+        let keywords = header.attributes.get("keywords").map(|value| {
+            value
+                .split(',')
+                .map(|k| k.trim().to_string())
+                .filter(|k| !k.is_empty())
+                .collect::<Vec<_>>()
+        });
+
+        Metadata {
+            title: header.title.clone(),
+            subject: header.attributes.get("description").cloned(),
+            authors: (!header.authors.is_empty()).then(|| header.authors.clone()),
+            keywords: keywords.filter(|k| !k.is_empty()),
+            language: header.attributes.get("lang").cloned(),
+            created_at: header.revision_date.clone(),
+            additional,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(feature = "office")]
+impl Default for AsciiDocExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "office")]
+impl Plugin for AsciiDocExtractor {
+    fn name(&self) -> &str {
+        "asciidoc-extractor"
+    }
+
+    fn version(&self) -> String {
+        env!("CARGO_PKG_VERSION").to_string()
+    }
+
+    fn initialize(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn shutdown(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn description(&self) -> &str {
+        "Native Rust extractor for AsciiDoc documents"
+    }
+
+    fn author(&self) -> &str {
+        "Kreuzberg Team"
+    }
+}
+
+#[cfg(feature = "office")]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl DocumentExtractor for AsciiDocExtractor {
+    #[cfg_attr(
+        feature = "otel",
+        tracing::instrument(
+            skip(self, content, config),
+            fields(
+                extractor.name = self.name(),
+                content.size_bytes = content.len(),
+            )
+        )
+    )]
+    async fn extract_bytes(
+        &self,
+        content: &[u8],
+        mime_type: &str,
+        config: &ExtractionConfig,
+    ) -> Result<ExtractionResult> {
+        let text = String::from_utf8_lossy(content).into_owned();
+
+        let (header, blocks) = Self::parse(&text);
+        let metadata = Self::build_metadata(&header);
+        let document = config
+            .needs_document_structure()
+            .then(|| markup::build_document_structure(&blocks));
+
+        Ok(ExtractionResult {
+            content: markup::render_text(&blocks),
+            mime_type: mime_type.to_string().into(),
+            metadata,
+            tables: markup::extract_tables(&blocks),
+            detected_languages: None,
+            chunks: None,
+            images: None,
+            djot_content: None,
+            pages: None,
+            elements: None,
+            ocr_elements: None,
+            document,
+            #[cfg(any(feature = "keywords-yake", feature = "keywords-rake"))]
+            extracted_keywords: None,
+            quality_score: None,
+            processing_warnings: Vec::new(),
+            annotations: None,
+        })
+    }
+
+    fn supported_mime_types(&self) -> &[&str] {
+        &["text/x-asciidoc", "text/asciidoc"]
+    }
+
+    fn priority(&self) -> i32 {
+        50
+    }
+}
+
+#[cfg(all(test, feature = "office"))]
+mod tests {
+    use super::*;
+    use crate::types::NodeContent;
+
+    const SAMPLE: &str = r#"= Deployment Guide
+Jane Doe <jane@example.com>; John Roe
+v2.1, 2024-03-01: Reviewed
+:keywords: deploy, ops
+:product: Kreuzberg
+
+Welcome to *{product}*, see <<install,the install section>>.
+
+[[install]]
+== Installation
+
+. Download the https://example.com/release[release]
+. Unpack it
+* nested bullet
+
+NOTE: Restart the service after upgrading.
+
+[source,bash]
+----
+cargo install kreuzberg
+----
+
+=== Configuration
+
+[WARNING]
+====
+Never commit secrets.
+====
+
+[cols="2*",options="header"]
+|===
+|Key |Default
+
+|port
+|8000
+|===
+
+____
+Simple is better than complex.
+____
+
+Port:: Listening port
+Host:: Bind address
+"#;
+
+    #[test]
+    fn test_asciidoc_extractor_plugin_interface() {
+        let extractor = AsciiDocExtractor::new();
+        assert_eq!(extractor.name(), "asciidoc-extractor");
+        assert_eq!(extractor.version(), env!("CARGO_PKG_VERSION"));
+        assert_eq!(extractor.priority(), 50);
+        assert!(extractor.supported_mime_types().contains(&"text/x-asciidoc"));
+        assert!(extractor.initialize().is_ok());
+        assert!(extractor.shutdown().is_ok());
+    }
+
+    #[test]
+    fn test_asciidoc_header_metadata() {
+        let (header, _) = AsciiDocExtractor::parse(SAMPLE);
+        let metadata = AsciiDocExtractor::build_metadata(&header);
+
+        assert_eq!(metadata.title.as_deref(), Some("Deployment Guide"));
+        assert_eq!(
+            metadata.authors,
+            Some(vec!["Jane Doe".to_string(), "John Roe".to_string()])
+        );
+        assert_eq!(metadata.created_at.as_deref(), Some("2024-03-01"));
+        assert_eq!(metadata.keywords, Some(vec!["deploy".to_string(), "ops".to_string()]));
+        assert_eq!(metadata.additional.get("revision"), Some(&serde_json::json!("2.1")));
+        assert_eq!(
+            metadata.additional["attributes"]["product"],
+            serde_json::json!("Kreuzberg")
+        );
+    }
+
+    #[test]
+    fn test_asciidoc_blocks() {
+        let (_, blocks) = AsciiDocExtractor::parse(SAMPLE);
+
+        assert_eq!(
+            blocks[0],
+            MarkupBlock::Paragraph("Welcome to Kreuzberg, see the install section.".to_string())
+        );
+        assert_eq!(
+            blocks[1],
+            MarkupBlock::Heading {
+                level: 1,
+                text: "Installation".to_string()
+            }
+        );
+        assert_eq!(
+            blocks[2],
+            MarkupBlock::List {
+                ordered: true,
+                items: vec![
+                    "Download the release".to_string(),
+                    "Unpack it".to_string(),
+                    "nested bullet".to_string()
+                ]
+            }
+        );
+        assert_eq!(
+            blocks[3],
+            MarkupBlock::Quote {
+                label: Some("NOTE".to_string()),
+                paragraphs: vec!["Restart the service after upgrading.".to_string()]
+            }
+        );
+        assert_eq!(
+            blocks[4],
+            MarkupBlock::Code {
+                language: Some("bash".to_string()),
+                text: "cargo install kreuzberg".to_string()
+            }
+        );
+        assert_eq!(
+            blocks[5],
+            MarkupBlock::Heading {
+                level: 2,
+                text: "Configuration".to_string()
+            }
+        );
+        assert_eq!(
+            blocks[6],
+            MarkupBlock::Quote {
+                label: Some("WARNING".to_string()),
+                paragraphs: vec!["Never commit secrets.".to_string()]
+            }
+        );
+        assert_eq!(
+            blocks[7],
+            MarkupBlock::Table(vec![
+                vec!["Key".to_string(), "Default".to_string()],
+                vec!["port".to_string(), "8000".to_string()],
+            ])
+        );
+        assert!(
+            matches!(&blocks[8], MarkupBlock::Quote { label: None, paragraphs } if paragraphs[0].starts_with("Simple"))
+        );
+        assert_eq!(
+            blocks[9],
+            MarkupBlock::List {
+                ordered: false,
+                items: vec!["Port: Listening port".to_string(), "Host: Bind address".to_string()]
+            }
+        );
+        assert_eq!(blocks.len(), 10);
+    }
+
+    #[test]
+    fn test_asciidoc_table_rows() {
+        let content = "|===\n|Name |Age\n\n|Alice |30\n|===\n";
+        let (_, blocks) = AsciiDocExtractor::parse(content);
+        assert_eq!(
+            blocks,
+            vec![MarkupBlock::Table(vec![
+                vec!["Name".to_string(), "Age".to_string()],
+                vec!["Alice".to_string(), "30".to_string()],
+            ])]
+        );
+    }
+
+    #[test]
+    fn test_asciidoc_inline_markup() {
+        let attributes = BTreeMap::new();
+        assert_eq!(
+            AsciiDocExtractor::convert_inline(
+                "Use `cargo`, _really_ **now** via link:docs/x.html[the docs]",
+                &attributes
+            ),
+            "Use cargo, really now via the docs"
+        );
+        assert_eq!(
+            AsciiDocExtractor::convert_inline("Press kbd:[Ctrl+C] and see xref:other.adoc[Other]", &attributes),
+            "Press Ctrl+C and see Other"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_asciidoc_extract_bytes_with_document_structure() {
+        let config = ExtractionConfig {
+            include_document_structure: true,
+            ..Default::default()
+        };
+        let result = AsciiDocExtractor::new()
+            .extract_bytes(SAMPLE.as_bytes(), "text/x-asciidoc", &config)
+            .await
+            .unwrap();
+
+        assert!(result.content.contains("# Installation"));
+        assert!(result.content.contains("```bash\ncargo install kreuzberg\n```"));
+        assert_eq!(result.tables.len(), 1);
+
+        let document = result.document.expect("document structure requested");
+        assert!(document.validate().is_ok());
+        let has = |pred: fn(&NodeContent) -> bool| document.nodes.iter().any(|n| pred(&n.content));
+        assert!(has(|c| matches!(c, NodeContent::Heading { level: 2, .. })));
+        assert!(has(|c| matches!(c, NodeContent::Code { language: Some(_), .. })));
+        assert!(has(|c| matches!(c, NodeContent::Quote)));
+        assert!(has(|c| matches!(c, NodeContent::Table { .. })));
+        assert!(has(|c| matches!(c, NodeContent::List { ordered: true })));
+    }
+}
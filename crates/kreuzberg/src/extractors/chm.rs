@@ -0,0 +1,233 @@
+//! Compiled HTML Help extractor.
+//!
+//! Supports: CHM (.chm)
+
+use crate::Result;
+use crate::core::config::{ExtractionConfig, OutputFormat};
+use crate::extraction::chm::{CHM_MIME_TYPE, parse_chm};
+use crate::plugins::{DocumentExtractor, Plugin};
+use crate::types::{ExtractionResult, Metadata};
+use ahash::AHashMap;
+use async_trait::async_trait;
+use std::borrow::Cow;
+
+/// Microsoft Compiled HTML Help extractor.
+///
+/// Reads the ITSF container (decompressing the LZX section), walks the topics
+/// in table of contents order and converts each topic's HTML to markdown with
+/// the HTML converter.
+pub struct ChmExtractor;
+
+impl ChmExtractor {
+    /// Create a new CHM extractor.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for ChmExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Plugin for ChmExtractor {
+    fn name(&self) -> &str {
+        "chm-extractor"
+    }
+
+    fn version(&self) -> String {
+        env!("CARGO_PKG_VERSION").to_string()
+    }
+
+    fn initialize(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn shutdown(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn description(&self) -> &str {
+        "Native Rust Compiled HTML Help (CHM) extractor with LZX decompression"
+    }
+
+    fn author(&self) -> &str {
+        "Kreuzberg Team"
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl DocumentExtractor for ChmExtractor {
+    #[cfg_attr(feature = "otel", tracing::instrument(
+        skip(self, content, config),
+        fields(
+            extractor.name = self.name(),
+            content.size_bytes = content.len(),
+        )
+    ))]
+    async fn extract_bytes(
+        &self,
+        content: &[u8],
+        mime_type: &str,
+        config: &ExtractionConfig,
+    ) -> Result<ExtractionResult> {
+        let document = {
+            #[cfg(feature = "tokio-runtime")]
+            {
+                if crate::core::batch_mode::is_batch_mode() {
+                    let content_owned = content.to_vec();
+                    let span = tracing::Span::current();
+                    tokio::task::spawn_blocking(move || {
+                        let _guard = span.entered();
+                        parse_chm(&content_owned)
+                    })
+                    .await
+                    .map_err(|e| {
+                        crate::error::KreuzbergError::parsing(format!("CHM extraction task failed: {}", e))
+                    })??
+                } else {
+                    parse_chm(content)?
+                }
+            }
+
+            #[cfg(not(feature = "tokio-runtime"))]
+            {
+                parse_chm(content)?
+            }
+        };
+
+        let mut sections = Vec::with_capacity(document.topics.len());
+        for topic in &document.topics {
+            let text = crate::extraction::html::convert_html_to_markdown(
+                &topic.html,
+                config.html_options.clone(),
+                Some(config.output_format),
+            )?;
+            let text = text.trim();
+            if !text.is_empty() {
+                sections.push(text.to_string());
+            }
+        }
+
+        let mut additional: AHashMap<Cow<'static, str>, serde_json::Value> = AHashMap::new();
+        additional.insert(Cow::Borrowed("topic_count"), serde_json::json!(document.topics.len()));
+        additional.insert(
+            Cow::Borrowed("topics"),
+            serde_json::Value::Array(
+                document
+                    .topics
+                    .iter()
+                    .map(|topic| serde_json::json!({ "title": topic.title, "path": topic.path }))
+                    .collect(),
+            ),
+        );
+
+        let pre_formatted = match config.output_format {
+            OutputFormat::Markdown => Some("markdown".to_string()),
+            OutputFormat::Djot => Some("djot".to_string()),
+            _ => None,
+        };
+
+        Ok(ExtractionResult {
+            content: sections.join("\n\n"),
+            mime_type: mime_type.to_string().into(),
+            metadata: Metadata {
+                title: document.title,
+                output_format: pre_formatted,
+                additional,
+                ..Default::default()
+            },
+            pages: None,
+            tables: vec![],
+            detected_languages: None,
+            chunks: None,
+            images: None,
+            djot_content: None,
+            elements: None,
+            ocr_elements: None,
+            document: None,
+            #[cfg(any(feature = "keywords-yake", feature = "keywords-rake"))]
+            extracted_keywords: None,
+            quality_score: None,
+            processing_warnings: Vec::new(),
+            annotations: None,
+        })
+    }
+
+    fn supported_mime_types(&self) -> &[&str] {
+        &[CHM_MIME_TYPE, "application/x-chm"]
+    }
+
+    fn priority(&self) -> i32 {
+        50
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chm_extractor_plugin_interface() {
+        let extractor = ChmExtractor::new();
+        assert_eq!(extractor.name(), "chm-extractor");
+        assert_eq!(extractor.priority(), 50);
+        assert!(extractor.initialize().is_ok());
+        assert!(extractor.shutdown().is_ok());
+    }
+
+    #[test]
+    fn test_chm_extractor_supported_mime_types() {
+        let extractor = ChmExtractor::new();
+        assert!(
+            extractor
+                .supported_mime_types()
+                .contains(&"application/vnd.ms-htmlhelp")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_chm_extractor_converts_topics() {
+        use crate::extraction::chm::test_support::{build_chm, system_file};
+
+        let data = build_chm(
+            &[("/#SYSTEM", system_file("", "Admin Guide"))],
+            &[
+                (
+                    "/intro.htm",
+                    b"<h1>Introduction</h1><p>Welcome to the <b>portal</b>.</p>".to_vec(),
+                ),
+                ("/setup.htm", b"<h2>Setup</h2><ul><li>Install</li></ul>".to_vec()),
+            ],
+        );
+
+        let result = ChmExtractor::new()
+            .extract_bytes(&data, "application/vnd.ms-htmlhelp", &ExtractionConfig::default())
+            .await
+            .unwrap();
+
+        assert!(result.content.contains("Introduction"));
+        assert!(result.content.contains("Welcome to the"));
+        assert!(result.content.contains("Setup"));
+        assert!(result.content.find("Introduction") < result.content.find("Setup"));
+        assert_eq!(result.metadata.title.as_deref(), Some("Admin Guide"));
+        assert_eq!(
+            result.metadata.additional.get("topic_count"),
+            Some(&serde_json::json!(2))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_chm_extractor_invalid_file() {
+        let result = ChmExtractor::new()
+            .extract_bytes(
+                b"not a chm",
+                "application/vnd.ms-htmlhelp",
+                &ExtractionConfig::default(),
+            )
+            .await;
+        assert!(result.is_err());
+    }
+}
@@ -203,6 +203,90 @@ fn build_document_structure(doc: &crate::extraction::docx::parser::Document) ->
             }
             crate::extraction::docx::parser::DocumentElement::Drawing(idx) => {
                 let drawing = &doc.drawings[*idx];
+
+                // Charts become tables of their series data
+                if let Some(chart) = drawing.chart_ref.as_ref().and_then(|rid| doc.charts.get(rid)) {
+                    let rows = chart.to_cells();
+                    if !rows.is_empty() {
+                        let cells = rows
+                            .iter()
+                            .enumerate()
+                            .flat_map(|(row_idx, row)| {
+                                row.iter().enumerate().map(move |(col_idx, content)| GridCell {
+                                    content: content.clone(),
+                                    row: row_idx as u32,
+                                    col: col_idx as u32,
+                                    row_span: 1,
+                                    col_span: 1,
+                                    is_header: row_idx == 0,
+                                    bbox: None,
+                                })
+                            })
+                            .collect();
+                        let grid = TableGrid {
+                            rows: rows.len() as u32,
+                            cols: rows[0].len() as u32,
+                            cells,
+                        };
+                        let node = DocumentNode {
+                            id: NodeId::generate("table", chart.title.as_deref().unwrap_or(""), None, node_count),
+                            content: NodeContent::Table { grid },
+                            parent: current_parent(&section_stack),
+                            children: Vec::new(),
+                            content_layer: ContentLayer::Body,
+                            page: None,
+                            page_end: None,
+                            bbox: None,
+                            annotations: Vec::new(),
+                        };
+                        node_count += 1;
+                        let table_idx = structure.push_node(node);
+                        if let Some(parent_idx) = current_parent(&section_stack) {
+                            structure.add_child(parent_idx, table_idx);
+                        }
+                        continue;
+                    }
+                }
+
+                // SmartArt diagrams become a list of their node texts
+                if let Some(smartart) = drawing.diagram_ref.as_ref().and_then(|rid| doc.smartarts.get(rid))
+                    && !smartart.is_empty()
+                {
+                    let list = DocumentNode {
+                        id: NodeId::generate("list", "", None, node_count),
+                        content: NodeContent::List { ordered: false },
+                        parent: current_parent(&section_stack),
+                        children: Vec::new(),
+                        content_layer: ContentLayer::Body,
+                        page: None,
+                        page_end: None,
+                        bbox: None,
+                        annotations: Vec::new(),
+                    };
+                    node_count += 1;
+                    let list_idx = structure.push_node(list);
+                    if let Some(parent_idx) = current_parent(&section_stack) {
+                        structure.add_child(parent_idx, list_idx);
+                    }
+                    for (_, text) in smartart.flatten() {
+                        let item = DocumentNode {
+                            id: NodeId::generate("list_item", text, None, node_count),
+                            content: NodeContent::ListItem { text: text.to_string() },
+                            parent: Some(list_idx),
+                            children: Vec::new(),
+                            content_layer: ContentLayer::Body,
+                            page: None,
+                            page_end: None,
+                            bbox: None,
+                            annotations: Vec::new(),
+                        };
+                        node_count += 1;
+                        let item_idx = structure.push_node(item);
+                        structure.add_child(list_idx, item_idx);
+                    }
+                    continue;
+                }
+
                 let description = drawing.doc_properties.as_ref().and_then(|dp| dp.description.clone());
                 let node = DocumentNode {
                     id: NodeId::generate("image", "", None, node_count),
@@ -304,12 +388,30 @@ type DocxParseResult = (
 fn parse_docx_core(content: &[u8], include_doc_structure: bool) -> crate::error::Result<DocxParseResult> {
     let doc = crate::extraction::docx::parser::parse_document(content)?;
     let text = doc.to_markdown();
-    let tables: Vec<Table> = doc
+    let mut tables: Vec<Table> = doc
         .tables
         .iter()
         .enumerate()
         .map(|(idx, table)| convert_docx_table_to_table(table, idx))
         .collect();
+    // Chart data follows the document tables, in drawing order
+    for chart in doc
+        .drawings
+        .iter()
+        .filter_map(|d| d.chart_ref.as_ref().and_then(|rid| doc.charts.get(rid)))
+    {
+        let cells = chart.to_cells();
+        if cells.is_empty() {
+            continue;
+        }
+        let markdown = cells_to_markdown(&cells);
+        tables.push(Table {
+            cells,
+            markdown,
+            page_number: tables.len() + 1,
+            bounding_box: None,
+        });
+    }
     let page_boundaries = crate::extraction::docx::detect_page_breaks_from_docx(content)?;
     let drawings = doc.drawings.clone();
     let image_rels = doc.image_relationships.clone();
@@ -408,7 +510,7 @@ impl DocumentExtractor for DocxExtractor {
         mime_type: &str,
         config: &ExtractionConfig,
     ) -> Result<ExtractionResult> {
-        let include_doc_structure = config.include_document_structure;
+        let include_doc_structure = config.needs_document_structure();
 
         let (text, tables, page_boundaries, drawings, image_rels, doc_structure) = {
             #[cfg(feature = "tokio-runtime")]
@@ -1637,6 +1739,8 @@ mod tests {
                 description: Some("A test image".to_string()),
             }),
             image_ref: None,
+            chart_ref: None,
+            diagram_ref: None,
         };
         let d_idx = doc.drawings.len();
         doc.drawings.push(drawing);
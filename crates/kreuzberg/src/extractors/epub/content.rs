@@ -3,36 +3,40 @@
 //! Handles extraction of text content from XHTML files in spine order,
 //! with markdown conversion and HTML cleaning utilities.
 
-use crate::Result;
+use crate::core::config::PageConfig;
+use crate::types::PageBoundary;
 use std::io::Cursor;
 use zip::ZipArchive;
 
-use super::metadata::parse_opf;
 use super::parsing::{read_file_from_zip, resolve_path};
 
+/// Text of one spine document.
+#[derive(Debug, Clone)]
+pub(super) struct Chapter {
+    /// Archive path of the XHTML document.
+    pub(super) href: String,
+    /// Plain text content.
+    pub(super) text: String,
+}
+
 /// Extract text content from an EPUB document by reading in spine order
+///
+/// Spine documents that cannot be read or contain no text are skipped.
 pub(super) fn extract_content(
     archive: &mut ZipArchive<Cursor<Vec<u8>>>,
-    opf_path: &str,
+    spine_hrefs: &[String],
     manifest_dir: &str,
-) -> Result<String> {
-    let opf_xml = read_file_from_zip(archive, opf_path)?;
-    let (_, spine_hrefs) = parse_opf(&opf_xml)?;
-
-    let mut content = String::new();
+) -> Vec<Chapter> {
+    let mut chapters = Vec::new();
 
-    for (index, href) in spine_hrefs.iter().enumerate() {
+    for href in spine_hrefs {
         let file_path = resolve_path(manifest_dir, href);
 
         match read_file_from_zip(archive, &file_path) {
             Ok(xhtml_content) => {
                 let text = extract_text_from_xhtml(&xhtml_content);
-                if !text.is_empty() {
-                    if index > 0 && !content.ends_with('\n') {
-                        content.push('\n');
-                    }
-                    content.push_str(&text);
-                    content.push('\n');
+                if !text.trim().is_empty() {
+                    chapters.push(Chapter { href: file_path, text });
                 }
             }
             Err(_) => {
@@ -41,7 +45,44 @@ pub(super) fn extract_content(
         }
     }
 
-    Ok(content.trim().to_string())
+    chapters
+}
+
+/// Join chapter texts into the document content.
+///
+/// Returns the content and the byte range of each chapter, inserting page
+/// markers before every chapter when configured.
+pub(super) fn assemble_content(chapters: &[Chapter], page_config: Option<&PageConfig>) -> (String, Vec<PageBoundary>) {
+    let mut content = String::new();
+    let mut boundaries = Vec::with_capacity(chapters.len());
+
+    for (index, chapter) in chapters.iter().enumerate() {
+        let page_number = index + 1;
+        let byte_start = content.len();
+
+        if let Some(cfg) = page_config
+            && cfg.insert_page_markers
+        {
+            content.push_str(&cfg.marker_format.replace("{page_num}", &page_number.to_string()));
+        } else if index > 0 && !content.ends_with('\n') {
+            content.push('\n');
+        }
+        content.push_str(chapter.text.trim());
+        content.push('\n');
+
+        boundaries.push(PageBoundary {
+            byte_start,
+            byte_end: content.len(),
+            page_number,
+        });
+    }
+
+    let content = content.trim_end().to_string();
+    if let Some(last) = boundaries.last_mut() {
+        last.byte_end = last.byte_end.min(content.len());
+    }
+
+    (content, boundaries)
 }
 
 /// Extract text from XHTML content using html-to-markdown-rs
@@ -273,6 +314,44 @@ pub(super) fn strip_html_tags(html: &str) -> String {
 mod tests {
     use super::*;
 
+    fn chapter(text: &str) -> Chapter {
+        Chapter {
+            href: "ch.xhtml".to_string(),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_assemble_content_boundaries() {
+        let chapters = vec![chapter("First chapter"), chapter("Second chapter\n")];
+        let (content, boundaries) = assemble_content(&chapters, None);
+
+        assert_eq!(content, "First chapter\nSecond chapter");
+        assert_eq!(boundaries.len(), 2);
+        assert_eq!(
+            &content[boundaries[0].byte_start..boundaries[0].byte_end],
+            "First chapter\n"
+        );
+        assert_eq!(
+            &content[boundaries[1].byte_start..boundaries[1].byte_end],
+            "Second chapter"
+        );
+    }
+
+    #[test]
+    fn test_assemble_content_page_markers() {
+        let config = PageConfig {
+            extract_pages: true,
+            insert_page_markers: true,
+            ..Default::default()
+        };
+        let (content, _) = assemble_content(&[chapter("One"), chapter("Two")], Some(&config));
+
+        assert!(content.contains("<!-- PAGE 1 -->"));
+        assert!(content.contains("<!-- PAGE 2 -->"));
+        assert!(content.find("One") < content.find("<!-- PAGE 2 -->"));
+    }
+
     #[test]
     fn test_strip_html_tags_simple() {
         let html = "<html><body><p>Hello World</p></body></html>";
@@ -25,7 +25,7 @@ pub(super) struct OepbMetadata {
 pub(super) fn extract_metadata(opf_xml: &str) -> Result<(OepbMetadata, BTreeMap<String, serde_json::Value>)> {
     let mut additional_metadata = BTreeMap::new();
 
-    let (epub_metadata, _) = parse_opf(opf_xml)?;
+    let epub_metadata = parse_opf(opf_xml)?.metadata;
 
     if let Some(ref identifier) = epub_metadata.identifier {
         additional_metadata.insert("identifier".to_string(), serde_json::json!(identifier.clone()));
@@ -50,15 +50,39 @@ pub(super) fn extract_metadata(opf_xml: &str) -> Result<(OepbMetadata, BTreeMap<
     Ok((epub_metadata, additional_metadata))
 }
 
-/// Parse OPF file and extract metadata and spine order
-pub(super) fn parse_opf(xml: &str) -> Result<(OepbMetadata, Vec<String>)> {
+/// A manifest entry referenced from the package document.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct ManifestItem {
+    pub(super) href: String,
+    pub(super) media_type: Option<String>,
+}
+
+/// Parsed OPF package document.
+#[derive(Debug, Default, Clone)]
+pub(super) struct OpfPackage {
+    pub(super) metadata: OepbMetadata,
+    /// Spine item hrefs in reading order (relative to the OPF directory).
+    pub(super) spine: Vec<String>,
+    /// EPUB3 navigation document (`properties="nav"`).
+    pub(super) nav_href: Option<String>,
+    /// EPUB2 NCX table of contents (`<spine toc="...">`).
+    pub(super) ncx_href: Option<String>,
+    /// Cover image (`properties="cover-image"` or EPUB2 `<meta name="cover">`).
+    pub(super) cover: Option<ManifestItem>,
+}
+
+/// Parse OPF file and extract metadata, spine order, navigation and cover references
+pub(super) fn parse_opf(xml: &str) -> Result<OpfPackage> {
     match roxmltree::Document::parse(xml) {
         Ok(doc) => {
             let root = doc.root();
 
             let mut metadata = OepbMetadata::default();
-            let mut manifest: BTreeMap<String, String> = BTreeMap::new();
+            let mut manifest: BTreeMap<String, ManifestItem> = BTreeMap::new();
             let mut spine_order: Vec<String> = Vec::new();
+            let mut nav_href = None;
+            let mut cover = None;
+            let mut cover_id = None;
 
             for node in root.descendants() {
                 match node.tag_name().name() {
@@ -111,23 +135,74 @@ pub(super) fn parse_opf(xml: &str) -> Result<(OepbMetadata, Vec<String>)> {
                         if let Some(id) = node.attribute("id")
                             && let Some(href) = node.attribute("href")
                         {
-                            manifest.insert(id.to_string(), href.to_string());
+                            let item = ManifestItem {
+                                href: href.to_string(),
+                                media_type: node.attribute("media-type").map(str::to_string),
+                            };
+                            let properties = node.attribute("properties").unwrap_or_default();
+                            if properties.split_whitespace().any(|p| p == "nav") {
+                                nav_href = Some(item.href.clone());
+                            }
+                            if properties.split_whitespace().any(|p| p == "cover-image") {
+                                cover = Some(item.clone());
+                            }
+                            manifest.insert(id.to_string(), item);
+                        }
+                    }
+                    "meta" => {
+                        if node.attribute("name") == Some("cover")
+                            && let Some(content) = node.attribute("content")
+                        {
+                            cover_id = Some(content.to_string());
                         }
                     }
                     _ => {}
                 }
             }
 
+            let mut ncx_href = None;
             for node in root.descendants() {
-                if node.tag_name().name() == "itemref"
-                    && let Some(idref) = node.attribute("idref")
-                    && let Some(href) = manifest.get(idref)
-                {
-                    spine_order.push(href.clone());
+                match node.tag_name().name() {
+                    "itemref" => {
+                        if let Some(idref) = node.attribute("idref")
+                            && let Some(item) = manifest.get(idref)
+                        {
+                            spine_order.push(item.href.clone());
+                        }
+                    }
+                    "spine" => {
+                        ncx_href = node
+                            .attribute("toc")
+                            .and_then(|id| manifest.get(id))
+                            .map(|item| item.href.clone());
+                    }
+                    _ => {}
                 }
             }
 
-            Ok((metadata, spine_order))
+            if ncx_href.is_none() {
+                ncx_href = manifest
+                    .values()
+                    .find(|item| item.media_type.as_deref() == Some("application/x-dtbncx+xml"))
+                    .map(|item| item.href.clone());
+            }
+
+            // EPUB2 names the cover by manifest id; only accept it if it is an image
+            if cover.is_none() {
+                cover = cover_id.and_then(|id| manifest.get(&id)).cloned().filter(|item| {
+                    item.media_type
+                        .as_deref()
+                        .is_some_and(|media_type| media_type.starts_with("image/"))
+                });
+            }
+
+            Ok(OpfPackage {
+                metadata,
+                spine: spine_order,
+                nav_href,
+                ncx_href,
+                cover,
+            })
         }
         Err(e) => Err(crate::KreuzbergError::Parsing {
             message: format!("Failed to parse OPF file: {}", e),
@@ -3,7 +3,9 @@
 //! This extractor provides native Rust-based EPUB extraction without GPL-licensed
 //! dependencies, extracting:
 //! - Metadata from OPF (Open Packaging Format) using Dublin Core standards
-//! - Content from XHTML files in spine order
+//! - Content from XHTML files in spine order, one page per chapter
+//! - Table of contents (EPUB3 nav or EPUB2 NCX) as document structure headings
+//! - Cover image when image extraction is enabled
 //! - Proper handling of EPUB2 and EPUB3 formats
 //!
 //! Uses only permissive-licensed crates:
@@ -14,20 +16,50 @@
 mod content;
 mod metadata;
 mod parsing;
+mod structure;
+mod toc;
 
 use crate::Result;
 use crate::core::config::ExtractionConfig;
 use crate::plugins::{DocumentExtractor, Plugin};
-use crate::types::{ExtractionResult, Metadata};
+use crate::types::{ExtractedImage, ExtractionResult, Metadata, PageContent, PageInfo, PageStructure, PageUnitType};
 use ahash::AHashMap;
 use async_trait::async_trait;
+use bytes::Bytes;
 use std::borrow::Cow;
 use std::io::Cursor;
 use zip::ZipArchive;
 
-use content::extract_content;
-use metadata::extract_metadata;
-use parsing::{parse_container_xml, read_file_from_zip};
+use content::{assemble_content, extract_content};
+use metadata::{extract_metadata, parse_opf};
+use parsing::{parent_dir, parse_container_xml, read_bytes_from_zip, read_file_from_zip, resolve_path};
+use structure::{build_document_structure, toc_anchors};
+use toc::{TocEntry, parse_nav, parse_ncx};
+
+/// Read the book's table of contents, preferring the EPUB3 nav document over the NCX.
+fn read_toc(
+    archive: &mut ZipArchive<Cursor<Vec<u8>>>,
+    manifest_dir: &str,
+    nav_href: Option<&str>,
+    ncx_href: Option<&str>,
+) -> Vec<TocEntry> {
+    if let Some(href) = nav_href {
+        let path = resolve_path(manifest_dir, href);
+        if let Ok(xml) = read_file_from_zip(archive, &path) {
+            let entries = parse_nav(&xml, &path);
+            if !entries.is_empty() {
+                return entries;
+            }
+        }
+    }
+    if let Some(href) = ncx_href {
+        let path = resolve_path(manifest_dir, href);
+        if let Ok(xml) = read_file_from_zip(archive, &path) {
+            return parse_ncx(&xml, &path);
+        }
+    }
+    Vec::new()
+}
 
 /// EPUB format extractor using permissive-licensed dependencies.
 ///
@@ -81,7 +113,7 @@ impl DocumentExtractor for EpubExtractor {
     #[cfg_attr(
         feature = "otel",
         tracing::instrument(
-            skip(self, content, _config),
+            skip(self, content, config),
             fields(
                 extractor.name = self.name(),
                 content.size_bytes = content.len(),
@@ -92,7 +124,7 @@ impl DocumentExtractor for EpubExtractor {
         &self,
         content: &[u8],
         mime_type: &str,
-        _config: &ExtractionConfig,
+        config: &ExtractionConfig,
     ) -> Result<ExtractionResult> {
         let cursor = Cursor::new(content.to_vec());
 
@@ -104,21 +136,127 @@ impl DocumentExtractor for EpubExtractor {
         let container_xml = read_file_from_zip(&mut archive, "META-INF/container.xml")?;
         let opf_path = parse_container_xml(&container_xml)?;
 
-        let manifest_dir = if let Some(last_slash) = opf_path.rfind('/') {
-            opf_path[..last_slash].to_string()
-        } else {
-            String::new()
-        };
+        let manifest_dir = parent_dir(&opf_path).to_string();
 
         let opf_xml = read_file_from_zip(&mut archive, &opf_path)?;
+        let package = parse_opf(&opf_xml)?;
+
+        let chapters = extract_content(&mut archive, &package.spine, &manifest_dir);
+        let toc = read_toc(
+            &mut archive,
+            &manifest_dir,
+            package.nav_href.as_deref(),
+            package.ncx_href.as_deref(),
+        );
+
+        // A chapter takes the title of the first TOC entry pointing into it
+        let anchors = toc_anchors(&chapters, &toc);
+        let chapter_titles: Vec<Option<String>> = (0..chapters.len())
+            .map(|index| {
+                toc.iter()
+                    .zip(&anchors)
+                    .find(|(entry, anchor)| **anchor == index && entry.href.as_deref() == Some(&chapters[index].href))
+                    .map(|(entry, _)| entry.title.clone())
+            })
+            .collect();
 
-        let extracted_content = extract_content(&mut archive, &opf_path, &manifest_dir)?;
+        let page_config = config.pages.as_ref();
+        let (extracted_content, boundaries) = assemble_content(&chapters, page_config);
 
         let (epub_metadata, additional_metadata) = extract_metadata(&opf_xml)?;
-        let metadata_map: AHashMap<Cow<'static, str>, serde_json::Value> = additional_metadata
+        let mut metadata_map: AHashMap<Cow<'static, str>, serde_json::Value> = additional_metadata
             .into_iter()
             .map(|(k, v)| (Cow::Owned(k), v))
             .collect();
+        metadata_map.insert(
+            Cow::Borrowed("chapters"),
+            serde_json::Value::Array(
+                chapters
+                    .iter()
+                    .zip(&chapter_titles)
+                    .enumerate()
+                    .map(|(index, (chapter, title))| {
+                        serde_json::json!({
+                            "index": index + 1,
+                            "title": title,
+                            "href": chapter.href,
+                        })
+                    })
+                    .collect(),
+            ),
+        );
+
+        let extract_images = config.images.as_ref().is_some_and(|img| img.extract_images);
+        let images = if extract_images {
+            let mut images = Vec::new();
+            if let Some(cover) = &package.cover
+                && let Ok(data) = read_bytes_from_zip(&mut archive, &resolve_path(&manifest_dir, &cover.href))
+            {
+                images.push(ExtractedImage {
+                    format: crate::extraction::image_format::detect_image_format(&data),
+                    data: Bytes::from(data),
+                    image_index: 0,
+                    page_number: None,
+                    width: None,
+                    height: None,
+                    colorspace: None,
+                    bits_per_component: None,
+                    is_mask: false,
+                    description: Some("cover".to_string()),
+                    ocr_result: None,
+                    bounding_box: None,
+                });
+            }
+
+            #[cfg(all(feature = "ocr", feature = "tokio-runtime"))]
+            let images = crate::extraction::image_ocr::process_images_with_ocr(images, config).await?;
+
+            Some(images)
+        } else {
+            None
+        };
+
+        let (page_structure, pages) = match page_config {
+            Some(_) => {
+                let page_contents: Vec<PageContent> = chapters
+                    .iter()
+                    .enumerate()
+                    .map(|(index, chapter)| PageContent {
+                        page_number: index + 1,
+                        content: chapter.text.trim().to_string(),
+                        tables: Vec::new(),
+                        images: Vec::new(),
+                        hierarchy: None,
+                        is_blank: Some(crate::extraction::blank_detection::is_page_text_blank(&chapter.text)),
+                    })
+                    .collect();
+                let page_infos = page_contents
+                    .iter()
+                    .zip(&chapter_titles)
+                    .map(|(page, title)| PageInfo {
+                        number: page.page_number,
+                        title: title.clone(),
+                        dimensions: None,
+                        image_count: None,
+                        table_count: None,
+                        hidden: None,
+                        is_blank: page.is_blank,
+                    })
+                    .collect();
+                let structure = PageStructure {
+                    total_count: chapters.len(),
+                    unit_type: PageUnitType::Page,
+                    boundaries: Some(boundaries),
+                    pages: Some(page_infos),
+                };
+                (Some(structure), Some(page_contents))
+            }
+            None => (None, None),
+        };
+
+        let document = config
+            .needs_document_structure()
+            .then(|| build_document_structure(&chapters, &toc));
 
         Ok(ExtractionResult {
             content: extracted_content,
@@ -128,18 +266,19 @@ impl DocumentExtractor for EpubExtractor {
                 authors: epub_metadata.creator.map(|c| vec![c]),
                 language: epub_metadata.language,
                 created_at: epub_metadata.date,
+                pages: page_structure,
                 additional: metadata_map,
                 ..Default::default()
             },
-            pages: None,
+            pages,
             tables: vec![],
             detected_languages: None,
             chunks: None,
-            images: None,
+            images,
             djot_content: None,
             elements: None,
             ocr_elements: None,
-            document: None,
+            document,
             #[cfg(any(feature = "keywords-yake", feature = "keywords-rake"))]
             extracted_keywords: None,
             quality_score: None,
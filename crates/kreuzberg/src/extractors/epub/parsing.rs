@@ -51,15 +51,52 @@ pub(super) fn read_file_from_zip(archive: &mut ZipArchive<Cursor<Vec<u8>>>, path
     }
 }
 
+/// Read a binary file (e.g. an image) from the ZIP archive
+pub(super) fn read_bytes_from_zip(archive: &mut ZipArchive<Cursor<Vec<u8>>>, path: &str) -> Result<Vec<u8>> {
+    let mut file = archive.by_name(path).map_err(|e| crate::KreuzbergError::Parsing {
+        message: format!("File not found in EPUB: {} ({})", path, e),
+        source: None,
+    })?;
+    let mut data = Vec::with_capacity(file.size() as usize);
+    std::io::Read::read_to_end(&mut file, &mut data).map_err(|e| crate::KreuzbergError::Parsing {
+        message: format!("Failed to read file from EPUB: {}", e),
+        source: None,
+    })?;
+    Ok(data)
+}
+
 /// Resolve a relative path within the manifest directory
+///
+/// `.` and `..` segments are normalized so the result can be looked up in the archive.
 pub(super) fn resolve_path(base_dir: &str, relative_path: &str) -> String {
-    if relative_path.starts_with('/') {
+    let joined = if relative_path.starts_with('/') {
         relative_path.trim_start_matches('/').to_string()
     } else if base_dir.is_empty() || base_dir == "." {
         relative_path.to_string()
     } else {
         format!("{}/{}", base_dir.trim_end_matches('/'), relative_path)
+    };
+
+    if !joined.split('/').any(|segment| segment == "." || segment == "..") {
+        return joined;
+    }
+
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in joined.split('/') {
+        match segment {
+            "." | "" => {}
+            ".." => {
+                segments.pop();
+            }
+            _ => segments.push(segment),
+        }
     }
+    segments.join("/")
+}
+
+/// Directory part of an archive path (empty for top-level files)
+pub(super) fn parent_dir(path: &str) -> &str {
+    path.rfind('/').map_or("", |last_slash| &path[..last_slash])
 }
 
 #[cfg(test)]
@@ -83,4 +120,16 @@ mod tests {
         let result = resolve_path("", "chapter.xhtml");
         assert_eq!(result, "chapter.xhtml");
     }
+
+    #[test]
+    fn test_resolve_path_parent_segments() {
+        let result = resolve_path("OEBPS/text", "../images/cover.jpg");
+        assert_eq!(result, "OEBPS/images/cover.jpg");
+    }
+
+    #[test]
+    fn test_parent_dir() {
+        assert_eq!(parent_dir("OEBPS/toc.ncx"), "OEBPS");
+        assert_eq!(parent_dir("toc.ncx"), "");
+    }
 }
@@ -0,0 +1,179 @@
+//! Document structure construction for EPUB books.
+//!
+//! Table of contents entries become heading sections and chapter text becomes
+//! paragraphs, with every node tagged with its chapter (page) number.
+
+use crate::types::{ContentLayer, DocumentNode, DocumentStructure, NodeContent, NodeId, NodeIndex};
+
+use super::content::Chapter;
+use super::toc::TocEntry;
+
+/// Chapter index each TOC entry belongs to.
+///
+/// Entries without a resolvable target stay with the chapter of the preceding entry.
+pub(super) fn toc_anchors(chapters: &[Chapter], toc: &[TocEntry]) -> Vec<usize> {
+    let mut current = 0;
+    toc.iter()
+        .map(|entry| {
+            if let Some(index) = entry
+                .href
+                .as_deref()
+                .and_then(|href| chapters.iter().position(|c| c.href == href))
+            {
+                current = index;
+            }
+            current
+        })
+        .collect()
+}
+
+/// Build a `DocumentStructure` from chapters and the table of contents.
+pub(super) fn build_document_structure(chapters: &[Chapter], toc: &[TocEntry]) -> DocumentStructure {
+    let anchors = toc_anchors(chapters, toc);
+    let paragraph_count: usize = chapters.iter().map(|c| c.text.lines().count()).sum();
+    let mut structure = DocumentStructure::with_capacity(paragraph_count + toc.len() * 2);
+    let mut section_stack: Vec<(u8, NodeIndex)> = Vec::new();
+    let mut node_count: u32 = 0;
+
+    let current_parent = |stack: &[(u8, NodeIndex)]| -> Option<NodeIndex> { stack.last().map(|(_, idx)| *idx) };
+
+    for (chapter_index, chapter) in chapters.iter().enumerate() {
+        let page = Some((chapter_index + 1) as u32);
+
+        for (entry, _) in toc.iter().zip(&anchors).filter(|(_, anchor)| **anchor == chapter_index) {
+            let level = entry.level.min(6);
+            while section_stack.last().is_some_and(|(l, _)| *l >= level) {
+                section_stack.pop();
+            }
+
+            let group = DocumentNode {
+                id: NodeId::generate("group", &entry.title, page, node_count),
+                content: NodeContent::Group {
+                    label: None,
+                    heading_level: Some(level),
+                    heading_text: Some(entry.title.clone()),
+                },
+                parent: current_parent(&section_stack),
+                children: Vec::new(),
+                content_layer: ContentLayer::Body,
+                page,
+                page_end: None,
+                bbox: None,
+                annotations: Vec::new(),
+            };
+            node_count += 1;
+            let group_idx = structure.push_node(group);
+            if let Some(parent_idx) = current_parent(&section_stack) {
+                structure.add_child(parent_idx, group_idx);
+            }
+
+            let heading = DocumentNode {
+                id: NodeId::generate("heading", &entry.title, page, node_count),
+                content: NodeContent::Heading {
+                    level,
+                    text: entry.title.clone(),
+                },
+                parent: Some(group_idx),
+                children: Vec::new(),
+                content_layer: ContentLayer::Body,
+                page,
+                page_end: None,
+                bbox: None,
+                annotations: Vec::new(),
+            };
+            node_count += 1;
+            let heading_idx = structure.push_node(heading);
+            structure.add_child(group_idx, heading_idx);
+
+            section_stack.push((level, group_idx));
+        }
+
+        for line in chapter.text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            let node = DocumentNode {
+                id: NodeId::generate("paragraph", line, page, node_count),
+                content: NodeContent::Paragraph { text: line.to_string() },
+                parent: current_parent(&section_stack),
+                children: Vec::new(),
+                content_layer: ContentLayer::Body,
+                page,
+                page_end: None,
+                bbox: None,
+                annotations: Vec::new(),
+            };
+            node_count += 1;
+            let idx = structure.push_node(node);
+            if let Some(parent_idx) = current_parent(&section_stack) {
+                structure.add_child(parent_idx, idx);
+            }
+        }
+    }
+
+    structure
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chapter(href: &str, text: &str) -> Chapter {
+        Chapter {
+            href: href.to_string(),
+            text: text.to_string(),
+        }
+    }
+
+    fn entry(title: &str, href: Option<&str>, level: u8) -> TocEntry {
+        TocEntry {
+            title: title.to_string(),
+            href: href.map(str::to_string),
+            level,
+        }
+    }
+
+    #[test]
+    fn test_build_document_structure_nests_toc_headings() {
+        let chapters = vec![chapter("a.xhtml", "Intro text"), chapter("b.xhtml", "Body\nMore body")];
+        let toc = vec![
+            entry("Part One", None, 1),
+            entry("Introduction", Some("a.xhtml"), 2),
+            entry("Chapter B", Some("b.xhtml"), 2),
+        ];
+
+        let structure = build_document_structure(&chapters, &toc);
+        let headings: Vec<_> = structure
+            .nodes
+            .iter()
+            .filter_map(|n| match &n.content {
+                NodeContent::Heading { level, text } => Some((*level, text.as_str(), n.page)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            headings,
+            vec![
+                (1, "Part One", Some(1)),
+                (2, "Introduction", Some(1)),
+                (2, "Chapter B", Some(2))
+            ]
+        );
+
+        let paragraphs: Vec<_> = structure
+            .nodes
+            .iter()
+            .filter(|n| matches!(n.content, NodeContent::Paragraph { .. }))
+            .collect();
+        assert_eq!(paragraphs.len(), 3);
+        assert!(paragraphs.iter().all(|p| p.parent.is_some()));
+        assert_eq!(paragraphs[2].page, Some(2));
+    }
+
+    #[test]
+    fn test_toc_anchors_unmatched_entries_follow_previous() {
+        let chapters = vec![chapter("a.xhtml", "A"), chapter("b.xhtml", "B")];
+        let toc = vec![
+            entry("B", Some("b.xhtml"), 1),
+            entry("External", Some("missing.xhtml"), 1),
+        ];
+        assert_eq!(toc_anchors(&chapters, &toc), vec![1, 1]);
+    }
+}
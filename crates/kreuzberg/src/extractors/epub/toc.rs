@@ -0,0 +1,206 @@
+//! Table of contents parsing for EPUB navigation documents.
+//!
+//! EPUB3 books carry an XHTML navigation document (`<nav epub:type="toc">`),
+//! EPUB2 books an NCX file (`<navMap>`). Both are flattened into a list of
+//! entries in reading order with their nesting depth.
+
+use roxmltree::{Document, Node, ParsingOptions};
+
+use super::parsing::{parent_dir, resolve_path};
+
+/// A single table of contents entry.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct TocEntry {
+    /// Entry label as shown in the book's table of contents.
+    pub(super) title: String,
+    /// Archive path of the target document, without fragment.
+    pub(super) href: Option<String>,
+    /// Nesting depth, starting at 1 for top-level entries.
+    pub(super) level: u8,
+}
+
+fn parse_document(xml: &str) -> Option<Document<'_>> {
+    let options = ParsingOptions {
+        allow_dtd: true,
+        ..Default::default()
+    };
+    Document::parse_with_options(xml, options).ok()
+}
+
+/// Resolve a TOC link relative to the navigation document, dropping the fragment.
+fn resolve_target(toc_path: &str, target: &str) -> Option<String> {
+    let path = target.split('#').next().unwrap_or_default();
+    if path.is_empty() {
+        // Fragment-only links point into the navigation document itself
+        return (!target.is_empty()).then(|| toc_path.to_string());
+    }
+    if path.contains("://") {
+        return None;
+    }
+    Some(resolve_path(parent_dir(toc_path), path))
+}
+
+/// Concatenate the text below a node, collapsing whitespace.
+fn node_text(node: Node<'_, '_>) -> String {
+    let text: String = node
+        .descendants()
+        .filter(|n| n.is_text())
+        .filter_map(|n| n.text())
+        .collect();
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn is_element(node: &Node<'_, '_>, name: &str) -> bool {
+    node.is_element() && node.tag_name().name().eq_ignore_ascii_case(name)
+}
+
+/// Parse an EPUB3 XHTML navigation document located at `nav_path` in the archive.
+pub(super) fn parse_nav(xml: &str, nav_path: &str) -> Vec<TocEntry> {
+    let Some(doc) = parse_document(xml) else {
+        return Vec::new();
+    };
+
+    let navs: Vec<Node> = doc.descendants().filter(|n| is_element(n, "nav")).collect();
+    let toc_nav = navs
+        .iter()
+        .find(|nav| {
+            nav.attributes()
+                .any(|attr| attr.name() == "type" && attr.value().split_whitespace().any(|v| v == "toc"))
+        })
+        .or_else(|| navs.first());
+
+    let mut entries = Vec::new();
+    if let Some(list) = toc_nav.and_then(|nav| nav.descendants().find(|n| is_element(n, "ol"))) {
+        collect_nav_list(list, 1, nav_path, &mut entries);
+    }
+    entries
+}
+
+fn collect_nav_list(list: Node<'_, '_>, level: u8, nav_path: &str, entries: &mut Vec<TocEntry>) {
+    for item in list.children().filter(|n| is_element(n, "li")) {
+        if let Some(label) = item.children().find(|n| is_element(n, "a") || is_element(n, "span")) {
+            let title = node_text(label);
+            if !title.is_empty() {
+                entries.push(TocEntry {
+                    title,
+                    href: label.attribute("href").and_then(|href| resolve_target(nav_path, href)),
+                    level,
+                });
+            }
+        }
+        for sublist in item.children().filter(|n| is_element(n, "ol")) {
+            collect_nav_list(sublist, level.saturating_add(1), nav_path, entries);
+        }
+    }
+}
+
+/// Parse an EPUB2 NCX document located at `ncx_path` in the archive.
+pub(super) fn parse_ncx(xml: &str, ncx_path: &str) -> Vec<TocEntry> {
+    let Some(doc) = parse_document(xml) else {
+        return Vec::new();
+    };
+
+    let mut entries = Vec::new();
+    if let Some(nav_map) = doc.descendants().find(|n| is_element(n, "navMap")) {
+        collect_nav_points(nav_map, 1, ncx_path, &mut entries);
+    }
+    entries
+}
+
+fn collect_nav_points(parent: Node<'_, '_>, level: u8, ncx_path: &str, entries: &mut Vec<TocEntry>) {
+    for point in parent.children().filter(|n| is_element(n, "navPoint")) {
+        let title = point
+            .children()
+            .find(|n| is_element(n, "navLabel"))
+            .map(node_text)
+            .unwrap_or_default();
+        if !title.is_empty() {
+            let href = point
+                .children()
+                .find(|n| is_element(n, "content"))
+                .and_then(|content| content.attribute("src"))
+                .and_then(|src| resolve_target(ncx_path, src));
+            entries.push(TocEntry { title, href, level });
+        }
+        collect_nav_points(point, level.saturating_add(1), ncx_path, entries);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_nav_nested() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<html xmlns="http://www.w3.org/1999/xhtml" xmlns:epub="http://www.idpf.org/2007/ops">
+<body>
+  <nav epub:type="landmarks"><ol><li><a href="cover.xhtml">Cover</a></li></ol></nav>
+  <nav epub:type="toc">
+    <ol>
+      <li><a href="text/ch1.xhtml">Chapter  One</a>
+        <ol><li><a href="text/ch1.xhtml#s1">Section 1.1</a></li></ol>
+      </li>
+      <li><span>Part II</span>
+        <ol><li><a href="text/ch2.xhtml">Chapter Two</a></li></ol>
+      </li>
+    </ol>
+  </nav>
+</body>
+</html>"#;
+
+        let entries = parse_nav(xml, "OEBPS/nav.xhtml");
+        assert_eq!(entries.len(), 4);
+        assert_eq!(entries[0].title, "Chapter One");
+        assert_eq!(entries[0].href.as_deref(), Some("OEBPS/text/ch1.xhtml"));
+        assert_eq!(entries[0].level, 1);
+        assert_eq!(entries[1].href.as_deref(), Some("OEBPS/text/ch1.xhtml"));
+        assert_eq!(entries[1].level, 2);
+        assert_eq!(entries[2].title, "Part II");
+        assert_eq!(entries[2].href, None);
+        assert_eq!(entries[3].level, 2);
+    }
+
+    #[test]
+    fn test_parse_ncx_nested() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE ncx PUBLIC "-//NISO//DTD ncx 2005-1//EN" "http://www.daisy.org/z3986/2005/ncx-2005-1.dtd">
+<ncx xmlns="http://www.daisy.org/z3986/2005/ncx/" version="2005-1">
+  <docTitle><text>Book</text></docTitle>
+  <navMap>
+    <navPoint id="n1" playOrder="1">
+      <navLabel><text>Introduction</text></navLabel>
+      <content src="intro.xhtml"/>
+      <navPoint id="n2" playOrder="2">
+        <navLabel><text>Background</text></navLabel>
+        <content src="intro.xhtml#bg"/>
+      </navPoint>
+    </navPoint>
+    <navPoint id="n3" playOrder="3">
+      <navLabel><text>Appendix</text></navLabel>
+      <content src="../extra/appendix.xhtml"/>
+    </navPoint>
+  </navMap>
+</ncx>"#;
+
+        let entries = parse_ncx(xml, "OEBPS/toc.ncx");
+        let summary: Vec<_> = entries
+            .iter()
+            .map(|e| (e.title.as_str(), e.href.as_deref(), e.level))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Introduction", Some("OEBPS/intro.xhtml"), 1),
+                ("Background", Some("OEBPS/intro.xhtml"), 2),
+                ("Appendix", Some("extra/appendix.xhtml"), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_toc_invalid_xml() {
+        assert!(parse_nav("<nav><ol>", "nav.xhtml").is_empty());
+        assert!(parse_ncx("not xml", "toc.ncx").is_empty());
+    }
+}
@@ -89,7 +89,7 @@ impl SyncExtractor for ExcelExtractor {
             _ => ".xlsx",
         };
 
-        let workbook = crate::extraction::excel::read_excel_bytes(content, extension)?;
+        let workbook = crate::extraction::excel::read_excel_bytes_with_config(content, extension, config)?;
         let content_text = match config.output_format {
             OutputFormat::Markdown | OutputFormat::Djot | OutputFormat::Html => {
                 crate::extraction::excel::excel_to_markdown(&workbook)
@@ -171,22 +171,27 @@ impl DocumentExtractor for ExcelExtractor {
                 if crate::core::batch_mode::is_batch_mode() {
                     let content_owned = content.to_vec();
                     let extension_owned = extension.to_string();
+                    let config_owned = config.clone();
                     let span = tracing::Span::current();
                     tokio::task::spawn_blocking(move || {
                         let _guard = span.entered();
-                        crate::extraction::excel::read_excel_bytes(&content_owned, &extension_owned)
+                        crate::extraction::excel::read_excel_bytes_with_config(
+                            &content_owned,
+                            &extension_owned,
+                            &config_owned,
+                        )
                     })
                     .await
                     .map_err(|e| {
                         crate::error::KreuzbergError::parsing(format!("Excel extraction task failed: {}", e))
                     })??
                 } else {
-                    crate::extraction::excel::read_excel_bytes(content, extension)?
+                    crate::extraction::excel::read_excel_bytes_with_config(content, extension, config)?
                 }
             }
             #[cfg(not(feature = "tokio-runtime"))]
             {
-                crate::extraction::excel::read_excel_bytes(content, extension)?
+                crate::extraction::excel::read_excel_bytes_with_config(content, extension, config)?
             }
         };
 
@@ -247,7 +252,7 @@ impl DocumentExtractor for ExcelExtractor {
             .to_str()
             .ok_or_else(|| crate::KreuzbergError::validation("Invalid file path".to_string()))?;
 
-        let workbook = crate::extraction::excel::read_excel_file(path_str)?;
+        let workbook = crate::extraction::excel::read_excel_file_with_config(path_str, config)?;
         let content = match config.output_format {
             OutputFormat::Markdown | OutputFormat::Djot | OutputFormat::Html => {
                 crate::extraction::excel::excel_to_markdown(&workbook)
@@ -360,6 +365,7 @@ mod tests {
                 vec!["Alice".to_string(), "30".to_string(), "NYC".to_string()],
                 vec!["Bob".to_string(), "25".to_string(), "LA".to_string()],
             ]),
+            drawing_text: None,
         };
 
         let workbook = crate::types::ExcelWorkbook {
@@ -389,6 +395,7 @@ mod tests {
             col_count: 0,
             cell_count: 0,
             table_cells: None,
+            drawing_text: None,
         };
 
         let workbook = crate::types::ExcelWorkbook {
@@ -421,6 +428,7 @@ mod tests {
                 vec!["Col1".to_string(), "Col2".to_string()],
                 vec!["A".to_string(), "B".to_string()],
             ]),
+            drawing_text: None,
         };
 
         let sheet2 = ExcelSheet {
@@ -439,6 +447,7 @@ mod tests {
                 vec!["X".to_string(), "Y".to_string()],
                 vec!["1".to_string(), "2".to_string()],
             ]),
+            drawing_text: None,
         };
 
         let workbook = crate::types::ExcelWorkbook {
@@ -476,6 +485,7 @@ mod tests {
                 vec!["Item|A".to_string(), "100".to_string(), "$1,000".to_string()],
                 vec!["Item B".to_string(), "200".to_string(), "$2,000".to_string()],
             ]),
+            drawing_text: None,
         };
 
         let workbook = crate::types::ExcelWorkbook {
@@ -28,15 +28,13 @@ impl ImageExtractor {
         mime_type: &str,
         config: &ExtractionConfig,
     ) -> Result<ExtractionResult> {
-        use crate::plugins::registry::get_ocr_backend_registry;
-
         let ocr_config = config.ocr.as_ref().ok_or_else(|| crate::KreuzbergError::Parsing {
             message: "OCR config required for image OCR".to_string(),
             source: None,
         })?;
 
         let backend = {
-            let registry = get_ocr_backend_registry();
+            let registry = crate::engine::current().ocr_backend_registry();
             let registry = registry.read().map_err(|e| crate::KreuzbergError::Plugin {
                 message: format!("Failed to acquire read lock on OCR backend registry: {}", e),
                 plugin_name: "ocr-registry".to_string(),
@@ -49,6 +47,8 @@ impl ImageExtractor {
         ocr_config_with_format.output_format = Some(config.output_format);
 
         let ocr_result = backend.process_image(content, &ocr_config_with_format).await?;
+        #[cfg(feature = "metrics")]
+        crate::metrics::global().record_ocr_pages(backend.name(), 1);
 
         // Full OCR with TIFF multi-frame support (requires tiff crate)
         #[cfg(feature = "ocr")]
@@ -0,0 +1,223 @@
+#![cfg(feature = "office")]
+
+//! Apple iWork extractor.
+//!
+//! Supports: Pages (.pages), Numbers (.numbers), Keynote (.key)
+
+use crate::Result;
+use crate::core::config::ExtractionConfig;
+use crate::extraction::iwork::{
+    IworkExtraction, IworkKind, KEYNOTE_MIME_TYPE, NUMBERS_MIME_TYPE, PAGES_MIME_TYPE, extract_iwork_from_bytes,
+};
+use crate::plugins::{DocumentExtractor, Plugin};
+use crate::types::{ExtractionResult, Metadata};
+use ahash::AHashMap;
+use async_trait::async_trait;
+use std::borrow::Cow;
+
+/// Native Apple iWork extractor.
+///
+/// Decodes the IWA archives inside Pages, Numbers and Keynote bundles:
+/// - Body text and text boxes
+/// - Numbers tables (also tables embedded in Pages and Keynote)
+/// - Keynote slides as pages, with speaker notes
+///
+/// Bundles whose IWA content cannot be decoded fall back to the embedded
+/// preview PDF when the `pdf` feature is enabled.
+pub struct IworkExtractor;
+
+impl IworkExtractor {
+    /// Create a new iWork extractor.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for IworkExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Plugin for IworkExtractor {
+    fn name(&self) -> &str {
+        "iwork-extractor"
+    }
+
+    fn version(&self) -> String {
+        env!("CARGO_PKG_VERSION").to_string()
+    }
+
+    fn initialize(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn shutdown(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn description(&self) -> &str {
+        "Native Rust Apple iWork (Pages, Numbers, Keynote) extractor with table and slide support"
+    }
+
+    fn author(&self) -> &str {
+        "Kreuzberg Team"
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl DocumentExtractor for IworkExtractor {
+    #[cfg_attr(feature = "otel", tracing::instrument(
+        skip(self, content, config),
+        fields(
+            extractor.name = self.name(),
+            content.size_bytes = content.len(),
+        )
+    ))]
+    async fn extract_bytes(
+        &self,
+        content: &[u8],
+        mime_type: &str,
+        config: &ExtractionConfig,
+    ) -> Result<ExtractionResult> {
+        let kind = IworkKind::from_mime_type(mime_type).unwrap_or(IworkKind::Pages);
+
+        let extraction = {
+            #[cfg(feature = "tokio-runtime")]
+            {
+                if crate::core::batch_mode::is_batch_mode() {
+                    let content_owned = content.to_vec();
+                    let pages_config = config.pages.clone();
+                    let span = tracing::Span::current();
+                    tokio::task::spawn_blocking(move || {
+                        let _guard = span.entered();
+                        extract_iwork_from_bytes(&content_owned, kind, pages_config.as_ref())
+                    })
+                    .await
+                    .map_err(|e| {
+                        crate::error::KreuzbergError::parsing(format!("iWork extraction task failed: {}", e))
+                    })??
+                } else {
+                    extract_iwork_from_bytes(content, kind, config.pages.as_ref())?
+                }
+            }
+
+            #[cfg(not(feature = "tokio-runtime"))]
+            {
+                extract_iwork_from_bytes(content, kind, config.pages.as_ref())?
+            }
+        };
+
+        let result = match extraction {
+            IworkExtraction::Native(result) => result,
+            IworkExtraction::PreviewPdf(preview) => return extract_preview_pdf(&preview, mime_type, config).await,
+        };
+
+        let mut additional: AHashMap<Cow<'static, str>, serde_json::Value> = AHashMap::new();
+        additional.insert(Cow::Borrowed("table_count"), serde_json::json!(result.tables.len()));
+        if result.slide_count > 0 {
+            additional.insert(Cow::Borrowed("slide_count"), serde_json::json!(result.slide_count));
+        }
+
+        Ok(ExtractionResult {
+            content: result.content,
+            mime_type: mime_type.to_string().into(),
+            metadata: Metadata {
+                pages: result.page_structure,
+                additional,
+                ..Default::default()
+            },
+            pages: result.page_contents,
+            tables: result.tables,
+            detected_languages: None,
+            chunks: None,
+            images: None,
+            djot_content: None,
+            elements: None,
+            ocr_elements: None,
+            document: None,
+            #[cfg(any(feature = "keywords-yake", feature = "keywords-rake"))]
+            extracted_keywords: None,
+            quality_score: None,
+            processing_warnings: Vec::new(),
+            annotations: None,
+        })
+    }
+
+    fn supported_mime_types(&self) -> &[&str] {
+        &[
+            PAGES_MIME_TYPE,
+            NUMBERS_MIME_TYPE,
+            KEYNOTE_MIME_TYPE,
+            "application/x-iwork-pages-sffpages",
+            "application/x-iwork-numbers-sffnumbers",
+            "application/x-iwork-keynote-sffkey",
+        ]
+    }
+
+    fn priority(&self) -> i32 {
+        50
+    }
+}
+
+/// Extract the bundle's preview PDF in place of the undecodable IWA content.
+#[cfg(feature = "pdf")]
+async fn extract_preview_pdf(preview: &[u8], mime_type: &str, config: &ExtractionConfig) -> Result<ExtractionResult> {
+    let mut result = crate::extractors::pdf::PdfExtractor::new()
+        .extract_bytes(preview, "application/pdf", config)
+        .await?;
+    result.mime_type = mime_type.to_string().into();
+    result
+        .metadata
+        .additional
+        .insert(Cow::Borrowed("extracted_from_preview"), serde_json::json!(true));
+    Ok(result)
+}
+
+#[cfg(not(feature = "pdf"))]
+async fn extract_preview_pdf(
+    _preview: &[u8],
+    _mime_type: &str,
+    _config: &ExtractionConfig,
+) -> Result<ExtractionResult> {
+    Err(crate::error::KreuzbergError::UnsupportedFormat(
+        "iWork document content could not be decoded; the preview PDF fallback requires the 'pdf' feature".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iwork_extractor_plugin_interface() {
+        let extractor = IworkExtractor::new();
+        assert_eq!(extractor.name(), "iwork-extractor");
+        assert_eq!(extractor.priority(), 50);
+        assert!(extractor.initialize().is_ok());
+        assert!(extractor.shutdown().is_ok());
+    }
+
+    #[test]
+    fn test_iwork_extractor_supported_mime_types() {
+        let extractor = IworkExtractor::new();
+        let mime_types = extractor.supported_mime_types();
+        assert!(mime_types.contains(&"application/vnd.apple.pages"));
+        assert!(mime_types.contains(&"application/vnd.apple.numbers"));
+        assert!(mime_types.contains(&"application/vnd.apple.keynote"));
+    }
+
+    #[tokio::test]
+    async fn test_iwork_extractor_invalid_bundle() {
+        let extractor = IworkExtractor::new();
+        let result = extractor
+            .extract_bytes(
+                b"not a zip",
+                "application/vnd.apple.pages",
+                &ExtractionConfig::default(),
+            )
+            .await;
+        assert!(result.is_err());
+    }
+}
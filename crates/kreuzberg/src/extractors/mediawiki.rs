@@ -0,0 +1,794 @@
+//! Native Rust MediaWiki (wikitext) extractor.
+//!
+//! This extractor parses wikitext line by line.
+//! It extracts:
+//! - Headings (== Heading ==)
+//! - Paragraphs with links, bold/italic quotes and HTML tags removed
+//! - Lists (* bullet, # numbered, ; term : definition)
+//! - Tables ({| ... |} with ! header and | data cells)
+//! - Code (<syntaxhighlight>, <source>, <pre> and space-indented lines)
+//! - Block quotes and admonition templates ({{Note|...}}, {{Warning|...}})
+//! - Metadata: {{DISPLAYTITLE:...}}, categories and redirects
+//!
+//! Other templates, references and comments are dropped.
+
+#[cfg(feature = "office")]
+use crate::Result;
+#[cfg(feature = "office")]
+use crate::core::config::ExtractionConfig;
+#[cfg(feature = "office")]
+use crate::extraction::markup::{self, MarkupBlock};
+#[cfg(feature = "office")]
+use crate::plugins::{DocumentExtractor, Plugin};
+#[cfg(feature = "office")]
+use crate::types::{ExtractionResult, Metadata};
+#[cfg(feature = "office")]
+use ahash::AHashMap;
+#[cfg(feature = "office")]
+use async_trait::async_trait;
+#[cfg(feature = "office")]
+use once_cell::sync::Lazy;
+#[cfg(feature = "office")]
+use regex::Regex;
+#[cfg(feature = "office")]
+use std::borrow::Cow;
+
+#[cfg(feature = "office")]
+static COMMENT: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)<!--.*?-->").expect("wikitext comment regex is valid"));
+#[cfg(feature = "office")]
+static REFERENCE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?is)<ref\b[^>/]*/>|<ref\b[^>]*>.*?</ref>").expect("wikitext reference regex is valid"));
+#[cfg(feature = "office")]
+static HEADING: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(={1,6})\s*(.+?)\s*(={1,6})\s*$").expect("wikitext heading regex is valid"));
+#[cfg(feature = "office")]
+static CATEGORY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\[\[\s*Category\s*:\s*([^|\]]+?)\s*(?:\|[^\]]*)?\]\]").expect("wikitext category regex is valid")
+});
+#[cfg(feature = "office")]
+static REDIRECT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^#REDIRECT\s*\[\[([^|\]]+)(?:\|[^\]]*)?\]\]").expect("wikitext redirect regex is valid")
+});
+#[cfg(feature = "office")]
+static CODE_OPEN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?i)^\s*<(syntaxhighlight|source|pre)\b([^>]*)>"#).expect("wikitext code regex is valid")
+});
+#[cfg(feature = "office")]
+static LANG_ATTRIBUTE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(?i)\blang\s*=\s*"?([\w+#-]+)"?"#).expect("wikitext lang regex is valid"));
+#[cfg(feature = "office")]
+static BLOCKQUOTE_OPEN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?i)^\s*<blockquote\b(?:[^>]*\bdata-label="([^"]*)")?[^>]*>"#).expect("wikitext quote regex is valid")
+});
+#[cfg(feature = "office")]
+static INTERNAL_LINK: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\[\[:?([^|\]]*)(?:\|([^\]]*))?\]\]").expect("wikitext link regex is valid"));
+#[cfg(feature = "office")]
+static EXTERNAL_LINK: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\[((?:https?|ftp|mailto|news|irc)://[^\s\]]+|mailto:[^\s\]]+)(?:\s+([^\]]*))?\]")
+        .expect("wikitext external link regex is valid")
+});
+#[cfg(feature = "office")]
+static HTML_TAG: Lazy<Regex> = Lazy::new(|| Regex::new(r"</?[a-zA-Z][^>]*>").expect("HTML tag regex is valid"));
+#[cfg(feature = "office")]
+static MAGIC_WORD: Lazy<Regex> = Lazy::new(|| Regex::new(r"__[A-Z]+__").expect("wikitext magic word regex is valid"));
+#[cfg(feature = "office")]
+static WHITESPACE: Lazy<Regex> = Lazy::new(|| Regex::new(r"[ \t]{2,}").expect("whitespace regex is valid"));
+
+/// Tags whose content is copied verbatim while expanding templates.
+#[cfg(feature = "office")]
+const VERBATIM_TAGS: [&str; 5] = ["syntaxhighlight", "source", "pre", "nowiki", "math"];
+
+/// Page-level metadata collected while parsing.
+#[cfg(feature = "office")]
+#[derive(Debug, Default)]
+struct WikiMetadata {
+    title: Option<String>,
+    categories: Vec<String>,
+    redirect: Option<String>,
+}
+
+/// Native Rust MediaWiki extractor.
+///
+/// Parses wikitext and extracts:
+/// - Headings, paragraphs, lists, tables, code blocks and quotes
+/// - Display title, categories and redirect target as metadata
+/// - A `DocumentStructure` when requested
+#[cfg(feature = "office")]
+pub struct MediaWikiExtractor;
+
+#[cfg(feature = "office")]
+impl MediaWikiExtractor {
+    /// Create a new MediaWiki extractor.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Parse wikitext into page metadata and blocks.
+    fn parse(content: &str) -> (WikiMetadata, Vec<MarkupBlock>) {
+        let mut meta = WikiMetadata::default();
+
+        let text = COMMENT.replace_all(content, "");
+        let text = REFERENCE.replace_all(&text, "");
+        let text = Self::expand_templates(&text, &mut meta);
+        let text = Self::remove_media_links(&text);
+        let text = CATEGORY.replace_all(&text, |caps: &regex::Captures| {
+            meta.categories.push(caps[1].to_string());
+            ""
+        });
+
+        let lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+        let blocks = Self::parse_blocks(&lines, &mut meta);
+        (meta, blocks)
+    }
+
+    /// Find the end of a `{{ }}` or `[[ ]]` construct starting at `start`.
+    fn matching_close(text: &str, start: usize, open: &str, close: &str) -> Option<usize> {
+        let mut depth = 0usize;
+        let mut pos = start;
+        while pos < text.len() {
+            let rest = &text[pos..];
+            if rest.starts_with(open) {
+                depth += 1;
+                pos += open.len();
+            } else if rest.starts_with(close) {
+                depth -= 1;
+                pos += close.len();
+                if depth == 0 {
+                    return Some(pos);
+                }
+            } else {
+                pos += rest.chars().next().map_or(1, char::len_utf8);
+            }
+        }
+        None
+    }
+
+    /// Split template or link arguments on `|` outside nested constructs.
+    fn split_arguments(inner: &str) -> Vec<&str> {
+        let mut parts = Vec::new();
+        let mut depth = 0i32;
+        let mut last = 0;
+        let bytes = inner.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'{' | b'[' => depth += 1,
+                b'}' | b']' => depth -= 1,
+                b'|' if depth <= 0 => {
+                    parts.push(&inner[last..i]);
+                    last = i + 1;
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+        parts.push(&inner[last..]);
+        parts
+    }
+
+    /// Expand templates: admonition and quote templates become `<blockquote>`
+    /// lines, `DISPLAYTITLE` sets the title and everything else is dropped.
+    fn expand_templates(text: &str, meta: &mut WikiMetadata) -> String {
+        let lower = text.to_ascii_lowercase();
+        let mut out = String::with_capacity(text.len());
+        let mut pos = 0;
+
+        while pos < text.len() {
+            let rest = &text[pos..];
+
+            if rest.starts_with('<')
+                && let Some(tag) = VERBATIM_TAGS.iter().find(|tag| lower[pos + 1..].starts_with(*tag))
+            {
+                let closing = format!("</{}>", tag);
+                let end = lower[pos..]
+                    .find(&closing)
+                    .map_or(text.len(), |i| pos + i + closing.len());
+                out.push_str(&text[pos..end]);
+                pos = end;
+                continue;
+            }
+
+            if rest.starts_with("{{") {
+                let Some(end) = Self::matching_close(text, pos, "{{", "}}") else {
+                    out.push_str(rest);
+                    break;
+                };
+                let inner = &text[pos + 2..end - 2];
+                let args = Self::split_arguments(inner);
+                let name = args[0].trim();
+                let name_lower = name.to_lowercase();
+
+                if let Some(title) = name_lower
+                    .strip_prefix("displaytitle:")
+                    .map(|_| name["displaytitle:".len()..].trim())
+                {
+                    meta.title = Some(Self::convert_inline(title));
+                } else {
+                    let label = match name_lower.as_str() {
+                        "note" | "notice" | "info" => Some(Some("NOTE")),
+                        "tip" | "hint" => Some(Some("TIP")),
+                        "important" => Some(Some("IMPORTANT")),
+                        "warning" => Some(Some("WARNING")),
+                        "caution" => Some(Some("CAUTION")),
+                        "quote" | "quotation" | "blockquote" | "cquote" => Some(None),
+                        _ => None,
+                    };
+                    if let Some(label) = label {
+                        let body = args[1..]
+                            .iter()
+                            .filter_map(|arg| match arg.split_once('=') {
+                                Some((key, value)) if matches!(key.trim(), "1" | "text" | "content" | "quote") => {
+                                    Some(value)
+                                }
+                                Some(_) => None,
+                                None => Some(*arg),
+                            })
+                            .next()
+                            .unwrap_or("");
+                        let body = Self::expand_templates(body, meta).replace('\n', " ");
+                        let attribute = label.map(|l| format!(" data-label=\"{}\"", l)).unwrap_or_default();
+                        out.push_str(&format!("\n<blockquote{}>{}</blockquote>\n", attribute, body.trim()));
+                    }
+                }
+                pos = end;
+                continue;
+            }
+
+            let ch = rest.chars().next().unwrap_or(' ');
+            out.push(ch);
+            pos += ch.len_utf8();
+        }
+
+        out
+    }
+
+    /// Remove `[[File:...]]`, `[[Image:...]]` and `[[Media:...]]` links (captions may nest links).
+    fn remove_media_links(text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut pos = 0;
+        while let Some(rel) = text[pos..].find("[[") {
+            let start = pos + rel;
+            out.push_str(&text[pos..start]);
+            let target = text[start + 2..].trim_start().to_ascii_lowercase();
+            let is_media = ["file:", "image:", "media:"].iter().any(|p| target.starts_with(p));
+            match Self::matching_close(text, start, "[[", "]]") {
+                Some(end) if is_media => pos = end,
+                _ => {
+                    out.push_str("[[");
+                    pos = start + 2;
+                }
+            }
+        }
+        out.push_str(&text[pos..]);
+        out
+    }
+
+    fn parse_blocks(lines: &[&str], meta: &mut WikiMetadata) -> Vec<MarkupBlock> {
+        let mut blocks = Vec::new();
+        let mut i = 0;
+
+        while i < lines.len() {
+            let line = lines[i];
+            let trimmed = line.trim();
+
+            if trimmed.is_empty() || trimmed.chars().all(|c| c == '-') && trimmed.len() >= 4 {
+                i += 1;
+                continue;
+            }
+
+            if let Some(caps) = REDIRECT.captures(trimmed) {
+                meta.redirect = Some(caps[1].trim().to_string());
+                i += 1;
+                continue;
+            }
+
+            if let Some(caps) = HEADING.captures(trimmed)
+                && caps[1].len() == caps[3].len()
+            {
+                blocks.push(MarkupBlock::Heading {
+                    level: caps[1].len() as u8,
+                    text: Self::convert_inline(&caps[2]),
+                });
+                i += 1;
+                continue;
+            }
+
+            if trimmed.starts_with("{|") {
+                let (rows, next) = Self::parse_table(lines, i);
+                if !rows.is_empty() {
+                    blocks.push(MarkupBlock::Table(rows));
+                }
+                i = next;
+                continue;
+            }
+
+            if let Some(caps) = CODE_OPEN.captures(line) {
+                let tag = caps[1].to_lowercase();
+                let language = LANG_ATTRIBUTE.captures(&caps[2]).map(|c| c[1].to_string());
+                let closing = format!("</{}>", tag);
+                let mut body = line[caps.get(0).map_or(0, |m| m.end())..].to_string();
+                while !body.to_lowercase().contains(&closing) && i + 1 < lines.len() {
+                    i += 1;
+                    body.push('\n');
+                    body.push_str(lines[i]);
+                }
+                i += 1;
+                let end = body.to_lowercase().find(&closing).unwrap_or(body.len());
+                let code = body[..end].trim_matches('\n').to_string();
+                blocks.push(MarkupBlock::Code {
+                    language,
+                    text: if tag == "pre" {
+                        HTML_TAG.replace_all(&code, "").into_owned()
+                    } else {
+                        code
+                    },
+                });
+                continue;
+            }
+
+            if let Some(caps) = BLOCKQUOTE_OPEN.captures(line) {
+                let label = caps.get(1).map(|m| m.as_str().to_string());
+                let mut body = line[caps.get(0).map_or(0, |m| m.end())..].to_string();
+                while !body.to_lowercase().contains("</blockquote>") && i + 1 < lines.len() {
+                    i += 1;
+                    body.push('\n');
+                    body.push_str(lines[i]);
+                }
+                i += 1;
+                let end = body.to_lowercase().find("</blockquote>").unwrap_or(body.len());
+                let paragraphs = body[..end]
+                    .split("\n\n")
+                    .map(|p| Self::convert_inline(&p.replace('\n', " ")))
+                    .filter(|p| !p.is_empty())
+                    .collect();
+                blocks.push(MarkupBlock::Quote { label, paragraphs });
+                continue;
+            }
+
+            if trimmed.starts_with(['*', '#', ';']) {
+                let ordered = trimmed.starts_with('#');
+                let mut items: Vec<String> = Vec::new();
+                while i < lines.len() && lines[i].trim_start().starts_with(['*', '#', ';', ':']) {
+                    let item = lines[i].trim_start();
+                    let marker_len = item.find(|c| !matches!(c, '*' | '#' | ';' | ':')).unwrap_or(item.len());
+                    let (marker, text) = item.split_at(marker_len);
+                    let text = if marker.ends_with(';') {
+                        match text.split_once(" : ") {
+                            Some((term, definition)) => format!("{}: {}", term.trim(), definition.trim()),
+                            None => text.trim().to_string(),
+                        }
+                    } else {
+                        text.trim().to_string()
+                    };
+                    let text = Self::convert_inline(&text);
+
+                    match items.last_mut() {
+                        // A ":" line continues the preceding item (definitions, indented replies)
+                        Some(last) if marker.ends_with(':') => {
+                            last.push_str(if last.ends_with(':') { " " } else { ": " });
+                            last.push_str(&text);
+                        }
+                        _ if !text.is_empty() => items.push(text),
+                        _ => {}
+                    }
+                    i += 1;
+                }
+                if !items.is_empty() {
+                    blocks.push(MarkupBlock::List { ordered, items });
+                }
+                continue;
+            }
+
+            if line.starts_with(' ') {
+                let mut code_lines = Vec::new();
+                while i < lines.len() && lines[i].starts_with(' ') && !lines[i].trim().is_empty() {
+                    code_lines.push(&lines[i][1..]);
+                    i += 1;
+                }
+                blocks.push(MarkupBlock::Code {
+                    language: None,
+                    text: code_lines.join("\n"),
+                });
+                continue;
+            }
+
+            let mut paragraph_lines = Vec::new();
+            while i < lines.len() {
+                let current = lines[i].trim();
+                if current.is_empty()
+                    || current.starts_with(['*', '#', ';', '='])
+                    || current.starts_with("{|")
+                    || CODE_OPEN.is_match(lines[i])
+                    || BLOCKQUOTE_OPEN.is_match(lines[i])
+                    || (lines[i].starts_with(' ') && !paragraph_lines.is_empty())
+                {
+                    break;
+                }
+                paragraph_lines.push(current.trim_start_matches(':').trim());
+                i += 1;
+            }
+            if paragraph_lines.is_empty() {
+                // Unrecognised markup line; skip it so parsing always advances
+                i += 1;
+                continue;
+            }
+            let text = Self::convert_inline(&paragraph_lines.join(" "));
+            if !text.is_empty() {
+                blocks.push(MarkupBlock::Paragraph(text));
+            }
+        }
+
+        blocks
+    }
+
+    /// Parse a `{| ... |}` table starting at `start`; returns rows and the next line index.
+    fn parse_table(lines: &[&str], start: usize) -> (Vec<Vec<String>>, usize) {
+        let mut rows: Vec<Vec<String>> = Vec::new();
+        let mut row: Vec<String> = Vec::new();
+        let mut i = start + 1;
+
+        while i < lines.len() {
+            let line = lines[i].trim();
+            i += 1;
+
+            if line.starts_with("|}") {
+                break;
+            }
+            if line.starts_with("{|") {
+                // Nested tables are skipped
+                let mut depth = 1;
+                while i < lines.len() && depth > 0 {
+                    let nested = lines[i].trim();
+                    if nested.starts_with("{|") {
+                        depth += 1;
+                    } else if nested.starts_with("|}") {
+                        depth -= 1;
+                    }
+                    i += 1;
+                }
+                continue;
+            }
+            if line.starts_with("|+") {
+                continue;
+            }
+            if line.starts_with("|-") {
+                if !row.is_empty() {
+                    rows.push(std::mem::take(&mut row));
+                }
+                continue;
+            }
+
+            let (cells, separator) = if let Some(rest) = line.strip_prefix('!') {
+                (rest, if rest.contains("!!") { "!!" } else { "||" })
+            } else if let Some(rest) = line.strip_prefix('|') {
+                (rest, "||")
+            } else {
+                if let Some(last) = row.last_mut()
+                    && !line.is_empty()
+                {
+                    let text = Self::convert_inline(line);
+                    if !text.is_empty() {
+                        if !last.is_empty() {
+                            last.push(' ');
+                        }
+                        last.push_str(&text);
+                    }
+                }
+                continue;
+            };
+
+            for cell in cells.split(separator) {
+                row.push(Self::convert_inline(Self::strip_cell_attributes(cell)));
+            }
+        }
+
+        if !row.is_empty() {
+            rows.push(row);
+        }
+        (rows, i)
+    }
+
+    /// Drop a leading `attr="value" |` cell attribute section.
+    fn strip_cell_attributes(cell: &str) -> &str {
+        let parts = Self::split_arguments(cell);
+        match parts.as_slice() {
+            [attributes, content, ..] if attributes.contains('=') && !attributes.contains("[[") => content,
+            _ => cell,
+        }
+    }
+
+    /// Replace links, quote formatting, HTML tags and entities with plain text.
+    fn convert_inline(text: &str) -> String {
+        let text = INTERNAL_LINK.replace_all(text, |caps: &regex::Captures| {
+            match caps.get(2).map(|m| m.as_str().trim()).filter(|label| !label.is_empty()) {
+                Some(label) => label.to_string(),
+                None => caps[1].trim().to_string(),
+            }
+        });
+        let text = EXTERNAL_LINK.replace_all(&text, |caps: &regex::Captures| {
+            caps.get(2).map_or(&caps[1], |m| m.as_str()).trim().to_string()
+        });
+        let text = text.replace("<br>", " ").replace("<br/>", " ").replace("<br />", " ");
+        let text = HTML_TAG.replace_all(&text, "");
+        let text = MAGIC_WORD.replace_all(&text, "");
+        let text = text
+            .replace("'''", "")
+            .replace("''", "")
+            .replace("&nbsp;", " ")
+            .replace("&ndash;", "\u{2013}")
+            .replace("&mdash;", "\u{2014}")
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&amp;", "&");
+        WHITESPACE.replace_all(text.trim(), " ").into_owned()
+    }
+
+    fn build_metadata(meta: &WikiMetadata, blocks: &[MarkupBlock]) -> Metadata {
+        let mut additional: AHashMap<Cow<'static, str>, serde_json::Value> = AHashMap::new();
+        if !meta.categories.is_empty() {
+            additional.insert(Cow::Borrowed("categories"), serde_json::json!(meta.categories));
+        }
+        if let Some(redirect) = &meta.redirect {
+            additional.insert(Cow::Borrowed("redirect"), serde_json::json!(redirect));
+        }
+
+        let title = meta.title.clone().or_else(|| {
+            blocks.iter().find_map(|block| match block {
+                MarkupBlock::Heading { level: 1, text } => Some(text.clone()),
+                _ => None,
+            })
+        });
+
+        Metadata {
+            title,
+            keywords: (!meta.categories.is_empty()).then(|| meta.categories.clone()),
+            additional,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(feature = "office")]
+impl Default for MediaWikiExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "office")]
+impl Plugin for MediaWikiExtractor {
+    fn name(&self) -> &str {
+        "mediawiki-extractor"
+    }
+
+    fn version(&self) -> String {
+        env!("CARGO_PKG_VERSION").to_string()
+    }
+
+    fn initialize(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn shutdown(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn description(&self) -> &str {
+        "Native Rust extractor for MediaWiki wikitext documents"
+    }
+
+    fn author(&self) -> &str {
+        "Kreuzberg Team"
+    }
+}
+
+#[cfg(feature = "office")]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl DocumentExtractor for MediaWikiExtractor {
+    #[cfg_attr(
+        feature = "otel",
+        tracing::instrument(
+            skip(self, content, config),
+            fields(
+                extractor.name = self.name(),
+                content.size_bytes = content.len(),
+            )
+        )
+    )]
+    async fn extract_bytes(
+        &self,
+        content: &[u8],
+        mime_type: &str,
+        config: &ExtractionConfig,
+    ) -> Result<ExtractionResult> {
+        let text = String::from_utf8_lossy(content).into_owned();
+
+        let (meta, blocks) = Self::parse(&text);
+        let metadata = Self::build_metadata(&meta, &blocks);
+        let document = config
+            .needs_document_structure()
+            .then(|| markup::build_document_structure(&blocks));
+
+        Ok(ExtractionResult {
+            content: markup::render_text(&blocks),
+            mime_type: mime_type.to_string().into(),
+            metadata,
+            tables: markup::extract_tables(&blocks),
+            detected_languages: None,
+            chunks: None,
+            images: None,
+            djot_content: None,
+            pages: None,
+            elements: None,
+            ocr_elements: None,
+            document,
+            #[cfg(any(feature = "keywords-yake", feature = "keywords-rake"))]
+            extracted_keywords: None,
+            quality_score: None,
+            processing_warnings: Vec::new(),
+            annotations: None,
+        })
+    }
+
+    fn supported_mime_types(&self) -> &[&str] {
+        &["text/x-mediawiki", "text/x-wiki"]
+    }
+
+    fn priority(&self) -> i32 {
+        50
+    }
+}
+
+#[cfg(all(test, feature = "office"))]
+mod tests {
+    use super::*;
+    use crate::types::NodeContent;
+
+    const SAMPLE: &str = r#"{{DISPLAYTITLE:Build ''Server''}}
+{{Infobox software
+| name = Build Server
+| license = {{MIT}}
+}}
+The '''build server''' runs [[Continuous integration|CI]] jobs.<ref>Internal memo</ref> See [https://ci.example.com the dashboard].
+<!-- hidden note -->
+
+== Setup ==
+# Install [[Docker]]
+# Register the runner
+#: with the admin token
+
+{{Warning|Do not expose port 8080.}}
+
+=== Config ===
+<syntaxhighlight lang="yaml">
+runners: 4
+</syntaxhighlight>
+
+{| class="wikitable"
+|+ Ports
+! Service !! Port
+|-
+| style="color:red" | web || 8080
+|-
+| api
+| 9090
+|}
+
+[[File:Diagram.png|thumb|The [[pipeline]] layout]]
+[[Category:Infrastructure]]
+[[Category:CI|Build]]
+"#;
+
+    #[test]
+    fn test_mediawiki_extractor_plugin_interface() {
+        let extractor = MediaWikiExtractor::new();
+        assert_eq!(extractor.name(), "mediawiki-extractor");
+        assert_eq!(extractor.version(), env!("CARGO_PKG_VERSION"));
+        assert_eq!(extractor.priority(), 50);
+        assert!(extractor.supported_mime_types().contains(&"text/x-mediawiki"));
+        assert!(extractor.initialize().is_ok());
+        assert!(extractor.shutdown().is_ok());
+    }
+
+    #[test]
+    fn test_mediawiki_blocks() {
+        let (meta, blocks) = MediaWikiExtractor::parse(SAMPLE);
+
+        assert_eq!(meta.title.as_deref(), Some("Build Server"));
+        assert_eq!(meta.categories, vec!["Infrastructure", "CI"]);
+        assert_eq!(
+            blocks,
+            vec![
+                MarkupBlock::Paragraph("The build server runs CI jobs. See the dashboard.".to_string()),
+                MarkupBlock::Heading {
+                    level: 2,
+                    text: "Setup".to_string()
+                },
+                MarkupBlock::List {
+                    ordered: true,
+                    items: vec![
+                        "Install Docker".to_string(),
+                        "Register the runner: with the admin token".to_string()
+                    ]
+                },
+                MarkupBlock::Quote {
+                    label: Some("WARNING".to_string()),
+                    paragraphs: vec!["Do not expose port 8080.".to_string()]
+                },
+                MarkupBlock::Heading {
+                    level: 3,
+                    text: "Config".to_string()
+                },
+                MarkupBlock::Code {
+                    language: Some("yaml".to_string()),
+                    text: "runners: 4".to_string()
+                },
+                MarkupBlock::Table(vec![
+                    vec!["Service".to_string(), "Port".to_string()],
+                    vec!["web".to_string(), "8080".to_string()],
+                    vec!["api".to_string(), "9090".to_string()],
+                ]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mediawiki_redirect() {
+        let (meta, blocks) = MediaWikiExtractor::parse("#REDIRECT [[Main Page]]\n");
+        assert_eq!(meta.redirect.as_deref(), Some("Main Page"));
+        assert!(blocks.is_empty());
+    }
+
+    #[test]
+    fn test_mediawiki_templates_inside_code_are_kept() {
+        let (_, blocks) = MediaWikiExtractor::parse("<pre>\n{{not a template}}\n</pre>\n");
+        assert_eq!(
+            blocks,
+            vec![MarkupBlock::Code {
+                language: None,
+                text: "{{not a template}}".to_string()
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mediawiki_extract_bytes_with_document_structure() {
+        let config = ExtractionConfig {
+            include_document_structure: true,
+            ..Default::default()
+        };
+        let result = MediaWikiExtractor::new()
+            .extract_bytes(SAMPLE.as_bytes(), "text/x-mediawiki", &config)
+            .await
+            .unwrap();
+
+        assert_eq!(result.metadata.title.as_deref(), Some("Build Server"));
+        assert_eq!(
+            result.metadata.keywords,
+            Some(vec!["Infrastructure".to_string(), "CI".to_string()])
+        );
+        assert!(result.content.contains("## Setup"));
+        assert!(result.content.contains("> WARNING: Do not expose port 8080."));
+        assert!(!result.content.contains("Infobox"));
+        assert_eq!(result.tables.len(), 1);
+
+        let document = result.document.expect("document structure requested");
+        assert!(document.validate().is_ok());
+        assert!(
+            document
+                .nodes
+                .iter()
+                .any(|n| matches!(n.content, NodeContent::Heading { level: 3, .. }))
+        );
+        assert!(document.nodes.iter().any(|n| matches!(n.content, NodeContent::Quote)));
+        assert!(
+            document
+                .nodes
+                .iter()
+                .any(|n| matches!(n.content, NodeContent::Table { .. }))
+        );
+    }
+}
@@ -0,0 +1,222 @@
+//! Mobipocket / Kindle e-book extractor.
+//!
+//! Supports: MOBI (.mobi, .prc), AZW (.azw) and KF8 (.azw3)
+
+use crate::Result;
+use crate::core::config::{ExtractionConfig, OutputFormat};
+use crate::extraction::mobi::{AZW_MIME_TYPE, AZW3_MIME_TYPE, MOBI_MIME_TYPE, MobiBook, parse_mobi};
+use crate::plugins::{DocumentExtractor, Plugin};
+use crate::types::{ExtractionResult, Metadata};
+use ahash::AHashMap;
+use async_trait::async_trait;
+use std::borrow::Cow;
+
+/// Mobipocket and Kindle Format 8 extractor.
+///
+/// Decodes the PalmDB text records (uncompressed, PalmDOC LZ77 or HUFF/CDIC)
+/// and converts the embedded HTML to markdown with the HTML converter.
+/// Metadata comes from the MOBI header and EXTH records. DRM-protected books
+/// are rejected.
+pub struct MobiExtractor;
+
+impl MobiExtractor {
+    /// Create a new MOBI extractor.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for MobiExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Plugin for MobiExtractor {
+    fn name(&self) -> &str {
+        "mobi-extractor"
+    }
+
+    fn version(&self) -> String {
+        env!("CARGO_PKG_VERSION").to_string()
+    }
+
+    fn initialize(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn shutdown(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn description(&self) -> &str {
+        "Native Rust Mobipocket/Kindle (MOBI, AZW, AZW3) extractor with PalmDOC and HUFF/CDIC decompression"
+    }
+
+    fn author(&self) -> &str {
+        "Kreuzberg Team"
+    }
+}
+
+fn book_metadata(book: &MobiBook) -> Metadata {
+    let mut additional: AHashMap<Cow<'static, str>, serde_json::Value> = AHashMap::new();
+    if let Some(publisher) = &book.publisher {
+        additional.insert(Cow::Borrowed("publisher"), serde_json::json!(publisher));
+    }
+    if let Some(description) = &book.description {
+        additional.insert(Cow::Borrowed("description"), serde_json::json!(description));
+    }
+    additional.insert(Cow::Borrowed("kf8"), serde_json::json!(book.is_kf8));
+
+    Metadata {
+        title: book.title.clone(),
+        authors: (!book.authors.is_empty()).then(|| book.authors.clone()),
+        keywords: (!book.subjects.is_empty()).then(|| book.subjects.clone()),
+        language: book.language.clone(),
+        created_at: book.published.clone(),
+        additional,
+        ..Default::default()
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl DocumentExtractor for MobiExtractor {
+    #[cfg_attr(feature = "otel", tracing::instrument(
+        skip(self, content, config),
+        fields(
+            extractor.name = self.name(),
+            content.size_bytes = content.len(),
+        )
+    ))]
+    async fn extract_bytes(
+        &self,
+        content: &[u8],
+        mime_type: &str,
+        config: &ExtractionConfig,
+    ) -> Result<ExtractionResult> {
+        let book = {
+            #[cfg(feature = "tokio-runtime")]
+            {
+                if crate::core::batch_mode::is_batch_mode() {
+                    let content_owned = content.to_vec();
+                    let span = tracing::Span::current();
+                    tokio::task::spawn_blocking(move || {
+                        let _guard = span.entered();
+                        parse_mobi(&content_owned)
+                    })
+                    .await
+                    .map_err(|e| {
+                        crate::error::KreuzbergError::parsing(format!("MOBI extraction task failed: {}", e))
+                    })??
+                } else {
+                    parse_mobi(content)?
+                }
+            }
+
+            #[cfg(not(feature = "tokio-runtime"))]
+            {
+                parse_mobi(content)?
+            }
+        };
+
+        let text = crate::extraction::html::convert_html_to_markdown(
+            &book.html,
+            config.html_options.clone(),
+            Some(config.output_format),
+        )?;
+
+        let mut metadata = book_metadata(&book);
+        metadata.output_format = match config.output_format {
+            OutputFormat::Markdown => Some("markdown".to_string()),
+            OutputFormat::Djot => Some("djot".to_string()),
+            _ => None,
+        };
+
+        Ok(ExtractionResult {
+            content: text,
+            mime_type: mime_type.to_string().into(),
+            metadata,
+            pages: None,
+            tables: vec![],
+            detected_languages: None,
+            chunks: None,
+            images: None,
+            djot_content: None,
+            elements: None,
+            ocr_elements: None,
+            document: None,
+            #[cfg(any(feature = "keywords-yake", feature = "keywords-rake"))]
+            extracted_keywords: None,
+            quality_score: None,
+            processing_warnings: Vec::new(),
+            annotations: None,
+        })
+    }
+
+    fn supported_mime_types(&self) -> &[&str] {
+        &[
+            MOBI_MIME_TYPE,
+            AZW_MIME_TYPE,
+            AZW3_MIME_TYPE,
+            "application/x-mobi8-ebook",
+        ]
+    }
+
+    fn priority(&self) -> i32 {
+        50
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extraction::mobi::test_support::{mobi_record0, palmdb};
+
+    #[test]
+    fn test_mobi_extractor_plugin_interface() {
+        let extractor = MobiExtractor::new();
+        assert_eq!(extractor.name(), "mobi-extractor");
+        assert_eq!(extractor.priority(), 50);
+        assert!(extractor.initialize().is_ok());
+        assert!(extractor.shutdown().is_ok());
+    }
+
+    #[test]
+    fn test_mobi_extractor_supported_mime_types() {
+        let extractor = MobiExtractor::new();
+        let mime_types = extractor.supported_mime_types();
+        assert!(mime_types.contains(&"application/x-mobipocket-ebook"));
+        assert!(mime_types.contains(&"application/vnd.amazon.mobi8-ebook"));
+    }
+
+    #[tokio::test]
+    async fn test_mobi_extractor_converts_html() {
+        let html = "<html><body><h1>Chapter One</h1><p>Call me <b>Ishmael</b>.</p><mbp:pagebreak/></body></html>";
+        let record0 = mobi_record0(1, html.len(), 1, "Moby Dick", &[(100, "Herman Melville")], None);
+        let data = palmdb(b"BOOKMOBI", &[record0, html.as_bytes().to_vec()]);
+
+        let result = MobiExtractor::new()
+            .extract_bytes(&data, "application/x-mobipocket-ebook", &ExtractionConfig::default())
+            .await
+            .unwrap();
+
+        assert!(result.content.contains("Chapter One"));
+        assert!(result.content.contains("Ishmael"));
+        assert!(!result.content.contains("<b>"));
+        assert_eq!(result.metadata.title.as_deref(), Some("Moby Dick"));
+        assert_eq!(result.metadata.authors, Some(vec!["Herman Melville".to_string()]));
+    }
+
+    #[tokio::test]
+    async fn test_mobi_extractor_invalid_file() {
+        let result = MobiExtractor::new()
+            .extract_bytes(
+                b"not a mobi",
+                "application/x-mobipocket-ebook",
+                &ExtractionConfig::default(),
+            )
+            .await;
+        assert!(result.is_err());
+    }
+}
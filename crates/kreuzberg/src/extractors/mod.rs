@@ -5,7 +5,7 @@
 
 use crate::Result;
 use crate::core::config::ExtractionConfig;
-use crate::plugins::registry::get_document_extractor_registry;
+use crate::plugins::registry::{DocumentExtractorRegistry, get_document_extractor_registry};
 use crate::types::ExtractionResult;
 use once_cell::sync::Lazy;
 use std::sync::Arc;
@@ -89,6 +89,9 @@ pub mod html;
 #[cfg(feature = "office")]
 pub mod bibtex;
 
+#[cfg(feature = "office")]
+pub mod chm;
+
 #[cfg(feature = "office")]
 pub mod citation;
 
@@ -107,15 +110,30 @@ pub mod fictionbook;
 #[cfg(feature = "office")]
 pub mod markdown;
 
+#[cfg(feature = "office")]
+pub mod mobi;
+
 #[cfg(feature = "mdx")]
 pub mod mdx;
 
 #[cfg(feature = "office")]
 pub mod rst;
 
+#[cfg(feature = "office")]
+pub mod asciidoc;
+
+#[cfg(feature = "office")]
+pub mod textile;
+
+#[cfg(feature = "office")]
+pub mod mediawiki;
+
 #[cfg(feature = "office")]
 pub mod latex;
 
+#[cfg(feature = "office")]
+pub mod iwork;
+
 #[cfg(feature = "office")]
 pub mod jupyter;
 
@@ -174,6 +192,9 @@ pub use html::HtmlExtractor;
 #[cfg(feature = "office")]
 pub use bibtex::BibtexExtractor;
 
+#[cfg(feature = "office")]
+pub use chm::ChmExtractor;
+
 #[cfg(feature = "office")]
 pub use citation::CitationExtractor;
 
@@ -189,6 +210,9 @@ pub use epub::EpubExtractor;
 #[cfg(feature = "office")]
 pub use fictionbook::FictionBookExtractor;
 
+#[cfg(feature = "office")]
+pub use mobi::MobiExtractor;
+
 pub use djot_format::DjotExtractor;
 
 #[cfg(feature = "office")]
@@ -200,9 +224,21 @@ pub use mdx::MdxExtractor;
 #[cfg(feature = "office")]
 pub use rst::RstExtractor;
 
+#[cfg(feature = "office")]
+pub use asciidoc::AsciiDocExtractor;
+
+#[cfg(feature = "office")]
+pub use textile::TextileExtractor;
+
+#[cfg(feature = "office")]
+pub use mediawiki::MediaWikiExtractor;
+
 #[cfg(feature = "office")]
 pub use latex::LatexExtractor;
 
+#[cfg(feature = "office")]
+pub use iwork::IworkExtractor;
+
 #[cfg(feature = "office")]
 pub use jupyter::JupyterExtractor;
 
@@ -296,6 +332,14 @@ pub fn register_default_extractors() -> Result<()> {
         .write()
         .map_err(|e| crate::KreuzbergError::Other(format!("Document extractor registry lock poisoned: {}", e)))?;
 
+    register_default_extractors_into(&mut registry)
+}
+
+/// Register all built-in extractors with `registry`.
+///
+/// Used by [`register_default_extractors`] for the global registry and by
+/// [`Kreuzberg::new`](crate::Kreuzberg::new) for engine-owned registries.
+pub(crate) fn register_default_extractors_into(registry: &mut DocumentExtractorRegistry) -> Result<()> {
     registry.register(Arc::new(PlainTextExtractor::new()))?;
     registry.register(Arc::new(MarkdownExtractor::new()))?;
     registry.register(Arc::new(StructuredExtractor::new()))?;
@@ -326,8 +370,13 @@ pub fn register_default_extractors() -> Result<()> {
         registry.register(Arc::new(CitationExtractor::new()))?;
         registry.register(Arc::new(EpubExtractor::new()))?;
         registry.register(Arc::new(FictionBookExtractor::new()))?;
+        registry.register(Arc::new(MobiExtractor::new()))?;
+        registry.register(Arc::new(ChmExtractor::new()))?;
         registry.register(Arc::new(RtfExtractor::new()))?;
         registry.register(Arc::new(RstExtractor::new()))?;
+        registry.register(Arc::new(AsciiDocExtractor::new()))?;
+        registry.register(Arc::new(TextileExtractor::new()))?;
+        registry.register(Arc::new(MediaWikiExtractor::new()))?;
         registry.register(Arc::new(LatexExtractor::new()))?;
         registry.register(Arc::new(JupyterExtractor::new()))?;
         registry.register(Arc::new(OrgModeExtractor::new()))?;
@@ -338,6 +387,7 @@ pub fn register_default_extractors() -> Result<()> {
         registry.register(Arc::new(PptExtractor::new()))?;
         registry.register(Arc::new(PptxExtractor::new()))?;
         registry.register(Arc::new(OdtExtractor::new()))?;
+        registry.register(Arc::new(IworkExtractor::new()))?;
     }
 
     #[cfg(feature = "mdx")]
@@ -417,14 +467,19 @@ mod tests {
 
         #[cfg(feature = "office")]
         {
-            expected_count += 11;
+            expected_count += 16;
             assert!(extractor_names.contains(&"markdown-extractor".to_string()));
             assert!(extractor_names.contains(&"bibtex-extractor".to_string()));
             assert!(extractor_names.contains(&"citation-extractor".to_string()));
             assert!(extractor_names.contains(&"epub-extractor".to_string()));
             assert!(extractor_names.contains(&"fictionbook-extractor".to_string()));
+            assert!(extractor_names.contains(&"mobi-extractor".to_string()));
+            assert!(extractor_names.contains(&"chm-extractor".to_string()));
             assert!(extractor_names.contains(&"rtf-extractor".to_string()));
             assert!(extractor_names.contains(&"rst-extractor".to_string()));
+            assert!(extractor_names.contains(&"asciidoc-extractor".to_string()));
+            assert!(extractor_names.contains(&"textile-extractor".to_string()));
+            assert!(extractor_names.contains(&"mediawiki-extractor".to_string()));
             assert!(extractor_names.contains(&"latex-extractor".to_string()));
             assert!(extractor_names.contains(&"jupyter-extractor".to_string()));
             assert!(extractor_names.contains(&"orgmode-extractor".to_string()));
@@ -434,12 +489,13 @@ mod tests {
 
         #[cfg(all(feature = "tokio-runtime", feature = "office"))]
         {
-            expected_count += 5;
+            expected_count += 6;
             assert!(extractor_names.contains(&"doc-extractor".to_string()));
             assert!(extractor_names.contains(&"docx-extractor".to_string()));
             assert!(extractor_names.contains(&"ppt-extractor".to_string()));
             assert!(extractor_names.contains(&"pptx-extractor".to_string()));
             assert!(extractor_names.contains(&"odt-extractor".to_string()));
+            assert!(extractor_names.contains(&"iwork-extractor".to_string()));
         }
 
         #[cfg(feature = "mdx")]
@@ -55,7 +55,15 @@ pub(crate) fn extract_all_from_document(
     let (native_text, boundaries, page_contents, pdf_metadata) =
         crate::pdf::text::extract_text_and_metadata_from_pdf_document(document, Some(config))?;
 
-    let tables = extract_tables_from_document(document, &pdf_metadata)?;
+    // The text pass stops at the first page reached after cancellation; make that an error
+    // unless partial results were requested, and skip the remaining passes otherwise.
+    let cancelled = config.checkpoint(|| "table detection".to_string())?;
+
+    let tables = if cancelled {
+        Vec::new()
+    } else {
+        extract_tables_from_document(document, &pdf_metadata, config)?
+    };
 
     let mut has_font_encoding_issues = false;
 
@@ -73,7 +81,7 @@ pub(crate) fn extract_all_from_document(
         force_ocr = config.force_ocr,
         "PDF markdown path: evaluating whether to render structured markdown"
     );
-    let pre_rendered_markdown = if needs_structured && !config.force_ocr {
+    let pre_rendered_markdown = if needs_structured && !config.force_ocr && !cancelled {
         let k = config
             .pdf_options
             .as_ref()
@@ -191,6 +199,7 @@ fn has_column_alignment(words: &[crate::ocr::table::HocrWord]) -> bool {
 fn extract_tables_from_document(
     document: &PdfDocument,
     _metadata: &crate::pdf::metadata::PdfExtractionMetadata,
+    config: &ExtractionConfig,
 ) -> Result<Vec<Table>> {
     use crate::ocr::table::{post_process_table, reconstruct_table, table_to_markdown};
     use crate::pdf::table::extract_words_from_page;
@@ -198,7 +207,14 @@ fn extract_tables_from_document(
     let mut all_tables = Vec::new();
 
     for (page_index, page) in document.pages().iter().enumerate() {
-        let words = extract_words_from_page(&page, 0.0)?;
+        if config.checkpoint(|| format!("table detection on page {}", page_index + 1))? {
+            break;
+        }
+
+        let words = extract_words_from_page(&page, 0.0);
+        let Some(words) = config.recover(|| format!("table detection on page {}", page_index + 1), words)? else {
+            continue;
+        };
 
         // Need at least 6 words for a meaningful table
         if words.len() < 6 {
@@ -277,6 +293,7 @@ fn extract_tables_from_document(
 fn extract_tables_from_document(
     _document: &PdfDocument,
     _metadata: &crate::pdf::metadata::PdfExtractionMetadata,
+    _config: &ExtractionConfig,
 ) -> Result<Vec<crate::types::Table>> {
     Ok(vec![])
 }
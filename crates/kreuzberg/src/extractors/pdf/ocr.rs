@@ -181,7 +181,6 @@ pub fn evaluate_per_page_ocr(
 #[cfg(feature = "ocr")]
 pub(crate) async fn extract_with_ocr(content: &[u8], config: &ExtractionConfig) -> crate::Result<String> {
     use crate::pdf::rendering::{PageRenderOptions, PdfRenderer};
-    use crate::plugins::registry::get_ocr_backend_registry;
     use image::ImageEncoder;
     use image::codecs::png::PngEncoder;
     use std::io::Cursor;
@@ -192,7 +191,7 @@ pub(crate) async fn extract_with_ocr(content: &[u8], config: &ExtractionConfig)
     })?;
 
     let backend = {
-        let registry = get_ocr_backend_registry();
+        let registry = crate::engine::current().ocr_backend_registry();
         let registry = registry.read().map_err(|e| crate::KreuzbergError::Plugin {
             message: format!("Failed to acquire read lock on OCR backend registry: {}", e),
             plugin_name: "ocr-registry".to_string(),
@@ -217,7 +216,11 @@ pub(crate) async fn extract_with_ocr(content: &[u8], config: &ExtractionConfig)
 
     let mut page_texts = Vec::with_capacity(images.len());
 
-    for image in images {
+    for (index, image) in images.into_iter().enumerate() {
+        if config.checkpoint(|| format!("OCR of page {}", index + 1))? {
+            break;
+        }
+
         let rgb_image = image.to_rgb8();
         let (width, height) = rgb_image.dimensions();
 
@@ -232,9 +235,13 @@ pub(crate) async fn extract_with_ocr(content: &[u8], config: &ExtractionConfig)
 
         let image_data = image_bytes.into_inner();
 
-        let ocr_result = backend.process_image(&image_data, ocr_config).await?;
+        let ocr_result = backend.process_image(&image_data, ocr_config).await;
+        #[cfg(feature = "metrics")]
+        crate::metrics::global().record_ocr_pages(backend.name(), 1);
 
-        page_texts.push(ocr_result.content);
+        // A skipped page stays in the output as an empty one to keep page numbers aligned.
+        let ocr_result = config.recover(|| format!("OCR of page {}", index + 1), ocr_result)?;
+        page_texts.push(ocr_result.map(|r| r.content).unwrap_or_default());
     }
 
     let page_marker_cfg = config.pages.as_ref().filter(|p| p.insert_page_markers);
@@ -67,16 +67,16 @@ impl DocumentExtractor for PptxExtractor {
         let pptx_result = {
             #[cfg(feature = "tokio-runtime")]
             {
-                let pages_config = config.pages.clone();
                 if crate::core::batch_mode::is_batch_mode() {
                     let content_owned = content.to_vec();
+                    let config_owned = config.clone();
                     let span = tracing::Span::current();
                     tokio::task::spawn_blocking(move || {
                         let _guard = span.entered();
-                        crate::extraction::pptx::extract_pptx_from_bytes(
+                        crate::extraction::pptx::extract_pptx_from_bytes_with_config(
                             &content_owned,
                             extract_images,
-                            pages_config.as_ref(),
+                            &config_owned,
                         )
                     })
                     .await
@@ -84,13 +84,13 @@ impl DocumentExtractor for PptxExtractor {
                         crate::error::KreuzbergError::parsing(format!("PPTX extraction task failed: {}", e))
                     })??
                 } else {
-                    crate::extraction::pptx::extract_pptx_from_bytes(content, extract_images, config.pages.as_ref())?
+                    crate::extraction::pptx::extract_pptx_from_bytes_with_config(content, extract_images, config)?
                 }
             }
 
             #[cfg(not(feature = "tokio-runtime"))]
             {
-                crate::extraction::pptx::extract_pptx_from_bytes(content, extract_images, config.pages.as_ref())?
+                crate::extraction::pptx::extract_pptx_from_bytes_with_config(content, extract_images, config)?
             }
         };
 
@@ -165,7 +165,7 @@ impl DocumentExtractor for PptxExtractor {
         let extract_images = config.images.as_ref().is_some_and(|img| img.extract_images);
 
         let pptx_result =
-            crate::extraction::pptx::extract_pptx_from_path(path_str, extract_images, config.pages.as_ref())?;
+            crate::extraction::pptx::extract_pptx_from_path_with_config(path_str, extract_images, config)?;
 
         let mut additional: AHashMap<Cow<'static, str>, serde_json::Value> = AHashMap::new();
         additional.insert(Cow::Borrowed("slide_count"), serde_json::json!(pptx_result.slide_count));
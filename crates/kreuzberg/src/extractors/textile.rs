@@ -0,0 +1,485 @@
+//! Native Rust Textile extractor.
+//!
+//! This extractor parses Textile markup line by line.
+//! It extracts:
+//! - Headings (h1. to h6.) with the first heading as document title
+//! - Paragraphs with inline markup removed
+//! - Lists (* bullet, # numbered, - term := definition)
+//! - Code blocks (bc., pre., including extended bc.. blocks)
+//! - Block quotes (bq.)
+//! - Tables (| cell | cell |, with |_. header cells)
+
+#[cfg(feature = "office")]
+use crate::Result;
+#[cfg(feature = "office")]
+use crate::core::config::ExtractionConfig;
+#[cfg(feature = "office")]
+use crate::extraction::markup::{self, MarkupBlock};
+#[cfg(feature = "office")]
+use crate::plugins::{DocumentExtractor, Plugin};
+#[cfg(feature = "office")]
+use crate::types::{ExtractionResult, Metadata};
+#[cfg(feature = "office")]
+use async_trait::async_trait;
+#[cfg(feature = "office")]
+use once_cell::sync::Lazy;
+#[cfg(feature = "office")]
+use regex::Regex;
+
+#[cfg(feature = "office")]
+static BLOCK_SIGNATURE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^(h[1-6]|p|bq|bc|pre|fn\d+|notextile|###)((?:\([^)]*\)|\{[^}]*\}|\[[^\]]*\]|[<>=()])*)(\.\.?)(?:\s+(.*))?$",
+    )
+    .expect("Textile block signature regex is valid")
+});
+#[cfg(feature = "office")]
+static LIST_ITEM: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^([*#]+)(?:\([^)]*\)|\{[^}]*\})*\s+(.*)$").expect("Textile list regex is valid"));
+#[cfg(feature = "office")]
+static DEFINITION_ITEM: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^-\s+(.+?)\s+:=\s*(.*)$").expect("Textile definition regex is valid"));
+#[cfg(feature = "office")]
+static TABLE_SIGNATURE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^table(?:\([^)]*\)|\{[^}]*\}|\[[^\]]*\]|[<>=])*\.\s*$").expect("Textile table regex is valid")
+});
+#[cfg(feature = "office")]
+static CELL_MODIFIERS: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:_|\\\d+|/\d+|\{[^}]*\}|\([^)]*\)|\[[^\]]*\]|[<>=^~-])+\.\s").expect("Textile cell regex is valid")
+});
+#[cfg(feature = "office")]
+static ROW_MODIFIERS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(?:\{[^}]*\}|\([^)]*\)|[<>=^~-])+\.\s*\|").expect("Textile row regex is valid"));
+#[cfg(feature = "office")]
+static LINK: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\[?"([^"\n]+?)(?:\s*\([^)"]*\))?":[^\s"<\]]*[^\s"<\].,;:!?)]\]?"#)
+        .expect("Textile link regex is valid")
+});
+#[cfg(feature = "office")]
+static IMAGE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"!(?:\([^)]*\)|\{[^}]*\}|[<>=])*[^\s!(]+(?:\(([^)]*)\))?!(?::\S+)?")
+        .expect("Textile image regex is valid")
+});
+#[cfg(feature = "office")]
+static SPAN_ATTRIBUTES: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"([%*_@])(?:\{[^}]*\}|\([^)]*\)|\[[^\]]*\])+").expect("Textile span regex is valid"));
+#[cfg(feature = "office")]
+static FOOTNOTE_REFERENCE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(\S)\[\d+\]").expect("Textile footnote regex is valid"));
+#[cfg(feature = "office")]
+static HTML_TAG: Lazy<Regex> = Lazy::new(|| Regex::new(r"</?[a-zA-Z][^>]*>").expect("HTML tag regex is valid"));
+#[cfg(feature = "office")]
+static CODE_LANGUAGE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\[([\w+#-]+)\]|\((?:[\w-]*\s)*language-([\w+#-]+)[^)]*\)").expect("Textile language regex is valid")
+});
+
+/// Native Rust Textile extractor.
+///
+/// Parses Textile documents and extracts:
+/// - Headings, paragraphs, lists, code blocks, quotes and tables
+/// - The first heading as document title
+/// - A `DocumentStructure` when requested
+#[cfg(feature = "office")]
+pub struct TextileExtractor;
+
+#[cfg(feature = "office")]
+impl TextileExtractor {
+    /// Create a new Textile extractor.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Parse a Textile document into blocks.
+    fn parse(content: &str) -> Vec<MarkupBlock> {
+        let lines: Vec<&str> = content.lines().map(str::trim_end).collect();
+        let mut blocks = Vec::new();
+        let mut i = 0;
+
+        while i < lines.len() {
+            let line = lines[i];
+
+            if line.trim().is_empty() {
+                i += 1;
+                continue;
+            }
+
+            if let Some(caps) = BLOCK_SIGNATURE.captures(line) {
+                let kind = caps[1].to_string();
+                let modifiers = caps.get(2).map_or("", |m| m.as_str()).to_string();
+                let extended = &caps[3] == "..";
+                let mut block_lines: Vec<&str> = caps.get(4).map(|m| vec![m.as_str()]).unwrap_or_default();
+                i += 1;
+
+                while i < lines.len() {
+                    let next = lines[i];
+                    if extended {
+                        if BLOCK_SIGNATURE.is_match(next) {
+                            break;
+                        }
+                    } else if next.trim().is_empty() {
+                        break;
+                    }
+                    block_lines.push(next);
+                    i += 1;
+                }
+                while block_lines.last().is_some_and(|l| l.trim().is_empty()) {
+                    block_lines.pop();
+                }
+
+                Self::push_signature_block(&mut blocks, &kind, &modifiers, &block_lines);
+                continue;
+            }
+
+            if line.starts_with('|') || TABLE_SIGNATURE.is_match(line) || ROW_MODIFIERS.is_match(line) {
+                let start = if TABLE_SIGNATURE.is_match(line) { i + 1 } else { i };
+                let mut end = start;
+                while end < lines.len() && (lines[end].starts_with('|') || ROW_MODIFIERS.is_match(lines[end])) {
+                    end += 1;
+                }
+                let rows = Self::parse_table(&lines[start..end]);
+                if !rows.is_empty() {
+                    blocks.push(MarkupBlock::Table(rows));
+                }
+                i = end.max(i + 1);
+                continue;
+            }
+
+            if LIST_ITEM.is_match(line) || DEFINITION_ITEM.is_match(line) {
+                let mut items: Vec<String> = Vec::new();
+                let ordered = LIST_ITEM.captures(line).is_some_and(|caps| caps[1].starts_with('#'));
+                while i < lines.len() && !lines[i].trim().is_empty() {
+                    let current = lines[i];
+                    if let Some(caps) = LIST_ITEM.captures(current) {
+                        items.push(Self::convert_inline(&caps[2]));
+                    } else if let Some(caps) = DEFINITION_ITEM.captures(current) {
+                        items.push(Self::convert_inline(&format!("{}: {}", &caps[1], &caps[2])));
+                    } else if let Some(last) = items.last_mut() {
+                        last.push(' ');
+                        last.push_str(&Self::convert_inline(current.trim()));
+                    }
+                    i += 1;
+                }
+                blocks.push(MarkupBlock::List { ordered, items });
+                continue;
+            }
+
+            let mut paragraph_lines = Vec::new();
+            while i < lines.len() && !lines[i].trim().is_empty() {
+                paragraph_lines.push(lines[i].trim());
+                i += 1;
+            }
+            let text = Self::convert_inline(&paragraph_lines.join(" "));
+            if !text.is_empty() {
+                blocks.push(MarkupBlock::Paragraph(text));
+            }
+        }
+
+        blocks
+    }
+
+    fn push_signature_block(blocks: &mut Vec<MarkupBlock>, kind: &str, modifiers: &str, lines: &[&str]) {
+        let paragraphs = || -> Vec<String> {
+            lines
+                .split(|line| line.trim().is_empty())
+                .map(|group| Self::convert_inline(&group.iter().map(|l| l.trim()).collect::<Vec<_>>().join(" ")))
+                .filter(|text| !text.is_empty())
+                .collect()
+        };
+
+        match kind {
+            "###" => {}
+            "bc" | "pre" => blocks.push(MarkupBlock::Code {
+                language: CODE_LANGUAGE
+                    .captures(modifiers)
+                    .and_then(|caps| caps.get(1).or_else(|| caps.get(2)))
+                    .map(|m| m.as_str().to_string()),
+                text: lines.join("\n"),
+            }),
+            "bq" => blocks.push(MarkupBlock::Quote {
+                label: None,
+                paragraphs: paragraphs(),
+            }),
+            _ if kind.starts_with('h') => {
+                let level = kind[1..].parse().unwrap_or(1);
+                blocks.push(MarkupBlock::Heading {
+                    level,
+                    text: Self::convert_inline(&lines.join(" ")),
+                });
+            }
+            _ if kind.starts_with("fn") => {
+                let text = paragraphs().join(" ");
+                blocks.push(MarkupBlock::Paragraph(format!("[{}] {}", &kind[2..], text)));
+            }
+            _ => blocks.extend(paragraphs().into_iter().map(MarkupBlock::Paragraph)),
+        }
+    }
+
+    /// Parse table rows, stripping row and cell modifiers.
+    fn parse_table(lines: &[&str]) -> Vec<Vec<String>> {
+        lines
+            .iter()
+            .map(|line| {
+                let line = match ROW_MODIFIERS.find(line) {
+                    Some(m) => &line[m.end() - 1..],
+                    None => line,
+                };
+                let line = line.trim().trim_start_matches('|');
+                let line = line.strip_suffix('|').unwrap_or(line);
+                line.split('|')
+                    .map(|cell| {
+                        let cell = cell.trim();
+                        let cell = match CELL_MODIFIERS.find(cell) {
+                            Some(m) => &cell[m.end()..],
+                            None => cell,
+                        };
+                        Self::convert_inline(cell)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .filter(|row: &Vec<String>| !row.is_empty())
+            .collect()
+    }
+
+    /// Replace links, images and phrase modifiers with plain text.
+    fn convert_inline(text: &str) -> String {
+        let text = IMAGE.replace_all(text, |caps: &regex::Captures| {
+            caps.get(1).map_or(String::new(), |m| m.as_str().to_string())
+        });
+        let text = LINK.replace_all(&text, "$1");
+        let text = FOOTNOTE_REFERENCE.replace_all(&text, "$1");
+        let text = HTML_TAG.replace_all(&text, "");
+        let text = SPAN_ATTRIBUTES.replace_all(&text, "$1");
+
+        let mut text = text.into_owned();
+        for delimiter in ["**", "__", "??", "*", "_", "@", "%", "^", "~", "+", "-"] {
+            text = markup::unwrap_delimited(&text, delimiter);
+        }
+        text.trim().to_string()
+    }
+}
+
+#[cfg(feature = "office")]
+impl Default for TextileExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "office")]
+impl Plugin for TextileExtractor {
+    fn name(&self) -> &str {
+        "textile-extractor"
+    }
+
+    fn version(&self) -> String {
+        env!("CARGO_PKG_VERSION").to_string()
+    }
+
+    fn initialize(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn shutdown(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn description(&self) -> &str {
+        "Native Rust extractor for Textile markup documents"
+    }
+
+    fn author(&self) -> &str {
+        "Kreuzberg Team"
+    }
+}
+
+#[cfg(feature = "office")]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl DocumentExtractor for TextileExtractor {
+    #[cfg_attr(
+        feature = "otel",
+        tracing::instrument(
+            skip(self, content, config),
+            fields(
+                extractor.name = self.name(),
+                content.size_bytes = content.len(),
+            )
+        )
+    )]
+    async fn extract_bytes(
+        &self,
+        content: &[u8],
+        mime_type: &str,
+        config: &ExtractionConfig,
+    ) -> Result<ExtractionResult> {
+        let text = String::from_utf8_lossy(content).into_owned();
+
+        let blocks = Self::parse(&text);
+        let title = blocks.iter().find_map(|block| match block {
+            MarkupBlock::Heading { text, .. } => Some(text.clone()),
+            _ => None,
+        });
+        let document = config
+            .needs_document_structure()
+            .then(|| markup::build_document_structure(&blocks));
+
+        Ok(ExtractionResult {
+            content: markup::render_text(&blocks),
+            mime_type: mime_type.to_string().into(),
+            metadata: Metadata {
+                title,
+                ..Default::default()
+            },
+            tables: markup::extract_tables(&blocks),
+            detected_languages: None,
+            chunks: None,
+            images: None,
+            djot_content: None,
+            pages: None,
+            elements: None,
+            ocr_elements: None,
+            document,
+            #[cfg(any(feature = "keywords-yake", feature = "keywords-rake"))]
+            extracted_keywords: None,
+            quality_score: None,
+            processing_warnings: Vec::new(),
+            annotations: None,
+        })
+    }
+
+    fn supported_mime_types(&self) -> &[&str] {
+        &["text/x-textile", "text/textile"]
+    }
+
+    fn priority(&self) -> i32 {
+        50
+    }
+}
+
+#[cfg(all(test, feature = "office"))]
+mod tests {
+    use super::*;
+    use crate::types::NodeContent;
+
+    const SAMPLE: &str = r#"h1. Release Notes
+
+Version *2.0* adds "streaming":https://example.com/streaming support[1].
+
+h2(#changes). Changes
+
+# Faster _parsing_
+# New @--watch@ flag
+## nested step
+
+bq. Upgrade at your own pace.
+
+bc[rust]. let x = 1;
+
+|_. Module |_. Status |
+| core | stable |
+|<. cli | beta |
+
+fn1. Available on Linux only.
+"#;
+
+    #[test]
+    fn test_textile_extractor_plugin_interface() {
+        let extractor = TextileExtractor::new();
+        assert_eq!(extractor.name(), "textile-extractor");
+        assert_eq!(extractor.version(), env!("CARGO_PKG_VERSION"));
+        assert_eq!(extractor.priority(), 50);
+        assert!(extractor.supported_mime_types().contains(&"text/x-textile"));
+        assert!(extractor.initialize().is_ok());
+        assert!(extractor.shutdown().is_ok());
+    }
+
+    #[test]
+    fn test_textile_blocks() {
+        let blocks = TextileExtractor::parse(SAMPLE);
+
+        assert_eq!(
+            blocks,
+            vec![
+                MarkupBlock::Heading {
+                    level: 1,
+                    text: "Release Notes".to_string()
+                },
+                MarkupBlock::Paragraph("Version 2.0 adds streaming support.".to_string()),
+                MarkupBlock::Heading {
+                    level: 2,
+                    text: "Changes".to_string()
+                },
+                MarkupBlock::List {
+                    ordered: true,
+                    items: vec![
+                        "Faster parsing".to_string(),
+                        "New --watch flag".to_string(),
+                        "nested step".to_string()
+                    ]
+                },
+                MarkupBlock::Quote {
+                    label: None,
+                    paragraphs: vec!["Upgrade at your own pace.".to_string()]
+                },
+                MarkupBlock::Code {
+                    language: Some("rust".to_string()),
+                    text: "let x = 1;".to_string()
+                },
+                MarkupBlock::Table(vec![
+                    vec!["Module".to_string(), "Status".to_string()],
+                    vec!["core".to_string(), "stable".to_string()],
+                    vec!["cli".to_string(), "beta".to_string()],
+                ]),
+                MarkupBlock::Paragraph("[1] Available on Linux only.".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_textile_extended_code_block() {
+        let content = "bc.. fn main() {\n\n    run();\n}\n\np. After code.";
+        let blocks = TextileExtractor::parse(content);
+        assert_eq!(
+            blocks[0],
+            MarkupBlock::Code {
+                language: None,
+                text: "fn main() {\n\n    run();\n}".to_string()
+            }
+        );
+        assert_eq!(blocks[1], MarkupBlock::Paragraph("After code.".to_string()));
+    }
+
+    #[test]
+    fn test_textile_inline_markup() {
+        assert_eq!(
+            TextileExtractor::convert_inline("See !logo.png(Logo)! and %{color:red}red% text, e-mail ok"),
+            "See Logo and red text, e-mail ok"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_textile_extract_bytes_with_document_structure() {
+        let config = ExtractionConfig {
+            include_document_structure: true,
+            ..Default::default()
+        };
+        let result = TextileExtractor::new()
+            .extract_bytes(SAMPLE.as_bytes(), "text/x-textile", &config)
+            .await
+            .unwrap();
+
+        assert_eq!(result.metadata.title.as_deref(), Some("Release Notes"));
+        assert!(result.content.contains("## Changes"));
+        assert_eq!(result.tables.len(), 1);
+
+        let document = result.document.expect("document structure requested");
+        assert!(document.validate().is_ok());
+        assert!(
+            document
+                .nodes
+                .iter()
+                .any(|n| matches!(n.content, NodeContent::Code { .. }))
+        );
+        assert!(document.nodes.iter().any(|n| matches!(n.content, NodeContent::Quote)));
+    }
+}
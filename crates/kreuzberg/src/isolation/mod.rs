@@ -0,0 +1,334 @@
+//! Process-isolated extraction.
+//!
+//! Native parsers (pdfium, Tesseract, calamine, ...) can hang, exhaust memory or segfault on
+//! malicious input, and [`panic_context`](crate::panic_context) only contains Rust panics.
+//! With isolation enabled on an engine, every document is extracted by a pre-started worker
+//! process instead of the calling process. The worker is killed when it exceeds
+//! [`IsolationConfig::timeout_secs`] or [`IsolationConfig::max_rss_bytes`], the call fails
+//! with [`KreuzbergError::Isolation`] and a fresh worker
+//! takes its place.
+//!
+//! Workers are started from [`IsolationConfig::worker_command`], by default the current
+//! executable, with the `KREUZBERG_ISOLATION_WORKER` environment variable set. That
+//! executable must call [`run_worker_if_requested`] at the very start of `main`; the
+//! `kreuzberg` CLI does so.
+//!
+//! Only the extractor runs in the worker. The host selects it from its engine, the worker
+//! runs the built-in extractor of that name, and the host then runs its engine's
+//! post-processors and validators on the returned result and caches it as usual. Extractors
+//! registered at runtime cannot run in a worker, so isolation is refused on an engine that has
+//! any, and an extraction fails if one is registered after isolation was enabled.
+//!
+//! Memory limits are best-effort: the host samples the worker's resident set size every 50 ms,
+//! which is only supported on Linux, so a worker can briefly exceed the limit or be killed
+//! only after a fast allocation spike has already hit the machine. For a hard cap, run the
+//! host under a cgroup memory limit or give [`IsolationConfig::worker_command`] a wrapper that
+//! sets one (`systemd-run --scope -p MemoryMax=...`, `prlimit --as=...`).
+//!
+//! Isolation cannot be enabled inside a worker; [`Kreuzberg::set_isolation`](crate::Kreuzberg::set_isolation)
+//! fails when `KREUZBERG_ISOLATION_WORKER` is set.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use kreuzberg::Kreuzberg;
+//! use kreuzberg::isolation::{IsolationConfig, run_worker_if_requested};
+//!
+//! #[tokio::main]
+//! async fn main() -> kreuzberg::Result<()> {
+//!     run_worker_if_requested();
+//!
+//!     let mut isolation = IsolationConfig::default();
+//!     isolation.timeout_secs = 60;
+//!     Kreuzberg::global().set_isolation(Some(isolation))?;
+//!
+//!     let result = kreuzberg::extract_file("untrusted.pdf", None, &Default::default()).await?;
+//!     println!("{}", result.content);
+//!     Ok(())
+//! }
+//! ```
+
+mod pool;
+mod worker;
+
+pub(crate) use pool::IsolationPool;
+pub use worker::run_worker_if_requested;
+
+use crate::core::config::ExtractionConfig;
+use crate::types::ExtractionResult;
+use crate::utils::framing::Framing;
+use crate::{KreuzbergError, Result};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::path::PathBuf;
+
+/// Environment variable marking a process as an isolation worker.
+pub const WORKER_ENV: &str = "KREUZBERG_ISOLATION_WORKER";
+
+/// Frames exchanged with workers; the largest message accepted from either side is 1 GiB.
+const FRAMING: Framing = Framing {
+    max_len: 1024 * 1024 * 1024,
+    peer: "isolation",
+};
+
+/// How to run isolated extraction workers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IsolationConfig {
+    /// Worker executable (default: the current executable).
+    #[serde(default)]
+    pub worker_command: Option<PathBuf>,
+    /// Arguments passed to the worker executable.
+    #[serde(default)]
+    pub worker_args: Vec<String>,
+    /// Number of worker processes, and therefore of concurrent extractions
+    /// (default: number of CPUs).
+    #[serde(default = "default_workers")]
+    pub workers: usize,
+    /// Seconds a single extraction may take before its worker is killed (default: 300).
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Resident memory a worker may use before it is killed (default: 4 GiB).
+    /// Enforced by sampling, on Linux only; see the [module docs](self). `None` disables the limit.
+    #[serde(default = "default_max_rss_bytes")]
+    pub max_rss_bytes: Option<u64>,
+    /// Seconds to wait for a new worker to report ready (default: 30).
+    #[serde(default = "default_startup_timeout_secs")]
+    pub startup_timeout_secs: u64,
+    /// Documents a worker extracts before it is replaced, bounding slow leaks in native
+    /// libraries (default: unlimited).
+    #[serde(default)]
+    pub max_documents_per_worker: Option<u64>,
+}
+
+fn default_workers() -> usize {
+    num_cpus::get()
+}
+
+fn default_timeout_secs() -> u64 {
+    300
+}
+
+fn default_max_rss_bytes() -> Option<u64> {
+    Some(4 * 1024 * 1024 * 1024)
+}
+
+fn default_startup_timeout_secs() -> u64 {
+    30
+}
+
+impl Default for IsolationConfig {
+    fn default() -> Self {
+        Self {
+            worker_command: None,
+            worker_args: Vec::new(),
+            workers: default_workers(),
+            timeout_secs: default_timeout_secs(),
+            max_rss_bytes: default_max_rss_bytes(),
+            startup_timeout_secs: default_startup_timeout_secs(),
+            max_documents_per_worker: None,
+        }
+    }
+}
+
+/// Message from the host to a worker.
+///
+/// Extraction requests name the built-in extractor the host selected; the worker runs only
+/// that extractor and leaves post-processing and validation to the host.
+#[derive(Serialize, Deserialize)]
+enum WorkerRequest {
+    Handshake,
+    ExtractFile {
+        path: PathBuf,
+        mime_type: String,
+        extractor: String,
+        config: ExtractionConfig,
+    },
+    ExtractBytes {
+        #[serde(with = "binary")]
+        content: Vec<u8>,
+        mime_type: String,
+        extractor: String,
+        config: ExtractionConfig,
+    },
+}
+
+/// Message from a worker to the host.
+#[derive(Serialize, Deserialize)]
+enum WorkerResponse {
+    Ready {
+        pid: u32,
+    },
+    /// The result as encoded by [`encode_result`](crate::core::extractor::encode_result).
+    Extracted(#[serde(with = "binary")] Vec<u8>),
+    Failed {
+        kind: FailureKind,
+        message: String,
+    },
+}
+
+/// Kind of a [`KreuzbergError`] returned by a worker.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+enum FailureKind {
+    Io,
+    Parsing,
+    Ocr,
+    Validation,
+    ImageProcessing,
+    MissingDependency,
+    UnsupportedFormat,
+    Other,
+}
+
+impl WorkerResponse {
+    fn extracted(mut result: ExtractionResult) -> Self {
+        match crate::core::extractor::encode_result(&mut result) {
+            Ok(encoded) => Self::Extracted(encoded),
+            Err(e) => Self::failed(e),
+        }
+    }
+
+    fn failed(error: KreuzbergError) -> Self {
+        let (kind, message) = match error {
+            KreuzbergError::Io(e) => (FailureKind::Io, e.to_string()),
+            KreuzbergError::Parsing { message, .. } => (FailureKind::Parsing, message),
+            KreuzbergError::Ocr { message, .. } => (FailureKind::Ocr, message),
+            KreuzbergError::Validation { message, .. } => (FailureKind::Validation, message),
+            KreuzbergError::ImageProcessing { message, .. } => (FailureKind::ImageProcessing, message),
+            KreuzbergError::MissingDependency(message) => (FailureKind::MissingDependency, message),
+            KreuzbergError::UnsupportedFormat(message) => (FailureKind::UnsupportedFormat, message),
+            other => (FailureKind::Other, other.to_string()),
+        };
+        Self::Failed { kind, message }
+    }
+}
+
+impl FailureKind {
+    fn into_error(self, message: String) -> KreuzbergError {
+        match self {
+            Self::Io => KreuzbergError::Io(std::io::Error::other(message)),
+            Self::Parsing => KreuzbergError::parsing(message),
+            Self::Ocr => KreuzbergError::ocr(message),
+            Self::Validation => KreuzbergError::validation(message),
+            Self::ImageProcessing => KreuzbergError::image_processing(message),
+            Self::MissingDependency => KreuzbergError::MissingDependency(message),
+            Self::UnsupportedFormat => KreuzbergError::UnsupportedFormat(message),
+            Self::Other => KreuzbergError::Other(message),
+        }
+    }
+}
+
+fn encode<T: Serialize>(message: &T) -> Result<Vec<u8>> {
+    rmp_serde::to_vec_named(message)
+        .map_err(|e| KreuzbergError::serialization(format!("Failed to encode isolation message: {}", e)))
+}
+
+fn decode<T: serde::de::DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+    rmp_serde::from_slice(bytes).map_err(|e| KreuzbergError::serialization(format!("Invalid isolation message: {}", e)))
+}
+
+/// Write one frame.
+fn write_frame(writer: &mut impl Write, payload: &[u8]) -> std::io::Result<()> {
+    FRAMING.write(writer, payload)
+}
+
+/// Read one frame.
+fn read_frame(reader: &mut impl Read) -> std::io::Result<Vec<u8>> {
+    FRAMING.read(reader)
+}
+
+/// Document bytes as a MessagePack binary value rather than an array of integers.
+mod binary {
+    use serde::{Deserializer, Serializer};
+
+    pub(super) fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(bytes)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = Vec<u8>;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("a byte array")
+            }
+
+            fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                Ok(v.to_vec())
+            }
+
+            fn visit_byte_buf<E: serde::de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+                Ok(v)
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(byte) = seq.next_element()? {
+                    bytes.push(byte);
+                }
+                Ok(bytes)
+            }
+        }
+
+        deserializer.deserialize_byte_buf(Visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_roundtrip() {
+        let mut buffer = Vec::new();
+        write_frame(&mut buffer, b"hello").unwrap();
+        write_frame(&mut buffer, b"").unwrap();
+
+        let mut reader = buffer.as_slice();
+        assert_eq!(read_frame(&mut reader).unwrap(), b"hello");
+        assert_eq!(read_frame(&mut reader).unwrap(), b"");
+        assert!(read_frame(&mut reader).is_err());
+    }
+
+    #[test]
+    fn test_request_content_is_binary() {
+        let request = WorkerRequest::ExtractBytes {
+            content: b"abc".to_vec(),
+            mime_type: "text/plain".to_string(),
+            extractor: "plain-text-extractor".to_string(),
+            config: ExtractionConfig::default(),
+        };
+        let encoded = encode(&request).unwrap();
+        assert!(encoded.windows(5).any(|w| w == [0xc4, 3, b'a', b'b', b'c']));
+
+        match decode::<WorkerRequest>(&encoded).unwrap() {
+            WorkerRequest::ExtractBytes { content, mime_type, .. } => {
+                assert_eq!(content, b"abc");
+                assert_eq!(mime_type, "text/plain");
+            }
+            _ => panic!("wrong request"),
+        }
+    }
+
+    #[test]
+    fn test_worker_errors_keep_their_kind() {
+        let roundtrip = |error| match decode(&encode(&WorkerResponse::failed(error)).unwrap()).unwrap() {
+            WorkerResponse::Failed { kind, message } => kind.into_error(message),
+            _ => panic!("wrong response"),
+        };
+
+        assert!(matches!(
+            roundtrip(KreuzbergError::parsing("bad xref")),
+            KreuzbergError::Parsing { message, .. } if message == "bad xref"
+        ));
+        assert!(matches!(
+            roundtrip(KreuzbergError::UnsupportedFormat("x/y".to_string())),
+            KreuzbergError::UnsupportedFormat(_)
+        ));
+        assert!(matches!(
+            roundtrip(KreuzbergError::cache("full")),
+            KreuzbergError::Other(message) if message == "Cache error: full"
+        ));
+    }
+}
@@ -0,0 +1,470 @@
+//! Host side of isolated extraction.
+//!
+//! Workers are plain child processes driven with blocking I/O on the blocking thread pool, so
+//! a pool can be shared by extractions running on different Tokio runtimes. While a request
+//! is in flight, the calling thread watches the clock and samples the worker's memory use,
+//! killing the worker once either limit is exceeded.
+
+use super::{IsolationConfig, WORKER_ENV, WorkerRequest, WorkerResponse, decode, encode, read_frame, write_frame};
+use crate::core::config::ExtractionConfig;
+use crate::error::{CancellationReason, IsolationFailure};
+use crate::types::ExtractionResult;
+use crate::{KreuzbergError, Result};
+use std::io::{BufRead, BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+
+/// How often a busy worker's memory use is sampled.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How long a worker that closed its stdout gets to exit before it is killed.
+const EXIT_GRACE: Duration = Duration::from_millis(200);
+
+/// Tells a busy worker's watcher whether to give up on the extraction.
+type Abort = Box<dyn Fn() -> Option<CancellationReason> + Send>;
+
+/// Pre-started worker processes of one engine.
+pub(crate) struct IsolationPool {
+    inner: Arc<Inner>,
+    permits: Semaphore,
+}
+
+struct Inner {
+    config: IsolationConfig,
+    command: PathBuf,
+    idle: Mutex<Vec<Worker>>,
+}
+
+struct Worker {
+    child: Child,
+    channel: Channel,
+    documents: u64,
+}
+
+struct Channel {
+    stdin: BufWriter<ChildStdin>,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl Channel {
+    fn exchange(&mut self, payload: &[u8]) -> std::io::Result<Vec<u8>> {
+        write_frame(&mut self.stdin, payload)?;
+        read_frame(&mut self.stdout)
+    }
+}
+
+impl Worker {
+    fn kill(mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+impl IsolationPool {
+    /// Start `config.workers` workers, blocking until all of them are ready.
+    ///
+    /// Fails inside a worker: a worker that enabled isolation would start workers of its own,
+    /// and each of those would do the same.
+    pub(crate) fn start(config: IsolationConfig) -> Result<Self> {
+        refuse_nested(std::env::var_os(WORKER_ENV).as_deref())?;
+        let command = match &config.worker_command {
+            Some(command) => command.clone(),
+            None => std::env::current_exe()?,
+        };
+        let workers = config.workers.max(1);
+        let inner = Arc::new(Inner {
+            config,
+            command,
+            idle: Mutex::new(Vec::with_capacity(workers)),
+        });
+
+        let started: Vec<Result<Worker>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers).map(|_| scope.spawn(|| inner.spawn())).collect();
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|_| Err(KreuzbergError::Other("Worker start-up panicked".to_string())))
+                })
+                .collect()
+        });
+        for worker in started {
+            inner.release(worker?);
+        }
+
+        Ok(Self {
+            inner,
+            permits: Semaphore::new(workers),
+        })
+    }
+
+    /// Extract a file in a worker with the built-in extractor named `extractor`, without
+    /// post-processing.
+    pub(crate) async fn extract_file(
+        &self,
+        path: &Path,
+        mime_type: &str,
+        extractor: &str,
+        config: &ExtractionConfig,
+    ) -> Result<ExtractionResult> {
+        let request = WorkerRequest::ExtractFile {
+            path: path.to_path_buf(),
+            mime_type: mime_type.to_string(),
+            extractor: extractor.to_string(),
+            config: worker_config(config),
+        };
+        self.extract(request, config).await
+    }
+
+    /// Extract a byte array in a worker with the built-in extractor named `extractor`,
+    /// without post-processing.
+    pub(crate) async fn extract_bytes(
+        &self,
+        content: &[u8],
+        mime_type: &str,
+        extractor: &str,
+        config: &ExtractionConfig,
+    ) -> Result<ExtractionResult> {
+        let request = WorkerRequest::ExtractBytes {
+            content: content.to_vec(),
+            mime_type: mime_type.to_string(),
+            extractor: extractor.to_string(),
+            config: worker_config(config),
+        };
+        self.extract(request, config).await
+    }
+
+    async fn extract(&self, request: WorkerRequest, config: &ExtractionConfig) -> Result<ExtractionResult> {
+        let _permit = self
+            .permits
+            .acquire()
+            .await
+            .map_err(|_| KreuzbergError::Other("Isolation pool has been shut down".to_string()))?;
+        let payload = encode(&request)?;
+        let inner = Arc::clone(&self.inner);
+        let abort = abort_condition(config);
+        tokio::task::spawn_blocking(move || inner.extract(&payload, abort))
+            .await
+            .map_err(|e| KreuzbergError::Other(format!("Isolated extraction task failed: {}", e)))?
+    }
+}
+
+/// When to kill a busy worker because its extraction was cancelled.
+///
+/// Workers enforce `timeout_secs` themselves, so with partial results they are given the
+/// chance to return what they extracted before the deadline. Explicit cancellation is not
+/// forwarded to workers.
+fn abort_condition(config: &ExtractionConfig) -> Option<Abort> {
+    let token = config.cancellation.clone()?;
+    let partial = config.partial_results_on_cancel;
+    Some(Box::new(move || {
+        token
+            .reason()
+            .filter(|reason| !(partial && *reason == CancellationReason::TimedOut))
+    }))
+}
+
+/// The host caches results itself, so workers never need to.
+fn worker_config(config: &ExtractionConfig) -> ExtractionConfig {
+    ExtractionConfig {
+        use_cache: false,
+        ..config.clone()
+    }
+}
+
+impl Inner {
+    fn extract(self: &Arc<Self>, payload: &[u8], abort: Option<Abort>) -> Result<ExtractionResult> {
+        let worker = match self.take_idle() {
+            Some(worker) => worker,
+            None => self.spawn()?,
+        };
+
+        let timeout = Duration::from_secs(self.config.timeout_secs);
+        let (mut worker, response) = match self.exchange(worker, payload, timeout, "Extraction", abort.as_deref()) {
+            Ok(exchanged) => exchanged,
+            Err(e) => {
+                self.replenish();
+                return Err(e);
+            }
+        };
+        worker.documents += 1;
+
+        match response {
+            WorkerResponse::Extracted(encoded) => {
+                self.release(worker);
+                crate::core::extractor::decode_result(&encoded)
+            }
+            WorkerResponse::Failed { kind, message } => {
+                self.release(worker);
+                Err(kind.into_error(message))
+            }
+            WorkerResponse::Ready { .. } => {
+                worker.kill();
+                self.replenish();
+                Err(isolation_error(
+                    IsolationFailure::Crash,
+                    "Worker answered an extraction request with a handshake",
+                ))
+            }
+        }
+    }
+
+    /// Send one request to `worker` and wait for the response, killing the worker if it takes
+    /// longer than `timeout`, uses too much memory, breaks the protocol or `abort` fires.
+    fn exchange(
+        &self,
+        worker: Worker,
+        payload: &[u8],
+        timeout: Duration,
+        what: &str,
+        abort: Option<&(dyn Fn() -> Option<CancellationReason> + Send)>,
+    ) -> Result<(Worker, WorkerResponse)> {
+        let Worker {
+            mut child,
+            mut channel,
+            documents,
+        } = worker;
+        let pid = child.id();
+        let deadline = Instant::now() + timeout;
+        let mut cancelled = None;
+
+        let outcome = std::thread::scope(|scope| {
+            let (tx, rx) = mpsc::channel();
+            let channel = &mut channel;
+            scope.spawn(move || {
+                let _ = tx.send(channel.exchange(payload));
+            });
+
+            loop {
+                let now = Instant::now();
+                if now >= deadline {
+                    let _ = child.kill();
+                    return Err((
+                        IsolationFailure::Timeout,
+                        format!("{} did not finish within {}s", what, timeout.as_secs()),
+                    ));
+                }
+                match rx.recv_timeout(POLL_INTERVAL.min(deadline - now)) {
+                    Ok(Ok(frame)) => return Ok(frame),
+                    Ok(Err(e)) => return Err((IsolationFailure::Crash, e.to_string())),
+                    Err(RecvTimeoutError::Disconnected) => {
+                        return Err((IsolationFailure::Crash, "worker I/O thread exited".to_string()));
+                    }
+                    Err(RecvTimeoutError::Timeout) => {
+                        if let Some(reason) = abort.and_then(|abort| abort()) {
+                            let _ = child.kill();
+                            cancelled = Some(reason);
+                            return Err((IsolationFailure::Timeout, format!("{} was {}", what, reason)));
+                        }
+                        if let (Some(limit), Some(used)) = (self.config.max_rss_bytes, resident_bytes(pid))
+                            && used > limit
+                        {
+                            let _ = child.kill();
+                            return Err((
+                                IsolationFailure::MemoryLimit,
+                                format!("Worker used {} bytes, more than the {} byte limit", used, limit),
+                            ));
+                        }
+                    }
+                }
+            }
+        });
+
+        let failure = match outcome.map(|frame| decode::<WorkerResponse>(&frame)) {
+            Ok(Ok(response)) => {
+                let worker = Worker {
+                    child,
+                    channel,
+                    documents,
+                };
+                return Ok((worker, response));
+            }
+            Ok(Err(e)) => (IsolationFailure::Crash, e.to_string()),
+            Err(failure) => failure,
+        };
+        if let Some(reason) = cancelled {
+            let _ = child.wait();
+            return Err(KreuzbergError::Cancelled(reason));
+        }
+
+        let message = match failure {
+            (IsolationFailure::Crash, cause) => exit_reason(&mut child, &cause),
+            (_, message) => {
+                let _ = child.wait();
+                message
+            }
+        };
+        tracing::warn!(worker = pid, kind = %failure.0, "Isolation worker failed: {}", message);
+        Err(isolation_error(failure.0, message))
+    }
+
+    fn spawn(&self) -> Result<Worker> {
+        let mut child = Command::new(&self.command)
+            .args(&self.config.worker_args)
+            .env(WORKER_ENV, "1")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| {
+                isolation_error(
+                    IsolationFailure::Crash,
+                    format!("Failed to start worker '{}': {}", self.command.display(), e),
+                )
+            })?;
+        let (Some(stdin), Some(stdout), Some(stderr)) = (child.stdin.take(), child.stdout.take(), child.stderr.take())
+        else {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(KreuzbergError::Other("Worker stdio was not captured".to_string()));
+        };
+
+        let pid = child.id();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stderr).lines().map_while(std::result::Result::ok) {
+                tracing::debug!(worker = pid, "{}", line);
+            }
+        });
+
+        let worker = Worker {
+            child,
+            channel: Channel {
+                stdin: BufWriter::new(stdin),
+                stdout: BufReader::new(stdout),
+            },
+            documents: 0,
+        };
+        let timeout = Duration::from_secs(self.config.startup_timeout_secs);
+        let hint = "make sure the worker executable calls kreuzberg::isolation::run_worker_if_requested()";
+        match self.exchange(worker, &encode(&WorkerRequest::Handshake)?, timeout, "Handshake", None) {
+            Ok((worker, WorkerResponse::Ready { .. })) => Ok(worker),
+            Ok((worker, _)) => {
+                worker.kill();
+                Err(isolation_error(
+                    IsolationFailure::Crash,
+                    format!("Worker did not answer the handshake; {}", hint),
+                ))
+            }
+            Err(KreuzbergError::Isolation { kind, message }) => {
+                Err(isolation_error(kind, format!("{}; {}", message, hint)))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn take_idle(&self) -> Option<Worker> {
+        self.idle.lock().unwrap_or_else(|e| e.into_inner()).pop()
+    }
+
+    /// Return a worker to the pool, or retire it once it has served its share of documents.
+    fn release(self: &Arc<Self>, worker: Worker) {
+        let retire = self
+            .config
+            .max_documents_per_worker
+            .is_some_and(|max| worker.documents >= max);
+        if retire {
+            worker.kill();
+            self.replenish();
+            return;
+        }
+
+        let mut idle = self.idle.lock().unwrap_or_else(|e| e.into_inner());
+        if idle.len() < self.config.workers.max(1) {
+            idle.push(worker);
+        } else {
+            drop(idle);
+            worker.kill();
+        }
+    }
+
+    /// Start a replacement worker in the background.
+    fn replenish(self: &Arc<Self>) {
+        let inner = Arc::clone(self);
+        std::thread::spawn(move || match inner.spawn() {
+            Ok(worker) => inner.release(worker),
+            Err(e) => tracing::warn!("Failed to start replacement isolation worker: {}", e),
+        });
+    }
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        let workers = std::mem::take(&mut *self.idle.lock().unwrap_or_else(|e| e.into_inner()));
+        for worker in workers {
+            worker.kill();
+        }
+    }
+}
+
+/// Describe why a worker stopped answering, killing it if it is still running.
+fn exit_reason(child: &mut Child, cause: &str) -> String {
+    let deadline = Instant::now() + EXIT_GRACE;
+    while Instant::now() < deadline {
+        if let Ok(Some(status)) = child.try_wait() {
+            return format!("Worker exited unexpectedly ({})", status);
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+    format!("Worker stopped responding: {}", cause)
+}
+
+/// Reject starting workers from a process that is itself a worker.
+fn refuse_nested(worker_env: Option<&std::ffi::OsStr>) -> Result<()> {
+    if worker_env.is_some() {
+        return Err(KreuzbergError::validation(format!(
+            "Isolation cannot be enabled in an isolation worker ({} is set)",
+            WORKER_ENV
+        )));
+    }
+    Ok(())
+}
+
+fn isolation_error(kind: IsolationFailure, message: impl Into<String>) -> KreuzbergError {
+    KreuzbergError::Isolation {
+        kind,
+        message: message.into(),
+    }
+}
+
+/// Resident set size of process `pid` in bytes.
+///
+/// Sampled every [`POLL_INTERVAL`], so an allocation spike between two samples is not seen;
+/// the memory limit is best-effort rather than a hard cap.
+#[cfg(target_os = "linux")]
+fn resident_bytes(pid: u32) -> Option<u64> {
+    let status = std::fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    let kib = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))?
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(kib * 1024)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn resident_bytes(_pid: u32) -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_refuse_nested_in_worker() {
+        assert!(refuse_nested(None).is_ok());
+        let err = refuse_nested(Some(std::ffi::OsStr::new("1"))).unwrap_err();
+        assert!(matches!(err, KreuzbergError::Validation { .. }));
+        assert!(err.to_string().contains(WORKER_ENV));
+    }
+}
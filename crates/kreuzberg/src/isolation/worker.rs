@@ -0,0 +1,158 @@
+//! Worker side of isolated extraction.
+
+use super::{WORKER_ENV, WorkerRequest, WorkerResponse, decode, encode, read_frame, write_frame};
+use crate::core::extractor::block_on;
+use crate::plugins::DocumentExtractor;
+use crate::{KreuzbergError, Result};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::sync::Arc;
+
+/// Serve extraction requests and exit if this process was started as an isolation worker.
+///
+/// Returns immediately otherwise. Call it at the start of `main` in every executable used as
+/// [`IsolationConfig::worker_command`](super::IsolationConfig::worker_command). Workers run
+/// only the built-in extractors.
+pub fn run_worker_if_requested() {
+    if std::env::var_os(WORKER_ENV).is_none() {
+        return;
+    }
+
+    let code = match protocol_output().and_then(|output| serve(std::io::stdin().lock(), output)) {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("kreuzberg isolation worker: {}", e);
+            1
+        }
+    };
+    std::process::exit(code);
+}
+
+/// Handle requests from `reader` until the host closes it.
+fn serve(reader: impl Read, writer: impl Write) -> std::io::Result<()> {
+    let mut reader = BufReader::new(reader);
+    let mut writer = BufWriter::new(writer);
+    loop {
+        let frame = match read_frame(&mut reader) {
+            Ok(frame) => frame,
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(()),
+            Err(e) => return Err(e),
+        };
+
+        let response = match decode(&frame) {
+            Ok(request) => handle(request),
+            Err(e) => WorkerResponse::failed(e),
+        };
+        let payload = encode(&response)
+            .or_else(|e| encode(&WorkerResponse::failed(e)))
+            .map_err(std::io::Error::other)?;
+        write_frame(&mut writer, &payload)?;
+    }
+}
+
+fn handle(request: WorkerRequest) -> WorkerResponse {
+    let result = match request {
+        WorkerRequest::Handshake => {
+            return WorkerResponse::Ready {
+                pid: std::process::id(),
+            };
+        }
+        WorkerRequest::ExtractFile {
+            path,
+            mime_type,
+            extractor,
+            config,
+        } => builtin_extractor(&extractor)
+            .and_then(|extractor| block_on(extractor.extract_file(&path, &mime_type, &config))),
+        WorkerRequest::ExtractBytes {
+            content,
+            mime_type,
+            extractor,
+            config,
+        } => builtin_extractor(&extractor)
+            .and_then(|extractor| block_on(extractor.extract_bytes(&content, &mime_type, &config))),
+    };
+    match result {
+        Ok(result) => WorkerResponse::extracted(result),
+        Err(e) => WorkerResponse::failed(e),
+    }
+}
+
+/// The extractor named `name` in the worker's global registry.
+fn builtin_extractor(name: &str) -> Result<Arc<dyn DocumentExtractor>> {
+    crate::extractors::ensure_initialized()?;
+    let registry = crate::plugins::registry::get_document_extractor_registry();
+    let registry = registry
+        .read()
+        .map_err(|e| KreuzbergError::Other(format!("Document extractor registry lock poisoned: {}", e)))?;
+    registry
+        .get_by_name(name)
+        .ok_or_else(|| KreuzbergError::Other(format!("Extractor '{}' is not available in the worker", name)))
+}
+
+/// Take over stdout for the protocol and send anything else written to it, e.g. by native
+/// libraries, to stderr.
+#[cfg(unix)]
+#[allow(unsafe_code)]
+fn protocol_output() -> std::io::Result<Box<dyn Write>> {
+    use std::os::fd::AsFd;
+
+    let output = std::io::stdout().as_fd().try_clone_to_owned()?;
+    // SAFETY: dup2 only replaces descriptor 1, which no Rust object owns exclusively.
+    if unsafe { libc::dup2(libc::STDERR_FILENO, libc::STDOUT_FILENO) } == -1 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(Box::new(std::fs::File::from(output)))
+}
+
+#[cfg(not(unix))]
+fn protocol_output() -> std::io::Result<Box<dyn Write>> {
+    Ok(Box::new(std::io::stdout()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::ExtractionConfig;
+
+    #[test]
+    fn test_serve_handles_requests_until_eof() {
+        let config = ExtractionConfig {
+            use_cache: false,
+            ..Default::default()
+        };
+        let mut input = Vec::new();
+        for request in [
+            WorkerRequest::Handshake,
+            WorkerRequest::ExtractBytes {
+                content: b"isolated text".to_vec(),
+                mime_type: "text/plain".to_string(),
+                extractor: "plain-text-extractor".to_string(),
+                config: config.clone(),
+            },
+            WorkerRequest::ExtractBytes {
+                content: Vec::new(),
+                mime_type: "text/plain".to_string(),
+                extractor: "unknown-extractor".to_string(),
+                config,
+            },
+        ] {
+            write_frame(&mut input, &encode(&request).unwrap()).unwrap();
+        }
+
+        let mut output = Vec::new();
+        serve(input.as_slice(), &mut output).unwrap();
+
+        let mut reader = output.as_slice();
+        let mut next = || decode::<WorkerResponse>(&read_frame(&mut reader).unwrap()).unwrap();
+        assert!(matches!(next(), WorkerResponse::Ready { pid } if pid == std::process::id()));
+        match next() {
+            WorkerResponse::Extracted(encoded) => {
+                let result = crate::core::extractor::decode_result(&encoded).unwrap();
+                assert!(result.content.contains("isolated text"));
+            }
+            _ => panic!("expected a result"),
+        }
+        assert!(matches!(next(), WorkerResponse::Failed { .. }));
+        assert!(reader.is_empty());
+    }
+}
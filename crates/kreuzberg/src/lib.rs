@@ -21,6 +21,7 @@
 //!
 //! - **Core Module** (`core`): Main extraction orchestration, MIME detection, config loading
 //! - **Plugin System**: Language-agnostic plugin architecture
+//! - **Engine** (`engine`): Instance-scoped plugin registries and configuration, e.g. one per tenant
 //! - **Extractors**: Format-specific extraction (PDF, images, Office docs, email, etc.)
 //! - **OCR**: Multiple OCR backend support (Tesseract, EasyOCR, PaddleOCR)
 //!
@@ -36,9 +37,13 @@
 
 pub mod cache;
 pub mod core;
+pub mod diff;
+pub mod engine;
 pub mod error;
 pub mod extraction;
 pub mod extractors;
+#[cfg(feature = "tokio-runtime")]
+pub mod isolation;
 pub mod panic_context;
 pub mod plugins;
 pub mod text;
@@ -51,6 +56,9 @@ pub mod api;
 #[cfg(feature = "mcp")]
 pub mod mcp;
 
+#[cfg(feature = "metrics")]
+pub mod metrics;
+
 #[cfg(feature = "chunking")]
 pub mod chunking;
 
@@ -85,9 +93,11 @@ pub mod paddle_ocr;
 #[cfg(feature = "pdf")]
 pub mod pdf;
 
-pub use error::{KreuzbergError, Result};
+pub use engine::Kreuzberg;
+pub use error::{CancellationReason, IsolationFailure, KreuzbergError, Result};
 pub use types::*;
 
+pub use core::cancellation::CancellationToken;
 #[cfg(feature = "tokio-runtime")]
 pub use core::extractor::{batch_extract_bytes, batch_extract_file};
 pub use core::extractor::{extract_bytes, extract_file};
@@ -98,8 +108,9 @@ pub use core::extractor::{batch_extract_bytes_sync, extract_bytes_sync};
 pub use core::extractor::{batch_extract_file_sync, extract_file_sync};
 
 pub use core::config::{
-    ChunkerType, ChunkingConfig, EmbeddingConfig, EmbeddingModelType, ExtractionConfig, ImageExtractionConfig,
-    LanguageDetectionConfig, OcrConfig, OutputFormat, PageConfig, PostProcessorConfig, TokenReductionConfig,
+    ChunkSizing, ChunkerType, ChunkingConfig, EmbeddingConfig, EmbeddingModelType, ExtractionConfig,
+    ImageExtractionConfig, LanguageDetectionConfig, OcrConfig, OutputFormat, PageConfig, PostProcessorConfig,
+    TokenReductionConfig,
 };
 
 #[cfg(feature = "api")]
@@ -88,6 +88,12 @@ pub fn map_kreuzberg_error_to_mcp(error: KreuzbergError) -> McpError {
 
         KreuzbergError::LockPoisoned(msg) => McpError::internal_error(format!("Internal lock poisoned: {}", msg), None),
 
+        KreuzbergError::Isolation { kind, message } => {
+            McpError::internal_error(format!("Isolated extraction failed ({}): {}", kind, message), None)
+        }
+
+        KreuzbergError::Cancelled(reason) => McpError::internal_error(format!("Extraction {}", reason), None),
+
         KreuzbergError::Other(msg) => McpError::internal_error(msg, None),
     }
 }
@@ -373,6 +373,8 @@ mod tests {
                     total_chunks: 1,
                     first_page: None,
                     last_page: None,
+                    heading_path: None,
+                    node_ids: None,
                 },
             }]),
             images: None,
@@ -11,6 +11,13 @@
 //! - **detect_mime_type**: Detect MIME type of a file
 //! - **cache_stats**: Get cache statistics
 //! - **cache_clear**: Clear the cache
+//! - **chunk**: Split text or a document (optionally a page range) into chunks
+//! - **embed**: Generate embeddings for texts
+//! - **extract_keywords**: Extract keywords from text or a document
+//! - **get_document_structure**: Navigate a document's structure by section or page range
+//!
+//! Extraction results are also exposed as MCP resources (`kreuzberg://doc/{hash}/...`)
+//! so large documents can be read in parts; see the `resources` module.
 //!
 //! # Example
 //!
@@ -27,7 +34,9 @@
 mod errors;
 mod format;
 mod params;
+mod resources;
 mod server;
+mod structure;
 
 // Re-export public API for backward compatibility
 pub use server::{KreuzbergMcp, start_mcp_server, start_mcp_server_with_config};
@@ -35,7 +44,10 @@ pub use server::{KreuzbergMcp, start_mcp_server, start_mcp_server_with_config};
 #[cfg(feature = "mcp-http")]
 pub use server::{start_mcp_server_http, start_mcp_server_http_with_config};
 
-pub use params::{BatchExtractFilesParams, DetectMimeTypeParams, ExtractBytesParams, ExtractFileParams};
+pub use params::{
+    BatchExtractFilesParams, ChunkParams, DetectMimeTypeParams, DocumentStructureParams, EmbedParams,
+    ExtractBytesParams, ExtractFileParams, ExtractKeywordsParams,
+};
 
 #[doc(hidden)]
 pub use errors::map_kreuzberg_error_to_mcp;
@@ -54,6 +54,93 @@ fn default_use_content() -> bool {
     true
 }
 
+/// Request parameters for chunking text or a document.
+#[derive(Debug, serde::Deserialize, serde::Serialize, schemars::JsonSchema)]
+pub struct ChunkParams {
+    /// Text to chunk (provide either `text` or `path`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// Path to a document to extract and chunk (provide either `text` or `path`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Optional MIME type hint for `path` (auto-detected if not provided)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    /// Maximum characters per chunk (default: 1000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_characters: Option<usize>,
+    /// Overlap between consecutive chunks in characters (default: 200)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overlap: Option<usize>,
+    /// Chunker type: "text" or "markdown" (default: "text")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chunker_type: Option<String>,
+    /// Only return chunks that end on or after this page (1-indexed, `path` only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_page: Option<usize>,
+    /// Only return chunks that start on or before this page (1-indexed, `path` only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_page: Option<usize>,
+    /// Extraction configuration used for `path` (JSON object)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config: Option<serde_json::Value>,
+}
+
+/// Request parameters for embedding generation.
+#[derive(Debug, serde::Deserialize, serde::Serialize, schemars::JsonSchema)]
+pub struct EmbedParams {
+    /// Texts to embed
+    pub texts: Vec<String>,
+    /// Embedding configuration (JSON object, e.g. `{"model": {"type": "preset", "name": "balanced"}}`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config: Option<serde_json::Value>,
+}
+
+/// Request parameters for keyword extraction.
+#[derive(Debug, serde::Deserialize, serde::Serialize, schemars::JsonSchema)]
+pub struct ExtractKeywordsParams {
+    /// Text to analyze (provide either `text` or `path`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// Path to a document to extract and analyze (provide either `text` or `path`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Optional MIME type hint for `path` (auto-detected if not provided)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    /// Keyword configuration (JSON object, e.g. `{"algorithm": "yake", "max_keywords": 10}`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config: Option<serde_json::Value>,
+}
+
+/// Request parameters for document structure retrieval.
+#[derive(Debug, serde::Deserialize, serde::Serialize, schemars::JsonSchema)]
+pub struct DocumentStructureParams {
+    /// Path to the document
+    pub path: String,
+    /// Optional MIME type hint (auto-detected if not provided)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    /// Return only this section: a node id, node index, or heading text
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub section: Option<String>,
+    /// Only return nodes on or after this page (1-indexed)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_page: Option<u32>,
+    /// Only return nodes on or before this page (1-indexed)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_page: Option<u32>,
+    /// Maximum tree depth to return (default: unlimited)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_depth: Option<usize>,
+    /// Return full node text instead of short previews (default: false)
+    #[serde(default)]
+    pub include_text: bool,
+    /// Extraction configuration (JSON object)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config: Option<serde_json::Value>,
+}
+
 /// Empty parameters for tools that take no arguments.
 ///
 /// This generates `{"type": "object", "properties": {}}` which is required by
@@ -190,4 +277,44 @@ mod tests {
         let params: EmptyParams = serde_json::from_str("{}").unwrap();
         let _ = params;
     }
+
+    #[test]
+    fn test_chunk_params_defaults() {
+        let params: ChunkParams = serde_json::from_str(r#"{"text": "Hello"}"#).unwrap();
+
+        assert_eq!(params.text.as_deref(), Some("Hello"));
+        assert_eq!(params.path, None);
+        assert_eq!(params.max_characters, None);
+        assert_eq!(params.first_page, None);
+    }
+
+    #[test]
+    fn test_embed_params_requires_texts() {
+        assert!(serde_json::from_str::<EmbedParams>("{}").is_err());
+
+        let params: EmbedParams = serde_json::from_str(r#"{"texts": ["a", "b"]}"#).unwrap();
+        assert_eq!(params.texts.len(), 2);
+        assert_eq!(params.config, None);
+    }
+
+    #[test]
+    fn test_extract_keywords_params_defaults() {
+        let params: ExtractKeywordsParams = serde_json::from_str(r#"{"path": "/test.pdf"}"#).unwrap();
+
+        assert_eq!(params.path.as_deref(), Some("/test.pdf"));
+        assert_eq!(params.text, None);
+        assert_eq!(params.config, None);
+    }
+
+    #[test]
+    fn test_document_structure_params_defaults() {
+        let json = r#"{"path": "/test.pdf", "section": "Introduction", "first_page": 2}"#;
+        let params: DocumentStructureParams = serde_json::from_str(json).unwrap();
+
+        assert_eq!(params.section.as_deref(), Some("Introduction"));
+        assert_eq!(params.first_page, Some(2));
+        assert_eq!(params.last_page, None);
+        assert_eq!(params.max_depth, None);
+        assert!(!params.include_text);
+    }
 }
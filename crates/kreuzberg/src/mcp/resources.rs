@@ -0,0 +1,743 @@
+//! MCP resources for extraction results.
+//!
+//! Every result produced by an extraction tool is registered under a content hash and
+//! exposed as a set of resources, so agents can read a large document piece by piece
+//! instead of receiving it in a single tool response:
+//!
+//! - `kreuzberg://doc/{hash}` – summary: metadata, counts and resource URIs
+//! - `kreuzberg://doc/{hash}/content/{n}` – the content in parts of [`CONTENT_PART_CHARS`]
+//! - `kreuzberg://doc/{hash}/page/{n}` – one page (when page extraction is enabled)
+//! - `kreuzberg://doc/{hash}/tables` – all tables as JSON
+//! - `kreuzberg://doc/{hash}/images/{i}` – one extracted image as a blob
+//!
+//! When the server's configuration has `use_cache` set, results are persisted in the `mcp`
+//! cache (under `KREUZBERG_CACHE_DIR` or the per-user cache directory), so resource URIs stay
+//! readable across server restarts and HTTP sessions. Image bytes are stored as separate raw
+//! entries rather than inside the result JSON. A few recent results are also kept in memory,
+//! bounded by count and size; without the cache, memory is the only copy and documents
+//! evicted from it disappear from the listing. `resources/list` covers the last
+//! [`MAX_DOCUMENTS`] documents registered by this server instance and is paginated with an
+//! opaque cursor.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+};
+
+use base64::prelude::*;
+use bytes::Bytes;
+use parking_lot::RwLock;
+use rmcp::model::{
+    AnnotateAble, Content, RawResource, RawResourceTemplate, Resource, ResourceContents, ResourceTemplate,
+};
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+
+use super::format::format_extraction_result;
+use crate::{
+    cache::{CacheLimits, GenericCache, default_backend, shared_user_cache},
+    core::config::ExtractionConfig,
+    types::ExtractionResult,
+};
+
+/// URI prefix of all document resources.
+pub(super) const URI_PREFIX: &str = "kreuzberg://doc/";
+
+/// Characters per `content/{n}` part.
+pub(super) const CONTENT_PART_CHARS: usize = 20_000;
+
+/// Largest combined content returned inline by the extraction tools.
+pub(super) const INLINE_CONTENT_CHARS: usize = 50_000;
+
+/// Characters of content previewed when a result is not returned inline.
+const PREVIEW_CHARS: usize = 1_000;
+
+/// Resources returned per `resources/list` page.
+const LIST_PAGE_SIZE: usize = 100;
+
+/// Results kept in memory in addition to the cache.
+const MEMORY_ENTRIES: usize = 8;
+
+/// Approximate size of the results kept in memory, with or without a cache.
+const MEMORY_BYTES: usize = 256 * 1024 * 1024;
+
+/// Documents listed by `resources/list`; older ones are dropped from the listing.
+const MAX_DOCUMENTS: usize = 1_000;
+
+/// Cache holding persisted results.
+const CACHE_TYPE: &str = "mcp";
+
+/// Summary of a registered result, enough to list its resources without loading it.
+#[derive(Debug, Clone)]
+pub(super) struct DocumentEntry {
+    pub hash: String,
+    pub name: String,
+    content_parts: usize,
+    pages: Vec<usize>,
+    tables: usize,
+    images: Vec<(usize, String)>,
+}
+
+impl DocumentEntry {
+    fn new(hash: String, name: String, result: &ExtractionResult) -> Self {
+        Self {
+            hash,
+            name,
+            content_parts: result.content.chars().count().div_ceil(CONTENT_PART_CHARS).max(1),
+            pages: result
+                .pages
+                .as_ref()
+                .map(|pages| pages.iter().map(|page| page.page_number).collect())
+                .unwrap_or_default(),
+            tables: result.tables.len(),
+            images: result
+                .images
+                .as_ref()
+                .map(|images| {
+                    images
+                        .iter()
+                        .enumerate()
+                        .map(|(i, image)| (i, image_mime_type(&image.format)))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    pub(super) fn uri(&self) -> String {
+        format!("{}{}", URI_PREFIX, self.hash)
+    }
+
+    /// The summary resource, linked from tool responses.
+    pub(super) fn summary_resource(&self) -> RawResource {
+        RawResource {
+            title: Some(format!("{} (summary)", self.name)),
+            description: Some("Metadata, counts and resource URIs of an extraction result".to_string()),
+            mime_type: Some("application/json".to_string()),
+            ..RawResource::new(self.uri(), self.name.clone())
+        }
+    }
+
+    /// Every resource of this document, in listing order.
+    fn resources(&self) -> Vec<Resource> {
+        let uri = self.uri();
+        let resource = |path: String, title: String, mime_type: &str| {
+            RawResource {
+                title: Some(title),
+                mime_type: Some(mime_type.to_string()),
+                ..RawResource::new(format!("{}/{}", uri, path), self.name.clone())
+            }
+            .no_annotation()
+        };
+
+        let mut resources = vec![self.summary_resource().no_annotation()];
+        resources.extend((1..=self.content_parts).map(|n| {
+            resource(
+                format!("content/{}", n),
+                format!("{} (content part {} of {})", self.name, n, self.content_parts),
+                "text/plain",
+            )
+        }));
+        resources.extend(self.pages.iter().map(|n| {
+            resource(
+                format!("page/{}", n),
+                format!("{} (page {})", self.name, n),
+                "text/plain",
+            )
+        }));
+        if self.tables > 0 {
+            resources.push(resource(
+                "tables".to_string(),
+                format!("{} ({} tables)", self.name, self.tables),
+                "application/json",
+            ));
+        }
+        resources.extend(
+            self.images
+                .iter()
+                .map(|(i, mime)| resource(format!("images/{}", i), format!("{} (image {})", self.name, i), mime)),
+        );
+        resources
+    }
+
+    /// JSON summary served at `kreuzberg://doc/{hash}` and returned for large tool results.
+    pub(super) fn summary(&self, result: &ExtractionResult) -> Value {
+        let uri = self.uri();
+        json!({
+            "uri": uri,
+            "name": self.name,
+            "mime_type": result.mime_type,
+            "metadata": result.metadata,
+            "content_chars": result.content.chars().count(),
+            "content_parts": (1..=self.content_parts).map(|n| format!("{}/content/{}", uri, n)).collect::<Vec<_>>(),
+            "pages": self.pages.len(),
+            "page_uri_template": (!self.pages.is_empty()).then(|| format!("{}/page/{{n}}", uri)),
+            "tables": self.tables,
+            "tables_uri": (self.tables > 0).then(|| format!("{}/tables", uri)),
+            "images": self.images.len(),
+            "image_uri_template": (!self.images.is_empty()).then(|| format!("{}/images/{{i}}", uri)),
+        })
+    }
+}
+
+/// Registry of extraction results exposed as MCP resources.
+pub(super) struct ResourceStore {
+    cache: Option<Arc<GenericCache>>,
+    documents: RwLock<VecDeque<DocumentEntry>>,
+    memory: RwLock<RecentResults>,
+}
+
+/// Recently used results with their approximate size, evicted least recently used first.
+#[derive(Default)]
+struct RecentResults {
+    results: HashMap<String, (Arc<ExtractionResult>, usize)>,
+    order: VecDeque<String>,
+    bytes: usize,
+}
+
+impl RecentResults {
+    /// Move `hash` to the most recently used end.
+    fn touch(&mut self, hash: &str) {
+        if let Some(position) = self.order.iter().position(|entry| entry == hash)
+            && let Some(entry) = self.order.remove(position)
+        {
+            self.order.push_back(entry);
+        }
+    }
+}
+
+impl ResourceStore {
+    /// Create a store for a server extracting with `config`.
+    ///
+    /// With `use_cache`, results are persisted in the `mcp` cache under `KREUZBERG_CACHE_DIR`
+    /// or the per-user cache directory, in `config.cache_backend`. Otherwise, or when the
+    /// cache cannot be opened, results are only kept in memory.
+    pub(super) fn new(config: &ExtractionConfig) -> Self {
+        let cache = config.use_cache.then(|| {
+            let backend = config.cache_backend.unwrap_or_else(default_backend);
+            shared_user_cache(CACHE_TYPE, backend, CacheLimits::new(30.0, 500.0, 1000.0))
+        });
+        Self::with_cache(cache.flatten())
+    }
+
+    fn with_cache(cache: Option<Arc<GenericCache>>) -> Self {
+        Self {
+            cache,
+            documents: RwLock::new(VecDeque::new()),
+            memory: RwLock::new(RecentResults::default()),
+        }
+    }
+
+    /// Register `result` and return its entry. Registering the same result again is a no-op.
+    pub(super) fn register(&self, name: &str, result: &ExtractionResult) -> DocumentEntry {
+        let mut stored = result.clone();
+        // Page images duplicate `images` and are not served as resources.
+        for page in stored.pages.iter_mut().flatten() {
+            page.images.clear();
+        }
+        let images: Vec<Bytes> = stored
+            .images
+            .iter_mut()
+            .flatten()
+            .map(|image| std::mem::take(&mut image.data))
+            .collect();
+        let serialized = serde_json::to_vec(&stored).unwrap_or_default();
+        let hash = document_hash(&serialized, &images);
+
+        if let Some(existing) = self.documents.read().iter().find(|entry| entry.hash == hash) {
+            return existing.clone();
+        }
+
+        if let Some(cache) = &self.cache {
+            let writes = std::iter::once((hash.clone(), serialized.clone())).chain(
+                images
+                    .iter()
+                    .enumerate()
+                    .map(|(i, data)| (image_key(&hash, i), data.to_vec())),
+            );
+            for (key, bytes) in writes {
+                if let Err(e) = cache.set(&key, bytes, None) {
+                    tracing::warn!("Failed to cache MCP resource {}: {}", key, e);
+                }
+            }
+        }
+
+        let size = serialized.len() + images.iter().map(Bytes::len).sum::<usize>();
+        for (image, data) in stored.images.iter_mut().flatten().zip(images) {
+            image.data = data;
+        }
+        let entry = DocumentEntry::new(hash.clone(), name.to_string(), &stored);
+        {
+            let mut documents = self.documents.write();
+            documents.push_back(entry.clone());
+            while documents.len() > MAX_DOCUMENTS {
+                documents.pop_front();
+            }
+        }
+        self.remember(&hash, Arc::new(stored), size);
+        entry
+    }
+
+    /// Keep `result` in memory, evicting the least recently used results beyond
+    /// [`MEMORY_ENTRIES`] when they can be reloaded from the cache, [`MAX_DOCUMENTS`] when
+    /// they cannot, or [`MEMORY_BYTES`].
+    /// Without a cache, evicted results are gone, so their documents are unlisted too.
+    fn remember(&self, hash: &str, result: Arc<ExtractionResult>, size: usize) {
+        let mut evicted = Vec::new();
+        {
+            let mut memory = self.memory.write();
+            match memory.results.insert(hash.to_string(), (result, size)) {
+                Some((_, previous)) => {
+                    memory.bytes = memory.bytes - previous + size;
+                    memory.touch(hash);
+                }
+                None => {
+                    memory.bytes += size;
+                    memory.order.push_back(hash.to_string());
+                }
+            }
+            let max_entries = if self.cache.is_some() {
+                MEMORY_ENTRIES
+            } else {
+                MAX_DOCUMENTS
+            };
+            while memory.order.len() > max_entries || (memory.order.len() > 1 && memory.bytes > MEMORY_BYTES) {
+                let Some(oldest) = memory.order.pop_front() else {
+                    break;
+                };
+                if let Some((_, size)) = memory.results.remove(&oldest) {
+                    memory.bytes -= size;
+                }
+                evicted.push(oldest);
+            }
+        }
+        if self.cache.is_none() && !evicted.is_empty() {
+            self.documents.write().retain(|entry| !evicted.contains(&entry.hash));
+        }
+    }
+
+    fn load(&self, hash: &str) -> Option<(DocumentEntry, Arc<ExtractionResult>)> {
+        let cached = {
+            let mut memory = self.memory.write();
+            let cached = memory.results.get(hash).map(|(result, _)| Arc::clone(result));
+            if cached.is_some() {
+                memory.touch(hash);
+            }
+            cached
+        };
+        let result = match cached {
+            Some(result) => result,
+            None => {
+                let cache = self.cache.as_ref()?;
+                let bytes = cache.get(hash, None).ok()??;
+                let mut result = serde_json::from_slice::<ExtractionResult>(&bytes).ok()?;
+                let mut size = bytes.len();
+                for (i, image) in result.images.iter_mut().flatten().enumerate() {
+                    if let Ok(Some(data)) = cache.get(&image_key(hash, i), None) {
+                        size += data.len();
+                        image.data = Bytes::from(data);
+                    }
+                }
+                let result = Arc::new(result);
+                self.remember(hash, Arc::clone(&result), size);
+                result
+            }
+        };
+
+        let entry = self
+            .documents
+            .read()
+            .iter()
+            .find(|entry| entry.hash == hash)
+            .cloned()
+            .unwrap_or_else(|| DocumentEntry::new(hash.to_string(), "document".to_string(), &result));
+        Some((entry, result))
+    }
+
+    /// One page of `resources/list`. The cursor is the offset into the full listing.
+    pub(super) fn list(&self, cursor: Option<&str>) -> Result<(Vec<Resource>, Option<String>), String> {
+        let offset = match cursor {
+            Some(cursor) => cursor
+                .parse::<usize>()
+                .map_err(|_| format!("Invalid cursor: '{}'", cursor))?,
+            None => 0,
+        };
+
+        let all: Vec<Resource> = self
+            .documents
+            .read()
+            .iter()
+            .flat_map(DocumentEntry::resources)
+            .collect();
+        let end = (offset + LIST_PAGE_SIZE).min(all.len());
+        let page = all.get(offset..end).map(<[Resource]>::to_vec).unwrap_or_default();
+        let next_cursor = (end < all.len()).then(|| end.to_string());
+        Ok((page, next_cursor))
+    }
+
+    /// Read the resource at `uri`, or `None` when it does not exist.
+    pub(super) fn read(&self, uri: &str) -> Option<ResourceContents> {
+        let path = uri.strip_prefix(URI_PREFIX)?;
+        let mut segments = path.split('/');
+        let hash = segments.next()?;
+        let (entry, result) = self.load(hash)?;
+
+        let text = |mime_type: &str, text: String| ResourceContents::TextResourceContents {
+            uri: uri.to_string(),
+            mime_type: Some(mime_type.to_string()),
+            text,
+            meta: None,
+        };
+        let index = |segment: Option<&str>| segment.and_then(|s| s.parse::<usize>().ok());
+
+        let contents = match (segments.next(), segments.next(), segments.next()) {
+            (None, None, None) => text("application/json", entry.summary(&result).to_string()),
+            (Some("content"), n, None) => {
+                let n = index(n).filter(|n| (1..=entry.content_parts).contains(n))?;
+                text("text/plain", content_part(&result.content, n))
+            }
+            (Some("page"), n, None) => {
+                let n = index(n)?;
+                let page = result.pages.as_ref()?.iter().find(|page| page.page_number == n)?;
+                text("text/plain", page.content.clone())
+            }
+            (Some("tables"), None, None) => text(
+                "application/json",
+                serde_json::to_string(&result.tables).unwrap_or_default(),
+            ),
+            (Some("images"), i, None) => {
+                let image = result.images.as_ref()?.get(index(i)?)?;
+                if image.data.is_empty() {
+                    return None;
+                }
+                ResourceContents::BlobResourceContents {
+                    uri: uri.to_string(),
+                    mime_type: Some(image_mime_type(&image.format)),
+                    blob: BASE64_STANDARD.encode(&image.data),
+                    meta: None,
+                }
+            }
+            _ => return None,
+        };
+        Some(contents)
+    }
+}
+
+/// Register `results` and build the tool response.
+///
+/// Results whose combined content fits in [`INLINE_CONTENT_CHARS`] are returned in full,
+/// as before. Larger ones are replaced by their summaries plus a short preview, and the
+/// agent reads the parts it needs through the linked resources.
+pub(super) fn extraction_response(store: &ResourceStore, results: &[(String, &ExtractionResult)]) -> Vec<Content> {
+    let entries: Vec<DocumentEntry> = results
+        .iter()
+        .map(|(name, result)| store.register(name, result))
+        .collect();
+    let total_chars: usize = results.iter().map(|(_, result)| result.content.chars().count()).sum();
+
+    let body = if total_chars <= INLINE_CONTENT_CHARS {
+        match results {
+            [(_, result)] => format_extraction_result(result),
+            _ => serde_json::to_string_pretty(&results.iter().map(|(_, result)| result).collect::<Vec<_>>())
+                .unwrap_or_default(),
+        }
+    } else {
+        let summaries: Vec<Value> = entries
+            .iter()
+            .zip(results)
+            .map(|(entry, (_, result))| {
+                let mut summary = entry.summary(result);
+                summary["content_preview"] = json!(result.content.chars().take(PREVIEW_CHARS).collect::<String>());
+                summary
+            })
+            .collect();
+        let body = json!({
+            "content_truncated": true,
+            "message": "The extracted content is too large to return inline. Read it through the linked kreuzberg://doc resources.",
+            "documents": summaries,
+        });
+        serde_json::to_string_pretty(&body).unwrap_or_default()
+    };
+
+    let mut content = vec![Content::text(body)];
+    content.extend(
+        entries
+            .iter()
+            .map(|entry| Content::resource_link(entry.summary_resource())),
+    );
+    content
+}
+
+/// Templates describing the resource URIs, for `resources/templates/list`.
+pub(super) fn resource_templates() -> Vec<ResourceTemplate> {
+    let template = |path: &str, name: &str, description: &str, mime_type: &str| {
+        RawResourceTemplate {
+            uri_template: format!("{}{{hash}}{}", URI_PREFIX, path),
+            name: name.to_string(),
+            title: None,
+            description: Some(description.to_string()),
+            mime_type: Some(mime_type.to_string()),
+            icons: None,
+        }
+        .no_annotation()
+    };
+
+    vec![
+        template(
+            "",
+            "document",
+            "Summary of an extraction result: metadata, counts and resource URIs",
+            "application/json",
+        ),
+        template(
+            "/content/{n}",
+            "content",
+            "Extracted content, split into parts of 20,000 characters (1-indexed)",
+            "text/plain",
+        ),
+        template("/page/{n}", "page", "Content of one page (1-indexed)", "text/plain"),
+        template("/tables", "tables", "All extracted tables as JSON", "application/json"),
+        template(
+            "/images/{i}",
+            "image",
+            "One extracted image (0-indexed)",
+            "application/octet-stream",
+        ),
+    ]
+}
+
+/// Part `n` (1-indexed) of `content`, split on character boundaries.
+pub(super) fn content_part(content: &str, n: usize) -> String {
+    content
+        .chars()
+        .skip((n - 1) * CONTENT_PART_CHARS)
+        .take(CONTENT_PART_CHARS)
+        .collect()
+}
+
+/// Stable hash identifying a document by its serialized result and image bytes.
+///
+/// Documents are served and cached by this hash, so it is SHA-256: a collision would serve
+/// another document, and the hash must not change between processes or builds.
+fn document_hash(serialized: &[u8], images: &[Bytes]) -> String {
+    let mut hasher = Sha256::new();
+    for part in std::iter::once(serialized).chain(images.iter().map(|data| data.as_ref())) {
+        hasher.update((part.len() as u64).to_le_bytes());
+        hasher.update(part);
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// Cache key of image `i` of the result stored under `hash`, kept as raw bytes.
+fn image_key(hash: &str, i: usize) -> String {
+    format!("{}-image-{}", hash, i)
+}
+
+fn image_mime_type(format: &str) -> String {
+    match format.to_lowercase().as_str() {
+        "jpg" | "jpeg" => "image/jpeg".to_string(),
+        "svg" => "image/svg+xml".to_string(),
+        format if format.contains('/') => format.to_string(),
+        format => format!("image/{}", format),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::*;
+    use crate::types::{ExtractedImage, Metadata, PageContent, Table};
+
+    fn result(content: String) -> ExtractionResult {
+        ExtractionResult {
+            content,
+            mime_type: Cow::Borrowed("text/plain"),
+            metadata: Metadata::default(),
+            tables: vec![Table {
+                cells: vec![vec!["a".to_string()]],
+                markdown: "| a |".to_string(),
+                page_number: 1,
+                bounding_box: None,
+            }],
+            pages: Some(vec![PageContent {
+                page_number: 1,
+                content: "first page".to_string(),
+                tables: Vec::new(),
+                images: Vec::new(),
+                hierarchy: None,
+                is_blank: None,
+            }]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_register_and_read_resources() {
+        let store = ResourceStore::with_cache(None);
+        let content = "x".repeat(CONTENT_PART_CHARS + 10);
+        let entry = store.register("big.txt", &result(content));
+        assert_eq!(entry.content_parts, 2);
+
+        let read_text = |uri: String| match store.read(&uri) {
+            Some(ResourceContents::TextResourceContents { text, .. }) => text,
+            other => panic!("unexpected contents for {}: {:?}", uri, other),
+        };
+
+        let summary: Value = serde_json::from_str(&read_text(entry.uri())).unwrap();
+        assert_eq!(summary["tables"], 1);
+        assert_eq!(summary["content_parts"].as_array().unwrap().len(), 2);
+
+        assert_eq!(read_text(format!("{}/content/2", entry.uri())).len(), 10);
+        assert_eq!(read_text(format!("{}/page/1", entry.uri())), "first page");
+        assert!(read_text(format!("{}/tables", entry.uri())).contains("| a |"));
+
+        assert!(store.read(&format!("{}/content/3", entry.uri())).is_none());
+        assert!(store.read(&format!("{}/images/0", entry.uri())).is_none());
+        assert!(store.read("kreuzberg://doc/unknown").is_none());
+    }
+
+    #[test]
+    fn test_large_results_are_returned_as_resource_links() {
+        let store = ResourceStore::with_cache(None);
+        let small = result("small".to_string());
+        let large = result("z".repeat(INLINE_CONTENT_CHARS + 1));
+
+        let inline = extraction_response(&store, &[("small.txt".to_string(), &small)]);
+        assert_eq!(inline.len(), 2);
+        let body: Value = serde_json::from_str(&inline[0].as_text().unwrap().text).unwrap();
+        assert_eq!(body["content"], "small");
+
+        let linked = extraction_response(&store, &[("large.txt".to_string(), &large)]);
+        let body: Value = serde_json::from_str(&linked[0].as_text().unwrap().text).unwrap();
+        assert_eq!(body["content_truncated"], true);
+        assert_eq!(body["documents"][0]["content_parts"].as_array().unwrap().len(), 3);
+        assert!(body.get("content").is_none());
+    }
+
+    #[test]
+    fn test_register_is_idempotent() {
+        let store = ResourceStore::with_cache(None);
+        let first = store.register("a.txt", &result("same".to_string()));
+        let second = store.register("a.txt", &result("same".to_string()));
+
+        assert_eq!(first.hash, second.hash);
+        assert_eq!(store.documents.read().len(), 1);
+    }
+
+    #[test]
+    fn test_document_hash_is_stable_and_covers_images() {
+        assert_eq!(
+            document_hash(b"abc", &[]),
+            "ce91dc5eec0139adf091900d225971d6ad246a845bad791b5693a9d0d55dd391"
+        );
+        let image = Bytes::from_static(b"png");
+        assert_ne!(
+            document_hash(b"abc", &[]),
+            document_hash(b"abc", std::slice::from_ref(&image))
+        );
+        assert_ne!(
+            document_hash(b"abc", &[image.clone(), Bytes::new()]),
+            document_hash(b"abc", &[Bytes::new(), image])
+        );
+    }
+
+    #[test]
+    fn test_list_paginates_with_cursor() {
+        let store = ResourceStore::with_cache(None);
+        store.register("big.txt", &result("y".repeat(CONTENT_PART_CHARS * 150)));
+
+        let (first, cursor) = store.list(None).unwrap();
+        assert_eq!(first.len(), LIST_PAGE_SIZE);
+        let cursor = cursor.expect("second page");
+
+        let (second, next) = store.list(Some(&cursor)).unwrap();
+        // summary + 150 content parts + 1 page + tables
+        assert_eq!(first.len() + second.len(), 153);
+        assert!(next.is_none());
+        assert!(store.list(Some("not-a-number")).is_err());
+    }
+
+    #[test]
+    fn test_results_survive_through_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = || {
+            GenericCache::new(
+                "mcp".to_string(),
+                Some(dir.path().to_string_lossy().into_owned()),
+                30.0,
+                500.0,
+                1000.0,
+            )
+            .ok()
+            .map(Arc::new)
+        };
+
+        let mut with_image = result("cached".to_string());
+        with_image.images = Some(vec![ExtractedImage {
+            data: Bytes::from_static(b"\x89PNG"),
+            format: Cow::Borrowed("png"),
+            image_index: 0,
+            page_number: None,
+            width: None,
+            height: None,
+            colorspace: None,
+            bits_per_component: None,
+            is_mask: false,
+            description: None,
+            ocr_result: None,
+            bounding_box: None,
+        }]);
+        let entry = ResourceStore::with_cache(cache()).register("doc.txt", &with_image);
+        let fresh = ResourceStore::with_cache(cache());
+
+        match fresh.read(&format!("{}/content/1", entry.uri())) {
+            Some(ResourceContents::TextResourceContents { text, .. }) => assert_eq!(text, "cached"),
+            other => panic!("unexpected contents: {:?}", other),
+        }
+        match fresh.read(&format!("{}/images/0", entry.uri())) {
+            Some(ResourceContents::BlobResourceContents { blob, .. }) => {
+                assert_eq!(BASE64_STANDARD.decode(blob).unwrap(), b"\x89PNG")
+            }
+            other => panic!("unexpected contents: {:?}", other),
+        }
+
+        let stored = cache().unwrap().get(&entry.hash, None).unwrap().unwrap();
+        let stored: Value = serde_json::from_slice(&stored).unwrap();
+        assert_eq!(stored["images"][0]["data"], json!([]));
+    }
+
+    #[test]
+    fn test_use_cache_false_keeps_results_in_memory_only() {
+        let config = ExtractionConfig {
+            use_cache: false,
+            ..Default::default()
+        };
+        assert!(ResourceStore::new(&config).cache.is_none());
+    }
+
+    #[test]
+    fn test_listing_is_bounded() {
+        let store = ResourceStore::with_cache(None);
+        for i in 0..MAX_DOCUMENTS + 5 {
+            store.register("doc.txt", &result(format!("document {}", i)));
+        }
+
+        assert_eq!(store.documents.read().len(), MAX_DOCUMENTS);
+        assert_eq!(store.memory.read().results.len(), MAX_DOCUMENTS);
+    }
+
+    #[test]
+    fn test_memory_only_store_evicts_by_size() {
+        let store = ResourceStore::with_cache(None);
+        let first = store.register("a.txt", &result("a".to_string()));
+        let second = store.register("b.txt", &result("b".to_string()));
+        assert!(store.read(&first.uri()).is_some());
+        store.remember("huge", Arc::new(result("c".to_string())), MEMORY_BYTES);
+
+        assert_eq!(store.memory.read().bytes, MEMORY_BYTES);
+        assert!(store.read(&second.uri()).is_none());
+        assert!(store.read(&first.uri()).is_none());
+        assert!(store.documents.read().iter().all(|entry| entry.hash != first.hash));
+    }
+}
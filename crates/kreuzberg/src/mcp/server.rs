@@ -4,9 +4,10 @@
 
 use crate::ExtractionConfig;
 use rmcp::{
-    ServerHandler, ServiceExt,
+    RoleServer, ServerHandler, ServiceExt,
     handler::server::{router::tool::ToolRouter, wrapper::Parameters},
     model::*,
+    service::RequestContext,
     tool, tool_handler, tool_router,
     transport::stdio,
 };
@@ -25,6 +26,8 @@ pub struct KreuzbergMcp {
     tool_router: ToolRouter<KreuzbergMcp>,
     /// Default extraction configuration loaded from config file via discovery
     default_config: std::sync::Arc<ExtractionConfig>,
+    /// Extraction results exposed as MCP resources
+    resources: std::sync::Arc<super::resources::ResourceStore>,
 }
 
 #[tool_router]
@@ -60,6 +63,7 @@ impl KreuzbergMcp {
     pub fn with_config(config: ExtractionConfig) -> Self {
         Self {
             tool_router: Self::tool_router(),
+            resources: std::sync::Arc::new(super::resources::ResourceStore::new(&config)),
             default_config: std::sync::Arc::new(config),
         }
     }
@@ -81,7 +85,8 @@ impl KreuzbergMcp {
         Parameters(params): Parameters<super::params::ExtractFileParams>,
     ) -> Result<CallToolResult, rmcp::ErrorData> {
         use super::errors::map_kreuzberg_error_to_mcp;
-        use super::format::{build_config, format_extraction_result};
+        use super::format::build_config;
+        use super::resources::extraction_response;
         use crate::extract_file;
 
         let config =
@@ -94,8 +99,13 @@ impl KreuzbergMcp {
             .await
             .map_err(map_kreuzberg_error_to_mcp)?;
 
-        let response = format_extraction_result(&result);
-        Ok(CallToolResult::success(vec![Content::text(response)]))
+        let name = std::path::Path::new(&params.path)
+            .file_name()
+            .map_or_else(|| params.path.clone(), |name| name.to_string_lossy().into_owned());
+        Ok(CallToolResult::success(extraction_response(
+            &self.resources,
+            &[(name, &result)],
+        )))
     }
 
     /// Extract content from base64-encoded bytes.
@@ -114,7 +124,8 @@ impl KreuzbergMcp {
         Parameters(params): Parameters<super::params::ExtractBytesParams>,
     ) -> Result<CallToolResult, rmcp::ErrorData> {
         use super::errors::map_kreuzberg_error_to_mcp;
-        use super::format::{build_config, format_extraction_result};
+        use super::format::build_config;
+        use super::resources::extraction_response;
         use crate::extract_bytes;
         use base64::prelude::*;
 
@@ -132,8 +143,10 @@ impl KreuzbergMcp {
             .await
             .map_err(map_kreuzberg_error_to_mcp)?;
 
-        let response = format_extraction_result(&result);
-        Ok(CallToolResult::success(vec![Content::text(response)]))
+        Ok(CallToolResult::success(extraction_response(
+            &self.resources,
+            &[("document".to_string(), &result)],
+        )))
     }
 
     /// Extract content from multiple files in parallel.
@@ -153,6 +166,7 @@ impl KreuzbergMcp {
     ) -> Result<CallToolResult, rmcp::ErrorData> {
         use super::errors::map_kreuzberg_error_to_mcp;
         use super::format::build_config;
+        use super::resources::extraction_response;
         use crate::batch_extract_file;
 
         let config =
@@ -163,7 +177,286 @@ impl KreuzbergMcp {
             .await
             .map_err(map_kreuzberg_error_to_mcp)?;
 
-        let response = serde_json::to_string_pretty(&results).unwrap_or_default();
+        let named: Vec<(String, &crate::ExtractionResult)> = params
+            .paths
+            .iter()
+            .map(|path| {
+                std::path::Path::new(path)
+                    .file_name()
+                    .map_or_else(|| path.clone(), |name| name.to_string_lossy().into_owned())
+            })
+            .zip(&results)
+            .collect();
+        Ok(CallToolResult::success(extraction_response(&self.resources, &named)))
+    }
+
+    /// Split text or a document into chunks.
+    ///
+    /// Documents are extracted with the server's default configuration merged with
+    /// `config`; `first_page`/`last_page` then select the chunks overlapping that page
+    /// range, so agents can read a large document piece by piece.
+    #[tool(
+        description = "Split text or a document into chunks. For documents, first_page/last_page return only chunks overlapping that page range.",
+        annotations(title = "Chunk", read_only_hint = true, idempotent_hint = true)
+    )]
+    async fn chunk(
+        &self,
+        Parameters(params): Parameters<super::params::ChunkParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        use super::errors::map_kreuzberg_error_to_mcp;
+        use super::format::build_config;
+        use crate::chunking::{ChunkerType, ChunkingConfig, chunk_text};
+
+        let chunker_type = match params.chunker_type.as_deref().map(str::to_lowercase).as_deref() {
+            None | Some("text") => ChunkerType::Text,
+            Some("markdown") => ChunkerType::Markdown,
+            Some(other) => {
+                return Err(rmcp::ErrorData::invalid_params(
+                    format!("Invalid chunker_type: '{}'. Valid values: 'text', 'markdown'", other),
+                    None,
+                ));
+            }
+        };
+        let defaults = ChunkingConfig::default();
+        let chunking = ChunkingConfig {
+            max_characters: params.max_characters.unwrap_or(defaults.max_characters),
+            overlap: params.overlap.unwrap_or(defaults.overlap),
+            chunker_type,
+            ..defaults
+        };
+        if chunking.overlap >= chunking.max_characters {
+            return Err(rmcp::ErrorData::invalid_params(
+                format!(
+                    "Invalid chunking configuration: overlap ({}) must be less than max_characters ({})",
+                    chunking.overlap, chunking.max_characters
+                ),
+                None,
+            ));
+        }
+
+        let page_range = params.first_page.is_some() || params.last_page.is_some();
+        let chunks = match (params.text, params.path) {
+            (Some(_), None) if page_range => {
+                return Err(rmcp::ErrorData::invalid_params(
+                    "first_page and last_page require 'path'",
+                    None,
+                ));
+            }
+            (Some(text), None) => {
+                chunk_text(&text, &chunking, None)
+                    .map_err(map_kreuzberg_error_to_mcp)?
+                    .chunks
+            }
+            (None, Some(path)) => {
+                let mut config = build_config(&self.default_config, params.config)
+                    .map_err(|e| rmcp::ErrorData::invalid_params(e, None))?;
+                config.chunking = Some(chunking);
+                crate::extract_file(&path, params.mime_type.as_deref(), &config)
+                    .await
+                    .map_err(map_kreuzberg_error_to_mcp)?
+                    .chunks
+                    .unwrap_or_default()
+            }
+            _ => {
+                return Err(rmcp::ErrorData::invalid_params(
+                    "Provide exactly one of 'text' or 'path'",
+                    None,
+                ));
+            }
+        };
+
+        let total_chunks = chunks.len();
+        let chunks: Vec<_> = chunks
+            .into_iter()
+            .filter(|chunk| {
+                if !page_range {
+                    return true;
+                }
+                let Some(start) = chunk.metadata.first_page else {
+                    return false;
+                };
+                let end = chunk.metadata.last_page.unwrap_or(start);
+                params.first_page.is_none_or(|first| end >= first) && params.last_page.is_none_or(|last| start <= last)
+            })
+            .collect();
+
+        let response = serde_json::to_string_pretty(&serde_json::json!({
+            "total_chunks": total_chunks,
+            "chunks": chunks,
+        }))
+        .unwrap_or_default();
+        Ok(CallToolResult::success(vec![Content::text(response)]))
+    }
+
+    /// Generate embeddings for a list of texts.
+    ///
+    /// Requires the `embeddings` feature; the model is downloaded on first use.
+    #[tool(
+        description = "Generate embedding vectors for a list of texts. Returns one vector per input text.",
+        annotations(title = "Embed", read_only_hint = true, idempotent_hint = true)
+    )]
+    fn embed(
+        &self,
+        Parameters(params): Parameters<super::params::EmbedParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        use super::errors::map_kreuzberg_error_to_mcp;
+
+        if params.texts.is_empty() || params.texts.iter().any(|text| text.is_empty()) {
+            return Err(rmcp::ErrorData::invalid_params(
+                "'texts' must contain at least one non-empty string",
+                None,
+            ));
+        }
+        let config: crate::core::config::EmbeddingConfig = params
+            .config
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|e| rmcp::ErrorData::invalid_params(format!("Invalid embedding configuration: {}", e), None))?
+            .unwrap_or_default();
+
+        #[cfg(feature = "embeddings")]
+        {
+            use crate::types::{Chunk, ChunkMetadata};
+
+            let total_chunks = params.texts.len();
+            let mut chunks: Vec<Chunk> = params
+                .texts
+                .into_iter()
+                .enumerate()
+                .map(|(chunk_index, content)| Chunk {
+                    embedding: None,
+                    metadata: ChunkMetadata {
+                        byte_start: 0,
+                        byte_end: content.len(),
+                        token_count: None,
+                        chunk_index,
+                        total_chunks,
+                        first_page: None,
+                        last_page: None,
+                        heading_path: None,
+                        node_ids: None,
+                    },
+                    content,
+                })
+                .collect();
+
+            crate::embeddings::generate_embeddings_for_chunks(&mut chunks, &config)
+                .map_err(map_kreuzberg_error_to_mcp)?;
+
+            let embeddings: Vec<Vec<f32>> = chunks.into_iter().filter_map(|chunk| chunk.embedding).collect();
+            let response = serde_json::to_string(&serde_json::json!({
+                "count": embeddings.len(),
+                "dimensions": embeddings.first().map_or(0, Vec::len),
+                "embeddings": embeddings,
+            }))
+            .unwrap_or_default();
+            Ok(CallToolResult::success(vec![Content::text(response)]))
+        }
+
+        #[cfg(not(feature = "embeddings"))]
+        {
+            let _ = config;
+            Err(map_kreuzberg_error_to_mcp(crate::KreuzbergError::MissingDependency(
+                "Embeddings feature is not enabled. Rebuild with --features embeddings".to_string(),
+            )))
+        }
+    }
+
+    /// Extract keywords from text or a document.
+    ///
+    /// Requires the `keywords-yake` or `keywords-rake` feature.
+    #[tool(
+        description = "Extract keywords from text or a document using YAKE or RAKE. Returns keywords with relevance scores.",
+        annotations(title = "Extract Keywords", read_only_hint = true, idempotent_hint = true)
+    )]
+    async fn extract_keywords(
+        &self,
+        Parameters(params): Parameters<super::params::ExtractKeywordsParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        use super::errors::map_kreuzberg_error_to_mcp;
+
+        #[cfg(any(feature = "keywords-yake", feature = "keywords-rake"))]
+        {
+            use crate::keywords::{KeywordConfig, extract_keywords};
+
+            let config: KeywordConfig = params
+                .config
+                .map(serde_json::from_value)
+                .transpose()
+                .map_err(|e| rmcp::ErrorData::invalid_params(format!("Invalid keyword configuration: {}", e), None))?
+                .unwrap_or_default();
+
+            let text = match (params.text, params.path) {
+                (Some(text), None) => text,
+                (None, Some(path)) => {
+                    crate::extract_file(&path, params.mime_type.as_deref(), &self.default_config)
+                        .await
+                        .map_err(map_kreuzberg_error_to_mcp)?
+                        .content
+                }
+                _ => {
+                    return Err(rmcp::ErrorData::invalid_params(
+                        "Provide exactly one of 'text' or 'path'",
+                        None,
+                    ));
+                }
+            };
+
+            let keywords = extract_keywords(&text, &config).map_err(map_kreuzberg_error_to_mcp)?;
+            let response = serde_json::to_string_pretty(&keywords).unwrap_or_default();
+            Ok(CallToolResult::success(vec![Content::text(response)]))
+        }
+
+        #[cfg(not(any(feature = "keywords-yake", feature = "keywords-rake")))]
+        {
+            let _ = (params, &self.default_config);
+            Err(map_kreuzberg_error_to_mcp(crate::KreuzbergError::MissingDependency(
+                "Keyword extraction is not enabled. Rebuild with --features keywords".to_string(),
+            )))
+        }
+    }
+
+    /// Return the structure of a document as a navigable outline.
+    ///
+    /// Nodes carry their index, id, type, page and a short text preview. `section`
+    /// narrows the outline to one section (by node id, index or heading text) and the
+    /// page range to nodes on those pages; `include_text` returns full node text.
+    #[tool(
+        description = "Get a document's structure (sections, headings, paragraphs, tables) as an outline with text previews. Narrow it with section (node id, index or heading text), first_page/last_page and max_depth; set include_text=true to read full text.",
+        annotations(title = "Get Document Structure", read_only_hint = true, idempotent_hint = true)
+    )]
+    async fn get_document_structure(
+        &self,
+        Parameters(params): Parameters<super::params::DocumentStructureParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        use super::errors::map_kreuzberg_error_to_mcp;
+        use super::format::build_config;
+        use super::structure::{OutlineOptions, document_outline};
+
+        let mut config =
+            build_config(&self.default_config, params.config).map_err(|e| rmcp::ErrorData::invalid_params(e, None))?;
+        config.include_document_structure = true;
+
+        let result = crate::extract_file(&params.path, params.mime_type.as_deref(), &config)
+            .await
+            .map_err(map_kreuzberg_error_to_mcp)?;
+        let structure = result.document.ok_or_else(|| {
+            rmcp::ErrorData::invalid_params(
+                format!("No document structure is available for '{}'", params.path),
+                None,
+            )
+        })?;
+
+        let options = OutlineOptions {
+            section: params.section.as_deref(),
+            first_page: params.first_page,
+            last_page: params.last_page,
+            max_depth: params.max_depth,
+            include_text: params.include_text,
+        };
+        let outline = document_outline(&structure, &options).map_err(|e| rmcp::ErrorData::invalid_params(e, None))?;
+
+        let response = serde_json::to_string_pretty(&outline).unwrap_or_default();
         Ok(CallToolResult::success(vec![Content::text(response)]))
     }
 
@@ -284,6 +577,7 @@ impl ServerHandler for KreuzbergMcp {
             protocol_version: ProtocolVersion::default(),
             capabilities: ServerCapabilities {
                 tools: Some(ToolsCapability::default()),
+                resources: Some(ResourcesCapability::default()),
                 ..Default::default()
             },
             server_info: Implementation {
@@ -298,11 +592,54 @@ impl ServerHandler for KreuzbergMcp {
                 "Extract content from documents in various formats. Supports PDFs, Word documents, \
                  Excel spreadsheets, images (with OCR), HTML, emails, and more. Use enable_ocr=true \
                  for scanned documents, force_ocr=true to always use OCR even if text extraction \
-                 succeeds."
+                 succeeds. For large documents, use get_document_structure and chunk with a page \
+                 range to read only the parts you need. Extraction results too large to return \
+                 inline are linked as kreuzberg://doc resources that can be read page by page."
                     .to_string(),
             ),
         }
     }
+
+    async fn list_resources(
+        &self,
+        request: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, rmcp::ErrorData> {
+        let cursor = request.and_then(|request| request.cursor);
+        let (resources, next_cursor) = self
+            .resources
+            .list(cursor.as_deref())
+            .map_err(|e| rmcp::ErrorData::invalid_params(e, None))?;
+        Ok(ListResourcesResult {
+            meta: None,
+            next_cursor,
+            resources,
+        })
+    }
+
+    async fn list_resource_templates(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourceTemplatesResult, rmcp::ErrorData> {
+        Ok(ListResourceTemplatesResult::with_all_items(
+            super::resources::resource_templates(),
+        ))
+    }
+
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, rmcp::ErrorData> {
+        let contents = self
+            .resources
+            .read(&request.uri)
+            .ok_or_else(|| rmcp::ErrorData::resource_not_found(format!("Resource not found: {}", request.uri), None))?;
+        Ok(ReadResourceResult {
+            contents: vec![contents],
+        })
+    }
 }
 
 impl Default for KreuzbergMcp {
@@ -474,9 +811,13 @@ mod tests {
         assert!(router.has_route("list_formats"));
         assert!(router.has_route("cache_stats"));
         assert!(router.has_route("cache_clear"));
+        assert!(router.has_route("chunk"));
+        assert!(router.has_route("embed"));
+        assert!(router.has_route("extract_keywords"));
+        assert!(router.has_route("get_document_structure"));
 
         let tools = router.list_all();
-        assert_eq!(tools.len(), 7);
+        assert_eq!(tools.len(), 11);
     }
 
     #[test]
@@ -606,6 +947,10 @@ mod tests {
             "list_formats",
             "cache_stats",
             "cache_clear",
+            "chunk",
+            "embed",
+            "extract_keywords",
+            "get_document_structure",
         ];
 
         for tool_name in expected_tools {
@@ -618,7 +963,7 @@ mod tests {
         let router = KreuzbergMcp::tool_router();
         let tools = router.list_all();
 
-        assert_eq!(tools.len(), 7, "Expected 7 tools, found {}", tools.len());
+        assert_eq!(tools.len(), 11, "Expected 11 tools, found {}", tools.len());
     }
 
     #[tokio::test]
@@ -714,4 +1059,82 @@ mod tests {
 
         assert_eq!(info1.server_info.name, info2.server_info.name);
     }
+
+    fn response_json(result: CallToolResult) -> serde_json::Value {
+        let text = result.content[0].as_text().expect("text content").text.clone();
+        serde_json::from_str(&text).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_chunk_tool_chunks_text() {
+        let server = KreuzbergMcp::with_config(ExtractionConfig::default());
+        let params = super::super::params::ChunkParams {
+            text: Some("First sentence here. Second sentence here. Third sentence here.".to_string()),
+            path: None,
+            mime_type: None,
+            max_characters: Some(25),
+            overlap: Some(0),
+            chunker_type: None,
+            first_page: None,
+            last_page: None,
+            config: None,
+        };
+
+        let response = response_json(server.chunk(Parameters(params)).await.unwrap());
+        let total = response["total_chunks"].as_u64().unwrap();
+        assert!(total > 1);
+        assert_eq!(response["chunks"].as_array().unwrap().len() as u64, total);
+    }
+
+    #[tokio::test]
+    async fn test_chunk_tool_rejects_page_range_for_text() {
+        let server = KreuzbergMcp::with_config(ExtractionConfig::default());
+        let params = super::super::params::ChunkParams {
+            text: Some("Some text".to_string()),
+            path: None,
+            mime_type: None,
+            max_characters: None,
+            overlap: None,
+            chunker_type: None,
+            first_page: Some(2),
+            last_page: None,
+            config: None,
+        };
+
+        assert!(server.chunk(Parameters(params)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_document_structure_tool_selects_section() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("doc.adoc");
+        std::fs::write(&path, "== Introduction\n\nIntro text.\n\n== Methods\n\nMethods text.\n").unwrap();
+        let server = KreuzbergMcp::with_config(ExtractionConfig {
+            use_cache: false,
+            ..Default::default()
+        });
+        let params = |section: Option<&str>| super::super::params::DocumentStructureParams {
+            path: path.to_string_lossy().into_owned(),
+            mime_type: None,
+            section: section.map(str::to_string),
+            first_page: None,
+            last_page: None,
+            max_depth: None,
+            include_text: false,
+            config: None,
+        };
+
+        let outline = response_json(server.get_document_structure(Parameters(params(None))).await.unwrap());
+        assert!(outline["total_nodes"].as_u64().unwrap() > 0);
+
+        let section = response_json(
+            server
+                .get_document_structure(Parameters(params(Some("methods"))))
+                .await
+                .unwrap(),
+        );
+        let rendered = section["nodes"].to_string();
+        assert!(rendered.contains("Methods"));
+        assert!(!rendered.contains("Intro text"));
+    }
 }
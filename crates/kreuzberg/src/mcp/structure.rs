@@ -0,0 +1,280 @@
+//! Compact outlines of a `DocumentStructure` for MCP clients.
+//!
+//! Returning the full structure of a large document would flood an agent's context, so
+//! nodes are rendered as a nested outline with short text previews. Clients narrow the
+//! outline to a section or page range and ask for full text only where they need it.
+
+use serde_json::{Map, Value, json};
+
+use crate::types::{DocumentNode, DocumentStructure, NodeContent, NodeIndex};
+
+/// Maximum characters of node text shown when full text was not requested.
+const PREVIEW_CHARS: usize = 120;
+
+/// Which part of the structure to render and how.
+#[derive(Debug, Default)]
+pub(super) struct OutlineOptions<'a> {
+    pub section: Option<&'a str>,
+    pub first_page: Option<u32>,
+    pub last_page: Option<u32>,
+    pub max_depth: Option<usize>,
+    pub include_text: bool,
+}
+
+/// Render `structure` as a JSON outline.
+///
+/// Returns an error message when `options.section` matches no node.
+pub(super) fn document_outline(structure: &DocumentStructure, options: &OutlineOptions<'_>) -> Result<Value, String> {
+    let roots: Vec<NodeIndex> = match options.section {
+        Some(section) => {
+            vec![find_section(structure, section).ok_or_else(|| format!("No section matching '{}' found", section))?]
+        }
+        None => structure.body_roots().map(|(index, _)| index).collect(),
+    };
+
+    let nodes: Vec<Value> = roots
+        .into_iter()
+        .filter_map(|index| render(structure, index, 0, options))
+        .collect();
+
+    Ok(json!({
+        "total_nodes": structure.len(),
+        "nodes": nodes,
+    }))
+}
+
+/// Resolve `section` to a node: an exact node id or index, else a title, heading or
+/// section group whose text matches (exactly first, then as a substring, ignoring case).
+///
+/// A matching heading resolves to its enclosing section group when it has one, so the
+/// section's body is returned along with its heading.
+fn find_section(structure: &DocumentStructure, section: &str) -> Option<NodeIndex> {
+    if let Some(position) = structure.nodes.iter().position(|node| node.id.as_ref() == section) {
+        return Some(NodeIndex(position as u32));
+    }
+    if let Ok(index) = section.parse::<u32>()
+        && structure.get(NodeIndex(index)).is_some()
+    {
+        return Some(NodeIndex(index));
+    }
+
+    let needle = section.trim().to_lowercase();
+    let headings: Vec<(usize, String)> = structure
+        .nodes
+        .iter()
+        .enumerate()
+        .filter_map(|(i, node)| heading_text(&node.content).map(|text| (i, text.trim().to_lowercase())))
+        .collect();
+    let (position, _) = headings
+        .iter()
+        .find(|(_, text)| *text == needle)
+        .or_else(|| headings.iter().find(|(_, text)| text.contains(&needle)))?;
+
+    let node = &structure.nodes[*position];
+    let enclosing_group = node
+        .parent
+        .filter(|_| matches!(node.content, NodeContent::Heading { .. }))
+        .filter(|parent| {
+            matches!(
+                structure.get(*parent).map(|p| &p.content),
+                Some(NodeContent::Group { .. })
+            )
+        });
+    Some(enclosing_group.unwrap_or(NodeIndex(*position as u32)))
+}
+
+fn heading_text(content: &NodeContent) -> Option<&str> {
+    match content {
+        NodeContent::Title { text } | NodeContent::Heading { text, .. } => Some(text),
+        NodeContent::Group { heading_text, .. } => heading_text.as_deref(),
+        _ => None,
+    }
+}
+
+/// Whether `node` lies within the requested page range. Nodes without page
+/// information are decided by their descendants.
+fn overlaps_pages(node: &DocumentNode, options: &OutlineOptions<'_>) -> Option<bool> {
+    if options.first_page.is_none() && options.last_page.is_none() {
+        return Some(true);
+    }
+    let start = node.page?;
+    let end = node.page_end.unwrap_or(start);
+    Some(options.first_page.is_none_or(|first| end >= first) && options.last_page.is_none_or(|last| start <= last))
+}
+
+fn subtree_in_range(structure: &DocumentStructure, index: NodeIndex, options: &OutlineOptions<'_>) -> bool {
+    let Some(node) = structure.get(index) else {
+        return false;
+    };
+    match overlaps_pages(node, options) {
+        Some(in_range) => in_range,
+        None => node
+            .children
+            .iter()
+            .any(|child| subtree_in_range(structure, *child, options)),
+    }
+}
+
+fn render(
+    structure: &DocumentStructure,
+    index: NodeIndex,
+    depth: usize,
+    options: &OutlineOptions<'_>,
+) -> Option<Value> {
+    if !subtree_in_range(structure, index, options) {
+        return None;
+    }
+    let node = structure.get(index)?;
+
+    let mut entry = Map::new();
+    entry.insert("index".to_string(), json!(index.0));
+    entry.insert("id".to_string(), json!(node.id.as_ref()));
+    entry.insert("type".to_string(), json!(node.content.node_type_str()));
+
+    let level = match &node.content {
+        NodeContent::Heading { level, .. } => Some(*level),
+        NodeContent::Group { heading_level, .. } => *heading_level,
+        _ => None,
+    };
+    if let Some(level) = level {
+        entry.insert("level".to_string(), json!(level));
+    }
+
+    let text = node.content.text().or_else(|| heading_text(&node.content));
+    if let Some(text) = text {
+        let text = if options.include_text {
+            text.to_string()
+        } else {
+            preview(text)
+        };
+        entry.insert("text".to_string(), json!(text));
+    }
+
+    if let Some(page) = node.page {
+        entry.insert("page".to_string(), json!(page));
+    }
+    if let Some(page_end) = node.page_end {
+        entry.insert("page_end".to_string(), json!(page_end));
+    }
+
+    if !node.children.is_empty() {
+        if options.max_depth.is_none_or(|max| depth < max) {
+            let children: Vec<Value> = node
+                .children
+                .iter()
+                .filter_map(|child| render(structure, *child, depth + 1, options))
+                .collect();
+            entry.insert("children".to_string(), Value::Array(children));
+        } else {
+            entry.insert("child_count".to_string(), json!(node.children.len()));
+        }
+    }
+
+    Some(Value::Object(entry))
+}
+
+fn preview(text: &str) -> String {
+    let mut chars = text.chars();
+    let head: String = chars.by_ref().take(PREVIEW_CHARS).collect();
+    if chars.next().is_some() {
+        format!("{}…", head.trim_end())
+    } else {
+        head
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ContentLayer, NodeId};
+
+    fn node(content: NodeContent, parent: Option<u32>, children: &[u32], page: Option<u32>) -> DocumentNode {
+        DocumentNode {
+            id: NodeId::new(format!("node-{}", content.node_type_str())),
+            content,
+            parent: parent.map(NodeIndex),
+            children: children.iter().copied().map(NodeIndex).collect(),
+            content_layer: ContentLayer::Body,
+            page,
+            page_end: None,
+            bbox: None,
+            annotations: Vec::new(),
+        }
+    }
+
+    fn sample() -> DocumentStructure {
+        let mut structure = DocumentStructure::new();
+        structure.nodes = vec![
+            node(
+                NodeContent::Group {
+                    label: None,
+                    heading_level: Some(1),
+                    heading_text: Some("Introduction".to_string()),
+                },
+                None,
+                &[1, 2],
+                None,
+            ),
+            node(
+                NodeContent::Heading {
+                    level: 1,
+                    text: "Introduction".to_string(),
+                },
+                Some(0),
+                &[],
+                Some(1),
+            ),
+            node(NodeContent::Paragraph { text: "x".repeat(500) }, Some(0), &[], Some(2)),
+        ];
+        structure
+    }
+
+    #[test]
+    fn test_outline_previews_text() {
+        let outline = document_outline(&sample(), &OutlineOptions::default()).unwrap();
+
+        assert_eq!(outline["total_nodes"], 3);
+        let paragraph = &outline["nodes"][0]["children"][1];
+        assert_eq!(paragraph["type"], "paragraph");
+        assert!(paragraph["text"].as_str().unwrap().chars().count() <= PREVIEW_CHARS + 1);
+    }
+
+    #[test]
+    fn test_outline_section_resolves_heading_to_group() {
+        let options = OutlineOptions {
+            section: Some("intro"),
+            include_text: true,
+            ..Default::default()
+        };
+        let outline = document_outline(&sample(), &options).unwrap();
+
+        assert_eq!(outline["nodes"][0]["type"], "group");
+        assert_eq!(outline["nodes"][0]["children"][1]["text"].as_str().unwrap().len(), 500);
+
+        let missing = OutlineOptions {
+            section: Some("Conclusion"),
+            ..Default::default()
+        };
+        assert!(document_outline(&sample(), &missing).is_err());
+    }
+
+    #[test]
+    fn test_outline_page_range_and_depth() {
+        let options = OutlineOptions {
+            first_page: Some(2),
+            ..Default::default()
+        };
+        let outline = document_outline(&sample(), &options).unwrap();
+        let children = outline["nodes"][0]["children"].as_array().unwrap();
+        assert_eq!(children.len(), 1);
+        assert_eq!(children[0]["page"], 2);
+
+        let options = OutlineOptions {
+            max_depth: Some(0),
+            ..Default::default()
+        };
+        let outline = document_outline(&sample(), &options).unwrap();
+        assert_eq!(outline["nodes"][0]["child_count"], 2);
+        assert!(outline["nodes"][0].get("children").is_none());
+    }
+}
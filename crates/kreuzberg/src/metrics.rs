@@ -0,0 +1,398 @@
+//! Process-wide operational metrics in Prometheus text format.
+//!
+//! Extraction, OCR and cache code record into a global [`Metrics`] registry when the
+//! `metrics` feature is enabled (it is enabled by the `api` feature). The API server
+//! exposes the registry at `GET /metrics`; embedders can call [`Metrics::render`] to
+//! serve it themselves.
+//!
+//! Recorded series:
+//!
+//! - `kreuzberg_http_requests_total` / `kreuzberg_http_request_duration_seconds` – per method, route and status
+//! - `kreuzberg_extraction_duration_seconds` / `kreuzberg_extraction_errors_total` – per MIME type and extractor
+//! - `kreuzberg_extractions_in_flight` – extractions currently running
+//! - `kreuzberg_ocr_pages_total` – pages or images sent to an OCR backend
+//! - `kreuzberg_cache_requests_total` / `kreuzberg_cache_hit_ratio` – per cache
+//! - `kreuzberg_string_buffer_pool_*` – shared buffer pool statistics (`pool-metrics` feature)
+//!
+//! # Example
+//!
+//! ```rust
+//! use kreuzberg::metrics;
+//!
+//! metrics::global().record_cache_lookup("ocr", true);
+//! let text = metrics::global().render();
+//! assert!(text.contains("kreuzberg_cache_requests_total"));
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    fmt::Write as _,
+    sync::atomic::{AtomicI64, Ordering},
+    time::Duration,
+};
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+
+/// Histogram bucket upper bounds in seconds, shared by all duration histograms.
+const DURATION_BUCKETS: [f64; 14] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 300.0,
+];
+
+static GLOBAL: Lazy<Metrics> = Lazy::new(Metrics::new);
+
+/// Access the process-wide metrics registry.
+pub fn global() -> &'static Metrics {
+    &GLOBAL
+}
+
+/// Cumulative duration histogram using [`DURATION_BUCKETS`].
+#[derive(Debug, Clone, Default)]
+struct Histogram {
+    buckets: [u64; DURATION_BUCKETS.len()],
+    count: u64,
+    sum: f64,
+}
+
+impl Histogram {
+    fn observe(&mut self, seconds: f64) {
+        for (bucket, bound) in self.buckets.iter_mut().zip(DURATION_BUCKETS) {
+            if seconds <= bound {
+                *bucket += 1;
+            }
+        }
+        self.count += 1;
+        self.sum += seconds;
+    }
+}
+
+/// Registry of counters, gauges and histograms.
+#[derive(Debug)]
+pub struct Metrics {
+    http_requests: Mutex<BTreeMap<(String, String, u16), u64>>,
+    http_durations: Mutex<BTreeMap<(String, String), Histogram>>,
+    extraction_durations: Mutex<BTreeMap<(String, String), Histogram>>,
+    extraction_errors: Mutex<BTreeMap<(String, String), u64>>,
+    ocr_pages: Mutex<BTreeMap<String, u64>>,
+    cache_lookups: Mutex<BTreeMap<String, (u64, u64)>>,
+    in_flight: AtomicI64,
+}
+
+/// Tracks one running extraction; decrements the in-flight gauge when dropped.
+#[derive(Debug)]
+pub struct InFlightGuard<'a> {
+    metrics: &'a Metrics,
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        self.metrics.in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Metrics {
+    /// Create an empty registry. Most callers should use [`global`] instead.
+    pub fn new() -> Self {
+        Self {
+            http_requests: Mutex::new(BTreeMap::new()),
+            http_durations: Mutex::new(BTreeMap::new()),
+            extraction_durations: Mutex::new(BTreeMap::new()),
+            extraction_errors: Mutex::new(BTreeMap::new()),
+            ocr_pages: Mutex::new(BTreeMap::new()),
+            cache_lookups: Mutex::new(BTreeMap::new()),
+            in_flight: AtomicI64::new(0),
+        }
+    }
+
+    /// Record a handled HTTP request.
+    ///
+    /// `route` should be the matched route template (e.g. `/jobs/{id}`), not the raw path,
+    /// to keep label cardinality bounded.
+    pub fn record_http_request(&self, method: &str, route: &str, status: u16, duration: Duration) {
+        *self
+            .http_requests
+            .lock()
+            .entry((method.to_string(), route.to_string(), status))
+            .or_default() += 1;
+        self.http_durations
+            .lock()
+            .entry((method.to_string(), route.to_string()))
+            .or_default()
+            .observe(duration.as_secs_f64());
+    }
+
+    /// Mark the start of an extraction. The returned guard ends it when dropped.
+    pub fn start_extraction(&self) -> InFlightGuard<'_> {
+        self.in_flight.fetch_add(1, Ordering::Relaxed);
+        InFlightGuard { metrics: self }
+    }
+
+    /// Record a finished extraction.
+    pub fn record_extraction(&self, mime_type: &str, extractor: &str, duration: Duration, success: bool) {
+        let key = (mime_type.to_string(), extractor.to_string());
+        if !success {
+            *self.extraction_errors.lock().entry(key.clone()).or_default() += 1;
+        }
+        self.extraction_durations
+            .lock()
+            .entry(key)
+            .or_default()
+            .observe(duration.as_secs_f64());
+    }
+
+    /// Record pages (or standalone images) processed by an OCR backend.
+    pub fn record_ocr_pages(&self, backend: &str, pages: u64) {
+        *self.ocr_pages.lock().entry(backend.to_string()).or_default() += pages;
+    }
+
+    /// Record a cache lookup for the named cache.
+    pub fn record_cache_lookup(&self, cache: &str, hit: bool) {
+        let mut lookups = self.cache_lookups.lock();
+        let (hits, misses) = lookups.entry(cache.to_string()).or_default();
+        if hit {
+            *hits += 1;
+        } else {
+            *misses += 1;
+        }
+    }
+
+    /// Number of extractions currently running.
+    pub fn extractions_in_flight(&self) -> i64 {
+        self.in_flight.load(Ordering::Relaxed)
+    }
+
+    /// Render all metrics in the Prometheus text exposition format (version 0.0.4).
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+
+        header(
+            &mut out,
+            "kreuzberg_http_requests_total",
+            "counter",
+            "HTTP requests handled, by method, route and status.",
+        );
+        for ((method, route, status), count) in self.http_requests.lock().iter() {
+            let labels = labels(&[("method", method), ("route", route), ("status", &status.to_string())]);
+            let _ = writeln!(out, "kreuzberg_http_requests_total{{{}}} {}", labels, count);
+        }
+
+        header(
+            &mut out,
+            "kreuzberg_http_request_duration_seconds",
+            "histogram",
+            "HTTP request latency in seconds, by method and route.",
+        );
+        for ((method, route), histogram) in self.http_durations.lock().iter() {
+            let labels = labels(&[("method", method), ("route", route)]);
+            write_histogram(&mut out, "kreuzberg_http_request_duration_seconds", &labels, histogram);
+        }
+
+        header(
+            &mut out,
+            "kreuzberg_extraction_duration_seconds",
+            "histogram",
+            "Extraction time in seconds including post-processing, by MIME type and extractor.",
+        );
+        for ((mime_type, extractor), histogram) in self.extraction_durations.lock().iter() {
+            let labels = labels(&[("mime_type", mime_type), ("extractor", extractor)]);
+            write_histogram(&mut out, "kreuzberg_extraction_duration_seconds", &labels, histogram);
+        }
+
+        header(
+            &mut out,
+            "kreuzberg_extraction_errors_total",
+            "counter",
+            "Failed extractions, by MIME type and extractor.",
+        );
+        for ((mime_type, extractor), count) in self.extraction_errors.lock().iter() {
+            let labels = labels(&[("mime_type", mime_type), ("extractor", extractor)]);
+            let _ = writeln!(out, "kreuzberg_extraction_errors_total{{{}}} {}", labels, count);
+        }
+
+        header(
+            &mut out,
+            "kreuzberg_extractions_in_flight",
+            "gauge",
+            "Extractions currently running.",
+        );
+        let _ = writeln!(out, "kreuzberg_extractions_in_flight {}", self.extractions_in_flight());
+
+        header(
+            &mut out,
+            "kreuzberg_ocr_pages_total",
+            "counter",
+            "Pages or images processed by an OCR backend.",
+        );
+        for (backend, pages) in self.ocr_pages.lock().iter() {
+            let _ = writeln!(
+                out,
+                "kreuzberg_ocr_pages_total{{{}}} {}",
+                labels(&[("backend", backend)]),
+                pages
+            );
+        }
+
+        let cache_lookups = self.cache_lookups.lock().clone();
+        header(
+            &mut out,
+            "kreuzberg_cache_requests_total",
+            "counter",
+            "Cache lookups, by cache and result.",
+        );
+        for (cache, (hits, misses)) in &cache_lookups {
+            let _ = writeln!(
+                out,
+                "kreuzberg_cache_requests_total{{{}}} {}",
+                labels(&[("cache", cache), ("result", "hit")]),
+                hits
+            );
+            let _ = writeln!(
+                out,
+                "kreuzberg_cache_requests_total{{{}}} {}",
+                labels(&[("cache", cache), ("result", "miss")]),
+                misses
+            );
+        }
+
+        header(
+            &mut out,
+            "kreuzberg_cache_hit_ratio",
+            "gauge",
+            "Fraction of cache lookups that were hits (0-1), by cache.",
+        );
+        for (cache, (hits, misses)) in &cache_lookups {
+            let total = hits + misses;
+            let ratio = if total == 0 { 0.0 } else { *hits as f64 / total as f64 };
+            let _ = writeln!(
+                out,
+                "kreuzberg_cache_hit_ratio{{{}}} {}",
+                labels(&[("cache", cache)]),
+                ratio
+            );
+        }
+
+        #[cfg(feature = "pool-metrics")]
+        {
+            let pool = crate::utils::string_pool::STRING_BUFFER_POOL.metrics();
+            header(
+                &mut out,
+                "kreuzberg_string_buffer_pool_acquires_total",
+                "counter",
+                "Buffers acquired from the shared string buffer pool.",
+            );
+            let _ = writeln!(
+                out,
+                "kreuzberg_string_buffer_pool_acquires_total {}",
+                pool.total_acquires
+            );
+            header(
+                &mut out,
+                "kreuzberg_string_buffer_pool_reuses_total",
+                "counter",
+                "Acquisitions served by reusing a pooled buffer.",
+            );
+            let _ = writeln!(out, "kreuzberg_string_buffer_pool_reuses_total {}", pool.total_reuses);
+            header(
+                &mut out,
+                "kreuzberg_string_buffer_pool_hit_ratio",
+                "gauge",
+                "Fraction of acquisitions served from the pool (0-1).",
+            );
+            let _ = writeln!(out, "kreuzberg_string_buffer_pool_hit_ratio {}", pool.hit_rate / 100.0);
+        }
+
+        out
+    }
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+fn labels(pairs: &[(&str, &str)]) -> String {
+    pairs
+        .iter()
+        .map(|(name, value)| format!("{}=\"{}\"", name, escape_label(value)))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+fn write_histogram(out: &mut String, name: &str, labels: &str, histogram: &Histogram) {
+    for (count, bound) in histogram.buckets.iter().zip(DURATION_BUCKETS) {
+        let _ = writeln!(out, "{}_bucket{{{},le=\"{}\"}} {}", name, labels, bound, count);
+    }
+    let _ = writeln!(out, "{}_bucket{{{},le=\"+Inf\"}} {}", name, labels, histogram.count);
+    let _ = writeln!(out, "{}_sum{{{}}} {}", name, labels, histogram.sum);
+    let _ = writeln!(out, "{}_count{{{}}} {}", name, labels, histogram.count);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_buckets_are_cumulative() {
+        let metrics = Metrics::new();
+        metrics.record_extraction("application/pdf", "pdf-extractor", Duration::from_millis(20), true);
+        metrics.record_extraction("application/pdf", "pdf-extractor", Duration::from_secs(3), false);
+
+        let text = metrics.render();
+        let labels = r#"mime_type="application/pdf",extractor="pdf-extractor""#;
+        assert!(text.contains(&format!(
+            "kreuzberg_extraction_duration_seconds_bucket{{{},le=\"0.025\"}} 1",
+            labels
+        )));
+        assert!(text.contains(&format!(
+            "kreuzberg_extraction_duration_seconds_bucket{{{},le=\"5\"}} 2",
+            labels
+        )));
+        assert!(text.contains(&format!(
+            "kreuzberg_extraction_duration_seconds_bucket{{{},le=\"+Inf\"}} 2",
+            labels
+        )));
+        assert!(text.contains(&format!("kreuzberg_extraction_duration_seconds_count{{{}}} 2", labels)));
+        assert!(text.contains(&format!("kreuzberg_extraction_errors_total{{{}}} 1", labels)));
+    }
+
+    #[test]
+    fn test_cache_ratio_and_in_flight() {
+        let metrics = Metrics::new();
+        metrics.record_cache_lookup("ocr", true);
+        metrics.record_cache_lookup("ocr", true);
+        metrics.record_cache_lookup("ocr", true);
+        metrics.record_cache_lookup("ocr", false);
+
+        let guard = metrics.start_extraction();
+        assert_eq!(metrics.extractions_in_flight(), 1);
+        let text = metrics.render();
+        assert!(text.contains("kreuzberg_cache_requests_total{cache=\"ocr\",result=\"hit\"} 3"));
+        assert!(text.contains("kreuzberg_cache_hit_ratio{cache=\"ocr\"} 0.75"));
+        assert!(text.contains("kreuzberg_extractions_in_flight 1"));
+
+        drop(guard);
+        assert_eq!(metrics.extractions_in_flight(), 0);
+    }
+
+    #[test]
+    fn test_http_requests_and_label_escaping() {
+        let metrics = Metrics::new();
+        metrics.record_http_request("GET", "/jobs/{id}", 404, Duration::from_millis(1));
+        metrics.record_ocr_pages("tess\"eract", 2);
+
+        let text = metrics.render();
+        assert!(text.contains("kreuzberg_http_requests_total{method=\"GET\",route=\"/jobs/{id}\",status=\"404\"} 1"));
+        assert!(text.contains("kreuzberg_ocr_pages_total{backend=\"tess\\\"eract\"} 2"));
+        assert!(text.contains("# TYPE kreuzberg_http_request_duration_seconds histogram"));
+    }
+}
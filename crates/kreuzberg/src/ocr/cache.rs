@@ -1,30 +1,37 @@
 use super::error::OcrError;
 use super::utils::compute_hash;
+use crate::cache::{CacheLimits, GenericCache, default_backend, open_backend, shared_cache};
 use crate::types::OcrExtractionResult;
-use std::fs;
 use std::path::PathBuf;
+use std::sync::Arc;
 
+const CACHE_TYPE: &str = "ocr";
+const MAX_AGE_DAYS: f64 = 30.0;
+const MAX_CACHE_SIZE_MB: f64 = 500.0;
+const MIN_FREE_SPACE_MB: f64 = 1000.0;
+
+/// Cache of OCR results keyed by image, backend and configuration, stored in the
+/// process-wide default cache backend.
 pub struct OcrCache {
-    cache_dir: PathBuf,
+    cache: Arc<GenericCache>,
 }
 
 impl OcrCache {
+    /// Open the cache in `cache_dir`, or share the process-wide OCR cache under
+    /// `KREUZBERG_CACHE_DIR` (or `.kreuzberg/ocr`) when `None`.
     pub fn new(cache_dir: Option<PathBuf>) -> Result<Self, OcrError> {
-        let cache_dir = cache_dir.unwrap_or_else(|| {
-            let mut path = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
-            path.push(".kreuzberg");
-            path.push("ocr");
-            path
-        });
-
-        fs::create_dir_all(&cache_dir)
-            .map_err(|e| OcrError::CacheError(format!("Failed to create cache directory: {}", e)))?;
-
-        Ok(Self { cache_dir })
-    }
+        let limits = CacheLimits::new(MAX_AGE_DAYS, MAX_CACHE_SIZE_MB, MIN_FREE_SPACE_MB);
+        let cache = match cache_dir {
+            Some(dir) => {
+                let backend = open_backend(default_backend(), dir, limits)
+                    .map_err(|e| OcrError::CacheError(format!("Failed to open cache: {}", e)))?;
+                Arc::new(GenericCache::from_backend(CACHE_TYPE.to_string(), backend))
+            }
+            None => shared_cache(CACHE_TYPE, limits)
+                .ok_or_else(|| OcrError::CacheError("OCR cache is unavailable".to_string()))?,
+        };
 
-    fn get_cache_path(&self, cache_key: &str) -> PathBuf {
-        self.cache_dir.join(format!("{}.msgpack", cache_key))
+        Ok(Self { cache })
     }
 
     pub fn get_cached_result(
@@ -34,20 +41,20 @@ impl OcrCache {
         config: &str,
     ) -> Result<Option<OcrExtractionResult>, OcrError> {
         let cache_key = self.generate_cache_key(image_hash, backend, config);
-        let cache_path = self.get_cache_path(&cache_key);
 
-        if !cache_path.exists() {
+        let Some(cached_bytes) = self
+            .cache
+            .get(&cache_key, None)
+            .map_err(|e| OcrError::CacheError(format!("Failed to read cache entry: {}", e)))?
+        else {
             return Ok(None);
-        }
-
-        let cached_bytes =
-            fs::read(&cache_path).map_err(|e| OcrError::CacheError(format!("Failed to read cache file: {}", e)))?;
+        };
 
         match rmp_serde::from_slice::<OcrExtractionResult>(&cached_bytes) {
             Ok(result) => Ok(Some(result)),
             Err(_) => {
                 // Stale cache entry (schema changed). Delete and treat as miss.
-                let _ = fs::remove_file(&cache_path);
+                let _ = self.cache.remove(&cache_key);
                 Ok(None)
             }
         }
@@ -61,29 +68,13 @@ impl OcrCache {
         result: &OcrExtractionResult,
     ) -> Result<(), OcrError> {
         let cache_key = self.generate_cache_key(image_hash, backend, config);
-        let cache_path = self.get_cache_path(&cache_key);
 
         let serialized = rmp_serde::to_vec(result)
             .map_err(|e| OcrError::CacheError(format!("Failed to serialize result: {}", e)))?;
 
-        let pid = std::process::id();
-        let thread_id = std::thread::current().id();
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_nanos();
-        let temp_name = format!("{}.tmp.{}.{:?}.{}", cache_key, pid, thread_id, timestamp);
-        let temp_path = self.cache_dir.join(temp_name);
-
-        fs::write(&temp_path, &serialized)
-            .map_err(|e| OcrError::CacheError(format!("Failed to write temp cache file: {}", e)))?;
-
-        fs::rename(&temp_path, &cache_path).map_err(|e| {
-            let _ = fs::remove_file(&temp_path);
-            OcrError::CacheError(format!("Failed to rename cache file: {}", e))
-        })?;
-
-        Ok(())
+        self.cache
+            .set(&cache_key, serialized, None)
+            .map_err(|e| OcrError::CacheError(format!("Failed to write cache entry: {}", e)))
     }
 
     fn generate_cache_key(&self, image_hash: &str, backend: &str, config: &str) -> String {
@@ -96,49 +87,21 @@ impl OcrCache {
     }
 
     pub fn clear(&self) -> Result<(), OcrError> {
-        if !self.cache_dir.exists() {
-            return Ok(());
-        }
-
-        let entries = fs::read_dir(&self.cache_dir)
-            .map_err(|e| OcrError::CacheError(format!("Failed to read cache directory: {}", e)))?;
-
-        for entry in entries.flatten() {
-            if let Some(ext) = entry.path().extension()
-                && ext == "msgpack"
-            {
-                let _ = fs::remove_file(entry.path());
-            }
-        }
-
-        Ok(())
+        self.cache
+            .clear()
+            .map(|_| ())
+            .map_err(|e| OcrError::CacheError(format!("Failed to clear cache: {}", e)))
     }
 
     pub fn get_stats(&self) -> Result<OcrCacheStats, OcrError> {
-        if !self.cache_dir.exists() {
-            return Ok(OcrCacheStats::default());
-        }
-
-        let entries = fs::read_dir(&self.cache_dir)
-            .map_err(|e| OcrError::CacheError(format!("Failed to read cache directory: {}", e)))?;
-
-        let mut total_files = 0;
-        let mut total_size_bytes = 0u64;
-
-        for entry in entries.flatten() {
-            if let Some(ext) = entry.path().extension()
-                && ext == "msgpack"
-            {
-                total_files += 1;
-                if let Ok(metadata) = entry.metadata() {
-                    total_size_bytes += metadata.len();
-                }
-            }
-        }
+        let stats = self
+            .cache
+            .get_stats()
+            .map_err(|e| OcrError::CacheError(format!("Failed to read cache statistics: {}", e)))?;
 
         Ok(OcrCacheStats {
-            total_files,
-            total_size_mb: total_size_bytes as f64 / 1024.0 / 1024.0,
+            total_files: stats.total_files,
+            total_size_mb: stats.total_size_mb,
         })
     }
 }
@@ -153,6 +116,7 @@ pub struct OcrCacheStats {
 mod tests {
     use super::*;
     use std::collections::HashMap;
+    use std::fs;
 
     #[test]
     fn test_cache_get_set() {
@@ -406,34 +370,25 @@ mod tests {
     }
 
     #[test]
-    fn test_cache_stats_nonexistent_dir() {
+    fn test_cache_stats_removed_dir() {
         let temp_dir = tempfile::tempdir().unwrap();
-        let cache_path = temp_dir.path().join("nonexistent");
-        let cache = OcrCache { cache_dir: cache_path };
+        let cache_path = temp_dir.path().join("removed");
+        let cache = OcrCache::new(Some(cache_path.clone())).unwrap();
+        fs::remove_dir_all(&cache_path).unwrap();
 
         let stats = cache.get_stats().unwrap();
         assert_eq!(stats.total_files, 0);
         assert_eq!(stats.total_size_mb, 0.0);
-    }
-
-    #[test]
-    fn test_cache_clear_nonexistent_dir() {
-        let temp_dir = tempfile::tempdir().unwrap();
-        let cache_path = temp_dir.path().join("nonexistent");
-        let cache = OcrCache { cache_dir: cache_path };
-
         assert!(cache.clear().is_ok());
     }
 
     #[test]
-    fn test_cache_get_path() {
+    fn test_cache_uses_given_directory() {
         let temp_dir = tempfile::tempdir().unwrap();
         let cache = OcrCache::new(Some(temp_dir.path().to_path_buf())).unwrap();
 
-        let path = cache.get_cache_path("abc123");
-
-        assert!(path.to_string_lossy().contains("abc123.msgpack"));
-        assert_eq!(path.parent().unwrap(), temp_dir.path());
+        assert_eq!(cache.cache.cache_dir(), temp_dir.path());
+        assert_eq!(cache.cache.cache_type(), "ocr");
     }
 
     #[test]
@@ -14,6 +14,7 @@ use crate::ocr::error::OcrError;
 use crate::ocr::hocr::convert_hocr_to_markdown;
 use crate::ocr::table::{extract_words_from_tsv, post_process_table, reconstruct_table, table_to_markdown};
 use crate::ocr::types::{BatchItemResult, TesseractConfig};
+use crate::ocr::utils::StableHasher;
 use crate::types::{OcrExtractionResult, OcrTable};
 use kreuzberg_tesseract::{TessPageSegMode, TesseractAPI};
 use std::collections::HashMap;
@@ -488,7 +489,7 @@ fn process_image_resolved(
     cache: &OcrCache,
     output_format: Option<crate::core::config::OutputFormat>,
 ) -> Result<OcrExtractionResult, OcrError> {
-    let mut hasher = ahash::AHasher::default();
+    let mut hasher = StableHasher::default();
     use std::hash::{Hash, Hasher};
     image_bytes.hash(&mut hasher);
     let image_hash = format!("{:016x}", hasher.finish());
@@ -625,16 +626,15 @@ mod tests {
 
     #[test]
     fn test_compute_image_hash_deterministic() {
-        use ahash::AHasher;
         use std::hash::{Hash, Hasher};
 
         let image_bytes = vec![1, 2, 3, 4, 5];
 
-        let mut hasher1 = AHasher::default();
+        let mut hasher1 = StableHasher::default();
         image_bytes.hash(&mut hasher1);
         let hash1 = format!("{:016x}", hasher1.finish());
 
-        let mut hasher2 = AHasher::default();
+        let mut hasher2 = StableHasher::default();
         image_bytes.hash(&mut hasher2);
         let hash2 = format!("{:016x}", hasher2.finish());
 
@@ -644,17 +644,16 @@ mod tests {
 
     #[test]
     fn test_compute_image_hash_different_data() {
-        use ahash::AHasher;
         use std::hash::{Hash, Hasher};
 
         let image_bytes1 = vec![1, 2, 3, 4, 5];
         let image_bytes2 = vec![5, 4, 3, 2, 1];
 
-        let mut hasher1 = AHasher::default();
+        let mut hasher1 = StableHasher::default();
         image_bytes1.hash(&mut hasher1);
         let hash1 = format!("{:016x}", hasher1.finish());
 
-        let mut hasher2 = AHasher::default();
+        let mut hasher2 = StableHasher::default();
         image_bytes2.hash(&mut hasher2);
         let hash2 = format!("{:016x}", hasher2.finish());
 
@@ -1,5 +1,5 @@
-use ahash::AHasher;
-use std::hash::{Hash, Hasher};
+use sha2::{Digest, Sha256};
+use std::hash::Hasher;
 
 /// Minimal supported Tesseract version
 pub const MINIMAL_SUPPORTED_TESSERACT_VERSION: u32 = 5;
@@ -13,10 +13,27 @@ pub const MIN_COLUMN_WIDTH: usize = 3;
 
 /// Compute a hash string from input data
 pub fn compute_hash(data: &str) -> String {
-    let mut hasher = AHasher::default();
-    data.hash(&mut hasher);
-    let hash = hasher.finish();
-    format!("{:016x}", hash)
+    let mut hasher = StableHasher::default();
+    hasher.write(data.as_bytes());
+    format!("{:016x}", hasher.finish())
+}
+
+/// [`Hasher`] backed by SHA-256, for cache keys that must be identical across processes
+/// and hosts (`ahash` is randomly seeded per process).
+#[derive(Default)]
+pub(crate) struct StableHasher(Sha256);
+
+impl Hasher for StableHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        let digest = self.0.clone().finalize();
+        let mut prefix = [0u8; 8];
+        prefix.copy_from_slice(&digest[..8]);
+        u64::from_be_bytes(prefix)
+    }
 }
 
 #[cfg(test)]
@@ -39,6 +56,11 @@ mod tests {
         assert_ne!(hash1, hash2);
     }
 
+    #[test]
+    fn test_compute_hash_is_stable_across_processes() {
+        assert_eq!(compute_hash("abc"), "ba7816bf8f01cfea");
+    }
+
     #[test]
     fn test_compute_hash_empty() {
         let hash = compute_hash("");
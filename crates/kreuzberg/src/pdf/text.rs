@@ -174,7 +174,7 @@ pub fn extract_text_from_pdf_document(
     if let Some(config) = page_config {
         extract_text_lazy_with_tracking(document, config, extraction_config)
     } else {
-        extract_text_lazy_fast_path(document)
+        extract_text_lazy_fast_path(document, extraction_config)
     }
 }
 
@@ -264,16 +264,26 @@ fn find_rotate_offset(bytes: &[u8], start: usize) -> Option<usize> {
 /// and extrapolating for the full document. This reduces String reallocation
 /// calls from O(n) to O(log n) while maintaining low peak memory usage.
 /// For large documents, this can reduce allocation overhead by 40-50%.
-fn extract_text_lazy_fast_path(document: &PdfDocument<'_>) -> Result<PdfTextExtractionResult> {
+fn extract_text_lazy_fast_path(
+    document: &PdfDocument<'_>,
+    extraction_config: Option<&crate::core::config::ExtractionConfig>,
+) -> Result<PdfTextExtractionResult> {
     let page_count = document.pages().len() as usize;
     let mut content = String::new();
     let mut total_sample_size = 0usize;
     let mut sample_count = 0;
 
     for (page_idx, page) in document.pages().iter().enumerate() {
+        if stop_before_page(extraction_config, page_idx) {
+            break;
+        }
+
         let text = page
             .text()
-            .map_err(|e| PdfError::TextExtractionFailed(format!("Page text extraction failed: {}", e)))?;
+            .map_err(|e| PdfError::TextExtractionFailed(format!("Page text extraction failed: {}", e)));
+        let Some(text) = recover(extraction_config, || format!("page {}", page_idx + 1), text)? else {
+            continue;
+        };
 
         let page_text = text.all();
         let page_size = page_text.len();
@@ -345,10 +355,16 @@ fn extract_text_lazy_with_tracking(
 
     for (page_idx, page) in document.pages().iter().enumerate() {
         let page_number = page_idx + 1;
+        if stop_before_page(extraction_config, page_idx) {
+            break;
+        }
 
         let text = page
             .text()
-            .map_err(|e| PdfError::TextExtractionFailed(format!("Page text extraction failed: {}", e)))?;
+            .map_err(|e| PdfError::TextExtractionFailed(format!("Page text extraction failed: {}", e)));
+        let Some(text) = recover(extraction_config, || format!("page {}", page_idx + 1), text)? else {
+            continue;
+        };
 
         let page_text_ref = text.all();
         let page_size = page_text_ref.len();
@@ -380,7 +396,13 @@ fn extract_text_lazy_with_tracking(
         if let Some(ref mut pages) = page_contents {
             // Extract hierarchy if enabled
             let hierarchy = if should_extract_hierarchy {
-                extract_page_hierarchy(&page, hierarchy_config.as_ref())?
+                let hierarchy = extract_page_hierarchy(&page, hierarchy_config.as_ref());
+                recover(
+                    extraction_config,
+                    || format!("heading detection on page {}", page_number),
+                    hierarchy,
+                )?
+                .flatten()
             } else {
                 None
             };
@@ -407,6 +429,26 @@ fn extract_text_lazy_with_tracking(
     Ok((content, Some(boundaries), page_contents))
 }
 
+/// Cancellation checkpoint before the page at `page_idx`.
+///
+/// Stops on cancellation whether or not partial results were requested; callers check the
+/// configuration again after the text pass to turn the latter case into an error.
+fn stop_before_page(extraction_config: Option<&crate::core::config::ExtractionConfig>, page_idx: usize) -> bool {
+    extraction_config.is_some_and(|config| !matches!(config.checkpoint(|| format!("page {}", page_idx + 1)), Ok(false)))
+}
+
+/// Best-effort recovery from a failure on one page; `Ok(None)` skips the unit.
+fn recover<T>(
+    extraction_config: Option<&crate::core::config::ExtractionConfig>,
+    location: impl FnOnce() -> String,
+    result: Result<T>,
+) -> Result<Option<T>> {
+    match extraction_config {
+        Some(config) => config.recover(location, result),
+        None => result.map(Some),
+    }
+}
+
 /// Extract text hierarchy from a single PDF page.
 ///
 /// Uses font size clustering to identify heading levels (H1-H6) and assigns
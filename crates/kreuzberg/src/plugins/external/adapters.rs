@@ -0,0 +1,157 @@
+//! Plugin trait implementations forwarding to an [`ExternalPlugin`].
+
+use super::ExternalPlugin;
+use super::protocol::Binary;
+use crate::Result;
+use crate::core::config::{ExtractionConfig, OcrConfig};
+use crate::plugins::{
+    DocumentExtractor, OcrBackend, OcrBackendType, Plugin, PostProcessor, ProcessingStage, Validator,
+};
+use crate::types::ExtractionResult;
+use async_trait::async_trait;
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Serialize)]
+struct ExtractParams<'a> {
+    content: Binary<'a>,
+    mime_type: &'a str,
+    config: &'a ExtractionConfig,
+}
+
+#[derive(Serialize)]
+struct OcrParams<'a> {
+    image: Binary<'a>,
+    config: &'a OcrConfig,
+}
+
+#[derive(Serialize)]
+struct ResultParams<'a> {
+    result: &'a ExtractionResult,
+    config: &'a ExtractionConfig,
+}
+
+macro_rules! external_adapter {
+    ($name:ident, $doc:literal) => {
+        #[doc = $doc]
+        pub(super) struct $name(Arc<ExternalPlugin>);
+
+        impl $name {
+            pub(super) fn new(plugin: Arc<ExternalPlugin>) -> Self {
+                Self(plugin)
+            }
+        }
+
+        impl Plugin for $name {
+            fn name(&self) -> &str {
+                &self.0.info.name
+            }
+
+            fn version(&self) -> String {
+                self.0.info.version.clone()
+            }
+
+            fn initialize(&self) -> Result<()> {
+                self.0.acquire();
+                Ok(())
+            }
+
+            fn shutdown(&self) -> Result<()> {
+                self.0.release();
+                Ok(())
+            }
+
+            fn description(&self) -> &str {
+                "External plugin"
+            }
+        }
+    };
+}
+
+external_adapter!(ExternalExtractor, "[`DocumentExtractor`] sending `extract` requests.");
+external_adapter!(ExternalOcrBackend, "[`OcrBackend`] sending `ocr` requests.");
+external_adapter!(ExternalPostProcessor, "[`PostProcessor`] sending `process` requests.");
+external_adapter!(ExternalValidator, "[`Validator`] sending `validate` requests.");
+
+#[async_trait]
+impl DocumentExtractor for ExternalExtractor {
+    async fn extract_bytes(
+        &self,
+        content: &[u8],
+        mime_type: &str,
+        config: &ExtractionConfig,
+    ) -> Result<ExtractionResult> {
+        let params = ExtractParams {
+            content: Binary(content),
+            mime_type,
+            config,
+        };
+        let value = self.0.call("extract", &params).await?;
+        let base = ExtractionResult {
+            mime_type: mime_type.to_string().into(),
+            ..Default::default()
+        };
+        self.0.decode_result("extract", value, &base)
+    }
+
+    fn supported_mime_types(&self) -> &[&str] {
+        &self.0.mime_types
+    }
+
+    fn priority(&self) -> i32 {
+        self.0.info.priority
+    }
+}
+
+#[async_trait]
+impl OcrBackend for ExternalOcrBackend {
+    async fn process_image(&self, image_bytes: &[u8], config: &OcrConfig) -> Result<ExtractionResult> {
+        let params = OcrParams {
+            image: Binary(image_bytes),
+            config,
+        };
+        let value = self.0.call("ocr", &params).await?;
+        let base = ExtractionResult {
+            mime_type: "text/plain".into(),
+            ..Default::default()
+        };
+        self.0.decode_result("ocr", value, &base)
+    }
+
+    fn supports_language(&self, lang: &str) -> bool {
+        self.0.info.languages.is_empty() || self.0.info.languages.iter().any(|l| l == lang)
+    }
+
+    fn backend_type(&self) -> OcrBackendType {
+        OcrBackendType::Custom
+    }
+
+    fn supported_languages(&self) -> Vec<String> {
+        self.0.info.languages.clone()
+    }
+}
+
+#[async_trait]
+impl PostProcessor for ExternalPostProcessor {
+    async fn process(&self, result: &mut ExtractionResult, config: &ExtractionConfig) -> Result<()> {
+        let value = self.0.call("process", &ResultParams { result, config }).await?;
+        *result = self.0.decode_result("process", value, result)?;
+        Ok(())
+    }
+
+    fn processing_stage(&self) -> ProcessingStage {
+        self.0.info.processing_stage
+    }
+}
+
+#[async_trait]
+impl Validator for ExternalValidator {
+    async fn validate(&self, result: &ExtractionResult, config: &ExtractionConfig) -> Result<()> {
+        self.0.call("validate", &ResultParams { result, config }).await?;
+        Ok(())
+    }
+
+    fn priority(&self) -> i32 {
+        self.0.info.priority
+    }
+}
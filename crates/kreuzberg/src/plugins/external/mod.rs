@@ -0,0 +1,417 @@
+//! Out-of-process and sandboxed plugins.
+//!
+//! An external plugin is an executable that speaks a small JSON-RPC protocol over its
+//! stdin and stdout. Kreuzberg starts the executable, asks it what it provides during a
+//! handshake, and registers adapters implementing
+//! [`DocumentExtractor`](super::DocumentExtractor), [`OcrBackend`](super::OcrBackend),
+//! [`PostProcessor`](super::PostProcessor) and [`Validator`](super::Validator) that forward
+//! calls to it. Plugins can therefore be written in any language, and a crashing plugin
+//! cannot take the host process down.
+//!
+//! With the `wasi-plugins` feature, the same messages can be handled by a WebAssembly module
+//! running in a WASI sandbox instead; see `WasiPluginConfig`.
+//!
+//! # Protocol
+//!
+//! Messages are frames of a 4-byte big-endian length followed by a JSON or MessagePack
+//! payload (see [`ExternalPluginEncoding`]). The host sends JSON-RPC 2.0 requests
+//! (`{"jsonrpc": "2.0", "id": 1, "method": ..., "params": ...}`) and the plugin answers each
+//! with a response carrying the same `id` and either a `result` or an
+//! `error: {"code", "message"}`. Error codes are listed in [`error_codes`].
+//!
+//! | Method | Params | Result |
+//! |--------|--------|--------|
+//! | `handshake` | `protocol_version`, `kreuzberg_version` | [`ExternalPluginInfo`] |
+//! | `extract` | `content`, `mime_type`, `config` | extraction result |
+//! | `ocr` | `image`, `config` (OCR config) | extraction result |
+//! | `process` | `result`, `config` | the processed extraction result |
+//! | `validate` | `result`, `config` | anything; an error rejects the result |
+//!
+//! Binary params (`content`, `image`) are base64 strings in JSON and binary values in
+//! MessagePack. Extraction results may omit any field; `process` keeps the previous value of
+//! missing fields and the other methods use their defaults.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use kreuzberg::Kreuzberg;
+//! use kreuzberg::core::config::ExtractionConfig;
+//! use kreuzberg::plugins::external::ExternalPluginConfig;
+//!
+//! # async fn example() -> kreuzberg::Result<()> {
+//! let engine = Kreuzberg::new(ExtractionConfig::default())?;
+//! let mut config = ExternalPluginConfig::new("/usr/local/bin/my-plugin");
+//! config.max_concurrency = 4;
+//! let plugin = engine.register_external_plugin(config).await?;
+//! println!("registered {} {}", plugin.info().name, plugin.info().version);
+//! # Ok(())
+//! # }
+//! ```
+
+mod adapters;
+mod process;
+mod protocol;
+#[cfg(feature = "wasi-plugins")]
+mod wasi;
+
+pub use protocol::{ExternalPluginEncoding, PROTOCOL_VERSION, error_codes};
+#[cfg(feature = "wasi-plugins")]
+pub use wasi::WasiPluginConfig;
+
+use crate::engine::Kreuzberg;
+use crate::plugins::ProcessingStage;
+use crate::types::ExtractionResult;
+use crate::{KreuzbergError, Result};
+use adapters::{ExternalExtractor, ExternalOcrBackend, ExternalPostProcessor, ExternalValidator};
+use process::{WorkerPool, plugin_error};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+
+/// How to run an external plugin executable.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExternalPluginConfig {
+    /// Executable to run.
+    pub command: PathBuf,
+    /// Arguments passed to the executable.
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Extra environment variables for the plugin process.
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// Working directory of the plugin process (default: inherited).
+    #[serde(default)]
+    pub working_dir: Option<PathBuf>,
+    /// Payload encoding (default: JSON).
+    #[serde(default)]
+    pub encoding: ExternalPluginEncoding,
+    /// Seconds to wait for a response before killing the process (default: 60).
+    #[serde(default = "default_request_timeout_secs")]
+    pub request_timeout_secs: u64,
+    /// Seconds to wait for the handshake of a new process (default: 10).
+    #[serde(default = "default_startup_timeout_secs")]
+    pub startup_timeout_secs: u64,
+    /// Maximum number of plugin processes, and therefore concurrent requests (default: 1).
+    #[serde(default = "default_max_concurrency")]
+    pub max_concurrency: usize,
+    /// Consecutive crashes or timeouts tolerated before the plugin is disabled (default: 3).
+    #[serde(default = "default_max_restarts")]
+    pub max_restarts: u32,
+}
+
+fn default_request_timeout_secs() -> u64 {
+    60
+}
+
+fn default_startup_timeout_secs() -> u64 {
+    10
+}
+
+fn default_max_concurrency() -> usize {
+    1
+}
+
+fn default_max_restarts() -> u32 {
+    3
+}
+
+impl ExternalPluginConfig {
+    /// Configuration running `command` without arguments and with default limits.
+    pub fn new(command: impl Into<PathBuf>) -> Self {
+        Self {
+            command: command.into(),
+            args: Vec::new(),
+            env: HashMap::new(),
+            working_dir: None,
+            encoding: ExternalPluginEncoding::default(),
+            request_timeout_secs: default_request_timeout_secs(),
+            startup_timeout_secs: default_startup_timeout_secs(),
+            max_concurrency: default_max_concurrency(),
+            max_restarts: default_max_restarts(),
+        }
+    }
+}
+
+/// Plugin kinds an external plugin can provide.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExternalPluginCapability {
+    /// Handles `extract` requests as a [`DocumentExtractor`](super::DocumentExtractor).
+    Extractor,
+    /// Handles `ocr` requests as an [`OcrBackend`](super::OcrBackend).
+    OcrBackend,
+    /// Handles `process` requests as a [`PostProcessor`](super::PostProcessor).
+    PostProcessor,
+    /// Handles `validate` requests as a [`Validator`](super::Validator).
+    Validator,
+}
+
+/// What an external plugin reported about itself during the handshake.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExternalPluginInfo {
+    /// Plugin name, used as the registry name of every adapter.
+    pub name: String,
+    /// Plugin version.
+    #[serde(default)]
+    pub version: String,
+    /// Plugin kinds provided.
+    pub capabilities: Vec<ExternalPluginCapability>,
+    /// MIME types handled by the extractor.
+    #[serde(default)]
+    pub mime_types: Vec<String>,
+    /// Extractor, post-processor and validator priority (default: 50).
+    #[serde(default = "default_priority")]
+    pub priority: i32,
+    /// Post-processor stage: `"early"`, `"middle"` (default) or `"late"`.
+    #[serde(default = "default_stage", deserialize_with = "deserialize_stage")]
+    pub processing_stage: ProcessingStage,
+    /// Languages supported by the OCR backend; empty means any.
+    #[serde(default)]
+    pub languages: Vec<String>,
+}
+
+fn default_priority() -> i32 {
+    50
+}
+
+fn default_stage() -> ProcessingStage {
+    ProcessingStage::Middle
+}
+
+fn deserialize_stage<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<ProcessingStage, D::Error> {
+    match String::deserialize(deserializer)?.to_lowercase().as_str() {
+        "early" => Ok(ProcessingStage::Early),
+        "middle" => Ok(ProcessingStage::Middle),
+        "late" => Ok(ProcessingStage::Late),
+        other => Err(serde::de::Error::unknown_variant(other, &["early", "middle", "late"])),
+    }
+}
+
+/// Where the requests of an [`ExternalPlugin`] are executed.
+enum Backend {
+    Process(Box<WorkerPool>),
+    #[cfg(feature = "wasi-plugins")]
+    Wasi(wasi::WasiModule),
+}
+
+/// A running external plugin.
+///
+/// Shared by the adapters registered for it; the plugin is shut down when the last
+/// registered adapter is shut down or the plugin is dropped.
+pub struct ExternalPlugin {
+    info: ExternalPluginInfo,
+    mime_types: Vec<&'static str>,
+    backend: Backend,
+    registrations: AtomicUsize,
+}
+
+impl ExternalPlugin {
+    /// Start the plugin executable and perform the handshake.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`KreuzbergError::Plugin`] if the executable cannot be started, does not
+    /// complete the handshake in time, or reports no capabilities.
+    pub async fn start(config: ExternalPluginConfig) -> Result<Arc<Self>> {
+        let command = config.command.display().to_string();
+        let (pool, info) = WorkerPool::start(config).await?;
+        Self::from_handshake(Backend::Process(Box::new(pool)), info, &command)
+    }
+
+    /// Load a WebAssembly plugin module and perform the handshake.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`KreuzbergError::Plugin`] if the module cannot be compiled, does not export
+    /// the plugin ABI, exceeds its limits during the handshake, or reports no capabilities.
+    #[cfg(feature = "wasi-plugins")]
+    pub async fn load_wasi(config: WasiPluginConfig) -> Result<Arc<Self>> {
+        let path = config.module.display().to_string();
+        let module = wasi::WasiModule::load(config).await?;
+        let info = module.handshake().await?;
+        Self::from_handshake(Backend::Wasi(module), info, &path)
+    }
+
+    fn from_handshake(backend: Backend, info: Value, source: &str) -> Result<Arc<Self>> {
+        let info: ExternalPluginInfo = serde_json::from_value(info)
+            .map_err(|e| plugin_error(format!("Invalid handshake response: {}", e), source))?;
+        if info.capabilities.is_empty() {
+            return Err(plugin_error("Plugin reported no capabilities", &info.name));
+        }
+
+        let mime_types = info.mime_types.iter().map(|mime| intern_mime_type(mime)).collect();
+
+        Ok(Arc::new(Self {
+            info,
+            mime_types,
+            backend,
+            registrations: AtomicUsize::new(0),
+        }))
+    }
+
+    /// Handshake information reported by the plugin.
+    pub fn info(&self) -> &ExternalPluginInfo {
+        &self.info
+    }
+
+    /// Whether the plugin provides `capability`.
+    pub fn has_capability(&self, capability: ExternalPluginCapability) -> bool {
+        self.info.capabilities.contains(&capability)
+    }
+
+    /// Kill the plugin processes or unload the module. Later requests fail with
+    /// [`KreuzbergError::Plugin`].
+    pub fn shutdown(&self) {
+        match &self.backend {
+            Backend::Process(pool) => pool.shutdown(),
+            #[cfg(feature = "wasi-plugins")]
+            Backend::Wasi(module) => module.shutdown(),
+        }
+    }
+
+    /// Register an adapter with `engine` for every capability of the plugin.
+    pub fn register_with(self: &Arc<Self>, engine: &Kreuzberg) -> Result<()> {
+        for capability in &self.info.capabilities {
+            match capability {
+                ExternalPluginCapability::Extractor => {
+                    engine.register_extractor(Arc::new(ExternalExtractor::new(Arc::clone(self))))?
+                }
+                ExternalPluginCapability::OcrBackend => {
+                    engine.register_ocr_backend(Arc::new(ExternalOcrBackend::new(Arc::clone(self))))?
+                }
+                ExternalPluginCapability::PostProcessor => engine.register_post_processor(
+                    Arc::new(ExternalPostProcessor::new(Arc::clone(self))),
+                    self.info.priority,
+                )?,
+                ExternalPluginCapability::Validator => {
+                    engine.register_validator(Arc::new(ExternalValidator::new(Arc::clone(self))))?
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub(super) async fn call<P: Serialize>(&self, method: &str, params: &P) -> Result<Value> {
+        match &self.backend {
+            Backend::Process(pool) => pool.call(method, params).await,
+            #[cfg(feature = "wasi-plugins")]
+            Backend::Wasi(module) => module.call(method, params).await,
+        }
+    }
+
+    /// Decode an extraction result, taking fields the plugin left out from `base`.
+    fn decode_result(&self, method: &str, value: Value, base: &ExtractionResult) -> Result<ExtractionResult> {
+        let mut merged = serde_json::to_value(base).map_err(|e| KreuzbergError::serialization(e.to_string()))?;
+        match (value, &mut merged) {
+            (Value::Object(fields), Value::Object(base_fields)) => base_fields.extend(fields),
+            (other, _) => {
+                return Err(plugin_error(
+                    format!("'{}' must return an extraction result object, got {}", method, other),
+                    &self.info.name,
+                ));
+            }
+        }
+        serde_json::from_value(merged).map_err(|e| {
+            plugin_error(
+                format!("Invalid extraction result from '{}': {}", method, e),
+                &self.info.name,
+            )
+        })
+    }
+
+    fn acquire(&self) {
+        self.registrations.fetch_add(1, Ordering::AcqRel);
+    }
+
+    fn release(&self) {
+        if self.registrations.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.shutdown();
+        }
+    }
+}
+
+impl std::fmt::Debug for ExternalPlugin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExternalPlugin").field("info", &self.info).finish()
+    }
+}
+
+/// Intern `mime` for the lifetime of the process.
+///
+/// `DocumentExtractor::supported_mime_types` hands out borrowed strings that must outlive
+/// the adapters. Each distinct MIME type is allocated once, so loading or restarting
+/// plugins does not grow memory.
+fn intern_mime_type(mime: &str) -> &'static str {
+    static INTERNED: OnceLock<parking_lot::Mutex<HashSet<&'static str>>> = OnceLock::new();
+
+    let mut interned = INTERNED.get_or_init(Default::default).lock();
+    if let Some(existing) = interned.get(mime) {
+        return existing;
+    }
+    let leaked: &'static str = Box::leak(mime.to_owned().into_boxed_str());
+    interned.insert(leaked);
+    leaked
+}
+
+/// Start an external plugin and register it with the global registries.
+///
+/// See [`Kreuzberg::register_external_plugin`] to register it with a specific engine.
+pub async fn register_external_plugin(config: ExternalPluginConfig) -> Result<Arc<ExternalPlugin>> {
+    Kreuzberg::global().register_external_plugin(config).await
+}
+
+/// Load a WebAssembly plugin and register it with the global registries.
+///
+/// See [`Kreuzberg::register_wasi_plugin`] to register it with a specific engine.
+#[cfg(feature = "wasi-plugins")]
+pub async fn register_wasi_plugin(config: WasiPluginConfig) -> Result<Arc<ExternalPlugin>> {
+    Kreuzberg::global().register_wasi_plugin(config).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_defaults_from_serde() {
+        let config: ExternalPluginConfig = serde_json::from_str(r#"{"command": "plugin"}"#).unwrap();
+        assert_eq!(config.command, PathBuf::from("plugin"));
+        assert_eq!(config.encoding, ExternalPluginEncoding::Json);
+        assert_eq!(config.request_timeout_secs, 60);
+        assert_eq!(config.max_concurrency, 1);
+        assert_eq!(config.max_restarts, 3);
+
+        let config: ExternalPluginConfig =
+            serde_json::from_str(r#"{"command": "plugin", "encoding": "msgpack"}"#).unwrap();
+        assert_eq!(config.encoding, ExternalPluginEncoding::MessagePack);
+    }
+
+    #[test]
+    fn test_handshake_info_defaults() {
+        let info: ExternalPluginInfo =
+            serde_json::from_str(r#"{"name": "p", "capabilities": ["post_processor"], "processing_stage": "Late"}"#)
+                .unwrap();
+        assert_eq!(info.capabilities, vec![ExternalPluginCapability::PostProcessor]);
+        assert_eq!(info.processing_stage, ProcessingStage::Late);
+        assert_eq!(info.priority, 50);
+        assert!(info.mime_types.is_empty());
+
+        assert!(
+            serde_json::from_str::<ExternalPluginInfo>(
+                r#"{"name": "p", "capabilities": [], "processing_stage": "sometime"}"#
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_mime_types_are_interned_once() {
+        let first = intern_mime_type("application/x-interned-test");
+        let second = intern_mime_type(&String::from("application/x-interned-test"));
+        assert_eq!(first, "application/x-interned-test");
+        assert!(std::ptr::eq(first, second));
+    }
+}
@@ -0,0 +1,285 @@
+//! Worker processes of an external plugin.
+//!
+//! A [`WorkerPool`] owns up to `max_concurrency` child processes, each serving one request
+//! at a time. Workers are spawned lazily, replaced after a crash or timeout, and the plugin
+//! is disabled once it has crashed more than `max_restarts` times in a row.
+
+use super::ExternalPluginConfig;
+use super::protocol::{HandshakeParams, Request, Response, read_frame, write_frame};
+use crate::{KreuzbergError, Result};
+use serde::Serialize;
+use serde_json::Value;
+use std::process::Stdio;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, BufReader, BufWriter};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+use tokio::sync::Semaphore;
+
+/// Failure of a single request.
+enum CallError {
+    /// The plugin answered with an error object; the worker remains usable.
+    Rpc(KreuzbergError),
+    /// The worker died, hung or broke the protocol and must be replaced.
+    Transport(String),
+}
+
+struct Worker {
+    child: Child,
+    stdin: BufWriter<ChildStdin>,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl Worker {
+    async fn request<P: Serialize>(
+        &mut self,
+        config: &ExternalPluginConfig,
+        plugin_name: &str,
+        id: u64,
+        method: &str,
+        params: &P,
+    ) -> std::result::Result<Value, CallError> {
+        let request = Request {
+            jsonrpc: "2.0",
+            id,
+            method,
+            params,
+        };
+        let payload = config
+            .encoding
+            .encode(&request)
+            .map_err(|e| CallError::Rpc(plugin_error(e.to_string(), plugin_name)))?;
+
+        write_frame(&mut self.stdin, &payload)
+            .await
+            .map_err(|e| CallError::Transport(format!("failed to send '{}' request: {}", method, e)))?;
+        let frame = read_frame(&mut self.stdout)
+            .await
+            .map_err(|e| CallError::Transport(format!("failed to read '{}' response: {}", method, e)))?;
+
+        let response: Response<Value> = config
+            .encoding
+            .decode(&frame)
+            .map_err(|e| CallError::Transport(format!("malformed '{}' response: {}", method, e)))?;
+        if response.id.is_some_and(|response_id| response_id != id) {
+            return Err(CallError::Transport(format!(
+                "response id {:?} does not match request id {}",
+                response.id, id
+            )));
+        }
+        response
+            .into_result(plugin_name)
+            .map(Option::unwrap_or_default)
+            .map_err(CallError::Rpc)
+    }
+}
+
+/// Pool of worker processes running one external plugin executable.
+pub(super) struct WorkerPool {
+    config: ExternalPluginConfig,
+    name: String,
+    idle: Mutex<Vec<Worker>>,
+    permits: Semaphore,
+    next_id: AtomicU64,
+    consecutive_crashes: AtomicU32,
+    disabled: AtomicBool,
+}
+
+impl WorkerPool {
+    /// Spawn the first worker and perform the handshake, returning the pool and the
+    /// handshake result.
+    pub(super) async fn start(config: ExternalPluginConfig) -> Result<(Self, Value)> {
+        let mut pool = Self {
+            name: config.command.display().to_string(),
+            idle: Mutex::new(Vec::new()),
+            permits: Semaphore::new(config.max_concurrency.max(1)),
+            next_id: AtomicU64::new(1),
+            consecutive_crashes: AtomicU32::new(0),
+            disabled: AtomicBool::new(false),
+            config,
+        };
+
+        let (worker, info) = pool.spawn().await?;
+        if let Some(name) = info.get("name").and_then(Value::as_str) {
+            pool.name = name.to_string();
+        }
+        pool.release(worker);
+        Ok((pool, info))
+    }
+
+    /// Send a request to an idle worker, spawning one if needed.
+    ///
+    /// A request that fails because its worker crashed is retried once on a fresh worker.
+    /// Timeouts are not retried.
+    pub(super) async fn call<P: Serialize>(&self, method: &str, params: &P) -> Result<Value> {
+        let _permit = self
+            .permits
+            .acquire()
+            .await
+            .map_err(|_| plugin_error("Plugin has been shut down", &self.name))?;
+
+        let timeout = Duration::from_secs(self.config.request_timeout_secs);
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            self.ensure_enabled()?;
+            let mut worker = match self.take_idle() {
+                Some(worker) => worker,
+                None => match self.spawn().await {
+                    Ok((worker, _)) => worker,
+                    Err(e) => {
+                        self.record_crash();
+                        return Err(e);
+                    }
+                },
+            };
+
+            let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+            let outcome =
+                tokio::time::timeout(timeout, worker.request(&self.config, &self.name, id, method, params)).await;
+
+            match outcome {
+                Ok(Ok(value)) => {
+                    self.consecutive_crashes.store(0, Ordering::Relaxed);
+                    self.release(worker);
+                    return Ok(value);
+                }
+                Ok(Err(CallError::Rpc(error))) => {
+                    self.consecutive_crashes.store(0, Ordering::Relaxed);
+                    self.release(worker);
+                    return Err(error);
+                }
+                Ok(Err(CallError::Transport(message))) => {
+                    tracing::warn!(plugin = %self.name, "External plugin worker failed: {}", message);
+                    self.record_crash();
+                    if attempts < 2 {
+                        continue;
+                    }
+                    return Err(plugin_error(message, &self.name));
+                }
+                Err(_) => {
+                    self.record_crash();
+                    return Err(plugin_error(
+                        format!("'{}' request timed out after {}s", method, timeout.as_secs()),
+                        &self.name,
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Kill all idle workers and refuse further requests. Busy workers are killed as soon
+    /// as their request completes.
+    pub(super) fn shutdown(&self) {
+        self.permits.close();
+        let workers = std::mem::take(&mut *self.idle.lock().unwrap_or_else(|e| e.into_inner()));
+        for mut worker in workers {
+            let _ = worker.child.start_kill();
+        }
+    }
+
+    fn ensure_enabled(&self) -> Result<()> {
+        if self.disabled.load(Ordering::Relaxed) {
+            return Err(plugin_error(
+                format!(
+                    "Plugin disabled after crashing more than {} times in a row",
+                    self.config.max_restarts
+                ),
+                &self.name,
+            ));
+        }
+        Ok(())
+    }
+
+    fn record_crash(&self) {
+        let crashes = self.consecutive_crashes.fetch_add(1, Ordering::Relaxed) + 1;
+        if crashes > self.config.max_restarts {
+            tracing::error!(plugin = %self.name, crashes, "Disabling external plugin");
+            self.disabled.store(true, Ordering::Relaxed);
+        }
+    }
+
+    fn take_idle(&self) -> Option<Worker> {
+        self.idle.lock().unwrap_or_else(|e| e.into_inner()).pop()
+    }
+
+    fn release(&self, worker: Worker) {
+        if !self.permits.is_closed() {
+            self.idle.lock().unwrap_or_else(|e| e.into_inner()).push(worker);
+        }
+    }
+
+    async fn spawn(&self) -> Result<(Worker, Value)> {
+        let config = &self.config;
+        let mut command = Command::new(&config.command);
+        command
+            .args(&config.args)
+            .envs(&config.env)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        if let Some(dir) = &config.working_dir {
+            command.current_dir(dir);
+        }
+
+        let mut child = command.spawn().map_err(|e| {
+            plugin_error(
+                format!("Failed to start '{}': {}", config.command.display(), e),
+                &self.name,
+            )
+        })?;
+        let (Some(stdin), Some(stdout), Some(stderr)) = (child.stdin.take(), child.stdout.take(), child.stderr.take())
+        else {
+            return Err(plugin_error("Plugin stdio was not captured", &self.name));
+        };
+
+        let plugin_name = self.name.clone();
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(stderr).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                tracing::debug!(plugin = %plugin_name, "{}", line);
+            }
+        });
+
+        let mut worker = Worker {
+            child,
+            stdin: BufWriter::new(stdin),
+            stdout: BufReader::new(stdout),
+        };
+
+        let params = HandshakeParams::current();
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let timeout = Duration::from_secs(config.startup_timeout_secs);
+        let info =
+            match tokio::time::timeout(timeout, worker.request(config, &self.name, id, "handshake", &params)).await {
+                Ok(Ok(info)) => info,
+                Ok(Err(CallError::Rpc(e))) => return Err(plugin_error(e.to_string(), &self.name)),
+                Ok(Err(CallError::Transport(message))) => {
+                    return Err(plugin_error(format!("Handshake failed: {}", message), &self.name));
+                }
+                Err(_) => {
+                    return Err(plugin_error(
+                        format!("Handshake timed out after {}s", timeout.as_secs()),
+                        &self.name,
+                    ));
+                }
+            };
+
+        Ok((worker, info))
+    }
+}
+
+impl Drop for WorkerPool {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+pub(super) fn plugin_error(message: impl Into<String>, plugin_name: &str) -> KreuzbergError {
+    KreuzbergError::Plugin {
+        message: message.into(),
+        plugin_name: plugin_name.to_string(),
+    }
+}
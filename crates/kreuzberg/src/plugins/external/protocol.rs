@@ -0,0 +1,239 @@
+//! Wire format of the external plugin protocol.
+//!
+//! Every message is a frame: a 4-byte big-endian payload length followed by the payload,
+//! a JSON-RPC 2.0 request or response encoded as JSON or MessagePack. Binary parameters
+//! are sent as base64 strings in JSON and as raw binary in MessagePack.
+
+use crate::utils::framing::Framing;
+use crate::{KreuzbergError, Result};
+use base64::Engine;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::Value;
+use tokio::io::{AsyncRead, AsyncWrite};
+
+/// Version of the protocol spoken by this build, sent in the handshake.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Frames exchanged with plugins; the largest frame accepted from a plugin is 512 MiB.
+const FRAMING: Framing = Framing {
+    max_len: 512 * 1024 * 1024,
+    peer: "plugin",
+};
+
+/// Error codes a plugin may return to select the kind of error raised on the host side.
+/// Any other code is reported as [`KreuzbergError::Other`].
+pub mod error_codes {
+    /// The input or result failed validation ([`KreuzbergError::Validation`](crate::KreuzbergError::Validation)).
+    pub const VALIDATION: i64 = 1;
+    /// The plugin cannot handle the MIME type ([`KreuzbergError::UnsupportedFormat`](crate::KreuzbergError::UnsupportedFormat)).
+    pub const UNSUPPORTED_FORMAT: i64 = 2;
+    /// The input could not be parsed ([`KreuzbergError::Parsing`](crate::KreuzbergError::Parsing)).
+    pub const PARSING: i64 = 3;
+    /// OCR failed ([`KreuzbergError::Ocr`](crate::KreuzbergError::Ocr)).
+    pub const OCR: i64 = 4;
+}
+
+/// Payload encoding of an external plugin.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExternalPluginEncoding {
+    /// UTF-8 JSON.
+    #[default]
+    Json,
+    /// MessagePack with named fields.
+    #[serde(rename = "msgpack")]
+    MessagePack,
+}
+
+impl ExternalPluginEncoding {
+    pub(super) fn encode<T: Serialize>(self, value: &T) -> Result<Vec<u8>> {
+        match self {
+            Self::Json => serde_json::to_vec(value)
+                .map_err(|e| KreuzbergError::serialization(format!("Failed to encode plugin message: {}", e))),
+            Self::MessagePack => rmp_serde::to_vec_named(value)
+                .map_err(|e| KreuzbergError::serialization(format!("Failed to encode plugin message: {}", e))),
+        }
+    }
+
+    pub(super) fn decode<T: DeserializeOwned>(self, bytes: &[u8]) -> Result<T> {
+        match self {
+            Self::Json => serde_json::from_slice(bytes)
+                .map_err(|e| KreuzbergError::serialization(format!("Invalid plugin message: {}", e))),
+            Self::MessagePack => rmp_serde::from_slice(bytes)
+                .map_err(|e| KreuzbergError::serialization(format!("Invalid plugin message: {}", e))),
+        }
+    }
+}
+
+/// Borrowed binary parameter: base64 in human-readable encodings, raw bytes otherwise.
+pub(super) struct Binary<'a>(pub(super) &'a [u8]);
+
+impl Serialize for Binary<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&base64::engine::general_purpose::STANDARD.encode(self.0))
+        } else {
+            serializer.serialize_bytes(self.0)
+        }
+    }
+}
+
+/// Params of the `handshake` request.
+#[derive(Serialize)]
+pub(super) struct HandshakeParams {
+    protocol_version: u32,
+    kreuzberg_version: &'static str,
+}
+
+impl HandshakeParams {
+    pub(super) fn current() -> Self {
+        Self {
+            protocol_version: PROTOCOL_VERSION,
+            kreuzberg_version: env!("CARGO_PKG_VERSION"),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub(super) struct Request<'a, P> {
+    pub(super) jsonrpc: &'static str,
+    pub(super) id: u64,
+    pub(super) method: &'a str,
+    pub(super) params: P,
+}
+
+#[derive(Deserialize)]
+pub(super) struct Response<T> {
+    #[serde(default)]
+    pub(super) id: Option<u64>,
+    #[serde(default = "Option::default")]
+    pub(super) result: Option<T>,
+    #[serde(default)]
+    pub(super) error: Option<RpcError>,
+}
+
+impl<T> Response<T> {
+    /// The result of the response, or the error the plugin returned.
+    pub(super) fn into_result(self, plugin_name: &str) -> Result<Option<T>> {
+        match (self.result, self.error) {
+            (_, Some(error)) => Err(error.into_error(plugin_name)),
+            (result, None) => Ok(result),
+        }
+    }
+}
+
+/// Error object of a JSON-RPC response.
+#[derive(Debug, Clone, Deserialize)]
+pub(super) struct RpcError {
+    pub(super) code: i64,
+    pub(super) message: String,
+    #[serde(default)]
+    pub(super) data: Option<Value>,
+}
+
+impl RpcError {
+    /// Convert to the matching error kind. Any `data` the plugin attached is appended to
+    /// the message.
+    pub(super) fn into_error(self, plugin_name: &str) -> KreuzbergError {
+        let message = match self.data.filter(|data| !data.is_null()) {
+            Some(Value::String(data)) => format!("{}: {} ({})", plugin_name, self.message, data),
+            Some(data) => format!("{}: {} ({})", plugin_name, self.message, data),
+            None => format!("{}: {}", plugin_name, self.message),
+        };
+        match self.code {
+            error_codes::VALIDATION => KreuzbergError::validation(message),
+            error_codes::UNSUPPORTED_FORMAT => KreuzbergError::UnsupportedFormat(message),
+            error_codes::PARSING => KreuzbergError::parsing(message),
+            error_codes::OCR => KreuzbergError::ocr(message),
+            _ => KreuzbergError::Other(message),
+        }
+    }
+}
+
+/// Write one frame.
+pub(super) async fn write_frame<W: AsyncWrite + Unpin>(writer: &mut W, payload: &[u8]) -> std::io::Result<()> {
+    FRAMING.write_async(writer, payload).await
+}
+
+/// Read one frame.
+pub(super) async fn read_frame<R: AsyncRead + Unpin>(reader: &mut R) -> std::io::Result<Vec<u8>> {
+    FRAMING.read_async(reader).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Params<'a> {
+        content: Binary<'a>,
+    }
+
+    #[tokio::test]
+    async fn test_frame_roundtrip() {
+        let mut buffer = Vec::new();
+        write_frame(&mut buffer, b"hello").await.unwrap();
+        write_frame(&mut buffer, b"").await.unwrap();
+        assert_eq!(&buffer[..4], &[0, 0, 0, 5]);
+
+        let mut reader = buffer.as_slice();
+        assert_eq!(read_frame(&mut reader).await.unwrap(), b"hello");
+        assert_eq!(read_frame(&mut reader).await.unwrap(), b"");
+        assert!(read_frame(&mut reader).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_oversized_frame_is_rejected() {
+        let header = (FRAMING.max_len as u32 + 1).to_be_bytes();
+        let mut reader = &header[..];
+        let err = read_frame(&mut reader).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_binary_encoding_per_format() {
+        let params = Params {
+            content: Binary(b"abc"),
+        };
+
+        let json: Value = serde_json::from_slice(&ExternalPluginEncoding::Json.encode(&params).unwrap()).unwrap();
+        assert_eq!(json["content"], "YWJj");
+
+        let msgpack = ExternalPluginEncoding::MessagePack.encode(&params).unwrap();
+        // fixmap(1), fixstr "content", bin8 of length 3
+        assert_eq!(&msgpack[msgpack.len() - 5..], &[0xc4, 3, b'a', b'b', b'c']);
+    }
+
+    #[test]
+    fn test_rpc_error_codes_map_to_error_kinds() {
+        let error = |code| RpcError {
+            code,
+            message: "bad".to_string(),
+            data: None,
+        };
+        assert!(matches!(
+            error(error_codes::VALIDATION).into_error("p"),
+            KreuzbergError::Validation { .. }
+        ));
+        assert!(matches!(
+            error(error_codes::UNSUPPORTED_FORMAT).into_error("p"),
+            KreuzbergError::UnsupportedFormat(_)
+        ));
+        assert!(matches!(
+            error(error_codes::PARSING).into_error("p"),
+            KreuzbergError::Parsing { .. }
+        ));
+        assert!(matches!(
+            error(error_codes::OCR).into_error("p"),
+            KreuzbergError::Ocr { .. }
+        ));
+        assert!(matches!(error(-32000).into_error("p"), KreuzbergError::Other(_)));
+
+        let with_data = RpcError {
+            data: Some(serde_json::json!({"page": 3})),
+            ..error(error_codes::PARSING)
+        };
+        assert!(with_data.into_error("p").to_string().contains(r#"bad ({"page":3})"#));
+    }
+}
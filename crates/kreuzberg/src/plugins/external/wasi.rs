@@ -0,0 +1,230 @@
+//! Sandboxed WebAssembly plugins.
+//!
+//! A WASI plugin is a WebAssembly module handling the same JSON-RPC messages as an external
+//! plugin process. Instead of framing them over stdio, the host writes each request into the
+//! module's memory and reads the response back:
+//!
+//! - `kreuzberg_alloc(len: i32) -> i32` returns a buffer of `len` bytes for the request.
+//! - `kreuzberg_call(ptr: i32, len: i32) -> i64` handles the request and returns the
+//!   response location, packed as `ptr << 32 | len`.
+//!
+//! Every request runs in a fresh instance with no preopened directories, environment,
+//! arguments or network access, bounded by [`WasiPluginConfig::max_memory_bytes`] and
+//! [`WasiPluginConfig::fuel`]. Anything the module writes to stderr is logged at debug level.
+//!
+//! Plugins are WASI preview 1 core modules with the ABI above, not components of the
+//! WebAssembly component model: there is no WIT interface, and `.wasm` components are
+//! rejected when loading. Core modules can be built with any toolchain that targets
+//! `wasm32-wasip1` and reuse the external plugin messages unchanged. Supporting components
+//! would need a WIT world mirroring those messages and wasmtime's component linker.
+
+use super::ExternalPluginEncoding;
+use super::process::plugin_error;
+use super::protocol::{HandshakeParams, Request, Response};
+use crate::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::sync::Semaphore;
+use wasmtime::{Config, Engine, Linker, Module, Store, StoreLimits, StoreLimitsBuilder, Trap};
+use wasmtime_wasi::WasiCtxBuilder;
+use wasmtime_wasi::pipe::MemoryOutputPipe;
+use wasmtime_wasi::preview1::{self, WasiP1Ctx};
+
+/// Largest amount of stderr output kept per request.
+const STDERR_CAPACITY: usize = 64 * 1024;
+
+/// How to load a WebAssembly plugin module.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WasiPluginConfig {
+    /// Path to the module, in binary (`.wasm`) or text (`.wat`) format.
+    pub module: PathBuf,
+    /// Payload encoding (default: JSON).
+    #[serde(default)]
+    pub encoding: ExternalPluginEncoding,
+    /// Maximum linear memory of the module in bytes (default: 256 MiB).
+    #[serde(default = "default_max_memory_bytes")]
+    pub max_memory_bytes: usize,
+    /// Fuel available to each request, roughly one unit per WebAssembly instruction
+    /// (default: 10 billion).
+    #[serde(default = "default_fuel")]
+    pub fuel: u64,
+    /// Maximum number of requests handled concurrently (default: 1).
+    #[serde(default = "default_max_concurrency")]
+    pub max_concurrency: usize,
+}
+
+fn default_max_memory_bytes() -> usize {
+    256 * 1024 * 1024
+}
+
+fn default_fuel() -> u64 {
+    10_000_000_000
+}
+
+fn default_max_concurrency() -> usize {
+    1
+}
+
+impl WasiPluginConfig {
+    /// Configuration loading `module` with default limits.
+    pub fn new(module: impl Into<PathBuf>) -> Self {
+        Self {
+            module: module.into(),
+            encoding: ExternalPluginEncoding::default(),
+            max_memory_bytes: default_max_memory_bytes(),
+            fuel: default_fuel(),
+            max_concurrency: default_max_concurrency(),
+        }
+    }
+}
+
+struct State {
+    wasi: WasiP1Ctx,
+    limits: StoreLimits,
+}
+
+struct Inner {
+    config: WasiPluginConfig,
+    name: String,
+    engine: Engine,
+    module: Module,
+    linker: Linker<State>,
+}
+
+/// A compiled plugin module, instantiated once per request.
+pub(super) struct WasiModule {
+    inner: Arc<Inner>,
+    permits: Semaphore,
+    next_id: AtomicU64,
+}
+
+impl WasiModule {
+    /// Compile the module and link the WASI imports.
+    pub(super) async fn load(config: WasiPluginConfig) -> Result<Self> {
+        let name = config.module.display().to_string();
+        let permits = Semaphore::new(config.max_concurrency.max(1));
+        let inner = tokio::task::spawn_blocking(move || -> Result<Inner> {
+            let mut engine_config = Config::new();
+            engine_config.consume_fuel(true);
+            let engine = Engine::new(&engine_config)
+                .map_err(|e| plugin_error(format!("Failed to create WebAssembly engine: {:#}", e), &name))?;
+            let module = Module::from_file(&engine, &config.module)
+                .map_err(|e| plugin_error(format!("Failed to load module: {:#}", e), &name))?;
+            let mut linker = Linker::new(&engine);
+            preview1::add_to_linker_sync(&mut linker, |state: &mut State| &mut state.wasi)
+                .map_err(|e| plugin_error(format!("Failed to link WASI: {:#}", e), &name))?;
+            Ok(Inner {
+                config,
+                name,
+                engine,
+                module,
+                linker,
+            })
+        })
+        .await
+        .map_err(|e| crate::KreuzbergError::Other(format!("WebAssembly plugin task failed: {}", e)))??;
+
+        Ok(Self {
+            inner: Arc::new(inner),
+            permits,
+            next_id: AtomicU64::new(1),
+        })
+    }
+
+    /// Send the `handshake` request.
+    pub(super) async fn handshake(&self) -> Result<Value> {
+        self.call("handshake", &HandshakeParams::current()).await
+    }
+
+    /// Run one request in a fresh instance of the module.
+    pub(super) async fn call<P: Serialize>(&self, method: &str, params: &P) -> Result<Value> {
+        let _permit = self
+            .permits
+            .acquire()
+            .await
+            .map_err(|_| plugin_error("Plugin has been shut down", &self.inner.name))?;
+
+        let encoding = self.inner.config.encoding;
+        let request = encoding.encode(&Request {
+            jsonrpc: "2.0",
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            method,
+            params,
+        })?;
+
+        let inner = Arc::clone(&self.inner);
+        let output = tokio::task::spawn_blocking(move || inner.run(&request))
+            .await
+            .map_err(|e| plugin_error(format!("WebAssembly plugin task failed: {}", e), &self.inner.name))??;
+
+        let response: Response<Value> = encoding
+            .decode(&output)
+            .map_err(|e| plugin_error(format!("Malformed '{}' response: {}", method, e), &self.inner.name))?;
+        Ok(response.into_result(&self.inner.name)?.unwrap_or_default())
+    }
+
+    /// Refuse further requests.
+    pub(super) fn shutdown(&self) {
+        self.permits.close();
+    }
+}
+
+impl Inner {
+    fn run(&self, request: &[u8]) -> Result<Vec<u8>> {
+        let stderr = MemoryOutputPipe::new(STDERR_CAPACITY);
+        let wasi = WasiCtxBuilder::new()
+            .stderr(stderr.clone())
+            .allow_tcp(false)
+            .allow_udp(false)
+            .allow_ip_name_lookup(false)
+            .build_p1();
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(self.config.max_memory_bytes)
+            .instances(1)
+            .build();
+        let mut store = Store::new(&self.engine, State { wasi, limits });
+        store.limiter(|state| &mut state.limits);
+
+        let result = store
+            .set_fuel(self.config.fuel)
+            .and_then(|()| self.invoke(&mut store, request));
+
+        for line in String::from_utf8_lossy(&stderr.contents()).lines() {
+            tracing::debug!(plugin = %self.name, "{}", line);
+        }
+
+        result.map_err(|e| {
+            let message = match e.downcast_ref::<Trap>() {
+                Some(Trap::OutOfFuel) => format!("Fuel limit of {} exhausted", self.config.fuel),
+                _ => format!("{:#}", e),
+            };
+            plugin_error(message, &self.name)
+        })
+    }
+
+    fn invoke(&self, store: &mut Store<State>, request: &[u8]) -> wasmtime::Result<Vec<u8>> {
+        let instance = self.linker.instantiate(&mut *store, &self.module)?;
+        if let Ok(initialize) = instance.get_typed_func::<(), ()>(&mut *store, "_initialize") {
+            initialize.call(&mut *store, ())?;
+        }
+
+        let memory = instance
+            .get_memory(&mut *store, "memory")
+            .ok_or_else(|| wasmtime::Error::msg("module does not export its memory"))?;
+        let alloc = instance.get_typed_func::<u32, u32>(&mut *store, "kreuzberg_alloc")?;
+        let call = instance.get_typed_func::<(u32, u32), u64>(&mut *store, "kreuzberg_call")?;
+
+        let len = u32::try_from(request.len()).map_err(|_| wasmtime::Error::msg("request too large"))?;
+        let ptr = alloc.call(&mut *store, len)?;
+        memory.write(&mut *store, ptr as usize, request)?;
+
+        let packed = call.call(&mut *store, (ptr, len))?;
+        let (ptr, len) = ((packed >> 32) as usize, (packed & 0xffff_ffff) as usize);
+        let mut response = vec![0u8; len];
+        memory.read(&*store, ptr, &mut response)?;
+        Ok(response)
+    }
+}
@@ -0,0 +1,130 @@
+//! Identity of registered plugin instances.
+//!
+//! Results cached on disk outlive the process that produced them, so they must be keyed by
+//! something that changes whenever the code behind a plugin may have changed. For plugins
+//! shipped with kreuzberg, `name@version` is enough: the kreuzberg version is part of every
+//! cache key. Plugins registered by the application can change their implementation without
+//! changing their name or version, so each registered instance gets an identity of its own
+//! that is never reused, in this process or any other.
+
+use super::Plugin;
+use once_cell::sync::Lazy;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+
+/// Modules of this crate whose plugins wrap code supplied at runtime.
+const RUNTIME_MODULES: &[&str] = &["kreuzberg::plugins::external::"];
+
+/// Random per-process prefix of instance identities.
+static PROCESS_NONCE: Lazy<String> = Lazy::new(|| {
+    let mut hasher = Sha256::new();
+    hasher.update(std::process::id().to_le_bytes());
+    if let Ok(elapsed) = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH) {
+        hasher.update(elapsed.as_nanos().to_le_bytes());
+    }
+    format!("{:x}", hasher.finalize())[..16].to_string()
+});
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Live instances by address, each with a check whether it is still alive and its id.
+type Instances = HashMap<usize, (Box<dyn Fn() -> bool + Send>, String)>;
+
+static INSTANCES: Lazy<Mutex<Instances>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Whether `plugin` is implemented by kreuzberg itself rather than by the application.
+pub(crate) fn is_builtin<T: Plugin + ?Sized>(plugin: &T) -> bool {
+    let type_name = plugin.type_name();
+    type_name.starts_with("kreuzberg::")
+        && !type_name.contains('<')
+        && !type_name.contains("::tests::")
+        && !RUNTIME_MODULES.iter().any(|module| type_name.starts_with(module))
+}
+
+/// Identity of `plugin` for cache keys: `name@version`, followed by a unique instance id
+/// for plugins that are not built in.
+pub(crate) fn identity<T: Plugin + ?Sized + 'static>(plugin: &Arc<T>) -> String {
+    let base = format!("{}@{}", plugin.name(), plugin.version());
+    if is_builtin(plugin.as_ref()) {
+        return base;
+    }
+    format!("{}#{}", base, instance_id(plugin))
+}
+
+/// An id that no other call returns, in this process or any other.
+pub(crate) fn unique_id() -> String {
+    format!("{}-{}", *PROCESS_NONCE, NEXT_ID.fetch_add(1, Ordering::Relaxed))
+}
+
+/// Id of the instance behind `plugin`, stable for as long as the instance lives.
+fn instance_id<T: Plugin + ?Sized + 'static>(plugin: &Arc<T>) -> String {
+    let address = Arc::as_ptr(plugin) as *const () as usize;
+    let mut instances = INSTANCES.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some((alive, id)) = instances.get(&address)
+        && alive()
+    {
+        return id.clone();
+    }
+
+    // Dropping the weak references of dead instances frees their allocation, after which
+    // the address may be handed to a new instance; that one is looked up as unknown.
+    instances.retain(|_, (alive, _)| alive());
+    let id = unique_id();
+    let weak: Weak<T> = Arc::downgrade(plugin);
+    instances.insert(address, (Box::new(move || weak.strong_count() > 0), id.clone()));
+    id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Result;
+
+    struct Local;
+
+    impl Plugin for Local {
+        fn name(&self) -> &str {
+            "local"
+        }
+
+        fn version(&self) -> String {
+            "1.0.0".to_string()
+        }
+
+        fn initialize(&self) -> Result<()> {
+            Ok(())
+        }
+
+        fn shutdown(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_runtime_plugins_get_one_identity_per_instance() {
+        let first = Arc::new(Local);
+        let second = Arc::new(Local);
+
+        assert!(!is_builtin(first.as_ref()));
+        assert_eq!(identity(&first), identity(&Arc::clone(&first)));
+        assert_ne!(identity(&first), identity(&second));
+        assert!(identity(&first).starts_with("local@1.0.0#"));
+
+        let old = identity(&first);
+        drop(first);
+        let replacement = Arc::new(Local);
+        assert_ne!(identity(&replacement), old);
+    }
+
+    #[test]
+    fn test_builtin_plugins_are_identified_by_name_and_version() {
+        let extractor = Arc::new(crate::extractors::PlainTextExtractor::new());
+        assert!(is_builtin(extractor.as_ref()));
+        assert_eq!(
+            identity(&extractor),
+            format!("{}@{}", extractor.name(), extractor.version())
+        );
+    }
+}
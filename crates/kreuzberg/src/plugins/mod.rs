@@ -17,6 +17,7 @@
 //! - **Rust** (native, highest performance)
 //! - **Python** (via PyO3 FFI bridge)
 //! - **Node.js** (future - via napi-rs FFI bridge)
+//! - **Any language** as a separate process speaking the [`external`] plugin protocol
 //!
 //! # Lifecycle Pattern
 //!
@@ -223,7 +224,10 @@
 //! }
 //! ```
 
+#[cfg(feature = "tokio-runtime")]
+pub mod external;
 mod extractor;
+pub(crate) mod identity;
 mod ocr;
 mod processor;
 pub mod registry;
@@ -142,6 +142,21 @@ impl DocumentExtractorRegistry {
         Err(KreuzbergError::UnsupportedFormat(mime_type.to_string()))
     }
 
+    /// Get the extractor registered under `name`.
+    pub fn get_by_name(&self, name: &str) -> Option<Arc<dyn DocumentExtractor>> {
+        let (mime_type, priority) = self.name_index.get(name)?.first()?;
+        let extractor = self.extractors.get(mime_type)?.get(priority)?;
+        (extractor.name() == name).then(|| Arc::clone(extractor))
+    }
+
+    /// Get all registered extractors.
+    pub fn get_all(&self) -> Vec<Arc<dyn DocumentExtractor>> {
+        self.name_index
+            .keys()
+            .filter_map(|name| self.get_by_name(name))
+            .collect()
+    }
+
     /// List all registered extractors.
     pub fn list(&self) -> Vec<String> {
         self.name_index.keys().cloned().collect()
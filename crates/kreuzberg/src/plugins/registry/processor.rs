@@ -103,6 +103,14 @@ impl PostProcessorRegistry {
         result
     }
 
+    /// Get all processors of every stage.
+    pub fn get_all(&self) -> Vec<Arc<dyn PostProcessor>> {
+        self.processors
+            .values()
+            .flat_map(|priority_map| priority_map.values().flatten().cloned())
+            .collect()
+    }
+
     /// List all registered processor names.
     pub fn list(&self) -> Vec<String> {
         self.name_index.keys().cloned().collect()
@@ -191,6 +191,15 @@ pub trait Plugin: Send + Sync {
     fn author(&self) -> &str {
         ""
     }
+
+    /// Type name of the implementing type.
+    ///
+    /// Used to tell plugins shipped with kreuzberg from plugins registered by the
+    /// application; do not override.
+    #[doc(hidden)]
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
 }
 
 #[cfg(test)]
@@ -191,9 +191,11 @@ pub struct ChunkMetadata {
     /// Byte offset where this chunk ends in the original text (UTF-8 valid boundary).
     pub byte_end: usize,
 
-    /// Number of tokens in this chunk (if available).
+    /// Number of tokens in this chunk.
     ///
-    /// This is calculated by the embedding model's tokenizer if embeddings are enabled.
+    /// Always set by the chunker: counted with the sizing tokenizer or the embedding
+    /// model's tokenizer when one is configured, otherwise estimated from the word lengths.
+    /// `None` only for chunks built outside the chunker.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub token_count: Option<usize>,
 
@@ -214,6 +216,18 @@ pub struct ChunkMetadata {
     /// Only populated when page tracking is enabled in extraction configuration.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_page: Option<usize>,
+
+    /// Heading texts from the outermost section down to the section this chunk belongs to.
+    ///
+    /// Only populated by the structure chunker; empty for content before the first heading.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heading_path: Option<Vec<String>>,
+
+    /// IDs of the `DocumentStructure` nodes whose content is in this chunk, in reading order.
+    ///
+    /// Only populated by the structure chunker.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node_ids: Option<Vec<String>>,
 }
 
 /// Extracted image from a document.
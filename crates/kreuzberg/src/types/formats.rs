@@ -41,6 +41,10 @@ pub struct ExcelSheet {
     /// None for empty sheets.
     #[serde(skip)]
     pub table_cells: Option<Vec<Vec<String>>>,
+    /// Plain-text rendering of charts and SmartArt diagrams drawn on the sheet.
+    /// Their markdown rendering is appended to `markdown`.
+    #[serde(skip)]
+    pub drawing_text: Option<String>,
 }
 
 /// XML extraction result.
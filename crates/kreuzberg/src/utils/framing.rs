@@ -0,0 +1,120 @@
+//! Length-prefixed frames spoken over child process pipes.
+//!
+//! A frame is a 4-byte big-endian payload length followed by the payload. Isolation workers
+//! use the blocking functions, external plugins the async ones.
+
+use std::io::{Read, Write};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// Frame limits of one protocol.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Framing {
+    /// Largest payload accepted from the peer.
+    pub max_len: usize,
+    /// Who is on the other end, used in error messages.
+    pub peer: &'static str,
+}
+
+impl Framing {
+    /// Write one frame.
+    pub(crate) fn write(&self, writer: &mut impl Write, payload: &[u8]) -> std::io::Result<()> {
+        writer.write_all(&self.header(payload)?)?;
+        writer.write_all(payload)?;
+        writer.flush()
+    }
+
+    /// Read one frame.
+    pub(crate) fn read(&self, reader: &mut impl Read) -> std::io::Result<Vec<u8>> {
+        let mut header = [0u8; 4];
+        reader.read_exact(&mut header)?;
+        let mut payload = vec![0u8; self.payload_len(header)?];
+        reader.read_exact(&mut payload)?;
+        Ok(payload)
+    }
+
+    /// Write one frame to an async writer.
+    pub(crate) async fn write_async<W: AsyncWrite + Unpin>(
+        &self,
+        writer: &mut W,
+        payload: &[u8],
+    ) -> std::io::Result<()> {
+        writer.write_all(&self.header(payload)?).await?;
+        writer.write_all(payload).await?;
+        writer.flush().await
+    }
+
+    /// Read one frame from an async reader.
+    pub(crate) async fn read_async<R: AsyncRead + Unpin>(&self, reader: &mut R) -> std::io::Result<Vec<u8>> {
+        let mut header = [0u8; 4];
+        reader.read_exact(&mut header).await?;
+        let mut payload = vec![0u8; self.payload_len(header)?];
+        reader.read_exact(&mut payload).await?;
+        Ok(payload)
+    }
+
+    fn header(&self, payload: &[u8]) -> std::io::Result<[u8; 4]> {
+        u32::try_from(payload.len()).map(u32::to_be_bytes).map_err(|_| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("{} message too large", self.peer),
+            )
+        })
+    }
+
+    fn payload_len(&self, header: [u8; 4]) -> std::io::Result<usize> {
+        let len = u32::from_be_bytes(header) as usize;
+        if len > self.max_len {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "{} message of {} bytes exceeds the {} byte limit",
+                    self.peer, len, self.max_len
+                ),
+            ));
+        }
+        Ok(len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAMING: Framing = Framing {
+        max_len: 16,
+        peer: "test",
+    };
+
+    #[test]
+    fn test_blocking_roundtrip() {
+        let mut buffer = Vec::new();
+        FRAMING.write(&mut buffer, b"hello").unwrap();
+        FRAMING.write(&mut buffer, b"").unwrap();
+        assert_eq!(&buffer[..4], &[0, 0, 0, 5]);
+
+        let mut reader = buffer.as_slice();
+        assert_eq!(FRAMING.read(&mut reader).unwrap(), b"hello");
+        assert_eq!(FRAMING.read(&mut reader).unwrap(), b"");
+        assert!(FRAMING.read(&mut reader).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_async_matches_blocking() {
+        let mut blocking = Vec::new();
+        FRAMING.write(&mut blocking, b"hello").unwrap();
+        let mut async_buffer = Vec::new();
+        FRAMING.write_async(&mut async_buffer, b"hello").await.unwrap();
+        assert_eq!(blocking, async_buffer);
+
+        let mut reader = blocking.as_slice();
+        assert_eq!(FRAMING.read_async(&mut reader).await.unwrap(), b"hello");
+    }
+
+    #[test]
+    fn test_oversized_frame_is_rejected() {
+        let header = (FRAMING.max_len as u32 + 1).to_be_bytes();
+        let err = FRAMING.read(&mut &header[..]).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("test message"));
+    }
+}
@@ -11,6 +11,8 @@ pub mod quality;
 #[cfg(feature = "quality")]
 pub mod string_utils;
 
+#[cfg(feature = "tokio-runtime")]
+pub(crate) mod framing;
 pub mod pool;
 pub mod pool_sizing;
 pub mod string_pool;
@@ -111,6 +111,9 @@ impl MimeStringPool {
             "text/x-org",
             "text/x-pod",
             "text/x-rst",
+            "text/x-asciidoc",
+            "text/x-textile",
+            "text/x-mediawiki",
             "application/zip",
             "application/x-zip-compressed",
             "application/x-tar",
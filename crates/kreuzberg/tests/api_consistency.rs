@@ -253,6 +253,10 @@ fn test_extraction_config_no_unknown_fields_in_default() {
         "output_format",
         "include_document_structure",
         "security_limits",
+        "timeout_secs",
+        "partial_results_on_cancel",
+        "best_effort",
+        "cache_backend",
     ];
 
     for key in obj.keys() {
@@ -50,3 +50,155 @@ Hello world\r\n\
 
     assert_eq!(content.trim_end_matches('\n'), "Hello world");
 }
+
+fn two_file_body(boundary: &str, extra_fields: &str) -> Vec<u8> {
+    format!(
+        "--{boundary}\r\n\
+Content-Disposition: form-data; name=\"files\"; filename=\"a.txt\"\r\n\
+Content-Type: text/plain\r\n\
+\r\n\
+First file\r\n\
+--{boundary}\r\n\
+Content-Disposition: form-data; name=\"files\"; filename=\"b.bin\"\r\n\
+Content-Type: application/x-unknown\r\n\
+\r\n\
+binary\r\n\
+{extra_fields}--{boundary}--\r\n"
+    )
+    .into_bytes()
+}
+
+#[tokio::test]
+async fn test_extract_streams_ndjson_per_file() {
+    let router = create_router_with_limits(ExtractionConfig::default(), ApiSizeLimits::from_mb(5, 5));
+
+    let boundary = "X-BOUNDARY";
+    let stream_field = format!(
+        "--{boundary}\r\n\
+Content-Disposition: form-data; name=\"stream\"\r\n\
+\r\n\
+ndjson\r\n"
+    );
+    let request = Request::builder()
+        .method("POST")
+        .uri("/extract")
+        .header("content-type", format!("multipart/form-data; boundary={boundary}"))
+        .body(Body::from(two_file_body(boundary, &stream_field)))
+        .expect("Failed to build request");
+
+    let response = router.oneshot(request).await.expect("Request failed");
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "application/x-ndjson");
+
+    let bytes = to_bytes(response.into_body(), 1_000_000)
+        .await
+        .expect("Failed to read body");
+    let mut items: Vec<Value> = std::str::from_utf8(&bytes)
+        .expect("NDJSON should be UTF-8")
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("Each line should be JSON"))
+        .collect();
+    items.sort_by_key(|item| item["index"].as_u64());
+
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0]["file_name"], "a.txt");
+    assert!(
+        items[0]["result"]["content"]
+            .as_str()
+            .expect("First file should succeed")
+            .contains("First file")
+    );
+    assert!(items[1]["result"].is_null());
+    assert!(
+        items[1]["error"]["message"].is_string(),
+        "Second file should report an error"
+    );
+}
+
+#[tokio::test]
+async fn test_extract_streams_sse_from_accept_header() {
+    let router = create_router_with_limits(ExtractionConfig::default(), ApiSizeLimits::from_mb(5, 5));
+
+    let boundary = "X-BOUNDARY";
+    let request = Request::builder()
+        .method("POST")
+        .uri("/extract")
+        .header("content-type", format!("multipart/form-data; boundary={boundary}"))
+        .header("accept", "text/event-stream")
+        .body(Body::from(two_file_body(boundary, "")))
+        .expect("Failed to build request");
+
+    let response = router.oneshot(request).await.expect("Request failed");
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "text/event-stream");
+
+    let bytes = to_bytes(response.into_body(), 1_000_000)
+        .await
+        .expect("Failed to read body");
+    let text = std::str::from_utf8(&bytes).expect("SSE should be UTF-8");
+
+    assert!(text.contains("event: result\n"));
+    assert!(text.contains("event: error\n"));
+    assert!(
+        text.trim_end()
+            .ends_with(r#"data: {"total":2,"succeeded":1,"failed":1}"#)
+    );
+}
+
+#[tokio::test]
+async fn test_extract_config_overrides_follow_server_policy() {
+    use kreuzberg::{api::create_router_with_limits_and_server_config, core::ServerConfig};
+
+    let mut server_config = ServerConfig::default();
+    server_config.config_overrides.allowed_fields = vec!["use_cache".to_string(), "chunking".to_string()];
+    server_config.config_overrides.denied_fields = vec!["force_ocr".to_string()];
+    let router = create_router_with_limits_and_server_config(
+        ExtractionConfig::default(),
+        ApiSizeLimits::from_mb(5, 5),
+        server_config,
+    );
+
+    let boundary = "X-BOUNDARY";
+    let send = |fields: String| {
+        let body = format!(
+            "--{boundary}\r\n\
+Content-Disposition: form-data; name=\"files\"; filename=\"test.txt\"\r\n\
+Content-Type: text/plain\r\n\
+\r\n\
+Hello world\r\n\
+{fields}--{boundary}--\r\n"
+        );
+        let request = Request::builder()
+            .method("POST")
+            .uri("/extract")
+            .header("content-type", format!("multipart/form-data; boundary={boundary}"))
+            .body(Body::from(body))
+            .expect("Failed to build request");
+        router.clone().oneshot(request)
+    };
+    let config_field =
+        |json: &str| format!("--{boundary}\r\nContent-Disposition: form-data; name=\"config\"\r\n\r\n{json}\r\n");
+
+    let response = send(config_field(
+        r#"{"use_cache": false, "chunking": {"max_chars": 6, "max_overlap": 0}}"#,
+    ))
+    .await
+    .expect("Request failed");
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = to_bytes(response.into_body(), 1_000_000).await.unwrap();
+    let value: Value = serde_json::from_slice(&bytes).unwrap();
+    assert!(value[0]["chunks"].as_array().is_some_and(|chunks| !chunks.is_empty()));
+
+    let response = send(config_field(r#"{"force_ocr": true}"#))
+        .await
+        .expect("Request failed");
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let bytes = to_bytes(response.into_body(), 1_000_000).await.unwrap();
+    let value: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(value["error_type"], "ConfigOverrideDeniedError");
+
+    let output_format =
+        format!("--{boundary}\r\nContent-Disposition: form-data; name=\"output_format\"\r\n\r\nhtml\r\n");
+    let response = send(output_format).await.expect("Request failed");
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
@@ -66,6 +66,61 @@ async fn test_info_endpoint() {
     assert!(info.rust_backend);
 }
 
+/// Test the metrics endpoint reports request and extraction series.
+#[tokio::test]
+async fn test_metrics_endpoint() {
+    let app = create_router(ExtractionConfig::default());
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/health")
+                .body(Body::empty())
+                .expect("Failed to create HTTP request body"),
+        )
+        .await
+        .expect("Failed to send HTTP request");
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Cache hits are not timed, so make sure this extraction actually runs.
+    let config = ExtractionConfig {
+        use_cache: false,
+        ..Default::default()
+    };
+    kreuzberg::extract_bytes(b"metrics", "text/plain", &config)
+        .await
+        .expect("Failed to extract text");
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/metrics")
+                .body(Body::empty())
+                .expect("Failed to create HTTP request body"),
+        )
+        .await
+        .expect("Failed to send HTTP request");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let content_type = response
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    assert!(content_type.starts_with("text/plain"));
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read HTTP response body");
+    let text = String::from_utf8(body.to_vec()).expect("Metrics are not valid UTF-8");
+
+    assert!(text.contains(r#"kreuzberg_http_requests_total{method="GET",route="/health",status="200"}"#));
+    assert!(text.contains(r#"kreuzberg_extraction_duration_seconds_count{mime_type="text/plain""#));
+    assert!(text.contains("kreuzberg_extractions_in_flight"));
+}
+
 /// Test extract endpoint with no files returns 400.
 #[tokio::test]
 async fn test_extract_no_files() {
@@ -1713,3 +1768,211 @@ async fn test_extract_multiple_large_files_exceeding_limit() {
         response.status()
     );
 }
+
+/// Test the asynchronous job lifecycle: submit, poll status, fetch result, delete.
+#[tokio::test]
+async fn test_job_lifecycle() {
+    let app = create_router(ExtractionConfig::default());
+
+    let boundary = "----boundary";
+    let body_content = format!(
+        "--{}\r\n\
+         Content-Disposition: form-data; name=\"files\"; filename=\"job.txt\"\r\n\
+         Content-Type: text/plain\r\n\
+         \r\n\
+         Queued extraction\r\n\
+         --{}--\r\n",
+        boundary, boundary
+    );
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/jobs")
+                .header("content-type", format!("multipart/form-data; boundary={}", boundary))
+                .body(Body::from(body_content))
+                .expect("Operation failed"),
+        )
+        .await
+        .expect("Operation failed");
+
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read HTTP response body");
+    let job: serde_json::Value = serde_json::from_slice(&body).expect("Failed to deserialize JSON response");
+    let id = job["id"].as_str().expect("Job id should be a string").to_string();
+    assert_eq!(job["progress"]["total_files"], 1);
+
+    let mut status = String::new();
+    for _ in 0..500 {
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/jobs/{}", id))
+                    .body(Body::empty())
+                    .expect("Operation failed"),
+            )
+            .await
+            .expect("Operation failed");
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("Failed to read HTTP response body");
+        let job: serde_json::Value = serde_json::from_slice(&body).expect("Failed to deserialize JSON response");
+        status = job["status"].as_str().unwrap_or_default().to_string();
+        if status != "queued" && status != "running" {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    assert_eq!(status, "completed");
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!("/jobs/{}/result", id))
+                .body(Body::empty())
+                .expect("Operation failed"),
+        )
+        .await
+        .expect("Operation failed");
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read HTTP response body");
+    let results: Vec<serde_json::Value> = serde_json::from_slice(&body).expect("Failed to deserialize JSON response");
+    assert_eq!(results.len(), 1);
+    assert!(
+        results[0]["content"]
+            .as_str()
+            .expect("Failed to extract string from JSON value")
+            .contains("Queued extraction")
+    );
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("DELETE")
+                .uri(format!("/jobs/{}", id))
+                .body(Body::empty())
+                .expect("Operation failed"),
+        )
+        .await
+        .expect("Operation failed");
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri(format!("/jobs/{}", id))
+                .body(Body::empty())
+                .expect("Operation failed"),
+        )
+        .await
+        .expect("Operation failed");
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read HTTP response body");
+    let error: serde_json::Value = serde_json::from_slice(&body).expect("Failed to deserialize JSON response");
+    assert_eq!(error["error_type"], "JobNotFoundError");
+}
+
+/// Test API key authentication, scopes and rate limiting.
+#[tokio::test]
+async fn test_api_key_auth_and_rate_limit() {
+    use kreuzberg::{
+        api::{ApiSizeLimits, create_router_with_limits_and_server_config},
+        core::{ApiKeyConfig, ApiScope, ServerConfig},
+    };
+
+    let mut admin = ApiKeyConfig::new("admin-key");
+    admin.scopes = vec![ApiScope::Admin];
+    let mut limited = ApiKeyConfig::new("limited-key");
+    limited.rate_limit_per_minute = Some(1);
+    let server_config = ServerConfig {
+        api_keys: vec![ApiKeyConfig::new("extract-key"), admin, limited],
+        ..Default::default()
+    };
+    let app = create_router_with_limits_and_server_config(
+        ExtractionConfig::default(),
+        ApiSizeLimits::default(),
+        server_config,
+    );
+
+    let request = |uri: &str, key: Option<&str>| {
+        let mut builder = Request::builder().uri(uri);
+        if let Some(key) = key {
+            builder = builder.header("authorization", format!("Bearer {}", key));
+        }
+        builder.body(Body::empty()).expect("Failed to create HTTP request body")
+    };
+
+    let response = app.clone().oneshot(request("/health", None)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app.clone().oneshot(request("/formats", None)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(response.headers().get("www-authenticate").unwrap(), "Bearer");
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(error["error_type"], "AuthenticationError");
+    assert_eq!(error["status_code"], 401);
+
+    let response = app
+        .clone()
+        .oneshot(request("/formats", Some("wrong-key")))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = app
+        .clone()
+        .oneshot(request("/formats", Some("extract-key")))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app
+        .clone()
+        .oneshot(request("/cache/stats", Some("extract-key")))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/metrics")
+                .header("x-api-key", "admin-key")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app
+        .clone()
+        .oneshot(request("/formats", Some("limited-key")))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = app
+        .clone()
+        .oneshot(request("/formats", Some("limited-key")))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert!(response.headers().contains_key("retry-after"));
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(error["error_type"], "RateLimitError");
+}
@@ -0,0 +1,99 @@
+//! Best-effort extraction: damaged units are skipped and reported instead of failing the
+//! whole document.
+
+#![cfg(all(feature = "office", feature = "archives"))]
+
+use kreuzberg::core::config::ExtractionConfig;
+use kreuzberg::core::extractor::extract_bytes;
+use kreuzberg::types::ExtractionResult;
+use std::io::{Cursor, Read, Write};
+use zip::write::{FileOptions, ZipWriter};
+
+mod helpers;
+
+const PPTX_MIME: &str = "application/vnd.openxmlformats-officedocument.presentationml.presentation";
+
+fn config(best_effort: bool) -> ExtractionConfig {
+    ExtractionConfig {
+        use_cache: false,
+        best_effort,
+        ..Default::default()
+    }
+}
+
+fn skipped(result: &ExtractionResult) -> Vec<&str> {
+    result
+        .processing_warnings
+        .iter()
+        .filter(|w| w.source == "extraction")
+        .map(|w| w.message.as_str())
+        .collect()
+}
+
+/// Copy a ZIP-based document, replacing the content of `name`.
+fn replace_entry(original: &[u8], name: &str, content: &[u8]) -> Vec<u8> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(original)).unwrap();
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i).unwrap();
+        let mut data = Vec::new();
+        file.read_to_end(&mut data).unwrap();
+        writer
+            .start_file::<_, ()>(file.name().to_string(), FileOptions::default())
+            .unwrap();
+        writer
+            .write_all(if file.name() == name { content } else { &data })
+            .unwrap();
+    }
+    writer.finish().unwrap().into_inner()
+}
+
+#[tokio::test]
+async fn test_pptx_damaged_slide_is_skipped() {
+    if helpers::skip_if_missing("pptx/simple.pptx") {
+        return;
+    }
+    let original = std::fs::read(helpers::get_test_file_path("pptx/simple.pptx")).unwrap();
+    let damaged = replace_entry(&original, "ppt/slides/slide1.xml", b"<p:sld><p:cSld");
+
+    assert!(extract_bytes(&damaged, PPTX_MIME, &config(false)).await.is_err());
+
+    let result = extract_bytes(&damaged, PPTX_MIME, &config(true)).await.unwrap();
+    let intact = extract_bytes(&original, PPTX_MIME, &config(true)).await.unwrap();
+    assert!(!result.content.trim().is_empty());
+    assert!(result.content.len() < intact.content.len());
+    assert!(skipped(&intact).is_empty());
+
+    let warnings = skipped(&result);
+    assert_eq!(warnings.len(), 1, "{warnings:?}");
+    assert!(warnings[0].starts_with("Skipped slide 1: "), "{}", warnings[0]);
+}
+
+#[tokio::test]
+async fn test_zip_unreadable_entry_is_reported() {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    let stored = FileOptions::<()>::default().compression_method(zip::CompressionMethod::Stored);
+    writer.start_file("good.txt", stored).unwrap();
+    writer.write_all(b"intact text").unwrap();
+    writer.start_file("docs/bad.txt", stored).unwrap();
+    writer.write_all(b"checksum bait").unwrap();
+    let mut bytes = writer.finish().unwrap().into_inner();
+
+    // Corrupt the stored data of the second entry so that its CRC check fails.
+    let offset = bytes.windows(13).position(|w| w == b"checksum bait").unwrap();
+    bytes[offset] = b'C';
+
+    let result = extract_bytes(&bytes, "application/zip", &config(false)).await.unwrap();
+    assert!(result.content.contains("intact text"));
+    assert!(skipped(&result).is_empty());
+
+    let result = extract_bytes(&bytes, "application/zip", &config(true)).await.unwrap();
+    assert!(result.content.contains("intact text"));
+    let warnings = skipped(&result);
+    assert_eq!(warnings.len(), 1, "{warnings:?}");
+    assert!(
+        warnings[0].starts_with("Skipped archive entry 'docs/bad.txt': "),
+        "{}",
+        warnings[0]
+    );
+}
@@ -0,0 +1,169 @@
+//! Per-document timeouts and cooperative cancellation.
+//!
+//! A test extractor produces one line per "page", sleeping between pages and optionally
+//! calling [`ExtractionConfig::checkpoint`] before each one.
+
+#![cfg(feature = "tokio-runtime")]
+
+use async_trait::async_trait;
+use kreuzberg::core::config::ExtractionConfig;
+use kreuzberg::plugins::{DocumentExtractor, Plugin};
+use kreuzberg::types::ExtractionResult;
+use kreuzberg::{CancellationReason, CancellationToken, Kreuzberg, KreuzbergError, Result};
+use std::borrow::Cow;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const PAGES: usize = 20;
+const PAGE_DELAY: Duration = Duration::from_millis(200);
+
+struct SlowExtractor {
+    cooperative: bool,
+}
+
+impl Plugin for SlowExtractor {
+    fn name(&self) -> &str {
+        "slow-extractor"
+    }
+    fn version(&self) -> String {
+        "1.0.0".to_string()
+    }
+    fn initialize(&self) -> Result<()> {
+        Ok(())
+    }
+    fn shutdown(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DocumentExtractor for SlowExtractor {
+    async fn extract_bytes(&self, _: &[u8], mime_type: &str, config: &ExtractionConfig) -> Result<ExtractionResult> {
+        let mut content = String::new();
+        for page in 1..=PAGES {
+            if self.cooperative && config.checkpoint(|| format!("page {}", page))? {
+                break;
+            }
+            tokio::time::sleep(PAGE_DELAY).await;
+            content.push_str(&format!("page {}\n", page));
+        }
+        Ok(ExtractionResult {
+            content,
+            mime_type: Cow::Owned(mime_type.to_string()),
+            ..Default::default()
+        })
+    }
+
+    fn supported_mime_types(&self) -> &[&str] {
+        &["text/plain"]
+    }
+
+    fn priority(&self) -> i32 {
+        1000
+    }
+}
+
+fn engine(cooperative: bool) -> Kreuzberg {
+    let engine = Kreuzberg::new(ExtractionConfig::default()).unwrap();
+    engine
+        .register_extractor(Arc::new(SlowExtractor { cooperative }))
+        .unwrap();
+    engine
+}
+
+fn config(timeout_secs: Option<u64>, partial_results_on_cancel: bool) -> ExtractionConfig {
+    ExtractionConfig {
+        use_cache: false,
+        timeout_secs,
+        partial_results_on_cancel,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_timeout_fails_even_without_checkpoints() {
+    let engine = engine(false);
+    let started = Instant::now();
+
+    let err = engine
+        .extract_bytes(b"doc", "text/plain", Some(&config(Some(1), false)))
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(err, KreuzbergError::Cancelled(CancellationReason::TimedOut)),
+        "{err:?}"
+    );
+    assert!(started.elapsed() < Duration::from_secs(3), "{:?}", started.elapsed());
+}
+
+#[tokio::test]
+async fn test_timeout_with_partial_results() {
+    let engine = engine(true);
+
+    let result = engine
+        .extract_bytes(b"doc", "text/plain", Some(&config(Some(1), true)))
+        .await
+        .unwrap();
+
+    assert!(result.content.starts_with("page 1\n"), "{}", result.content);
+    assert!(!result.content.contains(&format!("page {}\n", PAGES)));
+    let warning = result
+        .processing_warnings
+        .iter()
+        .find(|w| w.source == "cancellation")
+        .expect("cancellation warning");
+    assert!(warning.message.contains("timed out before page"), "{}", warning.message);
+}
+
+#[tokio::test]
+async fn test_explicit_cancellation() {
+    let engine = engine(true);
+    let token = CancellationToken::new();
+    let mut config = config(None, false);
+    config.cancellation = Some(token.clone());
+
+    let canceller = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        token.cancel();
+    });
+    let err = engine
+        .extract_bytes(b"doc", "text/plain", Some(&config))
+        .await
+        .unwrap_err();
+    canceller.await.unwrap();
+
+    assert!(
+        matches!(err, KreuzbergError::Cancelled(CancellationReason::Cancelled)),
+        "{err:?}"
+    );
+}
+
+#[tokio::test]
+async fn test_cancelled_batch_reports_every_document() {
+    let engine = engine(true);
+    let token = CancellationToken::new();
+    token.cancel();
+    let mut config = config(None, false);
+    config.cancellation = Some(token);
+
+    let contents = vec![
+        (b"one".to_vec(), "text/plain".to_string()),
+        (b"two".to_vec(), "text/plain".to_string()),
+    ];
+    let results = engine.batch_extract_bytes(contents, Some(&config)).await.unwrap();
+
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(|r| r.metadata.error.is_some()));
+}
+
+#[tokio::test]
+async fn test_untouched_token_does_not_interfere() {
+    let engine = engine(true);
+    let mut config = config(Some(60), false);
+    config.cancellation = Some(CancellationToken::new());
+
+    let result = engine.extract_bytes(b"doc", "text/plain", Some(&config)).await.unwrap();
+    assert_eq!(result.content.lines().count(), PAGES);
+    assert!(result.processing_warnings.is_empty());
+}
@@ -25,6 +25,7 @@ async fn test_chunking_enabled() {
             preset: None,
             trim: true,
             chunker_type: kreuzberg::chunking::ChunkerType::Text,
+            sizing: kreuzberg::chunking::ChunkSizing::Characters,
         }),
         ..Default::default()
     };
@@ -70,6 +71,7 @@ async fn test_chunking_with_overlap() {
             preset: None,
             trim: true,
             chunker_type: kreuzberg::chunking::ChunkerType::Text,
+            sizing: kreuzberg::chunking::ChunkSizing::Characters,
         }),
         ..Default::default()
     };
@@ -112,6 +114,7 @@ async fn test_chunking_custom_sizes() {
             preset: None,
             trim: true,
             chunker_type: kreuzberg::chunking::ChunkerType::Text,
+            sizing: kreuzberg::chunking::ChunkSizing::Characters,
         }),
         ..Default::default()
     };
@@ -138,6 +141,77 @@ async fn test_chunking_custom_sizes() {
     }
 }
 
+/// Test structure chunking - chunks follow sections and keep table rows whole.
+#[tokio::test]
+#[cfg(all(feature = "chunking", feature = "office"))]
+async fn test_chunking_structure() {
+    let config = ExtractionConfig {
+        chunking: Some(ChunkingConfig {
+            max_characters: 120,
+            overlap: 0,
+            embedding: None,
+            preset: None,
+            trim: true,
+            chunker_type: kreuzberg::chunking::ChunkerType::Structure,
+            sizing: kreuzberg::chunking::ChunkSizing::Characters,
+        }),
+        ..Default::default()
+    };
+
+    let rows: String = (1..=8).map(|i| format!("| M{} | {} Nm\n", i, i * 4)).collect();
+    let source = format!(
+        "= Manual\n\n== Installation\n\nUnpack the archive and run the installer.\n\n\
+         == Torque values\n\nTighten bolts in a star pattern.\n\n|===\n| Bolt | Torque\n\n{}|===\n",
+        rows
+    );
+
+    let result = extract_bytes(source.as_bytes(), "text/x-asciidoc", &config)
+        .await
+        .expect("Should extract successfully");
+
+    assert!(result.document.is_none(), "Structure was not requested");
+    let chunks = result.chunks.expect("Chunks should be present");
+    assert!(chunks.len() > 2, "Should have several chunks");
+
+    for chunk in &chunks {
+        assert!(!chunk.metadata.heading_path.as_ref().expect("Heading path").is_empty());
+        assert!(!chunk.metadata.node_ids.as_ref().expect("Node IDs").is_empty());
+        assert!(chunk.metadata.byte_start <= chunk.metadata.byte_end);
+        assert!(chunk.metadata.byte_end <= result.content.len());
+    }
+
+    let installation = chunks
+        .iter()
+        .find(|c| c.content.contains("Unpack the archive"))
+        .expect("Installation chunk");
+    assert!(installation.content.starts_with("Installation"));
+    assert_eq!(
+        installation.metadata.heading_path,
+        Some(vec!["Installation".to_string()])
+    );
+
+    let table_chunks: Vec<_> = chunks.iter().filter(|c| c.content.contains("| M")).collect();
+    assert!(table_chunks.len() > 1, "Table should be split");
+    for chunk in table_chunks {
+        assert!(
+            chunk.content.contains("| Bolt | Torque |"),
+            "Header repeated: {}",
+            chunk.content
+        );
+        assert!(
+            chunk
+                .content
+                .lines()
+                .filter(|l| l.starts_with("| M"))
+                .all(|l| l.ends_with("Nm |"))
+        );
+        assert_eq!(
+            chunk.metadata.heading_path.as_ref().unwrap().last().unwrap(),
+            "Torque values"
+        );
+    }
+}
+
 /// Test chunking disabled - no chunking when disabled.
 #[tokio::test]
 async fn test_chunking_disabled() {
@@ -524,6 +598,7 @@ async fn test_chunking_with_embeddings() {
             preset: None,
             trim: true,
             chunker_type: kreuzberg::chunking::ChunkerType::Text,
+            sizing: kreuzberg::chunking::ChunkSizing::Characters,
         }),
         ..Default::default()
     };
@@ -347,6 +347,7 @@ fn test_config_loading() {
 use_cache = false
 enable_quality_processing = true
 force_ocr = false
+cache_backend = "sharded"
 
 [ocr]
 backend = "tesseract"
@@ -364,6 +365,7 @@ max_overlap = 300
     assert!(!config.use_cache);
     assert!(config.enable_quality_processing);
     assert!(!config.force_ocr);
+    assert_eq!(config.cache_backend, Some(kreuzberg::cache::CacheBackendType::Sharded));
 
     let ocr_config = config.ocr.expect("Operation failed");
     assert_eq!(ocr_config.backend, "tesseract");
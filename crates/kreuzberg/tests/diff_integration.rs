@@ -0,0 +1,79 @@
+//! Integration tests for comparing two versions of a document.
+
+#![cfg(all(feature = "office", feature = "tokio-runtime"))]
+
+use kreuzberg::ExtractionConfig;
+use kreuzberg::diff::{ChangeKind, diff_files_sync};
+use std::fs;
+use tempfile::tempdir;
+
+const V1: &str = "= Service Agreement
+
+== 1. Payment
+
+Invoices are due within 30 days of receipt.
+
+Late payments accrue interest at the statutory rate.
+
+== 2. Termination
+
+Either party may terminate this agreement with 60 days notice.
+";
+
+const V2: &str = "= Service Agreement
+
+== 1. Scope
+
+The provider delivers the services described in the order form.
+
+== 2. Payment
+
+Invoices are due within 45 days of receipt.
+
+Late payments accrue interest at the statutory rate.
+
+== 3. Termination
+
+Either party may terminate this agreement with 60 days notice.
+";
+
+#[test]
+fn test_diff_files_aligns_sections_across_versions() {
+    let dir = tempdir().unwrap();
+    let old = dir.path().join("agreement_v1.adoc");
+    let new = dir.path().join("agreement_v2.adoc");
+    fs::write(&old, V1).unwrap();
+    fs::write(&new, V2).unwrap();
+
+    let config = ExtractionConfig {
+        use_cache: false,
+        ..Default::default()
+    };
+    let diff = diff_files_sync(&old, &new, &config).expect("diff should succeed");
+
+    let inserted = diff
+        .sections
+        .iter()
+        .find(|section| section.change == ChangeKind::Inserted)
+        .expect("the Scope section should be reported as inserted");
+    assert!(inserted.path.last().unwrap().contains("Scope"));
+
+    let payment = diff
+        .sections
+        .iter()
+        .find(|section| section.path.last().is_some_and(|heading| heading.contains("Payment")))
+        .expect("the Payment section should be reported");
+    assert_eq!(payment.change, ChangeKind::Modified);
+    assert_eq!(payment.changes.len(), 1, "only the due date paragraph changed");
+    assert_eq!(payment.changes[0].change, ChangeKind::Modified);
+
+    assert_eq!(diff.summary.sections_deleted, 0);
+    assert_eq!(diff.summary.blocks_deleted, 0);
+    assert!(
+        diff.to_unified("v1", "v2")
+            .contains("+Invoices are due within 45 days of receipt.")
+    );
+
+    let unchanged = diff_files_sync(&old, &old, &config).unwrap();
+    assert!(unchanged.is_empty());
+}
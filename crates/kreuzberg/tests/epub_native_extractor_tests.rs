@@ -8,7 +8,7 @@
 
 #![cfg(feature = "office")]
 
-use kreuzberg::core::config::ExtractionConfig;
+use kreuzberg::core::config::{ExtractionConfig, ImageExtractionConfig, PageConfig};
 use kreuzberg::extractors::EpubExtractor;
 use kreuzberg::plugins::DocumentExtractor;
 use std::path::PathBuf;
@@ -264,3 +264,141 @@ async fn test_native_epub_no_content_loss() {
 
     println!("✅ All EPUBs extracted successfully - no content loss!");
 }
+
+/// Test 7: Chapters as pages
+///
+/// Validates:
+/// - Each non-empty spine document becomes a page
+/// - Page boundaries index into the content
+/// - Chapter metadata lists title and href per chapter
+#[tokio::test]
+async fn test_native_epub_chapters_as_pages() {
+    let test_file = get_test_epub_path("epub2_cover.epub");
+    if !test_file.exists() {
+        println!("Skipping test: Test file not found at {:?}", test_file);
+        return;
+    }
+
+    let bytes = std::fs::read(&test_file).expect("Failed to read epub2_cover.epub");
+    let config = ExtractionConfig {
+        pages: Some(PageConfig {
+            extract_pages: true,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let result = EpubExtractor::new()
+        .extract_bytes(&bytes, "application/epub+zip", &config)
+        .await
+        .expect("Should extract epub2_cover.epub");
+
+    let pages = result.pages.as_ref().expect("Pages should be extracted");
+    assert!(!pages.is_empty());
+    assert_eq!(pages[0].page_number, 1);
+
+    let structure = result.metadata.pages.as_ref().expect("Page structure should be set");
+    assert_eq!(structure.total_count, pages.len());
+    let boundaries = structure.boundaries.as_ref().expect("Boundaries should be set");
+    for (boundary, page) in boundaries.iter().zip(pages) {
+        assert!(result.content[boundary.byte_start..boundary.byte_end].contains(page.content.as_str()));
+    }
+
+    let chapters = result
+        .metadata
+        .additional
+        .get("chapters")
+        .and_then(|c| c.as_array())
+        .expect("Chapter metadata should be present");
+    assert_eq!(chapters.len(), pages.len());
+    assert!(
+        chapters[0]["href"]
+            .as_str()
+            .is_some_and(|href| href.ends_with(".xhtml"))
+    );
+}
+
+/// Test 8: Table of contents as document structure
+///
+/// Validates:
+/// - EPUB3 nav entries become headings in the document structure
+#[tokio::test]
+async fn test_native_epub_toc_document_structure() {
+    let test_file = get_test_epub_path("wasteland.epub");
+    if !test_file.exists() {
+        println!("Skipping test: Test file not found at {:?}", test_file);
+        return;
+    }
+
+    let bytes = std::fs::read(&test_file).expect("Failed to read wasteland.epub");
+    let config = ExtractionConfig {
+        include_document_structure: true,
+        ..Default::default()
+    };
+
+    let result = EpubExtractor::new()
+        .extract_bytes(&bytes, "application/epub+zip", &config)
+        .await
+        .expect("Should extract wasteland.epub");
+
+    let document = result.document.as_ref().expect("Document structure should be built");
+    let headings: Vec<&str> = document
+        .nodes
+        .iter()
+        .filter_map(|node| match &node.content {
+            kreuzberg::types::NodeContent::Heading { text, .. } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert!(
+        headings.contains(&"I. THE BURIAL OF THE DEAD"),
+        "TOC headings missing: {:?}",
+        headings
+    );
+    assert!(document.validate().is_ok());
+}
+
+/// Test 9: Cover image extraction
+///
+/// Validates:
+/// - EPUB2 `<meta name="cover">` cover is returned when image extraction is enabled
+/// - No images are returned when image extraction is disabled
+#[tokio::test]
+async fn test_native_epub_cover_image_extraction() {
+    let test_file = get_test_epub_path("epub2_cover.epub");
+    if !test_file.exists() {
+        println!("Skipping test: Test file not found at {:?}", test_file);
+        return;
+    }
+
+    let bytes = std::fs::read(&test_file).expect("Failed to read epub2_cover.epub");
+    let extractor = EpubExtractor::new();
+    let config = ExtractionConfig {
+        images: Some(ImageExtractionConfig {
+            extract_images: true,
+            target_dpi: 300,
+            max_image_dimension: 4096,
+            auto_adjust_dpi: true,
+            min_dpi: 72,
+            max_dpi: 600,
+        }),
+        ..Default::default()
+    };
+
+    let result = extractor
+        .extract_bytes(&bytes, "application/epub+zip", &config)
+        .await
+        .expect("Should extract epub2_cover.epub");
+
+    let images = result.images.as_ref().expect("Images should be returned");
+    assert_eq!(images.len(), 1);
+    assert_eq!(images[0].format.as_ref(), "jpeg");
+    assert_eq!(images[0].description.as_deref(), Some("cover"));
+    assert!(!images[0].data.is_empty());
+
+    let without_images = extractor
+        .extract_bytes(&bytes, "application/epub+zip", &ExtractionConfig::default())
+        .await
+        .expect("Should extract epub2_cover.epub");
+    assert!(without_images.images.is_none());
+}
@@ -0,0 +1,360 @@
+//! Process-isolated extraction tests.
+//!
+//! The workers are a small Python script speaking the isolation protocol, so that hangs,
+//! crashes and memory blow-ups can be triggered on demand. Tests are skipped when `python3`
+//! is not available.
+
+#![cfg(feature = "tokio-runtime")]
+
+use async_trait::async_trait;
+use kreuzberg::core::config::ExtractionConfig;
+use kreuzberg::isolation::IsolationConfig;
+use kreuzberg::plugins::{DocumentExtractor, Plugin, PostProcessor, ProcessingStage};
+use kreuzberg::types::ExtractionResult;
+use kreuzberg::{IsolationFailure, Kreuzberg, KreuzbergError};
+use std::borrow::Cow;
+use std::process::Command;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+const WORKER_SCRIPT: &str = r#"
+import os, signal, struct, sys, time
+
+def pack(value):
+    if value is None:
+        return b"\xc0"
+    if isinstance(value, int):
+        return b"\xd3" + struct.pack(">q", value)
+    if isinstance(value, bytes):
+        return b"\xc6" + struct.pack(">I", len(value)) + value
+    if isinstance(value, str):
+        data = value.encode()
+        return b"\xdb" + struct.pack(">I", len(data)) + data
+    if isinstance(value, list):
+        return b"\xdd" + struct.pack(">I", len(value)) + b"".join(map(pack, value))
+    return b"\xdf" + struct.pack(">I", len(value)) + b"".join(pack(k) + pack(v) for k, v in value.items())
+
+def content(request):
+    start = request.index(b"content") + len(b"content")
+    assert request[start] == 0xc4
+    return request[start + 2 : start + 2 + request[start + 1]].decode()
+
+assert os.environ["KREUZBERG_ISOLATION_WORKER"] == "1"
+while True:
+    header = sys.stdin.buffer.read(4)
+    if len(header) < 4:
+        sys.exit(0)
+    request = sys.stdin.buffer.read(struct.unpack(">I", header)[0])
+    if b"Handshake" in request:
+        response = {"Ready": {"pid": os.getpid()}}
+    else:
+        text = content(request)
+        if text == "sleep":
+            time.sleep(60)
+        if text == "crash":
+            os.kill(os.getpid(), signal.SIGSEGV)
+        if text == "grow":
+            hog = b"x" * (512 * 1024 * 1024)
+            time.sleep(60)
+        if text == "broken":
+            response = {"Failed": {"kind": "Parsing", "message": "damaged input"}}
+        else:
+            result = {"content": "%s pid=%d" % (text.upper(), os.getpid()), "mime_type": "text/plain", "metadata": {}, "tables": []}
+            response = {"Extracted": pack({"result": result, "additional": {}})}
+    payload = pack(response)
+    sys.stdout.buffer.write(struct.pack(">I", len(payload)) + payload)
+    sys.stdout.buffer.flush()
+"#;
+
+fn python_available() -> bool {
+    Command::new("python3")
+        .arg("--version")
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+fn isolation_config(dir: &TempDir) -> IsolationConfig {
+    let script = dir.path().join("worker.py");
+    std::fs::write(&script, WORKER_SCRIPT).unwrap();
+    IsolationConfig {
+        worker_command: Some("python3".into()),
+        worker_args: vec![script.display().to_string()],
+        workers: 1,
+        timeout_secs: 2,
+        max_rss_bytes: Some(256 * 1024 * 1024),
+        ..Default::default()
+    }
+}
+
+fn isolated_engine(config: IsolationConfig) -> Kreuzberg {
+    let engine = Kreuzberg::new(ExtractionConfig::default()).unwrap();
+    engine.set_isolation(Some(config)).unwrap();
+    engine
+}
+
+fn uncached_config() -> ExtractionConfig {
+    ExtractionConfig {
+        use_cache: false,
+        ..Default::default()
+    }
+}
+
+async fn extract(engine: &Kreuzberg, text: &str) -> kreuzberg::Result<String> {
+    let result = engine
+        .extract_bytes(text.as_bytes(), "text/plain", Some(&uncached_config()))
+        .await?;
+    Ok(result.content)
+}
+
+fn worker_pid(content: &str) -> &str {
+    content.rsplit_once("pid=").unwrap().1
+}
+
+/// Plugin registered with the host engine, as an extractor or a post-processor.
+struct HostPlugin;
+
+impl Plugin for HostPlugin {
+    fn name(&self) -> &str {
+        "host-plugin"
+    }
+
+    fn version(&self) -> String {
+        "1.0.0".to_string()
+    }
+
+    fn initialize(&self) -> kreuzberg::Result<()> {
+        Ok(())
+    }
+
+    fn shutdown(&self) -> kreuzberg::Result<()> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PostProcessor for HostPlugin {
+    async fn process(&self, result: &mut ExtractionResult, _: &ExtractionConfig) -> kreuzberg::Result<()> {
+        result.content.push_str(" [host]");
+        Ok(())
+    }
+
+    fn processing_stage(&self) -> ProcessingStage {
+        ProcessingStage::Late
+    }
+}
+
+#[async_trait]
+impl DocumentExtractor for HostPlugin {
+    async fn extract_bytes(
+        &self,
+        _: &[u8],
+        mime_type: &str,
+        _: &ExtractionConfig,
+    ) -> kreuzberg::Result<ExtractionResult> {
+        Ok(ExtractionResult {
+            content: "from host extractor".to_string(),
+            mime_type: Cow::Owned(mime_type.to_string()),
+            ..Default::default()
+        })
+    }
+
+    fn supported_mime_types(&self) -> &[&str] {
+        &["text/plain"]
+    }
+
+    fn priority(&self) -> i32 {
+        1000
+    }
+}
+
+#[tokio::test]
+async fn test_isolated_extraction_runs_in_worker() {
+    if !python_available() {
+        return;
+    }
+    let dir = TempDir::new().unwrap();
+    let engine = isolated_engine(isolation_config(&dir));
+    assert!(engine.is_isolated());
+
+    let first = extract(&engine, "hello").await.unwrap();
+    assert!(first.starts_with("HELLO pid="), "{first}");
+    assert_ne!(worker_pid(&first), std::process::id().to_string());
+
+    let err = extract(&engine, "broken").await.unwrap_err();
+    assert!(
+        matches!(&err, KreuzbergError::Parsing { message, .. } if message == "damaged input"),
+        "{err:?}"
+    );
+
+    // Ordinary errors leave the worker in service.
+    let second = extract(&engine, "again").await.unwrap();
+    assert_eq!(worker_pid(&first), worker_pid(&second));
+
+    engine.set_isolation(None).unwrap();
+    assert!(!engine.is_isolated());
+    assert_eq!(extract(&engine, "hello").await.unwrap().trim(), "hello");
+}
+
+#[tokio::test]
+async fn test_host_post_processors_run_on_isolated_results() {
+    if !python_available() {
+        return;
+    }
+    let dir = TempDir::new().unwrap();
+    let engine = isolated_engine(isolation_config(&dir));
+    engine.register_post_processor(Arc::new(HostPlugin), 0).unwrap();
+
+    let content = extract(&engine, "hello").await.unwrap();
+    assert!(content.starts_with("HELLO pid="), "{content}");
+    assert!(content.ends_with(" [host]"), "{content}");
+}
+
+#[tokio::test]
+async fn test_isolation_refuses_extractors_registered_at_runtime() {
+    let dir = TempDir::new().unwrap();
+    let engine = Kreuzberg::new(ExtractionConfig::default()).unwrap();
+    engine.register_extractor(Arc::new(HostPlugin)).unwrap();
+    let err = engine.set_isolation(Some(isolation_config(&dir))).unwrap_err();
+    assert!(
+        matches!(&err, KreuzbergError::Validation { message, .. } if message.contains("host-plugin")),
+        "{err:?}"
+    );
+    assert!(!engine.is_isolated());
+
+    if !python_available() {
+        return;
+    }
+    engine.unregister_extractor("host-plugin").unwrap();
+    engine.set_isolation(Some(isolation_config(&dir))).unwrap();
+    engine.register_extractor(Arc::new(HostPlugin)).unwrap();
+    let err = extract(&engine, "hello").await.unwrap_err();
+    assert!(matches!(err, KreuzbergError::Validation { .. }), "{err:?}");
+}
+
+#[tokio::test]
+async fn test_isolated_extraction_timeout_recycles_worker() {
+    if !python_available() {
+        return;
+    }
+    let dir = TempDir::new().unwrap();
+    let engine = isolated_engine(isolation_config(&dir));
+    let before = extract(&engine, "a").await.unwrap();
+
+    let err = extract(&engine, "sleep").await.unwrap_err();
+    assert!(
+        matches!(
+            err,
+            KreuzbergError::Isolation {
+                kind: IsolationFailure::Timeout,
+                ..
+            }
+        ),
+        "{err:?}"
+    );
+
+    let after = extract(&engine, "b").await.unwrap();
+    assert_ne!(worker_pid(&before), worker_pid(&after));
+}
+
+#[tokio::test]
+async fn test_isolated_extraction_contains_crash() {
+    if !python_available() {
+        return;
+    }
+    let dir = TempDir::new().unwrap();
+    let engine = isolated_engine(isolation_config(&dir));
+
+    let err = extract(&engine, "crash").await.unwrap_err();
+    assert!(
+        matches!(
+            &err,
+            KreuzbergError::Isolation {
+                kind: IsolationFailure::Crash,
+                message,
+            } if message.contains("exited unexpectedly")
+        ),
+        "{err:?}"
+    );
+
+    assert!(
+        extract(&engine, "still alive")
+            .await
+            .unwrap()
+            .starts_with("STILL ALIVE")
+    );
+}
+
+#[cfg(target_os = "linux")]
+#[tokio::test]
+async fn test_isolated_extraction_memory_limit() {
+    if !python_available() {
+        return;
+    }
+    let dir = TempDir::new().unwrap();
+    let mut config = isolation_config(&dir);
+    config.timeout_secs = 30;
+    let engine = isolated_engine(config);
+
+    let err = extract(&engine, "grow").await.unwrap_err();
+    assert!(
+        matches!(
+            err,
+            KreuzbergError::Isolation {
+                kind: IsolationFailure::MemoryLimit,
+                ..
+            }
+        ),
+        "{err:?}"
+    );
+    assert!(extract(&engine, "ok").await.is_ok());
+}
+
+#[tokio::test]
+async fn test_isolated_worker_retired_after_max_documents() {
+    if !python_available() {
+        return;
+    }
+    let dir = TempDir::new().unwrap();
+    let mut config = isolation_config(&dir);
+    config.max_documents_per_worker = Some(1);
+    let engine = isolated_engine(config);
+
+    let first = extract(&engine, "one").await.unwrap();
+    let second = extract(&engine, "two").await.unwrap();
+    assert_ne!(worker_pid(&first), worker_pid(&second));
+}
+
+#[test]
+fn test_isolation_requires_a_worker() {
+    let engine = Kreuzberg::new(ExtractionConfig::default()).unwrap();
+    let config = IsolationConfig {
+        worker_command: Some("/nonexistent/kreuzberg-worker".into()),
+        workers: 1,
+        ..Default::default()
+    };
+    let err = engine.set_isolation(Some(config)).unwrap_err();
+    assert!(matches!(err, KreuzbergError::Isolation { .. }), "{err:?}");
+    assert!(!engine.is_isolated());
+
+    if !python_available() {
+        return;
+    }
+    let config = IsolationConfig {
+        worker_command: Some("python3".into()),
+        worker_args: vec!["-c".to_string(), "import time; time.sleep(60)".to_string()],
+        workers: 1,
+        startup_timeout_secs: 1,
+        ..Default::default()
+    };
+    let err = engine.set_isolation(Some(config)).unwrap_err();
+    assert!(
+        matches!(
+            &err,
+            KreuzbergError::Isolation {
+                kind: IsolationFailure::Timeout,
+                message,
+            } if message.contains("run_worker_if_requested")
+        ),
+        "{err:?}"
+    );
+}
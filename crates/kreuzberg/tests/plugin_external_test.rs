@@ -0,0 +1,267 @@
+//! External (out-of-process) plugin tests.
+//!
+//! Runs a small Python plugin speaking the stdio protocol. Tests are skipped when
+//! `python3` is not available.
+
+use kreuzberg::core::config::ExtractionConfig;
+use kreuzberg::plugins::external::{ExternalPlugin, ExternalPluginCapability, ExternalPluginConfig};
+use kreuzberg::{Kreuzberg, KreuzbergError};
+use std::process::Command;
+use tempfile::TempDir;
+
+const PLUGIN_SCRIPT: &str = r#"
+import base64, json, os, struct, sys, time
+
+capabilities = sys.argv[1].split(",")
+
+def read_frame():
+    header = sys.stdin.buffer.read(4)
+    if len(header) < 4:
+        sys.exit(0)
+    (length,) = struct.unpack(">I", header)
+    return json.loads(sys.stdin.buffer.read(length))
+
+def write_frame(message):
+    payload = json.dumps(message).encode()
+    sys.stdout.buffer.write(struct.pack(">I", len(payload)) + payload)
+    sys.stdout.buffer.flush()
+
+while True:
+    request = read_frame()
+    method, params = request["method"], request["params"]
+    response = {"jsonrpc": "2.0", "id": request["id"]}
+    if method == "handshake":
+        response["result"] = {
+            "name": "py-plugin",
+            "version": "0.1.0",
+            "capabilities": capabilities,
+            "mime_types": ["text/plain"],
+            "priority": 1000,
+            "processing_stage": "late",
+        }
+    elif method == "extract":
+        text = base64.b64decode(params["content"]).decode()
+        if text == "crash":
+            os._exit(1)
+        if text == "sleep":
+            time.sleep(30)
+        if text == "unsupported":
+            response["error"] = {"code": 2, "message": "cannot handle this"}
+        else:
+            response["result"] = {"content": text.upper(), "metadata": {"pid": os.getpid()}}
+    elif method == "process":
+        response["result"] = {"content": params["result"]["content"] + " [processed]"}
+    elif method == "validate":
+        if "REJECT" in params["result"]["content"]:
+            response["error"] = {"code": 1, "message": "rejected"}
+        else:
+            response["result"] = None
+    print("handled " + method, file=sys.stderr)
+    write_frame(response)
+"#;
+
+fn python_available() -> bool {
+    Command::new("python3")
+        .arg("--version")
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+fn plugin_config(dir: &TempDir, capabilities: &str) -> ExternalPluginConfig {
+    let script = dir.path().join("plugin.py");
+    std::fs::write(&script, PLUGIN_SCRIPT).unwrap();
+    let mut config = ExternalPluginConfig::new("python3");
+    config.args = vec![script.display().to_string(), capabilities.to_string()];
+    config
+}
+
+fn uncached_config() -> ExtractionConfig {
+    ExtractionConfig {
+        use_cache: false,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_external_extractor_handshake_and_extract() {
+    if !python_available() {
+        return;
+    }
+    let dir = TempDir::new().unwrap();
+    let engine = Kreuzberg::new(ExtractionConfig::default()).unwrap();
+    let plugin = engine
+        .register_external_plugin(plugin_config(&dir, "extractor"))
+        .await
+        .unwrap();
+
+    assert_eq!(plugin.info().name, "py-plugin");
+    assert_eq!(plugin.info().version, "0.1.0");
+    assert!(plugin.has_capability(ExternalPluginCapability::Extractor));
+    assert!(engine.list_extractors().unwrap().contains(&"py-plugin".to_string()));
+
+    let result = engine
+        .extract_bytes(b"hello", "text/plain", Some(&uncached_config()))
+        .await
+        .unwrap();
+    assert_eq!(result.content, "HELLO");
+    assert_eq!(result.mime_type, "text/plain");
+
+    let err = engine
+        .extract_bytes(b"unsupported", "text/plain", Some(&uncached_config()))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, KreuzbergError::UnsupportedFormat(_)), "{err:?}");
+}
+
+#[tokio::test]
+async fn test_external_post_processor_and_validator() {
+    if !python_available() {
+        return;
+    }
+    let dir = TempDir::new().unwrap();
+    let engine = Kreuzberg::new(ExtractionConfig::default()).unwrap();
+    engine
+        .register_external_plugin(plugin_config(&dir, "post_processor,validator"))
+        .await
+        .unwrap();
+
+    let result = engine
+        .extract_bytes(b"plain text", "text/plain", Some(&uncached_config()))
+        .await
+        .unwrap();
+    assert_eq!(result.content.trim_end(), "plain text [processed]");
+    assert_eq!(result.mime_type, "text/plain");
+
+    let err = engine
+        .extract_bytes(b"REJECT me", "text/plain", Some(&uncached_config()))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, KreuzbergError::Validation { .. }), "{err:?}");
+}
+
+#[tokio::test]
+async fn test_external_plugin_restarts_after_crash() {
+    if !python_available() {
+        return;
+    }
+    let dir = TempDir::new().unwrap();
+    let engine = Kreuzberg::new(ExtractionConfig::default()).unwrap();
+    engine
+        .register_external_plugin(plugin_config(&dir, "extractor"))
+        .await
+        .unwrap();
+    let config = uncached_config();
+
+    let first = engine.extract_bytes(b"a", "text/plain", Some(&config)).await.unwrap();
+    let err = engine
+        .extract_bytes(b"crash", "text/plain", Some(&config))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, KreuzbergError::Plugin { .. }), "{err:?}");
+
+    let after = engine.extract_bytes(b"b", "text/plain", Some(&config)).await.unwrap();
+    assert_eq!(after.content, "B");
+    assert_ne!(
+        first.metadata.additional.get("pid"),
+        after.metadata.additional.get("pid")
+    );
+}
+
+#[tokio::test]
+async fn test_external_plugin_disabled_after_max_restarts() {
+    if !python_available() {
+        return;
+    }
+    let dir = TempDir::new().unwrap();
+    let engine = Kreuzberg::new(ExtractionConfig::default()).unwrap();
+    let mut plugin_config = plugin_config(&dir, "extractor");
+    plugin_config.max_restarts = 1;
+    engine.register_external_plugin(plugin_config).await.unwrap();
+    let config = uncached_config();
+
+    // The crashing request is retried once, using up the allowed restarts.
+    assert!(
+        engine
+            .extract_bytes(b"crash", "text/plain", Some(&config))
+            .await
+            .is_err()
+    );
+    let err = engine
+        .extract_bytes(b"fine", "text/plain", Some(&config))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&err, KreuzbergError::Plugin { message, .. } if message.contains("disabled")),
+        "{err:?}"
+    );
+}
+
+#[tokio::test]
+async fn test_external_plugin_request_timeout() {
+    if !python_available() {
+        return;
+    }
+    let dir = TempDir::new().unwrap();
+    let engine = Kreuzberg::new(ExtractionConfig::default()).unwrap();
+    let mut plugin_config = plugin_config(&dir, "extractor");
+    plugin_config.request_timeout_secs = 1;
+    engine.register_external_plugin(plugin_config).await.unwrap();
+    let config = uncached_config();
+
+    let err = engine
+        .extract_bytes(b"sleep", "text/plain", Some(&config))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&err, KreuzbergError::Plugin { message, .. } if message.contains("timed out")),
+        "{err:?}"
+    );
+
+    let result = engine
+        .extract_bytes(b"awake", "text/plain", Some(&config))
+        .await
+        .unwrap();
+    assert_eq!(result.content, "AWAKE");
+}
+
+#[tokio::test]
+async fn test_external_plugin_concurrency_limit() {
+    if !python_available() {
+        return;
+    }
+    let dir = TempDir::new().unwrap();
+    let engine = Kreuzberg::new(ExtractionConfig::default()).unwrap();
+    let mut plugin_config = plugin_config(&dir, "extractor");
+    plugin_config.max_concurrency = 2;
+    engine.register_external_plugin(plugin_config).await.unwrap();
+
+    let contents = (0..8)
+        .map(|i| (format!("doc {i}").into_bytes(), "text/plain".to_string()))
+        .collect();
+    let results = engine
+        .batch_extract_bytes(contents, Some(&uncached_config()))
+        .await
+        .unwrap();
+
+    let pids: std::collections::HashSet<_> = results
+        .iter()
+        .map(|r| r.metadata.additional.get("pid").cloned())
+        .collect();
+    assert!(pids.len() <= 2, "{pids:?}");
+    assert!(results.iter().enumerate().all(|(i, r)| r.content == format!("DOC {i}")));
+}
+
+#[tokio::test]
+async fn test_external_plugin_bad_handshake() {
+    let err = ExternalPlugin::start(ExternalPluginConfig::new("/nonexistent/kreuzberg-plugin"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, KreuzbergError::Plugin { .. }), "{err:?}");
+
+    if !python_available() {
+        return;
+    }
+    let dir = TempDir::new().unwrap();
+    let err = ExternalPlugin::start(plugin_config(&dir, "")).await.unwrap_err();
+    assert!(matches!(err, KreuzbergError::Plugin { .. }), "{err:?}");
+}
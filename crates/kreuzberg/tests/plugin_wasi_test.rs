@@ -0,0 +1,179 @@
+//! Sandboxed WebAssembly plugin tests.
+//!
+//! The test plugin is a hand-written WAT module that answers requests by searching them for
+//! marker strings: `handshake`, and the base64 encodings of `spin` and `grow`, which loop
+//! forever and allocate memory forever respectively.
+
+#![cfg(feature = "wasi-plugins")]
+
+use kreuzberg::core::config::ExtractionConfig;
+use kreuzberg::plugins::external::{ExternalPlugin, WasiPluginConfig};
+use kreuzberg::{Kreuzberg, KreuzbergError};
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+const HANDSHAKE: &str = r#"{"result":{"name":"wasm-plugin","version":"1.0.0","capabilities":["extractor"],"mime_types":["text/plain"],"priority":1000}}"#;
+const EXTRACTED: &str = r#"{"result":{"content":"from wasm"}}"#;
+
+fn plugin_wat(exports_call: bool) -> String {
+    let call = if exports_call {
+        format!(
+            r#"
+  (func (export "kreuzberg_call") (param $ptr i32) (param $len i32) (result i64)
+    (if (call $contains (local.get $ptr) (local.get $len) (i32.const 0) (i32.const 9))
+      (then (return (call $respond (i32.const 64) (i32.const {handshake_len})))))
+    (if (call $contains (local.get $ptr) (local.get $len) (i32.const 16) (i32.const 6))
+      (then (loop $spin (br $spin))))
+    (if (call $contains (local.get $ptr) (local.get $len) (i32.const 32) (i32.const 6))
+      (then
+        (loop $grow (br_if $grow (i32.ne (memory.grow (i32.const 16)) (i32.const -1))))
+        (unreachable)))
+    (drop (call $fd_write (i32.const 2) (i32.const 1100) (i32.const 1) (i32.const 1200)))
+    (call $respond (i32.const 512) (i32.const {extracted_len})))"#,
+            handshake_len = HANDSHAKE.len(),
+            extracted_len = EXTRACTED.len(),
+        )
+    } else {
+        String::new()
+    };
+
+    format!(
+        r#"(module
+  (import "wasi_snapshot_preview1" "fd_write" (func $fd_write (param i32 i32 i32 i32) (result i32)))
+  (memory (export "memory") 1)
+  (global $heap (mut i32) (i32.const 4096))
+  (data (i32.const 0) "handshake")
+  (data (i32.const 16) "c3Bpbg")
+  (data (i32.const 32) "Z3Jvdw")
+  (data (i32.const 64) "{handshake}")
+  (data (i32.const 512) "{extracted}")
+  (data (i32.const 1024) "wasm stderr\n")
+  (data (i32.const 1100) "\00\04\00\00\0c\00\00\00")
+
+  (func (export "kreuzberg_alloc") (param $len i32) (result i32)
+    (local $ptr i32)
+    (local.set $ptr (global.get $heap))
+    (global.set $heap (i32.add (local.get $ptr) (local.get $len)))
+    (if (i32.gt_u (global.get $heap) (i32.mul (memory.size) (i32.const 65536)))
+      (then (drop (memory.grow (i32.add (i32.shr_u (local.get $len) (i32.const 16)) (i32.const 1))))))
+    (local.get $ptr))
+
+  (func $contains (param $hay i32) (param $hay_len i32) (param $needle i32) (param $needle_len i32) (result i32)
+    (local $i i32) (local $j i32)
+    (block $not_found
+      (loop $outer
+        (br_if $not_found (i32.gt_u (i32.add (local.get $i) (local.get $needle_len)) (local.get $hay_len)))
+        (local.set $j (i32.const 0))
+        (block $mismatch
+          (loop $inner
+            (if (i32.eq (local.get $j) (local.get $needle_len)) (then (return (i32.const 1))))
+            (br_if $mismatch
+              (i32.ne
+                (i32.load8_u (i32.add (local.get $hay) (i32.add (local.get $i) (local.get $j))))
+                (i32.load8_u (i32.add (local.get $needle) (local.get $j)))))
+            (local.set $j (i32.add (local.get $j) (i32.const 1)))
+            (br $inner)))
+        (local.set $i (i32.add (local.get $i) (i32.const 1)))
+        (br $outer)))
+    (i32.const 0))
+
+  (func $respond (param $ptr i32) (param $len i32) (result i64)
+    (i64.or
+      (i64.shl (i64.extend_i32_u (local.get $ptr)) (i64.const 32))
+      (i64.extend_i32_u (local.get $len))))
+{call}
+)"#,
+        handshake = HANDSHAKE.replace('"', "\\\""),
+        extracted = EXTRACTED.replace('"', "\\\""),
+    )
+}
+
+fn write_module(dir: &TempDir, exports_call: bool) -> PathBuf {
+    let path = dir.path().join("plugin.wat");
+    std::fs::write(&path, plugin_wat(exports_call)).unwrap();
+    path
+}
+
+fn uncached_config() -> ExtractionConfig {
+    ExtractionConfig {
+        use_cache: false,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_wasi_extractor_handshake_and_extract() {
+    let dir = TempDir::new().unwrap();
+    let engine = Kreuzberg::new(ExtractionConfig::default()).unwrap();
+    let plugin = engine
+        .register_wasi_plugin(WasiPluginConfig::new(write_module(&dir, true)))
+        .await
+        .unwrap();
+
+    assert_eq!(plugin.info().name, "wasm-plugin");
+    assert!(engine.list_extractors().unwrap().contains(&"wasm-plugin".to_string()));
+
+    let result = engine
+        .extract_bytes(b"hello", "text/plain", Some(&uncached_config()))
+        .await
+        .unwrap();
+    assert_eq!(result.content, "from wasm");
+    assert_eq!(result.mime_type, "text/plain");
+}
+
+#[tokio::test]
+async fn test_wasi_plugin_fuel_limit() {
+    let dir = TempDir::new().unwrap();
+    let engine = Kreuzberg::new(ExtractionConfig::default()).unwrap();
+    let mut config = WasiPluginConfig::new(write_module(&dir, true));
+    config.fuel = 1_000_000;
+    engine.register_wasi_plugin(config).await.unwrap();
+
+    let err = engine
+        .extract_bytes(b"spin", "text/plain", Some(&uncached_config()))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&err, KreuzbergError::Plugin { message, .. } if message.contains("Fuel limit")),
+        "{err:?}"
+    );
+
+    // Each request gets a fresh instance and a full fuel budget.
+    let result = engine
+        .extract_bytes(b"hello", "text/plain", Some(&uncached_config()))
+        .await
+        .unwrap();
+    assert_eq!(result.content, "from wasm");
+}
+
+#[tokio::test]
+async fn test_wasi_plugin_memory_limit() {
+    let dir = TempDir::new().unwrap();
+    let engine = Kreuzberg::new(ExtractionConfig::default()).unwrap();
+    let mut config = WasiPluginConfig::new(write_module(&dir, true));
+    config.max_memory_bytes = 4 * 1024 * 1024;
+    engine.register_wasi_plugin(config).await.unwrap();
+
+    let err = engine
+        .extract_bytes(b"grow", "text/plain", Some(&uncached_config()))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, KreuzbergError::Plugin { .. }), "{err:?}");
+}
+
+#[tokio::test]
+async fn test_wasi_plugin_requires_plugin_exports() {
+    let dir = TempDir::new().unwrap();
+    let err = ExternalPlugin::load_wasi(WasiPluginConfig::new(write_module(&dir, false)))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&err, KreuzbergError::Plugin { message, .. } if message.contains("kreuzberg_call")),
+        "{err:?}"
+    );
+
+    let err = ExternalPlugin::load_wasi(WasiPluginConfig::new(dir.path().join("missing.wasm")))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, KreuzbergError::Plugin { .. }), "{err:?}");
+}
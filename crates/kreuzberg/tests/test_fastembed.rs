@@ -151,6 +151,8 @@ async fn test_generate_embeddings_for_chunks_basic() {
                 token_count: None,
                 first_page: None,
                 last_page: None,
+                heading_path: None,
+                node_ids: None,
             },
         },
         Chunk {
@@ -164,6 +166,8 @@ async fn test_generate_embeddings_for_chunks_basic() {
                 token_count: None,
                 first_page: None,
                 last_page: None,
+                heading_path: None,
+                node_ids: None,
             },
         },
         Chunk {
@@ -177,6 +181,8 @@ async fn test_generate_embeddings_for_chunks_basic() {
                 token_count: None,
                 first_page: None,
                 last_page: None,
+                heading_path: None,
+                node_ids: None,
             },
         },
     ];
@@ -227,6 +233,8 @@ async fn test_generate_embeddings_for_chunks_normalization() {
             token_count: None,
             first_page: None,
             last_page: None,
+            heading_path: None,
+            node_ids: None,
         },
     }];
 
@@ -254,6 +262,8 @@ async fn test_generate_embeddings_for_chunks_normalization() {
             token_count: None,
             first_page: None,
             last_page: None,
+            heading_path: None,
+            node_ids: None,
         },
     }];
 
@@ -336,6 +346,8 @@ async fn test_generate_embeddings_for_chunks_model_caching() {
             token_count: None,
             first_page: None,
             last_page: None,
+            heading_path: None,
+            node_ids: None,
         },
     }];
 
@@ -364,6 +376,8 @@ async fn test_generate_embeddings_for_chunks_model_caching() {
             token_count: None,
             first_page: None,
             last_page: None,
+            heading_path: None,
+            node_ids: None,
         },
     }];
 
@@ -398,6 +412,8 @@ async fn test_generate_embeddings_for_chunks_invalid_preset() {
             token_count: None,
             first_page: None,
             last_page: None,
+            heading_path: None,
+            node_ids: None,
         },
     }];
 
@@ -443,6 +459,8 @@ async fn test_generate_embeddings_for_chunks_unknown_model() {
             token_count: None,
             first_page: None,
             last_page: None,
+            heading_path: None,
+            node_ids: None,
         },
     }];
 
@@ -489,6 +507,8 @@ async fn test_generate_embeddings_for_chunks_custom_model_not_supported() {
             token_count: None,
             first_page: None,
             last_page: None,
+            heading_path: None,
+            node_ids: None,
         },
     }];
 
@@ -536,6 +556,8 @@ async fn test_generate_embeddings_for_chunks_batch_size() {
                 token_count: None,
                 first_page: None,
                 last_page: None,
+                heading_path: None,
+                node_ids: None,
             },
         })
         .collect();
@@ -0,0 +1,176 @@
+//! Token-based chunk sizing with a HuggingFace `tokenizer.json`.
+//!
+//! The test tokenizer maps every word and every run of punctuation to one token, so
+//! expected token counts can be computed by hand.
+
+#![cfg(feature = "chunking-tokenizers")]
+
+use kreuzberg::KreuzbergError;
+use kreuzberg::chunking::{ChunkSizing, ChunkerType, ChunkingConfig, chunk_text};
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+const WORD_TOKENIZER: &str = r#"{
+  "version": "1.0",
+  "truncation": null,
+  "padding": null,
+  "added_tokens": [],
+  "normalizer": null,
+  "pre_tokenizer": { "type": "Whitespace" },
+  "post_processor": null,
+  "decoder": null,
+  "model": { "type": "WordLevel", "vocab": { "[UNK]": 0 }, "unk_token": "[UNK]" }
+}"#;
+
+fn word_tokenizer(dir: &TempDir) -> PathBuf {
+    let path = dir.path().join("tokenizer.json");
+    std::fs::write(&path, WORD_TOKENIZER).unwrap();
+    path
+}
+
+fn config(max_tokens: usize, overlap: usize, chunker_type: ChunkerType, tokenizer_path: PathBuf) -> ChunkingConfig {
+    ChunkingConfig {
+        max_characters: max_tokens,
+        overlap,
+        chunker_type,
+        sizing: ChunkSizing::Tokens {
+            tokenizer_path: Some(tokenizer_path),
+        },
+        ..Default::default()
+    }
+}
+
+fn words(text: &str) -> usize {
+    text.split_whitespace().count()
+}
+
+#[test]
+fn test_chunks_are_sized_in_tokens() {
+    let dir = TempDir::new().unwrap();
+    let text = (0..200).map(|i| format!("word{}", i)).collect::<Vec<_>>().join(" ");
+
+    let result = chunk_text(&text, &config(30, 5, ChunkerType::Text, word_tokenizer(&dir)), None).unwrap();
+
+    assert!(result.chunk_count >= 7, "{} chunks", result.chunk_count);
+    for chunk in &result.chunks {
+        let tokens = chunk.metadata.token_count.expect("token count");
+        assert_eq!(tokens, words(&chunk.content));
+        assert!(tokens <= 30, "{} tokens", tokens);
+        assert_eq!(&text[chunk.metadata.byte_start..chunk.metadata.byte_end], chunk.content);
+    }
+
+    // Consecutive chunks share the overlap tokens.
+    let first: Vec<&str> = result.chunks[0].content.split_whitespace().collect();
+    let second: Vec<&str> = result.chunks[1].content.split_whitespace().collect();
+    assert_eq!(first[first.len() - 5..], second[..5]);
+}
+
+#[test]
+fn test_token_and_character_sizing_differ() {
+    let dir = TempDir::new().unwrap();
+    // Long words: few tokens, many characters.
+    let text = "internationalization ".repeat(40);
+
+    let tokens = chunk_text(&text, &config(50, 0, ChunkerType::Text, word_tokenizer(&dir)), None).unwrap();
+    let characters = chunk_text(
+        &text,
+        &ChunkingConfig {
+            max_characters: 50,
+            overlap: 0,
+            ..Default::default()
+        },
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(tokens.chunk_count, 1);
+    assert_eq!(tokens.chunks[0].metadata.token_count, Some(40));
+    assert!(characters.chunk_count > 10);
+    // Without a tokenizer the count is estimated: 20 characters are five tokens.
+    assert!(
+        characters
+            .chunks
+            .iter()
+            .all(|c| c.metadata.token_count == Some(c.content.split_whitespace().count() * 5))
+    );
+}
+
+#[test]
+fn test_markdown_chunker_with_tokens() {
+    let dir = TempDir::new().unwrap();
+    let section = |title: &str| format!("# {}\n\n{}\n\n", title, "Body text of the section. ".repeat(6));
+    let text = format!("{}{}{}", section("One"), section("Two"), section("Three"));
+
+    let result = chunk_text(&text, &config(40, 0, ChunkerType::Markdown, word_tokenizer(&dir)), None).unwrap();
+
+    assert_eq!(result.chunk_count, 3);
+    for (chunk, title) in result.chunks.iter().zip(["One", "Two", "Three"]) {
+        assert!(chunk.content.starts_with(&format!("# {}", title)), "{}", chunk.content);
+        assert!(chunk.metadata.token_count.unwrap() <= 40);
+    }
+}
+
+#[tokio::test]
+#[cfg(feature = "office")]
+async fn test_structure_chunker_with_tokens() {
+    use kreuzberg::core::config::ExtractionConfig;
+    use kreuzberg::core::extractor::extract_bytes;
+
+    let dir = TempDir::new().unwrap();
+    let rows: String = (1..=12).map(|i| format!("| M{} | {} Nm\n", i, i * 4)).collect();
+    let source = format!(
+        "= Manual\n\n== Installation\n\nUnpack the archive and run the installer.\n\n\
+         == Torque values\n\n|===\n| Bolt | Torque\n\n{}|===\n",
+        rows
+    );
+    let extraction = ExtractionConfig {
+        use_cache: false,
+        chunking: Some(config(25, 0, ChunkerType::Structure, word_tokenizer(&dir))),
+        ..Default::default()
+    };
+
+    let result = extract_bytes(source.as_bytes(), "text/x-asciidoc", &extraction)
+        .await
+        .unwrap();
+    let chunks = result.chunks.expect("chunks");
+
+    assert!(chunks.len() > 2, "{} chunks", chunks.len());
+    for chunk in &chunks {
+        let tokens = chunk.metadata.token_count.expect("token count");
+        assert!(tokens <= 25, "{} tokens: {}", tokens, chunk.content);
+        assert!(chunk.metadata.heading_path.is_some());
+    }
+    // The table is split between rows, repeating its header.
+    assert!(chunks.iter().filter(|c| c.content.contains("| Bolt |")).count() > 1);
+}
+
+#[test]
+fn test_tokenizer_source_is_required() {
+    let config = ChunkingConfig {
+        sizing: ChunkSizing::Tokens { tokenizer_path: None },
+        ..Default::default()
+    };
+    let err = chunk_text("Some text", &config, None).unwrap_err();
+    assert!(matches!(err, KreuzbergError::Validation { .. }), "{err:?}");
+}
+
+#[test]
+#[cfg(not(feature = "embeddings"))]
+fn test_embedding_tokenizer_requires_embeddings_feature() {
+    let config = ChunkingConfig {
+        sizing: ChunkSizing::Tokens { tokenizer_path: None },
+        embedding: Some(kreuzberg::EmbeddingConfig::default()),
+        ..Default::default()
+    };
+    let err = chunk_text("Some text", &config, None).unwrap_err();
+    assert!(err.to_string().contains("`embeddings` feature"), "{err}");
+}
+
+#[test]
+fn test_unreadable_tokenizer_is_reported() {
+    let dir = TempDir::new().unwrap();
+    let missing = dir.path().join("missing.json");
+    let err = chunk_text("Some text", &config(10, 0, ChunkerType::Text, missing), None).unwrap_err();
+    assert!(matches!(err, KreuzbergError::Validation { .. }), "{err:?}");
+    assert!(err.to_string().contains("missing.json"), "{err}");
+}
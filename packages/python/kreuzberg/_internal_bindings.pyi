@@ -364,6 +364,21 @@ class ExtractionConfig:
         max_concurrent_extractions (int | None): Maximum concurrent extractions
             in batch operations. None = num_cpus * 2. Default: None
 
+        timeout_secs (int | None): Wall-clock limit per document in seconds.
+            An expired document raises TimeoutError. None = no limit. Default: None
+
+        partial_results_on_cancel (bool): Return what was extracted before the
+            timeout instead of raising, with a "cancellation" processing warning.
+            Default: False
+
+        best_effort (bool): Skip pages, slides, sheets and archive entries that
+            fail to extract instead of raising. Each skipped unit is reported as an
+            "extraction" processing warning. Default: False
+
+        cache_backend (str | None): Storage backend of the extraction result and
+            embedding caches: "filesystem", "sqlite" or "sharded". None = process
+            default (KREUZBERG_CACHE_BACKEND or "filesystem"). Default: None
+
         html_options (HtmlConversionOptions | None): HTML conversion options for
             converting documents to markdown. Default: None
 
@@ -409,6 +424,10 @@ class ExtractionConfig:
     result_format: str
     output_format: str
     include_document_structure: bool
+    timeout_secs: int | None
+    partial_results_on_cancel: bool
+    best_effort: bool
+    cache_backend: str | None
 
     def __init__(
         self,
@@ -431,6 +450,10 @@ class ExtractionConfig:
         result_format: str | None = None,
         output_format: str | None = None,
         include_document_structure: bool | None = None,
+        timeout_secs: int | None = None,
+        partial_results_on_cancel: bool | None = None,
+        best_effort: bool | None = None,
+        cache_backend: str | None = None,
     ) -> None: ...
     @staticmethod
     def from_file(path: str | Path) -> ExtractionConfig: ...
@@ -650,6 +673,18 @@ class ChunkingConfig:
             settings if provided). Use list_embedding_presets() to see available presets.
             Default: None
 
+        chunker_type (str): "text", "markdown", or "structure" to pack the sections,
+            paragraphs, list items and tables of the document structure. Structure
+            chunks carry "heading_path" and "node_ids" in their metadata and never
+            split a table row. Default: "text"
+
+        sizing (str): "characters", or "tokens" to measure max_chars and max_overlap in
+            tokens of a HuggingFace tokenizer. Token-sized chunks always carry
+            "token_count" in their metadata. Default: "characters"
+
+        tokenizer_path (str | None): Path to a tokenizer.json used with
+            sizing="tokens". None = the tokenizer of the embedding model. Default: None
+
     Example:
         Basic chunking with defaults:
             >>> from kreuzberg import ExtractionConfig, ChunkingConfig
@@ -664,6 +699,11 @@ class ChunkingConfig:
             ...     chunking=ChunkingConfig(max_chars=512, embedding=EmbeddingConfig(model=EmbeddingModelType.preset("balanced")))
             ... )
 
+        Chunks of at most 512 tokens:
+            >>> config = ExtractionConfig(
+            ...     chunking=ChunkingConfig(max_chars=512, max_overlap=50, sizing="tokens", tokenizer_path="tokenizer.json")
+            ... )
+
         Using preset configuration:
             >>> config = ExtractionConfig(chunking=ChunkingConfig(preset="semantic"))
     """
@@ -680,6 +720,9 @@ class ChunkingConfig:
         max_overlap: int | None = None,
         embedding: EmbeddingConfig | None = None,
         preset: str | None = None,
+        chunker_type: str | None = None,
+        sizing: str | None = None,
+        tokenizer_path: str | None = None,
     ) -> None: ...
 
 class ImageExtractionConfig:
@@ -1579,6 +1622,8 @@ class ChunkMetadata(TypedDict, total=False):
     token_count: int | None
     first_page: int
     last_page: int
+    heading_path: list[str]
+    node_ids: list[str]
 
 class Chunk:
     content: str
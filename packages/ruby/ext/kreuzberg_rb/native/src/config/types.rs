@@ -128,6 +128,7 @@ pub fn parse_chunking_config(ruby: &Ruby, hash: RHash) -> Result<ChunkingConfig,
         overlap: max_overlap,
         trim: true,
         chunker_type: kreuzberg::ChunkerType::Text,
+        sizing: kreuzberg::ChunkSizing::Characters,
         embedding,
         preset,
     };